@@ -4,6 +4,7 @@ use ockam_core::flow_control::{FlowControlId, FlowControls};
 use ockam_core::{Address, AllowAll, IncomingAccessControl};
 
 /// Trust Options for a Forwarding Service
+#[derive(Clone)]
 pub struct RelayServiceOptions {
     pub(super) service_incoming_access_control: Arc<dyn IncomingAccessControl>,
     pub(super) relays_incoming_access_control: Arc<dyn IncomingAccessControl>,
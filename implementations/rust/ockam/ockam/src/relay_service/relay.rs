@@ -5,9 +5,12 @@ use ockam_core::{
     route, Address, AllowAll, AllowOnwardAddress, Any, IncomingAccessControl, LocalMessage,
     OutgoingAccessControl, Result, Route, Routed, Worker,
 };
+#[cfg(feature = "std")]
+use ockam_node::RestartPolicy;
 use ockam_node::WorkerBuilder;
 use tracing::info;
 
+#[derive(Clone)]
 pub(super) struct Relay {
     forward_route: Route,
     // this option will be `None` after this worker is initialized, because
@@ -40,12 +43,14 @@ impl Relay {
             payload: Some(registration_payload.clone()),
         };
 
-        WorkerBuilder::new(relay)
+        let builder = WorkerBuilder::new(relay)
             .with_address(address)
             .with_incoming_access_control_arc(incoming_access_control)
-            .with_outgoing_access_control_arc(outgoing_access_control)
-            .start(ctx)
-            .await?;
+            .with_outgoing_access_control_arc(outgoing_access_control);
+        // A restarted relay sends its registration payload again
+        #[cfg(feature = "std")]
+        let builder = builder.with_restart_policy(RestartPolicy::one_for_one());
+        builder.start(ctx).await?;
 
         Ok(())
     }
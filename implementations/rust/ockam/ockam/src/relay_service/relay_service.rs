@@ -3,6 +3,8 @@ use crate::{Context, RelayServiceOptions};
 use core::str::from_utf8;
 use ockam_core::compat::boxed::Box;
 use ockam_core::{Address, Any, DenyAll, Result, Routed, Worker};
+#[cfg(feature = "std")]
+use ockam_node::RestartPolicy;
 use ockam_node::WorkerBuilder;
 
 /// Alias worker to register remote workers under local names.
@@ -10,6 +12,7 @@ use ockam_node::WorkerBuilder;
 /// To talk with this worker, you can use the
 /// [`RemoteRelay`](crate::remote::RemoteRelay) which is a
 /// compatible client for this server.
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct RelayService {
     options: RelayServiceOptions,
@@ -30,12 +33,13 @@ impl RelayService {
 
         let s = Self { options };
 
-        WorkerBuilder::new(s)
+        let builder = WorkerBuilder::new(s)
             .with_address(address)
             .with_incoming_access_control_arc(service_incoming_access_control)
-            .with_outgoing_access_control(DenyAll)
-            .start(ctx)
-            .await?;
+            .with_outgoing_access_control(DenyAll);
+        #[cfg(feature = "std")]
+        let builder = builder.with_restart_policy(RestartPolicy::one_for_one());
+        builder.start(ctx).await?;
 
         Ok(())
     }
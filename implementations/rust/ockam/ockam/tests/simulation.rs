@@ -0,0 +1,128 @@
+use ockam::compat::tokio::time;
+use ockam::identity::{secure_channels, SecureChannelListenerOptions, SecureChannelOptions};
+use ockam::remote::{RemoteRelay, RemoteRelayOptions};
+use ockam::workers::Echoer;
+use ockam::{RelayService, RelayServiceOptions};
+use ockam_core::{route, Result};
+use ockam_node::simulation::{LinkConditions, Simulation};
+use std::time::Duration;
+
+// Alice creates a secure channel to Bob over a simulated network
+// and reaches Bob's Echoer through it
+#[test]
+fn secure_channel_over_a_simulated_network() -> Result<()> {
+    let simulation = Simulation::new(0);
+    simulation
+        .network()
+        .set_default_conditions(LinkConditions::new().with_latency(Duration::from_millis(50)));
+
+    simulation.run(async {
+        // the identities are stored in a database answering queries from another thread.
+        // The clock must follow the real time, otherwise it would jump to the next timer,
+        // like the timeout of the handshake, while waiting for the database
+        time::resume();
+
+        let alice = simulation.create_node("alice").await?;
+        let bob = simulation.create_node("bob").await?;
+
+        let secure_channels = secure_channels().await?;
+        let identities_creation = secure_channels.identities().identities_creation();
+
+        let bob_identifier = identities_creation.create_identity().await?;
+        let listener_options =
+            SecureChannelListenerOptions::new().as_consumer(bob.transport().flow_control_id());
+        bob.context().start_worker("echoer", Echoer).await?;
+        bob.context()
+            .flow_controls()
+            .add_consumer("echoer", &listener_options.spawner_flow_control_id());
+        secure_channels
+            .create_secure_channel_listener(
+                bob.context(),
+                &bob_identifier,
+                "bob_listener",
+                listener_options,
+            )
+            .await?;
+
+        let alice_identifier = identities_creation.create_identity().await?;
+        let to_bob = alice.transport().connect("bob").await?;
+        let start = simulation.clock().elapsed();
+        let channel = secure_channels
+            .create_secure_channel(
+                alice.context(),
+                &alice_identifier,
+                route![to_bob, "bob_listener"],
+                SecureChannelOptions::new(),
+            )
+            .await?;
+
+        let reply: String = alice
+            .context()
+            .send_and_receive(route![channel, "echoer"], "Hello".to_string())
+            .await?;
+        assert_eq!(reply, "Hello");
+
+        // the first 2 messages of the handshake, then the message and its reply,
+        // were delayed by the latency of the network
+        assert!(simulation.clock().elapsed() - start >= Duration::from_millis(200));
+
+        alice.stop().await?;
+        bob.stop().await
+    })
+}
+
+// Cloud: Hosts a Relay service
+// Server: Creates a Relay on the Cloud and runs an Echoer
+// Client: Reaches the Server's Echoer through the Relay
+// All the nodes are connected by a simulated network
+#[test]
+fn relay_over_a_simulated_network() -> Result<()> {
+    let simulation = Simulation::new(0);
+    simulation
+        .network()
+        .set_default_conditions(LinkConditions::new().with_latency(Duration::from_millis(20)));
+
+    simulation.run(async {
+        let cloud = simulation.create_node("cloud").await?;
+        let server = simulation.create_node("server").await?;
+        let client = simulation.create_node("client").await?;
+
+        let cloud_flow_control_id = cloud.transport().flow_control_id();
+        let options = RelayServiceOptions::new()
+            .service_as_consumer(cloud_flow_control_id)
+            .relay_as_consumer(cloud_flow_control_id);
+        RelayService::create(cloud.context(), "forwarding_service", options).await?;
+
+        server.context().start_worker("echoer", Echoer).await?;
+        server
+            .context()
+            .flow_controls()
+            .add_consumer("echoer", server.transport().flow_control_id());
+        let server_to_cloud = server.transport().connect("cloud").await?;
+        let remote_info = RemoteRelay::create(
+            server.context(),
+            route![server_to_cloud],
+            RemoteRelayOptions::new(),
+        )
+        .await?;
+
+        let client_to_cloud = client.transport().connect("cloud").await?;
+        let reply: String = client
+            .context()
+            .send_and_receive(
+                route![client_to_cloud, remote_info.remote_address(), "echoer"],
+                "Hello".to_string(),
+            )
+            .await?;
+        assert_eq!(reply, "Hello");
+
+        // the reply was sent back to the client through the cloud
+        let statistics = simulation.network().statistics();
+        assert_eq!(statistics.dropped(), 0);
+        assert!(statistics.delivered() >= 4);
+
+        client.stop().await?;
+        server.stop().await?;
+        cloud.stop().await
+    })
+}
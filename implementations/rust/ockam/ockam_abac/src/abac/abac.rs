@@ -142,6 +142,43 @@ impl Abac {
         identifier: &Identifier,
         expression: &Expr,
     ) -> Result<bool> {
+        let environment = Self::identity_environment_static(
+            identities_attributes,
+            environment,
+            authority,
+            identifier,
+            expression,
+        )
+        .await?;
+        let (is_authorized, _) = Self::evaluate(expression, &environment, identifier);
+        Ok(is_authorized)
+    }
+
+    /// Return the environment used to evaluate a policy expression for a given identity
+    pub async fn identity_environment(
+        &self,
+        identifier: &Identifier,
+        expression: &Expr,
+    ) -> Result<Env> {
+        Self::identity_environment_static(
+            self.identities_attributes.clone(),
+            &self.environment,
+            &self.authority,
+            identifier,
+            expression,
+        )
+        .await
+    }
+
+    /// Return the initial environment augmented with the identifier
+    /// and the attributes of a given identity
+    pub async fn identity_environment_static(
+        identities_attributes: Arc<IdentitiesAttributes>,
+        environment: &Env,
+        authority: &Identifier,
+        identifier: &Identifier,
+        expression: &Expr,
+    ) -> Result<Env> {
         let mut environment = environment.clone();
 
         // add the identifier itself as a subject parameter
@@ -215,8 +252,17 @@ impl Abac {
             }
         }
 
-        // Finally, evaluate the expression and return the result:
-        match eval(expression, &environment) {
+        Ok(environment)
+    }
+
+    /// Evaluate a policy expression and return the authorization decision with its reason.
+    /// The access is denied if the evaluation fails or does not yield a boolean
+    pub fn evaluate(
+        expression: &Expr,
+        environment: &Env,
+        identifier: &Identifier,
+    ) -> (bool, String) {
+        match eval(expression, environment) {
             Ok(Expr::Bool(b)) => {
                 debug! {
                     policy        = %expression,
@@ -224,7 +270,7 @@ impl Abac {
                     is_authorized = %b,
                     "policy evaluated"
                 }
-                Ok(b)
+                (b, format!("the policy evaluated to {b}"))
             }
             Ok(x) => {
                 warn! {
@@ -233,7 +279,10 @@ impl Abac {
                     expr   = %x,
                     "evaluation did not yield a boolean result"
                 }
-                Ok(false)
+                (
+                    false,
+                    format!("the policy evaluation did not yield a boolean result: {x}"),
+                )
             }
             Err(e) => {
                 warn! {
@@ -242,7 +291,7 @@ impl Abac {
                     err    = %e,
                     "policy evaluation failed"
                 }
-                Ok(false)
+                (false, format!("the policy evaluation failed: {e}"))
             }
         }
     }
@@ -2,7 +2,9 @@
 mod abac;
 mod incoming;
 mod outgoing;
+mod trust_policy;
 
 pub use abac::*;
 pub use incoming::*;
 pub use outgoing::*;
+pub use trust_policy::*;
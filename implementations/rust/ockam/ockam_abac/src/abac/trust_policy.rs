@@ -0,0 +1,213 @@
+use ockam_core::async_trait;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::format;
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::vec;
+use ockam_core::Result;
+use ockam_identity::{Identifier, IdentitiesAttributes, SecureChannelTrustInfo, TrustPolicy};
+use tracing::debug;
+
+use crate::abac::Abac;
+use crate::abac::SUBJECT_KEY;
+use crate::Expr::*;
+use crate::{Env, Expr};
+
+/// This TrustPolicy verifies that the other participant of a secure channel
+/// has authenticated attributes that resolve the expression to `true`.
+///
+/// The attributes come from the credentials presented during the handshake, which are verified
+/// before the trust policy is checked. Those credentials are only verified if the secure channel
+/// options are configured with the same authority as this policy.
+#[derive(Debug)]
+pub struct TrustAbacPolicy {
+    expression: Expr,
+    abac: Abac,
+}
+
+impl TrustAbacPolicy {
+    /// Create a TrustPolicy which will verify that the other participant of a secure channel
+    /// has an authenticated attribute that resolves the expression to `true`
+    pub fn create(
+        identities_attributes: Arc<IdentitiesAttributes>,
+        authority: Identifier,
+        expression: Expr,
+    ) -> Self {
+        let abac = Abac::new(identities_attributes, authority, Env::new());
+
+        Self { expression, abac }
+    }
+
+    /// Create a TrustPolicy which will verify that the other participant of a secure channel
+    /// has an authenticated attribute with the correct name and value
+    pub fn create_name_value(
+        identities_attributes: Arc<IdentitiesAttributes>,
+        authority: Identifier,
+        attribute_name: &str,
+        attribute_value: &str,
+    ) -> Self {
+        let expression = List(vec![
+            Ident("=".into()),
+            Ident(format!("{SUBJECT_KEY}.{attribute_name}")),
+            Str(attribute_value.into()),
+        ]);
+        Self::create(identities_attributes, authority, expression)
+    }
+
+    pub fn expression(&self) -> &Expr {
+        &self.expression
+    }
+}
+
+#[async_trait]
+impl TrustPolicy for TrustAbacPolicy {
+    async fn check(&self, trust_info: &SecureChannelTrustInfo) -> Result<bool> {
+        let is_authorized = self
+            .abac
+            .is_identity_authorized(trust_info.their_identity_id(), &self.expression)
+            .await?;
+        if !is_authorized {
+            debug! {
+                policy = %self.expression,
+                identifier = %trust_info.their_identity_id(),
+                "the secure channel is rejected by the trust policy"
+            }
+        }
+        Ok(is_authorized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{eq, ident, str};
+    use core::time::Duration;
+    use ockam_core::compat::collections::BTreeMap;
+    use ockam_core::{route, AllowAll, Mailboxes};
+    use ockam_identity::models::CredentialSchemaIdentifier;
+    use ockam_identity::secure_channels::secure_channels;
+    use ockam_identity::utils::{now, AttributesBuilder};
+    use ockam_identity::{
+        identities, AttributesEntry, SecureChannelListenerOptions, SecureChannelOptions,
+    };
+    use ockam_node::{Context, MessageReceiveOptions};
+
+    #[tokio::test]
+    async fn test_trust_abac_policy() -> Result<()> {
+        let identities = identities().await?;
+        let identities_creation = identities.identities_creation();
+        let authority = identities_creation.create_identity().await?;
+        let operator = identities_creation.create_identity().await?;
+        let guest = identities_creation.create_identity().await?;
+        let identities_attributes = identities.identities_attributes();
+
+        for (identifier, role) in [(&operator, "operator"), (&guest, "guest")] {
+            let attributes = AttributesEntry::new(
+                BTreeMap::from([(b"role".to_vec(), role.as_bytes().to_vec())]),
+                now()?,
+                None,
+                Some(authority.clone()),
+            );
+            identities_attributes
+                .put_attributes(identifier, attributes)
+                .await?;
+        }
+
+        let trust_policy = TrustAbacPolicy::create(
+            identities_attributes,
+            authority,
+            eq([ident("subject.role"), str("operator")]),
+        );
+        let unknown = identities_creation.create_identity().await?;
+
+        assert!(
+            trust_policy
+                .check(&SecureChannelTrustInfo::new(operator))
+                .await?
+        );
+        assert!(
+            !trust_policy
+                .check(&SecureChannelTrustInfo::new(guest))
+                .await?
+        );
+        assert!(
+            !trust_policy
+                .check(&SecureChannelTrustInfo::new(unknown))
+                .await?
+        );
+        Ok(())
+    }
+
+    /// The credentials presented during the handshake are verified before the trust policy
+    /// is checked, so the listener can accept or refuse a channel based on their attributes
+    #[ockam_macros::test]
+    async fn test_trust_abac_policy_during_the_handshake(ctx: &mut Context) -> Result<()> {
+        let secure_channels = secure_channels().await?;
+        let identities = secure_channels.identities();
+        let identities_creation = identities.identities_creation();
+        let authority = identities_creation.create_identity().await?;
+        let listener = identities_creation.create_identity().await?;
+
+        let trust_policy = TrustAbacPolicy::create(
+            identities.identities_attributes(),
+            authority.clone(),
+            eq([ident("subject.role"), str("operator")]),
+        );
+        let listener = secure_channels
+            .create_secure_channel_listener(
+                ctx,
+                &listener,
+                "listener",
+                SecureChannelListenerOptions::new()
+                    .with_authority(authority.clone())
+                    .with_trust_policy(trust_policy),
+            )
+            .await?;
+
+        let mut child_ctx = ctx
+            .new_detached_with_mailboxes(Mailboxes::main(
+                "child",
+                Arc::new(AllowAll),
+                Arc::new(AllowAll),
+            ))
+            .await?;
+        ctx.flow_controls()
+            .add_consumer("child", listener.flow_control_id());
+
+        for (role, is_trusted) in [("operator", true), ("guest", false)] {
+            let initiator = identities_creation.create_identity().await?;
+            let credential = identities
+                .credentials()
+                .credentials_creation()
+                .issue_credential(
+                    &authority,
+                    &initiator,
+                    AttributesBuilder::with_schema(CredentialSchemaIdentifier(0))
+                        .with_attribute("role", role)
+                        .build(),
+                    Duration::from_secs(60 * 60),
+                )
+                .await?;
+
+            // the initiator doesn't know if the listener accepted the channel
+            // so we check that messages can go through it
+            let channel = secure_channels
+                .create_secure_channel(
+                    ctx,
+                    &initiator,
+                    route!["listener"],
+                    SecureChannelOptions::new().with_credential(credential)?,
+                )
+                .await?;
+            child_ctx
+                .send(route![channel, child_ctx.address()], role.to_string())
+                .await?;
+            let received = child_ctx
+                .receive_extended::<String>(
+                    MessageReceiveOptions::new().with_timeout(Duration::from_millis(500)),
+                )
+                .await;
+            assert_eq!(received.is_ok(), is_trusted, "role: {role}");
+        }
+        Ok(())
+    }
+}
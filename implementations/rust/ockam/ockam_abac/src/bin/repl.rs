@@ -9,7 +9,17 @@ const HELP: &str = r#"Available commands:
   :def <id> <expression>  -- Add an expression to the environment.
   :env                    -- Show all current environment entries.
   :clear                  -- Remove all bindings from the environment.
-  :help | :h | :?         -- Show this help message."#;
+  :help | :h | :?         -- Show this help message.
+
+Available operators:
+  and or not if                      -- Boolean logic, e.g. (and (= a 1) (not b)).
+  = != < > <= >=                     -- Comparisons, e.g. (<= 1 x 10).
+  member? exists?                    -- Membership and binding tests, e.g. (member? x [1 2]).
+  + - * /                            -- Arithmetic on ints or floats, e.g. (+ (now) 3600).
+  len                                -- Length of a string or a sequence.
+  starts-with? ends-with? contains?  -- String tests, e.g. (starts-with? x "eng").
+  regex?                             -- Regular expression match, e.g. (regex? x "^prod-.*").
+  now                                -- Current time in seconds since the Unix epoch."#;
 
 #[derive(Completer, Helper, Highlighter, Hinter, Validator)]
 struct ReplHelper {
@@ -0,0 +1,763 @@
+use core::fmt;
+
+use crate::abac::{ABAC_HAS_CREDENTIAL_KEY, SUBJECT_KEY};
+use crate::env::Env;
+use crate::error::CheckError;
+use crate::eval::eval;
+use crate::expr::{unit, Expr};
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::format;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::vec::Vec;
+
+#[cfg(feature = "std")]
+use regex::Regex;
+
+/// Namespace of the attributes describing the resource being accessed
+pub const RESOURCE_KEY: &str = "resource";
+
+/// Namespace of the attributes describing the action performed on a resource
+pub const ACTION_KEY: &str = "action";
+
+/// Maximum nesting of the expressions accepted by the checker
+const MAX_DEPTH: usize = 128;
+
+/// Type of an expression, as inferred by the [`TypeChecker`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Bool,
+    Int,
+    Float,
+    Str,
+    Seq(Box<Type>),
+    Unit,
+    /// Type of an expression which could not be inferred, because of a previous error
+    /// or because it is an empty sequence
+    Any,
+}
+
+impl Type {
+    /// Return the most precise type compatible with both types, if there is one
+    pub fn unify(&self, other: &Type) -> Option<Type> {
+        match (self, other) {
+            (Type::Any, t) | (t, Type::Any) => Some(t.clone()),
+            (Type::Seq(a), Type::Seq(b)) => a.unify(b).map(|t| Type::Seq(Box::new(t))),
+            (a, b) if a == b => Some(a.clone()),
+            _ => None,
+        }
+    }
+
+    fn of_value(value: &Expr) -> Type {
+        match value {
+            Expr::Str(_) => Type::Str,
+            Expr::Int(_) => Type::Int,
+            Expr::Float(_) => Type::Float,
+            Expr::Bool(_) => Type::Bool,
+            Expr::Seq(xs) => Type::Seq(Box::new(
+                xs.first().map(Type::of_value).unwrap_or(Type::Any),
+            )),
+            Expr::List(xs) if xs.is_empty() => Type::Unit,
+            Expr::Ident(_) | Expr::List(_) => Type::Any,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Bool => f.write_str("bool"),
+            Type::Int => f.write_str("int"),
+            Type::Float => f.write_str("float"),
+            Type::Str => f.write_str("str"),
+            Type::Seq(t) => write!(f, "[{t}]"),
+            Type::Unit => f.write_str("()"),
+            Type::Any => f.write_str("any"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The expression can't be evaluated successfully, it must be rejected
+    Error,
+    /// The expression can be evaluated but is most likely not what was intended
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => f.write_str("error"),
+            Severity::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A problem found in a policy expression
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The sub-expression where the problem was found
+    pub expr: Expr,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {}", self.message, self.expr)
+    }
+}
+
+/// Result of the type checking of an expression
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    typ: Type,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl CheckReport {
+    /// Inferred type of the expression
+    pub fn typ(&self) -> &Type {
+        &self.typ
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Return the warnings if the expression has no errors
+    pub fn into_result(self) -> Result<Vec<Diagnostic>, CheckError> {
+        let (errors, warnings): (Vec<_>, Vec<_>) = self
+            .diagnostics
+            .into_iter()
+            .partition(|d| d.severity == Severity::Error);
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(CheckError::new(errors))
+        }
+    }
+}
+
+/// Check a policy expression with the default [`TypeChecker`]
+pub fn check(expr: &Expr) -> CheckReport {
+    TypeChecker::default().check(expr)
+}
+
+/// Statically checks policy expressions before they are used for access control.
+///
+/// The checker infers the type of every sub-expression, given the types of the identifiers
+/// which are available when a policy is evaluated:
+///
+///  - `subject.has_credential` is a boolean,
+///  - any other identifier of the `subject`, `resource` and `action` namespaces is a string.
+///
+/// It reports as errors: unknown functions and identifiers, wrong numbers of arguments,
+/// type mismatches and expressions which don't evaluate to a boolean.
+/// It reports as warnings: unreachable branches and expressions which are always true or always false.
+#[derive(Debug, Clone)]
+pub struct TypeChecker {
+    namespaces: Vec<String>,
+    identifiers: BTreeMap<String, Type>,
+}
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        TypeChecker::new()
+            .with_namespace(SUBJECT_KEY)
+            .with_namespace(RESOURCE_KEY)
+            .with_namespace(ACTION_KEY)
+            .with_identifier(
+                format!("{SUBJECT_KEY}.{ABAC_HAS_CREDENTIAL_KEY}"),
+                Type::Bool,
+            )
+    }
+}
+
+/// Type of a sub-expression and its value when it can be computed statically
+struct Typed {
+    typ: Type,
+    constant: Option<Expr>,
+}
+
+impl Typed {
+    fn new(typ: Type) -> Self {
+        Typed {
+            typ,
+            constant: None,
+        }
+    }
+
+    fn constant(value: Expr) -> Self {
+        Typed {
+            typ: Type::of_value(&value),
+            constant: Some(value),
+        }
+    }
+
+    fn bool(&self) -> Option<bool> {
+        match self.constant {
+            Some(Expr::Bool(b)) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+impl TypeChecker {
+    /// Create a checker without any known identifier
+    pub fn new() -> Self {
+        TypeChecker {
+            namespaces: Vec::new(),
+            identifiers: BTreeMap::new(),
+        }
+    }
+
+    /// Accept all the identifiers `<namespace>.<name>` as strings
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespaces.push(namespace.into());
+        self
+    }
+
+    /// Accept an identifier with a specific type
+    pub fn with_identifier(mut self, identifier: impl Into<String>, typ: Type) -> Self {
+        self.identifiers.insert(identifier.into(), typ);
+        self
+    }
+
+    /// Check an expression used as a policy
+    pub fn check(&self, expr: &Expr) -> CheckReport {
+        let mut diagnostics = Vec::new();
+        let typed = self.infer(expr, 0, &mut diagnostics);
+        if typed.typ.unify(&Type::Bool).is_none() {
+            let message = format!("a policy must evaluate to a bool, found {}", typed.typ);
+            diagnostics.push(error(message, expr));
+        }
+        match typed.bool() {
+            Some(true) => diagnostics.push(warning(
+                "the policy is always true, access is always granted",
+                expr,
+            )),
+            Some(false) => diagnostics.push(warning(
+                "the policy is always false, access is never granted",
+                expr,
+            )),
+            None => {}
+        }
+        CheckReport {
+            typ: typed.typ,
+            diagnostics,
+        }
+    }
+
+    fn identifier_type(&self, id: &str) -> Option<Type> {
+        if let Some(typ) = self.identifiers.get(id) {
+            return Some(typ.clone());
+        }
+        match id.split_once('.') {
+            Some((namespace, name)) if !name.is_empty() => self
+                .namespaces
+                .iter()
+                .any(|n| n == namespace)
+                .then_some(Type::Str),
+            _ => None,
+        }
+    }
+
+    fn infer(&self, expr: &Expr, depth: usize, diagnostics: &mut Vec<Diagnostic>) -> Typed {
+        if depth > MAX_DEPTH {
+            diagnostics.push(error("the expression is nested too deeply", expr));
+            return Typed::new(Type::Any);
+        }
+        match expr {
+            Expr::Str(_) | Expr::Int(_) | Expr::Float(_) | Expr::Bool(_) => {
+                Typed::constant(expr.clone())
+            }
+            Expr::Ident(id) => match self.identifier_type(id) {
+                Some(typ) => Typed::new(typ),
+                None => {
+                    let message = format!(
+                        "unknown identifier '{id}', attributes must start with one of: {}",
+                        self.namespaces.join(", ")
+                    );
+                    diagnostics.push(error(message, expr));
+                    Typed::new(Type::Any)
+                }
+            },
+            Expr::Seq(xs) => {
+                let mut element_type = Type::Any;
+                let mut values = Some(Vec::new());
+                for x in xs {
+                    let typed = self.infer(x, depth + 1, diagnostics);
+                    match element_type.unify(&typed.typ) {
+                        Some(t) => element_type = t,
+                        None => {
+                            let message = format!(
+                                "sequence elements must have the same type, expected {element_type}, found {}",
+                                typed.typ
+                            );
+                            diagnostics.push(error(message, x))
+                        }
+                    }
+                    values = values.zip(typed.constant).map(|(mut vs, v)| {
+                        vs.push(v);
+                        vs
+                    });
+                }
+                match values {
+                    Some(values) => Typed::constant(Expr::Seq(values)),
+                    None => Typed::new(Type::Seq(Box::new(element_type))),
+                }
+            }
+            Expr::List(xs) => match &xs[..] {
+                [] => Typed::constant(unit()),
+                [Expr::Ident(op), args @ ..] => self.infer_call(expr, op, args, depth, diagnostics),
+                [other, ..] => {
+                    diagnostics.push(error("expected (op ...)", other));
+                    Typed::new(Type::Any)
+                }
+            },
+        }
+    }
+
+    fn infer_call(
+        &self,
+        expr: &Expr,
+        op: &str,
+        args: &[Expr],
+        depth: usize,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Typed {
+        match op {
+            "and" | "or" => self.infer_and_or(op, args, depth, diagnostics),
+            "if" => {
+                if args.len() != 3 {
+                    diagnostics.push(error("'if' requires three arguments", expr));
+                    return Typed::new(Type::Any);
+                }
+                let test = self.infer(&args[0], depth + 1, diagnostics);
+                self.expect(op, &test, &Type::Bool, &args[0], diagnostics);
+                let then = self.infer(&args[1], depth + 1, diagnostics);
+                let orelse = self.infer(&args[2], depth + 1, diagnostics);
+                let typ = match then.typ.unify(&orelse.typ) {
+                    Some(typ) => typ,
+                    None => {
+                        let message = format!(
+                            "the branches of 'if' must have the same type, found {} and {}",
+                            then.typ, orelse.typ
+                        );
+                        diagnostics.push(error(message, expr));
+                        Type::Any
+                    }
+                };
+                match test.bool() {
+                    Some(true) => {
+                        diagnostics.push(warning("unreachable 'if' branch", &args[2]));
+                        Typed {
+                            typ,
+                            constant: then.constant,
+                        }
+                    }
+                    Some(false) => {
+                        diagnostics.push(warning("unreachable 'if' branch", &args[1]));
+                        Typed {
+                            typ,
+                            constant: orelse.constant,
+                        }
+                    }
+                    None => Typed::new(typ),
+                }
+            }
+            "not" => {
+                let args = self.infer_args(op, expr, args, 1, Some(1), depth, diagnostics);
+                self.expect(op, &args[0].1, &Type::Bool, args[0].0, diagnostics);
+                self.fold(op, expr, &args, Type::Bool, diagnostics)
+            }
+            "=" | "!=" | "<" | ">" | "<=" | ">=" => {
+                let args = self.infer_args(op, expr, args, 2, None, depth, diagnostics);
+                self.same_type(op, &args, diagnostics);
+                self.fold(op, expr, &args, Type::Bool, diagnostics)
+            }
+            "member?" => {
+                let args = self.infer_args(op, expr, args, 2, Some(2), depth, diagnostics);
+                let element_type = Type::Seq(Box::new(args[0].1.typ.clone()));
+                if args[1].1.typ.unify(&element_type).is_none() {
+                    let message = format!(
+                        "'member?' expects a sequence of {} as second argument, found {}",
+                        args[0].1.typ, args[1].1.typ
+                    );
+                    diagnostics.push(error(message, args[1].0));
+                }
+                self.fold(op, expr, &args, Type::Bool, diagnostics)
+            }
+            "exists?" => {
+                for arg in args {
+                    match arg {
+                        Expr::Ident(_) => {
+                            self.infer(arg, depth + 1, diagnostics);
+                        }
+                        other => diagnostics
+                            .push(error("'exists?' expects identifiers as arguments", other)),
+                    }
+                }
+                Typed::new(Type::Bool)
+            }
+            "+" | "-" | "*" | "/" => {
+                let min = if op == "/" { 2 } else { 1 };
+                let args = self.infer_args(op, expr, args, min, None, depth, diagnostics);
+                let typ = self.same_type(op, &args, diagnostics);
+                if !matches!(typ, Type::Int | Type::Float | Type::Any) {
+                    let message = format!("'{op}' expects int or float arguments, found {typ}");
+                    diagnostics.push(error(message, expr));
+                }
+                self.fold(op, expr, &args, typ, diagnostics)
+            }
+            "len" => {
+                let args = self.infer_args(op, expr, args, 1, Some(1), depth, diagnostics);
+                if !matches!(args[0].1.typ, Type::Str | Type::Seq(_) | Type::Any) {
+                    let message =
+                        format!("'len' expects a str or a sequence, found {}", args[0].1.typ);
+                    diagnostics.push(error(message, args[0].0));
+                }
+                self.fold(op, expr, &args, Type::Int, diagnostics)
+            }
+            "starts-with?" | "ends-with?" | "contains?" => {
+                let args = self.infer_args(op, expr, args, 2, Some(2), depth, diagnostics);
+                for (arg, typed) in &args {
+                    self.expect(op, typed, &Type::Str, arg, diagnostics);
+                }
+                self.fold(op, expr, &args, Type::Bool, diagnostics)
+            }
+            #[cfg(feature = "std")]
+            "regex?" => {
+                let args = self.infer_args(op, expr, args, 2, Some(2), depth, diagnostics);
+                for (arg, typed) in &args {
+                    self.expect(op, typed, &Type::Str, arg, diagnostics);
+                }
+                if let Some(Expr::Str(pattern)) = &args[1].1.constant {
+                    if let Err(e) = Regex::new(pattern) {
+                        let message = format!("invalid regular expression: {e}");
+                        diagnostics.push(error(message, args[1].0));
+                        return Typed::new(Type::Bool);
+                    }
+                }
+                self.fold(op, expr, &args, Type::Bool, diagnostics)
+            }
+            "now" => {
+                if !args.is_empty() {
+                    diagnostics.push(error("'now' does not take arguments", expr));
+                }
+                Typed::new(Type::Int)
+            }
+            _ => {
+                diagnostics.push(error(format!("unknown function '{op}'"), expr));
+                for arg in args {
+                    self.infer(arg, depth + 1, diagnostics);
+                }
+                Typed::new(Type::Any)
+            }
+        }
+    }
+
+    /// 'and' and 'or' stop evaluating their arguments as soon as one of them
+    /// is respectively false or true. The remaining arguments are then unreachable.
+    fn infer_and_or(
+        &self,
+        op: &str,
+        args: &[Expr],
+        depth: usize,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Typed {
+        let stop_value = op == "or";
+        let mut stopped = false;
+        let mut all_constant = true;
+        let mut constant = None;
+        for arg in args {
+            let typed = self.infer(arg, depth + 1, diagnostics);
+            self.expect(op, &typed, &Type::Bool, arg, diagnostics);
+            if stopped {
+                let message = format!("unreachable argument of '{op}'");
+                diagnostics.push(warning(message, arg));
+                continue;
+            }
+            match typed.bool() {
+                Some(b) if b == stop_value => {
+                    stopped = true;
+                    // the result is only known statically if the previous arguments
+                    // are constants as well, otherwise their evaluation could fail
+                    if all_constant {
+                        constant = Some(Expr::Bool(stop_value))
+                    }
+                }
+                Some(_) => {}
+                None => all_constant = false,
+            }
+        }
+        if !stopped && all_constant {
+            constant = Some(Expr::Bool(!stop_value))
+        }
+        Typed {
+            typ: Type::Bool,
+            constant,
+        }
+    }
+
+    /// Infer the types of the arguments of a function and check their number.
+    /// Missing arguments are replaced with `Any` values, so that callers can index the result
+    #[allow(clippy::too_many_arguments)]
+    fn infer_args<'a>(
+        &self,
+        op: &str,
+        expr: &'a Expr,
+        args: &'a [Expr],
+        min: usize,
+        max: Option<usize>,
+        depth: usize,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Vec<(&'a Expr, Typed)> {
+        let message = match max {
+            Some(max) if min == max && args.len() != min => {
+                Some(format!("'{op}' requires {min} argument(s)"))
+            }
+            Some(max) if args.len() > max => {
+                Some(format!("'{op}' requires at most {max} arguments"))
+            }
+            _ if args.len() < min => Some(format!("'{op}' requires at least {min} argument(s)")),
+            _ => None,
+        };
+        if let Some(message) = message {
+            diagnostics.push(error(message, expr));
+        }
+        let mut typed: Vec<(&Expr, Typed)> = args
+            .iter()
+            .map(|arg| (arg, self.infer(arg, depth + 1, diagnostics)))
+            .collect();
+        while typed.len() < min {
+            typed.push((expr, Typed::new(Type::Any)));
+        }
+        typed
+    }
+
+    /// Check that all the arguments have the same type and return that type
+    fn same_type(
+        &self,
+        op: &str,
+        args: &[(&Expr, Typed)],
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Type {
+        let mut typ = Type::Any;
+        for (arg, typed) in args {
+            match typ.unify(&typed.typ) {
+                Some(t) => typ = t,
+                None => {
+                    let message = format!(
+                        "the arguments of '{op}' must have the same type, expected {typ}, found {}",
+                        typed.typ
+                    );
+                    diagnostics.push(error(message, arg))
+                }
+            }
+        }
+        typ
+    }
+
+    fn expect(
+        &self,
+        op: &str,
+        typed: &Typed,
+        expected: &Type,
+        arg: &Expr,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        if typed.typ.unify(expected).is_none() {
+            let message = format!("'{op}' expects {expected} arguments, found {}", typed.typ);
+            diagnostics.push(error(message, arg));
+        }
+    }
+
+    /// Evaluate a function call when all its arguments are constants.
+    /// Nothing is evaluated once an error has been found, to avoid reporting it twice
+    fn fold(
+        &self,
+        op: &str,
+        expr: &Expr,
+        args: &[(&Expr, Typed)],
+        typ: Type,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Typed {
+        if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+            return Typed::new(typ);
+        }
+        let mut call = Vec::with_capacity(args.len() + 1);
+        call.push(Expr::Ident(op.to_string()));
+        for (_, typed) in args {
+            match &typed.constant {
+                Some(value) => call.push(value.clone()),
+                None => return Typed::new(typ),
+            }
+        }
+        match eval(&Expr::List(call), &Env::new()) {
+            Ok(value) => Typed {
+                typ,
+                constant: Some(value),
+            },
+            Err(e) => {
+                diagnostics.push(error(e.to_string(), expr));
+                Typed::new(typ)
+            }
+        }
+    }
+}
+
+fn error(message: impl Into<String>, expr: &Expr) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Error,
+        message: message.into(),
+        expr: expr.clone(),
+    }
+}
+
+fn warning(message: impl Into<String>, expr: &Expr) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Warning,
+        message: message.into(),
+        expr: expr.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn report(input: &str) -> CheckReport {
+        check(&parse(input).unwrap().unwrap())
+    }
+
+    fn assert_valid(input: &str) {
+        let report = report(input);
+        assert!(
+            report.diagnostics().is_empty(),
+            "{input}: {:?}",
+            report.diagnostics()
+        );
+    }
+
+    fn assert_error(input: &str, message: &str) {
+        let report = report(input);
+        assert!(
+            report.errors().any(|d| d.message.contains(message)),
+            "{input}: {:?}",
+            report.diagnostics()
+        );
+    }
+
+    fn assert_warning(input: &str, message: &str) {
+        let report = report(input);
+        assert!(!report.has_errors(), "{input}: {:?}", report.diagnostics());
+        assert!(
+            report.warnings().any(|d| d.message.contains(message)),
+            "{input}: {:?}",
+            report.diagnostics()
+        );
+    }
+
+    #[test]
+    fn valid_policies() {
+        assert_valid("subject.has_credential");
+        assert_valid(r#"(= subject.component "web")"#);
+        assert_valid(
+            r#"(and subject.has_credential (= resource.id "db") (= action.id "handle_message"))"#,
+        );
+        assert_valid(r#"(member? subject.role ["admin" "enroller"])"#);
+        assert_valid(r#"(starts-with? subject.department "eng")"#);
+        assert_valid(r#"(regex? resource.id "^prod-.*")"#);
+        assert_valid(r#"(< (now) (+ 1700000000 3600))"#);
+        assert_valid(r#"(if (exists? subject.role) (= subject.role "admin") false)"#);
+        assert_valid(r#"(> (len subject.name) 3)"#);
+    }
+
+    #[test]
+    fn type_errors() {
+        assert_error(r#"(= subject.component 1)"#, "must have the same type");
+        assert_error(
+            r#"(and subject.has_credential "yes")"#,
+            "'and' expects bool arguments",
+        );
+        assert_error(r#"(+ 1 2.0)"#, "must have the same type");
+        assert_error(
+            r#"(starts-with? subject.name 1)"#,
+            "'starts-with?' expects str",
+        );
+        assert_error(r#"(member? 1 ["a" "b"])"#, "expects a sequence of int");
+        assert_error(r#"(if subject.has_credential 1 "a")"#, "same type");
+        assert_error(r#"(len 1)"#, "'len' expects a str or a sequence");
+        assert_error(r#"subject.component"#, "must evaluate to a bool");
+        assert_error(r#"(/ 1 0)"#, "division by zero");
+        assert_error(r#"(regex? subject.name "(")"#, "invalid regular expression");
+    }
+
+    #[test]
+    fn unknown_names_and_arity() {
+        assert_error(
+            r#"(frobnicate subject.name)"#,
+            "unknown function 'frobnicate'",
+        );
+        assert_error(r#"(= component "web")"#, "unknown identifier 'component'");
+        assert_error(
+            r#"(= other.component "web")"#,
+            "unknown identifier 'other.component'",
+        );
+        assert_error(r#"(not true false)"#, "'not' requires 1 argument(s)");
+        assert_error(r#"(= subject.name)"#, "'=' requires at least 2 argument(s)");
+        assert_error(r#"(now 1)"#, "'now' does not take arguments");
+        assert_error(r#"(1 2)"#, "expected (op ...)");
+    }
+
+    #[test]
+    fn lints() {
+        assert_warning("true", "always true");
+        assert_warning("(= 1 2)", "always false");
+        assert_warning(
+            "(or true subject.has_credential)",
+            "unreachable argument of 'or'",
+        );
+        assert_warning("(and (= 1 2) subject.has_credential)", "always false");
+        assert_warning(
+            r#"(if true subject.has_credential false)"#,
+            "unreachable 'if' branch",
+        );
+
+        // the result of 'or' is not known statically since 'subject.name' could be unbound
+        let report = report(r#"(or (= subject.name "a") true)"#);
+        assert!(report.warnings().all(|d| !d.message.contains("always")));
+    }
+
+    #[test]
+    fn deeply_nested_expression() {
+        let mut expr = Expr::Bool(true);
+        for _ in 0..(MAX_DEPTH + 1) {
+            expr = Expr::List(vec![Expr::Ident("not".into()), expr]);
+        }
+        let report = check(&expr);
+        assert!(report
+            .errors()
+            .any(|d| d.message.contains("nested too deeply")));
+    }
+}
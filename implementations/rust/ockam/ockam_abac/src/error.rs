@@ -1,8 +1,10 @@
+use crate::check::Diagnostic;
 use crate::expr::Expr;
 use core::fmt;
 use core::num::{ParseFloatError, ParseIntError};
 use core::str::Utf8Error;
 use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::vec::Vec;
 use ockam_core::errcode::{Kind, Origin};
 
 #[derive(Debug)]
@@ -22,8 +24,13 @@ pub enum EvalError {
     InvalidType(Expr, &'static str),
     TypeMismatch(Expr, Expr),
     Malformed(String),
+    Runtime(String),
 }
 
+/// Errors found by the [`crate::TypeChecker`] in a policy expression
+#[derive(Debug)]
+pub struct CheckError(Vec<Diagnostic>);
+
 #[derive(Debug)]
 pub enum MergeError {
     BindingExists(String),
@@ -40,11 +47,25 @@ impl EvalError {
         EvalError::Malformed(s.into())
     }
 
+    pub fn runtime<S: Into<String>>(s: S) -> Self {
+        EvalError::Runtime(s.into())
+    }
+
     pub fn is_unbound(&self) -> bool {
         matches!(self, EvalError::Unbound(_))
     }
 }
 
+impl CheckError {
+    pub fn new(errors: Vec<Diagnostic>) -> Self {
+        CheckError(errors)
+    }
+
+    pub fn errors(&self) -> &[Diagnostic] {
+        &self.0
+    }
+}
+
 impl From<Utf8Error> for ParseError {
     #[track_caller]
     fn from(e: Utf8Error) -> Self {
@@ -95,10 +116,24 @@ impl fmt::Display for EvalError {
             EvalError::InvalidType(e, m) => write!(f, "invalid type of expression {e}: {m}"),
             EvalError::Malformed(m) => write!(f, "malformed expression: {m}"),
             EvalError::TypeMismatch(a, b) => write!(f, "{a} and {b} are not of the same type"),
+            EvalError::Runtime(m) => write!(f, "evaluation error: {m}"),
         }
     }
 }
 
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid policy expression: ")?;
+        for (i, e) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?
+            }
+            write!(f, "{e}")?
+        }
+        Ok(())
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for ParseError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -127,6 +162,20 @@ impl From<ParseError> for ockam_core::Error {
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for CheckError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+impl From<CheckError> for ockam_core::Error {
+    #[track_caller]
+    fn from(e: CheckError) -> Self {
+        ockam_core::Error::new(Origin::Application, Kind::Invalid, e.to_string())
+    }
+}
+
 impl From<EvalError> for ockam_core::Error {
     #[track_caller]
     fn from(e: EvalError) -> Self {
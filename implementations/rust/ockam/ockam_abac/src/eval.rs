@@ -3,9 +3,37 @@ use core::cmp::Ordering;
 use crate::env::Env;
 use crate::error::EvalError;
 use crate::expr::{unit, Expr};
+use ockam_core::compat::format;
 use ockam_core::compat::string::ToString;
 use ockam_core::compat::vec::Vec;
 
+#[cfg(feature = "std")]
+use regex::Regex;
+
+/// Evaluate an expression in a given environment.
+///
+/// Identifiers are looked up in the environment and lists of the form `(op ...)`
+/// are evaluated by applying one of the following operators to their arguments:
+///
+/// | Operator            | Arguments                  | Result                                          |
+/// |---------------------|----------------------------|-------------------------------------------------|
+/// | `and`, `or`, `not`  | bools                      | logical conjunction, disjunction and negation   |
+/// | `if`                | bool, expr, expr           | one of the two branches, evaluated lazily       |
+/// | `=`, `!=`           | at least 2 values          | (in)equality of all arguments                   |
+/// | `<`, `>`, `<=`, `>=`| at least 2 values          | ordering of all consecutive arguments           |
+/// | `member?`           | value, seq                 | true if the value is an element of the sequence |
+/// | `exists?`           | identifiers                | true if all identifiers are bound               |
+/// | `+`, `-`, `*`, `/`  | ints or floats             | sum, difference (negation), product, quotient   |
+/// | `len`               | str or seq                 | number of characters or elements                |
+/// | `starts-with?`      | str, str                   | true if the first string has the given prefix   |
+/// | `ends-with?`        | str, str                   | true if the first string has the given suffix   |
+/// | `contains?`         | str, str                   | true if the first string contains the second    |
+/// | `regex?`            | str, str                   | true if the first string matches the pattern    |
+/// | `now`               |                            | the current time in seconds since the Unix epoch|
+///
+/// Every operator checks the number and the types of its arguments. Arithmetic operators
+/// do not mix integers and floats and fail on integer overflow or division by zero.
+/// `regex?` is only available with the `std` feature.
 #[rustfmt::skip]
 pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
     /// A stack operation.
@@ -22,8 +50,21 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
         Eq(usize),
         Gt(usize),
         Lt(usize),
+        Ge(usize),
+        Le(usize),
         Member,
         Seq(usize),
+        Add(usize),
+        Sub(usize),
+        Mul(usize),
+        Div(usize),
+        Neg,
+        Len,
+        StartsWith,
+        EndsWith,
+        Contains,
+        #[cfg(feature = "std")]
+        Regex,
     }
 
     // Control stack.
@@ -125,6 +166,20 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                             ctrl.push(Op::Not);
                             ctrl.push(Op::Eq(nargs))
                         }
+                        "<=" => {
+                            if nargs < 2 {
+                                let msg = "'<=' requires at least two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Le(nargs))
+                        }
+                        ">=" => {
+                            if nargs < 2 {
+                                let msg = "'>=' requires at least two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Ge(nargs))
+                        }
                         "member?" => {
                             if nargs != 2 {
                                 let msg = "'member?' requires two arguments";
@@ -149,6 +204,76 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                             args.push(Expr::Bool(b));
                             continue
                         }
+                        "+" => {
+                            if nargs == 0 {
+                                return Err(EvalError::malformed("'+' requires at least one argument"))
+                            }
+                            ctrl.push(Op::Add(nargs))
+                        }
+                        "-" => {
+                            match nargs {
+                                0 => return Err(EvalError::malformed("'-' requires at least one argument")),
+                                1 => ctrl.push(Op::Neg),
+                                _ => ctrl.push(Op::Sub(nargs))
+                            }
+                        }
+                        "*" => {
+                            if nargs == 0 {
+                                return Err(EvalError::malformed("'*' requires at least one argument"))
+                            }
+                            ctrl.push(Op::Mul(nargs))
+                        }
+                        "/" => {
+                            if nargs < 2 {
+                                let msg = "'/' requires at least two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Div(nargs))
+                        }
+                        "len" => {
+                            if nargs != 1 {
+                                return Err(EvalError::malformed("'len' requires one argument"))
+                            }
+                            ctrl.push(Op::Len)
+                        }
+                        "starts-with?" => {
+                            if nargs != 2 {
+                                let msg = "'starts-with?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::StartsWith)
+                        }
+                        "ends-with?" => {
+                            if nargs != 2 {
+                                let msg = "'ends-with?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::EndsWith)
+                        }
+                        "contains?" => {
+                            if nargs != 2 {
+                                let msg = "'contains?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Contains)
+                        }
+                        #[cfg(feature = "std")]
+                        "regex?" => {
+                            if nargs != 2 {
+                                let msg = "'regex?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Regex)
+                        }
+                        "now" => {
+                            if nargs != 0 {
+                                return Err(EvalError::malformed("'now' does not take arguments"))
+                            }
+                            let now = ockam_core::compat::time::now()
+                                .map_err(|e| EvalError::runtime(format!("cannot get the current time: {e}")))?;
+                            args.push(Expr::Int(now as i64));
+                            continue
+                        }
                         _  => return Err(EvalError::Unknown(id.to_string()))
                     }
                     for x in xs[1 ..].iter().rev() {
@@ -235,6 +360,12 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
             Op::Gt(n) => eval_predicate(n, &mut args, |x, y| {
                 x.compare(y).map(|o| o == Some(Ordering::Greater))
             })?,
+            Op::Le(n) => eval_predicate(n, &mut args, |x, y| {
+                x.compare(y).map(|o| matches!(o, Some(Ordering::Less | Ordering::Equal)))
+            })?,
+            Op::Ge(n) => eval_predicate(n, &mut args, |x, y| {
+                x.compare(y).map(|o| matches!(o, Some(Ordering::Greater | Ordering::Equal)))
+            })?,
             Op::Member => {
                 let s = pop(&mut args);
                 let y = pop(&mut args);
@@ -259,6 +390,56 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                 let s = args.split_off(args.len() - n);
                 args.push(Expr::Seq(s))
             }
+            Op::Add(n) => eval_arithmetic(n, &mut args, "+", i64::checked_add, |a, b| a + b)?,
+            Op::Sub(n) => eval_arithmetic(n, &mut args, "-", i64::checked_sub, |a, b| a - b)?,
+            Op::Mul(n) => eval_arithmetic(n, &mut args, "*", i64::checked_mul, |a, b| a * b)?,
+            Op::Div(n) => eval_arithmetic(n, &mut args, "/", i64::checked_div, |a, b| a / b)?,
+            Op::Neg => {
+                match pop(&mut args) {
+                    Expr::Int(i) => match i.checked_neg() {
+                        Some(i) => args.push(Expr::Int(i)),
+                        None    => return Err(EvalError::runtime("integer overflow in '-'"))
+                    }
+                    Expr::Float(x) => args.push(Expr::Float(-x)),
+                    other => {
+                        let msg = "'-' expects numeric arguments";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::Len => {
+                match pop(&mut args) {
+                    Expr::Str(s) => args.push(Expr::Int(s.chars().count() as i64)),
+                    Expr::Seq(xs) => args.push(Expr::Int(xs.len() as i64)),
+                    other => {
+                        let msg = "'len' expects a string or a sequence";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::StartsWith => {
+                let msg = "'starts-with?' expects string arguments";
+                eval_str_predicate(&mut args, msg, |s, p| Ok(s.starts_with(p)))?
+            }
+            Op::EndsWith => {
+                let msg = "'ends-with?' expects string arguments";
+                eval_str_predicate(&mut args, msg, |s, p| Ok(s.ends_with(p)))?
+            }
+            Op::Contains => {
+                let msg = "'contains?' expects string arguments";
+                eval_str_predicate(&mut args, msg, |s, p| Ok(s.contains(p)))?
+            }
+            #[cfg(feature = "std")]
+            Op::Regex => {
+                let msg = "'regex?' expects string arguments";
+                eval_str_predicate(&mut args, msg, |s, p| match Regex::new(p) {
+                    Ok(r)  => Ok(r.is_match(s)),
+                    Err(_) => {
+                        let msg = "'regex?' expects a valid regular expression";
+                        Err(EvalError::InvalidType(Expr::Str(p.to_string()), msg))
+                    }
+                })?
+            }
         }
     }
 
@@ -293,10 +474,78 @@ where
     Ok(())
 }
 
+/// Apply an arithmetic operation to the `n` topmost arguments, from left to right.
+///
+/// All arguments must be either integers or floats. Integer operations are checked
+/// and fail on overflow or division by zero.
+fn eval_arithmetic<I, F>(
+    n: usize,
+    args: &mut Vec<Expr>,
+    op: &'static str,
+    int_op: I,
+    float_op: F,
+) -> Result<(), EvalError>
+where
+    I: Fn(i64, i64) -> Option<i64>,
+    F: Fn(f64, f64) -> f64,
+{
+    let xs = args.split_off(args.len() - n);
+    let mut xs = xs.into_iter();
+    let mut result = match xs.next() {
+        Some(x @ (Expr::Int(_) | Expr::Float(_))) => x,
+        Some(other) => {
+            return Err(EvalError::InvalidType(
+                other,
+                "arithmetic operators expect numeric arguments",
+            ))
+        }
+        None => return Err(EvalError::malformed(format!("'{op}' requires arguments"))),
+    };
+    for x in xs {
+        result = match (result, x) {
+            (Expr::Int(a), Expr::Int(b)) => match int_op(a, b) {
+                Some(i) => Expr::Int(i),
+                None => {
+                    return Err(EvalError::runtime(format!(
+                        "integer overflow or division by zero in '{op}'"
+                    )))
+                }
+            },
+            (Expr::Float(a), Expr::Float(b)) => Expr::Float(float_op(a, b)),
+            (a, b @ (Expr::Int(_) | Expr::Float(_))) => return Err(EvalError::TypeMismatch(a, b)),
+            (_, other) => {
+                return Err(EvalError::InvalidType(
+                    other,
+                    "arithmetic operators expect numeric arguments",
+                ))
+            }
+        }
+    }
+    args.push(result);
+    Ok(())
+}
+
+/// Evaluate a predicate against the two topmost arguments, which must be strings.
+fn eval_str_predicate<F>(args: &mut Vec<Expr>, msg: &'static str, f: F) -> Result<(), EvalError>
+where
+    F: Fn(&str, &str) -> Result<bool, EvalError>,
+{
+    let y = pop(args);
+    let x = pop(args);
+    match (x, y) {
+        (Expr::Str(x), Expr::Str(y)) => {
+            let b = f(&x, &y)?;
+            args.push(Expr::Bool(b));
+            Ok(())
+        }
+        (Expr::Str(_), other) | (other, _) => Err(EvalError::InvalidType(other, msg)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::abac::{ABAC_HAS_CREDENTIAL_KEY, SUBJECT_KEY};
-    use crate::{eval, Env, Expr};
+    use crate::{eval, parse, Env, Expr};
 
     #[test]
     fn test() {
@@ -324,4 +573,59 @@ mod tests {
         let res = eval(&check_credential_expression, &environment).unwrap();
         matches!(res, Expr::Bool(true));
     }
+
+    #[test]
+    fn stdlib() {
+        let mut environment = Env::new();
+        environment.put("subject.department", Expr::Str("engineering".into()));
+        environment.put("resource.name", Expr::Str("prod-db".into()));
+        environment.put("subject.expires_at", Expr::Int(i64::MAX));
+
+        let check = |input: &str, expected: Expr| {
+            let expr = parse(input).unwrap().unwrap();
+            let result = eval(&expr, &environment).unwrap();
+            assert!(
+                result.equals(&expected).unwrap(),
+                "{input} evaluated to {result}"
+            );
+        };
+
+        check(
+            r#"(starts-with? subject.department "eng")"#,
+            Expr::CONST_TRUE,
+        );
+        check(
+            r#"(ends-with? subject.department "eng")"#,
+            Expr::CONST_FALSE,
+        );
+        check(r#"(contains? subject.department "nee")"#, Expr::CONST_TRUE);
+        check(r#"(regex? resource.name "^prod-.*")"#, Expr::CONST_TRUE);
+        check(r#"(regex? resource.name "^dev-.*")"#, Expr::CONST_FALSE);
+        check("(len subject.department)", Expr::Int(11));
+        check("(len [1 2 3])", Expr::Int(3));
+        check("(+ 1 2 3)", Expr::Int(6));
+        check("(- 10 2 3)", Expr::Int(5));
+        check("(- 10)", Expr::Int(-10));
+        check("(* 2 3 4)", Expr::Int(24));
+        check("(/ 7 2)", Expr::Int(3));
+        check("(+ 1.5 1.5)", Expr::Float(3.0));
+        check("(<= 1 1 2)", Expr::CONST_TRUE);
+        check("(>= 2 3)", Expr::CONST_FALSE);
+        check("(< (now) subject.expires_at)", Expr::CONST_TRUE);
+        check("(> (+ (now) 60) (now))", Expr::CONST_TRUE);
+
+        let fails = |input: &str| {
+            let expr = parse(input).unwrap().unwrap();
+            assert!(eval(&expr, &environment).is_err(), "{input} should fail");
+        };
+
+        fails("(+ 1 2.0)");
+        fails(r#"(+ 1 "a")"#);
+        fails("(/ 1 0)");
+        fails("(+ 9223372036854775807 1)");
+        fails(r#"(starts-with? 1 "a")"#);
+        fails(r#"(regex? resource.name "(")"#);
+        fails("(len 1)");
+        fails("(now 1)");
+    }
 }
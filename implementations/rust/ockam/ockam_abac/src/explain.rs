@@ -0,0 +1,182 @@
+use crate::env::Env;
+use crate::eval::eval;
+use crate::expr::Expr;
+use minicbor::{Decode, Encode};
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::vec::Vec;
+use serde::Serialize;
+
+/// Maximum nesting of sub-expressions which are traced by [`explain`]
+const MAX_DEPTH: u32 = 128;
+
+/// A step in the evaluation of a policy expression
+#[derive(Clone, Debug, Encode, Decode, Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct EvalStep {
+    /// Nesting level of the evaluated sub-expression, 0 for the top-level expression
+    #[n(1)] pub depth: u32,
+    /// Evaluated sub-expression
+    #[n(2)] pub expression: Expr,
+    /// Value of the sub-expression if its evaluation succeeded
+    #[n(3)] pub value: Option<Expr>,
+    /// Evaluation error otherwise
+    #[n(4)] pub error: Option<String>,
+}
+
+impl EvalStep {
+    fn new(depth: u32, expression: &Expr, env: &Env) -> Self {
+        let (value, error) = match eval(expression, env) {
+            Ok(value) => (Some(value), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            depth,
+            expression: expression.clone(),
+            value,
+            error,
+        }
+    }
+
+    #[cfg(test)]
+    fn is_bool(&self, b: bool) -> bool {
+        matches!(self.value, Some(Expr::Bool(v)) if v == b)
+    }
+}
+
+/// Evaluate an expression in a given environment and return the trace of its evaluation.
+///
+/// Each identifier and each operator application produces a step, in the order where their
+/// evaluation completes, so the last step is the one of the top-level expression.
+/// Sub-expressions which are not evaluated by [`eval`], like the second argument of
+/// `(or true x)` or the branch of an `if` which is not taken, are not part of the trace.
+/// The trace stops at the first failing step.
+pub fn explain(expr: &Expr, env: &Env) -> Vec<EvalStep> {
+    let mut steps = Vec::new();
+    explain_step(expr, env, 0, &mut steps);
+    steps
+}
+
+/// Add the steps for the evaluation of `expr` and return its value,
+/// or `None` if the evaluation failed
+fn explain_step(expr: &Expr, env: &Env, depth: u32, steps: &mut Vec<EvalStep>) -> Option<Expr> {
+    if depth > MAX_DEPTH {
+        return step(expr, env, depth, steps);
+    }
+    match expr {
+        Expr::Ident(_) => step(expr, env, depth, steps),
+        Expr::Seq(xs) => {
+            for x in xs {
+                explain_step(x, env, depth + 1, steps)?;
+            }
+            step(expr, env, depth, steps)
+        }
+        Expr::List(es) => {
+            if let Some((Expr::Ident(op), args)) = es.split_first() {
+                match op.as_str() {
+                    // the arguments of exists? are identifiers which may not be bound
+                    "exists?" => {}
+                    op @ ("and" | "or") => {
+                        let short_circuit = op == "or";
+                        for arg in args {
+                            let value = explain_step(arg, env, depth + 1, steps)?;
+                            if matches!(value, Expr::Bool(b) if b == short_circuit) {
+                                break;
+                            }
+                        }
+                    }
+                    "if" => {
+                        if let Some(test) = args.first() {
+                            let branch = match explain_step(test, env, depth + 1, steps)? {
+                                Expr::Bool(true) => args.get(1),
+                                Expr::Bool(false) => args.get(2),
+                                _ => None,
+                            };
+                            if let Some(branch) = branch {
+                                explain_step(branch, env, depth + 1, steps)?;
+                            }
+                        }
+                    }
+                    _ => {
+                        for arg in args {
+                            explain_step(arg, env, depth + 1, steps)?;
+                        }
+                    }
+                }
+            }
+            step(expr, env, depth, steps)
+        }
+        // literal values evaluate to themselves
+        _ => Some(expr.clone()),
+    }
+}
+
+fn step(expr: &Expr, env: &Env, depth: u32, steps: &mut Vec<EvalStep>) -> Option<Expr> {
+    let step = EvalStep::new(depth, expr, env);
+    let value = step.value.clone();
+    steps.push(step);
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{and, eq, ident, int, or, str, when};
+
+    #[test]
+    fn explain_steps() {
+        let mut env = Env::new();
+        env.put("subject.role", str("admin"));
+        env.put("subject.age", int(42));
+
+        let expr = or([
+            eq([ident("subject.role"), str("admin")]),
+            eq([ident("subject.age"), int(100)]),
+        ]);
+        let steps = explain(&expr, &env);
+
+        // the second argument of 'or' is not evaluated
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].depth, 2);
+        assert!(steps[0]
+            .value
+            .as_ref()
+            .unwrap()
+            .equals(&str("admin"))
+            .unwrap());
+        assert_eq!(steps[1].depth, 1);
+        assert!(steps[1].is_bool(true));
+        assert_eq!(steps[2].depth, 0);
+        assert!(steps[2].is_bool(true));
+
+        // only the branch which is taken is evaluated
+        let expr = when(
+            eq([ident("subject.age"), int(42)]),
+            ident("subject.role"),
+            ident("subject.unknown"),
+        );
+        let steps = explain(&expr, &env);
+        assert_eq!(steps.len(), 4);
+        assert!(steps[3]
+            .value
+            .as_ref()
+            .unwrap()
+            .equals(&str("admin"))
+            .unwrap());
+    }
+
+    #[test]
+    fn explain_stops_at_error() {
+        let env = Env::new();
+
+        let expr = and([
+            Expr::CONST_TRUE,
+            eq([ident("subject.role"), str("admin")]),
+            Expr::CONST_FALSE,
+        ]);
+        let steps = explain(&expr, &env);
+        assert_eq!(steps.len(), 1);
+        assert!(steps[0].value.is_none());
+        assert!(steps[0].error.is_some());
+    }
+}
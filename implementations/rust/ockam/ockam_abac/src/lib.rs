@@ -7,9 +7,11 @@
 extern crate alloc;
 extern crate core;
 
+mod check;
 mod env;
 mod error;
 mod eval;
+mod explain;
 mod policy;
 mod types;
 
@@ -22,12 +24,17 @@ pub mod resource;
 mod abac;
 pub use abac::*;
 
+pub use check::{
+    check, CheckReport, Diagnostic, Severity, Type, TypeChecker, ACTION_KEY, RESOURCE_KEY,
+};
 pub use env::Env;
-pub use error::{EvalError, ParseError};
+pub use error::{CheckError, EvalError, ParseError};
 pub use eval::eval;
+pub use explain::{explain, EvalStep};
 pub use expr::Expr;
 pub use policy::{
-    storage::*, Policies, PolicyAccessControl, ResourcePolicy, ResourceTypePolicy, Resources,
+    storage::*, Policies, PolicyAccessControl, PolicyDecision, PolicyExplanation, ResourcePolicy,
+    ResourceTypePolicy, Resources, TrustPolicyAccessControl,
 };
 pub use resource::{Resource, ResourceType};
 pub use types::{Action, ResourceName, Subject};
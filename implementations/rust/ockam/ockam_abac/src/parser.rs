@@ -12,10 +12,13 @@ use wast::lexer::Lexer;
 use wast::lexer::{FloatKind, TokenKind};
 
 /// Allowed identifier patterns.
+///
+/// Identifiers may start with `+` or `-` (e.g. the arithmetic operators), numbers
+/// like `-1` or `+inf` are recognized by the lexer before this pattern is checked.
 fn ident_pattern() -> &'static Regex {
     static INSTANCE: OnceBox<Regex> = OnceBox::new();
     INSTANCE.get_or_init(|| {
-        Box::new(Regex::new("^[a-zA-Z!$%&*/<=>?~_^][a-zA-Z0-9!$%&*/<=>?~_^.+-@]*$").unwrap())
+        Box::new(Regex::new("^[a-zA-Z!$%&*/<=>?~_^+-][a-zA-Z0-9!$%&*/<=>?~_^.+-@]*$").unwrap())
     })
 }
 
@@ -178,3 +181,43 @@ pub fn parse(s: &str) -> Result<Option<Expr>, ParseError> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::expr::{ident, int, str, Expr};
+
+    #[test]
+    fn parse_stdlib_operators() {
+        for op in [
+            "+",
+            "-",
+            "*",
+            "/",
+            "<=",
+            ">=",
+            "len",
+            "now",
+            "starts-with?",
+            "regex?",
+        ] {
+            let x = parse(&format!("({op} 1 2)")).unwrap().unwrap();
+            let expected = Expr::List(vec![ident(op), int(1), int(2)]);
+            assert!(x.equals(&expected).unwrap(), "{x} != {expected}")
+        }
+
+        let x = parse(r#"(regex? resource.name "^prod-.*")"#)
+            .unwrap()
+            .unwrap();
+        let expected = Expr::List(vec![
+            ident("regex?"),
+            ident("resource.name"),
+            str("^prod-.*"),
+        ]);
+        assert!(x.equals(&expected).unwrap());
+
+        let x = parse("(- -1 +inf)").unwrap().unwrap();
+        let expected = Expr::List(vec![ident("-"), int(-1), Expr::Float(f64::INFINITY)]);
+        assert!(x.equals(&expected).unwrap());
+    }
+}
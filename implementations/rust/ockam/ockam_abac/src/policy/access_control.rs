@@ -1,12 +1,18 @@
 use crate::abac::Abac;
-use crate::policy::{IncomingPolicyAccessControl, OutgoingPolicyAccessControl};
-use crate::{Action, Env, Policies, Resource};
+use crate::policy::{
+    IncomingPolicyAccessControl, OutgoingPolicyAccessControl, TrustPolicyAccessControl,
+};
+use crate::{
+    explain, Action, Env, EvalStep, Policies, PolicyDecision, PolicyExplanation, Resource,
+};
 use core::fmt;
 use core::fmt::{Debug, Formatter};
 use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
 use ockam_core::{Address, DenyAll, Result};
 use ockam_identity::{Identifier, IdentitiesAttributes};
 use ockam_node::Context;
+use tracing::debug;
 
 /// Evaluates a policy expression against an environment of attributes.
 ///
@@ -75,4 +81,101 @@ impl PolicyAccessControl {
             policy_access_control: self.clone(),
         })
     }
+
+    /// Create a trust policy for the secure channels created by a secure channel listener
+    pub fn create_trust_policy(&self) -> TrustPolicyAccessControl {
+        TrustPolicyAccessControl {
+            policy_access_control: self.clone(),
+        }
+    }
+
+    /// Return true if the identity is authorized to perform the action on the resource.
+    /// The decision is recorded in the policy decisions repository
+    pub(super) async fn is_identifier_authorized(
+        &self,
+        identifier: Option<Identifier>,
+    ) -> Result<bool> {
+        let (decision, _) = self.decide(identifier, false).await?;
+        let is_authorized = decision.is_authorized;
+        self.policies.record_policy_decision(decision).await;
+        Ok(is_authorized)
+    }
+
+    /// Evaluate the policy for a given identity and return the decision
+    /// with each step of the evaluation of the policy expression.
+    /// The decision is not recorded
+    pub async fn explain(&self, identifier: &Identifier) -> Result<PolicyExplanation> {
+        let (decision, steps) = self.decide(Some(identifier.clone()), true).await?;
+        Ok(PolicyExplanation { decision, steps })
+    }
+
+    async fn decide(
+        &self,
+        identifier: Option<Identifier>,
+        with_steps: bool,
+    ) -> Result<(PolicyDecision, Vec<EvalStep>)> {
+        // Load the policy expression for resource and action:
+        let expression = self
+            .policies
+            .get_expression_for_resource(&self.resource, &self.action)
+            .await?;
+
+        let expression = if let Some(expression) = expression {
+            expression
+        } else {
+            // If no expression exists for this resource and action, access is denied:
+            debug! {
+                resource = %self.resource,
+                action   = %self.action,
+                "no policy found; access denied"
+            }
+            let decision = PolicyDecision::deny(
+                identifier,
+                &self.resource,
+                &self.action,
+                None,
+                "no policy found",
+            )?;
+            return Ok((decision, Vec::new()));
+        };
+
+        let identifier = if let Some(identifier) = identifier {
+            identifier
+        } else {
+            debug! {
+                policy = %expression,
+                "identity identifier not found; access denied"
+            }
+            let decision = PolicyDecision::deny(
+                None,
+                &self.resource,
+                &self.action,
+                Some(expression),
+                "identity identifier not found",
+            )?;
+            return Ok((decision, Vec::new()));
+        };
+
+        let environment = self
+            .abac
+            .identity_environment(&identifier, &expression)
+            .await?;
+        let (is_authorized, reason) = Abac::evaluate(&expression, &environment, &identifier);
+        let steps = if with_steps {
+            explain(&expression, &environment)
+        } else {
+            Vec::new()
+        };
+
+        let mut decision = PolicyDecision::deny(
+            Some(identifier),
+            &self.resource,
+            &self.action,
+            Some(expression),
+            reason,
+        )?
+        .with_consulted_attributes(&environment);
+        decision.is_authorized = is_authorized;
+        Ok((decision, steps))
+    }
 }
@@ -2,16 +2,21 @@ mod access_control;
 mod incoming;
 mod outgoing;
 mod policies;
+mod policy_decision;
+mod policy_decisions_recorder;
 mod resource_policy;
 mod resource_type_policy;
 mod resources;
 pub(crate) mod storage;
+mod trust_policy;
 
 pub use access_control::*;
 pub use incoming::*;
 pub use outgoing::*;
+pub use trust_policy::*;
 
 pub use policies::Policies;
+pub use policy_decision::{PolicyDecision, PolicyExplanation};
 pub use resource_policy::ResourcePolicy;
 pub use resource_type_policy::ResourceTypePolicy;
 pub use resources::Resources;
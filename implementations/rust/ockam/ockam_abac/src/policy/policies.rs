@@ -1,8 +1,10 @@
 use crate::abac::{ABAC_HAS_CREDENTIAL_KEY, SUBJECT_KEY};
+use crate::policy::policy_decisions_recorder::PolicyDecisionsRecorder;
 use crate::policy::ResourceTypePolicy;
 use crate::{
-    Action, Env, Expr, PolicyAccessControl, Resource, ResourceName, ResourcePoliciesRepository,
-    ResourcePolicy, ResourceType, ResourceTypePoliciesRepository,
+    check, Action, Env, Expr, PolicyAccessControl, PolicyDecision, PolicyDecisionsRepository,
+    Resource, ResourceName, ResourcePoliciesRepository, ResourcePolicy, ResourceType,
+    ResourceTypePoliciesRepository,
 };
 use ockam_core::compat::format;
 use ockam_core::compat::sync::Arc;
@@ -10,22 +12,29 @@ use ockam_core::compat::vec::Vec;
 use ockam_core::Result;
 use ockam_identity::{Identifier, IdentitiesAttributes};
 use strum::IntoEnumIterator;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 #[derive(Clone)]
 pub struct Policies {
     resources_policies_repository: Arc<dyn ResourcePoliciesRepository>,
     resource_types_policies_repository: Arc<dyn ResourceTypePoliciesRepository>,
+    policy_decisions_repository: Arc<dyn PolicyDecisionsRepository>,
+    policy_decisions_recorder: PolicyDecisionsRecorder,
 }
 
 impl Policies {
     pub fn new(
         resources_policies_repository: Arc<dyn ResourcePoliciesRepository>,
         resource_types_policies_repository: Arc<dyn ResourceTypePoliciesRepository>,
+        policy_decisions_repository: Arc<dyn PolicyDecisionsRepository>,
     ) -> Self {
         Self {
             resources_policies_repository,
             resource_types_policies_repository,
+            policy_decisions_recorder: PolicyDecisionsRecorder::new(
+                policy_decisions_repository.clone(),
+            ),
+            policy_decisions_repository,
         }
     }
 
@@ -52,6 +61,15 @@ impl Policies {
         )
     }
 
+    /// Reject an expression which can't be evaluated successfully.
+    /// The expressions which are valid but suspicious, like an always-true policy, are only logged
+    fn check_expression(expression: &Expr) -> Result<()> {
+        for warning in check(expression).into_result()? {
+            warn!(policy = %expression, "{warning}");
+        }
+        Ok(())
+    }
+
     pub async fn get_policies(&self) -> Result<(Vec<ResourcePolicy>, Vec<ResourceTypePolicy>)> {
         let resource_policies = self.resources_policies_repository.get_policies().await?;
         let resource_type_policies = self
@@ -64,6 +82,8 @@ impl Policies {
 
 // Methods for resource policies
 impl Policies {
+    /// Store a default policy for each resource type and action.
+    /// By default, the subject must have a credential issued by the project authority
     pub async fn store_default_resource_type_policies(&self) -> Result<()> {
         for resource_type in ResourceType::iter() {
             for action in Action::iter() {
@@ -80,6 +100,7 @@ impl Policies {
         action: &Action,
         expression: &Expr,
     ) -> Result<()> {
+        Self::check_expression(expression)?;
         self.resources_policies_repository
             .store_policy(resource_name, action, expression)
             .await
@@ -148,6 +169,7 @@ impl Policies {
         action: &Action,
         expression: &Expr,
     ) -> Result<()> {
+        Self::check_expression(expression)?;
         self.resource_types_policies_repository
             .store_policy(resource_type, action, expression)
             .await
@@ -193,3 +215,25 @@ impl Policies {
             .await
     }
 }
+
+// Methods for policy decisions
+impl Policies {
+    /// Record a policy decision in the background.
+    /// Decisions can be dropped if they are taken at a too high rate
+    pub async fn record_policy_decision(&self, decision: PolicyDecision) {
+        self.policy_decisions_recorder.record(decision).await
+    }
+
+    /// Return the most recent policy decisions first
+    pub async fn get_policy_decisions(
+        &self,
+        resource_name: Option<&ResourceName>,
+        identifier: Option<&Identifier>,
+        only_denied: bool,
+        limit: u32,
+    ) -> Result<Vec<PolicyDecision>> {
+        self.policy_decisions_repository
+            .get_decisions(resource_name, identifier, only_denied, limit)
+            .await
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Action, Env, EvalStep, Expr, Resource, ResourceName, ResourceType};
+use minicbor::{Decode, Encode};
+use ockam_core::compat::collections::{BTreeMap, BTreeSet};
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::time::now;
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+use ockam_identity::Identifier;
+use serde::Serialize;
+
+/// Outcome of the evaluation of a policy, for a given identity, resource and action.
+///
+/// Each decision taken by a [`crate::PolicyAccessControl`] is recorded in order to
+/// provide an audit trail of the accesses granted or denied by a node.
+#[derive(Clone, Debug, Encode, Decode, Serialize, PartialEq)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PolicyDecision {
+    /// Time of the decision, in seconds since the Unix epoch
+    #[n(1)] pub timestamp: u64,
+    /// Identifier of the subject, if one could be found
+    #[n(2)] pub identifier: Option<Identifier>,
+    #[n(3)] pub resource_name: ResourceName,
+    #[n(4)] pub resource_type: ResourceType,
+    #[n(5)] pub action: Action,
+    /// Evaluated policy expression, if a policy exists for the resource and action
+    #[n(6)] pub expression: Option<Expr>,
+    /// Values of the attributes consulted by the policy expression
+    #[n(7)] pub attributes: BTreeMap<String, Expr>,
+    #[n(8)] pub is_authorized: bool,
+    /// Human-readable explanation of the decision
+    #[n(9)] pub reason: String,
+}
+
+impl PolicyDecision {
+    /// Create a decision denying the access to a resource.
+    /// The decision is then refined once the policy has been evaluated
+    pub fn deny(
+        identifier: Option<Identifier>,
+        resource: &Resource,
+        action: &Action,
+        expression: Option<Expr>,
+        reason: impl Into<String>,
+    ) -> Result<Self> {
+        Ok(Self {
+            timestamp: now()?,
+            identifier,
+            resource_name: resource.resource_name.clone(),
+            resource_type: resource.resource_type.clone(),
+            action: action.clone(),
+            expression,
+            attributes: BTreeMap::new(),
+            is_authorized: false,
+            reason: reason.into(),
+        })
+    }
+
+    /// Keep the values of the identifiers referenced by the policy expression
+    pub fn with_consulted_attributes(mut self, environment: &Env) -> Self {
+        if let Some(expression) = &self.expression {
+            self.attributes = identifiers(expression)
+                .into_iter()
+                .filter_map(|id| environment.get(&id).ok().map(|v| (id, v.clone())))
+                .collect();
+        }
+        self
+    }
+}
+
+/// A policy decision with the detailed evaluation of its policy expression
+#[derive(Clone, Debug, Encode, Decode, Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PolicyExplanation {
+    #[n(1)] pub decision: PolicyDecision,
+    #[n(2)] pub steps: Vec<EvalStep>,
+}
+
+/// Return the identifiers used as values in an expression, operators excluded
+fn identifiers(expression: &Expr) -> BTreeSet<String> {
+    let mut result = BTreeSet::new();
+    let mut to_visit = Vec::from([expression]);
+    while let Some(e) = to_visit.pop() {
+        match e {
+            Expr::Ident(id) => {
+                result.insert(id.to_string());
+            }
+            Expr::Seq(xs) => to_visit.extend(xs.iter()),
+            Expr::List(es) => match es.split_first() {
+                Some((Expr::Ident(_), args)) => to_visit.extend(args.iter()),
+                _ => to_visit.extend(es.iter()),
+            },
+            _ => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{and, eq, ident, str};
+
+    #[test]
+    fn consulted_attributes() -> Result<()> {
+        let mut env = Env::new();
+        env.put("subject.role", str("admin"));
+        env.put("subject.name", str("alice"));
+        env.put("resource.id", str("outlet"));
+
+        let expression = and([
+            eq([ident("subject.role"), str("admin")]),
+            eq([ident("subject.cluster"), str("dev")]),
+        ]);
+        let resource = Resource::new("outlet", ResourceType::TcpOutlet);
+        let decision = PolicyDecision::deny(
+            None,
+            &resource,
+            &Action::HandleMessage,
+            Some(expression),
+            "policy evaluation failed",
+        )?
+        .with_consulted_attributes(&env);
+
+        // only the attributes referenced by the expression, and bound in the environment, are kept
+        assert_eq!(
+            decision.attributes.keys().collect::<Vec<_>>(),
+            vec!["subject.role"]
+        );
+        Ok(())
+    }
+}
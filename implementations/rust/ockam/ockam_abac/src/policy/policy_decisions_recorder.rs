@@ -0,0 +1,187 @@
+use crate::{PolicyDecision, PolicyDecisionsRepository};
+use ockam_core::compat::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+#[cfg(feature = "std")]
+use crate::tokio::sync::mpsc::{channel, error::TrySendError, Sender};
+
+/// Maximum number of decisions waiting to be stored.
+/// When the queue is full, new decisions are dropped
+#[cfg(feature = "std")]
+const MAX_PENDING_DECISIONS: usize = 1024;
+
+/// Maximum number of decisions recorded per second.
+/// This keeps a flood of messages from turning into a flood of database writes
+const MAX_DECISIONS_PER_SECOND: u32 = 100;
+
+/// Record policy decisions without slowing down the access control decisions.
+///
+/// Decisions are sent to a background task storing them in the policy decisions repository.
+/// Once more than [`MAX_DECISIONS_PER_SECOND`] decisions have been recorded in a given second,
+/// or when the storage can't keep up, the other decisions are dropped and only counted.
+#[derive(Clone)]
+pub(crate) struct PolicyDecisionsRecorder {
+    repository: Arc<dyn PolicyDecisionsRepository>,
+    state: Arc<Mutex<RecorderState>>,
+}
+
+#[derive(Default)]
+struct RecorderState {
+    /// Second (since the Unix epoch) of the current rate limiting window
+    current_second: u64,
+    recorded_in_current_second: u32,
+    dropped: u64,
+    #[cfg(feature = "std")]
+    sender: Option<Sender<PolicyDecision>>,
+}
+
+impl PolicyDecisionsRecorder {
+    pub(crate) fn new(repository: Arc<dyn PolicyDecisionsRepository>) -> Self {
+        Self {
+            repository,
+            state: Arc::new(Mutex::new(RecorderState::default())),
+        }
+    }
+
+    /// Record a decision, unless the rate limit is exceeded
+    pub(crate) async fn record(&self, decision: PolicyDecision) {
+        if !self.is_under_rate_limit(decision.timestamp) {
+            return;
+        }
+
+        #[cfg(feature = "std")]
+        self.send(decision);
+
+        #[cfg(not(feature = "std"))]
+        if let Err(e) = self.repository.store_decision(&decision).await {
+            warn!(err = %e, "the policy decision could not be recorded");
+        }
+    }
+
+    fn is_under_rate_limit(&self, timestamp: u64) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.current_second != timestamp {
+            if state.dropped > 0 {
+                debug!(
+                    "{} policy decisions were not recorded because of the rate limit",
+                    state.dropped
+                );
+            }
+            state.current_second = timestamp;
+            state.recorded_in_current_second = 0;
+            state.dropped = 0;
+        }
+        if state.recorded_in_current_second >= MAX_DECISIONS_PER_SECOND {
+            state.dropped += 1;
+            return false;
+        }
+        state.recorded_in_current_second += 1;
+        true
+    }
+
+    /// Send the decision to the background task, starting it if necessary.
+    /// The task is started again if it stopped, for example when its runtime was shut down
+    #[cfg(feature = "std")]
+    fn send(&self, decision: PolicyDecision) {
+        let mut state = self.state.lock().unwrap();
+        let sender = match &state.sender {
+            Some(sender) if !sender.is_closed() => sender.clone(),
+            _ => {
+                let sender = self.start();
+                state.sender = Some(sender.clone());
+                sender
+            }
+        };
+        match sender.try_send(decision) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) => {
+                state.dropped += 1;
+            }
+            Err(TrySendError::Closed(_)) => {
+                warn!("the policy decisions recorder is stopped, the decision is not recorded");
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn start(&self) -> Sender<PolicyDecision> {
+        let (sender, mut receiver) = channel::<PolicyDecision>(MAX_PENDING_DECISIONS);
+        let repository = self.repository.clone();
+        ockam_node::spawn(async move {
+            while let Some(decision) = receiver.recv().await {
+                if let Err(e) = repository.store_decision(&decision).await {
+                    warn!(err = %e, "the policy decision could not be recorded");
+                }
+            }
+        });
+        sender
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{Action, PolicyDecisionSqlxDatabase, Resource, ResourceType};
+    use core::time::Duration;
+    use ockam_core::compat::time::now;
+
+    #[tokio::test]
+    async fn test_decisions_are_recorded_asynchronously() -> ockam_core::Result<()> {
+        let repository = Arc::new(PolicyDecisionSqlxDatabase::create().await?);
+        let recorder = PolicyDecisionsRecorder::new(repository.clone());
+
+        recorder.record(decision(now()?)?).await;
+
+        let decisions = wait_for_decisions(repository.clone(), 1).await?;
+        assert_eq!(decisions, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_decisions_are_rate_limited() -> ockam_core::Result<()> {
+        let repository = Arc::new(PolicyDecisionSqlxDatabase::create().await?);
+        let recorder = PolicyDecisionsRecorder::new(repository.clone());
+
+        // all the decisions are taken during the same second
+        let timestamp = now()?;
+        for _ in 0..MAX_DECISIONS_PER_SECOND + 10 {
+            recorder.record(decision(timestamp)?).await;
+        }
+        // a decision taken in the next second is recorded
+        recorder.record(decision(timestamp + 1)?).await;
+
+        let expected = MAX_DECISIONS_PER_SECOND + 1;
+        let decisions = wait_for_decisions(repository.clone(), expected).await?;
+        assert_eq!(decisions, expected);
+        Ok(())
+    }
+
+    /// HELPERS
+    fn decision(timestamp: u64) -> ockam_core::Result<PolicyDecision> {
+        let resource = Resource::new("outlet", ResourceType::TcpOutlet);
+        let mut decision =
+            PolicyDecision::deny(None, &resource, &Action::HandleMessage, None, "denied")?;
+        decision.timestamp = timestamp;
+        Ok(decision)
+    }
+
+    /// Wait until the expected number of decisions has been stored, or a timeout,
+    /// and return the number of stored decisions
+    async fn wait_for_decisions(
+        repository: Arc<PolicyDecisionSqlxDatabase>,
+        expected: u32,
+    ) -> ockam_core::Result<u32> {
+        let mut stored = 0;
+        for _ in 0..100 {
+            stored = repository
+                .get_decisions(None, None, false, expected + 100)
+                .await?
+                .len() as u32;
+            if stored >= expected {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        Ok(stored)
+    }
+}
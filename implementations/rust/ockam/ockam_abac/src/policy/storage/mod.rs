@@ -1,7 +1,10 @@
+mod policy_decision_repository;
 mod resource_policy_repository;
 mod resource_repository;
 mod resource_type_policy_repository;
 
+#[cfg(feature = "std")]
+pub(crate) mod policy_decision_repository_sql;
 #[cfg(feature = "std")]
 pub(crate) mod resource_policy_repository_sql;
 #[cfg(feature = "std")]
@@ -9,10 +12,13 @@ pub(crate) mod resource_repository_sql;
 #[cfg(feature = "std")]
 pub(crate) mod resource_type_policy_repository_sql;
 
+pub use policy_decision_repository::*;
 pub use resource_policy_repository::*;
 pub use resource_repository::*;
 pub use resource_type_policy_repository::*;
 
+#[cfg(feature = "std")]
+pub use policy_decision_repository_sql::*;
 #[cfg(feature = "std")]
 pub use resource_policy_repository_sql::*;
 #[cfg(feature = "std")]
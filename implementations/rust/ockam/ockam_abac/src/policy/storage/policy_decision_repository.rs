@@ -0,0 +1,24 @@
+use crate::{PolicyDecision, ResourceName};
+use ockam_core::async_trait;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+use ockam_identity::Identifier;
+
+/// This repository stores the decisions taken when evaluating policies.
+/// It provides an audit trail of the access granted or denied to a resource.
+#[async_trait]
+pub trait PolicyDecisionsRepository: Send + Sync + 'static {
+    /// Store a policy decision
+    async fn store_decision(&self, decision: &PolicyDecision) -> Result<()>;
+
+    /// Return the most recent decisions first, up to `limit` decisions,
+    /// optionally restricted to a resource name, an identifier and/or the denied accesses
+    async fn get_decisions(
+        &self,
+        resource_name: Option<&ResourceName>,
+        identifier: Option<&Identifier>,
+        only_denied: bool,
+        limit: u32,
+    ) -> Result<Vec<PolicyDecision>>;
+}
@@ -0,0 +1,226 @@
+use core::str::FromStr;
+use sqlx::*;
+use tracing::debug;
+
+use ockam_core::async_trait;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+use ockam_identity::Identifier;
+use ockam_node::database::{FromSqlxError, SqlxDatabase, ToSqlxType, ToVoid};
+
+use crate::{Action, Expr, PolicyDecision, PolicyDecisionsRepository, ResourceName, ResourceType};
+
+/// Maximum number of policy decisions kept for a given node.
+/// The oldest decisions are deleted when this number is exceeded
+pub const MAX_POLICY_DECISIONS: u32 = 10_000;
+
+#[derive(Clone)]
+pub struct PolicyDecisionSqlxDatabase {
+    database: SqlxDatabase,
+    node_name: String,
+}
+
+impl PolicyDecisionSqlxDatabase {
+    /// Create a new database for policy decisions
+    pub fn new(database: SqlxDatabase, node_name: &str) -> Self {
+        debug!("create a repository for policy decisions");
+        Self {
+            database,
+            node_name: node_name.to_string(),
+        }
+    }
+
+    /// Create a new in-memory database for policy decisions
+    pub async fn create() -> Result<Self> {
+        Ok(Self::new(
+            SqlxDatabase::in_memory("policy_decisions").await?,
+            "default",
+        ))
+    }
+}
+
+#[async_trait]
+impl PolicyDecisionsRepository for PolicyDecisionSqlxDatabase {
+    async fn store_decision(&self, decision: &PolicyDecision) -> Result<()> {
+        let mut transaction = self.database.begin().await.into_core()?;
+        let query1 = query(
+            r#"INSERT INTO policy_decision
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(decision.timestamp.to_sql())
+        .bind(decision.identifier.as_ref().map(|i| i.to_sql()))
+        .bind(decision.resource_name.to_sql())
+        .bind(decision.resource_type.to_sql())
+        .bind(decision.action.to_sql())
+        .bind(decision.expression.as_ref().map(|e| e.to_string().to_sql()))
+        .bind(minicbor::to_vec(&decision.attributes)?.to_sql())
+        .bind(decision.is_authorized.to_sql())
+        .bind(decision.reason.to_sql())
+        .bind(self.node_name.to_sql());
+        query1.execute(&mut *transaction).await.void()?;
+
+        // only keep the most recent decisions
+        let query2 = query(
+            r#"DELETE FROM policy_decision
+            WHERE node_name=$1 AND rowid <= (
+              SELECT rowid FROM policy_decision
+              WHERE node_name=$1
+              ORDER BY rowid DESC
+              LIMIT 1 OFFSET $2)"#,
+        )
+        .bind(self.node_name.to_sql())
+        .bind(MAX_POLICY_DECISIONS.to_sql());
+        query2.execute(&mut *transaction).await.void()?;
+
+        transaction.commit().await.void()
+    }
+
+    async fn get_decisions(
+        &self,
+        resource_name: Option<&ResourceName>,
+        identifier: Option<&Identifier>,
+        only_denied: bool,
+        limit: u32,
+    ) -> Result<Vec<PolicyDecision>> {
+        let query = query_as(
+            r#"SELECT timestamp, identifier, resource_name, resource_type, action, expression, attributes, is_authorized, reason
+            FROM policy_decision
+            WHERE node_name=$1
+              AND ($2 IS NULL OR resource_name=$2)
+              AND ($3 IS NULL OR identifier=$3)
+              AND ($4=0 OR is_authorized=0)
+            ORDER BY timestamp DESC, rowid DESC
+            LIMIT $5"#,
+        )
+        .bind(self.node_name.to_sql())
+        .bind(resource_name.map(|r| r.to_sql()))
+        .bind(identifier.map(|i| i.to_sql()))
+        .bind(only_denied.to_sql())
+        .bind(limit.to_sql());
+        let rows: Vec<PolicyDecisionRow> =
+            query.fetch_all(&*self.database.pool).await.into_core()?;
+        rows.into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<Vec<PolicyDecision>>>()
+    }
+}
+
+/// Low-level representation of a row in the policy_decision table
+#[derive(FromRow)]
+struct PolicyDecisionRow {
+    timestamp: i64,
+    identifier: Option<String>,
+    resource_name: String,
+    resource_type: String,
+    action: String,
+    expression: Option<String>,
+    attributes: Vec<u8>,
+    is_authorized: bool,
+    reason: String,
+}
+
+impl TryFrom<PolicyDecisionRow> for PolicyDecision {
+    type Error = ockam_core::Error;
+
+    fn try_from(row: PolicyDecisionRow) -> Result<Self, Self::Error> {
+        let attributes: BTreeMap<String, Expr> = minicbor::decode(&row.attributes)?;
+        Ok(PolicyDecision {
+            timestamp: row.timestamp as u64,
+            identifier: row
+                .identifier
+                .map(|i| Identifier::from_str(&i))
+                .transpose()?,
+            resource_name: ResourceName::from(row.resource_name),
+            resource_type: ResourceType::from_str(&row.resource_type)?,
+            action: Action::from_str(&row.action)?,
+            expression: row
+                .expression
+                .map(|e| Expr::try_from(e.as_str()))
+                .transpose()?,
+            attributes,
+            is_authorized: row.is_authorized,
+            reason: row.reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::*;
+    use crate::{Env, Resource};
+    use ockam_core::compat::sync::Arc;
+
+    #[tokio::test]
+    async fn test_repository() -> Result<()> {
+        let repo = policy_decision_repository().await?;
+
+        let identifier = Identifier::from_str(
+            "Iabababababababababababababababababababababababababababababababab",
+        )?;
+        let outlet = Resource::new("outlet", ResourceType::TcpOutlet);
+        let inlet = Resource::new("inlet", ResourceType::TcpInlet);
+        let expression = eq([ident("subject.role"), str("admin")]);
+        let mut env = Env::new();
+        env.put("subject.role", str("admin"));
+
+        // a decision can be stored with the attributes consulted by the policy
+        let mut allowed = PolicyDecision::deny(
+            Some(identifier.clone()),
+            &outlet,
+            &Action::HandleMessage,
+            Some(expression.clone()),
+            "",
+        )?
+        .with_consulted_attributes(&env);
+        allowed.is_authorized = true;
+        allowed.reason = "the policy evaluated to true".into();
+        repo.store_decision(&allowed).await?;
+
+        // a decision can be stored without an identifier or a policy expression
+        let denied = PolicyDecision::deny(
+            None,
+            &inlet,
+            &Action::HandleMessage,
+            None,
+            "no policy found",
+        )?;
+        repo.store_decision(&denied).await?;
+
+        // all the decisions can be retrieved, most recent first
+        let decisions = repo.get_decisions(None, None, false, 10).await?;
+        assert_eq!(decisions.len(), 2);
+        assert_eq!(decisions[0].resource_name, inlet.resource_name);
+        assert_eq!(decisions[1].identifier, Some(identifier.clone()));
+        assert!(decisions[1].attributes["subject.role"]
+            .equals(&str("admin"))
+            .unwrap());
+
+        // the decisions can be filtered
+        let decisions = repo
+            .get_decisions(Some(&outlet.resource_name), None, false, 10)
+            .await?;
+        assert_eq!(decisions.len(), 1);
+        assert!(decisions[0].is_authorized);
+
+        let decisions = repo
+            .get_decisions(None, Some(&identifier), false, 10)
+            .await?;
+        assert_eq!(decisions.len(), 1);
+
+        let decisions = repo.get_decisions(None, None, true, 10).await?;
+        assert_eq!(decisions.len(), 1);
+        assert!(!decisions[0].is_authorized);
+
+        let decisions = repo.get_decisions(None, None, false, 1).await?;
+        assert_eq!(decisions.len(), 1);
+
+        Ok(())
+    }
+
+    /// HELPERS
+    async fn policy_decision_repository() -> Result<Arc<dyn PolicyDecisionsRepository>> {
+        Ok(Arc::new(PolicyDecisionSqlxDatabase::create().await?))
+    }
+}
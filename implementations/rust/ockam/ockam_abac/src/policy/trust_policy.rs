@@ -0,0 +1,21 @@
+use crate::PolicyAccessControl;
+use core::fmt::Debug;
+use ockam_core::compat::boxed::Box;
+use ockam_core::{async_trait, Result};
+use ockam_identity::{SecureChannelTrustInfo, TrustPolicy};
+
+/// TrustPolicy checking the policy stored for a resource, for example a secure channel listener,
+/// against the other participant of a secure channel
+#[derive(Debug)]
+pub struct TrustPolicyAccessControl {
+    pub(super) policy_access_control: PolicyAccessControl,
+}
+
+#[async_trait]
+impl TrustPolicy for TrustPolicyAccessControl {
+    async fn check(&self, trust_info: &SecureChannelTrustInfo) -> Result<bool> {
+        self.policy_access_control
+            .is_identifier_authorized(Some(trust_info.their_identity_id().clone()))
+            .await
+    }
+}
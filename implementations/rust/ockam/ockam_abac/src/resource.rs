@@ -45,6 +45,30 @@ pub enum ResourceType {
     #[n(3)]
     #[strum(serialize = "echoer")]
     Echoer,
+    #[n(4)]
+    #[strum(serialize = "relay")]
+    Relay,
+    #[n(5)]
+    #[strum(serialize = "kafka-inlet")]
+    KafkaInlet,
+    #[n(6)]
+    #[strum(serialize = "kafka-outlet")]
+    KafkaOutlet,
+    #[n(7)]
+    #[strum(serialize = "secure-channel-listener")]
+    SecureChannelListener,
+    #[n(8)]
+    #[strum(serialize = "node-manager")]
+    NodeManager,
+    #[n(9)]
+    #[strum(serialize = "udp-inlet")]
+    UdpInlet,
+    #[n(10)]
+    #[strum(serialize = "udp-outlet")]
+    UdpOutlet,
+    #[n(11)]
+    #[strum(serialize = "message-queue")]
+    MessageQueue,
 }
 
 impl ResourceType {
@@ -0,0 +1,59 @@
+use miette::IntoDiagnostic;
+
+use ockam::identity::models::{RevocationListAndPurposeKey, RevokedSubject};
+use ockam::identity::Identifier;
+use ockam_core::api::Request;
+use ockam_core::async_trait;
+use ockam_node::Context;
+
+use crate::authenticator::revocation::types::RevokeSubject;
+use crate::cloud::{AuthorityNodeClient, HasSecureClient};
+use crate::nodes::service::default_address::DefaultAddress;
+
+#[async_trait]
+pub trait Revocations {
+    async fn revoke(&self, ctx: &Context, subject: Identifier) -> miette::Result<()>;
+
+    async fn get_revocation_list(
+        &self,
+        ctx: &Context,
+    ) -> miette::Result<RevocationListAndPurposeKey>;
+
+    async fn list_revoked_subjects(&self, ctx: &Context) -> miette::Result<Vec<RevokedSubject>>;
+}
+
+#[async_trait]
+impl Revocations for AuthorityNodeClient {
+    async fn revoke(&self, ctx: &Context, subject: Identifier) -> miette::Result<()> {
+        let req = Request::post("/").body(RevokeSubject::new(subject));
+        self.get_secure_client()
+            .tell(ctx, DefaultAddress::REVOCATION_LIST_ISSUER, req)
+            .await
+            .into_diagnostic()?
+            .success()
+            .into_diagnostic()
+    }
+
+    async fn get_revocation_list(
+        &self,
+        ctx: &Context,
+    ) -> miette::Result<RevocationListAndPurposeKey> {
+        let req = Request::get("/");
+        self.get_secure_client()
+            .ask(ctx, DefaultAddress::REVOCATION_LIST_ISSUER, req)
+            .await
+            .into_diagnostic()?
+            .success()
+            .into_diagnostic()
+    }
+
+    async fn list_revoked_subjects(&self, ctx: &Context) -> miette::Result<Vec<RevokedSubject>> {
+        let req = Request::get("/subjects");
+        self.get_secure_client()
+            .ask(ctx, DefaultAddress::REVOCATION_LIST_ISSUER, req)
+            .await
+            .into_diagnostic()?
+            .success()
+            .into_diagnostic()
+    }
+}
@@ -0,0 +1,147 @@
+use either::Either;
+
+use ockam::identity::models::{RevocationListAndPurposeKey, RevokedSubject};
+use ockam::identity::utils::now;
+use ockam::identity::{Credentials, Identifier, IdentitiesAttributes, RevocationRepository};
+use ockam_core::compat::sync::Arc;
+use ockam_core::Result;
+
+use crate::authenticator::common::EnrollerAccessControlChecks;
+use crate::authenticator::direct::{
+    AccountAuthorityInfo, DirectAuthenticatorError, DirectAuthenticatorResult,
+};
+use crate::authenticator::AuthorityMembersRepository;
+
+/// This struct maintains the list of subjects revoked by an Authority
+/// and issues signed revocation lists
+pub struct RevocationListIssuer {
+    members: Arc<dyn AuthorityMembersRepository>,
+    identities_attributes: Arc<IdentitiesAttributes>,
+    credentials: Arc<Credentials>,
+    revocations: Arc<dyn RevocationRepository>,
+    issuer: Identifier,
+    account_authority: Option<AccountAuthorityInfo>,
+}
+
+impl RevocationListIssuer {
+    pub fn new(
+        members: Arc<dyn AuthorityMembersRepository>,
+        identities_attributes: Arc<IdentitiesAttributes>,
+        credentials: Arc<Credentials>,
+        revocations: Arc<dyn RevocationRepository>,
+        issuer: &Identifier,
+        account_authority: Option<AccountAuthorityInfo>,
+    ) -> Self {
+        Self {
+            members,
+            identities_attributes,
+            credentials,
+            revocations,
+            issuer: issuer.clone(),
+            account_authority,
+        }
+    }
+
+    /// Return a revocation list, signed by the authority, with all the revoked subjects
+    #[instrument(skip_all)]
+    pub async fn issue_revocation_list(&self) -> Result<RevocationListAndPurposeKey> {
+        let revoked_subjects = self.revocations.get_revoked_subjects(&self.issuer).await?;
+        self.credentials
+            .credentials_creation()
+            .issue_revocation_list(&self.issuer, revoked_subjects)
+            .await
+    }
+
+    /// Revoke a subject: it is removed from the project members, won't be able to get a new
+    /// credential and its current credential will be rejected by the nodes using the revocation list
+    #[instrument(skip_all, fields(enroller = %enroller, subject = %subject))]
+    pub async fn revoke(
+        &self,
+        enroller: &Identifier,
+        subject: &Identifier,
+    ) -> Result<DirectAuthenticatorResult<()>> {
+        let check_enroller = EnrollerAccessControlChecks::check_identifier(
+            self.members.clone(),
+            self.identities_attributes.clone(),
+            enroller,
+            &self.account_authority,
+        )
+        .await?;
+
+        if !check_enroller.is_enroller {
+            warn!("Non-enroller {} is trying to revoke {}", enroller, subject);
+            return Ok(Either::Right(DirectAuthenticatorError(
+                "Non-enroller is trying to revoke a subject".to_string(),
+            )));
+        }
+
+        let check_subject = EnrollerAccessControlChecks::check_identifier(
+            self.members.clone(),
+            self.identities_attributes.clone(),
+            subject,
+            &self.account_authority,
+        )
+        .await?;
+
+        if check_subject.is_pre_trusted {
+            warn!(
+                "Enroller {} is trying to revoke a pre trusted identity {}",
+                enroller, subject
+            );
+            return Ok(Either::Right(DirectAuthenticatorError(
+                "Enroller is trying to revoke a pre trusted identity".to_string(),
+            )));
+        }
+
+        if check_subject.is_enroller && !check_enroller.is_admin {
+            warn!(
+                "Not admin {} is trying to revoke enroller {}",
+                enroller, subject
+            );
+            return Ok(Either::Right(DirectAuthenticatorError(
+                "Not admin is trying to revoke an enroller".to_string(),
+            )));
+        }
+
+        self.members.delete_member(subject).await?;
+        self.revocations
+            .add_revoked_subject(
+                &self.issuer,
+                RevokedSubject {
+                    subject: subject.clone(),
+                    revoked_at: now()?,
+                },
+            )
+            .await?;
+
+        info!("Successfully revoked {} by {}", subject, enroller);
+
+        Ok(Either::Left(()))
+    }
+
+    /// Return the list of revoked subjects
+    #[instrument(skip_all, fields(enroller = %enroller))]
+    pub async fn list_revoked_subjects(
+        &self,
+        enroller: &Identifier,
+    ) -> Result<DirectAuthenticatorResult<Vec<RevokedSubject>>> {
+        let check = EnrollerAccessControlChecks::check_identifier(
+            self.members.clone(),
+            self.identities_attributes.clone(),
+            enroller,
+            &self.account_authority,
+        )
+        .await?;
+
+        if !check.is_enroller {
+            warn!("Non-enroller {} is trying to list revocations", enroller);
+            return Ok(Either::Right(DirectAuthenticatorError(
+                "Non-enroller is trying to list revocations".to_string(),
+            )));
+        }
+
+        Ok(Either::Left(
+            self.revocations.get_revoked_subjects(&self.issuer).await?,
+        ))
+    }
+}
@@ -0,0 +1,108 @@
+use either::Either;
+use minicbor::Decoder;
+use tracing::trace;
+
+use ockam::identity::{
+    Credentials, Identifier, IdentitiesAttributes, IdentitySecureChannelLocalInfo,
+    RevocationRepository,
+};
+use ockam_core::api::{Method, RequestHeader, Response};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{Result, Routed, Worker};
+use ockam_node::Context;
+
+use crate::authenticator::direct::AccountAuthorityInfo;
+use crate::authenticator::revocation::types::RevokeSubject;
+use crate::authenticator::revocation::RevocationListIssuer;
+use crate::authenticator::AuthorityMembersRepository;
+
+/// This worker serves the revocation list of an Authority and lets enrollers revoke subjects
+pub struct RevocationListIssuerWorker {
+    issuer: RevocationListIssuer,
+}
+
+impl RevocationListIssuerWorker {
+    pub fn new(
+        members: Arc<dyn AuthorityMembersRepository>,
+        identities_attributes: Arc<IdentitiesAttributes>,
+        credentials: Arc<Credentials>,
+        revocations: Arc<dyn RevocationRepository>,
+        issuer: &Identifier,
+        account_authority: Option<AccountAuthorityInfo>,
+    ) -> Self {
+        Self {
+            issuer: RevocationListIssuer::new(
+                members,
+                identities_attributes,
+                credentials,
+                revocations,
+                issuer,
+                account_authority,
+            ),
+        }
+    }
+}
+
+#[ockam_core::worker]
+impl Worker for RevocationListIssuerWorker {
+    type Context = Context;
+    type Message = Vec<u8>;
+
+    async fn handle_message(&mut self, c: &mut Context, m: Routed<Self::Message>) -> Result<()> {
+        let secure_channel_info = match IdentitySecureChannelLocalInfo::find_info(m.local_message())
+        {
+            Ok(secure_channel_info) => secure_channel_info,
+            Err(_e) => {
+                let resp = Response::bad_request_no_request("secure channel required").to_vec()?;
+                c.send(m.return_route(), resp).await?;
+                return Ok(());
+            }
+        };
+
+        let from = secure_channel_info.their_identity_id();
+        let return_route = m.return_route();
+        let body = m.into_body()?;
+        let mut dec = Decoder::new(&body);
+        let req: RequestHeader = dec.decode()?;
+        trace! {
+            target: "revocation_list_issuer",
+            from   = %from,
+            id     = %req.id(),
+            method = ?req.method(),
+            path   = %req.path(),
+            body   = %req.has_body(),
+            "request"
+        }
+        let path_segments = req.path_segments::<5>();
+        let res = match (req.method(), path_segments.as_slice()) {
+            (Some(Method::Get), [""]) => {
+                let revocation_list = self.issuer.issue_revocation_list().await?;
+                Response::ok()
+                    .with_headers(&req)
+                    .body(revocation_list)
+                    .to_vec()?
+            }
+            (Some(Method::Get), ["subjects"]) => {
+                match self.issuer.list_revoked_subjects(&from).await? {
+                    Either::Left(revoked_subjects) => Response::ok()
+                        .with_headers(&req)
+                        .body(revoked_subjects)
+                        .to_vec()?,
+                    Either::Right(error) => Response::forbidden(&req, &error.0).to_vec()?,
+                }
+            }
+            (Some(Method::Post), [""]) => {
+                let revoke: RevokeSubject = dec.decode()?;
+                match self.issuer.revoke(&from, revoke.subject()).await? {
+                    Either::Left(_) => Response::ok().with_headers(&req).to_vec()?,
+                    Either::Right(error) => Response::forbidden(&req, &error.0).to_vec()?,
+                }
+            }
+            _ => Response::unknown_path(&req).to_vec()?,
+        };
+
+        c.send(return_route, res).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use minicbor::{Decode, Encode};
+use ockam::identity::Identifier;
+
+#[derive(Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RevokeSubject {
+    #[n(1)] subject: Identifier,
+}
+
+impl RevokeSubject {
+    pub fn new(subject: Identifier) -> Self {
+        RevokeSubject { subject }
+    }
+
+    pub fn subject(&self) -> &Identifier {
+        &self.subject
+    }
+}
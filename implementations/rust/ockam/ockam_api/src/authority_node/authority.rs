@@ -7,6 +7,7 @@ use crate::authenticator::direct::{AccountAuthorityInfo, DirectAuthenticatorWork
 use crate::authenticator::enrollment_tokens::{
     EnrollmentTokenAcceptorWorker, EnrollmentTokenIssuerWorker,
 };
+use crate::authenticator::revocation::RevocationListIssuerWorker;
 use crate::authenticator::{
     AuthorityEnrollmentTokenRepository, AuthorityEnrollmentTokenSqlxDatabase,
     AuthorityMembersRepository, AuthorityMembersSqlxDatabase,
@@ -34,6 +35,7 @@ use crate::nodes::service::default_address::DefaultAddress;
 //   - a credential issuer
 //   - an enrollment token issuer
 //   - an enrollment token acceptor
+//   - a revocation list issuer
 pub struct Authority {
     identifier: Identifier,
     secure_channels: Arc<SecureChannels>,
@@ -246,6 +248,33 @@ impl Authority {
         Ok(())
     }
 
+    /// Start the revocation list issuer service to revoke members and
+    /// serve a signed list of the revoked subjects
+    pub async fn start_revocation_list_issuer(
+        &self,
+        ctx: &Context,
+        secure_channel_flow_control_id: &FlowControlId,
+    ) -> Result<()> {
+        let identities = self.secure_channels.identities();
+        let issuer = RevocationListIssuerWorker::new(
+            self.members.clone(),
+            identities.identities_attributes(),
+            identities.credentials(),
+            identities.revocation_repository(),
+            &self.identifier,
+            self.account_authority.clone(),
+        );
+
+        let address = DefaultAddress::REVOCATION_LIST_ISSUER.to_string();
+        ctx.flow_controls()
+            .add_consumer(address.clone(), secure_channel_flow_control_id);
+
+        ctx.start_worker(address.clone(), issuer).await?;
+
+        info!("started a revocation list issuer at '{address}'");
+        Ok(())
+    }
+
     /// Start the Okta service to retrieve attributes authenticated by Okta
     pub async fn start_okta(
         &self,
@@ -11,7 +11,7 @@ use ockam::identity::Identifier;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::Error;
 use ockam_multiaddr::MultiAddr;
-use ockam_transport_tcp::TcpListener;
+use ockam_transport_tcp::{TcpInletTls, TcpListener};
 
 use crate::cli_state::{random_name, NamedVault, Result};
 use crate::cli_state::{CliState, CliStateError};
@@ -317,6 +317,21 @@ impl CliState {
             ))?)
     }
 
+    /// Return the PEM-encoded private key of the self-signed certificate served by the
+    /// TLS inlets of a node, creating it the first time
+    #[instrument(skip_all, fields(node_name = node_name))]
+    pub async fn get_or_create_node_tls_private_key(&self, node_name: &str) -> Result<String> {
+        let repository = self.nodes_repository();
+        if let Some(private_key) = repository.get_node_tls_private_key(node_name).await? {
+            return Ok(private_key);
+        }
+        let private_key = TcpInletTls::generate_private_key()?;
+        repository
+            .set_node_tls_private_key(node_name, &private_key)
+            .await?;
+        Ok(private_key)
+    }
+
     /// Return the node information for the given node name, otherwise for the default node
     #[instrument(skip_all, fields(node_name = node_name.clone()))]
     pub async fn get_node_or_default(&self, node_name: &Option<String>) -> Result<NodeInfo> {
@@ -670,6 +685,23 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_node_tls_private_key() -> Result<()> {
+        let cli = CliState::test().await?;
+        let node_name = "node-1";
+        cli.create_node(node_name).await?;
+
+        // the private key of the TLS inlets is created once
+        let private_key = cli.get_or_create_node_tls_private_key(node_name).await?;
+        let result = cli.get_or_create_node_tls_private_key(node_name).await?;
+        assert_eq!(result, private_key);
+
+        // and it is different for each node
+        let result = cli.get_or_create_node_tls_private_key("node-2").await?;
+        assert_ne!(result, private_key);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_create_node_with_optional_values() -> Result<()> {
         let cli = CliState::test().await?;
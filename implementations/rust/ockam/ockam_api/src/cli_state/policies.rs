@@ -1,5 +1,8 @@
 use crate::cli_state::CliState;
-use ockam_abac::{Policies, ResourcePolicySqlxDatabase, ResourceTypePolicySqlxDatabase};
+use ockam_abac::{
+    Policies, PolicyDecisionSqlxDatabase, ResourcePolicySqlxDatabase,
+    ResourceTypePolicySqlxDatabase,
+};
 use std::sync::Arc;
 
 impl CliState {
@@ -10,6 +13,7 @@ impl CliState {
                 self.database(),
                 node_name,
             )),
+            Arc::new(PolicyDecisionSqlxDatabase::new(self.database(), node_name)),
         )
     }
 }
@@ -12,6 +12,7 @@ use crate::cli_state::{EnrollmentsRepository, EnrollmentsSqlxDatabase};
 use crate::cli_state::{ProjectsRepository, ProjectsSqlxDatabase};
 use crate::cli_state::{SpacesRepository, SpacesSqlxDatabase};
 use crate::cli_state::{UsersRepository, UsersSqlxDatabase};
+use crate::message_queues::{MessageQueuesRepository, MessageQueuesSqlxDatabase};
 
 /// These functions create repository implementations to access data
 /// stored in the database
@@ -63,4 +64,8 @@ impl CliState {
     pub fn cached_credentials_repository(&self, node_name: &str) -> Arc<dyn CredentialRepository> {
         Arc::new(CredentialSqlxDatabase::new(self.database(), node_name))
     }
+
+    pub fn message_queues_repository(&self, node_name: &str) -> Arc<dyn MessageQueuesRepository> {
+        Arc::new(MessageQueuesSqlxDatabase::new(self.database(), node_name))
+    }
 }
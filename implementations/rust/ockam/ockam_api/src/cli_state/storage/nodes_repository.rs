@@ -12,6 +12,7 @@ use crate::config::lookup::InternetAddress;
 ///  - a node can be associated to a (single) project
 ///  - when a node is running we can persist its process id and its TCP listener address
 ///  - one of the nodes is always set as the default node
+///  - a node can have a private key for the self-signed certificate of its TLS inlets
 ///  - a node can be set as an authority node. The purpose of this flag is to be able to display
 ///    the node status without being able to start a TCP connection since the TCP listener might not be accessible
 ///
@@ -65,4 +66,10 @@ pub trait NodesRepository: Send + Sync + 'static {
 
     /// Return the name of the project associated to a node
     async fn get_node_project_name(&self, node_name: &str) -> Result<Option<String>>;
+
+    /// Store the PEM-encoded private key of the self-signed certificate of the TLS inlets of a node
+    async fn set_node_tls_private_key(&self, node_name: &str, private_key: &str) -> Result<()>;
+
+    /// Return the PEM-encoded private key of the self-signed certificate of the TLS inlets of a node
+    async fn get_node_tls_private_key(&self, node_name: &str) -> Result<Option<String>>;
 }
@@ -124,6 +124,14 @@ impl NodesRepository for NodesSqlxDatabase {
             .bind(node_name.to_sql());
         query.execute(&mut *transaction).await.void()?;
 
+        let query =
+            sqlx::query("DELETE FROM policy_decision WHERE node_name=?").bind(node_name.to_sql());
+        query.execute(&mut *transaction).await.void()?;
+
+        let query =
+            sqlx::query("DELETE FROM queued_message WHERE node_name=?").bind(node_name.to_sql());
+        query.execute(&mut *transaction).await.void()?;
+
         let query = sqlx::query("DELETE FROM identity_attributes WHERE node_name=?")
             .bind(node_name.to_sql());
         query.execute(&mut *transaction).await.void()?;
@@ -132,6 +140,10 @@ impl NodesRepository for NodesSqlxDatabase {
             sqlx::query("DELETE FROM node_project WHERE node_name=?").bind(node_name.to_sql());
         query.execute(&mut *transaction).await.void()?;
 
+        let query =
+            sqlx::query("DELETE FROM node_tls_key WHERE node_name=?").bind(node_name.to_sql());
+        query.execute(&mut *transaction).await.void()?;
+
         transaction.commit().await.void()
     }
 
@@ -189,6 +201,23 @@ impl NodesRepository for NodesSqlxDatabase {
         let project_name: Option<String> = row.map(|r| r.get(0));
         Ok(project_name)
     }
+
+    async fn set_node_tls_private_key(&self, node_name: &str, private_key: &str) -> Result<()> {
+        let query = query("INSERT OR REPLACE INTO node_tls_key VALUES (?1, ?2)")
+            .bind(node_name.to_sql())
+            .bind(private_key.to_sql());
+        query.execute(&*self.database.pool).await.void()
+    }
+
+    async fn get_node_tls_private_key(&self, node_name: &str) -> Result<Option<String>> {
+        let query = query("SELECT private_key FROM node_tls_key WHERE node_name = ?")
+            .bind(node_name.to_sql());
+        let row: Option<SqliteRow> = query
+            .fetch_optional(&*self.database.pool)
+            .await
+            .into_core()?;
+        Ok(row.map(|r| r.get(0)))
+    }
 }
 
 // Database serialization / deserialization
@@ -330,6 +359,27 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_node_tls_private_key() -> Result<()> {
+        let repository = create_repository().await?;
+
+        // a node can store the private key of its TLS inlets
+        let result = repository.get_node_tls_private_key("node_name").await?;
+        assert_eq!(result, None);
+        repository
+            .set_node_tls_private_key("node_name", "private key")
+            .await?;
+        let result = repository.get_node_tls_private_key("node_name").await?;
+        assert_eq!(result, Some("private key".into()));
+
+        // the key is deleted with the node
+        repository.delete_node("node_name").await?;
+        let result = repository.get_node_tls_private_key("node_name").await?;
+        assert_eq!(result, None);
+
+        Ok(())
+    }
+
     /// HELPERS
     async fn create_repository() -> Result<Arc<dyn NodesRepository>> {
         Ok(Arc::new(NodesSqlxDatabase::create().await?))
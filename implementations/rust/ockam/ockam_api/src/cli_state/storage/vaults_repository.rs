@@ -1,19 +1,27 @@
 use std::path::Path;
 
-use crate::cli_state::NamedVault;
+use crate::cli_state::{NamedVault, VaultType};
 use ockam_core::async_trait;
 use ockam_core::Result;
 
 /// This trait allows vaults to be defined with a name and a path
-/// in order to make it possible to store identity keys in different databases on disk (or in a KMS)
+/// in order to make it possible to store identity keys in different databases on disk (or in a KMS, or in a PKCS#11 token)
 #[async_trait]
 pub trait VaultsRepository: Send + Sync + 'static {
     /// Store a new vault path with an associated name
-    async fn store_vault(&self, name: &str, path: &Path, is_kms: bool) -> Result<NamedVault>;
+    async fn store_vault(
+        &self,
+        name: &str,
+        path: &Path,
+        vault_type: VaultType,
+    ) -> Result<NamedVault>;
 
     /// Update a vault path
     async fn update_vault(&self, name: &str, path: &Path) -> Result<()>;
 
+    /// Record that the secrets of a vault are encrypted at rest
+    async fn set_vault_as_encrypted(&self, name: &str) -> Result<()>;
+
     /// Delete a vault given its name
     async fn delete_named_vault(&self, name: &str) -> Result<()>;
 
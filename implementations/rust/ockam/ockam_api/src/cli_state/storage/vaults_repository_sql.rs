@@ -3,7 +3,7 @@ use std::str::FromStr;
 
 use sqlx::*;
 
-use crate::cli_state::{NamedVault, VaultsRepository};
+use crate::cli_state::{NamedVault, Pkcs11Token, VaultType, VaultsRepository};
 use ockam::{FromSqlxError, SqlxDatabase, ToSqlxType, ToVoid};
 use ockam_core::async_trait;
 use ockam_core::Result;
@@ -27,15 +27,27 @@ impl VaultsSqlxDatabase {
 
 #[async_trait]
 impl VaultsRepository for VaultsSqlxDatabase {
-    async fn store_vault(&self, name: &str, path: &Path, is_kms: bool) -> Result<NamedVault> {
-        let query = query("INSERT INTO vault VALUES (?1, ?2, ?3, ?4)")
+    async fn store_vault(
+        &self,
+        name: &str,
+        path: &Path,
+        vault_type: VaultType,
+    ) -> Result<NamedVault> {
+        let pkcs11_token = match &vault_type {
+            VaultType::Pkcs11(token) => Some(token),
+            _ => None,
+        };
+        let query = query("INSERT INTO vault VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")
             .bind(name.to_sql())
             .bind(path.to_sql())
             .bind(true.to_sql())
-            .bind(is_kms.to_sql());
+            .bind((vault_type == VaultType::AwsKms).to_sql())
+            .bind(pkcs11_token.map(|t| t.module_path.to_sql()))
+            .bind(pkcs11_token.map(|t| t.token_label.to_sql()))
+            .bind(false.to_sql());
         query.execute(&*self.database.pool).await.void()?;
 
-        Ok(NamedVault::new(name, path.into(), is_kms))
+        Ok(NamedVault::new(name, path.into(), vault_type))
     }
 
     async fn update_vault(&self, name: &str, path: &Path) -> Result<()> {
@@ -45,6 +57,13 @@ impl VaultsRepository for VaultsSqlxDatabase {
         query.execute(&*self.database.pool).await.void()
     }
 
+    async fn set_vault_as_encrypted(&self, name: &str) -> Result<()> {
+        let query = query("UPDATE vault SET is_encrypted=$1 WHERE name=$2")
+            .bind(true.to_sql())
+            .bind(name.to_sql());
+        query.execute(&*self.database.pool).await.void()
+    }
+
     /// Delete a vault by name
     async fn delete_named_vault(&self, name: &str) -> Result<()> {
         let query = query("DELETE FROM vault WHERE name=?").bind(name.to_sql());
@@ -53,7 +72,7 @@ impl VaultsRepository for VaultsSqlxDatabase {
 
     async fn get_named_vault(&self, name: &str) -> Result<Option<NamedVault>> {
         let query =
-            query_as("SELECT name, path, is_kms FROM vault WHERE name = $1").bind(name.to_sql());
+            query_as("SELECT name, path, is_kms, pkcs11_module, pkcs11_token_label, is_encrypted FROM vault WHERE name = $1").bind(name.to_sql());
         let row: Option<VaultRow> = query
             .fetch_optional(&*self.database.pool)
             .await
@@ -63,7 +82,7 @@ impl VaultsRepository for VaultsSqlxDatabase {
 
     async fn get_named_vault_with_path(&self, path: &Path) -> Result<Option<NamedVault>> {
         let query =
-            query_as("SELECT name, path, is_kms FROM vault WHERE path = $1").bind(path.to_sql());
+            query_as("SELECT name, path, is_kms, pkcs11_module, pkcs11_token_label, is_encrypted FROM vault WHERE path = $1").bind(path.to_sql());
         let row: Option<VaultRow> = query
             .fetch_optional(&*self.database.pool)
             .await
@@ -72,7 +91,9 @@ impl VaultsRepository for VaultsSqlxDatabase {
     }
 
     async fn get_named_vaults(&self) -> Result<Vec<NamedVault>> {
-        let query = query_as("SELECT name, path, is_kms FROM vault");
+        let query = query_as(
+            "SELECT name, path, is_kms, pkcs11_module, pkcs11_token_label, is_encrypted FROM vault",
+        );
         let rows: Vec<VaultRow> = query.fetch_all(&*self.database.pool).await.into_core()?;
         rows.iter().map(|r| r.named_vault()).collect()
     }
@@ -85,15 +106,27 @@ pub(crate) struct VaultRow {
     name: String,
     path: String,
     is_kms: bool,
+    pkcs11_module: Option<String>,
+    pkcs11_token_label: Option<String>,
+    is_encrypted: bool,
 }
 
 impl VaultRow {
     pub(crate) fn named_vault(&self) -> Result<NamedVault> {
+        let vault_type = match (&self.pkcs11_module, &self.pkcs11_token_label) {
+            (Some(module), Some(token_label)) => VaultType::Pkcs11(Pkcs11Token::new(
+                PathBuf::from_str(module.as_str()).unwrap(),
+                token_label,
+            )),
+            _ if self.is_kms => VaultType::AwsKms,
+            _ => VaultType::DatabaseVault,
+        };
         Ok(NamedVault::new(
             &self.name,
             PathBuf::from_str(self.path.as_str()).unwrap(),
-            self.is_kms,
-        ))
+            vault_type,
+        )
+        .with_encryption(self.is_encrypted))
     }
 }
 
@@ -108,14 +141,15 @@ mod test {
 
         // A vault can be defined with a path and stored under a specific name
         let named_vault1 = repository
-            .store_vault("vault1", Path::new("path"), false)
+            .store_vault("vault1", Path::new("path"), VaultType::DatabaseVault)
             .await?;
-        let expected = NamedVault::new("vault1", Path::new("path").into(), false);
+        let expected =
+            NamedVault::new("vault1", Path::new("path").into(), VaultType::DatabaseVault);
         assert_eq!(named_vault1, expected);
 
         // A vault with the same name can not be created twice
         let result = repository
-            .store_vault("vault1", Path::new("path"), false)
+            .store_vault("vault1", Path::new("path"), VaultType::DatabaseVault)
             .await;
         assert!(result.is_err());
 
@@ -136,9 +170,18 @@ mod test {
         let result = repository.get_named_vault("vault1").await?;
         assert_eq!(
             result,
-            Some(NamedVault::new("vault1", Path::new("path2").into(), false))
+            Some(NamedVault::new(
+                "vault1",
+                Path::new("path2").into(),
+                VaultType::DatabaseVault
+            ))
         );
 
+        // The vault can be marked as encrypted
+        repository.set_vault_as_encrypted("vault1").await?;
+        let result = repository.get_named_vault("vault1").await?;
+        assert_eq!(result.map(|v| v.is_encrypted()), Some(true));
+
         // The vault can also be deleted
         repository.delete_named_vault("vault1").await?;
         let result = repository.get_named_vault("vault1").await?;
@@ -152,13 +195,35 @@ mod test {
 
         // A KMS vault can be created by setting the kms flag to true
         let kms = repository
-            .store_vault("kms", Path::new("path"), true)
+            .store_vault("kms", Path::new("path"), VaultType::AwsKms)
             .await?;
-        let expected = NamedVault::new("kms", Path::new("path").into(), true);
+        let expected = NamedVault::new("kms", Path::new("path").into(), VaultType::AwsKms);
         assert_eq!(kms, expected);
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_store_pkcs11_vault() -> Result<()> {
+        let repository = create_repository().await?;
+
+        // A PKCS#11 vault is stored with the module and the label of its token
+        let token = Pkcs11Token::new(PathBuf::from("/usr/lib/softhsm/libsofthsm2.so"), "ockam");
+        let pkcs11 = repository
+            .store_vault(
+                "pkcs11",
+                Path::new("path"),
+                VaultType::Pkcs11(token.clone()),
+            )
+            .await?;
+        let expected =
+            NamedVault::new("pkcs11", Path::new("path").into(), VaultType::Pkcs11(token));
+        assert_eq!(pkcs11, expected);
+
+        let result = repository.get_named_vault("pkcs11").await?;
+        assert_eq!(result, Some(expected));
+        Ok(())
+    }
+
     /// HELPERS
     async fn create_repository() -> Result<Arc<dyn VaultsRepository>> {
         Ok(Arc::new(VaultsSqlxDatabase::create().await?))
@@ -6,9 +6,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use ockam::identity::{Identities, Vault};
+use ockam_core::compat::rand::{thread_rng, RngCore};
 use ockam_core::errcode::{Kind, Origin};
 use ockam_node::database::SqlxDatabase;
+use ockam_vault::storage::{SecretsKeySource, SecretsSqlxDatabase};
 use ockam_vault_aws::AwsSigningVault;
+use ockam_vault_pkcs11::{Pkcs11Config, Pkcs11SigningVault};
 
 use crate::cli_state::{random_name, CliState, CliStateError, Result};
 use crate::output::Output;
@@ -16,11 +19,22 @@ use crate::{fmt_log, fmt_ok};
 
 static DEFAULT_VAULT_NAME: &str = "default";
 
+/// Environment variable containing the passphrase used to encrypt the secrets of a vault
+pub const OCKAM_VAULT_PASSPHRASE: &str = "OCKAM_VAULT_PASSPHRASE";
+
+/// Environment variable containing the path of a file storing the hex-encoded 256 bits key
+/// used to encrypt the secrets of a vault
+pub const OCKAM_VAULT_KEY_FILE: &str = "OCKAM_VAULT_KEY_FILE";
+
+/// Name of the OS keyring service used to store the keys encrypting vault secrets
+const KEYRING_SERVICE: &str = "ockam";
+
 /// The methods below support the creation and update of local vaults
 ///
-///  - by default private keys are stored locally but they can also be stored in a KMS
+///  - by default private keys are stored locally but they can also be stored in a KMS or in a PKCS#11 token
 ///  - keys stored locally are stored with other application data in the local database if the default vault is used
 ///  - any additional vault stores its keys in a separate file
+///  - the secrets stored locally can be encrypted at rest
 ///
 impl CliState {
     /// Create a vault with a given name
@@ -33,7 +47,8 @@ impl CliState {
         vault_name: &Option<String>,
         path: &Option<PathBuf>,
     ) -> Result<NamedVault> {
-        self.create_a_vault(vault_name, path, false).await
+        self.create_a_vault(vault_name, path, VaultType::DatabaseVault)
+            .await
     }
 
     /// Create a KMS vault with a given name
@@ -46,7 +61,50 @@ impl CliState {
         vault_name: &Option<String>,
         path: &Option<PathBuf>,
     ) -> Result<NamedVault> {
-        self.create_a_vault(vault_name, path, true).await
+        self.create_a_vault(vault_name, path, VaultType::AwsKms)
+            .await
+    }
+
+    /// Create a vault with a given name, storing its signing keys in a PKCS#11 token.
+    /// The secure channel keys are still persisted in a database, at the given path or at a default path
+    #[instrument(skip_all, fields(vault_name = vault_name.clone(), token_label = token.token_label))]
+    pub async fn create_pkcs11_vault(
+        &self,
+        vault_name: &Option<String>,
+        path: &Option<PathBuf>,
+        token: Pkcs11Token,
+    ) -> Result<NamedVault> {
+        self.create_a_vault(vault_name, path, VaultType::Pkcs11(token))
+            .await
+    }
+
+    /// Encrypt the secrets of an existing vault.
+    ///
+    /// This is used both when creating a new encrypted vault and to migrate an existing vault.
+    /// The secrets are encrypted with a key which is, in order of preference:
+    ///
+    ///  - derived from the passphrase set in the OCKAM_VAULT_PASSPHRASE environment variable
+    ///  - read from the file set in the OCKAM_VAULT_KEY_FILE environment variable
+    ///  - read from the OS keyring. If no key is present, a random one is created and stored there
+    ///
+    /// The same key must then be available when the vault is opened, for example when a node starts.
+    #[instrument(skip_all, fields(vault_name = vault_name))]
+    pub async fn encrypt_named_vault(&self, vault_name: &str) -> Result<NamedVault> {
+        let vault = self.get_named_vault(vault_name).await?;
+        if vault.is_encrypted() {
+            return Err(ockam_core::Error::new(
+                Origin::Api,
+                Kind::Invalid,
+                format!("the secrets of the vault {vault_name} are already encrypted"),
+            ))?;
+        }
+
+        let key_source = vault_key_source(vault_name, true)?;
+        SecretsSqlxDatabase::create_encrypted(vault.database().await?, key_source).await?;
+        self.vaults_repository()
+            .set_vault_as_encrypted(vault_name)
+            .await?;
+        Ok(vault.with_encryption(true))
     }
 
     /// Delete an existing vault
@@ -86,6 +144,7 @@ impl CliState {
                 // otherwise delete the tables used by the database vault
                 self.purpose_keys_repository().delete_all().await?;
                 self.secrets_repository().delete_all().await?;
+                SecretsSqlxDatabase::delete_encryption_key(&self.database()).await?;
             }
         }
         Ok(())
@@ -154,7 +213,11 @@ impl CliState {
             "There is no default Vault on this machine, creating one..."
         ));
         let named_vault = self
-            .create_a_vault(&Some(vault_name.to_string()), &None, false)
+            .create_a_vault(
+                &Some(vault_name.to_string()),
+                &None,
+                VaultType::DatabaseVault,
+            )
             .await?;
         self.notify_message(fmt_ok!("Created a new Vault on your disk."));
         if is_default {
@@ -232,7 +295,7 @@ impl CliState {
 
 /// Private functions
 impl CliState {
-    /// Create a vault with the given name and indicate if it is going to be used as a KMS vault
+    /// Create a vault with the given name and indicate where its signing keys are stored
     /// If the vault with the same name already exists then an error is returned
     /// If there is already a file at the provided path, then an error is returned
     #[instrument(skip_all, fields(vault_name = vault_name))]
@@ -240,7 +303,7 @@ impl CliState {
         &self,
         vault_name: &Option<String>,
         path: &Option<PathBuf>,
-        is_kms: bool,
+        vault_type: VaultType,
     ) -> Result<NamedVault> {
         let vaults_repository = self.vaults_repository();
 
@@ -291,7 +354,7 @@ impl CliState {
 
         // store the vault metadata
         Ok(vaults_repository
-            .store_vault(&vault_name, &path, is_kms)
+            .store_vault(&vault_name, &path, vault_type)
             .await?)
     }
 
@@ -337,20 +400,72 @@ impl CliState {
     }
 }
 
+/// Location of the signing keys of a vault
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+pub enum VaultType {
+    /// The keys are stored in the vault database
+    DatabaseVault,
+    /// The keys are stored in AWS KMS
+    AwsKms,
+    /// The keys are stored in a PKCS#11 token
+    Pkcs11(Pkcs11Token),
+}
+
+impl Display for VaultType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultType::DatabaseVault => write!(f, "OCKAM"),
+            VaultType::AwsKms => write!(f, "AWS KMS"),
+            VaultType::Pkcs11(token) => write!(f, "PKCS#11 (token: {})", token.token_label),
+        }
+    }
+}
+
+/// PKCS#11 token used by a vault.
+/// The user PIN is not persisted, it is read from the OCKAM_PKCS11_PIN environment variable
+/// when the vault is opened
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Pkcs11Token {
+    /// Path of the PKCS#11 module to load
+    pub module_path: PathBuf,
+    /// Label of the token storing the keys
+    pub token_label: String,
+}
+
+impl Pkcs11Token {
+    /// Create a new PKCS#11 token reference
+    pub fn new(module_path: PathBuf, token_label: &str) -> Self {
+        Self {
+            module_path,
+            token_label: token_label.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NamedVault {
     name: String,
     path: PathBuf,
-    is_kms: bool,
+    vault_type: VaultType,
+    is_encrypted: bool,
 }
 
 impl NamedVault {
     /// Create a new named vault
-    pub fn new(name: &str, path: PathBuf, is_kms: bool) -> Self {
+    pub fn new(name: &str, path: PathBuf, vault_type: VaultType) -> Self {
         Self {
             name: name.to_string(),
             path,
-            is_kms,
+            vault_type,
+            is_encrypted: false,
+        }
+    }
+
+    /// Set the encryption status of the vault secrets
+    pub fn with_encryption(self, is_encrypted: bool) -> Self {
+        Self {
+            is_encrypted,
+            ..self
         }
     }
 
@@ -371,19 +486,48 @@ impl NamedVault {
 
     /// Return true if this vault is a KMS vault
     pub fn is_kms(&self) -> bool {
-        self.is_kms
+        self.vault_type == VaultType::AwsKms
+    }
+
+    /// Return the location of the vault signing keys
+    pub fn vault_type(&self) -> &VaultType {
+        &self.vault_type
     }
 
+    /// Return true if the secrets stored in the vault database are encrypted at rest
+    pub fn is_encrypted(&self) -> bool {
+        self.is_encrypted
+    }
+
+    /// Open the vault.
+    /// If the vault secrets are encrypted, they are unlocked with the key
+    /// configured for this vault, see [`CliState::encrypt_named_vault`]
     pub async fn vault(&self) -> Result<Vault> {
-        let mut vault = Vault::create_with_database(self.database().await?);
-        if self.is_kms {
-            let aws_vault = Arc::new(AwsSigningVault::create().await?);
-            vault.identity_vault = aws_vault.clone();
-            vault.credential_vault = aws_vault;
-            Ok(vault)
+        let database = self.database().await?;
+        let mut vault = if self.is_encrypted {
+            let key_source = vault_key_source(&self.name, false)?;
+            let secrets_repository =
+                SecretsSqlxDatabase::create_encrypted(database, key_source).await?;
+            Vault::create_with_secrets_repository(Arc::new(secrets_repository))
         } else {
-            Ok(vault)
+            Vault::create_with_database(database)
+        };
+        match &self.vault_type {
+            VaultType::DatabaseVault => (),
+            VaultType::AwsKms => {
+                let aws_vault = Arc::new(AwsSigningVault::create().await?);
+                vault.identity_vault = aws_vault.clone();
+                vault.credential_vault = aws_vault;
+            }
+            VaultType::Pkcs11(token) => {
+                let config =
+                    Pkcs11Config::new(&token.module_path, &token.token_label).with_pin_from_env();
+                let pkcs11_vault = Arc::new(Pkcs11SigningVault::create_with_config(config).await?);
+                vault.identity_vault = pkcs11_vault.clone();
+                vault.credential_vault = pkcs11_vault;
+            }
         }
+        Ok(vault)
     }
 
     async fn database(&self) -> Result<SqlxDatabase> {
@@ -395,14 +539,8 @@ impl NamedVault {
 impl Display for NamedVault {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Name: {}", self.name)?;
-        writeln!(
-            f,
-            "Type: {}",
-            match self.is_kms {
-                true => "AWS KMS",
-                false => "OCKAM",
-            }
-        )?;
+        writeln!(f, "Type: {}", self.vault_type)?;
+        writeln!(f, "Encrypted: {}", self.is_encrypted)?;
         Ok(())
     }
 }
@@ -411,18 +549,71 @@ impl Output for NamedVault {
     fn single(&self) -> crate::Result<String> {
         let mut output = String::new();
         writeln!(output, "Name: {}", self.name())?;
-        writeln!(
-            output,
-            "Type: {}",
-            match self.is_kms() {
-                true => "AWS KMS",
-                false => "OCKAM",
-            }
-        )?;
+        writeln!(output, "Type: {}", self.vault_type)?;
+        writeln!(output, "Encrypted: {}", self.is_encrypted)?;
         Ok(output)
     }
 }
 
+/// Return the source of the key used to encrypt the secrets of a vault.
+/// See [`CliState::encrypt_named_vault`] for the list of possible sources.
+/// If `create_if_missing` is true and there is no key for the vault in the OS keyring,
+/// a random key is created and stored in the keyring
+fn vault_key_source(vault_name: &str, create_if_missing: bool) -> Result<SecretsKeySource> {
+    if let Ok(passphrase) = std::env::var(OCKAM_VAULT_PASSPHRASE) {
+        return Ok(SecretsKeySource::Passphrase(passphrase));
+    }
+
+    if let Ok(key_file) = std::env::var(OCKAM_VAULT_KEY_FILE) {
+        let key = std::fs::read_to_string(&key_file)?;
+        return Ok(SecretsKeySource::Key(decode_key(key.trim(), &key_file)?));
+    }
+
+    let entry = keyring::Entry::new(KEYRING_SERVICE, &format!("vault-{vault_name}"))
+        .map_err(keyring_error)?;
+    match entry.get_password() {
+        Ok(key) => Ok(SecretsKeySource::Key(decode_key(&key, "the OS keyring")?)),
+        Err(keyring::Error::NoEntry) if create_if_missing => {
+            let mut key = [0u8; 32];
+            thread_rng().fill_bytes(&mut key);
+            entry
+                .set_password(&hex::encode(key))
+                .map_err(keyring_error)?;
+            Ok(SecretsKeySource::Key(key))
+        }
+        Err(keyring::Error::NoEntry) => Err(CliStateError::from(ockam_core::Error::new(
+            Origin::Api,
+            Kind::NotFound,
+            format!(
+                "the secrets of the vault {vault_name} are encrypted but no key was found. Please set the {OCKAM_VAULT_PASSPHRASE} or the {OCKAM_VAULT_KEY_FILE} environment variable"
+            ),
+        ))),
+        Err(e) => Err(keyring_error(e).into()),
+    }
+}
+
+/// Decode a hex-encoded 256 bits key
+fn decode_key(key: &str, location: &str) -> Result<[u8; 32]> {
+    let key = hex::decode(key)
+        .ok()
+        .and_then(|k| <[u8; 32]>::try_from(k).ok());
+    Ok(key.ok_or_else(|| {
+        ockam_core::Error::new(
+            Origin::Api,
+            Kind::Invalid,
+            format!("the vault key stored in {location} must be a hex-encoded 32 bytes key"),
+        )
+    })?)
+}
+
+fn keyring_error(e: keyring::Error) -> ockam_core::Error {
+    ockam_core::Error::new(
+        Origin::Api,
+        Kind::Io,
+        format!("cannot access the OS keyring: {e}"),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
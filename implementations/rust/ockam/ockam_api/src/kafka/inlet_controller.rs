@@ -3,7 +3,7 @@ use minicbor::Decoder;
 use ockam_core::compat::net::IpAddr;
 
 use ockam::compat::tokio::sync::Mutex;
-use ockam_abac::Expr;
+use ockam_abac::{Expr, ResourceType};
 use ockam_core::api::{Request, ResponseHeader, Status};
 use ockam_core::compat::collections::HashMap;
 use ockam_core::compat::net::SocketAddr;
@@ -130,6 +130,7 @@ impl KafkaInletController {
             None,
             true,
         );
+        payload.set_resource_type(ResourceType::KafkaInlet);
         if let Some(expr) = policy_expression {
             payload.set_policy_expression(expr);
         }
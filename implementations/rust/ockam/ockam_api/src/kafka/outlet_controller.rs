@@ -3,7 +3,7 @@ use crate::nodes::models::portal::{CreateOutlet, OutletStatus};
 use crate::nodes::NODEMANAGER_ADDR;
 use minicbor::Decoder;
 use ockam::compat::tokio::sync::Mutex;
-use ockam_abac::Expr;
+use ockam_abac::{Expr, ResourceType};
 use ockam_core::api::{Request, ResponseHeader, Status};
 use ockam_core::compat::collections::HashMap;
 use ockam_core::compat::sync::Arc;
@@ -72,6 +72,7 @@ impl KafkaOutletController {
     ) -> Result<SocketAddr> {
         let hostname_port = HostnamePort::from_socket_addr(socket_address)?;
         let mut payload = CreateOutlet::new(hostname_port, false, Some(worker_address), false);
+        payload.set_resource_type(ResourceType::KafkaOutlet);
         if let Some(expr) = policy_expression {
             payload.set_policy_expression(expr);
         }
@@ -2,10 +2,8 @@ use crate::kafka::outlet_controller::KafkaOutletController;
 use crate::kafka::portal_worker::KafkaPortalWorker;
 use crate::kafka::protocol_aware::OutletInterceptorImpl;
 use crate::kafka::KAFKA_OUTLET_INTERCEPTOR_ADDRESS;
-use ockam::identity::{Identifier, SecureChannels};
 use ockam::{Any, Context, Result, Routed, Worker};
 use ockam_abac::Expr;
-use ockam_abac::IncomingAbac;
 use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
 use ockam_core::{Address, IncomingAccessControl};
 use ockam_node::WorkerBuilder;
@@ -24,8 +22,7 @@ pub(crate) struct OutletManagerService {
 impl OutletManagerService {
     pub(crate) async fn create(
         context: &Context,
-        secure_channels: Arc<SecureChannels>,
-        authority_identifier: Identifier,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
         default_secure_channel_listener_flow_control_id: FlowControlId,
         policy_expression: Option<Expr>,
     ) -> Result<()> {
@@ -42,14 +39,10 @@ impl OutletManagerService {
 
         flow_controls.add_spawner(worker_address.clone(), &spawner_flow_control_id);
 
-        let abac = IncomingAbac::check_credential_only(
-            secure_channels.identities().identities_attributes(),
-            authority_identifier,
-        );
         // TOOD: Should we add outgoing?
         let worker = OutletManagerService {
             outlet_controller: KafkaOutletController::new(policy_expression),
-            incoming_access_control: Arc::new(abac),
+            incoming_access_control,
             spawner_flow_control_id: spawner_flow_control_id.clone(),
         };
 
@@ -126,9 +126,22 @@ impl Worker for KafkaPortalWorker {
                     context.stop_worker(context.address()).await?;
                 }
             }
-            PortalMessage::Ping => self.forward(context, routed_message).await?,
+            PortalMessage::Ping(_) => {
+                // The payloads are re-assembled and modified by the interceptor, so the windows
+                // of the flow control can't be accounted for: the flow control is disabled
+                // by removing the inlet window
+                let routed_message = Routed::new(
+                    routed_message.msg_addr(),
+                    routed_message.src_addr(),
+                    routed_message
+                        .into_local_message()
+                        .set_payload(PortalMessage::Ping(None).encode()?),
+                );
+                self.forward(context, routed_message).await?
+            }
+            PortalMessage::WindowUpdate(_) => self.forward(context, routed_message).await?,
 
-            PortalMessage::Pong => {
+            PortalMessage::Pong(_) => {
                 match self.receiving {
                     Receiving::Requests => {
                         // if we receive a pong message it means it must be from the other worker
@@ -501,25 +514,28 @@ mod test {
         context
             .send(
                 route![portal_inlet_address, context.address()],
-                PortalMessage::Ping.to_neutral_message()?,
+                PortalMessage::Ping(None).to_neutral_message()?,
             )
             .await?;
 
         let message = context.receive::<NeutralMessage>().await?;
         let return_route = message.return_route();
         let message = PortalMessage::decode(message.payload())?;
-        if let PortalMessage::Ping = message {
+        if let PortalMessage::Ping(None) = message {
         } else {
             panic!("invalid message type")
         }
 
         context
-            .send(return_route, PortalMessage::Pong.to_neutral_message()?)
+            .send(
+                return_route,
+                PortalMessage::Pong(None).to_neutral_message()?,
+            )
             .await?;
 
         let payload = context.receive::<NeutralMessage>().await?.into_payload();
         let message = PortalMessage::decode(&payload)?;
-        if let PortalMessage::Pong = message {
+        if let PortalMessage::Pong(None) = message {
         } else {
             panic!("invalid message type")
         }
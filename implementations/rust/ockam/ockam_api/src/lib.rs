@@ -29,6 +29,7 @@ pub mod enroll;
 pub mod error;
 pub mod hop;
 pub mod kafka;
+pub mod message_queues;
 pub mod minicbor_url;
 pub mod nodes;
 pub mod okta;
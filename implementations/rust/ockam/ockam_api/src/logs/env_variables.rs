@@ -86,6 +86,10 @@ pub(crate) const OCKAM_FOREGROUND_LOG_EXPORT_SCHEDULED_DELAY: &str =
 pub(crate) const OCKAM_BACKGROUND_LOG_EXPORT_SCHEDULED_DELAY: &str =
     "OCKAM_BACKGROUND_LOG_EXPORT_SCHEDULED_DELAY";
 
+/// Time between the export of the node metrics to the endpoint.
+/// Accepted values, see DurationVar. For example: 30s
+pub(crate) const OCKAM_METRIC_EXPORT_INTERVAL: &str = "OCKAM_METRIC_EXPORT_INTERVAL";
+
 ///
 /// OPENTELEMETRY COLLECTOR ERRORS CONFIGURATION
 ///
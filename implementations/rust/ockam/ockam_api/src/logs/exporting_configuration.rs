@@ -28,6 +28,8 @@ pub struct ExportingConfiguration {
     span_export_scheduled_delay: Duration,
     /// Maximum time to wait until sending the current batch of logs
     log_export_scheduled_delay: Duration,
+    /// Time between the export of the node metrics
+    metric_export_interval: Duration,
     /// Url of the OpenTelemetry collector
     opentelemetry_endpoint: Url,
     /// True if the user is an Ockam developer
@@ -66,6 +68,11 @@ impl ExportingConfiguration {
         self.span_export_scheduled_delay
     }
 
+    /// Return the time between the export of the node metrics
+    pub fn metric_export_interval(&self) -> Duration {
+        self.metric_export_interval
+    }
+
     /// Return the URL where to export spans and log records
     pub fn opentelemetry_endpoint(&self) -> Url {
         self.opentelemetry_endpoint.clone()
@@ -83,6 +90,7 @@ impl ExportingConfiguration {
             log_export_timeout: span_export_timeout()?,
             span_export_scheduled_delay: foreground_span_export_scheduled_delay()?,
             log_export_scheduled_delay: foreground_log_export_scheduled_delay()?,
+            metric_export_interval: metric_export_interval()?,
             opentelemetry_endpoint: opentelemetry_endpoint()?,
             is_ockam_developer: is_ockam_developer()?,
         })
@@ -99,6 +107,7 @@ impl ExportingConfiguration {
             log_export_timeout: log_export_timeout()?,
             span_export_scheduled_delay: background_span_export_scheduled_delay()?,
             log_export_scheduled_delay: background_log_export_scheduled_delay()?,
+            metric_export_interval: metric_export_interval()?,
             opentelemetry_endpoint: opentelemetry_endpoint()?,
             is_ockam_developer: is_ockam_developer()?,
         })
@@ -112,6 +121,7 @@ impl ExportingConfiguration {
             log_export_timeout: DEFAULT_EXPORT_TIMEOUT,
             span_export_scheduled_delay: DEFAULT_FOREGROUND_EXPORT_SCHEDULED_DELAY,
             log_export_scheduled_delay: DEFAULT_FOREGROUND_EXPORT_SCHEDULED_DELAY,
+            metric_export_interval: DEFAULT_METRIC_EXPORT_INTERVAL,
             opentelemetry_endpoint: Self::default_opentelemetry_endpoint()?,
             is_ockam_developer: is_ockam_developer()?,
         })
@@ -258,3 +268,8 @@ pub fn background_log_export_scheduled_delay() -> ockam_core::Result<Duration> {
         DEFAULT_BACKGROUND_EXPORT_SCHEDULED_DELAY,
     )
 }
+
+/// Return the delay between the export of the node metrics, defined by an environment variable
+pub fn metric_export_interval() -> ockam_core::Result<Duration> {
+    get_env_with_default(OCKAM_METRIC_EXPORT_INTERVAL, DEFAULT_METRIC_EXPORT_INTERVAL)
+}
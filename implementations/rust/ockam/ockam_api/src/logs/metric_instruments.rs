@@ -0,0 +1,98 @@
+use ockam::identity::{HANDSHAKES, HANDSHAKE_DURATION, HANDSHAKE_FAILURES, HANDSHAKE_TIMEOUTS};
+use ockam_node::metrics::{
+    MetricKind, MetricValue, MetricsRegistry, ACCESS_CONTROL_DENIALS, MAILBOX_DEPTH,
+    MESSAGES_ROUTED,
+};
+use ockam_transport_tcp::{
+    TCP_BYTES_RECEIVED, TCP_BYTES_SENT, TCP_PORTAL_SESSIONS, TCP_PORTAL_SESSIONS_STARTED,
+};
+use opentelemetry::metrics::Meter;
+use opentelemetry::KeyValue;
+
+/// Metrics of the global [`MetricsRegistry`] exported to the OpenTelemetry collector
+const EXPORTED_METRICS: &[(&str, MetricKind)] = &[
+    (MESSAGES_ROUTED, MetricKind::Counter),
+    (ACCESS_CONTROL_DENIALS, MetricKind::Counter),
+    (MAILBOX_DEPTH, MetricKind::Histogram),
+    (HANDSHAKES, MetricKind::Counter),
+    (HANDSHAKE_FAILURES, MetricKind::Counter),
+    (HANDSHAKE_TIMEOUTS, MetricKind::Counter),
+    (HANDSHAKE_DURATION, MetricKind::Histogram),
+    (TCP_BYTES_SENT, MetricKind::Counter),
+    (TCP_BYTES_RECEIVED, MetricKind::Counter),
+    (TCP_PORTAL_SESSIONS_STARTED, MetricKind::Counter),
+    (TCP_PORTAL_SESSIONS, MetricKind::Gauge),
+];
+
+/// Create observable instruments reading the metrics of the global [`MetricsRegistry`]
+/// every time they are collected by the meter provider.
+///
+/// Since OpenTelemetry has no observable histograms, a histogram `name` is exported as
+/// 2 counters: `name_count` and `name_sum`.
+pub(crate) fn register_metric_instruments(meter: &Meter) {
+    for (name, kind) in EXPORTED_METRICS {
+        let name = *name;
+        match kind {
+            MetricKind::Counter => {
+                meter
+                    .u64_observable_counter(name)
+                    .with_callback(move |observer| {
+                        observe_series(name, |value, attributes| {
+                            if let MetricValue::Counter(value) = value {
+                                observer.observe(*value, attributes)
+                            }
+                        })
+                    })
+                    .init();
+            }
+            MetricKind::Gauge => {
+                meter
+                    .i64_observable_gauge(name)
+                    .with_callback(move |observer| {
+                        observe_series(name, |value, attributes| {
+                            if let MetricValue::Gauge(value) = value {
+                                observer.observe(*value, attributes)
+                            }
+                        })
+                    })
+                    .init();
+            }
+            MetricKind::Histogram => {
+                meter
+                    .u64_observable_counter(format!("{name}_count"))
+                    .with_callback(move |observer| {
+                        observe_series(name, |value, attributes| {
+                            if let MetricValue::Histogram(histogram) = value {
+                                observer.observe(histogram.count, attributes)
+                            }
+                        })
+                    })
+                    .init();
+                meter
+                    .f64_observable_counter(format!("{name}_sum"))
+                    .with_callback(move |observer| {
+                        observe_series(name, |value, attributes| {
+                            if let MetricValue::Histogram(histogram) = value {
+                                observer.observe(histogram.sum, attributes)
+                            }
+                        })
+                    })
+                    .init();
+            }
+        }
+    }
+}
+
+/// Call `observe` with the current value and the attributes of each series of a metric
+fn observe_series(name: &str, observe: impl Fn(&MetricValue, &[KeyValue])) {
+    if let Some(family) = MetricsRegistry::global().family(name) {
+        for series in family.series {
+            let attributes: Vec<KeyValue> = series
+                .labels
+                .into_iter()
+                .map(|(label, value)| KeyValue::new(label, value))
+                .collect();
+            observe(&series.value, &attributes);
+        }
+    }
+}
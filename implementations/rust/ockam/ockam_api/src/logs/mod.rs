@@ -8,6 +8,7 @@
 //      - In a log file for a background node.
 //      - In the console for other commands.
 //   - If OCKAM_TRACING=true then, _additionally_, the spans and logs messages are sent to an OpenTelemetry collector.
+//     The node metrics are also periodically sent to the collector.
 ///
 mod current_span;
 mod default_values;
@@ -16,6 +17,7 @@ pub mod exporting_configuration;
 mod log_exporters;
 pub mod logging_configuration;
 mod logging_options;
+mod metric_instruments;
 pub mod setup;
 mod span_exporters;
 mod tracing_guard;
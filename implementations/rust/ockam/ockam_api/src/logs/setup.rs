@@ -1,4 +1,5 @@
 use gethostname::gethostname;
+use opentelemetry::metrics::MeterProvider;
 use opentelemetry::trace::TracerProvider;
 use opentelemetry::{global, KeyValue};
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
@@ -6,6 +7,8 @@ use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::export::logs::LogExporter;
 use opentelemetry_sdk::export::trace::SpanExporter;
 use opentelemetry_sdk::logs::{BatchLogProcessor, LoggerProvider};
+use opentelemetry_sdk::metrics::reader::{DefaultAggregationSelector, DefaultTemporalitySelector};
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::{BatchConfig, BatchConfigBuilder, BatchSpanProcessor};
 use opentelemetry_sdk::{self as sdk};
@@ -27,6 +30,7 @@ use tracing_subscriber::{fmt, layer::SubscriberExt, registry};
 use crate::cli_state::journeys::APP_NAME;
 use ockam_node::Executor;
 
+use crate::logs::metric_instruments::register_metric_instruments;
 use crate::logs::tracing_guard::TracingGuard;
 use crate::logs::{ExportingConfiguration, GlobalErrorHandler, LoggingConfiguration};
 use crate::logs::{LogFormat, OckamSpanExporter};
@@ -38,6 +42,8 @@ impl LoggingTracing {
     /// The app name is used to set an attribute on all events specifying if the event
     /// has been created by the cli or by a local node.
     ///
+    /// When spans are exported, the node metrics are periodically exported too.
+    ///
     /// The TracingGuard is used to flush all events when dropped.
     pub fn setup(
         logging_configuration: &LoggingConfiguration,
@@ -55,6 +61,7 @@ impl LoggingTracing {
                 app_name,
                 node_name,
             )
+            .with_meter_provider(create_meter_provider(app_name, exporting_configuration))
         } else if exporting_configuration.is_enabled() {
             Self::setup_tracing_only(
                 create_span_exporter(exporting_configuration),
@@ -63,6 +70,7 @@ impl LoggingTracing {
                 app_name,
                 node_name,
             )
+            .with_meter_provider(create_meter_provider(app_name, exporting_configuration))
         } else {
             Self::setup_local_logging_only(logging_configuration)
         }
@@ -215,6 +223,55 @@ fn create_span_exporter(
     .expect("can't create a span exporter")
 }
 
+/// Create an exporter for the node metrics
+// They are sent to an OpenTelemetry collector using gRPC
+fn create_metric_exporter(
+    exporting_configuration: &ExportingConfiguration,
+) -> opentelemetry_otlp::MetricsExporter {
+    let metric_export_timeout = exporting_configuration.span_export_timeout();
+    let endpoint = exporting_configuration.opentelemetry_endpoint().to_string();
+
+    Executor::execute_future(async move {
+        opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(endpoint)
+            .with_timeout(metric_export_timeout)
+            .with_metadata(get_otlp_headers())
+            .build_metrics_exporter(
+                Box::new(DefaultAggregationSelector::new()),
+                Box::new(DefaultTemporalitySelector::new()),
+            )
+            .expect("failed to create the metric exporter")
+    })
+    .expect("can't create a metric exporter")
+}
+
+/// Create a meter provider for OpenTelemetry
+/// The metrics of the global MetricsRegistry are exported periodically
+fn create_meter_provider(
+    app_name: &str,
+    exporting_configuration: &ExportingConfiguration,
+) -> SdkMeterProvider {
+    let app = app_name.to_string();
+    let metric_exporter = create_metric_exporter(exporting_configuration);
+    let metric_export_interval = exporting_configuration.metric_export_interval();
+    let metric_export_timeout = exporting_configuration.span_export_timeout();
+    Executor::execute_future(async move {
+        let reader = PeriodicReader::builder(metric_exporter, sdk::runtime::Tokio)
+            .with_interval(metric_export_interval)
+            .with_timeout(metric_export_timeout)
+            .build();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(reader)
+            .with_resource(make_resource(app))
+            .build();
+        register_metric_instruments(&provider.meter("ockam"));
+        global::set_meter_provider(provider.clone());
+        provider
+    })
+    .expect("Failed to build the meter provider")
+}
+
 /// Create the tracing layer for OpenTelemetry
 /// Spans are exported in batches
 fn create_opentelemetry_tracing_layer<
@@ -0,0 +1,25 @@
+use ockam_core::{async_trait, Result, Route};
+
+/// Connection of a [`MessageQueue`](crate::message_queues::MessageQueue) to the
+/// [`MessageQueueReceiver`](crate::message_queues::MessageQueueReceiver) of its destination node
+///
+/// The destination node is expected to be unreachable from time to time, so the queue
+/// disconnects when a delivery is not acknowledged, and connects again before the next attempt.
+#[async_trait]
+pub trait MessageQueueConnector: Send + 'static {
+    /// Return the route to the receiver, connecting to the destination node first if necessary
+    async fn connect(&mut self) -> Result<Route>;
+
+    /// Close the current connection, if any
+    async fn disconnect(&mut self);
+}
+
+/// A fixed route to a receiver, for example on the same node
+#[async_trait]
+impl MessageQueueConnector for Route {
+    async fn connect(&mut self) -> Result<Route> {
+        Ok(self.clone())
+    }
+
+    async fn disconnect(&mut self) {}
+}
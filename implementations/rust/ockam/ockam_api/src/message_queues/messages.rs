@@ -0,0 +1,80 @@
+use minicbor::{Decode, Encode};
+use ockam_core::compat::string::String;
+use ockam_core::compat::vec::Vec;
+use ockam_core::{Decodable, Encodable, Error, Route};
+
+/// Message sent by a message queue to the receiver of the destination node.
+/// It is sent again, with the same identifier, until it is acknowledged
+#[derive(Debug, Clone, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct QueuedMessageDelivery {
+    /// Name of the queue on the sending node
+    #[n(1)] pub queue_name: String,
+    /// Identifier of the message in the queue
+    #[n(2)] pub message_id: u64,
+    /// Address of the worker receiving the payload on the destination node
+    #[n(3)] pub destination: String,
+    #[cbor(with = "minicbor::bytes")]
+    #[n(4)] pub payload: Vec<u8>,
+}
+
+impl Encodable for QueuedMessageDelivery {
+    fn encode(self) -> Result<Vec<u8>, Error> {
+        minicbor::to_vec(self).map_err(Error::from)
+    }
+}
+
+impl Decodable for QueuedMessageDelivery {
+    fn decode(m: &[u8]) -> Result<Self, Error> {
+        minicbor::decode(m).map_err(Error::from)
+    }
+}
+
+impl ockam_core::Message for QueuedMessageDelivery {}
+
+/// Acknowledgement sent back by the receiver of the destination node once
+/// the payload of a [`QueuedMessageDelivery`] has been forwarded to its destination
+#[derive(Debug, Clone, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct QueuedMessageAck {
+    #[n(1)] pub message_id: u64,
+}
+
+impl Encodable for QueuedMessageAck {
+    fn encode(self) -> Result<Vec<u8>, Error> {
+        minicbor::to_vec(self).map_err(Error::from)
+    }
+}
+
+impl Decodable for QueuedMessageAck {
+    fn decode(m: &[u8]) -> Result<Self, Error> {
+        minicbor::decode(m).map_err(Error::from)
+    }
+}
+
+impl ockam_core::Message for QueuedMessageAck {}
+
+/// Outcome of a connection to the destination node of a queue
+#[derive(Debug, Clone, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub(crate) struct QueueConnection {
+    /// Route to the receiver, if the connection succeeded
+    #[n(1)] pub(crate) route: Option<Route>,
+}
+
+impl Encodable for QueueConnection {
+    fn encode(self) -> Result<Vec<u8>, Error> {
+        minicbor::to_vec(self).map_err(Error::from)
+    }
+}
+
+impl Decodable for QueueConnection {
+    fn decode(m: &[u8]) -> Result<Self, Error> {
+        minicbor::decode(m).map_err(Error::from)
+    }
+}
+
+impl ockam_core::Message for QueueConnection {}
@@ -0,0 +1,13 @@
+mod connector;
+mod messages;
+mod options;
+mod queue;
+mod receiver;
+mod storage;
+
+pub use connector::*;
+pub use messages::*;
+pub use options::*;
+pub use queue::*;
+pub use receiver::*;
+pub use storage::*;
@@ -0,0 +1,124 @@
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{AllowAll, IncomingAccessControl, OutgoingAccessControl};
+
+/// Default delay before the first retry of an unacknowledged message
+pub const DEFAULT_INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Default maximum delay between two delivery attempts of a message
+pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Default maximum number of messages stored in a queue
+pub const DEFAULT_MAX_DEPTH: u64 = 10_000;
+
+/// Default maximum number of messages sent at once when delivering a queue
+pub const DEFAULT_BATCH_SIZE: u32 = 100;
+
+/// Options for a [`MessageQueue`](crate::message_queues::MessageQueue)
+#[derive(Clone)]
+pub struct MessageQueueOptions {
+    pub(super) initial_retry_delay: Duration,
+    pub(super) max_retry_delay: Duration,
+    pub(super) max_depth: u64,
+    pub(super) batch_size: u32,
+    pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+}
+
+impl Default for MessageQueueOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageQueueOptions {
+    /// Default options
+    pub fn new() -> Self {
+        Self {
+            initial_retry_delay: DEFAULT_INITIAL_RETRY_DELAY,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            max_depth: DEFAULT_MAX_DEPTH,
+            batch_size: DEFAULT_BATCH_SIZE,
+            incoming_access_control: Arc::new(AllowAll),
+            outgoing_access_control: Arc::new(AllowAll),
+        }
+    }
+
+    /// Set the delay before the first retry of an unacknowledged message.
+    /// The delay is doubled after each attempt, up to the maximum retry delay
+    pub fn with_initial_retry_delay(mut self, delay: Duration) -> Self {
+        self.initial_retry_delay = delay;
+        self
+    }
+
+    /// Set the maximum delay between two delivery attempts of a message
+    pub fn with_max_retry_delay(mut self, delay: Duration) -> Self {
+        self.max_retry_delay = delay;
+        self
+    }
+
+    /// Set the maximum number of messages stored in the queue.
+    /// New messages are rejected when the queue is full
+    pub fn with_max_depth(mut self, max_depth: u64) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the maximum number of messages sent at once when delivering the queue
+    pub fn with_batch_size(mut self, batch_size: u32) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set the access control checking the acknowledgements sent by the destination node
+    pub fn with_incoming_access_control(
+        mut self,
+        access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Self {
+        self.incoming_access_control = access_control;
+        self
+    }
+
+    /// Set the access control checking the messages delivered to the destination node
+    pub fn with_outgoing_access_control(
+        mut self,
+        access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Self {
+        self.outgoing_access_control = access_control;
+        self
+    }
+
+    /// Maximum number of messages stored in the queue
+    pub fn max_depth(&self) -> u64 {
+        self.max_depth
+    }
+
+    /// Delay before the next delivery attempt of a message which
+    /// was already sent `attempts` times without being acknowledged
+    pub fn retry_delay(&self, attempts: u32) -> Duration {
+        let factor = 2u32.checked_pow(attempts).unwrap_or(u32::MAX);
+        self.initial_retry_delay
+            .checked_mul(factor)
+            .map_or(self.max_retry_delay, |delay| {
+                delay.min(self.max_retry_delay)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_is_exponential_and_bounded() {
+        let options = MessageQueueOptions::new()
+            .with_initial_retry_delay(Duration::from_secs(2))
+            .with_max_retry_delay(Duration::from_secs(60));
+
+        assert_eq!(options.retry_delay(0), Duration::from_secs(2));
+        assert_eq!(options.retry_delay(1), Duration::from_secs(4));
+        assert_eq!(options.retry_delay(4), Duration::from_secs(32));
+        assert_eq!(options.retry_delay(5), Duration::from_secs(60));
+        assert_eq!(options.retry_delay(100), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,586 @@
+use core::time::Duration;
+
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::time::now;
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{
+    async_trait, Address, AllowAll, AllowOnwardAddress, AllowSourceAddress, Any, Decodable,
+    DenyAll, Error, Mailbox, Mailboxes, Result, Route, Routed, Worker,
+};
+use ockam_node::{Context, DelayedEvent, WorkerBuilder};
+use tokio::sync::Mutex;
+
+use crate::message_queues::{
+    MessageQueueConnector, MessageQueueOptions, MessageQueuesRepository, QueueConnection,
+    QueuedMessage, QueuedMessageAck, QueuedMessageDelivery,
+};
+
+/// Addresses of a [`MessageQueue`] worker
+#[derive(Debug, Clone)]
+pub struct MessageQueueAddresses {
+    /// Address receiving the messages to store in the queue
+    main: Address,
+    /// Address receiving the acknowledgements of the destination node
+    acks: Address,
+    /// Address receiving the events triggering a new delivery attempt
+    retry: Address,
+    /// Address receiving the outcome of a connection to the destination node
+    connected: Address,
+}
+
+impl MessageQueueAddresses {
+    /// Create the addresses of a queue receiving its messages at the `main` address
+    pub fn new(main: impl Into<Address>) -> Self {
+        Self {
+            main: main.into(),
+            acks: Address::random_tagged("MessageQueue.acks"),
+            retry: Address::random_tagged("MessageQueue.retry"),
+            connected: Address::random_tagged("MessageQueue.connected"),
+        }
+    }
+
+    /// Address receiving the messages to store in the queue
+    pub fn main(&self) -> &Address {
+        &self.main
+    }
+
+    /// Address receiving the acknowledgements of the destination node.
+    /// A [`MessageQueueConnector`] must add it as a consumer of the flow control of its connections
+    pub fn acks(&self) -> &Address {
+        &self.acks
+    }
+
+    /// Address triggering the delivery of the messages which are due,
+    /// for example after they have been stored with [`MessageQueue::store_message`]
+    pub fn retry(&self) -> &Address {
+        &self.retry
+    }
+}
+
+/// Store-and-forward queue delivering messages to an intermittently connected node
+///
+/// The payload of each message sent to the main address of the queue is stored in a
+/// [`MessageQueuesRepository`] then sent to a
+/// [`MessageQueueReceiver`](crate::message_queues::MessageQueueReceiver) on the
+/// destination node, which forwards it to the destination address and acknowledges it.
+/// A message is deleted from the repository once it has been acknowledged. Otherwise it
+/// is sent again after a delay which doubles after every attempt, with a new connection
+/// to the destination node created by the queue [`MessageQueueConnector`].
+///
+/// The connections are created in a separate task, so that a destination node which is
+/// slow to answer does not prevent the queue from storing messages and receiving
+/// acknowledgements in the meantime.
+///
+/// The messages are delivered at least once: a message whose acknowledgement is lost
+/// is sent again, and the receiver only drops the duplicates it has recently seen.
+///
+/// The messages are stored under the queue name. If a queue is recreated with the same
+/// name, for example after a restart of the node, it resumes the delivery of the
+/// messages which were not acknowledged yet.
+pub struct MessageQueue {
+    name: String,
+    addresses: MessageQueueAddresses,
+    repository: Arc<dyn MessageQueuesRepository>,
+    connector: Arc<Mutex<Box<dyn MessageQueueConnector>>>,
+    /// Context used by the connection task to report the outcome of a connection
+    connector_ctx: Arc<Context>,
+    receiver_route: Option<Route>,
+    is_connecting: bool,
+    destination: String,
+    options: MessageQueueOptions,
+    retry: DelayedEvent<Vec<u8>>,
+}
+
+impl MessageQueue {
+    /// Start a queue delivering its messages to the `destination` address,
+    /// via the receiver at the end of the route returned by the `connector`
+    pub async fn create(
+        ctx: &Context,
+        name: impl Into<String>,
+        addresses: MessageQueueAddresses,
+        repository: Arc<dyn MessageQueuesRepository>,
+        connector: impl MessageQueueConnector,
+        destination: impl Into<String>,
+        options: MessageQueueOptions,
+    ) -> Result<()> {
+        let retry = DelayedEvent::create(ctx, addresses.retry.clone(), vec![]).await?;
+        let connector_ctx = ctx
+            .new_detached(
+                Address::random_tagged("MessageQueue.connector"),
+                DenyAll,
+                AllowOnwardAddress(addresses.connected.clone()),
+            )
+            .await?;
+
+        let mailboxes = Mailboxes::new(
+            // messages are only stored, never sent, from the main address
+            Mailbox::new(
+                addresses.main.clone(),
+                Arc::new(AllowAll),
+                Arc::new(DenyAll),
+            ),
+            vec![
+                // the deliveries are sent from the acks address so that
+                // the acknowledgements are routed back to it
+                Mailbox::new(
+                    addresses.acks.clone(),
+                    options.incoming_access_control.clone(),
+                    options.outgoing_access_control.clone(),
+                ),
+                // a delivery attempt only sends the messages which are due,
+                // so any local worker can trigger one
+                Mailbox::new(
+                    addresses.retry.clone(),
+                    Arc::new(AllowAll),
+                    Arc::new(DenyAll),
+                ),
+                Mailbox::new(
+                    addresses.connected.clone(),
+                    Arc::new(AllowSourceAddress(connector_ctx.address())),
+                    Arc::new(DenyAll),
+                ),
+            ],
+        );
+
+        let queue = MessageQueue {
+            name: name.into(),
+            addresses,
+            repository,
+            connector: Arc::new(Mutex::new(Box::new(connector))),
+            connector_ctx: Arc::new(connector_ctx),
+            receiver_route: None,
+            is_connecting: false,
+            destination: destination.into(),
+            options,
+            retry,
+        };
+        WorkerBuilder::new(queue)
+            .with_mailboxes(mailboxes)
+            .start(ctx)
+            .await
+    }
+
+    /// Store a message at the end of a queue, unless the queue already contains
+    /// `max_depth` messages. Return the identifier of the stored message.
+    ///
+    /// The message is delivered during the next delivery attempt of the queue,
+    /// which can be triggered by sending a message to its retry address
+    pub async fn store_message(
+        repository: &Arc<dyn MessageQueuesRepository>,
+        name: &str,
+        max_depth: u64,
+        payload: &[u8],
+    ) -> Result<u64> {
+        let statistics = repository.get_queue_statistics(name).await?;
+        if statistics.depth() >= max_depth {
+            return Err(Error::new(
+                Origin::Api,
+                Kind::ResourceExhausted,
+                format!(
+                    "the message queue {} is full ({} messages), the message is dropped",
+                    name,
+                    statistics.depth()
+                ),
+            ));
+        }
+
+        let message_id = repository.enqueue_message(name, payload, now()?).await?;
+        trace!(queue = %name, %message_id, "stored a message");
+        Ok(message_id)
+    }
+
+    /// Store a message, then try to deliver it right away
+    async fn enqueue(&mut self, ctx: &Context, payload: Vec<u8>) -> Result<()> {
+        Self::store_message(
+            &self.repository,
+            &self.name,
+            self.options.max_depth,
+            &payload,
+        )
+        .await?;
+        self.deliver_due_messages(ctx).await
+    }
+
+    /// Delete an acknowledged message
+    async fn acknowledge(&self, ack: QueuedMessageAck) -> Result<()> {
+        if self
+            .repository
+            .delete_message(&self.name, ack.message_id)
+            .await?
+        {
+            trace!(queue = %self.name, message_id = %ack.message_id, "a message was acknowledged");
+        } else {
+            debug!(queue = %self.name, message_id = %ack.message_id, "received a duplicate acknowledgement");
+        }
+        Ok(())
+    }
+
+    /// Send all the messages which are due for delivery, connecting to the destination
+    /// node first if necessary, and schedule the next attempt
+    async fn deliver_due_messages(&mut self, ctx: &Context) -> Result<()> {
+        let now = now()?;
+        let messages = self.get_due_messages(now).await?;
+        if messages.is_empty() {
+            return self.schedule_next_delivery(now).await;
+        }
+
+        // a message sent again was not acknowledged, the destination node
+        // might have been offline, so a new connection is created
+        if messages.iter().any(|message| message.attempts() > 0) {
+            self.receiver_route = None;
+        }
+
+        if self.receiver_route.is_some() {
+            self.send_messages(ctx, messages, now).await
+        } else {
+            // the messages are sent once the connection is established
+            self.connect();
+            Ok(())
+        }
+    }
+
+    /// Handle the outcome of a connection to the destination node
+    async fn connected(&mut self, ctx: &Context, connection: QueueConnection) -> Result<()> {
+        self.is_connecting = false;
+        self.receiver_route = connection.route;
+
+        // the messages are sent right away with the new connection, even if they were
+        // already sent before. If the connection failed, their next attempt is delayed
+        let now = now()?;
+        let messages = self.get_due_messages(now).await?;
+        self.send_messages(ctx, messages, now).await
+    }
+
+    async fn get_due_messages(&self, now: u64) -> Result<Vec<QueuedMessage>> {
+        self.repository
+            .get_due_messages(&self.name, now, self.options.batch_size)
+            .await
+    }
+
+    /// Send messages to the receiver if there is a connection,
+    /// record the attempt and schedule the next one
+    async fn send_messages(
+        &mut self,
+        ctx: &Context,
+        messages: Vec<QueuedMessage>,
+        now: u64,
+    ) -> Result<()> {
+        for message in messages {
+            let retry_delay = self.options.retry_delay(message.attempts());
+            self.repository
+                .record_delivery_attempt(
+                    &self.name,
+                    message.message_id(),
+                    now + retry_delay.as_secs().max(1),
+                )
+                .await?;
+
+            if let Some(route) = &self.receiver_route {
+                let delivery = QueuedMessageDelivery {
+                    queue_name: self.name.clone(),
+                    message_id: message.message_id(),
+                    destination: self.destination.clone(),
+                    payload: message.payload().to_vec(),
+                };
+                if let Err(e) = ctx
+                    .send_from_address(route.clone(), delivery, self.addresses.acks.clone())
+                    .await
+                {
+                    debug!(queue = %self.name, message_id = %message.message_id(), %e, "cannot deliver a queued message, it will be retried");
+                }
+            }
+        }
+
+        self.schedule_next_delivery(now).await
+    }
+
+    /// Connect to the destination node in a separate task, which reports
+    /// the route to the receiver on the `connected` address of the queue
+    fn connect(&mut self) {
+        if self.is_connecting {
+            return;
+        }
+        self.is_connecting = true;
+
+        let name = self.name.clone();
+        let connector = self.connector.clone();
+        let connector_ctx = self.connector_ctx.clone();
+        let connected = self.addresses.connected.clone();
+        ockam_node::spawn(async move {
+            let route = match connector.lock().await.connect().await {
+                Ok(route) => Some(route),
+                Err(e) => {
+                    debug!(queue = %name, %e, "cannot connect to the destination node, the delivery will be retried");
+                    None
+                }
+            };
+            if let Err(e) = connector_ctx
+                .send(connected, QueueConnection { route })
+                .await
+            {
+                debug!(queue = %name, %e, "cannot report the connection to the destination node");
+            }
+        });
+    }
+
+    async fn disconnect(&mut self) {
+        self.receiver_route = None;
+        self.connector.lock().await.disconnect().await;
+    }
+
+    /// Wake up the queue when the earliest retry is due
+    async fn schedule_next_delivery(&mut self, now: u64) -> Result<()> {
+        let statistics = self.repository.get_queue_statistics(&self.name).await?;
+        match statistics.next_attempt_at() {
+            Some(next_attempt_at) => {
+                let delay = Duration::from_secs(next_attempt_at.saturating_sub(now));
+                self.retry.schedule(delay).await
+            }
+            None => {
+                self.retry.cancel();
+                Ok(())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for MessageQueue {
+    type Message = Any;
+    type Context = Context;
+
+    async fn initialize(&mut self, _ctx: &mut Context) -> Result<()> {
+        // deliver the messages stored by a previous instance of this queue
+        self.retry.schedule(Duration::ZERO).await
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> Result<()> {
+        self.retry.cancel();
+        self.disconnect().await;
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let msg_addr = msg.msg_addr();
+        if msg_addr == self.addresses.main {
+            self.enqueue(ctx, msg.into_payload()).await
+        } else if msg_addr == self.addresses.acks {
+            self.acknowledge(QueuedMessageAck::decode(msg.payload())?)
+                .await
+        } else if msg_addr == self.addresses.retry {
+            self.deliver_due_messages(ctx).await
+        } else if msg_addr == self.addresses.connected {
+            self.connected(ctx, QueueConnection::decode(msg.payload())?)
+                .await
+        } else {
+            Err(Error::new(
+                Origin::Api,
+                Kind::Invalid,
+                format!(
+                    "the message queue {} received a message on an unknown address {}",
+                    self.name, msg_addr
+                ),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_queues::{MessageQueueReceiver, MessageQueuesSqlxDatabase};
+    use ockam_core::compat::string::ToString;
+    use ockam_core::{route, Encodable};
+    use ockam_node::MessageReceiveOptions;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[ockam_macros::test(timeout = 10_000)]
+    async fn test_messages_are_delivered_and_acknowledged(ctx: &mut Context) -> Result<()> {
+        let repository = create_repository().await?;
+        start_receiver(ctx).await?;
+        create_queue(ctx, repository.clone()).await?;
+
+        ctx.send("queue", "hello".to_string()).await?;
+        let message = ctx.receive::<String>().await?.into_body()?;
+        assert_eq!(message, "hello");
+
+        wait_until_empty(ctx, repository).await
+    }
+
+    #[ockam_macros::test(timeout = 10_000)]
+    async fn test_messages_are_retried_until_the_receiver_is_available(
+        ctx: &mut Context,
+    ) -> Result<()> {
+        let repository = create_repository().await?;
+        create_queue(ctx, repository.clone()).await?;
+
+        ctx.send("queue", "hello".to_string()).await?;
+        ctx.sleep(Duration::from_millis(100)).await;
+        assert_eq!(repository.get_queue_statistics("queue").await?.depth(), 1);
+
+        start_receiver(ctx).await?;
+        let message = ctx
+            .receive_extended::<String>(
+                MessageReceiveOptions::new().with_timeout(Duration::from_secs(5)),
+            )
+            .await?
+            .into_body()?;
+        assert_eq!(message, "hello");
+
+        wait_until_empty(ctx, repository).await
+    }
+
+    #[ockam_macros::test(timeout = 10_000)]
+    async fn test_stored_messages_are_delivered_by_a_new_queue(ctx: &mut Context) -> Result<()> {
+        let repository = create_repository().await?;
+        // a message stored by a previous instance of the queue
+        repository
+            .enqueue_message("queue", &"hello".to_string().encode()?, now()?)
+            .await?;
+
+        start_receiver(ctx).await?;
+        create_queue(ctx, repository.clone()).await?;
+
+        let message = ctx.receive::<String>().await?.into_body()?;
+        assert_eq!(message, "hello");
+
+        wait_until_empty(ctx, repository).await
+    }
+
+    #[tokio::test]
+    async fn test_a_full_queue_rejects_messages() -> Result<()> {
+        let repository = create_repository().await?;
+
+        MessageQueue::store_message(&repository, "queue", 1, b"hello").await?;
+        let result = MessageQueue::store_message(&repository, "queue", 1, b"hello").await;
+
+        assert_eq!(result.unwrap_err().code().kind, Kind::ResourceExhausted);
+        assert_eq!(repository.get_queue_statistics("queue").await?.depth(), 1);
+        Ok(())
+    }
+
+    #[ockam_macros::test(timeout = 10_000)]
+    async fn test_messages_are_only_forwarded_to_the_receiver_destinations(
+        ctx: &mut Context,
+    ) -> Result<()> {
+        let repository = create_repository().await?;
+        MessageQueueReceiver::new(vec!["other".into()])
+            .start(
+                ctx,
+                "receiver".into(),
+                Arc::new(AllowAll),
+                Arc::new(AllowAll),
+            )
+            .await?;
+        create_queue(ctx, repository.clone()).await?;
+
+        ctx.send("queue", "hello".to_string()).await?;
+
+        // the message is acknowledged and dropped by the receiver
+        wait_until_empty(ctx, repository).await?;
+        let received = ctx
+            .receive_extended::<String>(
+                MessageReceiveOptions::new().with_timeout(Duration::from_millis(200)),
+            )
+            .await;
+        assert!(received.is_err());
+        Ok(())
+    }
+
+    #[ockam_macros::test(timeout = 10_000)]
+    async fn test_a_slow_connection_does_not_block_the_queue(ctx: &mut Context) -> Result<()> {
+        let repository = create_repository().await?;
+        start_receiver(ctx).await?;
+        let release = Arc::new(AtomicBool::new(false));
+        MessageQueue::create(
+            ctx,
+            "queue",
+            MessageQueueAddresses::new("queue"),
+            repository.clone(),
+            SlowConnector {
+                release: release.clone(),
+            },
+            ctx.address().address(),
+            MessageQueueOptions::new(),
+        )
+        .await?;
+
+        // the messages are stored while the queue is still connecting
+        ctx.send("queue", "hello".to_string()).await?;
+        ctx.send("queue", "world".to_string()).await?;
+        while repository.get_queue_statistics("queue").await?.depth() < 2 {
+            ctx.sleep(Duration::from_millis(10)).await;
+        }
+
+        // then they are delivered once the connection is established
+        release.store(true, Ordering::SeqCst);
+        let first = ctx.receive::<String>().await?.into_body()?;
+        let second = ctx.receive::<String>().await?.into_body()?;
+        assert_eq!((first.as_str(), second.as_str()), ("hello", "world"));
+
+        wait_until_empty(ctx, repository).await
+    }
+
+    /// HELPERS
+    async fn create_repository() -> Result<Arc<dyn MessageQueuesRepository>> {
+        Ok(Arc::new(MessageQueuesSqlxDatabase::create().await?))
+    }
+
+    /// Create a queue delivering its messages to the context address via the "receiver" worker
+    async fn create_queue(
+        ctx: &Context,
+        repository: Arc<dyn MessageQueuesRepository>,
+    ) -> Result<()> {
+        MessageQueue::create(
+            ctx,
+            "queue",
+            MessageQueueAddresses::new("queue"),
+            repository,
+            route!["receiver"],
+            ctx.address().address(),
+            MessageQueueOptions::new(),
+        )
+        .await
+    }
+
+    /// Start a receiver forwarding the queued messages to the context address
+    async fn start_receiver(ctx: &Context) -> Result<()> {
+        MessageQueueReceiver::new(vec![ctx.address()])
+            .start(
+                ctx,
+                "receiver".into(),
+                Arc::new(AllowAll),
+                Arc::new(AllowAll),
+            )
+            .await
+    }
+
+    /// Connector which only connects to the "receiver" worker once it is released
+    struct SlowConnector {
+        release: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl MessageQueueConnector for SlowConnector {
+        async fn connect(&mut self) -> Result<Route> {
+            while !self.release.load(Ordering::SeqCst) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            Ok(route!["receiver"])
+        }
+
+        async fn disconnect(&mut self) {}
+    }
+
+    async fn wait_until_empty(
+        ctx: &Context,
+        repository: Arc<dyn MessageQueuesRepository>,
+    ) -> Result<()> {
+        while repository.get_queue_statistics("queue").await?.depth() > 0 {
+            ctx.sleep(Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+}
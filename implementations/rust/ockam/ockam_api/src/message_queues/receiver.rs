@@ -0,0 +1,154 @@
+use ockam::identity::IdentitySecureChannelLocalInfo;
+use ockam_core::compat::collections::{BTreeSet, VecDeque};
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
+use ockam_core::{
+    async_trait, route, Address, AllowOnwardAddresses, DenyAll, IncomingAccessControl, Mailbox,
+    Mailboxes, NeutralMessage, OutgoingAccessControl, Result, Routed, Worker,
+};
+use ockam_node::{Context, WorkerBuilder};
+
+use crate::message_queues::{QueuedMessageAck, QueuedMessageDelivery};
+
+/// Number of deliveries remembered by a [`MessageQueueReceiver`] in order to drop duplicates
+pub const DELIVERIES_WINDOW: usize = 1024;
+
+/// Identifies a message of a queue: identifier of the sending node, queue name, message id
+type DeliveryKey = (Option<String>, String, u64);
+
+/// Worker receiving the messages of the [`MessageQueue`](crate::message_queues::MessageQueue)s
+/// of other nodes.
+///
+/// The payload of each message is forwarded to its destination address, on this node,
+/// then the message is acknowledged. A message which is received again because its
+/// acknowledgement was lost is acknowledged without being forwarded, provided that it is
+/// one of the last [`DELIVERIES_WINDOW`] messages received.
+///
+/// Only the destinations given when the receiver is created can receive messages.
+/// The payloads are sent from a separate address which can only send messages to those
+/// destinations, so that the receiver can not be used to reach other workers of the node.
+pub struct MessageQueueReceiver {
+    destinations: BTreeSet<Address>,
+    forwarder: Address,
+    delivered: VecDeque<DeliveryKey>,
+    delivered_keys: BTreeSet<DeliveryKey>,
+}
+
+impl MessageQueueReceiver {
+    /// Create a receiver forwarding the payloads of the queued messages to the given destinations
+    pub fn new(destinations: Vec<Address>) -> Self {
+        Self {
+            destinations: destinations.into_iter().collect(),
+            forwarder: Address::random_tagged("MessageQueueReceiver.forwarder"),
+            delivered: VecDeque::new(),
+            delivered_keys: BTreeSet::new(),
+        }
+    }
+
+    /// Start the receiver at the given address.
+    ///
+    /// The incoming access control checks the nodes sending queued messages, and the outgoing
+    /// access control checks the nodes receiving the acknowledgements
+    pub async fn start(
+        self,
+        ctx: &Context,
+        address: Address,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+        outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let destinations = self.destinations.iter().cloned().collect();
+        let mailboxes = Mailboxes::new(
+            Mailbox::new(address, incoming_access_control, outgoing_access_control),
+            vec![Mailbox::new(
+                self.forwarder.clone(),
+                Arc::new(DenyAll),
+                Arc::new(AllowOnwardAddresses(destinations)),
+            )],
+        );
+        WorkerBuilder::new(self)
+            .with_mailboxes(mailboxes)
+            .start(ctx)
+            .await
+    }
+
+    /// Remember a delivered message, forgetting the oldest one if the window is full
+    fn remember(&mut self, key: DeliveryKey) {
+        if self.delivered.len() >= DELIVERIES_WINDOW {
+            if let Some(oldest) = self.delivered.pop_front() {
+                self.delivered_keys.remove(&oldest);
+            }
+        }
+        self.delivered_keys.insert(key.clone());
+        self.delivered.push_back(key);
+    }
+}
+
+#[async_trait]
+impl Worker for MessageQueueReceiver {
+    type Message = QueuedMessageDelivery;
+    type Context = Context;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<QueuedMessageDelivery>,
+    ) -> Result<()> {
+        let sender = IdentitySecureChannelLocalInfo::find_info(msg.local_message())
+            .ok()
+            .map(|info| info.their_identity_id().to_string());
+        let return_route = msg.return_route();
+        let delivery = msg.into_body()?;
+        let message_id = delivery.message_id;
+        let key = (sender, delivery.queue_name.clone(), message_id);
+
+        if self.delivered_keys.contains(&key) {
+            debug!(queue = %delivery.queue_name, %message_id, "dropping a duplicate queued message");
+        } else {
+            match delivery.destination.parse::<Address>() {
+                Ok(destination) if self.destinations.contains(&destination) => {
+                    // the message is not acknowledged if it can not be forwarded,
+                    // so that it is sent again later
+                    ctx.send_from_address(
+                        route![destination],
+                        NeutralMessage::from(delivery.payload),
+                        self.forwarder.clone(),
+                    )
+                    .await?;
+                }
+                _ => {
+                    // this message can never be delivered, it is acknowledged to be dropped by the queue
+                    warn!(queue = %delivery.queue_name, %message_id, destination = %delivery.destination, "dropping a queued message for a destination which is not allowed by the receiver");
+                }
+            }
+            self.remember(key);
+        }
+
+        ctx.send(return_route, QueuedMessageAck { message_id })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_the_last_deliveries_are_remembered() {
+        let mut receiver = MessageQueueReceiver::new(vec![]);
+        for message_id in 0..(DELIVERIES_WINDOW as u64 + 1) {
+            receiver.remember((None, "queue".to_string(), message_id));
+        }
+
+        assert_eq!(receiver.delivered.len(), DELIVERIES_WINDOW);
+        assert_eq!(receiver.delivered_keys.len(), DELIVERIES_WINDOW);
+        assert!(!receiver
+            .delivered_keys
+            .contains(&(None, "queue".to_string(), 0)));
+        assert!(receiver.delivered_keys.contains(&(
+            None,
+            "queue".to_string(),
+            DELIVERIES_WINDOW as u64
+        )));
+    }
+}
@@ -0,0 +1,44 @@
+use ockam_core::async_trait;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+
+use crate::message_queues::{QueueStatistics, QueuedMessage};
+
+/// This repository stores the messages of the store-and-forward queues of a node
+/// until their delivery is acknowledged.
+///
+/// All times are expressed in seconds since the epoch.
+#[async_trait]
+pub trait MessageQueuesRepository: Send + Sync + 'static {
+    /// Store a message at the end of a queue, to be delivered as soon as possible.
+    /// Return the identifier of the stored message
+    async fn enqueue_message(&self, queue_name: &str, payload: &[u8], now: u64) -> Result<u64>;
+
+    /// Return at most `limit` messages of a queue which are due for delivery at `now`,
+    /// oldest first
+    async fn get_due_messages(
+        &self,
+        queue_name: &str,
+        now: u64,
+        limit: u32,
+    ) -> Result<Vec<QueuedMessage>>;
+
+    /// Record a delivery attempt for a message and schedule the next one
+    async fn record_delivery_attempt(
+        &self,
+        queue_name: &str,
+        message_id: u64,
+        next_attempt_at: u64,
+    ) -> Result<()>;
+
+    /// Delete an acknowledged message.
+    /// Return false if the message was not found
+    async fn delete_message(&self, queue_name: &str, message_id: u64) -> Result<bool>;
+
+    /// Delete all the messages of a queue
+    async fn delete_queue(&self, queue_name: &str) -> Result<()>;
+
+    /// Return the depth and age of a queue
+    async fn get_queue_statistics(&self, queue_name: &str) -> Result<QueueStatistics>;
+}
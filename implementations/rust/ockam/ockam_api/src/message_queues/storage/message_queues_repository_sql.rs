@@ -0,0 +1,188 @@
+use sqlx::*;
+use tracing::debug;
+
+use ockam_core::async_trait;
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+use ockam_node::database::{FromSqlxError, SqlxDatabase, ToSqlxType, ToVoid};
+
+use crate::message_queues::{
+    MessageQueuesRepository, QueueStatistics, QueueStatisticsRow, QueuedMessage, QueuedMessageRow,
+};
+
+/// Implementation of [`MessageQueuesRepository`] trait based on an underlying database
+/// using sqlx as its API, and Sqlite as its driver
+#[derive(Clone)]
+pub struct MessageQueuesSqlxDatabase {
+    database: SqlxDatabase,
+    node_name: String,
+}
+
+impl MessageQueuesSqlxDatabase {
+    /// Create a new database for message queues
+    pub fn new(database: SqlxDatabase, node_name: &str) -> Self {
+        debug!("create a repository for message queues");
+        Self {
+            database,
+            node_name: node_name.to_string(),
+        }
+    }
+
+    /// Create a new in-memory database for message queues
+    pub async fn create() -> Result<Self> {
+        Ok(Self::new(
+            SqlxDatabase::in_memory("message queues").await?,
+            "default",
+        ))
+    }
+}
+
+#[async_trait]
+impl MessageQueuesRepository for MessageQueuesSqlxDatabase {
+    async fn enqueue_message(&self, queue_name: &str, payload: &[u8], now: u64) -> Result<u64> {
+        let query = query(
+            r#"INSERT INTO queued_message (queue_name, payload, enqueued_at, attempts, next_attempt_at, node_name)
+            VALUES (?, ?, ?, 0, ?, ?)"#,
+        )
+        .bind(queue_name.to_sql())
+        .bind(payload.to_vec().to_sql())
+        .bind(now.to_sql())
+        .bind(now.to_sql())
+        .bind(self.node_name.to_sql());
+        let result = query.execute(&*self.database.pool).await.into_core()?;
+        Ok(result.last_insert_rowid() as u64)
+    }
+
+    async fn get_due_messages(
+        &self,
+        queue_name: &str,
+        now: u64,
+        limit: u32,
+    ) -> Result<Vec<QueuedMessage>> {
+        let query = query_as(
+            r#"SELECT message_id, queue_name, payload, enqueued_at, attempts, next_attempt_at
+            FROM queued_message
+            WHERE node_name=? AND queue_name=? AND next_attempt_at<=?
+            ORDER BY message_id
+            LIMIT ?"#,
+        )
+        .bind(self.node_name.to_sql())
+        .bind(queue_name.to_sql())
+        .bind(now.to_sql())
+        .bind(limit.to_sql());
+        let rows: Vec<QueuedMessageRow> =
+            query.fetch_all(&*self.database.pool).await.into_core()?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn record_delivery_attempt(
+        &self,
+        queue_name: &str,
+        message_id: u64,
+        next_attempt_at: u64,
+    ) -> Result<()> {
+        let query = query(
+            r#"UPDATE queued_message SET attempts=attempts+1, next_attempt_at=?
+            WHERE node_name=? AND queue_name=? AND message_id=?"#,
+        )
+        .bind(next_attempt_at.to_sql())
+        .bind(self.node_name.to_sql())
+        .bind(queue_name.to_sql())
+        .bind(message_id.to_sql());
+        query.execute(&*self.database.pool).await.void()
+    }
+
+    async fn delete_message(&self, queue_name: &str, message_id: u64) -> Result<bool> {
+        let query =
+            query("DELETE FROM queued_message WHERE node_name=? AND queue_name=? AND message_id=?")
+                .bind(self.node_name.to_sql())
+                .bind(queue_name.to_sql())
+                .bind(message_id.to_sql());
+        let result = query.execute(&*self.database.pool).await.into_core()?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_queue(&self, queue_name: &str) -> Result<()> {
+        let query = query("DELETE FROM queued_message WHERE node_name=? AND queue_name=?")
+            .bind(self.node_name.to_sql())
+            .bind(queue_name.to_sql());
+        query.execute(&*self.database.pool).await.void()
+    }
+
+    async fn get_queue_statistics(&self, queue_name: &str) -> Result<QueueStatistics> {
+        let query = query_as(
+            r#"SELECT COUNT(*) AS depth, MIN(enqueued_at) AS oldest_enqueued_at, MIN(next_attempt_at) AS next_attempt_at
+            FROM queued_message
+            WHERE node_name=? AND queue_name=?"#,
+        )
+        .bind(self.node_name.to_sql())
+        .bind(queue_name.to_sql());
+        let row: QueueStatisticsRow = query.fetch_one(&*self.database.pool).await.into_core()?;
+        Ok(row.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ockam_core::compat::sync::Arc;
+
+    #[tokio::test]
+    async fn test_enqueue_and_acknowledge_messages() -> Result<()> {
+        let repository = create_repository().await?;
+
+        let first = repository.enqueue_message("queue", b"first", 10).await?;
+        let second = repository.enqueue_message("queue", b"second", 20).await?;
+        repository.enqueue_message("other", b"other", 5).await?;
+        assert!(first < second);
+
+        // the messages are returned oldest first, and only for the requested queue
+        let due = repository.get_due_messages("queue", 20, 10).await?;
+        let payloads: Vec<&[u8]> = due.iter().map(|m| m.payload()).collect();
+        assert_eq!(payloads, vec![&b"first"[..], &b"second"[..]]);
+        assert_eq!(due[0].attempts(), 0);
+
+        let statistics = repository.get_queue_statistics("queue").await?;
+        assert_eq!(statistics, QueueStatistics::new(2, Some(10), Some(10)));
+
+        assert!(repository.delete_message("queue", first).await?);
+        assert!(!repository.delete_message("queue", first).await?);
+        let statistics = repository.get_queue_statistics("queue").await?;
+        assert_eq!(statistics, QueueStatistics::new(1, Some(20), Some(20)));
+
+        repository.delete_queue("queue").await?;
+        let statistics = repository.get_queue_statistics("queue").await?;
+        assert_eq!(statistics, QueueStatistics::default());
+        assert_eq!(repository.get_queue_statistics("other").await?.depth(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delivery_attempts_are_rescheduled() -> Result<()> {
+        let repository = create_repository().await?;
+
+        let message_id = repository.enqueue_message("queue", b"hello", 10).await?;
+        repository
+            .record_delivery_attempt("queue", message_id, 15)
+            .await?;
+
+        // the message is not due before its next attempt
+        assert!(repository
+            .get_due_messages("queue", 14, 10)
+            .await?
+            .is_empty());
+
+        let due = repository.get_due_messages("queue", 15, 10).await?;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].message_id(), message_id);
+        assert_eq!(due[0].attempts(), 1);
+        assert_eq!(due[0].next_attempt_at(), 15);
+        assert_eq!(due[0].enqueued_at(), 10);
+        Ok(())
+    }
+
+    /// HELPERS
+    async fn create_repository() -> Result<Arc<dyn MessageQueuesRepository>> {
+        Ok(Arc::new(MessageQueuesSqlxDatabase::create().await?))
+    }
+}
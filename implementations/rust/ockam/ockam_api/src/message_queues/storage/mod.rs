@@ -0,0 +1,7 @@
+mod message_queues_repository;
+mod message_queues_repository_sql;
+mod queued_message;
+
+pub use message_queues_repository::*;
+pub use message_queues_repository_sql::*;
+pub use queued_message::*;
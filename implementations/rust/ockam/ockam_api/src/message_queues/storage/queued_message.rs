@@ -0,0 +1,126 @@
+use ockam_core::compat::string::String;
+use ockam_core::compat::vec::Vec;
+
+/// Message stored in a message queue until its delivery is acknowledged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedMessage {
+    message_id: u64,
+    queue_name: String,
+    payload: Vec<u8>,
+    enqueued_at: u64,
+    attempts: u32,
+    next_attempt_at: u64,
+}
+
+impl QueuedMessage {
+    pub fn new(
+        message_id: u64,
+        queue_name: String,
+        payload: Vec<u8>,
+        enqueued_at: u64,
+        attempts: u32,
+        next_attempt_at: u64,
+    ) -> Self {
+        Self {
+            message_id,
+            queue_name,
+            payload,
+            enqueued_at,
+            attempts,
+            next_attempt_at,
+        }
+    }
+    pub fn message_id(&self) -> u64 {
+        self.message_id
+    }
+    pub fn queue_name(&self) -> &str {
+        &self.queue_name
+    }
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+    /// Time, in seconds since the epoch, when the message was stored
+    pub fn enqueued_at(&self) -> u64 {
+        self.enqueued_at
+    }
+    /// Number of delivery attempts made so far
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+    /// Time, in seconds since the epoch, of the next delivery attempt
+    pub fn next_attempt_at(&self) -> u64 {
+        self.next_attempt_at
+    }
+}
+
+/// Depth and age of a message queue
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueueStatistics {
+    depth: u64,
+    oldest_enqueued_at: Option<u64>,
+    next_attempt_at: Option<u64>,
+}
+
+impl QueueStatistics {
+    pub fn new(depth: u64, oldest_enqueued_at: Option<u64>, next_attempt_at: Option<u64>) -> Self {
+        Self {
+            depth,
+            oldest_enqueued_at,
+            next_attempt_at,
+        }
+    }
+    /// Number of messages waiting for an acknowledgement
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+    /// Time when the oldest message of the queue was stored, if the queue is not empty
+    pub fn oldest_enqueued_at(&self) -> Option<u64> {
+        self.oldest_enqueued_at
+    }
+    /// Time of the earliest scheduled delivery attempt, if the queue is not empty
+    pub fn next_attempt_at(&self) -> Option<u64> {
+        self.next_attempt_at
+    }
+}
+
+// Low-level representation of a table row
+#[derive(sqlx::FromRow)]
+pub(crate) struct QueuedMessageRow {
+    message_id: i64,
+    queue_name: String,
+    payload: Vec<u8>,
+    enqueued_at: i64,
+    attempts: i64,
+    next_attempt_at: i64,
+}
+
+impl From<QueuedMessageRow> for QueuedMessage {
+    fn from(row: QueuedMessageRow) -> Self {
+        QueuedMessage::new(
+            row.message_id as u64,
+            row.queue_name,
+            row.payload,
+            row.enqueued_at as u64,
+            row.attempts as u32,
+            row.next_attempt_at as u64,
+        )
+    }
+}
+
+// Low-level representation of the aggregated statistics of a queue
+#[derive(sqlx::FromRow)]
+pub(crate) struct QueueStatisticsRow {
+    depth: i64,
+    oldest_enqueued_at: Option<i64>,
+    next_attempt_at: Option<i64>,
+}
+
+impl From<QueueStatisticsRow> for QueueStatistics {
+    fn from(row: QueueStatisticsRow) -> Self {
+        QueueStatistics::new(
+            row.depth as u64,
+            row.oldest_enqueued_at.map(|t| t as u64),
+            row.next_attempt_at.map(|t| t as u64),
+        )
+    }
+}
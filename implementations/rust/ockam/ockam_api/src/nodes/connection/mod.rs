@@ -1,4 +1,5 @@
 mod plain_tcp;
+mod plain_transport;
 mod project;
 mod secure;
 
@@ -16,6 +17,7 @@ use crate::local_multiaddr_to_route;
 use crate::nodes::service::default_address::DefaultAddress;
 use crate::nodes::NodeManager;
 pub(crate) use plain_tcp::PlainTcpInstantiator;
+pub(crate) use plain_transport::PlainTransportInstantiator;
 pub(crate) use project::ProjectInstantiator;
 pub(crate) use secure::SecureChannelInstantiator;
 use std::fmt::{Debug, Formatter};
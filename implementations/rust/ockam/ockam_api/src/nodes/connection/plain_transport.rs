@@ -0,0 +1,106 @@
+use crate::error::ApiError;
+use crate::nodes::connection::{Changes, ConnectionBuilder, Instantiator};
+use crate::{multiaddr_to_transport_route, route_to_multiaddr};
+use std::sync::Arc;
+
+use crate::nodes::NodeManager;
+use ockam_core::{async_trait, Error, Result, Route, TransportType};
+use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Udp, Unix, Ws};
+use ockam_multiaddr::{Match, MultiAddr, Protocol};
+use ockam_node::Context;
+use ockam_transport_udp::UDP;
+use ockam_transport_uds::{UdsTransport, UDS};
+use ockam_transport_websocket::{WebSocketTransport, WS};
+
+/// Creates a connection over a UDP, WebSocket or Unix domain socket transport.
+///
+/// The transport address is replaced with the address of a connection worker by
+/// resolving it with the transport registered with the node.
+/// The transport is created the first time it is used.
+pub(crate) struct PlainTransportInstantiator {
+    matches: Vec<Match>,
+}
+
+impl PlainTransportInstantiator {
+    /// Instantiate the UDP and WebSocket addresses, for example /ip4/127.0.0.1/udp/4000
+    pub(crate) fn socket() -> Self {
+        Self {
+            matches: vec![
+                // matches any host address followed by a udp or ws protocol
+                Match::any([DnsAddr::CODE, Ip4::CODE, Ip6::CODE]),
+                Match::any([Udp::CODE, Ws::CODE]),
+            ],
+        }
+    }
+
+    /// Instantiate the Unix domain socket addresses, for example /unix/%2Ftmp%2Fockam.sock
+    pub(crate) fn unix() -> Self {
+        Self {
+            matches: vec![Unix::CODE.into()],
+        }
+    }
+
+    /// Create the transport for the given transport type if it is not registered yet
+    async fn create_transport(
+        ctx: &Context,
+        node_manager: &NodeManager,
+        transport_type: TransportType,
+    ) -> Result<()> {
+        if ctx.is_transport_registered(transport_type) {
+            return Ok(());
+        }
+
+        if transport_type == UDP {
+            // The UDP transport is kept by the node manager since it is also used by UDP portals
+            node_manager.udp_transport(ctx).await?;
+        } else if transport_type == WS {
+            WebSocketTransport::create(ctx).await?;
+        } else if transport_type == UDS {
+            UdsTransport::create(ctx).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Instantiator for PlainTransportInstantiator {
+    fn matches(&self) -> Vec<Match> {
+        self.matches.clone()
+    }
+
+    async fn instantiate(
+        &self,
+        ctx: Arc<Context>,
+        node_manager: &NodeManager,
+        _transport_route: Route,
+        extracted: (MultiAddr, MultiAddr, MultiAddr),
+    ) -> Result<Changes, Error> {
+        let (before, transport_piece, after) = extracted;
+
+        let route = multiaddr_to_transport_route(&transport_piece).ok_or_else(|| {
+            ApiError::core(format!(
+                "Couldn't convert MultiAddr to route: transport_piece={transport_piece}"
+            ))
+        })?;
+
+        for address in route.iter().filter(|a| !a.is_local()) {
+            Self::create_transport(&ctx, node_manager, address.transport_type()).await?;
+        }
+        let route = ctx.resolve_transport_route(route).await?;
+
+        let multiaddr = route_to_multiaddr(&route).ok_or_else(|| {
+            ApiError::core(format!(
+                "Couldn't convert route to MultiAddr: transport_route={route}"
+            ))
+        })?;
+
+        let current_multiaddr = ConnectionBuilder::combine(before, multiaddr, after)?;
+
+        Ok(Changes {
+            current_multiaddr,
+            flow_control_id: None,
+            secure_channel_encryptors: vec![],
+            tcp_connection: None,
+        })
+    }
+}
@@ -0,0 +1,222 @@
+//! Store-and-forward message queues request/response types
+
+use std::time::Duration;
+
+use minicbor::{Decode, Encode};
+use ockam::identity::Identifier;
+use ockam_abac::Expr;
+use ockam_multiaddr::MultiAddr;
+use serde::{Deserialize, Serialize};
+
+use crate::colors::color_primary;
+use crate::output::Output;
+
+/// Request body to create a message queue
+#[derive(Clone, Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct CreateMessageQueue {
+    /// The name of the queue, which is also the address receiving the messages to store
+    #[n(1)] pub name: String,
+    /// The route to the destination node
+    #[n(2)] pub to: MultiAddr,
+    /// The address of the worker receiving the messages on the destination node
+    #[n(3)] pub destination: String,
+    /// An authorised identity for secure channels.
+    /// Only set for non-project addresses as for projects the project's
+    /// authorised identity will be used.
+    #[n(4)] pub authorized: Option<Identifier>,
+    /// The expression for the access control policy for this queue.
+    /// If not set, the policy set for the [message queue resource type](ockam_abac::ResourceType::MessageQueue)
+    /// will be used.
+    #[n(5)] pub policy_expression: Option<Expr>,
+    /// The maximum number of messages stored in the queue
+    #[n(6)] pub max_depth: Option<u64>,
+    /// The delay before the first retry of an unacknowledged message
+    #[n(7)] pub initial_retry_delay: Option<Duration>,
+    /// The maximum delay between two delivery attempts of a message
+    #[n(8)] pub max_retry_delay: Option<Duration>,
+}
+
+impl CreateMessageQueue {
+    pub fn new(name: String, to: MultiAddr, destination: String) -> Self {
+        Self {
+            name,
+            to,
+            destination,
+            authorized: None,
+            policy_expression: None,
+            max_depth: None,
+            initial_retry_delay: None,
+            max_retry_delay: None,
+        }
+    }
+
+    pub fn set_authorized(&mut self, authorized: Identifier) {
+        self.authorized = Some(authorized);
+    }
+
+    pub fn set_policy_expression(&mut self, expression: Expr) {
+        self.policy_expression = Some(expression);
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: u64) {
+        self.max_depth = Some(max_depth);
+    }
+
+    pub fn set_initial_retry_delay(&mut self, delay: Duration) {
+        self.initial_retry_delay = Some(delay);
+    }
+
+    pub fn set_max_retry_delay(&mut self, delay: Duration) {
+        self.max_retry_delay = Some(delay);
+    }
+}
+
+/// Request body to start the receiver of the messages queued by other nodes
+#[derive(Clone, Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct StartMessageQueueReceiver {
+    /// The addresses of the workers which can receive the queued messages
+    #[n(1)] pub destinations: Vec<String>,
+    /// An authorised identity for the nodes sending queued messages.
+    /// It is required when the node is not a project member
+    #[n(2)] pub authorized: Option<Identifier>,
+    /// The expression for the access control policy of the receiver.
+    /// If not set, the policy set for the [message queue resource type](ockam_abac::ResourceType::MessageQueue)
+    /// will be used.
+    #[n(3)] pub policy_expression: Option<Expr>,
+}
+
+impl StartMessageQueueReceiver {
+    pub fn new(destinations: Vec<String>) -> Self {
+        Self {
+            destinations,
+            authorized: None,
+            policy_expression: None,
+        }
+    }
+
+    pub fn set_authorized(&mut self, authorized: Identifier) {
+        self.authorized = Some(authorized);
+    }
+
+    pub fn set_policy_expression(&mut self, expression: Expr) {
+        self.policy_expression = Some(expression);
+    }
+}
+
+/// Request body to store a message in a message queue
+#[derive(Clone, Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct EnqueueMessage {
+    #[cbor(with = "minicbor::bytes")]
+    #[n(1)] pub payload: Vec<u8>,
+}
+
+impl EnqueueMessage {
+    pub fn new(payload: Vec<u8>) -> Self {
+        Self { payload }
+    }
+}
+
+/// Response body when interacting with a message queue
+#[derive(Clone, Debug, Decode, Encode, Serialize, Deserialize, PartialEq)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct MessageQueueStatus {
+    #[n(1)] pub name: String,
+    #[n(2)] pub to: String,
+    #[n(3)] pub destination: String,
+    /// Number of messages waiting for an acknowledgement
+    #[n(4)] pub depth: u64,
+    /// Age, in seconds, of the oldest message of the queue
+    #[n(5)] pub oldest_message_age: Option<u64>,
+    /// Delay, in seconds, before the next delivery attempt
+    #[n(6)] pub next_attempt_in: Option<u64>,
+}
+
+impl MessageQueueStatus {
+    pub fn new(
+        name: impl Into<String>,
+        to: impl Into<String>,
+        destination: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            to: to.into(),
+            destination: destination.into(),
+            depth: 0,
+            oldest_message_age: None,
+            next_attempt_in: None,
+        }
+    }
+
+    pub fn with_depth(
+        mut self,
+        depth: u64,
+        oldest_message_age: Option<u64>,
+        next_attempt_in: Option<u64>,
+    ) -> Self {
+        self.depth = depth;
+        self.oldest_message_age = oldest_message_age;
+        self.next_attempt_in = next_attempt_in;
+        self
+    }
+}
+
+impl Output for MessageQueueStatus {
+    fn single(&self) -> crate::Result<String> {
+        Ok(format!(
+            r#"
+Message Queue:
+    Name:               {}
+    To:                 {}
+    Destination:        {}
+    Depth:              {}
+    Oldest Message Age: {}
+    Next Attempt In:    {}
+"#,
+            self.name,
+            self.to,
+            self.destination,
+            self.depth,
+            format_seconds(self.oldest_message_age),
+            format_seconds(self.next_attempt_in),
+        ))
+    }
+
+    fn list(&self) -> crate::Result<String> {
+        Ok(format!(
+            "{} to {} at {}: {} message(s), oldest {}",
+            color_primary(&self.name),
+            color_primary(&self.destination),
+            color_primary(&self.to),
+            color_primary(self.depth.to_string()),
+            color_primary(format_seconds(self.oldest_message_age)),
+        ))
+    }
+}
+
+fn format_seconds(seconds: Option<u64>) -> String {
+    match seconds {
+        Some(seconds) => format!("{seconds}s"),
+        None => "N/A".to_string(),
+    }
+}
+
+/// Response body when returning a list of message queues
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct MessageQueueList {
+    #[n(1)] pub list: Vec<MessageQueueStatus>,
+}
+
+impl MessageQueueList {
+    pub fn new(list: Vec<MessageQueueStatus>) -> Self {
+        Self { list }
+    }
+}
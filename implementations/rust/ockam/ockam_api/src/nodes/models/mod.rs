@@ -5,10 +5,12 @@
 pub mod base;
 pub mod credentials;
 pub mod flow_controls;
+pub mod message_queue;
 pub mod policies;
 pub mod portal;
 pub mod relay;
 pub mod secure_channel;
 pub mod services;
 pub mod transport;
+pub mod udp_portal;
 pub mod workers;
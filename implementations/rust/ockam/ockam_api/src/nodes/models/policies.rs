@@ -1,4 +1,5 @@
 use minicbor::{Decode, Encode};
+use ockam::identity::Identifier;
 use ockam_abac::{Action, Expr, ResourceName, ResourcePolicy, ResourceType, ResourceTypePolicy};
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::Error;
@@ -59,6 +60,54 @@ impl PoliciesList {
     }
 }
 
+/// Request the policy decisions taken by a node, most recent first
+#[derive(Clone, Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PolicyDecisionsRequest {
+    #[n(1)] pub resource_name: Option<ResourceName>,
+    #[n(2)] pub identifier: Option<Identifier>,
+    #[n(3)] pub only_denied: bool,
+    #[n(4)] pub limit: u32,
+}
+
+impl PolicyDecisionsRequest {
+    pub fn new(
+        resource_name: Option<ResourceName>,
+        identifier: Option<Identifier>,
+        only_denied: bool,
+        limit: u32,
+    ) -> Self {
+        Self {
+            resource_name,
+            identifier,
+            only_denied,
+            limit,
+        }
+    }
+}
+
+/// Request the evaluation, step by step, of the policy protecting a resource
+/// when it is accessed by a given identity
+#[derive(Clone, Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct ExplainPolicyRequest {
+    #[n(1)] pub resource_name: ResourceName,
+    #[n(2)] pub action: Action,
+    #[n(3)] pub identifier: Identifier,
+}
+
+impl ExplainPolicyRequest {
+    pub fn new(resource_name: ResourceName, action: Action, identifier: Identifier) -> Self {
+        Self {
+            resource_name,
+            action,
+            identifier,
+        }
+    }
+}
+
 /// A view for the specific policy types returned by policies repositories. This is used
 /// to simplify the type returned by the NodeManager in the api requests.
 #[derive(Debug, Decode, Encode, Serialize, PartialEq, Eq)]
@@ -3,20 +3,22 @@
 use colorful::Colorful;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::colors::{color_primary, OckamColor};
 use minicbor::{Decode, Encode};
-use ockam::identity::Identifier;
+use ockam::identity::{Identifier, IdentitySecureChannelLocalInfo, TimestampInSeconds};
 use ockam::route;
-use ockam_abac::Expr;
+use ockam_abac::{Expr, ResourceType};
 use ockam_core::{Address, IncomingAccessControl, OutgoingAccessControl, Route};
 use ockam_multiaddr::MultiAddr;
-use ockam_transport_tcp::HostnamePort;
+use ockam_transport_tcp::{
+    HostnamePort, TcpPortalConnectionInfo, TcpPortalLimits, TcpPortalStats, TcpProxyConfig,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::error::ApiError;
-use crate::output::Output;
+use crate::output::{human_readable_time, Output};
 use crate::route_to_multiaddr;
 use crate::session::sessions::ConnectionStatus;
 
@@ -51,6 +53,13 @@ pub struct CreateInlet {
     #[n(8)] pub(crate) policy_expression: Option<Expr>,
     /// Create the inlet and wait for the outlet to connect
     #[n(9)] pub(crate) wait_connection: bool,
+    /// The resource type of this inlet, used to look up its policy when no policy expression is set.
+    /// If not set, the inlet is a [TCP inlet](ockam_abac::ResourceType::TcpInlet).
+    #[n(10)] pub(crate) resource_type: Option<ResourceType>,
+    /// If set, the inlet terminates the TLS connections of its clients
+    #[n(11)] pub(crate) tls: Option<InletTls>,
+    /// Limits on the number of connections and their throughput
+    #[n(12)] pub(crate) limits: Option<TcpPortalLimits>,
 }
 
 impl CreateInlet {
@@ -72,6 +81,9 @@ impl CreateInlet {
             wait_for_outlet_duration: None,
             policy_expression: None,
             wait_connection,
+            resource_type: None,
+            tls: None,
+            limits: None,
         }
     }
 
@@ -94,6 +106,9 @@ impl CreateInlet {
             wait_for_outlet_duration: None,
             policy_expression: None,
             wait_connection,
+            resource_type: None,
+            tls: None,
+            limits: None,
         }
     }
 
@@ -105,6 +120,18 @@ impl CreateInlet {
         self.policy_expression = Some(expression);
     }
 
+    pub fn set_resource_type(&mut self, resource_type: ResourceType) {
+        self.resource_type = Some(resource_type);
+    }
+
+    pub fn set_tls(&mut self, tls: InletTls) {
+        self.tls = Some(tls);
+    }
+
+    pub fn set_limits(&mut self, limits: TcpPortalLimits) {
+        self.limits = Some(limits);
+    }
+
     pub fn listen_addr(&self) -> String {
         self.listen_addr.clone()
     }
@@ -134,6 +161,43 @@ impl CreateInlet {
     }
 }
 
+/// TLS configuration of an inlet
+#[derive(Clone, Debug, Decode, Encode, Default, PartialEq, Eq)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct InletTls {
+    /// The PEM-encoded certificate chain served by the inlet.
+    /// If not set, a self-signed certificate is generated for the node identity.
+    #[n(1)] pub certificate_chain: Option<String>,
+    /// The PEM-encoded private key of the certificate chain
+    #[n(2)] pub private_key: Option<String>,
+    /// The PEM-encoded certificate authorities of the client certificates.
+    /// If set, the clients must present a certificate signed by one of these authorities.
+    #[n(3)] pub client_certificate_authorities: Option<String>,
+}
+
+impl InletTls {
+    /// Serve a self-signed certificate generated for the node identity
+    pub fn self_signed() -> Self {
+        Self::default()
+    }
+
+    /// Serve the given certificate chain
+    pub fn with_certificate(certificate_chain: String, private_key: String) -> Self {
+        Self {
+            certificate_chain: Some(certificate_chain),
+            private_key: Some(private_key),
+            client_certificate_authorities: None,
+        }
+    }
+
+    /// Require the clients to present a certificate signed by one of the given authorities
+    pub fn with_client_authentication(mut self, certificate_authorities: String) -> Self {
+        self.client_certificate_authorities = Some(certificate_authorities);
+        self
+    }
+}
+
 /// Request body to create an outlet
 #[derive(Clone, Debug, Decode, Encode)]
 #[rustfmt::skip]
@@ -152,6 +216,14 @@ pub struct CreateOutlet {
     /// If not set, the policy set for the [TCP outlet resource type](ockam_abac::ResourceType::TcpOutlet)
     /// will be used.
     #[n(5)] pub policy_expression: Option<Expr>,
+    /// The resource type of this outlet, used to look up its policy when no policy expression is set.
+    /// If not set, the outlet is a [TCP outlet](ockam_abac::ResourceType::TcpOutlet).
+    #[n(6)] pub resource_type: Option<ResourceType>,
+    /// The proxy used to reach the outlet destination.
+    /// If not set, the proxy environment variables of the node are used.
+    #[n(7)] pub proxy: Option<TcpProxyConfig>,
+    /// Limits on the number of connections and their throughput
+    #[n(8)] pub limits: Option<TcpPortalLimits>,
 }
 
 impl CreateOutlet {
@@ -167,12 +239,175 @@ impl CreateOutlet {
             worker_addr,
             reachable_from_default_secure_channel,
             policy_expression: None,
+            resource_type: None,
+            proxy: None,
+            limits: None,
         }
     }
 
     pub fn set_policy_expression(&mut self, expression: Expr) {
         self.policy_expression = Some(expression);
     }
+
+    pub fn set_resource_type(&mut self, resource_type: ResourceType) {
+        self.resource_type = Some(resource_type);
+    }
+
+    pub fn set_proxy(&mut self, proxy: TcpProxyConfig) {
+        self.proxy = Some(proxy);
+    }
+
+    pub fn set_limits(&mut self, limits: TcpPortalLimits) {
+        self.limits = Some(limits);
+    }
+}
+
+/// Counters of the connections of an inlet or an outlet
+#[derive(Clone, Debug, Default, Decode, Encode, Serialize, Deserialize, PartialEq)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PortalStats {
+    #[n(1)] pub active_connections: u64,
+    #[n(2)] pub total_connections: u64,
+    #[n(3)] pub rejected_connections: u64,
+    /// Number of bytes read from the local TCP connections
+    #[n(4)] pub bytes_received: u64,
+    /// Number of bytes written to the local TCP connections
+    #[n(5)] pub bytes_sent: u64,
+}
+
+impl From<&TcpPortalStats> for PortalStats {
+    fn from(stats: &TcpPortalStats) -> Self {
+        Self {
+            active_connections: stats.active_connections() as u64,
+            total_connections: stats.total_connections(),
+            rejected_connections: stats.rejected_connections(),
+            bytes_received: stats.bytes_received(),
+            bytes_sent: stats.bytes_sent(),
+        }
+    }
+}
+
+impl PortalStats {
+    fn display(&self) -> String {
+        format!(
+            r#"    Connections: {active} active, {total} total, {rejected} rejected
+    Traffic: {received} bytes received, {sent} bytes sent"#,
+            active = color_primary(self.active_connections.to_string()),
+            total = color_primary(self.total_connections.to_string()),
+            rejected = color_primary(self.rejected_connections.to_string()),
+            received = color_primary(self.bytes_received.to_string()),
+            sent = color_primary(self.bytes_sent.to_string()),
+        )
+    }
+}
+
+/// Live connection of an inlet or an outlet
+#[derive(Clone, Debug, Decode, Encode, Serialize, Deserialize, PartialEq)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PortalConnectionStatus {
+    #[n(1)] pub id: u64,
+    /// Address of the local TCP peer: the client of an inlet, or the TCP server of an outlet
+    #[n(2)] pub peer_addr: String,
+    /// Identifier of the node on the other side of the portal, if it is using a secure channel
+    #[n(3)] pub remote_identifier: Option<Identifier>,
+    #[n(4)] pub started_at: TimestampInSeconds,
+    #[n(5)] pub last_activity: TimestampInSeconds,
+    /// Number of bytes read from the local TCP connection
+    #[n(6)] pub bytes_received: u64,
+    /// Number of bytes written to the local TCP connection
+    #[n(7)] pub bytes_sent: u64,
+}
+
+impl From<&TcpPortalConnectionInfo> for PortalConnectionStatus {
+    fn from(info: &TcpPortalConnectionInfo) -> Self {
+        let timestamp = |time: SystemTime| {
+            TimestampInSeconds(
+                time.duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default(),
+            )
+        };
+        Self {
+            id: info.id(),
+            peer_addr: info.peer_address().to_string(),
+            remote_identifier: IdentitySecureChannelLocalInfo::find_info_from_list(
+                info.remote_local_info(),
+            )
+            .ok()
+            .map(|info| info.their_identity_id()),
+            started_at: timestamp(info.started_at()),
+            last_activity: timestamp(info.last_activity()),
+            bytes_received: info.bytes_received(),
+            bytes_sent: info.bytes_sent(),
+        }
+    }
+}
+
+impl Output for PortalConnectionStatus {
+    fn single(&self) -> crate::Result<String> {
+        Ok(format!(
+            r#"Connection {id}
+    Peer Address: {peer_addr}
+    Remote Identifier: {remote_identifier}
+    Started At: {started_at}
+    Last Activity: {last_activity}
+    Traffic: {received} bytes received, {sent} bytes sent"#,
+            id = color_primary(self.id.to_string()),
+            peer_addr = color_primary(&self.peer_addr),
+            remote_identifier = color_primary(
+                self.remote_identifier
+                    .as_ref()
+                    .map(|i| i.to_string())
+                    .unwrap_or("N/A".to_string())
+            ),
+            started_at = color_primary(human_readable_time(self.started_at)),
+            last_activity = color_primary(human_readable_time(self.last_activity)),
+            received = color_primary(self.bytes_received.to_string()),
+            sent = color_primary(self.bytes_sent.to_string()),
+        ))
+    }
+
+    fn list(&self) -> crate::Result<String> {
+        Ok(format!(
+            "Connection {} from {} ({}), {} bytes received, {} bytes sent",
+            color_primary(self.id.to_string()),
+            color_primary(&self.peer_addr),
+            self.remote_identifier
+                .as_ref()
+                .map(|i| i.to_string())
+                .unwrap_or("N/A".to_string()),
+            self.bytes_received,
+            self.bytes_sent,
+        ))
+    }
+}
+
+/// Response body when returning the live connections of an inlet or an outlet
+#[derive(Debug, Clone, Decode, Encode, Serialize, Deserialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PortalConnectionList {
+    #[n(1)] pub list: Vec<PortalConnectionStatus>,
+}
+
+impl PortalConnectionList {
+    pub fn new(list: Vec<PortalConnectionStatus>) -> Self {
+        Self { list }
+    }
+}
+
+impl From<&TcpPortalStats> for PortalConnectionList {
+    fn from(stats: &TcpPortalStats) -> Self {
+        Self::new(
+            stats
+                .connections()
+                .iter()
+                .map(PortalConnectionStatus::from)
+                .collect(),
+        )
+    }
 }
 
 /// Response body when interacting with a portal endpoint
@@ -188,6 +423,7 @@ pub struct InletStatus {
     #[n(5)] pub outlet_route: Option<String>,
     #[n(6)] pub status: ConnectionStatus,
     #[n(7)] pub outlet_addr: String,
+    #[n(8)] pub stats: Option<PortalStats>,
 }
 
 impl InletStatus {
@@ -209,8 +445,14 @@ impl InletStatus {
             outlet_route: outlet_route.into(),
             status,
             outlet_addr: outlet_addr.into(),
+            stats: None,
         }
     }
+
+    pub fn with_stats(mut self, stats: PortalStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
 }
 
 impl Output for InletStatus {
@@ -231,6 +473,7 @@ Inlet
     TCP Address: {bind_addr}
     Outlet Address: {outlet_route}
     Outlet Destination: {outlet_addr}
+{stats}
             "#,
             alias = self
                 .alias
@@ -246,6 +489,7 @@ Inlet
                 .color(OckamColor::PrimaryResource.color()),
             outlet_route = outlet.color(OckamColor::PrimaryResource.color()),
             outlet_addr = self.outlet_addr,
+            stats = self.stats.as_ref().map(|s| s.display()).unwrap_or_default(),
         );
 
         Ok(output)
@@ -281,6 +525,7 @@ pub struct OutletStatus {
     #[n(2)] pub worker_addr: Address,
     /// An optional status payload
     #[n(3)] pub payload: Option<String>,
+    #[n(4)] pub stats: Option<PortalStats>,
 }
 
 impl OutletStatus {
@@ -293,9 +538,15 @@ impl OutletStatus {
             socket_addr,
             worker_addr,
             payload: payload.into(),
+            stats: None,
         }
     }
 
+    pub fn with_stats(mut self, stats: PortalStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
     pub fn worker_address(&self) -> Result<MultiAddr, ockam_core::Error> {
         route_to_multiaddr(&route![self.worker_addr.to_string()])
             .ok_or_else(|| ApiError::core("Invalid Worker Address"))
@@ -317,9 +568,11 @@ impl Output for OutletStatus {
 Outlet:
     TCP Address:    {}
     Worker Address: {}
+{}
 "#,
             self.socket_addr,
-            self.worker_address()?
+            self.worker_address()?,
+            self.stats.as_ref().map(|s| s.display()).unwrap_or_default(),
         );
 
         Ok(output)
@@ -372,5 +625,6 @@ pub enum OutletAccessControl {
             Arc<dyn OutgoingAccessControl>,
         ),
     ),
-    PolicyExpression(Option<Expr>),
+    /// Use the policy set for the outlet, or for its resource type if no expression is given
+    PolicyExpression(ResourceType, Option<Expr>),
 }
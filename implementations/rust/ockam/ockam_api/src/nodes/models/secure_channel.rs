@@ -7,6 +7,7 @@ use serde::Serialize;
 
 use ockam::identity::models::CredentialAndPurposeKey;
 use ockam::identity::{Identifier, SecureChannel, DEFAULT_TIMEOUT};
+use ockam_abac::Expr;
 use ockam_core::flow_control::FlowControlId;
 use ockam_core::{route, Address, Result};
 use ockam_multiaddr::MultiAddr;
@@ -155,6 +156,7 @@ pub struct CreateSecureChannelListenerRequest {
     #[n(1)] pub addr: Address,
     #[n(2)] pub authorized_identifiers: Option<Vec<Identifier>>,
     #[n(3)] pub identity_name: Option<String>,
+    #[n(4)] pub policy_expression: Option<Expr>,
 }
 
 impl CreateSecureChannelListenerRequest {
@@ -167,8 +169,13 @@ impl CreateSecureChannelListenerRequest {
             addr: addr.to_owned(),
             authorized_identifiers,
             identity_name,
+            policy_expression: None,
         }
     }
+
+    pub fn set_policy_expression(&mut self, expression: Expr) {
+        self.policy_expression = Some(expression);
+    }
 }
 
 /// Response body when deleting a Secure Channel Listener
@@ -1,4 +1,5 @@
 use minicbor::{Decode, Encode};
+use ockam_transport_tcp::TcpProxyConfig;
 
 /// Request body when instructing a node to create a transport
 #[derive(Debug, Clone, Decode, Encode, PartialEq, Eq)]
@@ -7,11 +8,18 @@ use minicbor::{Decode, Encode};
 pub struct CreateTcpConnection {
     /// The address payload for the transport
     #[n(1)] pub addr: String,
+    /// The proxy configuration. The proxy environment variables of the node are used if it is not set
+    #[n(2)] pub proxy: Option<TcpProxyConfig>,
 }
 
 impl CreateTcpConnection {
     pub fn new(addr: String) -> Self {
-        Self { addr }
+        Self { addr, proxy: None }
+    }
+
+    pub fn with_proxy(mut self, proxy: TcpProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
     }
 }
 
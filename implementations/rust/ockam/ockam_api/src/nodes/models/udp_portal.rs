@@ -0,0 +1,212 @@
+//! UDP inlets and outlets request/response types
+
+use std::time::Duration;
+
+use minicbor::{Decode, Encode};
+use ockam::identity::Identifier;
+use ockam_abac::Expr;
+use ockam_core::Address;
+use ockam_multiaddr::MultiAddr;
+use serde::{Deserialize, Serialize};
+
+use crate::colors::color_primary;
+use crate::output::Output;
+
+/// Request body to create a UDP inlet
+#[derive(Clone, Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct CreateUdpInlet {
+    /// The address the inlet should bind to
+    #[n(1)] pub listen_addr: String,
+    /// The address of the UDP outlet
+    #[n(2)] pub outlet_addr: MultiAddr,
+    /// A human-friendly alias for this inlet
+    #[n(3)] pub alias: String,
+    /// An authorised identity for secure channels.
+    /// Only set for non-project addresses as for projects the project's
+    /// authorised identity will be used.
+    #[n(4)] pub authorized: Option<Identifier>,
+    /// The expression for the access control policy for this inlet.
+    /// If not set, the policy set for the [UDP inlet resource type](ockam_abac::ResourceType::UdpInlet)
+    /// will be used.
+    #[n(5)] pub policy_expression: Option<Expr>,
+    /// The delay after which the session of a client is closed if it is idle
+    #[n(6)] pub idle_timeout: Option<Duration>,
+}
+
+impl CreateUdpInlet {
+    pub fn new(listen_addr: String, outlet_addr: MultiAddr, alias: String) -> Self {
+        Self {
+            listen_addr,
+            outlet_addr,
+            alias,
+            authorized: None,
+            policy_expression: None,
+            idle_timeout: None,
+        }
+    }
+
+    pub fn set_authorized(&mut self, authorized: Identifier) {
+        self.authorized = Some(authorized);
+    }
+
+    pub fn set_policy_expression(&mut self, expression: Expr) {
+        self.policy_expression = Some(expression);
+    }
+
+    pub fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.idle_timeout = Some(idle_timeout);
+    }
+}
+
+/// Request body to create a UDP outlet
+#[derive(Clone, Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct CreateUdpOutlet {
+    /// The hostname and port of the UDP server
+    #[n(1)] pub destination: String,
+    /// The address of the outlet worker
+    #[n(2)] pub worker_addr: Option<Address>,
+    /// The expression for the access control policy for this outlet.
+    /// If not set, the policy set for the [UDP outlet resource type](ockam_abac::ResourceType::UdpOutlet)
+    /// will be used.
+    #[n(3)] pub policy_expression: Option<Expr>,
+    /// The delay after which a session is closed if it is idle
+    #[n(4)] pub idle_timeout: Option<Duration>,
+}
+
+impl CreateUdpOutlet {
+    pub fn new(destination: String, worker_addr: Option<Address>) -> Self {
+        Self {
+            destination,
+            worker_addr,
+            policy_expression: None,
+            idle_timeout: None,
+        }
+    }
+
+    pub fn set_policy_expression(&mut self, expression: Expr) {
+        self.policy_expression = Some(expression);
+    }
+
+    pub fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.idle_timeout = Some(idle_timeout);
+    }
+}
+
+/// Response body when interacting with a UDP inlet
+#[derive(Clone, Debug, Decode, Encode, Serialize, Deserialize, PartialEq)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct UdpInletStatus {
+    #[n(1)] pub alias: String,
+    #[n(2)] pub bind_addr: String,
+    #[n(3)] pub outlet_addr: String,
+}
+
+impl UdpInletStatus {
+    pub fn new(
+        alias: impl Into<String>,
+        bind_addr: impl Into<String>,
+        outlet_addr: impl Into<String>,
+    ) -> Self {
+        Self {
+            alias: alias.into(),
+            bind_addr: bind_addr.into(),
+            outlet_addr: outlet_addr.into(),
+        }
+    }
+}
+
+impl Output for UdpInletStatus {
+    fn single(&self) -> crate::Result<String> {
+        Ok(format!(
+            r#"
+UDP Inlet:
+    Alias:          {}
+    Listen Address: {}
+    Outlet Address: {}
+"#,
+            self.alias, self.bind_addr, self.outlet_addr
+        ))
+    }
+
+    fn list(&self) -> crate::Result<String> {
+        Ok(format!(
+            "{} from {} to {}",
+            color_primary(&self.alias),
+            color_primary(&self.bind_addr),
+            color_primary(&self.outlet_addr),
+        ))
+    }
+}
+
+/// Response body when interacting with a UDP outlet
+#[derive(Clone, Debug, Decode, Encode, Serialize, Deserialize, PartialEq)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct UdpOutletStatus {
+    #[n(1)] pub worker_addr: Address,
+    #[n(2)] pub destination: String,
+}
+
+impl UdpOutletStatus {
+    pub fn new(worker_addr: Address, destination: impl Into<String>) -> Self {
+        Self {
+            worker_addr,
+            destination: destination.into(),
+        }
+    }
+}
+
+impl Output for UdpOutletStatus {
+    fn single(&self) -> crate::Result<String> {
+        Ok(format!(
+            r#"
+UDP Outlet:
+    Worker Address: {}
+    Destination:    {}
+"#,
+            self.worker_addr.address(),
+            self.destination
+        ))
+    }
+
+    fn list(&self) -> crate::Result<String> {
+        Ok(format!(
+            "From address {} to UDP server {}",
+            color_primary(self.worker_addr.address()),
+            color_primary(&self.destination),
+        ))
+    }
+}
+
+/// Response body when returning a list of UDP inlets
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct UdpInletList {
+    #[n(1)] pub list: Vec<UdpInletStatus>,
+}
+
+impl UdpInletList {
+    pub fn new(list: Vec<UdpInletStatus>) -> Self {
+        Self { list }
+    }
+}
+
+/// Response body when returning a list of UDP outlets
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct UdpOutletList {
+    #[n(1)] pub list: Vec<UdpOutletStatus>,
+}
+
+impl UdpOutletList {
+    pub fn new(list: Vec<UdpOutletStatus>) -> Self {
+        Self { list }
+    }
+}
@@ -1,4 +1,6 @@
 use crate::cli_state::random_name;
+use crate::message_queues::MessageQueueAddresses;
+use crate::nodes::connection::Connection;
 use crate::nodes::models::relay::RelayInfo;
 use crate::session::sessions::{ReplacerOutputKind, Session};
 use crate::DefaultAddress;
@@ -8,6 +10,7 @@ use ockam_core::compat::collections::BTreeMap;
 use ockam_core::{Address, Route};
 use ockam_multiaddr::MultiAddr;
 use ockam_node::compat::asynchronous::RwLock;
+use ockam_transport_tcp::TcpPortalStats;
 use std::borrow::Borrow;
 use std::fmt::Display;
 use std::net::SocketAddr;
@@ -147,6 +150,7 @@ pub(crate) struct InletInfo {
     pub(crate) bind_addr: String,
     pub(crate) outlet_addr: MultiAddr,
     pub(crate) session: Session,
+    pub(crate) stats: TcpPortalStats,
 }
 
 impl InletInfo {
@@ -155,14 +159,21 @@ impl InletInfo {
             bind_addr: bind_addr.to_owned(),
             outlet_addr,
             session,
+            stats: TcpPortalStats::default(),
         }
     }
+
+    pub(crate) fn with_stats(mut self, stats: TcpPortalStats) -> Self {
+        self.stats = stats;
+        self
+    }
 }
 
 #[derive(Clone)]
 pub struct OutletInfo {
     pub(crate) socket_addr: SocketAddr,
     pub(crate) worker_addr: Address,
+    pub(crate) stats: TcpPortalStats,
 }
 
 impl OutletInfo {
@@ -174,8 +185,35 @@ impl OutletInfo {
         Self {
             socket_addr: *socket_addr,
             worker_addr,
+            stats: TcpPortalStats::default(),
         }
     }
+
+    pub(crate) fn with_stats(mut self, stats: TcpPortalStats) -> Self {
+        self.stats = stats;
+        self
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct UdpInletInfo {
+    pub(crate) bind_addr: SocketAddr,
+    pub(crate) outlet_addr: MultiAddr,
+    pub(crate) listener_address: Address,
+    pub(crate) connection: Connection,
+}
+
+#[derive(Clone)]
+pub(crate) struct UdpOutletInfo {
+    pub(crate) destination: String,
+}
+
+#[derive(Clone)]
+pub(crate) struct MessageQueueInfo {
+    pub(crate) to: MultiAddr,
+    pub(crate) destination: String,
+    pub(crate) addresses: MessageQueueAddresses,
+    pub(crate) max_depth: u64,
 }
 
 #[derive(Clone)]
@@ -225,6 +263,10 @@ pub(crate) struct Registry {
     pub(crate) relays: RegistryOf<String, RegistryRelayInfo>,
     pub(crate) inlets: RegistryOf<String, InletInfo>,
     pub(crate) outlets: RegistryOf<Address, OutletInfo>,
+    pub(crate) udp_inlets: RegistryOf<String, UdpInletInfo>,
+    pub(crate) udp_outlets: RegistryOf<Address, UdpOutletInfo>,
+    pub(crate) message_queues: RegistryOf<String, MessageQueueInfo>,
+    pub(crate) message_queue_receivers: RegistryOf<Address, Vec<Address>>,
 }
 
 pub(crate) struct RegistryOf<K, V> {
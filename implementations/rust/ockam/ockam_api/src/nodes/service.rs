@@ -11,7 +11,9 @@ pub mod default_address;
 mod flow_controls;
 pub(crate) mod in_memory_node;
 pub mod kafka_services;
+mod message_queues;
 pub mod messages;
+mod metrics_endpoint;
 mod node_services;
 pub(crate) mod policy;
 pub mod portals;
@@ -19,6 +21,7 @@ mod projects;
 pub mod relay;
 mod secure_channel;
 mod transport;
+mod udp_portals;
 pub mod workers;
 
 mod manager;
@@ -26,6 +29,7 @@ mod trust;
 mod worker;
 
 pub use manager::*;
+pub use metrics_endpoint::*;
 pub use trust::*;
 pub use worker::*;
 
@@ -11,12 +11,14 @@ impl DefaultAddress {
     pub const CREDENTIAL_ISSUER: &'static str = "credential_issuer";
     pub const ENROLLMENT_TOKEN_ISSUER: &'static str = "enrollment_token_issuer";
     pub const ENROLLMENT_TOKEN_ACCEPTOR: &'static str = "enrollment_token_acceptor";
+    pub const REVOCATION_LIST_ISSUER: &'static str = "revocation_list_issuer";
     pub const OKTA_IDENTITY_PROVIDER: &'static str = "okta";
     pub const KAFKA_OUTLET: &'static str = "kafka_outlet";
     pub const KAFKA_CONSUMER: &'static str = "kafka_consumer";
     pub const KAFKA_INLET: &'static str = "kafka_inlet";
     pub const KAFKA_PRODUCER: &'static str = "kafka_producer";
     pub const KAFKA_DIRECT: &'static str = "kafka_direct";
+    pub const MESSAGE_QUEUE_RECEIVER: &'static str = "message_queue_receiver";
 
     pub fn is_valid(name: &str) -> bool {
         matches!(name, |Self::OUTLET_SERVICE| Self::RELAY_SERVICE
@@ -28,11 +30,13 @@ impl DefaultAddress {
             | Self::CREDENTIAL_ISSUER
             | Self::ENROLLMENT_TOKEN_ISSUER
             | Self::ENROLLMENT_TOKEN_ACCEPTOR
+            | Self::REVOCATION_LIST_ISSUER
             | Self::OKTA_IDENTITY_PROVIDER
             | Self::KAFKA_CONSUMER
             | Self::KAFKA_PRODUCER
             | Self::KAFKA_OUTLET
-            | Self::KAFKA_DIRECT)
+            | Self::KAFKA_DIRECT
+            | Self::MESSAGE_QUEUE_RECEIVER)
     }
 
     pub fn iter() -> impl Iterator<Item = &'static str> {
@@ -47,11 +51,13 @@ impl DefaultAddress {
             Self::CREDENTIAL_ISSUER,
             Self::ENROLLMENT_TOKEN_ISSUER,
             Self::ENROLLMENT_TOKEN_ACCEPTOR,
+            Self::REVOCATION_LIST_ISSUER,
             Self::OKTA_IDENTITY_PROVIDER,
             Self::KAFKA_CONSUMER,
             Self::KAFKA_PRODUCER,
             Self::KAFKA_OUTLET,
             Self::KAFKA_DIRECT,
+            Self::MESSAGE_QUEUE_RECEIVER,
         ]
         .iter()
         .copied()
@@ -83,10 +89,16 @@ mod test {
         assert!(DefaultAddress::is_valid(
             DefaultAddress::ENROLLMENT_TOKEN_ACCEPTOR
         ));
+        assert!(DefaultAddress::is_valid(
+            DefaultAddress::REVOCATION_LIST_ISSUER
+        ));
         assert!(DefaultAddress::is_valid(
             DefaultAddress::OKTA_IDENTITY_PROVIDER
         ));
         assert!(DefaultAddress::is_valid(DefaultAddress::KAFKA_CONSUMER));
         assert!(DefaultAddress::is_valid(DefaultAddress::KAFKA_PRODUCER));
+        assert!(DefaultAddress::is_valid(
+            DefaultAddress::MESSAGE_QUEUE_RECEIVER
+        ));
     }
 }
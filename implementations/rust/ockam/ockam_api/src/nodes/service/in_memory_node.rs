@@ -182,6 +182,9 @@ impl InMemoryNode {
 
     pub async fn stop(&self, ctx: &Context) -> Result<()> {
         self.medic_handle.stop_medic(ctx).await?;
+        if let Some(revocation_list_retriever) = &self.revocation_list_retriever {
+            revocation_list_retriever.stop();
+        }
         for addr in DefaultAddress::iter() {
             let result = ctx.stop_worker(addr).await;
             // when stopping we can safely ignore missing services
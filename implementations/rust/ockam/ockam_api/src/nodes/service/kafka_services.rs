@@ -1,14 +1,16 @@
 use std::net::IpAddr;
 
 use crate::cli_state::random_name;
+use ockam::identity::Identifier;
 use ockam::{Address, Context, Result};
+use ockam_abac::{Action, Resource, ResourceType};
 use ockam_core::api::{Error, Response};
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::compat::rand::random_string;
-use ockam_core::route;
+use ockam_core::{route, IncomingAccessControl};
 use ockam_multiaddr::proto::Project;
 use ockam_multiaddr::MultiAddr;
-use ockam_transport_tcp::HostnamePort;
+use ockam_transport_tcp::{HostnamePort, TcpPortalLimits};
 
 use super::NodeManagerWorker;
 use crate::error::ApiError;
@@ -28,6 +30,7 @@ use crate::nodes::service::default_address::DefaultAddress;
 use crate::nodes::InMemoryNode;
 use crate::nodes::NodeManager;
 use crate::port_range::PortRange;
+use std::sync::Arc;
 
 impl NodeManagerWorker {
     pub(super) async fn start_kafka_outlet_service(
@@ -193,10 +196,12 @@ impl InMemoryNode {
             }
         };
 
+        let interceptor_incoming_ac = self
+            .kafka_outlet_interceptor_access_control(context, project_authority.clone())
+            .await?;
         OutletManagerService::create(
             context,
-            self.secure_channels.clone(),
-            project_authority.clone(),
+            interceptor_incoming_ac,
             default_secure_channel_listener_flow_control_id,
             outlet_policy_expression.clone(),
         )
@@ -207,7 +212,10 @@ impl InMemoryNode {
             false,
             Some(KAFKA_OUTLET_BOOTSTRAP_ADDRESS.into()),
             false,
-            OutletAccessControl::PolicyExpression(outlet_policy_expression.clone()),
+            OutletAccessControl::PolicyExpression(
+                ResourceType::KafkaOutlet,
+                outlet_policy_expression.clone(),
+            ),
         )
         .await?;
 
@@ -235,22 +243,26 @@ impl InMemoryNode {
 
         // since we cannot call APIs of node manager via message due to the read/write lock
         // we need to call it directly
-        self.create_inlet(
-            context,
-            SocketAddr::new(bind_ip, server_bootstrap_port).to_string(),
-            route![local_interceptor_address.clone()],
-            route![
-                KAFKA_OUTLET_INTERCEPTOR_ADDRESS,
-                KAFKA_OUTLET_BOOTSTRAP_ADDRESS
-            ],
-            "/secure/api".parse()?,
-            random_name(),
-            outlet_policy_expression,
-            None,
-            None,
-            true,
-        )
-        .await?;
+        self.node_manager
+            .create_inlet_with_resource_type(
+                context,
+                SocketAddr::new(bind_ip, server_bootstrap_port).to_string(),
+                route![local_interceptor_address.clone()],
+                route![
+                    KAFKA_OUTLET_INTERCEPTOR_ADDRESS,
+                    KAFKA_OUTLET_BOOTSTRAP_ADDRESS
+                ],
+                "/secure/api".parse()?,
+                random_name(),
+                ResourceType::KafkaInlet,
+                outlet_policy_expression,
+                None,
+                None,
+                true,
+                None,
+                TcpPortalLimits::default(),
+            )
+            .await?;
 
         KafkaPortalListener::create(
             context,
@@ -333,22 +345,26 @@ impl InMemoryNode {
 
         // since we cannot call APIs of node manager via message due to the read/write lock
         // we need to call it directly
-        self.create_inlet(
-            context,
-            SocketAddr::new(bind_ip, server_bootstrap_port).to_string(),
-            route![local_interceptor_address.clone()],
-            route![
-                KAFKA_OUTLET_INTERCEPTOR_ADDRESS,
-                KAFKA_OUTLET_BOOTSTRAP_ADDRESS
-            ],
-            outlet_node_multiaddr,
-            inlet_alias,
-            inlet_policy_expression,
-            None,
-            None,
-            true,
-        )
-        .await?;
+        self.node_manager
+            .create_inlet_with_resource_type(
+                context,
+                SocketAddr::new(bind_ip, server_bootstrap_port).to_string(),
+                route![local_interceptor_address.clone()],
+                route![
+                    KAFKA_OUTLET_INTERCEPTOR_ADDRESS,
+                    KAFKA_OUTLET_BOOTSTRAP_ADDRESS
+                ],
+                outlet_node_multiaddr,
+                inlet_alias,
+                ResourceType::KafkaInlet,
+                inlet_policy_expression,
+                None,
+                None,
+                true,
+                None,
+                TcpPortalLimits::default(),
+            )
+            .await?;
 
         KafkaPortalListener::create(
             context,
@@ -395,10 +411,12 @@ impl NodeManager {
             .ok_or(ApiError::core("NodeManager has no authority"))?;
         let outlet_policy_expression = None;
 
+        let interceptor_incoming_ac = self
+            .kafka_outlet_interceptor_access_control(context, project_authority)
+            .await?;
         OutletManagerService::create(
             context,
-            self.secure_channels.clone(),
-            project_authority,
+            interceptor_incoming_ac,
             default_secure_channel_listener_flow_control_id,
             outlet_policy_expression.clone(),
         )
@@ -411,7 +429,10 @@ impl NodeManager {
                 false,
                 Some(KAFKA_OUTLET_BOOTSTRAP_ADDRESS.into()),
                 false,
-                OutletAccessControl::PolicyExpression(outlet_policy_expression),
+                OutletAccessControl::PolicyExpression(
+                    ResourceType::KafkaOutlet,
+                    outlet_policy_expression,
+                ),
             )
             .await
         {
@@ -431,6 +452,26 @@ impl NodeManager {
         Ok(())
     }
 
+    /// Return the access control of the Kafka outlet interceptor, which accepts the
+    /// connections of the Kafka clients.
+    /// It uses the policy set for the Kafka outlet resource type unless a policy is set for the interceptor
+    async fn kafka_outlet_interceptor_access_control(
+        &self,
+        context: &Context,
+        project_authority: Identifier,
+    ) -> Result<Arc<dyn IncomingAccessControl>> {
+        let (incoming_ac, _) = self
+            .access_control(
+                context,
+                Some(project_authority),
+                Resource::new(KAFKA_OUTLET_INTERCEPTOR_ADDRESS, ResourceType::KafkaOutlet),
+                Action::HandleMessage,
+                None,
+            )
+            .await?;
+        Ok(incoming_ac)
+    }
+
     /// Delete a Kafka service from the registry.
     /// The expected kind must match the actual kind
     pub async fn delete_kafka_service(
@@ -1,26 +1,27 @@
 use crate::cloud::project::Project;
 use crate::cloud::{AuthorityNodeClient, CredentialsEnabled, ProjectNodeClient};
 use crate::nodes::connection::{
-    Connection, ConnectionBuilder, PlainTcpInstantiator, ProjectInstantiator,
-    SecureChannelInstantiator,
+    Connection, ConnectionBuilder, PlainTcpInstantiator, PlainTransportInstantiator,
+    ProjectInstantiator, SecureChannelInstantiator,
 };
-use crate::nodes::models::portal::{OutletList, OutletStatus};
+use crate::nodes::models::portal::{OutletList, OutletStatus, PortalStats};
 use crate::nodes::models::transport::{TransportMode, TransportType};
 use crate::nodes::registry::Registry;
 use crate::nodes::service::{
-    random_alias, CredentialRetrieverCreators, NodeManagerCredentialRetrieverOptions,
-    NodeManagerTrustOptions,
+    random_alias, CredentialRetrieverCreators, MetricsEndpoint,
+    NodeManagerCredentialRetrieverOptions, NodeManagerTrustOptions,
 };
 use crate::session::MedicHandle;
 use crate::{CliState, DefaultAddress};
 use miette::IntoDiagnostic;
 use ockam::identity::{
     CachedCredentialRetrieverCreator, CredentialRetrieverCreator, Identifier,
-    MemoryCredentialRetrieverCreator, RemoteCredentialRetrieverCreator, SecureChannels,
+    MemoryCredentialRetrieverCreator, RemoteCredentialRetrieverCreator,
+    RemoteRevocationListRetriever, SecureChannels, DEFAULT_REVOCATION_LIST_REFRESH_INTERVAL,
 };
 use ockam::{RelayService, RelayServiceOptions};
 use ockam_abac::expr::str;
-use ockam_abac::{Action, Env, Expr, Policies, Resource, Resources};
+use ockam_abac::{Action, Env, Expr, Policies, Resource, ResourceType, Resources};
 use ockam_core::flow_control::FlowControlId;
 use ockam_core::{
     AllowAll, AsyncTryClone, CachedIncomingAccessControl, CachedOutgoingAccessControl,
@@ -29,6 +30,7 @@ use ockam_core::{
 use ockam_multiaddr::MultiAddr;
 use ockam_node::Context;
 use ockam_transport_tcp::TcpTransport;
+use ockam_transport_udp::UdpTransport;
 use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -45,11 +47,16 @@ pub struct NodeManager {
     pub(super) node_identifier: Identifier,
     pub(super) api_transport_flow_control_id: FlowControlId,
     pub(crate) tcp_transport: TcpTransport,
+    /// The UDP transport is only created when it is used for the first time
+    pub(crate) udp_transport: tokio::sync::Mutex<Option<UdpTransport>>,
     pub(crate) secure_channels: Arc<SecureChannels>,
     pub(crate) credential_retriever_creators: CredentialRetrieverCreators,
     pub(super) project_authority: Option<Identifier>,
     pub(crate) registry: Arc<Registry>,
     pub(crate) medic_handle: MedicHandle,
+    pub(crate) revocation_list_retriever: Option<RemoteRevocationListRetriever>,
+    /// HTTP endpoint serving the metrics of the node, if it was requested
+    pub(crate) metrics_endpoint: Option<MetricsEndpoint>,
 }
 
 impl NodeManager {
@@ -80,10 +87,27 @@ impl NodeManager {
         self.node_name.clone()
     }
 
+    /// Return the address of the HTTP endpoint serving the metrics of the node, if it was started
+    pub fn metrics_address(&self) -> Option<SocketAddr> {
+        self.metrics_endpoint.as_ref().map(|e| e.address())
+    }
+
     pub fn tcp_transport(&self) -> &TcpTransport {
         &self.tcp_transport
     }
 
+    /// Return the UDP transport of the node, creating it if it does not exist yet
+    pub async fn udp_transport(&self, ctx: &Context) -> ockam_core::Result<UdpTransport> {
+        let mut udp_transport = self.udp_transport.lock().await;
+        if let Some(udp_transport) = udp_transport.as_ref() {
+            return udp_transport.async_try_clone().await;
+        }
+        let created = UdpTransport::create(ctx).await?;
+        let result = created.async_try_clone().await?;
+        *udp_transport = Some(created);
+        Ok(result)
+    }
+
     pub async fn list_outlets(&self) -> OutletList {
         OutletList::new(
             self.registry
@@ -93,6 +117,7 @@ impl NodeManager {
                 .iter()
                 .map(|(_, info)| {
                     OutletStatus::new(info.socket_addr, info.worker_addr.clone(), None)
+                        .with_stats(PortalStats::from(&info.stats))
                 })
                 .collect(),
         )
@@ -175,10 +200,7 @@ impl NodeManager {
         let resource_type_str = resource.resource_type.to_string();
         let action_str = action.as_ref();
         if let Some(authority) = authority {
-            // Populate environment with known attributes:
-            let mut env = Env::new();
-            env.put("resource.id", str(resource_name_str));
-            env.put("action.id", str(action_str));
+            let env = Self::policy_environment(&resource, &action);
 
             // Store policy for the given resource and action
             let policies = self.policies();
@@ -222,6 +244,14 @@ impl NodeManager {
         }
     }
 
+    /// Populate an environment with the known attributes of a resource and an action
+    pub(super) fn policy_environment(resource: &Resource, action: &Action) -> Env {
+        let mut env = Env::new();
+        env.put("resource.id", str(resource.resource_name.as_str()));
+        env.put("action.id", str(action.as_ref()));
+        env
+    }
+
     pub fn policies(&self) -> Policies {
         self.cli_state.policies(&self.node_name)
     }
@@ -237,6 +267,7 @@ pub struct NodeManagerGeneralOptions {
     pub(super) node_name: String,
     pub(super) start_default_services: bool,
     pub(super) persistent: bool,
+    pub(super) metrics_address: Option<SocketAddr>,
 }
 
 impl NodeManagerGeneralOptions {
@@ -251,8 +282,15 @@ impl NodeManagerGeneralOptions {
             node_name,
             start_default_services,
             persistent,
+            metrics_address: None,
         }
     }
+
+    /// Serve the metrics of the node over HTTP on the given address, see [`MetricsEndpoint`]
+    pub fn with_metrics_address(mut self, metrics_address: Option<SocketAddr>) -> Self {
+        self.metrics_address = metrics_address;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -322,6 +360,26 @@ impl NodeManager {
             .store_default_resource_type_policies()
             .await?;
 
+        let revocation_list_retriever =
+            match &trust_options.project_member_credential_retriever_options {
+                NodeManagerCredentialRetrieverOptions::Remote { info, .. } => {
+                    debug!("start the revocation list retriever");
+                    let retriever = RemoteRevocationListRetriever::new(
+                        ctx.async_try_clone().await?,
+                        Arc::new(transport_options.tcp_transport.clone()),
+                        secure_channels.clone(),
+                        info.issuer.clone(),
+                        info.route.clone(),
+                        DefaultAddress::REVOCATION_LIST_ISSUER.to_string(),
+                        node_identifier.clone(),
+                        DEFAULT_REVOCATION_LIST_REFRESH_INTERVAL,
+                    );
+                    retriever.start();
+                    Some(retriever)
+                }
+                _ => None,
+            };
+
         let project_member_credential_retriever_creator: Option<
             Arc<dyn CredentialRetrieverCreator>,
         > = match trust_options.project_member_credential_retriever_options {
@@ -331,6 +389,7 @@ impl NodeManager {
                     issuer.clone(),
                     scope,
                     secure_channels.identities().cached_credentials_repository(),
+                    secure_channels.identities().revocation_repository(),
                 )))
             }
             NodeManagerCredentialRetrieverOptions::Remote { info, scope } => {
@@ -356,6 +415,7 @@ impl NodeManager {
                     issuer.clone(),
                     scope,
                     secure_channels.identities().cached_credentials_repository(),
+                    secure_channels.identities().revocation_repository(),
                 )))
             }
             NodeManagerCredentialRetrieverOptions::Remote { info, scope } => {
@@ -378,17 +438,28 @@ impl NodeManager {
             _account_admin: None,
         };
 
+        let metrics_endpoint = match general_options.metrics_address {
+            Some(address) => {
+                debug!("start the metrics endpoint");
+                Some(MetricsEndpoint::start(address)?)
+            }
+            None => None,
+        };
+
         let mut s = Self {
             cli_state,
             node_name,
             node_identifier,
             api_transport_flow_control_id: transport_options.api_transport_flow_control_id,
             tcp_transport: transport_options.tcp_transport,
+            udp_transport: Default::default(),
             secure_channels,
             credential_retriever_creators,
             project_authority: trust_options.project_authority,
             registry,
             medic_handle,
+            revocation_list_retriever,
+            metrics_endpoint,
         };
 
         debug!("initializing services");
@@ -410,12 +481,22 @@ impl NodeManager {
         self.start_uppercase_service_impl(ctx, DefaultAddress::UPPERCASE_SERVICE.into())
             .await?;
 
+        let (relay_service_incoming_ac, _) = self
+            .access_control(
+                ctx,
+                self.project_authority(),
+                Resource::new(DefaultAddress::RELAY_SERVICE, ResourceType::Relay),
+                Action::HandleMessage,
+                None,
+            )
+            .await?;
         RelayService::create(
             ctx,
             DefaultAddress::RELAY_SERVICE,
             RelayServiceOptions::new()
                 .service_as_consumer(api_flow_control_id)
-                .relay_as_consumer(api_flow_control_id),
+                .relay_as_consumer(api_flow_control_id)
+                .with_service_incoming_access_control(relay_service_incoming_ac),
         )
         .await?;
 
@@ -423,6 +504,7 @@ impl NodeManager {
             DefaultAddress::SECURE_CHANNEL_LISTENER.into(),
             None, // Not checking identifiers here in favor of credential check
             None,
+            None,
             ctx,
         )
         .await?;
@@ -485,6 +567,10 @@ impl NodeManager {
             .await?
             .instantiate(ctx.clone(), self, PlainTcpInstantiator::new())
             .await?
+            .instantiate(ctx.clone(), self, PlainTransportInstantiator::socket())
+            .await?
+            .instantiate(ctx.clone(), self, PlainTransportInstantiator::unix())
+            .await?
             .instantiate(
                 ctx.clone(),
                 self,
@@ -0,0 +1,450 @@
+use std::sync::Arc;
+
+use ockam::identity::{Identifier, IdentityIdAccessControl};
+use ockam::{Address, Result};
+use ockam_abac::{Action, Resource, ResourceType};
+use ockam_core::api::{Error, Response};
+use ockam_core::compat::time::now;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{
+    async_trait, route, AllowAll, AsyncTryClone, IncomingAccessControl, NeutralMessage,
+    OutgoingAccessControl, Route, LOCAL,
+};
+use ockam_multiaddr::MultiAddr;
+use ockam_node::Context;
+
+use crate::message_queues::{
+    MessageQueue, MessageQueueAddresses, MessageQueueConnector, MessageQueueOptions,
+    MessageQueueReceiver, MessageQueuesRepository,
+};
+use crate::nodes::connection::Connection;
+use crate::nodes::models::message_queue::{
+    CreateMessageQueue, EnqueueMessage, MessageQueueList, MessageQueueStatus,
+    StartMessageQueueReceiver,
+};
+use crate::nodes::registry::MessageQueueInfo;
+use crate::nodes::service::default_address::DefaultAddress;
+use crate::session::sessions::MAX_CONNECT_TIME;
+
+use super::{NodeManager, NodeManagerWorker};
+
+impl NodeManagerWorker {
+    pub(super) async fn get_message_queues(
+        &self,
+    ) -> Result<Response<MessageQueueList>, Response<Error>> {
+        match self.node_manager.list_message_queues().await {
+            Ok(queues) => Ok(Response::ok().body(queues)),
+            Err(e) => Err(Response::internal_error_no_request(&e.to_string())),
+        }
+    }
+
+    pub(super) async fn show_message_queue(
+        &self,
+        name: &str,
+    ) -> Result<Response<MessageQueueStatus>, Response<Error>> {
+        match self.node_manager.show_message_queue(name).await {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => match e.code().kind {
+                Kind::NotFound => Err(Response::not_found_no_request(&e.to_string())),
+                _ => Err(Response::internal_error_no_request(&e.to_string())),
+            },
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub(super) async fn create_message_queue(
+        &self,
+        ctx: &Context,
+        create_queue: CreateMessageQueue,
+    ) -> Result<Response<MessageQueueStatus>, Response<Error>> {
+        match self
+            .node_manager
+            .create_message_queue(ctx, create_queue)
+            .await
+        {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => Err(Response::bad_request_no_request(&format!("{e:?}"))),
+        }
+    }
+
+    pub(super) async fn start_message_queue_receiver(
+        &self,
+        ctx: &Context,
+        start_receiver: StartMessageQueueReceiver,
+    ) -> Result<Response, Response<Error>> {
+        match self
+            .node_manager
+            .start_message_queue_receiver(ctx, start_receiver)
+            .await
+        {
+            Ok(_) => Ok(Response::ok()),
+            Err(e) => Err(Response::bad_request_no_request(&e.to_string())),
+        }
+    }
+
+    pub(super) async fn enqueue_message(
+        &self,
+        ctx: &Context,
+        name: &str,
+        enqueue_message: EnqueueMessage,
+    ) -> Result<Response<MessageQueueStatus>, Response<Error>> {
+        match self
+            .node_manager
+            .enqueue_message(ctx, name, enqueue_message.payload)
+            .await
+        {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => match e.code().kind {
+                Kind::NotFound => Err(Response::not_found_no_request(&e.to_string())),
+                Kind::ResourceExhausted => Err(Response::bad_request_no_request(&e.to_string())),
+                _ => Err(Response::internal_error_no_request(&e.to_string())),
+            },
+        }
+    }
+
+    pub(super) async fn delete_message_queue(
+        &self,
+        ctx: &Context,
+        name: &str,
+    ) -> Result<Response<MessageQueueStatus>, Response<Error>> {
+        match self.node_manager.delete_message_queue(ctx, name).await {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => match e.code().kind {
+                Kind::NotFound => Err(Response::not_found_no_request(&e.to_string())),
+                _ => Err(Response::internal_error_no_request(&e.to_string())),
+            },
+        }
+    }
+}
+
+impl NodeManager {
+    /// Repository storing the messages of the queues of this node
+    pub fn message_queues_repository(&self) -> Arc<dyn MessageQueuesRepository> {
+        self.cli_state.message_queues_repository(&self.node_name)
+    }
+
+    /// Start the worker receiving the messages of the queues of other nodes,
+    /// and forwarding them to the given destinations.
+    ///
+    /// The receiver only accepts messages coming from a secure channel, sent by a project
+    /// member or by the `authorized` identity. If no policy expression is given, the policy
+    /// set for the message queue resource type is used for project members
+    pub async fn start_message_queue_receiver(
+        &self,
+        ctx: &Context,
+        start_receiver: StartMessageQueueReceiver,
+    ) -> Result<()> {
+        let StartMessageQueueReceiver {
+            destinations,
+            authorized,
+            policy_expression,
+        } = start_receiver;
+        let address: Address = DefaultAddress::MESSAGE_QUEUE_RECEIVER.into();
+        info!(
+            ?destinations,
+            "Handling request to start the message queue receiver"
+        );
+
+        if self
+            .registry
+            .message_queue_receivers
+            .contains_key(&address)
+            .await
+        {
+            let message = "The message queue receiver is already started";
+            return Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::AlreadyExists,
+                message,
+            ));
+        }
+        let destinations = destinations
+            .iter()
+            .map(|destination| match destination.parse::<Address>() {
+                Ok(address) if address.transport_type() == LOCAL => Ok(address),
+                _ => {
+                    let message = format!("The destination '{destination}' is not a local address");
+                    Err(ockam_core::Error::new(Origin::Node, Kind::Invalid, message))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let (incoming_ac, outgoing_ac): (
+            Arc<dyn IncomingAccessControl>,
+            Arc<dyn OutgoingAccessControl>,
+        ) = match (authorized, self.project_authority()) {
+            (Some(authorized), _) => (
+                Arc::new(IdentityIdAccessControl::new(vec![authorized])),
+                // the acknowledgements are only sent back to the nodes which
+                // passed the incoming access control
+                Arc::new(AllowAll),
+            ),
+            (None, Some(authority)) => {
+                self.access_control(
+                    ctx,
+                    Some(authority),
+                    Resource::new(address.address(), ResourceType::MessageQueue),
+                    Action::HandleMessage,
+                    policy_expression,
+                )
+                .await?
+            }
+            (None, None) => {
+                let message = "The message queue receiver requires an authorized identity when the node is not a project member";
+                return Err(ockam_core::Error::new(Origin::Node, Kind::Invalid, message));
+            }
+        };
+
+        // The receiver only receives messages via the secure channel listeners
+        for listener in self.registry.secure_channel_listeners.values().await {
+            ctx.flow_controls()
+                .add_consumer(address.clone(), listener.listener().flow_control_id());
+        }
+
+        MessageQueueReceiver::new(destinations.clone())
+            .start(ctx, address.clone(), incoming_ac, outgoing_ac)
+            .await?;
+        self.registry
+            .message_queue_receivers
+            .insert(address, destinations)
+            .await;
+        Ok(())
+    }
+
+    /// Create a queue storing the messages sent to its name and delivering them to
+    /// the `destination` address on the node at `to`, via its message queue receiver.
+    ///
+    /// The destination node does not need to be reachable when the queue is created:
+    /// the queue connects to it when it has messages to deliver, and connects again
+    /// when its deliveries are not acknowledged
+    #[instrument(skip_all)]
+    pub async fn create_message_queue(
+        self: &Arc<Self>,
+        ctx: &Context,
+        create_queue: CreateMessageQueue,
+    ) -> Result<MessageQueueStatus> {
+        let CreateMessageQueue {
+            name,
+            to,
+            destination,
+            authorized,
+            policy_expression,
+            max_depth,
+            initial_retry_delay,
+            max_retry_delay,
+        } = create_queue;
+        info!(%name, %to, %destination, "Handling request to create a message queue");
+
+        if self.registry.message_queues.contains_key(&name).await {
+            let message = format!("A message queue with name '{name}' already exists");
+            return Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::AlreadyExists,
+                message,
+            ));
+        }
+        let address = match name.parse::<Address>() {
+            Ok(address) if address.transport_type() == LOCAL => address,
+            _ => {
+                let message = format!("The message queue name '{name}' is not a local address");
+                return Err(ockam_core::Error::new(Origin::Node, Kind::Invalid, message));
+            }
+        };
+
+        let (incoming_ac, outgoing_ac) = self
+            .access_control(
+                ctx,
+                self.project_authority(),
+                Resource::new(name.clone(), ResourceType::MessageQueue),
+                Action::HandleMessage,
+                policy_expression,
+            )
+            .await?;
+
+        let addresses = MessageQueueAddresses::new(address);
+        let connector = NodeMessageQueueConnector {
+            node_manager: self.clone(),
+            context: Arc::new(ctx.async_try_clone().await?),
+            to: to.clone(),
+            authorized,
+            acks: addresses.acks().clone(),
+            connection: None,
+        };
+
+        let mut options = MessageQueueOptions::new()
+            .with_incoming_access_control(incoming_ac)
+            .with_outgoing_access_control(outgoing_ac);
+        if let Some(max_depth) = max_depth {
+            options = options.with_max_depth(max_depth);
+        }
+        if let Some(delay) = initial_retry_delay {
+            options = options.with_initial_retry_delay(delay);
+        }
+        if let Some(delay) = max_retry_delay {
+            options = options.with_max_retry_delay(delay);
+        }
+
+        MessageQueue::create(
+            ctx,
+            name.clone(),
+            addresses.clone(),
+            self.message_queues_repository(),
+            connector,
+            destination.clone(),
+            options.clone(),
+        )
+        .await?;
+
+        let info = MessageQueueInfo {
+            to,
+            destination,
+            addresses,
+            max_depth: options.max_depth(),
+        };
+        self.registry
+            .message_queues
+            .insert(name.clone(), info.clone())
+            .await;
+
+        self.message_queue_status(&name, &info).await
+    }
+
+    /// Stop a message queue and delete the messages it still stores
+    pub async fn delete_message_queue(
+        &self,
+        ctx: &Context,
+        name: &str,
+    ) -> Result<MessageQueueStatus> {
+        info!(%name, "Handling request to delete a message queue");
+        if let Some(queue) = self.registry.message_queues.remove(name).await {
+            let status = self.message_queue_status(name, &queue).await?;
+            // the queue closes its connection to the destination node when it stops
+            if let Err(e) = ctx.stop_worker(queue.addresses.main().clone()).await {
+                warn!(%name, %e, "Failed to stop the message queue worker");
+            }
+            self.message_queues_repository().delete_queue(name).await?;
+            self.resources().delete_resource(&name.into()).await?;
+            Ok(status)
+        } else {
+            Err(message_queue_not_found(name))
+        }
+    }
+
+    /// Store a message in a queue, to be delivered to its destination.
+    /// An error is returned if the message can not be stored, for example when the queue is full
+    pub async fn enqueue_message(
+        &self,
+        ctx: &Context,
+        name: &str,
+        payload: Vec<u8>,
+    ) -> Result<MessageQueueStatus> {
+        match self.registry.message_queues.get(name).await {
+            Some(queue) => {
+                MessageQueue::store_message(
+                    &self.message_queues_repository(),
+                    name,
+                    queue.max_depth,
+                    &payload,
+                )
+                .await?;
+                // deliver the message right away if possible
+                ctx.send(
+                    route![queue.addresses.retry().clone()],
+                    NeutralMessage::from(vec![]),
+                )
+                .await?;
+                self.message_queue_status(name, &queue).await
+            }
+            None => Err(message_queue_not_found(name)),
+        }
+    }
+
+    pub async fn show_message_queue(&self, name: &str) -> Result<MessageQueueStatus> {
+        match self.registry.message_queues.get(name).await {
+            Some(queue) => self.message_queue_status(name, &queue).await,
+            None => Err(message_queue_not_found(name)),
+        }
+    }
+
+    pub async fn list_message_queues(&self) -> Result<MessageQueueList> {
+        let mut list = vec![];
+        for (name, queue) in self.registry.message_queues.entries().await {
+            list.push(self.message_queue_status(&name, &queue).await?);
+        }
+        Ok(MessageQueueList::new(list))
+    }
+
+    /// Return the configuration of a queue, with its current depth and age
+    async fn message_queue_status(
+        &self,
+        name: &str,
+        queue: &MessageQueueInfo,
+    ) -> Result<MessageQueueStatus> {
+        let statistics = self
+            .message_queues_repository()
+            .get_queue_statistics(name)
+            .await?;
+        let now = now()?;
+        Ok(
+            MessageQueueStatus::new(name, queue.to.to_string(), &queue.destination).with_depth(
+                statistics.depth(),
+                statistics
+                    .oldest_enqueued_at()
+                    .map(|enqueued_at| now.saturating_sub(enqueued_at)),
+                statistics
+                    .next_attempt_at()
+                    .map(|next_attempt_at| next_attempt_at.saturating_sub(now)),
+            ),
+        )
+    }
+}
+
+/// Connects a message queue to the message queue receiver of the node at `to`
+struct NodeMessageQueueConnector {
+    node_manager: Arc<NodeManager>,
+    context: Arc<Context>,
+    to: MultiAddr,
+    authorized: Option<Identifier>,
+    acks: Address,
+
+    // current connection
+    connection: Option<Connection>,
+}
+
+#[async_trait]
+impl MessageQueueConnector for NodeMessageQueueConnector {
+    async fn connect(&mut self) -> Result<Route> {
+        self.disconnect().await;
+        debug!(to = %self.to, "connecting a message queue to its destination node");
+
+        let connection = self
+            .node_manager
+            .make_connection(
+                self.context.clone(),
+                &self.to,
+                self.node_manager.identifier(),
+                self.authorized.clone(),
+                Some(MAX_CONNECT_TIME),
+            )
+            .await?;
+        // The acknowledgements are received via the connection to the destination node
+        connection.add_consumer(self.context.clone(), &self.acks);
+        let route = connection.route();
+        self.connection = Some(connection);
+
+        Ok(route![route?, DefaultAddress::MESSAGE_QUEUE_RECEIVER])
+    }
+
+    async fn disconnect(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            if let Err(e) = connection.close(&self.context, &self.node_manager).await {
+                warn!(to = %self.to, %e, "Failed to close the connection of a message queue");
+            }
+        }
+    }
+}
+
+fn message_queue_not_found(name: &str) -> ockam_core::Error {
+    let message = format!("Message queue with name {name} not found");
+    ockam_core::Error::new(Origin::Node, Kind::NotFound, message)
+}
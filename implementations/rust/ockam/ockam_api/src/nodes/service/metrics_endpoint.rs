@@ -0,0 +1,123 @@
+use crate::ApiError;
+use ockam_core::env::get_env;
+use ockam_node::metrics::{MetricsRegistry, PROMETHEUS_CONTENT_TYPE};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Socket address where a node serves its metrics, for example: 127.0.0.1:9464.
+/// The metrics endpoint is not started if this variable is not set
+pub const OCKAM_METRICS_ADDRESS: &str = "OCKAM_METRICS_ADDRESS";
+
+/// Path of the metrics endpoint
+const METRICS_PATH: &str = "/metrics";
+
+/// HTTP endpoint serving the metrics of the process on `/metrics`,
+/// in the Prometheus text exposition format.
+///
+/// The endpoint is stopped when this value is dropped
+pub struct MetricsEndpoint {
+    server: Arc<Server>,
+    address: SocketAddr,
+}
+
+impl MetricsEndpoint {
+    /// Start serving the metrics on the given socket address
+    pub fn start(address: SocketAddr) -> ockam_core::Result<MetricsEndpoint> {
+        let server = Arc::new(Server::http(address).map_err(|e| {
+            ApiError::core(format!(
+                "failed to start the metrics endpoint on {address}: {e}"
+            ))
+        })?);
+        // the actual address is different if the port 0 was used
+        let address = server.server_addr().to_ip().unwrap_or(address);
+
+        let handle = server.clone();
+        thread::Builder::new()
+            .name("metrics-endpoint".to_string())
+            .spawn(move || {
+                for request in handle.incoming_requests() {
+                    respond(request);
+                }
+                debug!("the metrics endpoint is stopped");
+            })
+            .map_err(|e| ApiError::core(format!("failed to start the metrics endpoint: {e}")))?;
+
+        info!("serving the metrics on http://{address}{METRICS_PATH}");
+        Ok(MetricsEndpoint { server, address })
+    }
+
+    /// Socket address of the endpoint
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+/// Return the address of the metrics endpoint given by the `OCKAM_METRICS_ADDRESS`
+/// environment variable, if it is set
+pub fn metrics_address_from_env() -> ockam_core::Result<Option<SocketAddr>> {
+    match get_env::<String>(OCKAM_METRICS_ADDRESS)? {
+        Some(address) => Ok(Some(address.parse().map_err(|e| {
+            ApiError::core(format!(
+                "invalid {OCKAM_METRICS_ADDRESS} value '{address}': {e}"
+            ))
+        })?)),
+        None => Ok(None),
+    }
+}
+
+impl Drop for MetricsEndpoint {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+fn respond(request: Request) {
+    let path = request.url().split('?').next().unwrap_or_default();
+    let result = if request.method() == &Method::Get && path == METRICS_PATH {
+        let content_type = Header::from_bytes("Content-Type", PROMETHEUS_CONTENT_TYPE)
+            .expect("the content type header is valid");
+        let body = MetricsRegistry::global().encode_prometheus();
+        request.respond(Response::from_string(body).with_header(content_type))
+    } else {
+        request.respond(Response::from_string("Not Found").with_status_code(404))
+    };
+    if let Err(e) = result {
+        debug!("cannot send a response from the metrics endpoint: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    #[test]
+    fn test_metrics_endpoint() {
+        MetricsRegistry::global()
+            .counter("ockam_test_endpoint_total", "Test counter", &[])
+            .inc();
+        let endpoint = MetricsEndpoint::start("127.0.0.1:0".parse().unwrap()).unwrap();
+
+        let response = get(endpoint.address(), METRICS_PATH);
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("ockam_test_endpoint_total 1"));
+
+        let response = get(endpoint.address(), "/other");
+        assert!(response.starts_with("HTTP/1.1 404"));
+    }
+
+    fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+}
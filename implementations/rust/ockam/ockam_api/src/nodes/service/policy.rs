@@ -1,10 +1,15 @@
-use ockam_abac::{Action, Expr};
+use ockam::identity::Identifier;
+use ockam_abac::{Action, Expr, PolicyDecision, PolicyExplanation, ResourceName};
 use ockam_core::api::{Error, Request, Response};
+use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{async_trait, Result};
 use ockam_node::Context;
 use std::str::FromStr;
 
-use crate::nodes::models::policies::{PoliciesList, Policy, ResourceTypeOrName, SetPolicyRequest};
+use crate::nodes::models::policies::{
+    ExplainPolicyRequest, PoliciesList, Policy, PolicyDecisionsRequest, ResourceTypeOrName,
+    SetPolicyRequest,
+};
 use crate::nodes::{BackgroundNodeClient, NodeManagerWorker};
 
 use super::NodeManager;
@@ -57,6 +62,29 @@ impl NodeManagerWorker {
             Err(e) => Err(Response::internal_error_no_request(&e.to_string())),
         }
     }
+
+    pub(super) async fn get_policy_decisions(
+        &self,
+        request: PolicyDecisionsRequest,
+    ) -> Result<Response<Vec<PolicyDecision>>, Response<Error>> {
+        match self.node_manager.get_policy_decisions(request).await {
+            Ok(decisions) => Ok(Response::ok().body(decisions)),
+            Err(e) => Err(Response::internal_error_no_request(&e.to_string())),
+        }
+    }
+
+    pub(super) async fn explain_policy(
+        &self,
+        request: ExplainPolicyRequest,
+    ) -> Result<Response<PolicyExplanation>, Response<Error>> {
+        match self.node_manager.explain_policy(request).await {
+            Ok(explanation) => Ok(Response::ok().body(explanation)),
+            Err(e) if e.code().kind == Kind::NotFound => {
+                Err(Response::not_found_no_request(&e.to_string()))
+            }
+            Err(e) => Err(Response::internal_error_no_request(&e.to_string())),
+        }
+    }
 }
 
 impl NodeManager {
@@ -144,6 +172,55 @@ impl NodeManager {
             }
         }
     }
+
+    /// Return the most recent decisions taken by the policy access controls of this node
+    pub async fn get_policy_decisions(
+        &self,
+        request: PolicyDecisionsRequest,
+    ) -> Result<Vec<PolicyDecision>> {
+        self.policies()
+            .get_policy_decisions(
+                request.resource_name.as_ref(),
+                request.identifier.as_ref(),
+                request.only_denied,
+                request.limit,
+            )
+            .await
+    }
+
+    /// Evaluate the policy of a resource for a given identity, as it would be evaluated
+    /// by the access control of that resource, and return each step of the evaluation
+    pub async fn explain_policy(&self, request: ExplainPolicyRequest) -> Result<PolicyExplanation> {
+        let authority = self.project_authority().ok_or_else(|| {
+            ockam_core::Error::new(
+                Origin::Api,
+                Kind::NotFound,
+                "policies are only evaluated on nodes having a project authority",
+            )
+        })?;
+        let resource = self
+            .resources()
+            .get_resource(&request.resource_name)
+            .await?
+            .ok_or_else(|| {
+                ockam_core::Error::new(
+                    Origin::Api,
+                    Kind::NotFound,
+                    format!("the resource {} does not exist", request.resource_name),
+                )
+            })?;
+        let env = Self::policy_environment(&resource, &request.action);
+        self.policies()
+            .make_policy_access_control(
+                self.cli_state.identities_attributes(&self.node_name),
+                resource,
+                request.action,
+                env,
+                authority,
+            )
+            .explain(&request.identifier)
+            .await
+    }
 }
 
 pub fn policy_path(a: &Action) -> String {
@@ -179,6 +256,23 @@ pub trait Policies {
         resource: &ResourceTypeOrName,
         action: &Action,
     ) -> miette::Result<()>;
+
+    async fn list_policy_decisions(
+        &self,
+        ctx: &Context,
+        resource_name: Option<&ResourceName>,
+        identifier: Option<&Identifier>,
+        only_denied: bool,
+        limit: u32,
+    ) -> miette::Result<Vec<PolicyDecision>>;
+
+    async fn explain_policy(
+        &self,
+        ctx: &Context,
+        resource_name: &ResourceName,
+        action: &Action,
+        identifier: &Identifier,
+    ) -> miette::Result<PolicyExplanation>;
 }
 
 #[async_trait]
@@ -225,4 +319,35 @@ impl Policies for BackgroundNodeClient {
         self.tell(ctx, request).await?;
         Ok(())
     }
+
+    async fn list_policy_decisions(
+        &self,
+        ctx: &Context,
+        resource_name: Option<&ResourceName>,
+        identifier: Option<&Identifier>,
+        only_denied: bool,
+        limit: u32,
+    ) -> miette::Result<Vec<PolicyDecision>> {
+        let payload = PolicyDecisionsRequest::new(
+            resource_name.cloned(),
+            identifier.cloned(),
+            only_denied,
+            limit,
+        );
+        let request = Request::get("/policy/decisions").body(payload);
+        self.ask(ctx, request).await
+    }
+
+    async fn explain_policy(
+        &self,
+        ctx: &Context,
+        resource_name: &ResourceName,
+        action: &Action,
+        identifier: &Identifier,
+    ) -> miette::Result<PolicyExplanation> {
+        let payload =
+            ExplainPolicyRequest::new(resource_name.clone(), action.clone(), identifier.clone());
+        let request = Request::get("/policy/explain").body(payload);
+        self.ask(ctx, request).await
+    }
 }
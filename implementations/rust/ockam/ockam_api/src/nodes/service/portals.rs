@@ -15,13 +15,16 @@ use ockam_core::{async_trait, route, AsyncTryClone, Route};
 use ockam_multiaddr::proto::Project as ProjectProto;
 use ockam_multiaddr::{MultiAddr, Protocol};
 use ockam_node::Context;
-use ockam_transport_tcp::{HostnamePort, TcpInletOptions, TcpOutletOptions};
+use ockam_transport_tcp::{
+    HostnamePort, TcpInletOptions, TcpInletTls, TcpOutletOptions, TcpPortalLimits, TcpPortalStats,
+    TcpProxyConfig,
+};
 
 use crate::error::ApiError;
 use crate::nodes::connection::Connection;
 use crate::nodes::models::portal::{
-    CreateInlet, CreateOutlet, InletList, InletStatus, OutletAccessControl, OutletList,
-    OutletStatus,
+    CreateInlet, CreateOutlet, InletList, InletStatus, InletTls, OutletAccessControl, OutletList,
+    OutletStatus, PortalConnectionList, PortalConnectionStatus, PortalStats,
 };
 use crate::nodes::registry::{InletInfo, OutletInfo};
 use crate::nodes::service::default_address::DefaultAddress;
@@ -57,20 +60,33 @@ impl NodeManagerWorker {
             wait_for_outlet_duration,
             policy_expression,
             wait_connection,
+            resource_type,
+            tls,
+            limits,
         } = create_inlet;
+        let tls = match tls {
+            Some(tls) => match self.node_manager.inlet_tls(tls).await {
+                Ok(tls) => Some(tls),
+                Err(e) => return Err(Response::bad_request_no_request(&format!("{e:?}"))),
+            },
+            None => None,
+        };
         match self
             .node_manager
-            .create_inlet(
+            .create_inlet_with_resource_type(
                 ctx,
                 listen_addr,
                 prefix_route,
                 suffix_route,
                 outlet_addr,
                 alias,
+                resource_type.unwrap_or(ResourceType::TcpInlet),
                 policy_expression,
                 wait_for_outlet_duration,
                 authorized,
                 wait_connection,
+                tls,
+                limits.unwrap_or_default(),
             )
             .await
         {
@@ -100,6 +116,30 @@ impl NodeManagerWorker {
             ))),
         }
     }
+
+    pub(super) async fn get_inlet_connections(
+        &self,
+        alias: &str,
+    ) -> Result<Response<PortalConnectionList>, Response<Error>> {
+        match self.node_manager.list_inlet_connections(alias).await {
+            Some(connections) => Ok(Response::ok().body(connections)),
+            None => Err(Response::not_found_no_request(&format!(
+                "Inlet with alias {alias} not found"
+            ))),
+        }
+    }
+
+    pub(super) async fn close_inlet_connection(
+        &self,
+        alias: &str,
+        id: &str,
+    ) -> Result<Response<PortalConnectionStatus>, Response<Error>> {
+        let id = parse_connection_id(id)?;
+        match self.node_manager.close_inlet_connection(alias, id).await {
+            Ok(connection) => Ok(Response::ok().body(connection)),
+            Err(e) => Err(Response::not_found_no_request(&e.to_string())),
+        }
+    }
 }
 
 /// OUTLETS
@@ -116,17 +156,25 @@ impl NodeManagerWorker {
             reachable_from_default_secure_channel,
             policy_expression,
             tls,
+            resource_type,
+            proxy,
+            limits,
         } = create_outlet;
 
         match self
             .node_manager
-            .create_outlet(
+            .create_outlet_with_proxy(
                 ctx,
                 hostname_port,
                 tls,
+                proxy.unwrap_or_default(),
+                limits.unwrap_or_default(),
                 worker_addr,
                 reachable_from_default_secure_channel,
-                OutletAccessControl::PolicyExpression(policy_expression),
+                OutletAccessControl::PolicyExpression(
+                    resource_type.unwrap_or(ResourceType::TcpOutlet),
+                    policy_expression,
+                ),
             )
             .await
         {
@@ -141,11 +189,14 @@ impl NodeManagerWorker {
     ) -> Result<Response<OutletStatus>, Response<Error>> {
         match self.node_manager.delete_outlet(worker_addr).await {
             Ok(res) => match res {
-                Some(outlet_info) => Ok(Response::ok().body(OutletStatus::new(
-                    outlet_info.socket_addr,
-                    outlet_info.worker_addr.clone(),
-                    None,
-                ))),
+                Some(outlet_info) => Ok(Response::ok().body(
+                    OutletStatus::new(
+                        outlet_info.socket_addr,
+                        outlet_info.worker_addr.clone(),
+                        None,
+                    )
+                    .with_stats(PortalStats::from(&outlet_info.stats)),
+                )),
                 None => Err(Response::bad_request_no_request(&format!(
                     "Outlet with address {worker_addr} not found"
                 ))),
@@ -171,6 +222,39 @@ impl NodeManagerWorker {
             .with_headers(req)
             .body(self.node_manager.list_outlets().await)
     }
+
+    pub(super) async fn get_outlet_connections(
+        &self,
+        worker_addr: &Address,
+    ) -> Result<Response<PortalConnectionList>, Response<Error>> {
+        match self.node_manager.list_outlet_connections(worker_addr).await {
+            Some(connections) => Ok(Response::ok().body(connections)),
+            None => Err(Response::not_found_no_request(&format!(
+                "Outlet with address {worker_addr} not found"
+            ))),
+        }
+    }
+
+    pub(super) async fn close_outlet_connection(
+        &self,
+        worker_addr: &Address,
+        id: &str,
+    ) -> Result<Response<PortalConnectionStatus>, Response<Error>> {
+        let id = parse_connection_id(id)?;
+        match self
+            .node_manager
+            .close_outlet_connection(worker_addr, id)
+            .await
+        {
+            Ok(connection) => Ok(Response::ok().body(connection)),
+            Err(e) => Err(Response::not_found_no_request(&e.to_string())),
+        }
+    }
+}
+
+fn parse_connection_id(id: &str) -> Result<u64, Response<Error>> {
+    id.parse()
+        .map_err(|_| Response::bad_request_no_request(&format!("Invalid connection id {id}")))
 }
 
 /// OUTLETS
@@ -186,6 +270,34 @@ impl NodeManager {
         worker_addr: Option<Address>,
         reachable_from_default_secure_channel: bool,
         access_control: OutletAccessControl,
+    ) -> Result<OutletStatus> {
+        self.create_outlet_with_proxy(
+            ctx,
+            hostname_port,
+            tls,
+            TcpProxyConfig::default(),
+            TcpPortalLimits::default(),
+            worker_addr,
+            reachable_from_default_secure_channel,
+            access_control,
+        )
+        .await
+    }
+
+    /// Create an outlet connecting to its destination with the given proxy configuration,
+    /// and limiting its connections with the given limits
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all)]
+    pub async fn create_outlet_with_proxy(
+        &self,
+        ctx: &Context,
+        hostname_port: HostnamePort,
+        tls: bool,
+        proxy: TcpProxyConfig,
+        limits: TcpPortalLimits,
+        worker_addr: Option<Address>,
+        reachable_from_default_secure_channel: bool,
+        access_control: OutletAccessControl,
     ) -> Result<OutletStatus> {
         let worker_addr = self
             .registry
@@ -213,11 +325,11 @@ impl NodeManager {
             OutletAccessControl::AccessControl((incoming_ac, outgoing_ac)) => {
                 (incoming_ac, outgoing_ac)
             }
-            OutletAccessControl::PolicyExpression(expression) => {
+            OutletAccessControl::PolicyExpression(resource_type, expression) => {
                 self.access_control(
                     ctx,
                     self.project_authority(),
-                    Resource::new(worker_addr.address(), ResourceType::TcpOutlet),
+                    Resource::new(worker_addr.address(), resource_type),
                     Action::HandleMessage,
                     expression,
                 )
@@ -225,11 +337,15 @@ impl NodeManager {
             }
         };
 
+        let stats = TcpPortalStats::default();
         let options = {
             let options = TcpOutletOptions::new()
                 .with_incoming_access_control(incoming_ac)
                 .with_outgoing_access_control(outgoing_ac)
-                .with_tls(tls);
+                .with_tls(tls)
+                .with_proxy_config(proxy)
+                .with_limits(limits)
+                .with_stats(stats.clone());
             let options = if self.project_authority().is_none() {
                 options.as_consumer(&self.api_transport_flow_control_id)
             } else {
@@ -262,11 +378,12 @@ impl NodeManager {
                     .outlets
                     .insert(
                         worker_addr.clone(),
-                        OutletInfo::new(&socket_addr, Some(&worker_addr)),
+                        OutletInfo::new(&socket_addr, Some(&worker_addr)).with_stats(stats.clone()),
                     )
                     .await;
 
                 OutletStatus::new(socket_addr, worker_addr, None)
+                    .with_stats(PortalStats::from(&stats))
             }
             Err(e) => {
                 warn!(at = %socket_addr, err = %e, "Failed to create TCP outlet");
@@ -308,16 +425,44 @@ impl NodeManager {
         info!(%worker_addr, "Handling request to show outlet portal");
         if let Some(outlet_to_show) = self.registry.outlets.get(worker_addr).await {
             debug!(%worker_addr, "Outlet not found in node registry");
-            Some(OutletStatus::new(
-                outlet_to_show.socket_addr,
-                outlet_to_show.worker_addr.clone(),
-                None,
-            ))
+            Some(
+                OutletStatus::new(
+                    outlet_to_show.socket_addr,
+                    outlet_to_show.worker_addr.clone(),
+                    None,
+                )
+                .with_stats(PortalStats::from(&outlet_to_show.stats)),
+            )
         } else {
             error!(%worker_addr, "Outlet not found in the node registry");
             None
         }
     }
+
+    pub async fn list_outlet_connections(
+        &self,
+        worker_addr: &Address,
+    ) -> Option<PortalConnectionList> {
+        let outlet = self.registry.outlets.get(worker_addr).await?;
+        Some(PortalConnectionList::from(&outlet.stats))
+    }
+
+    /// Close a live connection of an outlet and return its last status
+    pub async fn close_outlet_connection(
+        &self,
+        worker_addr: &Address,
+        id: u64,
+    ) -> Result<PortalConnectionStatus> {
+        info!(%worker_addr, %id, "Handling request to close an outlet connection");
+        match self.registry.outlets.get(worker_addr).await {
+            Some(outlet) => close_connection(&outlet.stats, id),
+            None => Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::NotFound,
+                format!("Outlet with address {worker_addr} not found"),
+            )),
+        }
+    }
 }
 
 /// INLETS
@@ -336,6 +481,45 @@ impl NodeManager {
         wait_for_outlet_duration: Option<Duration>,
         authorized: Option<Identifier>,
         wait_connection: bool,
+    ) -> Result<InletStatus> {
+        self.create_inlet_with_resource_type(
+            ctx,
+            listen_addr,
+            prefix_route,
+            suffix_route,
+            outlet_addr,
+            alias,
+            ResourceType::TcpInlet,
+            policy_expression,
+            wait_for_outlet_duration,
+            authorized,
+            wait_connection,
+            None,
+            TcpPortalLimits::default(),
+        )
+        .await
+    }
+
+    /// Create an inlet whose access control uses the policy of the given resource type
+    /// when no policy expression is given, which terminates the TLS connections of
+    /// its clients if `tls` is set, and which limits its connections with the given limits
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all)]
+    pub async fn create_inlet_with_resource_type(
+        self: &Arc<Self>,
+        ctx: &Context,
+        listen_addr: String,
+        prefix_route: Route,
+        suffix_route: Route,
+        outlet_addr: MultiAddr,
+        alias: String,
+        resource_type: ResourceType,
+        policy_expression: Option<Expr>,
+        wait_for_outlet_duration: Option<Duration>,
+        authorized: Option<Identifier>,
+        wait_connection: bool,
+        tls: Option<TcpInletTls>,
+        limits: TcpPortalLimits,
     ) -> Result<InletStatus> {
         info!("Handling request to create inlet portal");
         debug! {
@@ -390,6 +574,8 @@ impl NodeManager {
             }
         }
 
+        // The stats are kept when the inlet is re-created by the session replacer
+        let stats = TcpPortalStats::default();
         let replacer = InletSessionReplacer {
             node_manager: self.clone(),
             context: Arc::new(ctx.async_try_clone().await?),
@@ -399,8 +585,11 @@ impl NodeManager {
             suffix_route,
             authorized,
             wait_for_outlet_duration: wait_for_outlet_duration.unwrap_or(MAX_CONNECT_TIME),
-            resource: Resource::new(alias.clone(), ResourceType::TcpInlet),
+            resource: Resource::new(alias.clone(), resource_type),
             policy_expression,
+            tls,
+            limits,
+            stats: stats.clone(),
             connection: None,
             inlet_address: None,
         };
@@ -432,7 +621,8 @@ impl NodeManager {
             .inlets
             .insert(
                 alias.clone(),
-                InletInfo::new(&listen_addr, outlet_addr.clone(), session),
+                InletInfo::new(&listen_addr, outlet_addr.clone(), session)
+                    .with_stats(stats.clone()),
             )
             .await;
 
@@ -447,7 +637,46 @@ impl NodeManager {
                 .map(|s| s.connection_status)
                 .unwrap_or(ConnectionStatus::Down),
             outlet_addr.to_string(),
-        ))
+        )
+        .with_stats(PortalStats::from(&stats)))
+    }
+
+    /// Create the TLS configuration of an inlet. When no certificate is given, a
+    /// self-signed certificate is generated for `localhost` and the node identifier.
+    /// Its private key is stored with the node, so that the certificate stays the same
+    /// across restarts and can be trusted once by the clients
+    pub async fn inlet_tls(&self, tls: InletTls) -> Result<TcpInletTls> {
+        let tcp_inlet_tls = match (tls.certificate_chain, tls.private_key) {
+            (Some(certificate_chain), Some(private_key)) => {
+                TcpInletTls::from_pem(certificate_chain.as_bytes(), private_key.as_bytes())?
+            }
+            (None, None) => {
+                let private_key = self
+                    .cli_state
+                    .get_or_create_node_tls_private_key(&self.node_name)
+                    .await?;
+                TcpInletTls::self_signed_with_private_key(
+                    vec![
+                        "localhost".to_string(),
+                        self.identifier().to_string().to_lowercase(),
+                    ],
+                    private_key.as_bytes(),
+                )?
+            }
+            _ => {
+                return Err(ockam_core::Error::new(
+                    Origin::Node,
+                    Kind::Invalid,
+                    "A TLS certificate chain and its private key must be provided together",
+                ))
+            }
+        };
+        match tls.client_certificate_authorities {
+            Some(certificate_authorities) => {
+                tcp_inlet_tls.with_client_authentication(certificate_authorities.as_bytes())
+            }
+            None => Ok(tcp_inlet_tls),
+        }
     }
 
     pub async fn delete_inlet(&self, alias: &str) -> Result<InletStatus> {
@@ -464,7 +693,8 @@ impl NodeManager {
                 None,
                 ConnectionStatus::Down,
                 inlet_to_delete.outlet_addr.to_string(),
-            ))
+            )
+            .with_stats(PortalStats::from(&inlet_to_delete.stats)))
         } else {
             error!(%alias, "Inlet not found in the node registry");
             let message = format!("Inlet with alias {alias} not found");
@@ -481,28 +711,34 @@ impl NodeManager {
         if let Some(inlet_info) = self.registry.inlets.get(alias).await {
             if let Some(status) = inlet_info.session.status() {
                 if let ReplacerOutputKind::Inlet(status) = &status.kind {
-                    Some(InletStatus::new(
-                        inlet_info.bind_addr.to_string(),
-                        status.worker.address().to_string(),
-                        alias,
-                        None,
-                        status.route.to_string(),
-                        status.connection_status,
-                        inlet_info.outlet_addr.to_string(),
-                    ))
+                    Some(
+                        InletStatus::new(
+                            inlet_info.bind_addr.to_string(),
+                            status.worker.address().to_string(),
+                            alias,
+                            None,
+                            status.route.to_string(),
+                            status.connection_status,
+                            inlet_info.outlet_addr.to_string(),
+                        )
+                        .with_stats(PortalStats::from(&inlet_info.stats)),
+                    )
                 } else {
                     panic!("Unexpected outcome: {:?}", status.kind)
                 }
             } else {
-                Some(InletStatus::new(
-                    inlet_info.bind_addr.to_string(),
-                    None,
-                    alias,
-                    None,
-                    None,
-                    ConnectionStatus::Down,
-                    inlet_info.outlet_addr.to_string(),
-                ))
+                Some(
+                    InletStatus::new(
+                        inlet_info.bind_addr.to_string(),
+                        None,
+                        alias,
+                        None,
+                        None,
+                        ConnectionStatus::Down,
+                        inlet_info.outlet_addr.to_string(),
+                    )
+                    .with_stats(PortalStats::from(&inlet_info.stats)),
+                )
             }
         } else {
             error!(%alias, "Inlet not found in the node registry");
@@ -510,6 +746,28 @@ impl NodeManager {
         }
     }
 
+    pub async fn list_inlet_connections(&self, alias: &str) -> Option<PortalConnectionList> {
+        let inlet = self.registry.inlets.get(alias).await?;
+        Some(PortalConnectionList::from(&inlet.stats))
+    }
+
+    /// Close a live connection of an inlet and return its last status
+    pub async fn close_inlet_connection(
+        &self,
+        alias: &str,
+        id: u64,
+    ) -> Result<PortalConnectionStatus> {
+        info!(%alias, %id, "Handling request to close an inlet connection");
+        match self.registry.inlets.get(alias).await {
+            Some(inlet) => close_connection(&inlet.stats, id),
+            None => Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::NotFound,
+                format!("Inlet with alias {alias} not found"),
+            )),
+        }
+    }
+
     pub async fn list_inlets(&self) -> InletList {
         InletList::new(
             self.registry
@@ -518,7 +776,7 @@ impl NodeManager {
                 .await
                 .iter()
                 .map(|(alias, info)| {
-                    if let Some(status) = info.session.status().as_ref() {
+                    let inlet_status = if let Some(status) = info.session.status().as_ref() {
                         match &status.kind {
                             ReplacerOutputKind::Inlet(status) => InletStatus::new(
                                 &info.bind_addr,
@@ -543,13 +801,30 @@ impl NodeManager {
                             ConnectionStatus::Down,
                             info.outlet_addr.to_string(),
                         )
-                    }
+                    };
+                    inlet_status.with_stats(PortalStats::from(&info.stats))
                 })
                 .collect(),
         )
     }
 }
 
+fn close_connection(stats: &TcpPortalStats, id: u64) -> Result<PortalConnectionStatus> {
+    let not_found = || {
+        ockam_core::Error::new(
+            Origin::Node,
+            Kind::NotFound,
+            format!("Connection {id} not found"),
+        )
+    };
+    let connection = stats.connection(id).ok_or_else(not_found)?;
+    if stats.close_connection(id) {
+        Ok(PortalConnectionStatus::from(&connection))
+    } else {
+        Err(not_found())
+    }
+}
+
 impl InMemoryNode {
     #[allow(clippy::too_many_arguments)]
     #[instrument(skip_all)]
@@ -594,6 +869,9 @@ struct InletSessionReplacer {
     wait_for_outlet_duration: Duration,
     resource: Resource,
     policy_expression: Option<Expr>,
+    tls: Option<TcpInletTls>,
+    limits: TcpPortalLimits,
+    stats: TcpPortalStats,
 
     // current status
     connection: Option<Connection>,
@@ -672,6 +950,13 @@ impl SessionReplacer for InletSessionReplacer {
             let options = TcpInletOptions::new()
                 .with_incoming_access_control(incoming_ac)
                 .with_outgoing_access_control(outgoing_ac);
+            let options = match &self.tls {
+                Some(tls) => options.with_tls(tls.clone()),
+                None => options,
+            };
+            let options = options
+                .with_limits(self.limits.clone())
+                .with_stats(self.stats.clone());
 
             // Finally, attempt to create a new inlet using the new route:
             let inlet_address = self
@@ -739,6 +1024,8 @@ pub trait Inlets {
         alias: &str,
         authorized_identifier: &Option<Identifier>,
         policy_expression: &Option<Expr>,
+        tls: &Option<InletTls>,
+        limits: &Option<TcpPortalLimits>,
         wait_for_outlet_timeout: Duration,
         validate: bool,
     ) -> miette::Result<Reply<InletStatus>>;
@@ -746,6 +1033,19 @@ pub trait Inlets {
     async fn show_inlet(&self, ctx: &Context, alias: &str) -> miette::Result<Reply<InletStatus>>;
 
     async fn delete_inlet(&self, ctx: &Context, inlet_alias: &str) -> miette::Result<Reply<()>>;
+
+    async fn list_inlet_connections(
+        &self,
+        ctx: &Context,
+        alias: &str,
+    ) -> miette::Result<Reply<PortalConnectionList>>;
+
+    async fn close_inlet_connection(
+        &self,
+        ctx: &Context,
+        alias: &str,
+        id: u64,
+    ) -> miette::Result<Reply<PortalConnectionStatus>>;
 }
 
 #[async_trait]
@@ -758,6 +1058,8 @@ impl Inlets for BackgroundNodeClient {
         alias: &str,
         authorized_identifier: &Option<Identifier>,
         policy_expression: &Option<Expr>,
+        tls: &Option<InletTls>,
+        limits: &Option<TcpPortalLimits>,
         wait_for_outlet_timeout: Duration,
         wait_connection: bool,
     ) -> miette::Result<Reply<InletStatus>> {
@@ -786,6 +1088,12 @@ impl Inlets for BackgroundNodeClient {
             if let Some(e) = policy_expression.as_ref() {
                 payload.set_policy_expression(e.clone())
             }
+            if let Some(tls) = tls.as_ref() {
+                payload.set_tls(tls.clone())
+            }
+            if let Some(limits) = limits.as_ref() {
+                payload.set_limits(limits.clone())
+            }
             payload.set_wait_ms(wait_for_outlet_timeout.as_millis() as u64);
             Request::post("/node/inlet").body(payload)
         };
@@ -801,28 +1109,66 @@ impl Inlets for BackgroundNodeClient {
         let request = Request::delete(format!("/node/inlet/{inlet_alias}"));
         self.tell_and_get_reply(ctx, request).await
     }
+
+    async fn list_inlet_connections(
+        &self,
+        ctx: &Context,
+        alias: &str,
+    ) -> miette::Result<Reply<PortalConnectionList>> {
+        let request = Request::get(format!("/node/inlet/{alias}/connections"));
+        self.ask_and_get_reply(ctx, request).await
+    }
+
+    async fn close_inlet_connection(
+        &self,
+        ctx: &Context,
+        alias: &str,
+        id: u64,
+    ) -> miette::Result<Reply<PortalConnectionStatus>> {
+        let request = Request::delete(format!("/node/inlet/{alias}/connections/{id}"));
+        self.ask_and_get_reply(ctx, request).await
+    }
 }
 
 #[async_trait]
 pub trait Outlets {
+    #[allow(clippy::too_many_arguments)]
     async fn create_outlet(
         &self,
         ctx: &Context,
         to: HostnamePort,
         tls: bool,
+        proxy: Option<TcpProxyConfig>,
+        limits: Option<TcpPortalLimits>,
         from: Option<&Address>,
         policy_expression: Option<Expr>,
     ) -> miette::Result<OutletStatus>;
+
+    async fn list_outlet_connections(
+        &self,
+        ctx: &Context,
+        worker_addr: &Address,
+    ) -> miette::Result<PortalConnectionList>;
+
+    async fn close_outlet_connection(
+        &self,
+        ctx: &Context,
+        worker_addr: &Address,
+        id: u64,
+    ) -> miette::Result<PortalConnectionStatus>;
 }
 
 #[async_trait]
 impl Outlets for BackgroundNodeClient {
+    #[allow(clippy::too_many_arguments)]
     #[instrument(skip_all, fields(to = % to, from = ? from))]
     async fn create_outlet(
         &self,
         ctx: &Context,
         to: HostnamePort,
         tls: bool,
+        proxy: Option<TcpProxyConfig>,
+        limits: Option<TcpPortalLimits>,
         from: Option<&Address>,
         policy_expression: Option<Expr>,
     ) -> miette::Result<OutletStatus> {
@@ -830,8 +1176,39 @@ impl Outlets for BackgroundNodeClient {
         if let Some(policy_expression) = policy_expression {
             payload.set_policy_expression(policy_expression);
         }
+        if let Some(proxy) = proxy {
+            payload.set_proxy(proxy);
+        }
+        if let Some(limits) = limits {
+            payload.set_limits(limits);
+        }
         let req = Request::post("/node/outlet").body(payload);
         let result: OutletStatus = self.ask(ctx, req).await?;
         Ok(result)
     }
+
+    async fn list_outlet_connections(
+        &self,
+        ctx: &Context,
+        worker_addr: &Address,
+    ) -> miette::Result<PortalConnectionList> {
+        let req = Request::get(format!(
+            "/node/outlet/{}/connections",
+            worker_addr.address()
+        ));
+        self.ask(ctx, req).await
+    }
+
+    async fn close_outlet_connection(
+        &self,
+        ctx: &Context,
+        worker_addr: &Address,
+        id: u64,
+    ) -> miette::Result<PortalConnectionStatus> {
+        let req = Request::delete(format!(
+            "/node/outlet/{}/connections/{id}",
+            worker_addr.address()
+        ));
+        self.ask(ctx, req).await
+    }
 }
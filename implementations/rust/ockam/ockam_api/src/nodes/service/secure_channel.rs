@@ -9,6 +9,7 @@ use ockam::identity::{
 };
 use ockam::identity::{SecureChannel, SecureChannelListener};
 use ockam::{Address, Result, Route};
+use ockam_abac::{Action, Expr, Resource, ResourceType, TrustPolicyAccessControl};
 use ockam_core::api::{Error, Response};
 use ockam_core::compat::sync::Arc;
 use ockam_core::errcode::{Kind, Origin};
@@ -114,12 +115,18 @@ impl NodeManagerWorker {
             addr,
             authorized_identifiers,
             identity_name,
-            ..
+            policy_expression,
         } = create_secure_channel_listener;
 
         let response = self
             .node_manager
-            .create_secure_channel_listener(addr, authorized_identifiers, identity_name, ctx)
+            .create_secure_channel_listener(
+                addr,
+                authorized_identifiers,
+                identity_name,
+                policy_expression,
+                ctx,
+            )
             .await
             .map(|_| Response::ok())?;
         Ok(response)
@@ -293,6 +300,7 @@ impl NodeManager {
         address: Address,
         authorized_identifiers: Option<Vec<Identifier>>,
         identity_name: Option<String>,
+        policy_expression: Option<Expr>,
         ctx: &Context,
     ) -> Result<SecureChannelListener> {
         debug!(
@@ -320,9 +328,15 @@ impl NodeManager {
         let options =
             SecureChannelListenerOptions::new().as_consumer(&self.api_transport_flow_control_id);
 
-        let options = match authorized_identifiers {
-            Some(ids) => options.with_trust_policy(TrustMultiIdentifiersPolicy::new(ids)),
-            None => options.with_trust_policy(TrustEveryonePolicy),
+        let options = match (authorized_identifiers, policy_expression) {
+            (Some(ids), _) => options.with_trust_policy(TrustMultiIdentifiersPolicy::new(ids)),
+            // the policy is checked against the attributes of the credentials
+            // presented by the other party during the handshake
+            (None, Some(expression)) => options.with_trust_policy(
+                self.secure_channel_listener_trust_policy(&address, expression)
+                    .await?,
+            ),
+            (None, None) => options.with_trust_policy(TrustEveryonePolicy),
         };
 
         let options = match self.project_authority() {
@@ -359,9 +373,49 @@ impl NodeManager {
             listener.flow_control_id(),
         );
 
+        // The message queue receivers only receive messages via secure channels
+        for receiver in self.registry.message_queue_receivers.keys().await {
+            ctx.flow_controls()
+                .add_consumer(receiver, listener.flow_control_id());
+        }
+
         Ok(listener)
     }
 
+    /// Return a trust policy checking the policy expression of a secure channel listener
+    async fn secure_channel_listener_trust_policy(
+        &self,
+        address: &Address,
+        expression: Expr,
+    ) -> Result<TrustPolicyAccessControl> {
+        let authority = self.project_authority().ok_or_else(|| {
+            ockam_core::Error::new(
+                Origin::Api,
+                Kind::Invalid,
+                "A policy can only be set on a secure channel listener when the node has a project authority",
+            )
+        })?;
+
+        let resource = Resource::new(address.address(), ResourceType::SecureChannelListener);
+        let action = Action::HandleMessage;
+        let policies = self.policies();
+        policies
+            .store_policy_for_resource_name(&resource.resource_name, &action, &expression)
+            .await?;
+        self.resources().store_resource(&resource).await?;
+
+        let env = Self::policy_environment(&resource, &action);
+        Ok(policies
+            .make_policy_access_control(
+                self.cli_state.identities_attributes(&self.node_name),
+                resource,
+                action,
+                env,
+                authority,
+            )
+            .create_trust_policy())
+    }
+
     pub async fn delete_secure_channel_listener(
         &self,
         ctx: &Context,
@@ -369,6 +423,9 @@ impl NodeManager {
     ) -> Result<SecureChannelListenerInfo> {
         debug!("deleting secure channel listener: {addr}");
         ctx.stop_worker(addr.clone()).await?;
+        self.resources()
+            .delete_resource(&addr.address().into())
+            .await?;
         self.registry
             .secure_channel_listeners
             .remove(addr)
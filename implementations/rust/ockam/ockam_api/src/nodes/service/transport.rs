@@ -3,7 +3,7 @@ use std::net::SocketAddr;
 use ockam::Result;
 use ockam_core::api::{Error, RequestHeader, Response};
 use ockam_node::Context;
-use ockam_transport_tcp::{TcpConnectionOptions, TcpListenerOptions};
+use ockam_transport_tcp::{TcpConnectionOptions, TcpListenerOptions, TcpProxyConfig};
 
 use super::{NodeManager, NodeManagerWorker};
 use crate::nodes::models::transport::{
@@ -46,9 +46,10 @@ impl NodeManager {
     async fn create_tcp_connection(
         &self,
         address: String,
+        proxy: TcpProxyConfig,
         ctx: &Context,
     ) -> Result<TransportStatus> {
-        let options = TcpConnectionOptions::new();
+        let options = TcpConnectionOptions::new().with_proxy_config(proxy);
 
         // Add all Hop workers as consumers for Demo purposes
         // Production nodes should not run any Hop workers
@@ -148,11 +149,11 @@ impl NodeManagerWorker {
         ctx: &Context,
         create: CreateTcpConnection,
     ) -> Result<Response<TransportStatus>, Response<Error>> {
-        let CreateTcpConnection { addr, .. } = create;
+        let CreateTcpConnection { addr, proxy, .. } = create;
         info!("Handling request to create a new TCP connection: {addr}");
 
         self.node_manager
-            .create_tcp_connection(addr.to_string(), ctx)
+            .create_tcp_connection(addr.to_string(), proxy.unwrap_or_default(), ctx)
             .await
             .map(|status| Response::ok().body(status))
             .map_err(|msg| {
@@ -0,0 +1,379 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use ockam::identity::Identifier;
+use ockam::{Address, Result};
+use ockam_abac::{Action, Expr, Resource, ResourceType};
+use ockam_core::api::{Error, Response};
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::AsyncTryClone;
+use ockam_multiaddr::MultiAddr;
+use ockam_node::Context;
+use ockam_transport_udp::{UdpInletOptions, UdpOutletOptions};
+
+use crate::nodes::models::udp_portal::{
+    CreateUdpInlet, CreateUdpOutlet, UdpInletList, UdpInletStatus, UdpOutletList, UdpOutletStatus,
+};
+use crate::nodes::registry::{UdpInletInfo, UdpOutletInfo};
+use crate::nodes::service::default_address::DefaultAddress;
+
+use super::{random_alias, NodeManager, NodeManagerWorker};
+
+/// UDP INLETS
+impl NodeManagerWorker {
+    pub(super) async fn get_udp_inlets(&self) -> Result<Response<UdpInletList>, Response<Error>> {
+        Ok(Response::ok().body(self.node_manager.list_udp_inlets().await))
+    }
+
+    #[instrument(skip_all)]
+    pub(super) async fn create_udp_inlet(
+        &self,
+        ctx: &Context,
+        create_inlet: CreateUdpInlet,
+    ) -> Result<Response<UdpInletStatus>, Response<Error>> {
+        let CreateUdpInlet {
+            listen_addr,
+            outlet_addr,
+            alias,
+            authorized,
+            policy_expression,
+            idle_timeout,
+        } = create_inlet;
+        match self
+            .node_manager
+            .create_udp_inlet(
+                ctx,
+                listen_addr,
+                outlet_addr,
+                alias,
+                authorized,
+                policy_expression,
+                idle_timeout,
+            )
+            .await
+        {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => Err(Response::bad_request_no_request(&format!("{e:?}"))),
+        }
+    }
+
+    pub(super) async fn delete_udp_inlet(
+        &self,
+        ctx: &Context,
+        alias: &str,
+    ) -> Result<Response<UdpInletStatus>, Response<Error>> {
+        match self.node_manager.delete_udp_inlet(ctx, alias).await {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => Err(Response::bad_request_no_request(&format!("{e:?}"))),
+        }
+    }
+}
+
+/// UDP OUTLETS
+impl NodeManagerWorker {
+    pub(super) async fn get_udp_outlets(&self) -> Result<Response<UdpOutletList>, Response<Error>> {
+        Ok(Response::ok().body(self.node_manager.list_udp_outlets().await))
+    }
+
+    #[instrument(skip_all)]
+    pub(super) async fn create_udp_outlet(
+        &self,
+        ctx: &Context,
+        create_outlet: CreateUdpOutlet,
+    ) -> Result<Response<UdpOutletStatus>, Response<Error>> {
+        let CreateUdpOutlet {
+            destination,
+            worker_addr,
+            policy_expression,
+            idle_timeout,
+        } = create_outlet;
+        match self
+            .node_manager
+            .create_udp_outlet(
+                ctx,
+                destination,
+                worker_addr,
+                policy_expression,
+                idle_timeout,
+            )
+            .await
+        {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => Err(Response::bad_request_no_request(&format!("{e:?}"))),
+        }
+    }
+
+    pub(super) async fn delete_udp_outlet(
+        &self,
+        ctx: &Context,
+        worker_addr: &Address,
+    ) -> Result<Response<UdpOutletStatus>, Response<Error>> {
+        match self.node_manager.delete_udp_outlet(ctx, worker_addr).await {
+            Ok(status) => Ok(Response::ok().body(status)),
+            Err(e) => Err(Response::bad_request_no_request(&format!("{e:?}"))),
+        }
+    }
+}
+
+/// UDP INLETS
+impl NodeManager {
+    /// Create a UDP inlet bound to `listen_addr` and sending the datagrams of
+    /// its clients to the UDP outlet at `outlet_addr`
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all)]
+    pub async fn create_udp_inlet(
+        &self,
+        ctx: &Context,
+        listen_addr: String,
+        outlet_addr: MultiAddr,
+        alias: String,
+        authorized: Option<Identifier>,
+        policy_expression: Option<Expr>,
+        idle_timeout: Option<Duration>,
+    ) -> Result<UdpInletStatus> {
+        info!(%listen_addr, %outlet_addr, %alias, "Handling request to create UDP inlet");
+
+        if self.registry.udp_inlets.contains_key(&alias).await {
+            let message = format!("A UDP inlet with alias '{alias}' already exists");
+            return Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::AlreadyExists,
+                message,
+            ));
+        }
+
+        let (incoming_ac, outgoing_ac) = self
+            .access_control(
+                ctx,
+                self.project_authority(),
+                Resource::new(alias.clone(), ResourceType::UdpInlet),
+                Action::HandleMessage,
+                policy_expression,
+            )
+            .await?;
+
+        let connection = self
+            .make_connection(
+                Arc::new(ctx.async_try_clone().await?),
+                &outlet_addr,
+                self.identifier(),
+                authorized,
+                None,
+            )
+            .await?;
+
+        let options = UdpInletOptions::new()
+            .with_incoming_access_control(incoming_ac)
+            .with_outgoing_access_control(outgoing_ac);
+        let options = match idle_timeout {
+            Some(idle_timeout) => options.with_idle_timeout(idle_timeout),
+            None => options,
+        };
+
+        let udp = self.udp_transport(ctx).await?;
+        let created = match connection.route() {
+            Ok(route) => udp.create_inlet(&listen_addr, route, options).await,
+            Err(e) => Err(e),
+        };
+        let (bind_addr, listener_address) = match created {
+            Ok(created) => created,
+            Err(e) => {
+                warn!(%listen_addr, err = %e, "Failed to create UDP inlet");
+                if let Err(e) = connection.close(ctx, self).await {
+                    warn!(%e, "Failed to close the connection of the UDP inlet");
+                }
+                return Err(e);
+            }
+        };
+
+        self.registry
+            .udp_inlets
+            .insert(
+                alias.clone(),
+                UdpInletInfo {
+                    bind_addr,
+                    outlet_addr: outlet_addr.clone(),
+                    listener_address,
+                    connection,
+                },
+            )
+            .await;
+
+        Ok(UdpInletStatus::new(
+            alias,
+            bind_addr.to_string(),
+            outlet_addr.to_string(),
+        ))
+    }
+
+    pub async fn delete_udp_inlet(&self, ctx: &Context, alias: &str) -> Result<UdpInletStatus> {
+        info!(%alias, "Handling request to delete UDP inlet");
+        if let Some(inlet) = self.registry.udp_inlets.remove(alias).await {
+            let udp = self.udp_transport(ctx).await?;
+            if let Err(e) = udp.stop_inlet(inlet.listener_address.clone()).await {
+                warn!(%alias, %e, "Failed to stop the UDP inlet listener");
+            }
+            if let Err(e) = inlet.connection.close(ctx, self).await {
+                warn!(%alias, %e, "Failed to close the connection of the UDP inlet");
+            }
+            self.resources().delete_resource(&alias.into()).await?;
+            Ok(UdpInletStatus::new(
+                alias,
+                inlet.bind_addr.to_string(),
+                inlet.outlet_addr.to_string(),
+            ))
+        } else {
+            let message = format!("UDP inlet with alias {alias} not found");
+            Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::NotFound,
+                message,
+            ))
+        }
+    }
+
+    pub async fn list_udp_inlets(&self) -> UdpInletList {
+        UdpInletList::new(
+            self.registry
+                .udp_inlets
+                .entries()
+                .await
+                .iter()
+                .map(|(alias, info)| {
+                    UdpInletStatus::new(
+                        alias,
+                        info.bind_addr.to_string(),
+                        info.outlet_addr.to_string(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// UDP OUTLETS
+impl NodeManager {
+    /// Create a UDP outlet at `worker_addr` forwarding the datagrams of
+    /// the inlets sessions to `destination`
+    #[instrument(skip_all)]
+    pub async fn create_udp_outlet(
+        &self,
+        ctx: &Context,
+        destination: String,
+        worker_addr: Option<Address>,
+        policy_expression: Option<Expr>,
+        idle_timeout: Option<Duration>,
+    ) -> Result<UdpOutletStatus> {
+        let worker_addr = worker_addr.unwrap_or_else(|| random_alias().into());
+        info!(%destination, %worker_addr, "Handling request to create UDP outlet");
+
+        if self.registry.udp_outlets.contains_key(&worker_addr).await {
+            let message = format!("A UDP outlet with address '{worker_addr}' already exists");
+            return Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::AlreadyExists,
+                message,
+            ));
+        }
+
+        let (incoming_ac, outgoing_ac) = self
+            .access_control(
+                ctx,
+                self.project_authority(),
+                Resource::new(worker_addr.address(), ResourceType::UdpOutlet),
+                Action::HandleMessage,
+                policy_expression,
+            )
+            .await?;
+
+        let options = UdpOutletOptions::new()
+            .with_incoming_access_control(incoming_ac)
+            .with_outgoing_access_control(outgoing_ac);
+        let options = if self.project_authority().is_none() {
+            options.as_consumer(&self.api_transport_flow_control_id)
+        } else {
+            options
+        };
+        // Accept messages from the default secure channel listener
+        let options = if let Some(flow_control_id) = ctx
+            .flow_controls()
+            .get_flow_control_with_spawner(&DefaultAddress::SECURE_CHANNEL_LISTENER.into())
+        {
+            options.as_consumer(&flow_control_id)
+        } else {
+            options
+        };
+        let options = match idle_timeout {
+            Some(idle_timeout) => options.with_idle_timeout(idle_timeout),
+            None => options,
+        };
+
+        let udp = self.udp_transport(ctx).await?;
+        if let Err(e) = udp
+            .create_outlet(worker_addr.clone(), destination.clone(), options)
+            .await
+        {
+            warn!(%destination, err = %e, "Failed to create UDP outlet");
+            let message = format!("Failed to create UDP outlet: {e}");
+            return Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::Internal,
+                message,
+            ));
+        }
+
+        self.registry
+            .udp_outlets
+            .insert(
+                worker_addr.clone(),
+                UdpOutletInfo {
+                    destination: destination.clone(),
+                },
+            )
+            .await;
+
+        Ok(UdpOutletStatus::new(worker_addr, destination))
+    }
+
+    pub async fn delete_udp_outlet(
+        &self,
+        ctx: &Context,
+        worker_addr: &Address,
+    ) -> Result<UdpOutletStatus> {
+        info!(%worker_addr, "Handling request to delete UDP outlet");
+        if let Some(outlet) = self.registry.udp_outlets.remove(worker_addr).await {
+            let udp = self.udp_transport(ctx).await?;
+            if let Err(e) = udp.stop_outlet(worker_addr.clone()).await {
+                warn!(%worker_addr, %e, "Failed to stop the UDP outlet");
+            }
+            self.resources()
+                .delete_resource(&worker_addr.address().into())
+                .await?;
+            Ok(UdpOutletStatus::new(
+                worker_addr.clone(),
+                outlet.destination,
+            ))
+        } else {
+            let message = format!("UDP outlet with address {worker_addr} not found");
+            Err(ockam_core::Error::new(
+                Origin::Node,
+                Kind::NotFound,
+                message,
+            ))
+        }
+    }
+
+    pub async fn list_udp_outlets(&self) -> UdpOutletList {
+        UdpOutletList::new(
+            self.registry
+                .udp_outlets
+                .entries()
+                .await
+                .iter()
+                .map(|(worker_addr, info)| {
+                    UdpOutletStatus::new(worker_addr.clone(), info.destination.clone())
+                })
+                .collect(),
+        )
+    }
+}
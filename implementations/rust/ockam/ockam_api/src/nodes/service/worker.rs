@@ -4,8 +4,12 @@ use crate::nodes::service::{encode_response, TARGET};
 use crate::nodes::{InMemoryNode, NODEMANAGER_ADDR};
 use crate::DefaultAddress;
 use minicbor::Decoder;
+use ockam::identity::IdentitySecureChannelLocalInfo;
+use ockam_abac::{Action, Resource, ResourceType};
 use ockam_core::api::{RequestHeader, Response};
-use ockam_core::{Address, Routed, Worker};
+use ockam_core::{
+    async_trait, Address, AllowAll, IncomingAccessControl, RelayMessage, Routed, Worker,
+};
 use ockam_node::Context;
 use std::error::Error;
 use std::sync::Arc;
@@ -20,6 +24,31 @@ impl NodeManagerWorker {
         NodeManagerWorker { node_manager }
     }
 
+    /// Start the node manager worker.
+    /// When the node has a project authority, the messages received via a secure channel
+    /// are checked against the policy of the node manager resource
+    pub async fn start(self, ctx: &Context) -> ockam_core::Result<()> {
+        let (policy_access_control, _) = self
+            .node_manager
+            .access_control(
+                ctx,
+                self.node_manager.project_authority(),
+                Resource::new(NODEMANAGER_ADDR, ResourceType::NodeManager),
+                Action::HandleMessage,
+                None,
+            )
+            .await?;
+        ctx.start_worker_with_access_control(
+            NODEMANAGER_ADDR,
+            self,
+            NodeManagerAccessControl {
+                policy_access_control,
+            },
+            AllowAll,
+        )
+        .await
+    }
+
     pub async fn stop(&self, ctx: &Context) -> ockam_core::Result<()> {
         self.node_manager.stop(ctx).await?;
         ctx.stop_worker(NODEMANAGER_ADDR).await?;
@@ -27,6 +56,26 @@ impl NodeManagerWorker {
     }
 }
 
+/// Access control for the node manager worker.
+///
+/// Local messages, for example the requests sent by the command line over a TCP connection,
+/// are always accepted. Messages received via a secure channel must satisfy the policy
+/// of the node manager resource.
+#[derive(Debug)]
+struct NodeManagerAccessControl {
+    policy_access_control: Arc<dyn IncomingAccessControl>,
+}
+
+#[async_trait]
+impl IncomingAccessControl for NodeManagerAccessControl {
+    async fn is_authorized(&self, relay_msg: &RelayMessage) -> ockam_core::Result<bool> {
+        if IdentitySecureChannelLocalInfo::find_info(relay_msg.local_message()).is_err() {
+            return Ok(true);
+        }
+        self.policy_access_control.is_authorized(relay_msg).await
+    }
+}
+
 impl NodeManagerWorker {
     //////// Request matching and response handling ////////
 
@@ -176,6 +225,27 @@ impl NodeManagerWorker {
                 encode_response(req, self.create_relay(ctx, req, dec.decode()?).await)?
             }
 
+            // ==*== Message queues ==*==
+            (Get, ["node", "message_queue"]) => {
+                encode_response(req, self.get_message_queues().await)?
+            }
+            (Get, ["node", "message_queue", name]) => {
+                encode_response(req, self.show_message_queue(name).await)?
+            }
+            (Post, ["node", "message_queue"]) => {
+                encode_response(req, self.create_message_queue(ctx, dec.decode()?).await)?
+            }
+            (Post, ["node", "message_queue_receiver"]) => encode_response(
+                req,
+                self.start_message_queue_receiver(ctx, dec.decode()?).await,
+            )?,
+            (Post, ["node", "message_queue", name, "messages"]) => {
+                encode_response(req, self.enqueue_message(ctx, name, dec.decode()?).await)?
+            }
+            (Delete, ["node", "message_queue", name]) => {
+                encode_response(req, self.delete_message_queue(ctx, name).await)?
+            }
+
             // ==*== Inlets & Outlets ==*==
             (Get, ["node", "inlet"]) => encode_response(req, self.get_inlets().await)?,
             (Get, ["node", "inlet", alias]) => encode_response(req, self.show_inlet(alias).await)?,
@@ -197,8 +267,39 @@ impl NodeManagerWorker {
             (Delete, ["node", "inlet", alias]) => {
                 encode_response(req, self.delete_inlet(alias).await)?
             }
+            (Get, ["node", "inlet", alias, "connections"]) => {
+                encode_response(req, self.get_inlet_connections(alias).await)?
+            }
+            (Delete, ["node", "inlet", alias, "connections", id]) => {
+                encode_response(req, self.close_inlet_connection(alias, id).await)?
+            }
+            (Get, ["node", "outlet", addr, "connections"]) => {
+                let addr: Address = addr.to_string().into();
+                encode_response(req, self.get_outlet_connections(&addr).await)?
+            }
+            (Delete, ["node", "outlet", addr, "connections", id]) => {
+                let addr: Address = addr.to_string().into();
+                encode_response(req, self.close_outlet_connection(&addr, id).await)?
+            }
             (Delete, ["node", "portal"]) => todo!(),
 
+            // ==*== UDP Inlets & Outlets ==*==
+            (Get, ["node", "udp", "inlet"]) => encode_response(req, self.get_udp_inlets().await)?,
+            (Get, ["node", "udp", "outlet"]) => encode_response(req, self.get_udp_outlets().await)?,
+            (Post, ["node", "udp", "inlet"]) => {
+                encode_response(req, self.create_udp_inlet(ctx, dec.decode()?).await)?
+            }
+            (Post, ["node", "udp", "outlet"]) => {
+                encode_response(req, self.create_udp_outlet(ctx, dec.decode()?).await)?
+            }
+            (Delete, ["node", "udp", "inlet", alias]) => {
+                encode_response(req, self.delete_udp_inlet(ctx, alias).await)?
+            }
+            (Delete, ["node", "udp", "outlet", addr]) => {
+                let addr: Address = addr.to_string().into();
+                encode_response(req, self.delete_udp_outlet(ctx, &addr).await)?
+            }
+
             // ==*== Flow Controls ==*==
             (Post, ["node", "flow_controls", "add_consumer"]) => {
                 encode_response(req, self.add_consumer(ctx, dec.decode()?).await)?
@@ -208,6 +309,12 @@ impl NodeManagerWorker {
             (Get, ["node", "workers"]) => encode_response(req, self.list_workers(ctx).await)?,
 
             // ==*== Policies ==*==
+            (Get, ["policy", "decisions"]) => {
+                encode_response(req, self.get_policy_decisions(dec.decode()?).await)?
+            }
+            (Get, ["policy", "explain"]) => {
+                encode_response(req, self.explain_policy(dec.decode()?).await)?
+            }
             (Post, ["policy", action]) => {
                 let payload: SetPolicyRequest = dec.decode()?;
                 encode_response(
@@ -246,6 +353,9 @@ impl Worker for NodeManagerWorker {
     type Context = Context;
 
     async fn shutdown(&mut self, ctx: &mut Self::Context) -> ockam_core::Result<()> {
+        if let Some(revocation_list_retriever) = &self.node_manager.revocation_list_retriever {
+            revocation_list_retriever.stop();
+        }
         self.node_manager.medic_handle.stop_medic(ctx).await
     }
 
@@ -324,8 +324,9 @@ mod tests {
 
     use ockam::{route, Address, Context};
     use ockam_core::compat::sync::Arc;
-    use ockam_core::{async_trait, AsyncTryClone, Error, Result};
+    use ockam_core::{async_trait, AsyncTryClone, Error, Result, Route};
     use ockam_multiaddr::MultiAddr;
+    use ockam_node::simulation::Simulation;
 
     use crate::echoer::Echoer;
     use crate::hop::Hop;
@@ -338,13 +339,19 @@ mod tests {
     struct MockReplacer {
         pub called: Arc<AtomicBool>,
         pub can_return: Arc<AtomicBool>,
+        pub route: Route,
     }
 
     impl MockReplacer {
         pub fn new() -> Self {
+            Self::with_route(route!["hop"])
+        }
+
+        pub fn with_route(route: Route) -> Self {
             Self {
                 called: Arc::new(AtomicBool::new(false)),
                 can_return: Arc::new(AtomicBool::new(false)),
+                route,
             }
         }
     }
@@ -357,9 +364,9 @@ mod tests {
                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
             }
             Ok(ReplacerOutcome {
-                ping_route: route!["hop"],
+                ping_route: self.route.clone(),
                 kind: ReplacerOutputKind::Inlet(CurrentInletStatus {
-                    route: route!["hop"],
+                    route: self.route.clone(),
                     worker: Address::from_string("echo"),
                     connection_status: ConnectionStatus::Up,
                 }),
@@ -416,11 +423,11 @@ mod tests {
             .inlets
             .insert(
                 "inlet-1".into(),
-                crate::nodes::registry::InletInfo {
-                    bind_addr: "127.0.0.1:10000".to_string(),
-                    outlet_addr: MultiAddr::default(),
-                    session: session.clone(),
-                },
+                crate::nodes::registry::InletInfo::new(
+                    "127.0.0.1:10000",
+                    MultiAddr::default(),
+                    session.clone(),
+                ),
             )
             .await;
 
@@ -452,4 +459,79 @@ mod tests {
         medic_task.abort();
         ctx.stop().await
     }
+
+    #[test]
+    fn test_session_recovery_after_network_partition() -> Result<()> {
+        let simulation = Simulation::new(0);
+        simulation.run(async {
+            let alice = simulation.create_node("alice").await?;
+            let bob = simulation.create_node("bob").await?;
+
+            // Medic relies on echo, on the other node, to verify if a session is alive
+            bob.context()
+                .flow_controls()
+                .add_consumer("echo", bob.transport().flow_control_id());
+            bob.context()
+                .start_worker(Address::from_string("echo"), Echoer)
+                .await?;
+
+            let registry = Arc::new(Registry::default());
+            let medic = Medic::new_extended(
+                registry.clone(),
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+            );
+            let medic_task = medic
+                .start(alice.context().async_try_clone().await?)
+                .await?;
+
+            let to_bob = alice.transport().connect("bob").await?;
+            let mock_replacer = MockReplacer::with_route(route![to_bob.clone()]);
+            let session = Session::new(mock_replacer.clone());
+            session.up(ReplacerOutcome {
+                ping_route: route![to_bob.clone()],
+                kind: ReplacerOutputKind::Inlet(CurrentInletStatus {
+                    route: route![to_bob],
+                    worker: Address::from_string("mock-address"),
+                    connection_status: ConnectionStatus::Up,
+                }),
+            });
+            registry
+                .inlets
+                .insert(
+                    "inlet-1".into(),
+                    crate::nodes::registry::InletInfo::new(
+                        "127.0.0.1:10000",
+                        MultiAddr::default(),
+                        session.clone(),
+                    ),
+                )
+                .await;
+
+            // The session stays up while bob answers the pings
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            assert_eq!(session.connection_status(), ConnectionStatus::Up);
+            assert!(!mock_replacer.called.load(Ordering::Acquire));
+
+            // Once the nodes are partitioned, the session is degraded and replaced
+            simulation.network().partition(&["alice"], &["bob"]);
+            while !mock_replacer.called.load(Ordering::Acquire) {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            assert_eq!(session.connection_status(), ConnectionStatus::Degraded);
+
+            // The session is up again when the network is healed
+            simulation.network().heal();
+            mock_replacer.can_return.store(true, Ordering::Release);
+            while session.connection_status() != ConnectionStatus::Up {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            assert_eq!(session.connection_status(), ConnectionStatus::Up);
+
+            medic_task.abort();
+            alice.stop().await?;
+            bob.stop().await
+        })
+    }
 }
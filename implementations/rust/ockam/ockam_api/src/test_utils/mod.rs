@@ -25,7 +25,7 @@ use crate::authenticator::credential_issuer::{DEFAULT_CREDENTIAL_VALIDITY, PROJE
 use crate::cli_state::{random_name, CliState};
 use crate::nodes::service::{NodeManagerGeneralOptions, NodeManagerTransportOptions};
 use crate::nodes::InMemoryNode;
-use crate::nodes::{NodeManagerWorker, NODEMANAGER_ADDR};
+use crate::nodes::NodeManagerWorker;
 
 /// This struct is used by tests, it has two responsibilities:
 /// - guard to delete the cli state at the end of the test, the cli state
@@ -113,9 +113,7 @@ pub async fn start_manager_for_tests(
     let node_manager = Arc::new(node_manager);
     let node_manager_worker = NodeManagerWorker::new(node_manager.clone());
 
-    context
-        .start_worker(NODEMANAGER_ADDR, node_manager_worker)
-        .await?;
+    node_manager_worker.start(context).await?;
 
     let secure_channels = node_manager.secure_channels();
     let handle = NodeManagerHandle {
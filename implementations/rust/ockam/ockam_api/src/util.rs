@@ -7,10 +7,13 @@ use ockam_core::errcode::{Kind, Origin};
 use ockam_core::flow_control::FlowControlId;
 use ockam_core::{Address, Error, Result, Route, TransportType, LOCAL};
 use ockam_multiaddr::proto::{
-    DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp, Worker,
+    DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp, Udp, Unix, Worker, Ws,
 };
-use ockam_multiaddr::{Code, MultiAddr, Protocol};
+use ockam_multiaddr::{Code, MultiAddr, ProtoValue, Protocol};
 use ockam_transport_tcp::{TcpConnection, TcpConnectionOptions, TCP};
+use ockam_transport_udp::UDP;
+use ockam_transport_uds::UDS;
+use ockam_transport_websocket::WS;
 
 use crate::error::ApiError;
 
@@ -207,6 +210,9 @@ pub async fn multiaddr_to_route(
 /// For example /tcp/127.0.0.1/port/4000 is transformed to the Address (TCP, "127.0.0.1:4000")
 /// The creation of a TCP worker and the substitution of that transport address to a worker address
 /// is done later with `context.resolve_transport_route(route)`
+///
+/// UDP, WebSocket and Unix domain socket addresses are supported as well, for example
+/// /ip4/127.0.0.1/udp/4000, /dnsaddr/localhost/ws/8000 or /unix/%2Ftmp%2Fockam.sock
 pub fn multiaddr_to_transport_route(ma: &MultiAddr) -> Option<Route> {
     let mut route = Route::new();
     let mut it = ma.iter().peekable();
@@ -215,28 +221,31 @@ pub fn multiaddr_to_transport_route(ma: &MultiAddr) -> Option<Route> {
         match p.code() {
             Ip4::CODE => {
                 let ip4 = p.cast::<Ip4>()?;
-                let port = it.next()?.cast::<Tcp>()?;
-                let socket_addr = SocketAddrV4::new(*ip4, *port);
-                route = route.append(Address::new(TCP, socket_addr.to_string()))
+                let (transport_type, port) = transport_port(&it.next()?)?;
+                let socket_addr = SocketAddrV4::new(*ip4, port);
+                route = route.append(Address::new(transport_type, socket_addr.to_string()))
             }
             Ip6::CODE => {
                 let ip6 = p.cast::<Ip6>()?;
-                let port = it.next()?.cast::<Tcp>()?;
-                let socket_addr = SocketAddrV6::new(*ip6, *port, 0, 0);
-                route = route.append(Address::new(TransportType::new(1), socket_addr.to_string()))
+                let (transport_type, port) = transport_port(&it.next()?)?;
+                let socket_addr = SocketAddrV6::new(*ip6, port, 0, 0);
+                route = route.append(Address::new(transport_type, socket_addr.to_string()))
             }
             DnsAddr::CODE => {
                 let host = p.cast::<DnsAddr>()?;
                 if let Some(p) = it.peek() {
-                    if p.code() == Tcp::CODE {
-                        let port = p.cast::<Tcp>()?;
-                        let addr = format!("{}:{}", &*host, *port);
-                        route = route.append(Address::new(TransportType::new(1), addr));
+                    if let Some((transport_type, port)) = transport_port(p) {
+                        let addr = format!("{}:{}", &*host, port);
+                        route = route.append(Address::new(transport_type, addr));
                         let _ = it.next();
                         continue;
                     }
                 }
             }
+            Unix::CODE => {
+                let path = p.cast::<Unix>()?;
+                route = route.append(Address::new(UDS, &*path))
+            }
             Worker::CODE => {
                 let local = p.cast::<Worker>()?;
                 route = route.append(Address::new(LOCAL, &*local))
@@ -258,6 +267,16 @@ pub fn multiaddr_to_transport_route(ma: &MultiAddr) -> Option<Route> {
     Some(route.into())
 }
 
+/// Return the transport type and the port number of a protocol following a host address
+fn transport_port(p: &ProtoValue) -> Option<(TransportType, u16)> {
+    match p.code() {
+        Tcp::CODE => Some((TCP, *p.cast::<Tcp>()?)),
+        Udp::CODE => Some((UDP, *p.cast::<Udp>()?)),
+        Ws::CODE => Some((WS, *p.cast::<Ws>()?)),
+        _ => None,
+    }
+}
+
 /// Try to convert a multiaddr to an Ockam Address
 pub fn multiaddr_to_addr(ma: &MultiAddr) -> Option<Address> {
     let mut it = ma.iter().peekable();
@@ -342,6 +361,10 @@ pub fn is_local_node(ma: &MultiAddr) -> miette::Result<bool> {
                     .map(|ip4| ip4.is_loopback())
                     .ok_or_else(|| miette!("Invalid \"ip4\" value"))?;
             }
+            // A "/unix" socket is always local
+            Unix::CODE => {
+                at_rust_node = true;
+            }
             // A "/ip6" will be local if it matches the loopback address
             Ip6::CODE => {
                 at_rust_node = p
@@ -370,9 +393,45 @@ pub fn local_worker(code: &Code) -> Result<bool> {
         | Ip4::CODE
         | Ip6::CODE
         | Tcp::CODE
+        | Udp::CODE
+        | Ws::CODE
+        | Unix::CODE
         | Secure::CODE => Ok(false),
         Worker::CODE | Service::CODE => Ok(true),
 
         _ => Err(ApiError::core(format!("unknown transport type: {code}"))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam_core::route;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_multiaddr_to_transport_route() {
+        let cases = [
+            (
+                "/ip4/127.0.0.1/tcp/4000/service/api",
+                route![(TCP, "127.0.0.1:4000"), "api"],
+            ),
+            (
+                "/ip4/127.0.0.1/udp/4000/service/api",
+                route![(UDP, "127.0.0.1:4000"), "api"],
+            ),
+            (
+                "/dnsaddr/localhost/ws/8000/secure/api",
+                route![(WS, "localhost:8000"), "api"],
+            ),
+            (
+                "/unix/%2Ftmp%2Fockam.sock/service/api",
+                route![(UDS, "/tmp/ockam.sock"), "api"],
+            ),
+        ];
+        for (ma, expected) in cases {
+            let ma = MultiAddr::from_str(ma).unwrap();
+            assert_eq!(multiaddr_to_transport_route(&ma), Some(expected));
+        }
+    }
+}
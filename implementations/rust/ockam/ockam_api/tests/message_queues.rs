@@ -0,0 +1,150 @@
+use ockam::route;
+use ockam_api::nodes::models::message_queue::{
+    CreateMessageQueue, EnqueueMessage, MessageQueueStatus, StartMessageQueueReceiver,
+};
+use ockam_api::nodes::NODEMANAGER_ADDR;
+use ockam_api::test_utils::start_manager_for_tests;
+use ockam_core::api::Request;
+use ockam_core::errcode::Kind;
+use ockam_core::{NeutralMessage, Result};
+use ockam_multiaddr::MultiAddr;
+use ockam_node::api::Client;
+use ockam_node::{Context, MessageReceiveOptions};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[ockam_macros::test(timeout = 30_000)]
+async fn message_queue_delivers_to_the_receiver_destinations(ctx: &mut Context) -> Result<()> {
+    let handle = start_manager_for_tests(ctx, None, None).await?;
+    let node_manager = handle.node_manager.clone();
+
+    // the receiver and the queue are on the same node, and connected via a secure channel
+    node_manager
+        .start_message_queue_receiver(
+            ctx,
+            StartMessageQueueReceiver::new(vec![ctx.address().address().to_string()]),
+        )
+        .await?;
+    node_manager
+        .create_message_queue(
+            ctx,
+            CreateMessageQueue::new(
+                "queue".to_string(),
+                MultiAddr::from_str("/secure/api")?,
+                ctx.address().address().to_string(),
+            ),
+        )
+        .await?;
+
+    node_manager
+        .enqueue_message(ctx, "queue", b"hello".to_vec())
+        .await?;
+    let message = ctx
+        .receive_extended::<NeutralMessage>(
+            MessageReceiveOptions::new().with_timeout(Duration::from_secs(10)),
+        )
+        .await?
+        .into_body()?
+        .into_vec();
+    assert_eq!(message, b"hello");
+
+    // the message is deleted once it has been acknowledged
+    while node_manager.show_message_queue("queue").await?.depth > 0 {
+        ctx.sleep(Duration::from_millis(50)).await;
+    }
+
+    // the receiver can only be started once
+    let result = node_manager
+        .start_message_queue_receiver(ctx, StartMessageQueueReceiver::new(vec![]))
+        .await;
+    assert_eq!(result.unwrap_err().code().kind, Kind::AlreadyExists);
+    Ok(())
+}
+
+#[ockam_macros::test(timeout = 30_000)]
+async fn message_queue_rejects_messages_when_full(ctx: &mut Context) -> Result<()> {
+    let handle = start_manager_for_tests(ctx, None, None).await?;
+    let node_manager = handle.node_manager.clone();
+
+    // the destination node is not reachable, so the messages stay in the queue
+    let mut create_queue = CreateMessageQueue::new(
+        "queue".to_string(),
+        MultiAddr::from_str("/secure/unknown")?,
+        "echo".to_string(),
+    );
+    create_queue.set_max_depth(1);
+    node_manager.create_message_queue(ctx, create_queue).await?;
+
+    let status = node_manager
+        .enqueue_message(ctx, "queue", b"hello".to_vec())
+        .await?;
+    assert_eq!(status.depth, 1);
+
+    let result = node_manager
+        .enqueue_message(ctx, "queue", b"hello".to_vec())
+        .await;
+    assert_eq!(result.unwrap_err().code().kind, Kind::ResourceExhausted);
+    assert_eq!(node_manager.show_message_queue("queue").await?.depth, 1);
+    Ok(())
+}
+
+#[ockam_macros::test(timeout = 30_000)]
+async fn message_queue_requests_are_handled_by_the_node_manager_worker(
+    ctx: &mut Context,
+) -> Result<()> {
+    let _handle = start_manager_for_tests(ctx, None, None).await?;
+    let client = Client::new(&route![NODEMANAGER_ADDR], None);
+    let destination = ctx.address().address().to_string();
+
+    // a destination which is not a local address is rejected
+    let result = client
+        .tell(
+            ctx,
+            Request::post("/node/message_queue_receiver").body(StartMessageQueueReceiver::new(
+                vec!["1#127.0.0.1:4000".to_string()],
+            )),
+        )
+        .await?
+        .success();
+    assert!(result.is_err());
+
+    // same requests as the `ockam message queue` commands
+    client
+        .tell(
+            ctx,
+            Request::post("/node/message_queue_receiver")
+                .body(StartMessageQueueReceiver::new(vec![destination.clone()])),
+        )
+        .await?
+        .success()?;
+    let mut create_queue = CreateMessageQueue::new(
+        "queue".to_string(),
+        MultiAddr::from_str("/secure/api")?,
+        destination,
+    );
+    create_queue.set_max_depth(10);
+    let status: MessageQueueStatus = client
+        .ask(ctx, Request::post("/node/message_queue").body(create_queue))
+        .await?
+        .success()?;
+    assert_eq!(status.name, "queue");
+
+    let _: MessageQueueStatus = client
+        .ask(
+            ctx,
+            Request::post("/node/message_queue/queue/messages")
+                .body(EnqueueMessage::new(b"hello".to_vec())),
+        )
+        .await?
+        .success()?;
+    let message = ctx
+        .receive_extended::<NeutralMessage>(
+            MessageReceiveOptions::new().with_timeout(Duration::from_secs(10)),
+        )
+        .await?
+        .into_body()?
+        .into_vec();
+    assert_eq!(message, b"hello");
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use ockam::identity::HANDSHAKES;
+use ockam_api::echoer::Echoer;
+use ockam_api::nodes::service::MetricsEndpoint;
+use ockam_api::test_utils::start_manager_for_tests;
+use ockam_api::DefaultAddress;
+use ockam_core::{route, Address, DenyAll, NeutralMessage, Result};
+use ockam_multiaddr::MultiAddr;
+use ockam_node::metrics::{ACCESS_CONTROL_DENIALS, MESSAGES_ROUTED};
+use ockam_node::{Context, WorkerBuilder};
+use ockam_transport_tcp::{TCP_BYTES_RECEIVED, TCP_BYTES_SENT};
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// The metrics recorded while a secure channel is used over TCP, and while a message
+/// is rejected by an access control, are served by the metrics endpoint
+#[ockam_macros::test]
+async fn secure_channel_over_tcp_metrics_are_served(context: &mut Context) -> Result<()> {
+    let endpoint = MetricsEndpoint::start("127.0.0.1:0".parse().unwrap())?;
+    let handle = start_manager_for_tests(context, None, None).await?;
+    let node_manager = &handle.node_manager;
+
+    // create a secure channel with the node itself, through its TCP listener
+    let listen_address = handle
+        .cli_state
+        .get_node(&node_manager.node_name())
+        .await?
+        .tcp_listener_address()
+        .unwrap();
+    let channel_address = listen_address
+        .multi_addr()?
+        .concat(&MultiAddr::from_str("/service/api")?)?;
+    let channel = node_manager
+        .create_secure_channel(context, channel_address, None, None, None, None)
+        .await?;
+
+    context
+        .flow_controls()
+        .add_consumer(context.address(), channel.flow_control_id());
+    context
+        .send(
+            route![channel.clone(), DefaultAddress::ECHO_SERVICE],
+            NeutralMessage::from(b"hello".to_vec()),
+        )
+        .await?;
+    context.receive::<NeutralMessage>().await?;
+
+    // this worker rejects all the messages sent to it
+    let denied = Address::from_string("metrics_denied");
+    WorkerBuilder::new(Echoer)
+        .with_address(denied.clone())
+        .with_incoming_access_control(DenyAll)
+        .start(context)
+        .await?;
+    context
+        .send(denied.clone(), NeutralMessage::from(b"hello".to_vec()))
+        .await?;
+
+    let expected = [
+        format!("{HANDSHAKES}{{role=\"initiator\"}} 1"),
+        format!("{HANDSHAKES}{{role=\"responder\"}} 1"),
+        format!("{TCP_BYTES_SENT}{{"),
+        format!("{TCP_BYTES_RECEIVED}{{"),
+        format!(
+            "{MESSAGES_ROUTED}{{address=\"{}\"}}",
+            Address::from_string(DefaultAddress::ECHO_SERVICE)
+        ),
+        format!("{ACCESS_CONTROL_DENIALS}{{address=\"{denied}\",direction=\"incoming\"}} 1"),
+    ];
+
+    // the rejected message is handled asynchronously by the worker
+    let metrics = timeout(Duration::from_secs(5), async {
+        loop {
+            let metrics = get_metrics(endpoint.address()).await;
+            if expected.iter().all(|line| metrics.contains(line)) {
+                return metrics;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await;
+    assert!(
+        metrics.is_ok(),
+        "the metrics should contain {expected:?}, got:\n{}",
+        get_metrics(endpoint.address()).await
+    );
+
+    node_manager
+        .delete_secure_channel(context, channel.encryptor_address())
+        .await?;
+    Ok(())
+}
+
+/// Return the body of the response of the metrics endpoint
+async fn get_metrics(address: SocketAddr) -> String {
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    stream
+        .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"));
+    response
+}
@@ -582,3 +582,130 @@ fn test_portal_payload_transfer(outgoing_disruption: Disruption, incoming_disrup
 
     result.unwrap();
 }
+
+#[ockam_macros::test]
+async fn udp_inlet_outlet_local_successful(context: &mut Context) -> ockam::Result<()> {
+    let node_manager_handle = start_manager_for_tests(context, None, None).await?;
+    let node_manager = &node_manager_handle.node_manager;
+
+    // UDP echo server
+    let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let server_addr = server.local_addr().unwrap();
+    spawn(async move {
+        let mut buf = vec![0; 1024];
+        while let Ok((len, peer)) = server.recv_from(&mut buf).await {
+            let _ = server.send_to(&buf[..len], peer).await;
+        }
+    });
+
+    let outlet_status = node_manager
+        .create_udp_outlet(
+            context,
+            server_addr.to_string(),
+            Some(Address::from_string("udp_outlet")),
+            None,
+            None,
+        )
+        .await?;
+    assert_eq!(outlet_status.worker_addr.address(), "udp_outlet");
+
+    let inlet_status = node_manager
+        .create_udp_inlet(
+            context,
+            "127.0.0.1:0".to_string(),
+            MultiAddr::from_str("/secure/api/service/udp_outlet")?,
+            "udp_alias".to_string(),
+            None,
+            None,
+            None,
+        )
+        .await?;
+    assert_eq!(inlet_status.alias, "udp_alias");
+    assert_eq!(inlet_status.outlet_addr, "/secure/api/service/udp_outlet");
+    assert_ne!(inlet_status.bind_addr, "127.0.0.1:0");
+
+    let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    client
+        .send_to(b"hello", inlet_status.bind_addr.as_str())
+        .await
+        .unwrap();
+    let mut buf = [0u8; 1024];
+    let (len, _) = timeout(Duration::from_secs(5), client.recv_from(&mut buf))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(&buf[..len], b"hello");
+
+    let inlets = node_manager.list_udp_inlets().await;
+    assert_eq!(inlets.list.len(), 1);
+    assert_eq!(inlets.list[0].alias, "udp_alias");
+
+    node_manager.delete_udp_inlet(context, "udp_alias").await?;
+    assert!(node_manager.list_udp_inlets().await.list.is_empty());
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn udp_inlet_with_an_existing_alias_is_rejected(context: &mut Context) -> ockam::Result<()> {
+    let node_manager_handle = start_manager_for_tests(context, None, None).await?;
+    let node_manager = &node_manager_handle.node_manager;
+
+    node_manager
+        .create_udp_outlet(
+            context,
+            "127.0.0.1:5000".to_string(),
+            Some(Address::from_string("udp_outlet")),
+            None,
+            None,
+        )
+        .await?;
+
+    let create_inlet = || {
+        node_manager.create_udp_inlet(
+            context,
+            "127.0.0.1:0".to_string(),
+            MultiAddr::from_str("/secure/api/service/udp_outlet").unwrap(),
+            "udp_alias".to_string(),
+            None,
+            None,
+            None,
+        )
+    };
+    create_inlet().await?;
+    let error = create_inlet().await.unwrap_err();
+    assert_eq!(error.code().kind, Kind::AlreadyExists);
+
+    // A missing inlet can not be deleted
+    let error = node_manager
+        .delete_udp_inlet(context, "unknown")
+        .await
+        .unwrap_err();
+    assert_eq!(error.code().kind, Kind::NotFound);
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn udp_inlet_with_an_unreachable_outlet_is_not_registered(
+    context: &mut Context,
+) -> ockam::Result<()> {
+    let node_manager_handle = start_manager_for_tests(context, None, None).await?;
+    let node_manager = &node_manager_handle.node_manager;
+
+    let result = node_manager
+        .create_udp_inlet(
+            context,
+            "127.0.0.1:0".to_string(),
+            MultiAddr::from_str("/dnsaddr/localhost/tcp/1/service/udp_outlet")?,
+            "udp_alias".to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
+    assert!(result.is_err());
+    assert!(node_manager.list_udp_inlets().await.list.is_empty());
+
+    Ok(())
+}
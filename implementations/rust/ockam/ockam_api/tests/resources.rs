@@ -0,0 +1,158 @@
+use ockam::identity::models::CredentialAndPurposeKey;
+use ockam::identity::utils::AttributesBuilder;
+use ockam::identity::{Identifier, SecureChannel};
+use ockam_abac::expr::{eq, ident, str};
+use ockam_abac::{Resource, ResourceType};
+use ockam_api::authenticator::credential_issuer::{
+    DEFAULT_CREDENTIAL_VALIDITY, PROJECT_MEMBER_SCHEMA,
+};
+use ockam_api::nodes::service::default_address::DefaultAddress;
+use ockam_api::nodes::NODEMANAGER_ADDR;
+use ockam_api::test_utils::{start_manager_for_tests, NodeManagerHandle};
+use ockam_core::{route, Address, NeutralMessage, Result};
+use ockam_multiaddr::MultiAddr;
+use ockam_node::{Context, MessageReceiveOptions};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[ockam_macros::test(timeout = 30_000)]
+async fn node_services_are_registered_as_resources(ctx: &mut Context) -> Result<()> {
+    let handle = start_manager_for_tests(ctx, None, None).await?;
+    let resources = handle.node_manager.resources();
+
+    let relay = resources
+        .get_resource(&DefaultAddress::RELAY_SERVICE.into())
+        .await?;
+    assert_eq!(
+        relay,
+        Some(Resource::new(
+            DefaultAddress::RELAY_SERVICE,
+            ResourceType::Relay
+        ))
+    );
+
+    let node_manager = resources.get_resource(&NODEMANAGER_ADDR.into()).await?;
+    assert_eq!(
+        node_manager,
+        Some(Resource::new(NODEMANAGER_ADDR, ResourceType::NodeManager))
+    );
+    Ok(())
+}
+
+#[ockam_macros::test(timeout = 30_000)]
+async fn secure_channel_listener_policy_is_checked_during_the_handshake(
+    ctx: &mut Context,
+) -> Result<()> {
+    let handle = start_manager_for_tests(ctx, None, None).await?;
+    let node_manager = handle.node_manager.clone();
+
+    let listener_address = Address::from_string("operators");
+    node_manager
+        .create_secure_channel_listener(
+            listener_address.clone(),
+            None,
+            None,
+            Some(eq([ident("subject.role"), str("operator")])),
+            ctx,
+        )
+        .await?;
+
+    let resource = node_manager
+        .resources()
+        .get_resource(&listener_address.address().into())
+        .await?;
+    assert_eq!(
+        resource,
+        Some(Resource::new(
+            listener_address.address(),
+            ResourceType::SecureChannelListener
+        ))
+    );
+
+    // only the initiators presenting an operator credential are trusted
+    let operator = handle
+        .cli_state
+        .create_identity_with_name("operator")
+        .await?;
+    let credential = issue_credential(&handle, &operator.identifier(), "operator").await?;
+    let channel = node_manager
+        .create_secure_channel(
+            ctx,
+            MultiAddr::from_str("/service/operators")?,
+            Some("operator".to_string()),
+            None,
+            Some(credential),
+            None,
+        )
+        .await?;
+    assert!(send_to_echoer(ctx, &channel).await.is_ok());
+
+    let guest = handle.cli_state.create_identity_with_name("guest").await?;
+    let credential = issue_credential(&handle, &guest.identifier(), "guest").await?;
+    // the initiator completes its part of the handshake before the listener checks the policy,
+    // so the rejection is only visible when no message goes through the channel
+    let channel = node_manager
+        .create_secure_channel(
+            ctx,
+            MultiAddr::from_str("/service/operators")?,
+            Some("guest".to_string()),
+            None,
+            Some(credential),
+            None,
+        )
+        .await?;
+    assert!(send_to_echoer(ctx, &channel).await.is_err());
+
+    // the resource is removed with the listener
+    node_manager
+        .delete_secure_channel_listener(ctx, &listener_address)
+        .await?;
+    let resource = node_manager
+        .resources()
+        .get_resource(&listener_address.address().into())
+        .await?;
+    assert_eq!(resource, None);
+    Ok(())
+}
+
+/// HELPERS
+async fn send_to_echoer(ctx: &mut Context, channel: &SecureChannel) -> Result<()> {
+    ctx.flow_controls()
+        .add_consumer(ctx.address(), channel.flow_control_id());
+    ctx.send(
+        route![channel.clone(), DefaultAddress::ECHO_SERVICE],
+        NeutralMessage::from(b"hello".to_vec()),
+    )
+    .await?;
+    let message = ctx
+        .receive_extended::<NeutralMessage>(
+            MessageReceiveOptions::new().with_timeout(Duration::from_secs(2)),
+        )
+        .await?
+        .into_body()?
+        .into_vec();
+    assert_eq!(message, b"hello");
+    Ok(())
+}
+
+async fn issue_credential(
+    handle: &NodeManagerHandle,
+    subject: &Identifier,
+    role: &str,
+) -> Result<CredentialAndPurposeKey> {
+    // the node is its own project authority in tests
+    handle
+        .secure_channels
+        .identities()
+        .credentials()
+        .credentials_creation()
+        .issue_credential(
+            &handle.node_manager.identifier(),
+            subject,
+            AttributesBuilder::with_schema(PROJECT_MEMBER_SCHEMA)
+                .with_attribute("role", role)
+                .build(),
+            DEFAULT_CREDENTIAL_VALIDITY,
+        )
+        .await
+}
@@ -219,6 +219,8 @@ impl AppState {
                 &inlet_alias,
                 &None,
                 &Some(expr),
+                &None,
+                &None,
                 Duration::from_secs(5),
                 true,
             )
@@ -720,9 +720,7 @@ pub(crate) async fn make_node_manager(
     );
 
     let node_manager_worker = NodeManagerWorker::new(node_manager.clone());
-    ctx.start_worker(NODEMANAGER_ADDR, node_manager_worker)
-        .await
-        .into_diagnostic()?;
+    node_manager_worker.start(&ctx).await.into_diagnostic()?;
 
     ctx.flow_controls()
         .add_consumer(NODEMANAGER_ADDR, listener.flow_control_id());
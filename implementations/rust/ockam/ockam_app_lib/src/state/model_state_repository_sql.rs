@@ -116,6 +116,7 @@ impl TcpOutletStatusRow {
             socket_addr,
             worker_addr,
             payload: self.payload.clone(),
+            stats: None,
         })
     }
 }
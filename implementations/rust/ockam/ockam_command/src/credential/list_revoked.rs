@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use clap::Args;
+
+use ockam::identity::models::RevokedSubject;
+use ockam::Context;
+use ockam_api::authenticator::revocation::Revocations;
+use ockam_api::nodes::InMemoryNode;
+use ockam_api::output::Output;
+use ockam_multiaddr::MultiAddr;
+
+use crate::project_member::{create_authority_client, get_project};
+use crate::util::api::IdentityOpts;
+use crate::{Command, CommandGlobalOpts, Result};
+
+/// List the subjects revoked by a Project Authority, as an authorized enroller
+#[derive(Clone, Debug, Args)]
+pub struct ListRevokedCommand {
+    #[command(flatten)]
+    identity_opts: IdentityOpts,
+
+    /// Which project's Authority to request
+    #[arg(long, short, value_name = "ROUTE_TO_PROJECT")]
+    to: Option<MultiAddr>,
+}
+
+#[async_trait]
+impl Command for ListRevokedCommand {
+    const NAME: &'static str = "credential list-revoked";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> Result<()> {
+        let project = get_project(&opts.state, &self.to).await?;
+
+        let node = InMemoryNode::start_with_project_name(
+            ctx,
+            &opts.state,
+            Some(project.name().to_string()),
+        )
+        .await?;
+
+        let authority_node_client =
+            create_authority_client(&node, &opts.state, &self.identity_opts, &project).await?;
+
+        let revoked_subjects: Vec<RevokedSubjectOutput> = authority_node_client
+            .list_revoked_subjects(ctx)
+            .await?
+            .into_iter()
+            .map(RevokedSubjectOutput)
+            .collect();
+
+        let plain = opts.terminal.build_list(
+            &revoked_subjects,
+            "Revoked subjects",
+            "No revoked subjects found on that Authority node.",
+        )?;
+        opts.terminal.stdout().plain(plain).write_line()?;
+
+        Ok(())
+    }
+}
+
+struct RevokedSubjectOutput(RevokedSubject);
+
+impl Output for RevokedSubjectOutput {
+    fn single(&self) -> ockam_api::Result<String> {
+        Ok(format!(
+            "{} (revoked at: {})",
+            self.0.subject, self.0.revoked_at.0
+        ))
+    }
+}
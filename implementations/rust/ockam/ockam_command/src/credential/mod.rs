@@ -12,11 +12,15 @@ pub(crate) use store::StoreCommand;
 pub(crate) use verify::VerifyCommand;
 
 use crate::credential::list::ListCommand;
+use crate::credential::list_revoked::ListRevokedCommand;
+use crate::credential::revoke::RevokeCommand;
 use crate::error::Error;
-use crate::{CommandGlobalOpts, Result};
+use crate::{Command, CommandGlobalOpts, Result};
 
 pub(crate) mod issue;
 pub(crate) mod list;
+pub(crate) mod list_revoked;
+pub(crate) mod revoke;
 pub(crate) mod store;
 pub(crate) mod verify;
 
@@ -35,6 +39,8 @@ pub enum CredentialSubcommand {
     Issue(IssueCommand),
     Store(StoreCommand),
     Verify(VerifyCommand),
+    Revoke(RevokeCommand),
+    ListRevoked(ListRevokedCommand),
 }
 
 impl CredentialSubcommand {
@@ -44,6 +50,8 @@ impl CredentialSubcommand {
             CredentialSubcommand::Issue(c) => c.name(),
             CredentialSubcommand::Store(c) => c.name(),
             CredentialSubcommand::Verify(c) => c.name(),
+            CredentialSubcommand::Revoke(c) => c.name(),
+            CredentialSubcommand::ListRevoked(c) => c.name(),
         }
     }
 }
@@ -55,6 +63,8 @@ impl CredentialCommand {
             CredentialSubcommand::Issue(c) => c.run(opts),
             CredentialSubcommand::Store(c) => c.run(opts),
             CredentialSubcommand::Verify(c) => c.run(opts),
+            CredentialSubcommand::Revoke(c) => c.run(opts),
+            CredentialSubcommand::ListRevoked(c) => c.run(opts),
         }
     }
 
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::identity::Identifier;
+use ockam::Context;
+use ockam_api::authenticator::revocation::Revocations;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::InMemoryNode;
+use ockam_multiaddr::MultiAddr;
+
+use crate::project_member::{create_authority_client, get_project};
+use crate::util::api::IdentityOpts;
+use crate::util::parsers::identity_identifier_parser;
+use crate::{Command, CommandGlobalOpts, Result};
+
+/// Revoke the credentials of a Project member, as an authorized enroller.
+/// The member is removed from the Project and nodes trusting the Project Authority
+/// stop accepting its credentials once they refresh the Authority's revocation list
+#[derive(Clone, Debug, Args)]
+pub struct RevokeCommand {
+    #[command(flatten)]
+    identity_opts: IdentityOpts,
+
+    /// Which project's Authority must revoke the member
+    #[arg(long, short, value_name = "ROUTE_TO_PROJECT")]
+    to: Option<MultiAddr>,
+
+    /// Identifier of the member to revoke
+    #[arg(value_name = "IDENTIFIER", value_parser = identity_identifier_parser)]
+    subject: Identifier,
+}
+
+#[async_trait]
+impl Command for RevokeCommand {
+    const NAME: &'static str = "credential revoke";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> Result<()> {
+        let project = get_project(&opts.state, &self.to).await?;
+
+        let node = InMemoryNode::start_with_project_name(
+            ctx,
+            &opts.state,
+            Some(project.name().to_string()),
+        )
+        .await?;
+
+        let authority_node_client =
+            create_authority_client(&node, &opts.state, &self.identity_opts, &project).await?;
+
+        authority_node_client
+            .revoke(ctx, self.subject.clone())
+            .await?;
+
+        opts.terminal.stdout().plain(fmt_ok!(
+            "The credentials of {} are revoked. It won't be able to access Project resources, like portals of other members",
+            color_primary(self.subject.to_string())
+        ));
+
+        Ok(())
+    }
+}
@@ -68,6 +68,7 @@ mod subcommand;
 mod subscription;
 pub mod tcp;
 mod terminal;
+pub mod udp;
 mod upgrade;
 pub mod util;
 pub mod value_parsers;
@@ -1,9 +1,11 @@
 use clap::{Args, Subcommand};
 
+pub use queue::QueueCommand;
 pub use send::SendCommand;
 
 use crate::CommandGlobalOpts;
 
+mod queue;
 mod send;
 
 /// Send and receive messages
@@ -18,18 +20,22 @@ pub struct MessageCommand {
 pub enum MessageSubcommand {
     #[command(display_order = 800)]
     Send(SendCommand),
+    #[command(display_order = 800)]
+    Queue(QueueCommand),
 }
 
 impl MessageCommand {
     pub fn run(self, opts: CommandGlobalOpts) -> miette::Result<()> {
         match self.subcommand {
             MessageSubcommand::Send(c) => c.run(opts),
+            MessageSubcommand::Queue(c) => c.run(opts),
         }
     }
 
     pub fn name(&self) -> String {
         match &self.subcommand {
             MessageSubcommand::Send(c) => c.name(),
+            MessageSubcommand::Queue(c) => c.name(),
         }
     }
 }
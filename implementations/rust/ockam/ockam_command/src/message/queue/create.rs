@@ -0,0 +1,175 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+use miette::{miette, IntoDiagnostic};
+use tokio::sync::Mutex;
+use tokio::try_join;
+
+use ockam::identity::Identifier;
+use ockam::Context;
+use ockam_abac::Expr;
+use ockam_api::address::extract_address_value;
+use ockam_api::cli_state::random_name;
+use ockam_api::colors::color_primary;
+use ockam_api::nodes::models::message_queue::{CreateMessageQueue, MessageQueueStatus};
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_api::{fmt_log, fmt_ok};
+use ockam_core::api::Request;
+use ockam_multiaddr::proto;
+use ockam_multiaddr::{MultiAddr, Protocol as _};
+
+use crate::node::util::initialize_default_node;
+use crate::tcp::util::alias_parser;
+use crate::util::duration::duration_parser;
+use crate::util::process_nodes_multiaddr;
+use crate::{Command, CommandGlobalOpts};
+
+/// Create a message queue
+#[derive(Clone, Debug, Args)]
+pub struct CreateCommand {
+    /// Name of the message queue. Messages sent to `/service/<NAME>` on the node are stored in the queue
+    #[arg(display_order = 900, id = "NAME", value_parser = alias_parser, default_value_t = random_name(), hide_default_value = true)]
+    pub name: String,
+
+    /// Node on which to create the message queue
+    #[arg(long, display_order = 900, id = "NODE_NAME", value_parser = extract_address_value)]
+    pub at: Option<String>,
+
+    /// Route to the node receiving the messages, for example `/node/n1`
+    #[arg(long, display_order = 900, id = "ROUTE")]
+    pub to: String,
+
+    /// Address of the service receiving the messages on the destination node, for example `echo`
+    #[arg(long, display_order = 900, id = "ADDRESS")]
+    pub destination: String,
+
+    /// Authorized identifier for secure channel connection
+    #[arg(long, name = "AUTHORIZED", display_order = 900)]
+    pub authorized: Option<Identifier>,
+
+    /// Policy expression that will be used for access control to the message queue.
+    /// If you don't provide it, the policy set for the "message-queue" resource type will be used.
+    ///
+    /// You can check the fallback policy with `ockam policy show --resource-type message-queue`.
+    #[arg(hide = true, long = "allow", display_order = 900, id = "EXPRESSION")]
+    pub policy_expression: Option<Expr>,
+
+    /// Maximum number of messages stored in the queue. Messages sent to a full queue are rejected
+    #[arg(long, display_order = 900, id = "MAX_DEPTH")]
+    pub max_depth: Option<u64>,
+
+    /// Delay before the first retry of an unacknowledged message. It doubles after each attempt
+    #[arg(long, display_order = 900, id = "INITIAL_RETRY_DELAY", value_parser = duration_parser)]
+    pub initial_retry_delay: Option<Duration>,
+
+    /// Maximum delay between two delivery attempts of a message
+    #[arg(long, display_order = 900, id = "MAX_RETRY_DELAY", value_parser = duration_parser)]
+    pub max_retry_delay: Option<Duration>,
+}
+
+#[async_trait]
+impl Command for CreateCommand {
+    const NAME: &'static str = "message queue create";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        initialize_default_node(ctx, &opts).await?;
+        let to = MultiAddr::from_str(&self.to)
+            .map_err(|e| miette!("Invalid route '{}': {e}", self.to))?;
+        let to = process_nodes_multiaddr(&to, &opts.state).await?;
+        if to.matches(0, &[proto::Project::CODE.into()]) && self.authorized.is_some() {
+            return Err(miette!(
+                "--authorized can not be used with project addresses"
+            ))?;
+        }
+
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.at).await?;
+        let node_name = node.node_name();
+        let is_finished: Mutex<bool> = Mutex::new(false);
+
+        let send_req = async {
+            let mut payload =
+                CreateMessageQueue::new(self.name.clone(), to.clone(), self.destination.clone());
+            if let Some(authorized) = &self.authorized {
+                payload.set_authorized(authorized.clone());
+            }
+            if let Some(expression) = &self.policy_expression {
+                payload.set_policy_expression(expression.clone());
+            }
+            if let Some(max_depth) = self.max_depth {
+                payload.set_max_depth(max_depth);
+            }
+            if let Some(delay) = self.initial_retry_delay {
+                payload.set_initial_retry_delay(delay);
+            }
+            if let Some(delay) = self.max_retry_delay {
+                payload.set_max_retry_delay(delay);
+            }
+            let res: MessageQueueStatus = node
+                .ask(ctx, Request::post("/node/message_queue").body(payload))
+                .await?;
+            *is_finished.lock().await = true;
+            Ok(res)
+        };
+
+        let output_messages = vec![
+            format!(
+                "Creating message queue on node {}...",
+                color_primary(&node_name)
+            ),
+            format!(
+                "Establishing connection to node {}...",
+                color_primary(&self.to)
+            ),
+        ];
+        let progress_output = opts
+            .terminal
+            .progress_output(&output_messages, &is_finished);
+        let (queue, _) = try_join!(send_req, progress_output)?;
+
+        opts.terminal
+            .stdout()
+            .plain(
+                fmt_ok!(
+                    "Message queue {} on node {} is now storing messages\n",
+                    color_primary(&queue.name),
+                    color_primary(&node_name)
+                ) + &fmt_log!(
+                    "for the service {} at {}",
+                    color_primary(&queue.destination),
+                    color_primary(&queue.to)
+                ),
+            )
+            .machine(queue.name.clone())
+            .json(serde_json::to_string_pretty(&queue).into_diagnostic()?)
+            .write_line()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::run::parser::resource::utils::parse_cmd_from_args;
+
+    use super::*;
+
+    #[test]
+    fn command_can_be_parsed_from_name() {
+        let cmd = parse_cmd_from_args(
+            CreateCommand::NAME,
+            &[
+                "readings".to_string(),
+                "--to".to_string(),
+                "/node/n1".to_string(),
+                "--destination".to_string(),
+                "echo".to_string(),
+                "--initial-retry-delay".to_string(),
+                "5s".to_string(),
+            ],
+        );
+        assert!(cmd.is_ok());
+    }
+}
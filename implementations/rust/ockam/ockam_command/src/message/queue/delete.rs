@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::Context;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::models::message_queue::MessageQueueStatus;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::tcp::util::alias_parser;
+use crate::{Command, CommandGlobalOpts};
+
+/// Delete a message queue, and the messages it still stores
+#[derive(Clone, Debug, Args)]
+pub struct DeleteCommand {
+    /// Name of the message queue to delete
+    #[arg(display_order = 900, id = "NAME", value_parser = alias_parser)]
+    name: String,
+
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for DeleteCommand {
+    const NAME: &'static str = "message queue delete";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let queue: MessageQueueStatus = node
+            .ask(
+                ctx,
+                Request::delete(format!("/node/message_queue/{}", self.name)),
+            )
+            .await?;
+
+        opts.terminal
+            .stdout()
+            .plain(fmt_ok!(
+                "Message queue {} on node {} has been deleted, with {} undelivered message(s)",
+                color_primary(&self.name),
+                color_primary(node.node_name()),
+                color_primary(queue.depth.to_string())
+            ))
+            .machine(&self.name)
+            .json(serde_json::json!({ "name": self.name, "node": node.node_name() }))
+            .write_line()?;
+
+        Ok(())
+    }
+}
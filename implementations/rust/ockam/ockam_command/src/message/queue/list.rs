@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::Context;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_info;
+use ockam_api::nodes::models::message_queue::MessageQueueList;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::{Command, CommandGlobalOpts};
+
+/// List the message queues of a node, with the number of messages they store
+#[derive(Clone, Debug, Args)]
+pub struct ListCommand {
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for ListCommand {
+    const NAME: &'static str = "message queue list";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let queues: MessageQueueList = node.ask(ctx, Request::get("/node/message_queue")).await?;
+
+        let empty_message = fmt_info!(
+            "No message queues found on node {}",
+            color_primary(node.node_name())
+        );
+        let list = opts.terminal.build_list(
+            &queues.list,
+            &format!("Message queues on node {}", color_primary(node.node_name())),
+            &empty_message,
+        )?;
+
+        opts.terminal
+            .stdout()
+            .plain(list)
+            .json(serde_json::json!(queues.list))
+            .write_line()?;
+
+        Ok(())
+    }
+}
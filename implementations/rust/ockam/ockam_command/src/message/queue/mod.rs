@@ -0,0 +1,67 @@
+use clap::{Args, Subcommand};
+
+use create::CreateCommand;
+use delete::DeleteCommand;
+use list::ListCommand;
+use receiver::ReceiverCommand;
+use send::SendCommand;
+use show::ShowCommand;
+
+use crate::{docs, Command, CommandGlobalOpts};
+
+pub mod create;
+mod delete;
+mod list;
+mod receiver;
+mod send;
+mod show;
+
+const LONG_ABOUT: &str = include_str!("./static/long_about.txt");
+const AFTER_LONG_HELP: &str = include_str!("./static/after_long_help.txt");
+
+/// Manage durable message queues
+#[derive(Clone, Debug, Args)]
+#[command(
+    arg_required_else_help = true,
+    subcommand_required = true,
+    long_about = docs::about(LONG_ABOUT),
+    after_long_help = docs::after_help(AFTER_LONG_HELP),
+)]
+pub struct QueueCommand {
+    #[command(subcommand)]
+    pub subcommand: QueueSubCommand,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum QueueSubCommand {
+    Create(CreateCommand),
+    Delete(DeleteCommand),
+    List(ListCommand),
+    Show(ShowCommand),
+    Send(SendCommand),
+    Receiver(ReceiverCommand),
+}
+
+impl QueueCommand {
+    pub fn run(self, opts: CommandGlobalOpts) -> miette::Result<()> {
+        match self.subcommand {
+            QueueSubCommand::Create(c) => c.run(opts),
+            QueueSubCommand::Delete(c) => c.run(opts),
+            QueueSubCommand::List(c) => c.run(opts),
+            QueueSubCommand::Show(c) => c.run(opts),
+            QueueSubCommand::Send(c) => c.run(opts),
+            QueueSubCommand::Receiver(c) => c.run(opts),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match &self.subcommand {
+            QueueSubCommand::Create(c) => c.name(),
+            QueueSubCommand::Delete(c) => c.name(),
+            QueueSubCommand::List(c) => c.name(),
+            QueueSubCommand::Show(c) => c.name(),
+            QueueSubCommand::Send(c) => c.name(),
+            QueueSubCommand::Receiver(c) => c.name(),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::identity::Identifier;
+use ockam::Context;
+use ockam_abac::Expr;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::models::message_queue::StartMessageQueueReceiver;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::{Command, CommandGlobalOpts};
+
+/// Start the receiver of the messages queued by other nodes
+#[derive(Clone, Debug, Args)]
+pub struct ReceiverCommand {
+    /// Address of a service which can receive the queued messages, for example `echo`.
+    /// This argument can be repeated
+    #[arg(
+        long = "destination",
+        display_order = 900,
+        id = "ADDRESS",
+        required = true
+    )]
+    pub destinations: Vec<String>,
+
+    /// Identifier of the node sending queued messages.
+    /// It is required when the node is not a project member
+    #[arg(long, name = "AUTHORIZED", display_order = 900)]
+    pub authorized: Option<Identifier>,
+
+    /// Policy expression that will be used for access control to the receiver.
+    /// If you don't provide it, the policy set for the "message-queue" resource type will be used.
+    ///
+    /// You can check the fallback policy with `ockam policy show --resource-type message-queue`.
+    #[arg(hide = true, long = "allow", display_order = 900, id = "EXPRESSION")]
+    pub policy_expression: Option<Expr>,
+
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for ReceiverCommand {
+    const NAME: &'static str = "message queue receiver";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let mut payload = StartMessageQueueReceiver::new(self.destinations.clone());
+        if let Some(authorized) = &self.authorized {
+            payload.set_authorized(authorized.clone());
+        }
+        if let Some(expression) = &self.policy_expression {
+            payload.set_policy_expression(expression.clone());
+        }
+
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        node.tell(
+            ctx,
+            Request::post("/node/message_queue_receiver").body(payload),
+        )
+        .await?;
+
+        opts.terminal
+            .stdout()
+            .plain(fmt_ok!(
+                "The message queue receiver of node {} forwards the queued messages to {}",
+                color_primary(node.node_name()),
+                color_primary(self.destinations.join(", "))
+            ))
+            .write_line()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::run::parser::resource::utils::parse_cmd_from_args;
+
+    use super::*;
+
+    #[test]
+    fn command_can_be_parsed_from_name() {
+        let cmd = parse_cmd_from_args(
+            ReceiverCommand::NAME,
+            &[
+                "--destination".to_string(),
+                "echo".to_string(),
+                "--destination".to_string(),
+                "uppercase".to_string(),
+            ],
+        );
+        assert!(cmd.is_ok());
+    }
+}
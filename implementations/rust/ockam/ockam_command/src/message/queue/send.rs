@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+use miette::{Context as _, IntoDiagnostic};
+
+use ockam::Context;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::models::message_queue::{EnqueueMessage, MessageQueueStatus};
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::tcp::util::alias_parser;
+use crate::{Command, CommandGlobalOpts};
+
+/// Store a message in a message queue, to be delivered to its destination
+#[derive(Clone, Debug, Args)]
+pub struct SendCommand {
+    /// Name of the message queue
+    #[arg(display_order = 900, id = "NAME", value_parser = alias_parser)]
+    name: String,
+
+    /// The message to send
+    #[arg(display_order = 900, id = "MESSAGE")]
+    message: String,
+
+    /// Flag to indicate that the message is hex encoded
+    #[arg(long, display_order = 900)]
+    hex: bool,
+
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for SendCommand {
+    const NAME: &'static str = "message queue send";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let payload = if self.hex {
+            hex::decode(&self.message)
+                .into_diagnostic()
+                .context("The message is not a valid hex string")?
+        } else {
+            self.message.as_bytes().to_vec()
+        };
+
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let queue: MessageQueueStatus = node
+            .ask(
+                ctx,
+                Request::post(format!("/node/message_queue/{}/messages", self.name))
+                    .body(EnqueueMessage::new(payload)),
+            )
+            .await?;
+
+        opts.terminal
+            .stdout()
+            .plain(fmt_ok!(
+                "The message has been sent to the message queue {} on node {}",
+                color_primary(&self.name),
+                color_primary(node.node_name())
+            ))
+            .machine(&self.name)
+            .json(serde_json::to_string_pretty(&queue).into_diagnostic()?)
+            .write_line()?;
+
+        Ok(())
+    }
+}
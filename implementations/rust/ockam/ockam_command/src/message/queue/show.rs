@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use clap::Args;
+use miette::IntoDiagnostic;
+
+use ockam::Context;
+use ockam_api::nodes::models::message_queue::MessageQueueStatus;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_api::output::Output;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::tcp::util::alias_parser;
+use crate::{Command, CommandGlobalOpts};
+
+/// Show the depth and the age of the oldest message of a message queue
+#[derive(Clone, Debug, Args)]
+pub struct ShowCommand {
+    /// Name of the message queue to show
+    #[arg(display_order = 900, id = "NAME", value_parser = alias_parser)]
+    name: String,
+
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for ShowCommand {
+    const NAME: &'static str = "message queue show";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let queue: MessageQueueStatus = node
+            .ask(
+                ctx,
+                Request::get(format!("/node/message_queue/{}", self.name)),
+            )
+            .await?;
+
+        opts.terminal
+            .stdout()
+            .plain(queue.single()?)
+            .machine(queue.depth.to_string())
+            .json(serde_json::to_string_pretty(&queue).into_diagnostic()?)
+            .write_line()?;
+
+        Ok(())
+    }
+}
@@ -14,7 +14,7 @@ use ockam::{Context, TcpTransport};
 use ockam_api::colors::color_primary;
 use ockam_api::nodes::InMemoryNode;
 use ockam_api::nodes::{
-    service::{NodeManagerGeneralOptions, NodeManagerTransportOptions},
+    service::{metrics_address_from_env, NodeManagerGeneralOptions, NodeManagerTransportOptions},
     NodeManagerWorker, NODEMANAGER_ADDR,
 };
 use ockam_api::terminal::notification::NotificationHandler;
@@ -111,7 +111,8 @@ impl CreateCommand {
                 node_name.clone(),
                 self.launch_config.is_none(),
                 true,
-            ),
+            )
+            .with_metrics_address(metrics_address_from_env().into_diagnostic()?),
             NodeManagerTransportOptions::new(tcp_listener.flow_control_id().clone(), tcp),
             trust_options,
         )
@@ -122,9 +123,7 @@ impl CreateCommand {
         let node_manager_worker = NodeManagerWorker::new(Arc::new(node_man));
         ctx.flow_controls()
             .add_consumer(NODEMANAGER_ADDR, tcp_listener.flow_control_id());
-        ctx.start_worker(NODEMANAGER_ADDR, node_manager_worker)
-            .await
-            .into_diagnostic()?;
+        node_manager_worker.start(ctx).await.into_diagnostic()?;
         debug!(%node_name, "node manager worker started");
 
         if self.start_services(ctx, &opts).await.is_err() {
@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+use miette::IntoDiagnostic;
+
+use ockam::identity::{Identifier, TimestampInSeconds};
+use ockam::Context;
+use ockam_abac::{Action, EvalStep, PolicyDecision, ResourceName};
+use ockam_api::colors::color_primary;
+use ockam_api::nodes::{BackgroundNodeClient, Policies};
+use ockam_api::output::{human_readable_time, Output};
+use ockam_api::{fmt_log, fmt_ok, fmt_warn};
+
+use crate::{Command, CommandGlobalOpts, Result};
+
+/// Show the decisions taken by the policies of a node, most recent first.
+/// With --explain, evaluate the policy of a resource for a given identity and show each step of the evaluation
+#[derive(Clone, Debug, Args)]
+pub struct AuditCommand {
+    #[arg(long, display_order = 900, id = "NODE_NAME")]
+    at: Option<String>,
+
+    /// Only show the decisions taken for this resource
+    #[arg(long)]
+    resource: Option<String>,
+
+    /// Only show the decisions taken for this identity
+    #[arg(long)]
+    identifier: Option<Identifier>,
+
+    /// Only show the denied accesses
+    #[arg(long)]
+    denied: bool,
+
+    /// Maximum number of decisions to show
+    #[arg(long, default_value_t = 50)]
+    limit: u32,
+
+    /// Evaluate the policy of the resource for the identity, without recording the decision
+    #[arg(long, requires_all = ["resource", "identifier"], conflicts_with_all = ["denied", "limit"])]
+    explain: bool,
+
+    /// Action evaluated with --explain
+    #[arg(long, default_value_t = Action::HandleMessage, requires = "explain")]
+    action: Action,
+}
+
+#[async_trait]
+impl Command for AuditCommand {
+    const NAME: &'static str = "policy audit";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.at).await?;
+        let resource_name = self.resource.as_deref().map(ResourceName::from);
+
+        if self.explain {
+            let (Some(resource_name), Some(identifier)) = (resource_name, self.identifier) else {
+                unreachable!("the explain flag requires a resource and an identifier")
+            };
+            let explanation = node
+                .explain_policy(ctx, &resource_name, &self.action, &identifier)
+                .await?;
+
+            let mut plain = String::new();
+            for step in explanation.steps.iter() {
+                writeln!(plain, "{}", EvalStepOutput(step).single()?).into_diagnostic()?;
+            }
+            let decision = PolicyDecisionOutput(&explanation.decision).single()?;
+            if explanation.decision.is_authorized {
+                plain.push_str(&fmt_ok!("{decision}"));
+            } else {
+                plain.push_str(&fmt_warn!("{decision}"));
+            }
+            opts.terminal
+                .stdout()
+                .plain(plain)
+                .json(serde_json::to_string(&explanation).into_diagnostic()?)
+                .write_line()?;
+            return Ok(());
+        }
+
+        let decisions = node
+            .list_policy_decisions(
+                ctx,
+                resource_name.as_ref(),
+                self.identifier.as_ref(),
+                self.denied,
+                self.limit,
+            )
+            .await?;
+        let plain = opts.terminal.build_list(
+            &decisions
+                .iter()
+                .map(PolicyDecisionOutput)
+                .collect::<Vec<_>>(),
+            &format!("Policy decisions on Node {}", node.node_name()),
+            &format!("No policy decisions on Node {}", node.node_name()),
+        )?;
+        opts.terminal
+            .stdout()
+            .plain(plain)
+            .json(serde_json::to_string(&decisions).into_diagnostic()?)
+            .write_line()?;
+        Ok(())
+    }
+}
+
+struct PolicyDecisionOutput<'a>(&'a PolicyDecision);
+
+impl Output for PolicyDecisionOutput<'_> {
+    fn single(&self) -> ockam_api::Result<String> {
+        let d = self.0;
+        let mut output = format!(
+            "{} {} {} for resource {} ({}), action {}: {}",
+            human_readable_time(TimestampInSeconds(d.timestamp)),
+            if d.is_authorized { "allowed" } else { "denied" },
+            d.identifier
+                .as_ref()
+                .map(|i| color_primary(i.to_string()).to_string())
+                .unwrap_or_else(|| "an unknown identity".to_string()),
+            color_primary(d.resource_name.to_string()),
+            d.resource_type,
+            d.action,
+            d.reason
+        );
+        if let Some(expression) = &d.expression {
+            write!(output, "\n{}", fmt_log!("Policy: {expression}"))?;
+        }
+        for (name, value) in d.attributes.iter() {
+            write!(output, "\n{}", fmt_log!("{name} = {value}"))?;
+        }
+        Ok(output)
+    }
+}
+
+struct EvalStepOutput<'a>(&'a EvalStep);
+
+impl Output for EvalStepOutput<'_> {
+    fn single(&self) -> ockam_api::Result<String> {
+        let step = self.0;
+        let result = match (&step.value, &step.error) {
+            (Some(value), _) => color_primary(value.to_string()).to_string(),
+            (None, Some(error)) => format!("error: {error}"),
+            (None, None) => "no value".to_string(),
+        };
+        Ok(fmt_log!(
+            "{}{} => {}",
+            "  ".repeat(step.depth as usize),
+            step.expression,
+            result
+        ))
+    }
+}
@@ -6,7 +6,7 @@ use colorful::Colorful;
 use miette::IntoDiagnostic;
 
 use ockam::Context;
-use ockam_abac::{Action, Expr, ResourceName, ResourceType};
+use ockam_abac::{check, Action, Expr, ResourceName, ResourceType};
 use ockam_api::colors::color_primary;
 use ockam_api::nodes::models::policies::ResourceTypeOrName;
 use ockam_api::nodes::{BackgroundNodeClient, Policies};
@@ -41,6 +41,12 @@ impl Command for CreateCommand {
     const NAME: &'static str = "policy create";
 
     async fn async_run(mut self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        // Reject invalid expressions before reaching the node
+        let warnings = check(&self.expression).into_result().into_diagnostic()?;
+        for warning in warnings {
+            opts.terminal.write_line(fmt_warn!("{warning}"))?;
+        }
+
         initialize_default_node(ctx, &opts).await?;
 
         // Backwards compatibility
@@ -5,12 +5,14 @@ use miette::miette;
 
 use ockam_abac::ResourceType;
 
+use crate::policy::audit::AuditCommand;
 pub use crate::policy::create::CreateCommand;
 use crate::policy::delete::DeleteCommand;
 use crate::policy::list::ListCommand;
 use crate::policy::show::ShowCommand;
 use crate::{Command, CommandGlobalOpts};
 
+mod audit;
 mod create;
 mod delete;
 mod list;
@@ -29,6 +31,7 @@ pub enum PolicySubcommand {
     Show(ShowCommand),
     Delete(DeleteCommand),
     List(ListCommand),
+    Audit(AuditCommand),
 }
 
 impl PolicySubcommand {
@@ -38,6 +41,7 @@ impl PolicySubcommand {
             PolicySubcommand::Show(c) => c.name(),
             PolicySubcommand::Delete(c) => c.name(),
             PolicySubcommand::List(c) => c.name(),
+            PolicySubcommand::Audit(c) => c.name(),
         }
     }
 }
@@ -49,6 +53,7 @@ impl PolicyCommand {
             PolicySubcommand::Show(c) => c.run(opts),
             PolicySubcommand::Delete(c) => c.run(opts),
             PolicySubcommand::List(c) => c.run(opts),
+            PolicySubcommand::Audit(c) => c.run(opts),
         }
     }
 
@@ -75,7 +75,7 @@ pub enum ProjectMemberSubcommand {
 /// Get the project authority from the first address protocol.
 ///
 /// If the first protocol is a `/project`, look up the project's config.
-pub(super) async fn get_project(
+pub(crate) async fn get_project(
     cli_state: &CliState,
     input: &Option<MultiAddr>,
 ) -> crate::Result<Project> {
@@ -105,7 +105,7 @@ pub(super) async fn get_project(
     }
 }
 
-pub(super) async fn create_authority_client(
+pub(crate) async fn create_authority_client(
     node: &NodeManager,
     cli_state: &CliState,
     identity_opts: &IdentityOpts,
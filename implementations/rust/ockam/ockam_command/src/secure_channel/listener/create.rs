@@ -5,6 +5,7 @@ use miette::{miette, IntoDiagnostic, WrapErr};
 use crate::{docs, CommandGlobalOpts};
 use ockam::identity::Identifier;
 use ockam::Context;
+use ockam_abac::Expr;
 use ockam_api::colors::OckamColor;
 use ockam_api::nodes::models::secure_channel::CreateSecureChannelListenerRequest;
 use ockam_api::nodes::{BackgroundNodeClient, NODEMANAGER_ADDR};
@@ -41,6 +42,16 @@ pub struct CreateCommand {
     /// If it is different from the default node identity
     #[arg(value_name = "IDENTITY_NAME", long)]
     identity: Option<String>,
+
+    /// Policy expression checked against the credential attributes of the secure channel initiators.
+    /// If you don't provide it, the authorized identifiers are checked, or every initiator is trusted.
+    #[arg(
+        hide = true,
+        long = "allow",
+        id = "EXPRESSION",
+        conflicts_with = "authorized"
+    )]
+    policy_expression: Option<Expr>,
 }
 
 impl CreateCommand {
@@ -57,13 +68,15 @@ impl CreateCommand {
     async fn async_run(&self, ctx: &Context, opts: CommandGlobalOpts) -> miette::Result<()> {
         initialize_default_node(ctx, &opts).await?;
         let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
-        let req = Request::post("/node/secure_channel_listener").body(
-            CreateSecureChannelListenerRequest::new(
-                &self.address,
-                self.authorized.clone(),
-                self.identity.clone(),
-            ),
+        let mut payload = CreateSecureChannelListenerRequest::new(
+            &self.address,
+            self.authorized.clone(),
+            self.identity.clone(),
         );
+        if let Some(expression) = &self.policy_expression {
+            payload.set_policy_expression(expression.clone());
+        }
+        let req = Request::post("/node/secure_channel_listener").body(payload);
         let result = node.tell(ctx, req).await;
         match result {
             Ok(_) => {
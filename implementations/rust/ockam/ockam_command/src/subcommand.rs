@@ -51,6 +51,8 @@ use crate::tcp::connection::TcpConnectionCommand;
 use crate::tcp::inlet::TcpInletCommand;
 use crate::tcp::listener::TcpListenerCommand;
 use crate::tcp::outlet::TcpOutletCommand;
+use crate::udp::inlet::UdpInletCommand;
+use crate::udp::outlet::UdpOutletCommand;
 use crate::util::api::RetryOpts;
 use crate::util::async_cmd;
 use crate::vault::VaultCommand;
@@ -82,6 +84,9 @@ pub enum OckamSubcommand {
     TcpOutlet(TcpOutletCommand),
     TcpInlet(TcpInletCommand),
 
+    UdpOutlet(UdpOutletCommand),
+    UdpInlet(UdpInletCommand),
+
     KafkaInlet(KafkaInletCommand),
     KafkaOutlet(KafkaOutletCommand),
 
@@ -138,6 +143,9 @@ impl OckamSubcommand {
             OckamSubcommand::TcpOutlet(c) => c.run(opts),
             OckamSubcommand::TcpInlet(c) => c.run(opts),
 
+            OckamSubcommand::UdpOutlet(c) => c.run(opts),
+            OckamSubcommand::UdpInlet(c) => c.run(opts),
+
             OckamSubcommand::KafkaInlet(c) => c.run(opts),
             OckamSubcommand::KafkaConsumer(c) => c.run(opts),
             OckamSubcommand::KafkaProducer(c) => c.run(opts),
@@ -268,6 +276,8 @@ impl OckamSubcommand {
             OckamSubcommand::TcpConnection(c) => c.name(),
             OckamSubcommand::TcpOutlet(c) => c.name(),
             OckamSubcommand::TcpInlet(c) => c.name(),
+            OckamSubcommand::UdpOutlet(c) => c.name(),
+            OckamSubcommand::UdpInlet(c) => c.name(),
             OckamSubcommand::KafkaInlet(c) => c.name(),
             OckamSubcommand::KafkaOutlet(c) => c.name(),
             OckamSubcommand::KafkaConsumer(c) => c.name(),
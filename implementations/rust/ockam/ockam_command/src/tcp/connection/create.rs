@@ -12,6 +12,7 @@ use ockam_core::api::Request;
 use ockam_node::Context;
 
 use crate::node::util::initialize_default_node;
+use crate::tcp::util::ProxyOpts;
 use crate::util::async_cmd;
 use crate::{docs, CommandGlobalOpts};
 
@@ -35,6 +36,9 @@ pub struct CreateCommand {
     /// The address to connect to
     #[arg(id = "to", short, long, value_name = "ADDRESS")]
     pub address: String,
+
+    #[command(flatten)]
+    proxy_opts: ProxyOpts,
 }
 
 impl CreateCommand {
@@ -98,7 +102,10 @@ impl CreateCommand {
     async fn async_run(&self, ctx: &Context, opts: CommandGlobalOpts) -> miette::Result<()> {
         initialize_default_node(ctx, &opts).await?;
         let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.from).await?;
-        let payload = models::transport::CreateTcpConnection::new(self.address.clone());
+        let mut payload = models::transport::CreateTcpConnection::new(self.address.clone());
+        if let Some(proxy) = self.proxy_opts.proxy_config() {
+            payload = payload.with_proxy(proxy);
+        }
         let request = Request::post("/node/tcp/connection").body(payload);
 
         let transport_status: TransportStatus = node.ask(ctx, request).await?;
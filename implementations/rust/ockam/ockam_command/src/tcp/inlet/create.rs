@@ -30,7 +30,7 @@ use ockam_multiaddr::proto;
 use ockam_multiaddr::{MultiAddr, Protocol as _};
 
 use crate::node::util::initialize_default_node;
-use crate::tcp::util::alias_parser;
+use crate::tcp::util::{alias_parser, InletTlsOpts, PortalLimitsOpts};
 use crate::{docs, Command, CommandGlobalOpts, Error};
 
 use crate::util::duration::duration_parser;
@@ -101,6 +101,12 @@ pub struct CreateCommand {
     /// Create the TCP Inlet without waiting for the TCP Outlet to connect
     #[arg(long, default_value = "false")]
     no_connection_wait: bool,
+
+    #[command(flatten)]
+    pub tls_opts: InletTlsOpts,
+
+    #[command(flatten)]
+    pub limits_opts: PortalLimitsOpts,
 }
 
 pub(crate) fn default_from_addr() -> SocketAddr {
@@ -129,6 +135,8 @@ impl Command for CreateCommand {
         let mut node = BackgroundNodeClient::create(ctx, &opts.state, &cmd.at).await?;
         cmd.timeout.map(|t| node.set_timeout_mut(t));
 
+        let tls = cmd.tls_opts.inlet_tls()?;
+        let limits = cmd.limits_opts.limits();
         let is_finished: Mutex<bool> = Mutex::new(false);
         let progress_bar = opts.terminal.progress_spinner();
         let create_inlet = async {
@@ -148,6 +156,8 @@ impl Command for CreateCommand {
                         &cmd.alias,
                         &cmd.authorized,
                         &cmd.policy_expression,
+                        &tls,
+                        &limits,
                         cmd.connection_wait,
                         !cmd.no_connection_wait,
                     )
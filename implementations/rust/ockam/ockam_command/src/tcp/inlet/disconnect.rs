@@ -0,0 +1,66 @@
+use clap::Args;
+use colorful::Colorful;
+use miette::IntoDiagnostic;
+
+use ockam::Context;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::service::portals::Inlets;
+use ockam_api::nodes::BackgroundNodeClient;
+
+use crate::node::NodeOpts;
+use crate::tcp::util::alias_parser;
+use crate::util::async_cmd;
+use crate::{docs, CommandGlobalOpts};
+
+const AFTER_LONG_HELP: &str = include_str!("./static/disconnect/after_long_help.txt");
+
+/// Close a live connection of a TCP Inlet
+#[derive(Clone, Debug, Args)]
+#[command(after_long_help = docs::after_help(AFTER_LONG_HELP))]
+pub struct DisconnectCommand {
+    /// Alias of the inlet
+    #[arg(display_order = 900, required = true, id = "ALIAS", value_parser = alias_parser)]
+    alias: String,
+
+    /// Id of the connection to close, as displayed by `ockam tcp-inlet show`
+    #[arg(display_order = 901, long, id = "CONNECTION_ID")]
+    connection: u64,
+
+    /// Node on which the inlet was started
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+impl DisconnectCommand {
+    pub fn run(self, opts: CommandGlobalOpts) -> miette::Result<()> {
+        async_cmd(&self.name(), opts.clone(), |ctx| async move {
+            self.async_run(&ctx, opts).await
+        })
+    }
+
+    pub fn name(&self) -> String {
+        "tcp-inlet disconnect".into()
+    }
+
+    pub async fn async_run(&self, ctx: &Context, opts: CommandGlobalOpts) -> miette::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let connection = node
+            .close_inlet_connection(ctx, &self.alias, self.connection)
+            .await?
+            .success()
+            .into_diagnostic()?;
+
+        opts.terminal
+            .stdout()
+            .plain(fmt_ok!(
+                "Connection {} from {} to the TCP inlet {} has been closed",
+                color_primary(connection.id.to_string()),
+                color_primary(&connection.peer_addr),
+                color_primary(&self.alias)
+            ))
+            .json(serde_json::to_string(&connection).into_diagnostic()?)
+            .write_line()?;
+        Ok(())
+    }
+}
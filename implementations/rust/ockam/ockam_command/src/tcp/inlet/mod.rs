@@ -2,6 +2,7 @@ use clap::{Args, Subcommand};
 
 use create::CreateCommand;
 use delete::DeleteCommand;
+use disconnect::DisconnectCommand;
 pub(crate) use list::ListCommand;
 pub(crate) use show::ShowCommand;
 
@@ -9,6 +10,7 @@ use crate::{docs, Command, CommandGlobalOpts};
 
 pub(crate) mod create;
 mod delete;
+mod disconnect;
 mod list;
 mod show;
 
@@ -32,6 +34,7 @@ pub struct TcpInletCommand {
 pub enum TcpInletSubCommand {
     Create(CreateCommand),
     Delete(DeleteCommand),
+    Disconnect(DisconnectCommand),
     List(ListCommand),
     Show(ShowCommand),
 }
@@ -41,6 +44,7 @@ impl TcpInletCommand {
         match self.subcommand {
             TcpInletSubCommand::Create(c) => c.run(opts),
             TcpInletSubCommand::Delete(c) => c.run(opts),
+            TcpInletSubCommand::Disconnect(c) => c.run(opts),
             TcpInletSubCommand::List(c) => c.run(opts),
             TcpInletSubCommand::Show(c) => c.run(opts),
         }
@@ -50,6 +54,7 @@ impl TcpInletCommand {
         match &self.subcommand {
             TcpInletSubCommand::Create(c) => c.name(),
             TcpInletSubCommand::Delete(c) => c.name(),
+            TcpInletSubCommand::Disconnect(c) => c.name(),
             TcpInletSubCommand::List(c) => c.name(),
             TcpInletSubCommand::Show(c) => c.name(),
         }
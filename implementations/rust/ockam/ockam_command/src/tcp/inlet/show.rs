@@ -10,7 +10,7 @@ use ockam_api::nodes::service::portals::Inlets;
 use ockam_api::nodes::BackgroundNodeClient;
 
 use crate::node::NodeOpts;
-use crate::tcp::util::alias_parser;
+use crate::tcp::util::{alias_parser, connections_list};
 use crate::util::async_cmd;
 use crate::{docs, CommandGlobalOpts};
 
@@ -51,13 +51,22 @@ impl ShowCommand {
             .success()
             .into_diagnostic()?;
 
-        let json = serde_json::to_string(&inlet_status).into_diagnostic()?;
+        let connections = node
+            .list_inlet_connections(ctx, &self.alias)
+            .await?
+            .success()
+            .into_diagnostic()?;
+
+        let mut json = serde_json::to_value(&inlet_status).into_diagnostic()?;
+        json["connections"] = serde_json::to_value(&connections.list).into_diagnostic()?;
+        let json = serde_json::to_string(&json).into_diagnostic()?;
         let InletStatus {
             alias,
             bind_addr,
             outlet_route,
             status,
             outlet_addr,
+            stats,
             ..
         } = inlet_status;
 
@@ -70,6 +79,18 @@ impl ShowCommand {
           Outlet Route: {outlet_route}
           Outlet Destination: {outlet_addr}
     "#};
+        let plain = match stats {
+            Some(stats) => format!(
+                "{plain}  Connections: {} active, {} total, {} rejected\n  Traffic: {} bytes received, {} bytes sent\n",
+                stats.active_connections,
+                stats.total_connections,
+                stats.rejected_connections,
+                stats.bytes_received,
+                stats.bytes_sent,
+            ),
+            None => plain,
+        };
+        let plain = format!("{plain}{}", connections_list(&connections.list)?);
         let machine = bind_addr;
         opts.terminal
             .stdout()
@@ -10,6 +10,7 @@ use tokio::sync::Mutex;
 use tokio::try_join;
 
 use crate::node::util::initialize_default_node;
+use crate::tcp::util::{PortalLimitsOpts, ProxyOpts};
 use crate::{docs, Command, CommandGlobalOpts};
 use ockam::Context;
 use ockam_abac::Expr;
@@ -62,6 +63,12 @@ pub struct CreateCommand {
     /// You can check the fallback policy with `ockam policy show --resource-type tcp-outlet`.
     #[arg(hide = true, long = "allow", display_order = 904, id = "EXPRESSION")]
     pub policy_expression: Option<Expr>,
+
+    #[command(flatten)]
+    pub proxy_opts: ProxyOpts,
+
+    #[command(flatten)]
+    pub limits_opts: PortalLimitsOpts,
 }
 
 #[async_trait]
@@ -81,6 +88,8 @@ impl Command for CreateCommand {
                     ctx,
                     self.to.clone(),
                     self.tls,
+                    self.proxy_opts.proxy_config(),
+                    self.limits_opts.limits(),
                     from.as_ref(),
                     self.policy_expression,
                 )
@@ -0,0 +1,65 @@
+use clap::Args;
+use colorful::Colorful;
+use miette::IntoDiagnostic;
+
+use ockam::Context;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::service::portals::Outlets;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::Address;
+
+use crate::node::NodeOpts;
+use crate::tcp::util::alias_parser;
+use crate::util::async_cmd;
+use crate::{docs, CommandGlobalOpts};
+
+const AFTER_LONG_HELP: &str = include_str!("./static/disconnect/after_long_help.txt");
+
+/// Close a live connection of a TCP Outlet
+#[derive(Clone, Debug, Args)]
+#[command(after_long_help = docs::after_help(AFTER_LONG_HELP))]
+pub struct DisconnectCommand {
+    /// Alias of the Outlet
+    #[arg(display_order = 900, required = true, id = "ALIAS", value_parser = alias_parser)]
+    alias: String,
+
+    /// Id of the connection to close, as displayed by `ockam tcp-outlet show`
+    #[arg(display_order = 901, long, id = "CONNECTION_ID")]
+    connection: u64,
+
+    /// Node on which the Outlet was started. If you don't provide it, the default node will be used
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+impl DisconnectCommand {
+    pub fn run(self, opts: CommandGlobalOpts) -> miette::Result<()> {
+        async_cmd(&self.name(), opts.clone(), |ctx| async move {
+            self.async_run(&ctx, opts).await
+        })
+    }
+
+    pub fn name(&self) -> String {
+        "tcp-outlet disconnect".into()
+    }
+
+    pub async fn async_run(&self, ctx: &Context, opts: CommandGlobalOpts) -> miette::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let worker_addr = Address::from_string(&self.alias);
+        let connection = node
+            .close_outlet_connection(ctx, &worker_addr, self.connection)
+            .await?;
+
+        opts.terminal
+            .stdout()
+            .plain(fmt_ok!(
+                "Connection {} of the TCP Outlet {} has been closed",
+                color_primary(connection.id.to_string()),
+                color_primary(&self.alias)
+            ))
+            .json(serde_json::to_string(&connection).into_diagnostic()?)
+            .write_line()?;
+        Ok(())
+    }
+}
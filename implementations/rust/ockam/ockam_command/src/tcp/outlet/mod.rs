@@ -2,6 +2,7 @@ use clap::{Args, Subcommand};
 
 use create::CreateCommand;
 use delete::DeleteCommand;
+use disconnect::DisconnectCommand;
 use list::ListCommand;
 use show::ShowCommand;
 
@@ -9,6 +10,7 @@ use crate::{docs, Command, CommandGlobalOpts};
 
 pub mod create;
 mod delete;
+mod disconnect;
 pub mod list;
 mod show;
 
@@ -32,6 +34,7 @@ pub struct TcpOutletCommand {
 pub enum TcpOutletSubCommand {
     Create(CreateCommand),
     Delete(DeleteCommand),
+    Disconnect(DisconnectCommand),
     List(ListCommand),
     Show(ShowCommand),
 }
@@ -41,6 +44,7 @@ impl TcpOutletCommand {
         match self.subcommand {
             TcpOutletSubCommand::Create(c) => c.run(opts),
             TcpOutletSubCommand::Delete(c) => c.run(opts),
+            TcpOutletSubCommand::Disconnect(c) => c.run(opts),
             TcpOutletSubCommand::List(c) => c.run(opts),
             TcpOutletSubCommand::Show(c) => c.run(opts),
         }
@@ -50,6 +54,7 @@ impl TcpOutletCommand {
         match &self.subcommand {
             TcpOutletSubCommand::Create(c) => c.name(),
             TcpOutletSubCommand::Delete(c) => c.name(),
+            TcpOutletSubCommand::Disconnect(c) => c.name(),
             TcpOutletSubCommand::List(c) => c.name(),
             TcpOutletSubCommand::Show(c) => c.name(),
         }
@@ -7,17 +7,18 @@ use miette::{miette, IntoDiagnostic};
 use serde::Serialize;
 
 use ockam::Context;
+use ockam_api::nodes::service::portals::Outlets;
 use ockam_api::nodes::BackgroundNodeClient;
 use ockam_api::terminal::{Terminal, TerminalStream};
 use ockam_api::{
     address::extract_address_value,
-    nodes::models::portal::{OutletList, OutletStatus},
+    nodes::models::portal::{OutletList, OutletStatus, PortalConnectionStatus, PortalStats},
 };
 use ockam_core::api::Request;
 use ockam_core::AsyncTryClone;
 use ockam_multiaddr::MultiAddr;
 
-use crate::tcp::util::alias_parser;
+use crate::tcp::util::{alias_parser, connections_list};
 use crate::{docs, CommandGlobalOpts};
 use ockam_api::output::Output;
 
@@ -71,6 +72,9 @@ struct OutletInformation {
     node_name: String,
     worker_addr: MultiAddr,
     socket_addr: SocketAddr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<PortalStats>,
+    connections: Vec<PortalConnectionStatus>,
 }
 
 impl Output for OutletInformation {
@@ -80,6 +84,19 @@ impl Output for OutletInformation {
         write!(w, "\n  On Node: {}", self.node_name)?;
         write!(w, "\n  From address: {}", self.worker_addr)?;
         write!(w, "\n  To TCP server: {}", self.socket_addr)?;
+        if let Some(stats) = &self.stats {
+            write!(
+                w,
+                "\n  Connections: {} active, {} total, {} rejected",
+                stats.active_connections, stats.total_connections, stats.rejected_connections
+            )?;
+            write!(
+                w,
+                "\n  Traffic: {} bytes received, {} bytes sent",
+                stats.bytes_received, stats.bytes_sent
+            )?;
+        }
+        write!(w, "\n{}", connections_list(&self.connections)?.trim_end())?;
         Ok(w)
     }
 }
@@ -152,10 +169,17 @@ impl ShowCommandTui for ShowTui {
             .node
             .ask(&self.ctx, Request::get(format!("/node/outlet/{item_name}")))
             .await?;
+        let connections = self
+            .node
+            .list_outlet_connections(&self.ctx, &outlet_status.worker_addr)
+            .await?
+            .list;
         let info = OutletInformation {
             node_name: self.node.node_name().to_string(),
             worker_addr: outlet_status.worker_address().into_diagnostic()?,
             socket_addr: outlet_status.socket_addr,
+            stats: outlet_status.stats,
+            connections,
         };
         self.terminal()
             .stdout()
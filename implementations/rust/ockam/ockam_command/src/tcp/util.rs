@@ -1,5 +1,14 @@
-use miette::miette;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
+use clap::Args;
+use miette::{miette, Context as _, IntoDiagnostic};
+use ockam_api::nodes::models::portal::{InletTls, PortalConnectionStatus};
+use ockam_api::output::Output;
+use ockam_transport_tcp::{TcpPortalLimits, TcpProxy, TcpProxyConfig};
+
+use crate::util::duration::duration_parser;
 use crate::Result;
 
 pub fn alias_parser(arg: &str) -> Result<String> {
@@ -9,3 +18,130 @@ pub fn alias_parser(arg: &str) -> Result<String> {
         Ok(arg.to_string())
     }
 }
+
+/// Format the live connections of a portal, for the plain output of the `show` commands
+pub fn connections_list(connections: &[PortalConnectionStatus]) -> ockam_api::Result<String> {
+    if connections.is_empty() {
+        return Ok("  Live connections: none\n".to_string());
+    }
+    let mut output = "  Live connections:\n".to_string();
+    for connection in connections {
+        output.push_str(&format!("    {}\n", connection.list()?));
+    }
+    Ok(output)
+}
+
+/// Proxy options for the commands creating outgoing TCP connections
+#[derive(Clone, Debug, Args)]
+pub struct ProxyOpts {
+    /// Proxy used to reach the destination: `http://[user:password@]host:port` for an HTTP proxy
+    /// accepting CONNECT requests, or `socks5://[user:password@]host:port` for a SOCKS5 proxy.
+    /// If you don't provide it, the HTTPS_PROXY and NO_PROXY environment variables of the node are used
+    #[arg(long, value_name = "PROXY_URL", value_parser = TcpProxy::from_str, conflicts_with = "no_proxy")]
+    pub proxy: Option<TcpProxy>,
+
+    /// Connect directly to the destination, ignoring the proxy environment variables of the node
+    #[arg(long)]
+    pub no_proxy: bool,
+}
+
+impl ProxyOpts {
+    /// Return the proxy configuration to send to the node, if the default one must not be used
+    pub fn proxy_config(&self) -> Option<TcpProxyConfig> {
+        match (&self.proxy, self.no_proxy) {
+            (Some(proxy), _) => Some(TcpProxyConfig::Proxy(proxy.clone())),
+            (None, true) => Some(TcpProxyConfig::NoProxy),
+            (None, false) => None,
+        }
+    }
+}
+
+/// TLS options for the commands creating TCP inlets
+#[derive(Clone, Debug, Args)]
+pub struct InletTlsOpts {
+    /// Terminate the TLS connections of the clients. If you don't provide a certificate
+    /// with `--tls-certificate`, a self-signed certificate is generated for `localhost`
+    /// and the identifier of the node
+    #[arg(long)]
+    pub tls: bool,
+
+    /// Path to the PEM-encoded certificate chain served to the clients
+    #[arg(long, value_name = "PEM_FILE", requires = "tls_private_key")]
+    pub tls_certificate: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key of the certificate chain
+    #[arg(long, value_name = "PEM_FILE", requires = "tls_certificate")]
+    pub tls_private_key: Option<PathBuf>,
+
+    /// Path to the PEM-encoded certificate authorities of the client certificates.
+    /// The clients will have to present a certificate signed by one of these authorities
+    #[arg(long, value_name = "PEM_FILE")]
+    pub tls_client_ca: Option<PathBuf>,
+}
+
+impl InletTlsOpts {
+    /// Return the TLS configuration to send to the node, if any TLS option is set
+    pub fn inlet_tls(&self) -> miette::Result<Option<InletTls>> {
+        let tls = match (&self.tls_certificate, &self.tls_private_key) {
+            (Some(certificate), Some(private_key)) => {
+                InletTls::with_certificate(read_pem(certificate)?, read_pem(private_key)?)
+            }
+            _ if self.tls || self.tls_client_ca.is_some() => InletTls::self_signed(),
+            _ => return Ok(None),
+        };
+        Ok(Some(match &self.tls_client_ca {
+            Some(client_ca) => tls.with_client_authentication(read_pem(client_ca)?),
+            None => tls,
+        }))
+    }
+}
+
+/// Options limiting the connections of TCP inlets and outlets
+#[derive(Clone, Debug, Args)]
+pub struct PortalLimitsOpts {
+    /// Maximum number of concurrent connections. New connections are rejected when it is reached
+    #[arg(long, value_name = "CONNECTIONS")]
+    pub max_connections: Option<usize>,
+
+    /// Maximum number of bytes per second transferred by each connection
+    #[arg(long, value_name = "BYTES_PER_SECOND")]
+    pub connection_rate_limit: Option<u64>,
+
+    /// Maximum number of bytes per second transferred by all the connections
+    #[arg(long, value_name = "BYTES_PER_SECOND")]
+    pub total_rate_limit: Option<u64>,
+
+    /// Close the connections which didn't transfer any data for this duration
+    #[arg(long, value_name = "DURATION", value_parser = duration_parser)]
+    pub idle_timeout: Option<Duration>,
+}
+
+impl PortalLimitsOpts {
+    /// Return the limits to send to the node, if any limit is set
+    pub fn limits(&self) -> Option<TcpPortalLimits> {
+        let mut limits = TcpPortalLimits::new();
+        if let Some(max_connections) = self.max_connections {
+            limits = limits.with_max_connections(max_connections);
+        }
+        if let Some(bytes_per_second) = self.connection_rate_limit {
+            limits = limits.with_connection_rate_limit(bytes_per_second);
+        }
+        if let Some(bytes_per_second) = self.total_rate_limit {
+            limits = limits.with_total_rate_limit(bytes_per_second);
+        }
+        if let Some(idle_timeout) = self.idle_timeout {
+            limits = limits.with_idle_timeout(idle_timeout);
+        }
+        if limits == TcpPortalLimits::default() {
+            None
+        } else {
+            Some(limits)
+        }
+    }
+}
+
+fn read_pem(path: &PathBuf) -> miette::Result<String> {
+    std::fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err(format!("Cannot read the PEM file {}", path.display()))
+}
@@ -0,0 +1,167 @@
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+use miette::{miette, IntoDiagnostic};
+use tokio::sync::Mutex;
+use tokio::try_join;
+
+use ockam::identity::Identifier;
+use ockam::Context;
+use ockam_abac::Expr;
+use ockam_api::address::extract_address_value;
+use ockam_api::cli_state::random_name;
+use ockam_api::colors::color_primary;
+use ockam_api::nodes::models::udp_portal::{CreateUdpInlet, UdpInletStatus};
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_api::{fmt_log, fmt_ok};
+use ockam_core::api::Request;
+use ockam_multiaddr::proto;
+use ockam_multiaddr::{MultiAddr, Protocol as _};
+
+use crate::node::util::initialize_default_node;
+use crate::tcp::util::alias_parser;
+use crate::util::duration::duration_parser;
+use crate::util::parsers::socket_addr_parser;
+use crate::util::process_nodes_multiaddr;
+use crate::{docs, Command, CommandGlobalOpts};
+
+const AFTER_LONG_HELP: &str = include_str!("./static/after_long_help.txt");
+
+/// Create a UDP Inlet
+#[derive(Clone, Debug, Args)]
+#[command(after_long_help = docs::after_help(AFTER_LONG_HELP))]
+pub struct CreateCommand {
+    /// Node on which to start the UDP Inlet
+    #[arg(long, display_order = 900, id = "NODE_NAME", value_parser = extract_address_value)]
+    pub at: Option<String>,
+
+    /// Address on which to accept UDP datagrams, in the format `<IP>:<PORT>`
+    #[arg(long, display_order = 900, id = "SOCKET_ADDRESS", value_parser = socket_addr_parser)]
+    pub from: SocketAddr,
+
+    /// Route to a UDP Outlet, for example `/node/n1/service/udp-outlet`
+    #[arg(long, display_order = 900, id = "ROUTE")]
+    pub to: String,
+
+    /// Authorized identifier for secure channel connection
+    #[arg(long, name = "AUTHORIZED", display_order = 900)]
+    pub authorized: Option<Identifier>,
+
+    /// Assign a name to this UDP Inlet
+    #[arg(long, display_order = 900, id = "ALIAS", value_parser = alias_parser, default_value_t = random_name(), hide_default_value = true)]
+    pub alias: String,
+
+    /// Policy expression that will be used for access control to the UDP Inlet.
+    /// If you don't provide it, the policy set for the "udp-inlet" resource type will be used.
+    ///
+    /// You can check the fallback policy with `ockam policy show --resource-type udp-inlet`.
+    #[arg(hide = true, long = "allow", display_order = 900, id = "EXPRESSION")]
+    pub policy_expression: Option<Expr>,
+
+    /// Close the session of a client after it didn't send or receive any datagram for this duration
+    #[arg(long, display_order = 900, id = "IDLE_TIMEOUT", value_parser = duration_parser)]
+    pub idle_timeout: Option<Duration>,
+}
+
+#[async_trait]
+impl Command for CreateCommand {
+    const NAME: &'static str = "udp-inlet create";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        initialize_default_node(ctx, &opts).await?;
+        let to = MultiAddr::from_str(&self.to)
+            .map_err(|e| miette!("Invalid route '{}': {e}", self.to))?;
+        let to = process_nodes_multiaddr(&to, &opts.state).await?;
+        if to.matches(0, &[proto::Project::CODE.into()]) && self.authorized.is_some() {
+            return Err(miette!(
+                "--authorized can not be used with project addresses"
+            ))?;
+        }
+
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.at).await?;
+        let node_name = node.node_name();
+        let is_finished: Mutex<bool> = Mutex::new(false);
+
+        let send_req = async {
+            let mut payload =
+                CreateUdpInlet::new(self.from.to_string(), to.clone(), self.alias.clone());
+            if let Some(authorized) = &self.authorized {
+                payload.set_authorized(authorized.clone());
+            }
+            if let Some(expression) = &self.policy_expression {
+                payload.set_policy_expression(expression.clone());
+            }
+            if let Some(idle_timeout) = self.idle_timeout {
+                payload.set_idle_timeout(idle_timeout);
+            }
+            let res: UdpInletStatus = node
+                .ask(ctx, Request::post("/node/udp/inlet").body(payload))
+                .await?;
+            *is_finished.lock().await = true;
+            Ok(res)
+        };
+
+        let output_messages = vec![
+            format!(
+                "Creating UDP Inlet on node {}...",
+                color_primary(&node_name)
+            ),
+            format!(
+                "Binding UDP socket at {}...",
+                color_primary(self.from.to_string())
+            ),
+            format!(
+                "Establishing connection to outlet {}...",
+                color_primary(&self.to)
+            ),
+        ];
+        let progress_output = opts
+            .terminal
+            .progress_output(&output_messages, &is_finished);
+        let (inlet, _) = try_join!(send_req, progress_output)?;
+
+        opts.terminal
+            .stdout()
+            .plain(
+                fmt_ok!(
+                    "UDP Inlet {} on node {} is now sending datagrams\n",
+                    color_primary(&inlet.alias),
+                    color_primary(&node_name)
+                ) + &fmt_log!(
+                    "from {} to the outlet at {}",
+                    color_primary(&inlet.bind_addr),
+                    color_primary(&inlet.outlet_addr)
+                ),
+            )
+            .machine(inlet.bind_addr.clone())
+            .json(serde_json::to_string_pretty(&inlet).into_diagnostic()?)
+            .write_line()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::run::parser::resource::utils::parse_cmd_from_args;
+
+    use super::*;
+
+    #[test]
+    fn command_can_be_parsed_from_name() {
+        let cmd = parse_cmd_from_args(
+            CreateCommand::NAME,
+            &[
+                "--from".to_string(),
+                "127.0.0.1:5353".to_string(),
+                "--to".to_string(),
+                "/node/n1/service/udp-outlet".to_string(),
+            ],
+        );
+        assert!(cmd.is_ok());
+    }
+}
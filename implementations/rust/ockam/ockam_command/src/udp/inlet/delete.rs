@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::Context;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::models::udp_portal::UdpInletStatus;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::tcp::util::alias_parser;
+use crate::{Command, CommandGlobalOpts};
+
+/// Delete a UDP Inlet
+#[derive(Clone, Debug, Args)]
+pub struct DeleteCommand {
+    /// Alias of the UDP Inlet to delete
+    #[arg(display_order = 900, id = "ALIAS", value_parser = alias_parser)]
+    alias: String,
+
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for DeleteCommand {
+    const NAME: &'static str = "udp-inlet delete";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let _: UdpInletStatus = node
+            .ask(
+                ctx,
+                Request::delete(format!("/node/udp/inlet/{}", self.alias)),
+            )
+            .await?;
+
+        opts.terminal
+            .stdout()
+            .plain(fmt_ok!(
+                "UDP Inlet with alias {} on node {} has been deleted",
+                color_primary(&self.alias),
+                color_primary(node.node_name())
+            ))
+            .machine(&self.alias)
+            .json(serde_json::json!({ "alias": self.alias, "node": node.node_name() }))
+            .write_line()?;
+
+        Ok(())
+    }
+}
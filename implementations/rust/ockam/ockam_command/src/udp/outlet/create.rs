@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+use miette::IntoDiagnostic;
+use tokio::sync::Mutex;
+use tokio::try_join;
+
+use ockam::Context;
+use ockam_abac::Expr;
+use ockam_api::address::extract_address_value;
+use ockam_api::colors::color_primary;
+use ockam_api::nodes::models::udp_portal::{CreateUdpOutlet, UdpOutletStatus};
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_api::{fmt_info, fmt_log, fmt_ok};
+use ockam_core::api::Request;
+use ockam_core::Address;
+
+use crate::node::util::initialize_default_node;
+use crate::util::duration::duration_parser;
+use crate::{docs, Command, CommandGlobalOpts};
+
+const AFTER_LONG_HELP: &str = include_str!("./static/after_long_help.txt");
+
+/// Create a UDP Outlet that runs adjacent to a UDP server
+#[derive(Clone, Debug, Args)]
+#[command(after_long_help = docs::after_help(AFTER_LONG_HELP))]
+pub struct CreateCommand {
+    /// UDP address of your UDP server: domain:port. Your Outlet will send the datagrams to it
+    #[arg(long, display_order = 900, id = "HOSTNAME_PORT")]
+    pub to: String,
+
+    /// Address of your UDP Outlet, which is used as the last part of the route of
+    /// a UDP Inlet (using `ockam udp-inlet create --to <OUTLET_ROUTE>`)
+    #[arg(long, display_order = 902, id = "OUTLET_ADDRESS", default_value = "udp-outlet", value_parser = extract_address_value)]
+    pub from: String,
+
+    /// Your UDP Outlet will be created on this node. If you don't provide it, the default
+    /// node will be used
+    #[arg(long, display_order = 903, id = "NODE_NAME", value_parser = extract_address_value)]
+    pub at: Option<String>,
+
+    /// Policy expression that will be used for access control to the UDP Outlet.
+    /// If you don't provide it, the policy set for the "udp-outlet" resource type will be used.
+    ///
+    /// You can check the fallback policy with `ockam policy show --resource-type udp-outlet`.
+    #[arg(hide = true, long = "allow", display_order = 904, id = "EXPRESSION")]
+    pub policy_expression: Option<Expr>,
+
+    /// Close a session after it didn't send or receive any datagram for this duration
+    #[arg(long, display_order = 905, id = "IDLE_TIMEOUT", value_parser = duration_parser)]
+    pub idle_timeout: Option<Duration>,
+}
+
+#[async_trait]
+impl Command for CreateCommand {
+    const NAME: &'static str = "udp-outlet create";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        initialize_default_node(ctx, &opts).await?;
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.at).await?;
+        let node_name = node.node_name();
+        let is_finished: Mutex<bool> = Mutex::new(false);
+
+        let send_req = async {
+            let mut payload =
+                CreateUdpOutlet::new(self.to.clone(), Some(Address::from(self.from.clone())));
+            if let Some(expression) = &self.policy_expression {
+                payload.set_policy_expression(expression.clone());
+            }
+            if let Some(idle_timeout) = self.idle_timeout {
+                payload.set_idle_timeout(idle_timeout);
+            }
+            let res: UdpOutletStatus = node
+                .ask(ctx, Request::post("/node/udp/outlet").body(payload))
+                .await?;
+            *is_finished.lock().await = true;
+            Ok(res)
+        };
+
+        let output_messages = vec![
+            format!("Creating UDP Outlet to {}...", color_primary(&self.to)),
+            format!(
+                "Creating outlet service on node {}...",
+                color_primary(&node_name)
+            ),
+        ];
+        let progress_output = opts
+            .terminal
+            .progress_output(&output_messages, &is_finished);
+        let (outlet, _) = try_join!(send_req, progress_output)?;
+
+        opts.terminal
+            .stdout()
+            .plain(
+                fmt_ok!("Created a new UDP Outlet\n")
+                    + &fmt_log!("  Node: {}\n", color_primary(&node_name))
+                    + &fmt_log!(
+                        "  Outlet Address: {}\n",
+                        color_primary(outlet.worker_addr.address())
+                    )
+                    + &fmt_log!("  Destination: {}\n", color_primary(&outlet.destination))
+                    + &fmt_info!(
+                        "You may want to take a look at the {}, {} commands next",
+                        color_primary("ockam udp-inlet"),
+                        color_primary("ockam policy")
+                    ),
+            )
+            .machine(outlet.worker_addr.address())
+            .json(serde_json::to_string_pretty(&outlet).into_diagnostic()?)
+            .write_line()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::run::parser::resource::utils::parse_cmd_from_args;
+
+    use super::*;
+
+    #[test]
+    fn command_can_be_parsed_from_name() {
+        let cmd = parse_cmd_from_args(
+            CreateCommand::NAME,
+            &["--to".to_string(), "127.0.0.1:53".to_string()],
+        );
+        assert!(cmd.is_ok());
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::Context;
+use ockam_api::address::extract_address_value;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_ok;
+use ockam_api::nodes::models::udp_portal::UdpOutletStatus;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::{Command, CommandGlobalOpts};
+
+/// Delete a UDP Outlet
+#[derive(Clone, Debug, Args)]
+pub struct DeleteCommand {
+    /// Address of the UDP Outlet to delete
+    #[arg(display_order = 900, id = "OUTLET_ADDRESS", value_parser = extract_address_value)]
+    address: String,
+
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for DeleteCommand {
+    const NAME: &'static str = "udp-outlet delete";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let _: UdpOutletStatus = node
+            .ask(
+                ctx,
+                Request::delete(format!("/node/udp/outlet/{}", self.address)),
+            )
+            .await?;
+
+        opts.terminal
+            .stdout()
+            .plain(fmt_ok!(
+                "UDP Outlet with address {} on node {} has been deleted",
+                color_primary(&self.address),
+                color_primary(node.node_name())
+            ))
+            .machine(&self.address)
+            .json(serde_json::json!({ "address": self.address, "node": node.node_name() }))
+            .write_line()?;
+
+        Ok(())
+    }
+}
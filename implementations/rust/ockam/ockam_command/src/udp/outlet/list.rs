@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::Context;
+use ockam_api::colors::color_primary;
+use ockam_api::fmt_info;
+use ockam_api::nodes::models::udp_portal::UdpOutletList;
+use ockam_api::nodes::BackgroundNodeClient;
+use ockam_core::api::Request;
+
+use crate::node::NodeOpts;
+use crate::{Command, CommandGlobalOpts};
+
+/// List the UDP Outlets of a node
+#[derive(Clone, Debug, Args)]
+pub struct ListCommand {
+    #[command(flatten)]
+    node_opts: NodeOpts,
+}
+
+#[async_trait]
+impl Command for ListCommand {
+    const NAME: &'static str = "udp-outlet list";
+
+    async fn async_run(self, ctx: &Context, opts: CommandGlobalOpts) -> crate::Result<()> {
+        let node = BackgroundNodeClient::create(ctx, &opts.state, &self.node_opts.at_node).await?;
+        let outlets: UdpOutletList = node.ask(ctx, Request::get("/node/udp/outlet")).await?;
+
+        let empty_message = fmt_info!(
+            "No UDP Outlets found on node {}",
+            color_primary(node.node_name())
+        );
+        let list = opts.terminal.build_list(
+            &outlets.list,
+            &format!("UDP Outlets on node {}", color_primary(node.node_name())),
+            &empty_message,
+        )?;
+
+        opts.terminal
+            .stdout()
+            .plain(list)
+            .json(serde_json::json!(outlets.list))
+            .write_line()?;
+
+        Ok(())
+    }
+}
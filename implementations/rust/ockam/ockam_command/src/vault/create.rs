@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use async_trait::async_trait;
 use clap::Args;
 use colorful::Colorful;
+use ockam_api::cli_state::Pkcs11Token;
 use ockam_api::{fmt_info, fmt_ok};
 
 use ockam_node::Context;
@@ -27,6 +28,27 @@ pub struct CreateCommand {
 
     #[arg(long, default_value = "false")]
     pub aws_kms: bool,
+
+    /// Path of a PKCS#11 module, used to store the identity keys in an HSM or a smartcard.
+    /// The user PIN of the token is read from the OCKAM_PKCS11_PIN environment variable
+    #[arg(
+        long,
+        value_name = "MODULE_PATH",
+        requires = "pkcs11_token",
+        conflicts_with = "aws_kms"
+    )]
+    pub pkcs11_module: Option<PathBuf>,
+
+    /// Label of the PKCS#11 token storing the identity keys
+    #[arg(long, value_name = "TOKEN_LABEL", requires = "pkcs11_module")]
+    pub pkcs11_token: Option<String>,
+
+    /// Encrypt the secrets stored in the vault database.
+    /// The encryption key is derived from the OCKAM_VAULT_PASSPHRASE environment variable,
+    /// read from the file given by the OCKAM_VAULT_KEY_FILE environment variable,
+    /// or otherwise generated and stored in the OS keyring
+    #[arg(long, default_value = "false")]
+    pub encrypted: bool,
 }
 
 #[async_trait]
@@ -41,11 +63,26 @@ impl Command for CreateCommand {
         }
         let vault = if self.aws_kms {
             opts.state.create_kms_vault(&self.name, &self.path).await?
+        } else if let (Some(module_path), Some(token_label)) =
+            (&self.pkcs11_module, &self.pkcs11_token)
+        {
+            opts.state
+                .create_pkcs11_vault(
+                    &self.name,
+                    &self.path,
+                    Pkcs11Token::new(module_path.clone(), token_label),
+                )
+                .await?
         } else {
             opts.state
                 .create_named_vault(&self.name, &self.path)
                 .await?
         };
+        let vault = if self.encrypted {
+            opts.state.encrypt_named_vault(&vault.name()).await?
+        } else {
+            vault
+        };
 
         opts.terminal
             .stdout()
@@ -30,22 +30,28 @@ impl Output for VaultOutput {
                 Name: {name}
                 Type: {vault_type}
                 Path: {vault_path}
+                Encrypted: {is_encrypted}
             "#,
             name = self
                 .vault
                 .name()
                 .to_string()
                 .color(OckamColor::PrimaryResource.color()),
-            vault_type = match self.vault.is_kms() {
-                true => "AWS KMS",
-                false => "OCKAM",
-            }
-            .to_string()
-            .color(OckamColor::PrimaryResource.color()),
+            vault_type = self
+                .vault
+                .vault_type()
+                .to_string()
+                .color(OckamColor::PrimaryResource.color()),
             vault_path = self
                 .vault
                 .path_as_string()
                 .color(OckamColor::PrimaryResource.color()),
+            is_encrypted = if self.vault.is_encrypted() {
+                "yes"
+            } else {
+                "no"
+            }
+            .color(OckamColor::PrimaryResource.color()),
         ))
     }
 
@@ -53,22 +59,28 @@ impl Output for VaultOutput {
         Ok(formatdoc!(
             r#"Name: {name}
             Type: {vault_type}
-            Path: {vault_path}"#,
+            Path: {vault_path}
+            Encrypted: {is_encrypted}"#,
             name = self
                 .vault
                 .name()
                 .to_string()
                 .color(OckamColor::PrimaryResource.color()),
-            vault_type = match self.vault.is_kms() {
-                true => "AWS KMS",
-                false => "OCKAM",
-            }
-            .to_string()
-            .color(OckamColor::PrimaryResource.color()),
+            vault_type = self
+                .vault
+                .vault_type()
+                .to_string()
+                .color(OckamColor::PrimaryResource.color()),
             vault_path = self
                 .vault
                 .path_as_string()
                 .color(OckamColor::PrimaryResource.color()),
+            is_encrypted = if self.vault.is_encrypted() {
+                "yes"
+            } else {
+                "no"
+            }
+            .color(OckamColor::PrimaryResource.color()),
         ))
     }
 }
@@ -1,10 +1,14 @@
 use core::time::Duration;
 
 use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
 use ockam_core::Result;
 use ockam_vault::{VaultForSigning, VaultForVerifyingSignatures};
 
-use crate::models::{Attributes, Credential, CredentialAndPurposeKey, CredentialData, Identifier};
+use crate::models::{
+    Attributes, Credential, CredentialAndPurposeKey, CredentialData, Identifier, RevocationList,
+    RevocationListAndPurposeKey, RevocationListData, RevokedSubject,
+};
 use crate::utils::now;
 use crate::{IdentitiesVerification, PurposeKeyCreation, TimestampInSeconds};
 
@@ -85,4 +89,43 @@ impl CredentialsCreation {
 
         Ok(res)
     }
+
+    /// Issue a signed [`RevocationList`] containing the given revoked subjects
+    pub async fn issue_revocation_list(
+        &self,
+        issuer: &Identifier,
+        revoked_subjects: Vec<RevokedSubject>,
+    ) -> Result<RevocationListAndPurposeKey> {
+        let issuer_purpose_key = self
+            .purpose_keys_creation
+            .get_or_create_credential_purpose_key(issuer)
+            .await?;
+
+        let revocation_list_data = RevocationListData {
+            revoked_subjects,
+            created_at: now()?,
+        };
+        let revocation_list_data = minicbor::to_vec(revocation_list_data)?;
+
+        let versioned_data = RevocationList::create_versioned_data(revocation_list_data);
+        let versioned_data = minicbor::to_vec(&versioned_data)?;
+
+        let versioned_data_hash = self.verifying_vault.sha256(&versioned_data).await?;
+
+        let signature = self
+            .credential_vault
+            .sign(issuer_purpose_key.key(), &versioned_data_hash.0)
+            .await?;
+        let signature = signature.into();
+
+        let revocation_list = RevocationList {
+            data: versioned_data,
+            signature,
+        };
+
+        Ok(RevocationListAndPurposeKey {
+            revocation_list,
+            purpose_key_attestation: issuer_purpose_key.attestation().clone(),
+        })
+    }
 }
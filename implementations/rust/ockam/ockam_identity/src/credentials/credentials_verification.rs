@@ -4,16 +4,17 @@ use ockam_core::compat::collections::BTreeMap;
 use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
 use ockam_core::Result;
-use ockam_vault::VaultForVerifyingSignatures;
+use ockam_vault::{VaultForVerifyingSignatures, VerifyingPublicKey};
 
 use crate::identities::AttributesEntry;
 use crate::models::{
-    CredentialAndPurposeKey, CredentialData, Identifier, PurposePublicKey, VersionedData,
+    CredentialAndPurposeKey, CredentialData, Identifier, PurposeKeyAttestationData,
+    PurposePublicKey, RevocationListAndPurposeKey, RevocationListData, VersionedData,
 };
 use crate::utils::now;
 use crate::{
     CredentialAndPurposeKeyData, IdentityAttributesRepository, IdentityError,
-    PurposeKeyVerification, TimestampInSeconds,
+    PurposeKeyVerification, RevocationRepository, TimestampInSeconds,
 };
 
 /// We allow Credentials to be created in the future related to this machine's time due to
@@ -25,6 +26,7 @@ pub struct CredentialsVerification {
     purpose_keys_verification: Arc<PurposeKeyVerification>,
     verifying_vault: Arc<dyn VaultForVerifyingSignatures>,
     identities_attributes_repository: Arc<dyn IdentityAttributesRepository>,
+    revocation_repository: Arc<dyn RevocationRepository>,
 }
 
 impl CredentialsVerification {
@@ -33,11 +35,13 @@ impl CredentialsVerification {
         purpose_keys_verification: Arc<PurposeKeyVerification>,
         verifying_vault: Arc<dyn VaultForVerifyingSignatures>,
         identities_attributes_repository: Arc<dyn IdentityAttributesRepository>,
+        revocation_repository: Arc<dyn RevocationRepository>,
     ) -> Self {
         Self {
             purpose_keys_verification,
             verifying_vault,
             identities_attributes_repository,
+            revocation_repository,
         }
     }
 }
@@ -200,6 +204,19 @@ impl CredentialsVerification {
             )
             .await?;
 
+        debug!("verify revocation");
+        if self
+            .revocation_repository
+            .is_revoked(subject, &credential_data.purpose_key_data.subject)
+            .await?
+        {
+            warn!(
+                "the credential of {} was revoked by {}",
+                subject, credential_data.purpose_key_data.subject
+            );
+            return Err(IdentityError::CredentialRevoked)?;
+        }
+
         let map = credential_data.credential_data.subject_attributes.map;
         let map: BTreeMap<_, _> = map
             .into_iter()
@@ -220,4 +237,79 @@ impl CredentialsVerification {
 
         Ok(())
     }
+
+    /// Verify a [`RevocationList`] and return its content
+    pub async fn verify_revocation_list(
+        &self,
+        authorities: &[Identifier],
+        revocation_list_and_purpose_key: &RevocationListAndPurposeKey,
+    ) -> Result<RevocationListData> {
+        debug!("verify purpose key attestation");
+        let purpose_key_data = self
+            .purpose_keys_verification
+            .verify_purpose_key_attestation(
+                None,
+                &revocation_list_and_purpose_key.purpose_key_attestation,
+            )
+            .await?;
+
+        debug!("verify issuer");
+        if !authorities.contains(&purpose_key_data.subject) {
+            warn!(
+                "unknown authority on a revocation list: {}. Accepted authorities: {:?}",
+                purpose_key_data.subject, authorities
+            );
+            return Err(IdentityError::UnknownAuthority)?;
+        }
+
+        debug!("verify signature");
+        let public_key = Self::get_credential_signing_key(&purpose_key_data)?;
+        let versioned_data_hash = self
+            .verifying_vault
+            .sha256(&revocation_list_and_purpose_key.revocation_list.data)
+            .await?;
+
+        let signature = revocation_list_and_purpose_key
+            .revocation_list
+            .signature
+            .clone()
+            .into();
+
+        if !self
+            .verifying_vault
+            .verify_signature(&public_key, &versioned_data_hash.0, &signature)
+            .await?
+        {
+            return Err(IdentityError::RevocationListVerificationFailed)?;
+        }
+
+        let revocation_list_data = revocation_list_and_purpose_key.get_revocation_list_data()?;
+
+        debug!("verify dates");
+        if revocation_list_data.created_at < purpose_key_data.created_at
+            || revocation_list_data.created_at > purpose_key_data.expires_at
+        {
+            // The revocation list must be signed while the purpose key is valid
+            return Err(IdentityError::RevocationListVerificationFailed)?;
+        }
+
+        let now = now()?;
+        if revocation_list_data.created_at > now
+            && revocation_list_data.created_at - now > MAX_ALLOWED_TIME_DRIFT
+        {
+            // A revocation list can't be created in the future
+            return Err(IdentityError::RevocationListVerificationFailed)?;
+        }
+
+        Ok(revocation_list_data)
+    }
+
+    fn get_credential_signing_key(
+        purpose_key_data: &PurposeKeyAttestationData,
+    ) -> Result<VerifyingPublicKey> {
+        match purpose_key_data.public_key.clone() {
+            PurposePublicKey::SecureChannelStatic(_) => Err(IdentityError::InvalidKeyType)?,
+            PurposePublicKey::CredentialSigning(public_key) => Ok(public_key.into()),
+        }
+    }
 }
@@ -2,7 +2,7 @@ use crate::models::CredentialAndPurposeKey;
 use crate::utils::now;
 use crate::{
     CredentialRepository, CredentialRetriever, CredentialRetrieverCreator, Identifier,
-    IdentityError, TimestampInSeconds,
+    IdentityError, RevocationRepository, TimestampInSeconds,
 };
 use async_trait::async_trait;
 use ockam_core::compat::boxed::Box;
@@ -21,6 +21,7 @@ pub struct CachedCredentialRetriever {
     subject: Identifier,
     scope: String,
     cache: Arc<dyn CredentialRepository>,
+    revocations: Arc<dyn RevocationRepository>,
 }
 
 impl CachedCredentialRetriever {
@@ -30,22 +31,26 @@ impl CachedCredentialRetriever {
         subject: Identifier,
         scope: String,
         cache: Arc<dyn CredentialRepository>,
+        revocations: Arc<dyn RevocationRepository>,
     ) -> Self {
         Self {
             issuer,
             subject,
             scope,
             cache,
+            revocations,
         }
     }
 
     /// Retrieve a credential from the credentials storage and check its expiration
+    /// and its revocation status
     pub async fn retrieve_impl(
         issuer: &Identifier,
         for_identity: &Identifier,
         scope: &str,
         now: TimestampInSeconds,
         cache: Arc<dyn CredentialRepository>,
+        revocations: Arc<dyn RevocationRepository>,
         clock_skew_gap: TimestampInSeconds,
     ) -> Result<Option<CredentialAndPurposeKey>> {
         debug!(
@@ -53,6 +58,21 @@ impl CachedCredentialRetriever {
             for_identity, issuer
         );
 
+        // a revoked subject must not present its cached credential anymore
+        if revocations.is_revoked(for_identity, issuer).await? {
+            debug!(
+                "The credential for: {} was revoked by: {}. Deleting...",
+                for_identity, issuer
+            );
+            if let Some(err) = cache.delete(for_identity, issuer, scope).await.err() {
+                error!(
+                    "Error deleting revoked credential for {} from {}. Err={}",
+                    for_identity, issuer, err
+                );
+            }
+            return Ok(None);
+        }
+
         // check if we have a valid cached credential
         if let Some(cached_credential) = cache.get(for_identity, issuer, scope).await? {
             // add an extra minute to have a bit of leeway for clock skew
@@ -87,15 +107,22 @@ pub struct CachedCredentialRetrieverCreator {
     issuer: Identifier,
     scope: String,
     cache: Arc<dyn CredentialRepository>,
+    revocations: Arc<dyn RevocationRepository>,
 }
 
 impl CachedCredentialRetrieverCreator {
     /// Constructor
-    pub fn new(issuer: Identifier, scope: String, cache: Arc<dyn CredentialRepository>) -> Self {
+    pub fn new(
+        issuer: Identifier,
+        scope: String,
+        cache: Arc<dyn CredentialRepository>,
+        revocations: Arc<dyn RevocationRepository>,
+    ) -> Self {
         Self {
             issuer,
             scope,
             cache,
+            revocations,
         }
     }
 }
@@ -108,6 +135,7 @@ impl CredentialRetrieverCreator for CachedCredentialRetrieverCreator {
             subject.clone(),
             self.scope.clone(),
             self.cache.clone(),
+            self.revocations.clone(),
         )))
     }
 }
@@ -126,6 +154,7 @@ impl CredentialRetriever for CachedCredentialRetriever {
             &self.scope,
             now,
             self.cache.clone(),
+            self.revocations.clone(),
             // We can't refresh the credential, so let's still present it even if it's
             // potentially expired
             0.into(),
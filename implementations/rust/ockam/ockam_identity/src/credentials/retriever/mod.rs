@@ -3,8 +3,10 @@ mod cache_retriever;
 mod credential_retriever;
 mod memory_retriever;
 mod remote_retriever;
+mod revocation_list_retriever;
 
 pub use cache_retriever::*;
 pub use credential_retriever::*;
 pub use memory_retriever::*;
 pub use remote_retriever::*;
+pub use revocation_list_retriever::*;
@@ -138,6 +138,7 @@ impl RemoteCredentialRetriever {
             self.secure_channels
                 .identities
                 .cached_credentials_repository(),
+            self.secure_channels.identities.revocation_repository(),
             self.timing_options.clock_skew_gap,
         )
         .await?
@@ -0,0 +1,175 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, info, warn};
+
+use ockam_core::api::Request;
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::{Arc, RwLock};
+use ockam_core::compat::time::Duration;
+use ockam_core::{Result, Route};
+use ockam_node::Context;
+use ockam_transport_core::Transport;
+
+use crate::models::RevocationListAndPurposeKey;
+use crate::utils::now;
+use crate::{
+    Identifier, RemoteCredentialRetrieverTimingOptions, SecureChannels, SecureClient,
+    TimestampInSeconds,
+};
+
+/// Default interval between two requests for the revocation list of an Authority
+pub const DEFAULT_REVOCATION_LIST_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// This retriever periodically requests the revocation list issued by an Authority node,
+/// verifies it, and caches its content in the [`crate::RevocationRepository`].
+///
+/// The cached copy is then consulted when a credential is presented, so that a revoked
+/// subject is rejected before the expiration of its credential.
+#[derive(Clone)]
+pub struct RemoteRevocationListRetriever {
+    ctx: Arc<Context>,
+    transport: Arc<dyn Transport>,
+    secure_channels: Arc<SecureChannels>,
+    issuer: Identifier,
+    route: Route,
+    service_address: String,
+    subject: Identifier,
+    refresh_interval: Duration,
+    timing_options: RemoteCredentialRetrieverTimingOptions,
+    last_created_at: Arc<RwLock<Option<TimestampInSeconds>>>,
+    is_stopped: Arc<AtomicBool>,
+}
+
+impl RemoteRevocationListRetriever {
+    /// Create a new revocation list retriever
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: Context,
+        transport: Arc<dyn Transport>,
+        secure_channels: Arc<SecureChannels>,
+        issuer: Identifier,
+        route: Route,
+        service_address: String,
+        subject: Identifier,
+        refresh_interval: Duration,
+    ) -> Self {
+        debug!(
+            "Creation of RemoteRevocationListRetriever for: {}, authority: {}",
+            subject, issuer
+        );
+
+        Self {
+            ctx: Arc::new(ctx),
+            transport,
+            secure_channels,
+            issuer,
+            route,
+            service_address,
+            subject,
+            refresh_interval,
+            timing_options: Default::default(),
+            last_created_at: Default::default(),
+            is_stopped: Default::default(),
+        }
+    }
+
+    /// Retrieve the revocation list a first time and schedule its periodic refresh.
+    /// This is done in the background: a failure to reach the Authority is not fatal,
+    /// the refresh is simply retried later
+    pub fn start(&self) {
+        let s = self.clone();
+        ockam_node::spawn(async move {
+            loop {
+                if s.is_stopped.load(Ordering::Relaxed) {
+                    debug!("Stopped the revocation list refresh from {}", s.issuer);
+                    break;
+                }
+                if let Err(err) = s.refresh().await {
+                    warn!(
+                        "Error retrieving the revocation list from {}: {}",
+                        s.issuer, err
+                    );
+                }
+                match now() {
+                    Ok(now) => {
+                        s.ctx
+                            .sleep_long_until(*now + s.refresh_interval.as_secs())
+                            .await
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Error getting the current time to schedule the next revocation list refresh from {}: {}",
+                            s.issuer, err
+                        );
+                        s.ctx.sleep(s.refresh_interval).await
+                    }
+                }
+            }
+        });
+    }
+
+    /// Stop refreshing the revocation list
+    pub fn stop(&self) {
+        self.is_stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Retrieve the latest revocation list from the Authority, verify it
+    /// and replace the cached revoked subjects
+    pub async fn refresh(&self) -> Result<()> {
+        let client = SecureClient::new(
+            self.secure_channels.clone(),
+            None,
+            self.transport.clone(),
+            self.route.clone(),
+            &self.issuer,
+            &self.subject,
+            self.timing_options.secure_channel_creation_timeout,
+            self.timing_options.request_timeout,
+        );
+
+        let revocation_list: RevocationListAndPurposeKey = client
+            .ask(&self.ctx, &self.service_address, Request::get("/"))
+            .await?
+            .success()?;
+
+        let revocation_list_data = self
+            .secure_channels
+            .identities()
+            .credentials()
+            .credentials_verification()
+            .verify_revocation_list(&[self.issuer.clone()], &revocation_list)
+            .await?;
+
+        let last_created_at = *self.last_created_at.read().unwrap();
+        if let Some(last_created_at) = last_created_at {
+            if revocation_list_data.created_at < last_created_at {
+                debug!(
+                    "Ignoring a revocation list from {} older than the cached one",
+                    self.issuer
+                );
+                return Ok(());
+            }
+        }
+
+        let identities = self.secure_channels.identities();
+        let identities_attributes = identities.identities_attributes();
+        for revoked_subject in revocation_list_data.revoked_subjects.iter() {
+            // attributes of a revoked subject must not be used for access control anymore
+            identities_attributes
+                .delete_attributes(&revoked_subject.subject, &self.issuer)
+                .await?;
+        }
+
+        info!(
+            "Retrieved a revocation list with {} revoked subjects from {}",
+            revocation_list_data.revoked_subjects.len(),
+            self.issuer
+        );
+        identities
+            .revocation_repository()
+            .put_revoked_subjects(&self.issuer, revocation_list_data.revoked_subjects)
+            .await?;
+
+        *self.last_created_at.write().unwrap() = Some(revocation_list_data.created_at);
+        Ok(())
+    }
+}
@@ -68,6 +68,16 @@ pub enum IdentityError {
     AddressIsNotSubscribedForThatCredentialRetriever,
     /// Credential retriever couldn't return a credential
     NoCredential,
+    /// The Credential subject was revoked by the Authority
+    CredentialRevoked,
+    /// RevocationList Verification Failed
+    RevocationListVerificationFailed,
+    /// Unknown version of the RevocationList
+    UnknownRevocationListVersion,
+    /// Invalid data_type value for RevocationList
+    InvalidRevocationListDataType,
+    /// The other side of a secure channel did not renew its key as required by the rekey policy
+    RekeyPolicyViolation,
 }
 
 impl ockam_core::compat::error::Error for IdentityError {}
@@ -12,6 +12,9 @@ use crate::identities::storage::CredentialRepository;
 use crate::identities::storage::CredentialSqlxDatabase;
 #[cfg(feature = "storage")]
 use crate::identities::storage::IdentityAttributesSqlxDatabase;
+use crate::identities::storage::RevocationRepository;
+#[cfg(feature = "storage")]
+use crate::identities::storage::RevocationSqlxDatabase;
 use crate::identities::{ChangeHistoryRepository, IdentitiesKeys};
 use crate::models::ChangeHistory;
 use crate::purpose_keys::storage::PurposeKeysRepository;
@@ -32,6 +35,7 @@ pub struct Identities {
     identity_attributes_repository: Arc<dyn IdentityAttributesRepository>,
     purpose_keys_repository: Arc<dyn PurposeKeysRepository>,
     cached_credentials_repository: Arc<dyn CredentialRepository>,
+    revocation_repository: Arc<dyn RevocationRepository>,
 }
 
 impl Identities {
@@ -55,6 +59,11 @@ impl Identities {
         self.cached_credentials_repository.clone()
     }
 
+    /// Return the revocation repository
+    pub fn revocation_repository(&self) -> Arc<dyn RevocationRepository> {
+        self.revocation_repository.clone()
+    }
+
     /// Get an [`Identity`] from the repository
     pub async fn get_identity(&self, identifier: &Identifier) -> Result<Identity> {
         self.identities_verification()
@@ -124,6 +133,7 @@ impl Identities {
             self.purpose_keys(),
             self.identities_creation().clone(),
             self.identity_attributes_repository.clone(),
+            self.revocation_repository.clone(),
         ))
     }
 }
@@ -136,6 +146,7 @@ impl Identities {
         identity_attributes_repository: Arc<dyn IdentityAttributesRepository>,
         purpose_keys_repository: Arc<dyn PurposeKeysRepository>,
         cached_credentials_repository: Arc<dyn CredentialRepository>,
+        revocation_repository: Arc<dyn RevocationRepository>,
     ) -> Identities {
         Identities {
             vault,
@@ -143,6 +154,7 @@ impl Identities {
             identity_attributes_repository,
             purpose_keys_repository,
             cached_credentials_repository,
+            revocation_repository,
         }
     }
 
@@ -172,8 +184,10 @@ impl Identities {
             )),
             purpose_keys_repository: Arc::new(PurposeKeysSqlxDatabase::new(database.clone())),
             cached_credentials_repository: Arc::new(CredentialSqlxDatabase::new(
-                database, node_name,
+                database.clone(),
+                node_name,
             )),
+            revocation_repository: Arc::new(RevocationSqlxDatabase::new(database, node_name)),
         }
     }
 }
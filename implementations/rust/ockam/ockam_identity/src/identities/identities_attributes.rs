@@ -42,6 +42,18 @@ impl IdentitiesAttributes {
     pub async fn put_attributes(&self, subject: &Identifier, entry: AttributesEntry) -> Result<()> {
         self.repository.put_attributes(subject, entry).await
     }
+
+    /// Remove the attributes attested by a given issuer for a subject
+    #[instrument(skip_all, fields(subject = %subject, attested_by = %attested_by))]
+    pub async fn delete_attributes(
+        &self,
+        subject: &Identifier,
+        attested_by: &Identifier,
+    ) -> Result<()> {
+        self.repository
+            .delete_attributes(subject, attested_by)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -5,7 +5,7 @@ use ockam_core::Result;
 use ockam_node::database::SqlxDatabase;
 use ockam_vault::storage::SecretsRepository;
 
-use crate::identities::storage::CredentialRepository;
+use crate::identities::storage::{CredentialRepository, RevocationRepository};
 use crate::identities::{ChangeHistoryRepository, Identities};
 use crate::purpose_keys::storage::PurposeKeysRepository;
 use crate::{IdentityAttributesRepository, Vault};
@@ -18,6 +18,7 @@ pub struct IdentitiesBuilder {
     pub(crate) identity_attributes_repository: Arc<dyn IdentityAttributesRepository>,
     pub(crate) purpose_keys_repository: Arc<dyn PurposeKeysRepository>,
     pub(crate) cached_credentials_repository: Arc<dyn CredentialRepository>,
+    pub(crate) revocation_repository: Arc<dyn RevocationRepository>,
 }
 
 /// Return a default identities
@@ -81,6 +82,12 @@ impl IdentitiesBuilder {
         self
     }
 
+    /// Set a specific repository for revoked subjects
+    pub fn with_revocation_repository(mut self, repository: Arc<dyn RevocationRepository>) -> Self {
+        self.revocation_repository = repository;
+        self
+    }
+
     /// Build identities
     pub fn build(self) -> Arc<Identities> {
         Arc::new(Identities::new(
@@ -89,6 +96,7 @@ impl IdentitiesBuilder {
             self.identity_attributes_repository,
             self.purpose_keys_repository,
             self.cached_credentials_repository,
+            self.revocation_repository,
         ))
     }
 }
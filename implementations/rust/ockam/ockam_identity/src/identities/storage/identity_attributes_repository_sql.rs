@@ -70,6 +70,20 @@ impl IdentityAttributesRepository for IdentityAttributesSqlxDatabase {
         query.execute(&*self.database.pool).await.void()
     }
 
+    async fn delete_attributes(
+        &self,
+        subject: &Identifier,
+        attested_by: &Identifier,
+    ) -> Result<()> {
+        let query = query(
+            "DELETE FROM identity_attributes WHERE identifier=$1 AND attested_by=$2 AND node_name=$3",
+        )
+        .bind(subject.to_sql())
+        .bind(attested_by.to_sql())
+        .bind(self.node_name.to_sql());
+        query.execute(&*self.database.pool).await.void()
+    }
+
     // This query is regularly invoked by IdentitiesAttributes to make sure that we expire attributes regularly
     async fn delete_expired_attributes(&self, now: TimestampInSeconds) -> Result<()> {
         let query = query("DELETE FROM identity_attributes WHERE expires<=? AND node_name=?")
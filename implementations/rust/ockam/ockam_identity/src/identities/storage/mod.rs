@@ -8,11 +8,15 @@ pub use credential_repository_sql::*;
 pub use identity_attributes_repository::*;
 #[cfg(feature = "storage")]
 pub use identity_attributes_repository_sql::*;
+pub use revocation_repository::*;
+#[cfg(feature = "storage")]
+pub use revocation_repository_sql::*;
 
 mod attributes_entry;
 mod change_history_repository;
 mod credential_repository;
 mod identity_attributes_repository;
+mod revocation_repository;
 
 #[cfg(feature = "storage")]
 mod change_history_repository_sql;
@@ -20,3 +24,5 @@ mod change_history_repository_sql;
 mod credential_repository_sql;
 #[cfg(feature = "storage")]
 mod identity_attributes_repository_sql;
+#[cfg(feature = "storage")]
+mod revocation_repository_sql;
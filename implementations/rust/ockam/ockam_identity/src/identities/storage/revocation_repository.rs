@@ -0,0 +1,30 @@
+use crate::models::RevokedSubject;
+use crate::Identifier;
+use async_trait::async_trait;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+
+/// This trait supports the persistence of the subjects revoked by an Authority
+#[async_trait]
+pub trait RevocationRepository: Send + Sync + 'static {
+    /// Return true if the subject was revoked by the given issuer
+    async fn is_revoked(&self, subject: &Identifier, issuer: &Identifier) -> Result<bool>;
+
+    /// Get all the subjects revoked by the given issuer
+    async fn get_revoked_subjects(&self, issuer: &Identifier) -> Result<Vec<RevokedSubject>>;
+
+    /// Add a revoked subject for the given issuer
+    async fn add_revoked_subject(
+        &self,
+        issuer: &Identifier,
+        revoked_subject: RevokedSubject,
+    ) -> Result<()>;
+
+    /// Replace all the subjects revoked by the given issuer
+    async fn put_revoked_subjects(
+        &self,
+        issuer: &Identifier,
+        revoked_subjects: Vec<RevokedSubject>,
+    ) -> Result<()>;
+}
@@ -0,0 +1,174 @@
+use core::str::FromStr;
+
+use sqlx::*;
+use tracing::debug;
+
+use ockam_core::async_trait;
+use ockam_core::Result;
+use ockam_node::database::{FromSqlxError, SqlxDatabase, ToSqlxType, ToVoid};
+
+use crate::models::{Identifier, RevokedSubject};
+use crate::{RevocationRepository, TimestampInSeconds};
+
+/// Implementation of [`RevocationRepository`] trait based on an underlying database
+/// using sqlx as its API, and Sqlite as its driver
+#[derive(Clone)]
+pub struct RevocationSqlxDatabase {
+    database: SqlxDatabase,
+    node_name: String,
+}
+
+impl RevocationSqlxDatabase {
+    /// Create a new database
+    pub fn new(database: SqlxDatabase, node_name: &str) -> Self {
+        debug!("create a repository for revoked subjects");
+        Self {
+            database,
+            node_name: node_name.to_string(),
+        }
+    }
+
+    /// Create a new in-memory database
+    pub async fn create() -> Result<Self> {
+        Ok(Self::new(
+            SqlxDatabase::in_memory("revocation").await?,
+            "default",
+        ))
+    }
+}
+
+#[async_trait]
+impl RevocationRepository for RevocationSqlxDatabase {
+    async fn is_revoked(&self, subject: &Identifier, issuer: &Identifier) -> Result<bool> {
+        let query = query_as(
+            "SELECT subject_identifier, revoked_at FROM revoked_subject WHERE subject_identifier=$1 AND issuer_identifier=$2 AND node_name=$3"
+            )
+            .bind(subject.to_sql())
+            .bind(issuer.to_sql())
+            .bind(self.node_name.to_sql());
+        let row: Option<RevokedSubjectRow> = query
+            .fetch_optional(&*self.database.pool)
+            .await
+            .into_core()?;
+        Ok(row.is_some())
+    }
+
+    async fn get_revoked_subjects(&self, issuer: &Identifier) -> Result<Vec<RevokedSubject>> {
+        let query = query_as(
+            "SELECT subject_identifier, revoked_at FROM revoked_subject WHERE issuer_identifier=$1 AND node_name=$2"
+            )
+            .bind(issuer.to_sql())
+            .bind(self.node_name.to_sql());
+        let rows: Vec<RevokedSubjectRow> =
+            query.fetch_all(&*self.database.pool).await.into_core()?;
+        rows.into_iter().map(|r| r.revoked_subject()).collect()
+    }
+
+    async fn add_revoked_subject(
+        &self,
+        issuer: &Identifier,
+        revoked_subject: RevokedSubject,
+    ) -> Result<()> {
+        let query = query(
+            "INSERT OR REPLACE INTO revoked_subject (subject_identifier, issuer_identifier, revoked_at, node_name) VALUES (?, ?, ?, ?)"
+            )
+            .bind(revoked_subject.subject.to_sql())
+            .bind(issuer.to_sql())
+            .bind(revoked_subject.revoked_at.to_sql())
+            .bind(self.node_name.to_sql());
+        query.execute(&*self.database.pool).await.void()
+    }
+
+    async fn put_revoked_subjects(
+        &self,
+        issuer: &Identifier,
+        revoked_subjects: Vec<RevokedSubject>,
+    ) -> Result<()> {
+        let mut transaction = self.database.begin().await.into_core()?;
+        let query1 = query("DELETE FROM revoked_subject WHERE issuer_identifier=? AND node_name=?")
+            .bind(issuer.to_sql())
+            .bind(self.node_name.to_sql());
+        query1.execute(&mut *transaction).await.void()?;
+
+        for revoked_subject in revoked_subjects {
+            let query2 = query(
+                "INSERT OR REPLACE INTO revoked_subject (subject_identifier, issuer_identifier, revoked_at, node_name) VALUES (?, ?, ?, ?)"
+                )
+                .bind(revoked_subject.subject.to_sql())
+                .bind(issuer.to_sql())
+                .bind(revoked_subject.revoked_at.to_sql())
+                .bind(self.node_name.to_sql());
+            query2.execute(&mut *transaction).await.void()?;
+        }
+
+        transaction.commit().await.void()
+    }
+}
+
+// Low-level representation of a table row
+#[derive(FromRow)]
+struct RevokedSubjectRow {
+    subject_identifier: String,
+    revoked_at: i64,
+}
+
+impl RevokedSubjectRow {
+    fn revoked_subject(&self) -> Result<RevokedSubject> {
+        Ok(RevokedSubject {
+            subject: Identifier::from_str(&self.subject_identifier)?,
+            revoked_at: TimestampInSeconds(self.revoked_at as u64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ockam_core::compat::sync::Arc;
+
+    use super::*;
+    use crate::identities;
+    use crate::utils::now;
+
+    #[tokio::test]
+    async fn test_revocation_repository() -> Result<()> {
+        let repository: Arc<dyn RevocationRepository> =
+            Arc::new(RevocationSqlxDatabase::create().await?);
+
+        let identities = identities().await?;
+        let issuer = identities.identities_creation().create_identity().await?;
+        let subject1 = identities.identities_creation().create_identity().await?;
+        let subject2 = identities.identities_creation().create_identity().await?;
+
+        assert!(!repository.is_revoked(&subject1, &issuer).await?);
+
+        let revoked_subject1 = RevokedSubject {
+            subject: subject1.clone(),
+            revoked_at: now()?,
+        };
+        repository
+            .add_revoked_subject(&issuer, revoked_subject1.clone())
+            .await?;
+        assert!(repository.is_revoked(&subject1, &issuer).await?);
+        assert!(!repository.is_revoked(&subject1, &subject2).await?);
+        assert_eq!(
+            repository.get_revoked_subjects(&issuer).await?,
+            vec![revoked_subject1]
+        );
+
+        let revoked_subject2 = RevokedSubject {
+            subject: subject2.clone(),
+            revoked_at: now()?,
+        };
+        repository
+            .put_revoked_subjects(&issuer, vec![revoked_subject2.clone()])
+            .await?;
+        assert!(!repository.is_revoked(&subject1, &issuer).await?);
+        assert!(repository.is_revoked(&subject2, &issuer).await?);
+        assert_eq!(
+            repository.get_revoked_subjects(&issuer).await?,
+            vec![revoked_subject2]
+        );
+
+        Ok(())
+    }
+}
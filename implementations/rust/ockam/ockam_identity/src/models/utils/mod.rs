@@ -2,4 +2,5 @@ mod change_history;
 mod credentials;
 mod identifiers;
 mod purpose_key_attestation;
+mod revocation_list;
 mod timestamp;
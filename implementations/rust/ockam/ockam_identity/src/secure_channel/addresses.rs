@@ -22,6 +22,8 @@ pub(crate) struct Addresses {
     pub(crate) encryptor_api: Address,
     // Used by the encryptor itself for timer notifications (to force credentials refresh)
     pub(crate) encryptor_internal: Address,
+    // Used by the encryptor itself for timer notifications (to renew an expired key on an idle channel)
+    pub(crate) encryptor_rekey: Address,
 }
 
 impl Addresses {
@@ -39,6 +41,8 @@ impl Addresses {
             Address::random_tagged(&format!("SecureChannel.{}.encryptor.api", role_str));
         let encryptor_internal =
             Address::random_tagged(&format!("SecureChannel.{}.encryptor.internal", role_str));
+        let encryptor_rekey =
+            Address::random_tagged(&format!("SecureChannel.{}.encryptor.rekey", role_str));
 
         Self {
             decryptor_internal,
@@ -47,6 +51,7 @@ impl Addresses {
             encryptor,
             encryptor_api,
             encryptor_internal,
+            encryptor_rekey,
         }
     }
 }
@@ -10,11 +10,12 @@ use crate::secure_channel::encryptor::{Encryptor, KEY_RENEWAL_INTERVAL};
 use crate::secure_channel::handshake::handshake_state_machine::CommonStateMachine;
 use crate::secure_channel::key_tracker::KeyTracker;
 use crate::secure_channel::nonce_tracker::NonceTracker;
+use crate::secure_channel::rekey_metrics::{KeyUsage, RekeyMetricsRecorder};
 use crate::secure_channel::Addresses;
 use crate::{
     DecryptionRequest, DecryptionResponse, Identities, IdentityError,
     IdentitySecureChannelLocalInfo, PlaintextPayloadMessage, RefreshCredentialsMessage,
-    SecureChannelMessage,
+    RekeyPolicy, SecureChannelMessage,
 };
 
 use crate::secure_channel::encryptor_worker::SecureChannelSharedState;
@@ -46,16 +47,18 @@ impl DecryptorHandler {
         vault: Arc<dyn VaultForSecureChannels>,
         their_identity_id: Identifier,
         shared_state: SecureChannelSharedState,
-    ) -> Self {
-        Self {
+        rekey_policy: RekeyPolicy,
+        rekey_metrics: RekeyMetricsRecorder,
+    ) -> Result<Self> {
+        Ok(Self {
             role,
             addresses,
             their_identity_id,
-            decryptor: Decryptor::new(key, vault),
+            decryptor: Decryptor::new(key, vault, rekey_policy, rekey_metrics)?,
             identities,
             authority,
             shared_state,
-        }
+        })
     }
 
     #[instrument(skip_all)]
@@ -207,15 +210,31 @@ pub(crate) struct Decryptor {
     vault: Arc<dyn VaultForSecureChannels>,
     key_tracker: KeyTracker,
     nonce_tracker: NonceTracker,
+    rekey_policy: RekeyPolicy,
+    key_usage: KeyUsage,
+    rekey_metrics: RekeyMetricsRecorder,
 }
 
 impl Decryptor {
-    pub fn new(key: AeadSecretKeyHandle, vault: Arc<dyn VaultForSecureChannels>) -> Self {
-        Self {
+    pub fn new(
+        key: AeadSecretKeyHandle,
+        vault: Arc<dyn VaultForSecureChannels>,
+        rekey_policy: RekeyPolicy,
+        rekey_metrics: RekeyMetricsRecorder,
+    ) -> Result<Self> {
+        let now = ockam_core::compat::time::now()?;
+        rekey_metrics.start_decryption(now);
+        let max_intervals_ahead = rekey_policy.max_intervals_ahead();
+        Ok(Self {
             vault,
-            key_tracker: KeyTracker::new(key, KEY_RENEWAL_INTERVAL),
-            nonce_tracker: NonceTracker::new(),
-        }
+            key_tracker: KeyTracker::new(key, KEY_RENEWAL_INTERVAL)
+                .with_max_intervals_ahead(max_intervals_ahead),
+            nonce_tracker: NonceTracker::new()
+                .with_max_shift(max_intervals_ahead * KEY_RENEWAL_INTERVAL),
+            rekey_policy,
+            key_usage: KeyUsage::new(now),
+            rekey_metrics,
+        })
     }
 
     /// Restore 12-byte nonce needed for AES GCM from 8 byte that we use for noise
@@ -234,15 +253,32 @@ impl Decryptor {
         }
 
         let (nonce, nonce_buffer) = Self::convert_nonce_from_small(&payload[..8])?;
+
+        // the other side must renew its key after the number of messages set by the rekey policy
+        if nonce % KEY_RENEWAL_INTERVAL >= self.rekey_policy.messages() {
+            warn!(
+                "This nonce exceeds the number of messages allowed per key: {}",
+                nonce
+            );
+            return Err(IdentityError::RekeyPolicyViolation)?;
+        }
         let nonce_tracker = self.nonce_tracker.mark(nonce)?;
 
         // get the key corresponding to the current nonce and
-        // rekey if necessary
-        let key = if let Some(key) = self.key_tracker.get_key(nonce)? {
-            key
+        // rekey if necessary. Several keys must be derived if some messages
+        // starting new intervals have been lost
+        let (key, new_keys) = if let Some(key) = self.key_tracker.get_key(nonce)? {
+            (key, vec![])
         } else {
-            Encryptor::rekey(&self.vault, &self.key_tracker.current_key).await?
+            let mut new_keys = vec![];
+            let mut key = self.key_tracker.current_key.clone();
+            for _ in 0..self.key_tracker.intervals_ahead(nonce) {
+                key = Encryptor::rekey(&self.vault, &key).await?;
+                new_keys.push(key.clone());
+            }
+            (key, new_keys)
         };
+        let is_new_key = !new_keys.is_empty();
 
         // to improve protection against connection disruption attacks, we want to validate the
         // message with a decryption _before_ committing to the new state
@@ -251,15 +287,62 @@ impl Decryptor {
             .aead_decrypt(&key, &payload[8..], &nonce_buffer, &[])
             .await;
 
-        if result.is_ok() {
-            self.nonce_tracker = nonce_tracker;
-            if let Some(key_to_delete) = self.key_tracker.update_key(key)? {
-                self.vault.delete_aead_secret_key(key_to_delete).await?;
+        match &result {
+            Ok(decrypted) => {
+                let now = ockam_core::compat::time::now()?;
+                let is_current_key = !is_new_key && key == self.key_tracker.current_key;
+                if is_current_key && self.bytes_limit_exceeded(decrypted.len()) {
+                    warn!("This message exceeds the number of bytes allowed per key");
+                    return Err(IdentityError::RekeyPolicyViolation)?;
+                }
+
+                self.nonce_tracker = nonce_tracker;
+                // the derived keys are tracked in order, otherwise the decryption key is tracked
+                let keys_to_track = if is_new_key { new_keys } else { vec![key] };
+                for key_to_track in keys_to_track {
+                    if let Some(key_to_delete) = self.key_tracker.update_key(key_to_track)? {
+                        self.vault.delete_aead_secret_key(key_to_delete).await?;
+                    }
+                }
+
+                if is_new_key {
+                    self.rekey_metrics
+                        .record_decryption_rekey(&self.key_usage, now);
+                    self.key_usage = KeyUsage::new(now);
+                }
+                if is_new_key || is_current_key {
+                    self.key_usage.add_message(decrypted.len());
+                }
+            }
+            Err(_) => {
+                // the keys derived for an invalid message are not kept
+                for new_key in new_keys {
+                    self.vault.delete_aead_secret_key(new_key).await?;
+                }
             }
         }
         result
     }
 
+    /// Return true if decrypting a message of the given length with the current key
+    /// exceeds the number of bytes allowed by the rekey policy.
+    /// The first message decrypted with a key is always accepted
+    fn bytes_limit_exceeded(&self, length: usize) -> bool {
+        match self.rekey_policy.bytes() {
+            Some(bytes) => {
+                self.key_usage.messages > 0
+                    && self.key_usage.bytes.saturating_add(length as u64) > bytes
+            }
+            None => false,
+        }
+    }
+
+    /// Return the rekey metrics of the secure channel
+    #[cfg(test)]
+    pub(crate) fn rekey_metrics(&self) -> crate::SecureChannelRekeyMetrics {
+        self.rekey_metrics.get()
+    }
+
     /// Remove the channel keys on shutdown
     #[instrument(skip_all)]
     pub(crate) async fn shutdown(&self) -> Result<()> {
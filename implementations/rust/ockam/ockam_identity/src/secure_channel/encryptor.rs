@@ -1,3 +1,4 @@
+use core::time::Duration;
 use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
 use ockam_core::errcode::{Kind, Origin};
@@ -5,18 +6,27 @@ use ockam_core::{Error, Result};
 use ockam_vault::{AeadSecretKeyHandle, VaultForSecureChannels};
 use tracing_attributes::instrument;
 
-use crate::IdentityError;
+use crate::secure_channel::rekey_metrics::{KeyUsage, RekeyMetricsRecorder};
+use crate::{IdentityError, RekeyPolicy};
 
 pub(crate) struct Encryptor {
     key: AeadSecretKeyHandle,
+    // interval of nonces corresponding to the current key
+    key_interval: u64,
     nonce: u64,
     vault: Arc<dyn VaultForSecureChannels>,
+    rekey_policy: RekeyPolicy,
+    key_usage: KeyUsage,
+    rekey_metrics: RekeyMetricsRecorder,
 }
 
 // To simplify the implementation we use the same constant for the size of the message
 // window we accept with the message period used to rekey.
 // This means we only need to keep the current key and the previous one.
 pub(crate) const KEY_RENEWAL_INTERVAL: u64 = 32;
+// When the rekey policy renews keys before the end of their interval, the decryptor accepts
+// nonces up to this number of intervals ahead, so that lost messages don't break the channel.
+pub(crate) const MAX_INTERVALS_AHEAD: u64 = KEY_RENEWAL_INTERVAL;
 pub(crate) const SIZE_OF_NONCE: usize = 8;
 pub(crate) const SIZE_OF_TAG: usize = 16;
 pub(crate) const SIZE_OF_ENCRYPT_OVERHEAD: usize = SIZE_OF_NONCE + SIZE_OF_TAG;
@@ -56,18 +66,27 @@ impl Encryptor {
 
     #[instrument(skip_all)]
     pub async fn encrypt(&mut self, destination: &mut Vec<u8>, payload: &[u8]) -> Result<()> {
-        let current_nonce = self.nonce;
+        let mut current_nonce = self.nonce;
+        let now = ockam_core::compat::time::now()?;
+
+        // If the rekey policy requires a new key before the end of the current interval
+        // we skip the remaining nonces of the interval. This way the other side
+        // knows that the next key must be used
+        let interval_start = current_nonce - current_nonce % KEY_RENEWAL_INTERVAL;
+        if current_nonce > interval_start && self.rekey_required(payload.len(), now) {
+            current_nonce = interval_start.saturating_add(KEY_RENEWAL_INTERVAL);
+        }
+
         if current_nonce == u64::MAX {
             return Err(IdentityError::NonceOverflow)?;
         }
 
-        self.nonce += 1;
+        self.nonce = current_nonce + 1;
 
-        if current_nonce > 0 && current_nonce % KEY_RENEWAL_INTERVAL == 0 {
-            let new_key = Self::rekey(&self.vault, &self.key).await?;
-            let old_key = core::mem::replace(&mut self.key, new_key);
-            self.vault.delete_aead_secret_key(old_key).await?;
+        if current_nonce / KEY_RENEWAL_INTERVAL > self.key_interval {
+            self.renew_key(now).await?;
         }
+        self.key_usage.add_message(payload.len());
 
         let (small_nonce, nonce) = Self::convert_nonce_from_u64(current_nonce);
         destination.extend_from_slice(&small_nonce);
@@ -79,12 +98,95 @@ impl Encryptor {
         Ok(())
     }
 
+    /// Renew the current key if it has been used and its lifetime exceeds the duration
+    /// set by the rekey policy. This allows idle channels to renew their keys.
+    ///
+    /// The next message is then sent with the first nonce of the next interval.
+    /// Return true if the key has been renewed
+    #[instrument(skip_all)]
+    pub(crate) async fn renew_expired_key(&mut self) -> Result<bool> {
+        let now = ockam_core::compat::time::now()?;
+        let expired = self.rekey_policy.duration().map_or(false, |duration| {
+            self.key_usage.lifetime(now) >= duration.as_secs()
+        });
+        // an unused key does not need to be renewed, this also makes sure that
+        // an idle channel skips at most one interval
+        if !expired || self.key_usage.messages == 0 {
+            return Ok(false);
+        }
+
+        let next_interval_start = (self.key_interval + 1)
+            .checked_mul(KEY_RENEWAL_INTERVAL)
+            .ok_or(IdentityError::NonceOverflow)?;
+        self.nonce = next_interval_start;
+        self.renew_key(now).await?;
+        Ok(true)
+    }
+
+    /// Return the delay before the current key expires, if the rekey policy has a duration.
+    /// The delay for an unused key is the full duration since it will only be renewed once used
+    pub(crate) fn key_expiration_delay(&self) -> Result<Option<Duration>> {
+        let now = ockam_core::compat::time::now()?;
+        Ok(self.rekey_policy.duration().map(|duration| {
+            if self.key_usage.messages == 0 {
+                duration
+            } else {
+                let lifetime = Duration::from_secs(self.key_usage.lifetime(now));
+                duration
+                    .saturating_sub(lifetime)
+                    .max(Duration::from_secs(1))
+            }
+        }))
+    }
+
+    /// Replace the current key with the key of the next interval
+    async fn renew_key(&mut self, now: u64) -> Result<()> {
+        let new_key = Self::rekey(&self.vault, &self.key).await?;
+        let old_key = core::mem::replace(&mut self.key, new_key);
+        self.vault.delete_aead_secret_key(old_key).await?;
+        self.key_interval += 1;
+        self.rekey_metrics
+            .record_encryption_rekey(&self.key_usage, now);
+        self.key_usage = KeyUsage::new(now);
+        Ok(())
+    }
+
+    /// Return true if the current key must be renewed before encrypting a payload
+    fn rekey_required(&self, payload_length: usize, now: u64) -> bool {
+        let messages_limit_reached = self.key_usage.messages >= self.rekey_policy.messages();
+        let bytes_limit_reached = self.rekey_policy.bytes().map_or(false, |bytes| {
+            self.key_usage.bytes.saturating_add(payload_length as u64) > bytes
+        });
+        let time_limit_reached = self.rekey_policy.duration().map_or(false, |duration| {
+            self.key_usage.lifetime(now) >= duration.as_secs()
+        });
+        messages_limit_reached || bytes_limit_reached || time_limit_reached
+    }
+
     pub fn new(
         key: AeadSecretKeyHandle,
         nonce: u64,
         vault: Arc<dyn VaultForSecureChannels>,
-    ) -> Self {
-        Self { key, nonce, vault }
+        rekey_policy: RekeyPolicy,
+        rekey_metrics: RekeyMetricsRecorder,
+    ) -> Result<Self> {
+        let now = ockam_core::compat::time::now()?;
+        rekey_metrics.start_encryption(now);
+        Ok(Self {
+            key,
+            key_interval: nonce / KEY_RENEWAL_INTERVAL,
+            nonce,
+            vault,
+            rekey_policy,
+            key_usage: KeyUsage::new(now),
+            rekey_metrics,
+        })
+    }
+
+    /// Return the rekey metrics of the secure channel
+    #[cfg(test)]
+    pub(crate) fn rekey_metrics(&self) -> crate::SecureChannelRekeyMetrics {
+        self.rekey_metrics.get()
     }
 
     #[instrument(skip_all)]
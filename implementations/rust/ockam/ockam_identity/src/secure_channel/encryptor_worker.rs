@@ -9,7 +9,7 @@ use ockam_core::compat::vec::Vec;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{async_trait, Decodable, Error, LocalMessage, Route};
 use ockam_core::{Any, Result, Routed, Worker};
-use ockam_node::Context;
+use ockam_node::{Context, DelayedEvent};
 
 use crate::models::CredentialAndPurposeKey;
 use crate::secure_channel::addresses::Addresses;
@@ -37,6 +37,7 @@ pub(crate) struct EncryptorWorker {
     credential_retriever: Option<Arc<dyn CredentialRetriever>>,
     last_presented_credential: Option<CredentialAndPurposeKey>,
     shared_state: SecureChannelSharedState,
+    rekey_timer: Option<DelayedEvent<()>>,
 }
 
 impl EncryptorWorker {
@@ -62,6 +63,7 @@ impl EncryptorWorker {
             credential_retriever,
             last_presented_credential,
             shared_state,
+            rekey_timer: None,
         }
     }
 
@@ -288,6 +290,27 @@ impl EncryptorWorker {
         Ok(())
     }
 
+    /// Renew the current key if it expired while the channel was idle
+    /// and schedule the next check
+    #[instrument(skip_all)]
+    async fn handle_rekey_timer(&mut self) -> Result<()> {
+        if self.encryptor.renew_expired_key().await? {
+            debug!("Renewed the expired key of {}", self.addresses.encryptor);
+        }
+        self.schedule_rekey_timer().await
+    }
+
+    /// Schedule a check of the current key expiration, if the rekey policy has a duration
+    async fn schedule_rekey_timer(&mut self) -> Result<()> {
+        if let (Some(rekey_timer), Some(delay)) = (
+            self.rekey_timer.as_mut(),
+            self.encryptor.key_expiration_delay()?,
+        ) {
+            rekey_timer.schedule(delay).await?;
+        }
+        Ok(())
+    }
+
     async fn send_close_channel(&mut self, ctx: &Context) -> Result<()> {
         let msg = SecureChannelMessage::Close;
 
@@ -311,11 +334,17 @@ impl Worker for EncryptorWorker {
     type Message = Any;
     type Context = Context;
 
-    async fn initialize(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
         if let Some(credential_retriever) = &self.credential_retriever {
             credential_retriever.subscribe(&self.addresses.encryptor_internal)?;
         }
 
+        if self.encryptor.key_expiration_delay()?.is_some() {
+            self.rekey_timer =
+                Some(DelayedEvent::create(ctx, self.addresses.encryptor_rekey.clone(), ()).await?);
+            self.schedule_rekey_timer().await?;
+        }
+
         Ok(())
     }
 
@@ -333,6 +362,8 @@ impl Worker for EncryptorWorker {
             self.handle_encrypt_api(ctx, msg).await?;
         } else if msg_addr == self.addresses.encryptor_internal {
             self.handle_refresh_credentials(ctx).await?;
+        } else if msg_addr == self.addresses.encryptor_rekey {
+            self.handle_rekey_timer().await?;
         } else {
             return Err(IdentityError::UnknownChannelMsgDestination)?;
         }
@@ -345,6 +376,9 @@ impl Worker for EncryptorWorker {
         if let Some(credential_retriever) = &self.credential_retriever {
             credential_retriever.unsubscribe(&self.addresses.encryptor_internal)?;
         }
+        if let Some(rekey_timer) = self.rekey_timer.as_mut() {
+            rekey_timer.cancel();
+        }
 
         let _ = context
             .stop_worker(self.addresses.decryptor_internal.clone())
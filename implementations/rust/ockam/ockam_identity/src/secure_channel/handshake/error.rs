@@ -16,6 +16,8 @@ pub enum XXError {
     ExceededMaxMessageLen,
     /// Invalid internal state.
     InvalidInternalState,
+    /// The key exchange requested by the initiator is not allowed by the responder.
+    KeyExchangeNotAllowed,
 }
 
 impl StdError for XXError {}
@@ -29,6 +31,7 @@ impl fmt::Display for XXError {
                 write!(f, "exceeded maximum allowed message length for noise")
             }
             Self::InvalidInternalState => write!(f, "invalid internal state"),
+            Self::KeyExchangeNotAllowed => write!(f, "key exchange not allowed"),
         }
     }
 }
@@ -41,6 +44,7 @@ impl From<XXError> for Error {
             XXError::MessageLenMismatch => Kind::Misuse,
             XXError::ExceededMaxMessageLen => Kind::Invalid,
             XXError::InvalidInternalState => Kind::Internal,
+            XXError::KeyExchangeNotAllowed => Kind::Unsupported,
         };
 
         Error::new(Origin::KeyExchange, kind, err)
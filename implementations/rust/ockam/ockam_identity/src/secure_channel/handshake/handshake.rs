@@ -4,8 +4,10 @@ use ockam_core::compat::vec::Vec;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{Error, Result};
 use ockam_vault::{
-    AeadSecretKeyHandle, HKDFNumberOfOutputs, SecretBufferHandle, VaultForSecureChannels,
-    X25519PublicKey, X25519SecretKeyHandle, X25519_PUBLIC_KEY_LENGTH,
+    AeadSecretKeyHandle, HKDFNumberOfOutputs, MlKem768Ciphertext, MlKem768PublicKey,
+    MlKem768SecretKeyHandle, SecretBufferHandle, VaultForSecureChannels, X25519PublicKey,
+    X25519SecretKeyHandle, ML_KEM_768_CIPHERTEXT_LENGTH, ML_KEM_768_PUBLIC_KEY_LENGTH,
+    X25519_PUBLIC_KEY_LENGTH,
 };
 use sha2::{Digest, Sha256};
 use Status::*;
@@ -13,6 +15,7 @@ use Status::*;
 use crate::secure_channel::handshake::error::XXError;
 use crate::secure_channel::handshake::handshake_state_machine::{HandshakeKeys, Status};
 use crate::secure_channel::Role;
+use crate::KeyExchange;
 
 /// The number of bytes in a SHA256 digest
 pub const SHA256_SIZE: usize = 32;
@@ -20,14 +23,25 @@ pub const SHA256_SIZE: usize = 32;
 pub const AES_GCM_TAGSIZE: usize = 16;
 /// Maximum allowed noise message size
 pub const NOISE_MAX_MESSAGE_SIZE: usize = 65535;
+/// The number of bytes of the encrypted ML-KEM ciphertext sent in message 2 of a hybrid handshake
+const ENCRYPTED_KEM_CIPHERTEXT_LENGTH: usize = ML_KEM_768_CIPHERTEXT_LENGTH + AES_GCM_TAGSIZE;
 
 /// Implementation of a Handshake for the noise protocol
 /// The first members are used in the implementation of some of the protocol steps, for example to
 /// encrypt messages
 /// The variables used in the protocol itself: s, e, rs, re,... are handled in `HandshakeState`
+///
+/// With the hybrid key exchange, the pattern is extended with an ephemeral ML-KEM key, similar
+/// to the `XXhfs` pattern of the Noise KEM extensions:
+///
+///   -> e, e1
+///   <- e, ee, ekem1, s, es
+///   -> s, se
+///
 pub(super) struct Handshake {
     vault: Arc<dyn VaultForSecureChannels>,
     protocol_name: [u8; 32],
+    key_exchange: KeyExchange,
     pub(super) state: HandshakeState,
 }
 
@@ -55,6 +69,18 @@ impl Handshake {
         state.mix_hash(&e_pub_key.0);
         let mut message1 = e_pub_key.0.to_vec();
 
+        // output e1.pubKey for the hybrid key exchange
+        if self.is_hybrid() {
+            let e_kem = self
+                .vault
+                .generate_ephemeral_ml_kem_768_secret_key()
+                .await?;
+            let e_kem_pub_key = self.vault.get_ml_kem_768_public_key(&e_kem).await?;
+            state.e_kem = Some(e_kem);
+            state.mix_hash(&e_kem_pub_key.0);
+            message1.extend_from_slice(&e_kem_pub_key.0);
+        }
+
         // output message 1 payload
         message1.extend_from_slice(payload);
         state.mix_hash(payload);
@@ -80,8 +106,15 @@ impl Handshake {
 
         state.re = Some(X25519PublicKey(*key));
 
+        // read e1.pubKey for the hybrid key exchange
+        if self.is_hybrid() {
+            let kem_key = Self::read_message1_kem_key(message1)?;
+            state.mix_hash(kem_key);
+            state.re_kem = Some(MlKem768PublicKey(kem_key.to_vec()));
+        }
+
         // decode payload
-        let payload = Self::read_message1_payload(message1)?;
+        let payload = Self::read_message1_payload(message1, self.message1_kem_length())?;
         state.mix_hash(payload);
 
         self.state = state;
@@ -102,6 +135,16 @@ impl Handshake {
         let dh = self.dh(state.e()?, state.re()?).await?;
         self.hkdf(&mut state, dh).await?;
 
+        // encapsulate a secret for re1, encrypt and output the KEM ciphertext
+        // ck, k = HKDF(ck, KEM shared secret, 2)
+        if self.is_hybrid() {
+            let (ciphertext, shared_secret) =
+                self.vault.ml_kem_768_encapsulate(state.re_kem()?).await?;
+            let c = self.encrypt_and_hash(&mut state, &ciphertext.0).await?;
+            message2.extend_from_slice(c.as_slice());
+            self.hkdf(&mut state, shared_secret).await?;
+        }
+
         // encrypt and output s.pubKey
         let s_pub_key = self.get_public_key(state.s()?).await?;
         let c = self.encrypt_and_hash(&mut state, &s_pub_key.0).await?;
@@ -139,8 +182,20 @@ impl Handshake {
         let dh = self.dh(state.e()?, state.re()?).await?;
         self.hkdf(&mut state, dh).await?;
 
+        // decrypt the KEM ciphertext and decapsulate the shared secret with e1
+        // ck, k = HKDF(ck, KEM shared secret, 2)
+        if self.is_hybrid() {
+            let c = Self::read_message2_encrypted_kem_ciphertext(message2)?;
+            let ciphertext = MlKem768Ciphertext(self.hash_and_decrypt(&mut state, c).await?);
+            let shared_secret = self
+                .vault
+                .ml_kem_768_decapsulate(state.e_kem()?, &ciphertext)
+                .await?;
+            self.hkdf(&mut state, shared_secret).await?;
+        }
+
         // decrypt rs.pubKey
-        let rs_pub_key = Self::read_message2_encrypted_key(message2)?;
+        let rs_pub_key = Self::read_message2_encrypted_key(message2, self.message2_kem_length())?;
         let rs_pub_key = self.hash_and_decrypt(&mut state, rs_pub_key).await?;
         let rs_pub_key = X25519PublicKey(
             rs_pub_key
@@ -154,7 +209,7 @@ impl Handshake {
         self.hkdf(&mut state, dh).await?;
 
         // decrypt payload
-        let c = Self::read_message2_payload(message2)?;
+        let c = Self::read_message2_payload(message2, self.message2_kem_length())?;
         let payload = self.hash_and_decrypt(&mut state, c).await?;
 
         self.state = state;
@@ -259,10 +314,54 @@ impl Handshake {
         Ok(Handshake {
             vault,
             protocol_name: *PROTOCOL_NAME,
+            key_exchange: KeyExchange::X25519,
             state: HandshakeState::new(static_key, ephemeral_key),
         })
     }
 
+    /// Set the key exchange to perform. This must be done before the handshake is initialized
+    /// since the protocol name depends on the key exchange
+    pub(super) fn set_key_exchange(&mut self, key_exchange: KeyExchange) {
+        self.protocol_name = match key_exchange {
+            KeyExchange::X25519 => *PROTOCOL_NAME,
+            KeyExchange::HybridX25519MlKem768 => *HYBRID_PROTOCOL_NAME,
+        };
+        self.key_exchange = key_exchange;
+    }
+
+    /// Return the key exchange used by an initiator given its first message.
+    /// The first message does not contain a payload, so its length only depends on the key exchange
+    pub(super) fn get_key_exchange_from_message1(message1: &[u8]) -> KeyExchange {
+        if message1.len() == X25519_PUBLIC_KEY_LENGTH + ML_KEM_768_PUBLIC_KEY_LENGTH {
+            KeyExchange::HybridX25519MlKem768
+        } else {
+            KeyExchange::X25519
+        }
+    }
+
+    /// Return true if this handshake performs the hybrid X25519 + ML-KEM key exchange
+    fn is_hybrid(&self) -> bool {
+        self.key_exchange == KeyExchange::HybridX25519MlKem768
+    }
+
+    /// Number of bytes of the ML-KEM public key present in message 1
+    fn message1_kem_length(&self) -> usize {
+        if self.is_hybrid() {
+            ML_KEM_768_PUBLIC_KEY_LENGTH
+        } else {
+            0
+        }
+    }
+
+    /// Number of bytes of the encrypted ML-KEM ciphertext present in message 2
+    fn message2_kem_length(&self) -> usize {
+        if self.is_hybrid() {
+            ENCRYPTED_KEM_CIPHERTEXT_LENGTH
+        } else {
+            0
+        }
+    }
+
     /// Import the ck secret
     async fn import_ck_secret(&self, content: Vec<u8>) -> Result<SecretBufferHandle> {
         self.vault.import_secret_buffer(content).await
@@ -374,6 +473,13 @@ impl Handshake {
             .delete_ephemeral_x25519_secret_key(self.state.take_e()?)
             .await?;
 
+        if let Some(e_kem) = self.state.e_kem.take() {
+            _ = self
+                .vault
+                .delete_ephemeral_ml_kem_768_secret_key(e_kem)
+                .await?;
+        }
+
         Ok(())
     }
 }
@@ -381,10 +487,13 @@ impl Handshake {
 cfg_if! {
     if #[cfg(any(not(feature = "disable_default_noise_protocol"), feature = "OCKAM_XX_25519_AES256_GCM_SHA256"))] {
         pub const PROTOCOL_NAME: &[u8; 32] = b"OCKAM_XX_25519_AES256_GCM_SHA256";
+        pub const HYBRID_PROTOCOL_NAME: &[u8; 32] = b"OCKAM_XXhfs_25519MLKEM768_AES256";
     } else if #[cfg(feature = "OCKAM_XX_25519_AES128_GCM_SHA256")] {
         pub const PROTOCOL_NAME: &[u8; 32] = b"OCKAM_XX_25519_AES128_GCM_SHA256";
+        pub const HYBRID_PROTOCOL_NAME: &[u8; 32] = b"OCKAM_XXhfs_25519MLKEM768_AES128";
     } else if #[cfg(feature = "OCKAM_XX_25519_ChaChaPolyBLAKE2s")] {
         pub const PROTOCOL_NAME: &[u8; 32] = b"OCKAM_XX_25519_ChaChaPolyBLAKE2s";
+        pub const HYBRID_PROTOCOL_NAME: &[u8; 32] = b"OCKAM_XXhfs_25519MLKEM768_ChaCha";
     }
 }
 
@@ -402,21 +511,35 @@ impl Handshake {
         vault.generate_ephemeral_x25519_secret_key().await
     }
 
-    /// Read the message 1 payload which is present after the public key
-    fn read_message1_payload(message: &[u8]) -> Result<&[u8]> {
-        Self::read_end::<X25519_PUBLIC_KEY_LENGTH>(message)
+    /// Read the message 1 ML-KEM public key which is present after the public key
+    /// for the hybrid key exchange
+    fn read_message1_kem_key(message: &[u8]) -> Result<&[u8]> {
+        Self::read_middle::<X25519_PUBLIC_KEY_LENGTH, ML_KEM_768_PUBLIC_KEY_LENGTH>(message)
+    }
+
+    /// Read the message 1 payload which is present after the public key(s)
+    fn read_message1_payload(message: &[u8], kem_length: usize) -> Result<&[u8]> {
+        Self::read_end_at(message, X25519_PUBLIC_KEY_LENGTH + kem_length)
+    }
+
+    /// Read the message 2 encrypted ML-KEM ciphertext which is present after the public key
+    /// for the hybrid key exchange
+    fn read_message2_encrypted_kem_ciphertext(message: &[u8]) -> Result<&[u8]> {
+        Self::read_middle::<X25519_PUBLIC_KEY_LENGTH, ENCRYPTED_KEM_CIPHERTEXT_LENGTH>(message)
     }
 
     /// Read the message 2 encrypted key, which is present after the public key
-    fn read_message2_encrypted_key(message: &[u8]) -> Result<&[u8]> {
+    /// and the encrypted ML-KEM ciphertext if any
+    fn read_message2_encrypted_key(message: &[u8], kem_length: usize) -> Result<&[u8]> {
         const L: usize = X25519_PUBLIC_KEY_LENGTH + AES_GCM_TAGSIZE;
+        let message = Self::read_end_at(message, kem_length)?;
         Self::read_middle::<X25519_PUBLIC_KEY_LENGTH, L>(message)
     }
 
     /// Read the message 2 encrypted payload, which is present after the encrypted key
-    fn read_message2_payload(message: &[u8]) -> Result<&[u8]> {
+    fn read_message2_payload(message: &[u8], kem_length: usize) -> Result<&[u8]> {
         const L: usize = 2 * X25519_PUBLIC_KEY_LENGTH + AES_GCM_TAGSIZE;
-        Self::read_end::<L>(message)
+        Self::read_end_at(message, L + kem_length)
     }
 
     /// Read the message 3 encrypted key at the beginning of the message
@@ -449,6 +572,15 @@ impl Handshake {
         Ok(message[N..].try_into().unwrap())
     }
 
+    /// Read the bytes of the message after the first 'start' bytes, when 'start' is only known at runtime
+    fn read_end_at(message: &[u8], start: usize) -> Result<&[u8]> {
+        if message.len() < start {
+            return Err(XXError::MessageLenMismatch)?;
+        }
+
+        Ok(&message[start..])
+    }
+
     /// Read 'length' bytes of the message after the first 'drop_length' bytes
     fn read_middle<const N: usize, const L: usize>(message: &[u8]) -> Result<&[u8]> {
         if message.len() < N + L {
@@ -469,8 +601,10 @@ impl Handshake {
 pub(super) struct HandshakeState {
     pub(super) s: Option<X25519SecretKeyHandle>,
     e: Option<X25519SecretKeyHandle>,
+    e_kem: Option<MlKem768SecretKeyHandle>,
     k: Option<AeadSecretKeyHandle>,
     re: Option<X25519PublicKey>,
+    re_kem: Option<MlKem768PublicKey>,
     pub(super) rs: Option<X25519PublicKey>,
     n: u64,
     h: [u8; SHA256_SIZE],
@@ -487,8 +621,10 @@ impl HandshakeState {
         HandshakeState {
             s: Some(s),
             e: Some(e),
+            e_kem: None,
             k: None,
             re: None,
+            re_kem: None,
             rs: None,
             n: 0,
             h: [0u8; SHA256_SIZE],
@@ -580,6 +716,26 @@ impl HandshakeState {
         })
     }
 
+    pub(super) fn e_kem(&self) -> Result<&MlKem768SecretKeyHandle> {
+        self.e_kem.as_ref().ok_or_else(|| {
+            Error::new(
+                Origin::KeyExchange,
+                Kind::Invalid,
+                "key id e1 should have been set",
+            )
+        })
+    }
+
+    pub(super) fn re_kem(&self) -> Result<&MlKem768PublicKey> {
+        self.re_kem.as_ref().ok_or_else(|| {
+            Error::new(
+                Origin::KeyExchange,
+                Kind::Invalid,
+                "public key re1 should have been set",
+            )
+        })
+    }
+
     pub(super) fn re(&self) -> Result<&X25519PublicKey> {
         self.re.as_ref().ok_or_else(|| {
             Error::new(
@@ -676,6 +832,63 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_hybrid_handshake() -> Result<()> {
+        let vault = SoftwareVaultForSecureChannels::create().await?;
+
+        let initiator_static_key = vault.generate_static_x25519_secret_key().await?;
+        let mut initiator = Handshake::new(vault.clone(), initiator_static_key).await?;
+        initiator.set_key_exchange(KeyExchange::HybridX25519MlKem768);
+
+        let responder_static_key = vault.generate_static_x25519_secret_key().await?;
+        let mut responder = Handshake::new(vault.clone(), responder_static_key).await?;
+
+        initiator.initialize().await?;
+        let message1 = initiator.encode_message1(&[]).await?;
+        assert_eq!(
+            message1.len(),
+            X25519_PUBLIC_KEY_LENGTH + ML_KEM_768_PUBLIC_KEY_LENGTH
+        );
+
+        // the responder detects the key exchange from the first message
+        let key_exchange = Handshake::get_key_exchange_from_message1(&message1);
+        assert_eq!(key_exchange, KeyExchange::HybridX25519MlKem768);
+        responder.set_key_exchange(key_exchange);
+        responder.initialize().await?;
+        responder.decode_message1(&message1).await?;
+
+        let message2 = responder.encode_message2(b"message2").await?;
+        assert_eq!(initiator.decode_message2(&message2).await?, b"message2");
+
+        let message3 = initiator.encode_message3(b"message3").await?;
+        assert_eq!(responder.decode_message3(&message3).await?, b"message3");
+
+        // both sides share the same transcript
+        assert_eq!(initiator.state.h, responder.state.h);
+
+        initiator.set_final_state(Role::Initiator).await?;
+        responder.set_final_state(Role::Responder).await?;
+
+        // the ephemeral ML-KEM key is deleted at the end of the handshake
+        assert_eq!(vault.number_of_ephemeral_ml_kem_768_secrets(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_key_exchange_from_message1() -> Result<()> {
+        assert_eq!(
+            Handshake::get_key_exchange_from_message1(&[0; X25519_PUBLIC_KEY_LENGTH]),
+            KeyExchange::X25519
+        );
+        assert_eq!(
+            Handshake::get_key_exchange_from_message1(
+                &[0; X25519_PUBLIC_KEY_LENGTH + ML_KEM_768_PUBLIC_KEY_LENGTH]
+            ),
+            KeyExchange::HybridX25519MlKem768
+        );
+        Ok(())
+    }
+
     // --------------------
     // TESTS IMPLEMENTATION
     // --------------------
@@ -768,6 +981,7 @@ mod tests {
             Ok(Handshake {
                 vault,
                 protocol_name,
+                key_exchange: KeyExchange::X25519,
                 state: HandshakeState::new(static_key, ephemeral_key),
             })
         }
@@ -786,6 +1000,7 @@ mod tests {
             Ok(Handshake {
                 vault,
                 protocol_name,
+                key_exchange: KeyExchange::X25519,
                 state: HandshakeState::new(static_key, ephemeral_key),
             })
         }
@@ -0,0 +1,86 @@
+use crate::secure_channel::Role;
+use ockam_node::metrics::{MetricsRegistry, DURATION_BUCKETS};
+use std::time::Instant;
+
+/// Number of completed secure channel handshakes, per role
+pub const HANDSHAKES: &str = "ockam_secure_channel_handshakes_total";
+
+/// Number of failed secure channel handshakes, per role
+pub const HANDSHAKE_FAILURES: &str = "ockam_secure_channel_handshake_failures_total";
+
+/// Number of secure channel handshakes which did not complete in time, for initiators
+pub const HANDSHAKE_TIMEOUTS: &str = "ockam_secure_channel_handshake_timeouts_total";
+
+/// Duration of the completed secure channel handshakes in seconds, per role
+pub const HANDSHAKE_DURATION: &str = "ockam_secure_channel_handshake_duration_seconds";
+
+/// Record the outcome of the handshake performed by a `HandshakeWorker`
+pub(crate) struct HandshakeMetrics {
+    role: Role,
+    started_at: Instant,
+    recorded: bool,
+}
+
+impl HandshakeMetrics {
+    /// Start measuring a handshake now
+    pub(crate) fn start(role: Role) -> Self {
+        Self {
+            role,
+            started_at: Instant::now(),
+            recorded: false,
+        }
+    }
+
+    /// Record a completed handshake
+    pub(crate) fn succeeded(&mut self) {
+        if self.recorded {
+            return;
+        }
+        self.recorded = true;
+
+        let registry = MetricsRegistry::global();
+        let labels = [("role", self.role.str())];
+        registry
+            .counter(
+                HANDSHAKES,
+                "Number of completed secure channel handshakes",
+                &labels,
+            )
+            .inc();
+        registry
+            .histogram(
+                HANDSHAKE_DURATION,
+                "Duration of the completed secure channel handshakes in seconds",
+                DURATION_BUCKETS,
+                &labels,
+            )
+            .observe(self.started_at.elapsed().as_secs_f64());
+    }
+
+    /// Record a failed handshake, a handshake only fails once
+    pub(crate) fn failed(&mut self) {
+        if self.recorded {
+            return;
+        }
+        self.recorded = true;
+
+        MetricsRegistry::global()
+            .counter(
+                HANDSHAKE_FAILURES,
+                "Number of failed secure channel handshakes",
+                &[("role", self.role.str())],
+            )
+            .inc();
+    }
+}
+
+/// Record a handshake which was not completed before the timeout given to the initiator
+pub(crate) fn record_handshake_timeout() {
+    MetricsRegistry::global()
+        .counter(
+            HANDSHAKE_TIMEOUTS,
+            "Number of secure channel handshakes which did not complete in time",
+            &[("role", Role::Initiator.str())],
+        )
+        .inc();
+}
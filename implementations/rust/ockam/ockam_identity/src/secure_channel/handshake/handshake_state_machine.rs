@@ -12,7 +12,8 @@ use crate::models::{
     ChangeHistory, CredentialAndPurposeKey, PurposeKeyAttestation, PurposePublicKey,
 };
 use crate::{
-    CredentialRetriever, Identifier, Identities, IdentityError, SecureChannelTrustInfo, TrustPolicy,
+    CredentialRetriever, Identifier, Identities, IdentityError, RekeyPolicy,
+    SecureChannelTrustInfo, TrustPolicy,
 };
 
 /// Interface for a state machine in a key exchange protocol
@@ -61,6 +62,8 @@ pub(super) struct HandshakeResults {
     pub(super) handshake_keys: HandshakeKeys,
     pub(super) their_identifier: Identifier,
     pub(super) presented_credential: Option<CredentialAndPurposeKey>,
+    pub(super) encryptor_rekey_policy: RekeyPolicy,
+    pub(super) decryptor_rekey_policy: RekeyPolicy,
 }
 
 /// This struct implements functions common to both initiator and the responder state machines
@@ -72,7 +75,9 @@ pub(crate) struct CommonStateMachine {
     pub(super) trust_policy: Arc<dyn TrustPolicy>,
     pub(super) authority: Option<Identifier>, // TODO: Replace with ABAC
     pub(super) presented_credential: Option<CredentialAndPurposeKey>,
+    pub(super) rekey_policy: RekeyPolicy,
     their_identifier: Option<Identifier>,
+    their_rekey_policy: Option<RekeyPolicy>,
 }
 
 impl CommonStateMachine {
@@ -83,6 +88,7 @@ impl CommonStateMachine {
         credential_retriever: Option<Arc<dyn CredentialRetriever>>,
         trust_policy: Arc<dyn TrustPolicy>,
         authority: Option<Identifier>,
+        rekey_policy: RekeyPolicy,
     ) -> Self {
         Self {
             identities,
@@ -92,7 +98,9 @@ impl CommonStateMachine {
             trust_policy,
             authority,
             presented_credential: None,
+            rekey_policy,
             their_identifier: None,
+            their_rekey_policy: None,
         }
     }
 
@@ -102,6 +110,7 @@ impl CommonStateMachine {
     ///  - the current Identity Change History
     ///  - the current Secure Channel Purpose Key Attestation
     ///  - the Identity Credentials and corresponding Credentials Purpose Key Attestations
+    ///  - the rekey policy which the other party must honor
     ///
     pub(super) async fn make_identity_payload(&mut self) -> Result<Vec<u8>> {
         // prepare the payload that will be sent either in message 2 or message 3
@@ -118,6 +127,7 @@ impl CommonStateMachine {
             change_history,
             purpose_key_attestation: self.purpose_key_attestation.clone(),
             credentials,
+            rekey_policy: Some(self.rekey_policy),
         };
         Ok(minicbor::to_vec(payload)?)
     }
@@ -142,6 +152,7 @@ impl CommonStateMachine {
         .await?;
 
         self.their_identifier = Some(identifier);
+        self.their_rekey_policy = peer.rekey_policy;
 
         Ok(())
     }
@@ -153,11 +164,24 @@ impl CommonStateMachine {
         &self,
         handshake_keys: Option<HandshakeKeys>,
     ) -> Option<HandshakeResults> {
+        // Both parties apply the strictest combination of their rekey policies.
+        // A party which did not send a rekey policy only renews its keys at the end
+        // of each nonce interval, so we can't expect more from its messages
+        let (encryptor_rekey_policy, decryptor_rekey_policy) = match &self.their_rekey_policy {
+            Some(their_rekey_policy) => {
+                let rekey_policy = self.rekey_policy.combine(their_rekey_policy);
+                (rekey_policy, rekey_policy)
+            }
+            None => (self.rekey_policy, RekeyPolicy::default()),
+        };
+
         match (self.their_identifier.clone(), handshake_keys) {
             (Some(their_identifier), Some(handshake_keys)) => Some(HandshakeResults {
                 their_identifier,
                 handshake_keys,
                 presented_credential: self.presented_credential.clone(),
+                encryptor_rekey_policy,
+                decryptor_rekey_policy,
             }),
             _ => None,
         }
@@ -203,8 +227,10 @@ impl CommonStateMachine {
             }
         }
 
-        Self::check_trust_policy(trust_policy, &their_identifier).await?;
+        // The credentials are verified first so that the trust policy can make a decision
+        // based on the attributes attested by the authority
         Self::verify_credentials(identities, authority, &their_identifier, credentials).await?;
+        Self::check_trust_policy(trust_policy, &their_identifier).await?;
 
         Ok(their_identifier)
     }
@@ -287,4 +313,7 @@ pub(super) struct IdentityAndCredentials {
     /// Credentials associated to the identity along with corresponding Credentials Purpose Keys
     /// to verify those Credentials
     #[n(2)] pub(super) credentials: Vec<CredentialAndPurposeKey>,
+    /// Rekey policy to apply on the secure channel. This field is optional
+    /// for compatibility with parties which don't send it
+    #[n(3)] pub(super) rekey_policy: Option<RekeyPolicy>,
 }
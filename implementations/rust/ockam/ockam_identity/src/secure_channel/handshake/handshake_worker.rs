@@ -18,6 +18,10 @@ use crate::models::Identifier;
 use crate::secure_channel::decryptor::DecryptorHandler;
 use crate::secure_channel::encryptor::Encryptor;
 use crate::secure_channel::encryptor_worker::{EncryptorWorker, SecureChannelSharedState};
+#[cfg(feature = "std")]
+use crate::secure_channel::handshake::handshake_metrics::{
+    record_handshake_timeout, HandshakeMetrics,
+};
 use crate::secure_channel::handshake::handshake_state_machine::Action::SendMessage;
 use crate::secure_channel::handshake::handshake_state_machine::Event::{
     Initialize, ReceivedMessage,
@@ -27,10 +31,11 @@ use crate::secure_channel::handshake::handshake_state_machine::{
 };
 use crate::secure_channel::handshake::initiator_state_machine::InitiatorStateMachine;
 use crate::secure_channel::handshake::responder_state_machine::ResponderStateMachine;
-use crate::secure_channel::{Addresses, Role};
+use crate::secure_channel::{Addresses, RekeyMetricsRecorder, Role};
 use crate::{
-    ChangeHistoryRepository, CredentialRetriever, IdentityError, SecureChannelPurposeKey,
-    SecureChannelRegistryEntry, SecureChannels, TrustPolicy, IDENTITY_SECURE_CHANNEL_IDENTIFIER,
+    ChangeHistoryRepository, CredentialRetriever, IdentityError, KeyExchange, RekeyPolicy,
+    SecureChannelPurposeKey, SecureChannelRegistryEntry, SecureChannels, TrustPolicy,
+    IDENTITY_SECURE_CHANNEL_IDENTIFIER,
 };
 
 /// This struct implements a Worker receiving and sending messages
@@ -51,6 +56,9 @@ pub(crate) struct HandshakeWorker {
     credential_retriever: Option<Arc<dyn CredentialRetriever>>,
 
     shared_state: SecureChannelSharedState,
+
+    #[cfg(feature = "std")]
+    metrics: HandshakeMetrics,
 }
 
 #[ockam_core::worker]
@@ -58,30 +66,14 @@ impl Worker for HandshakeWorker {
     type Message = Any;
     type Context = Context;
 
-    /// Initialize the state machine with an `Initialize` event
-    /// Depending on the state machine role there might be a message to send to the other party
+    /// Start the handshake, see `start_handshake`
     async fn initialize(&mut self, context: &mut Self::Context) -> Result<()> {
-        if let Some(credential_retriever) = &self.credential_retriever {
-            credential_retriever.initialize().await?;
-        }
-
-        match self.state_machine.on_event(Initialize).await? {
-            SendMessage(message) => {
-                debug!(
-                    "remote route {:?}, decryptor remote {:?}",
-                    self.remote_route.clone(),
-                    self.addresses.decryptor_remote.clone()
-                );
-                context
-                    .send_from_address(
-                        self.remote_route()?,
-                        message,
-                        self.addresses.decryptor_remote.clone(),
-                    )
-                    .await
-            }
-            Action::NoAction => Ok(()),
+        let result = self.start_handshake(context).await;
+        #[cfg(feature = "std")]
+        if result.is_err() {
+            self.metrics.failed();
         }
+        result
     }
 
     /// Handle a message coming from the other party
@@ -101,7 +93,12 @@ impl Worker for HandshakeWorker {
         if self.decryptor_handler.is_some() {
             self.handle_decrypt(context, message).await
         } else {
-            self.handle_handshake(context, message).await
+            let result = self.handle_handshake(context, message).await;
+            #[cfg(feature = "std")]
+            if result.is_err() {
+                self.metrics.failed();
+            }
+            result
         }
     }
 
@@ -120,7 +117,34 @@ impl Worker for HandshakeWorker {
 }
 
 impl HandshakeWorker {
+    /// Initialize the state machine with an `Initialize` event
+    /// Depending on the state machine role there might be a message to send to the other party
+    async fn start_handshake(&mut self, context: &mut Context) -> Result<()> {
+        if let Some(credential_retriever) = &self.credential_retriever {
+            credential_retriever.initialize().await?;
+        }
+
+        match self.state_machine.on_event(Initialize).await? {
+            SendMessage(message) => {
+                debug!(
+                    "remote route {:?}, decryptor remote {:?}",
+                    self.remote_route.clone(),
+                    self.addresses.decryptor_remote.clone()
+                );
+                context
+                    .send_from_address(
+                        self.remote_route()?,
+                        message,
+                        self.addresses.decryptor_remote.clone(),
+                    )
+                    .await
+            }
+            Action::NoAction => Ok(()),
+        }
+    }
+
     /// Create a new HandshakeWorker with a role of either INITIATOR or RESPONDER
+    /// The key exchange is the one used by an initiator, or the one required by a responder
     #[allow(clippy::too_many_arguments)]
     pub(crate) async fn create(
         context: &Context,
@@ -135,6 +159,8 @@ impl HandshakeWorker {
         remote_route: Option<Route>,
         timeout: Option<Duration>,
         role: Role,
+        key_exchange: Option<KeyExchange>,
+        rekey_policy: RekeyPolicy,
     ) -> Result<()> {
         let vault = secure_channels.identities.vault().secure_channel_vault;
         let identities = secure_channels.identities();
@@ -149,6 +175,8 @@ impl HandshakeWorker {
                     credential_retriever.clone(),
                     trust_policy,
                     authority.clone(),
+                    rekey_policy,
+                    key_exchange.unwrap_or_default(),
                 )
                 .await?,
             )
@@ -162,6 +190,8 @@ impl HandshakeWorker {
                     credential_retriever.clone(),
                     trust_policy,
                     authority.clone(),
+                    rekey_policy,
+                    key_exchange,
                 )
                 .await?,
             )
@@ -190,6 +220,8 @@ impl HandshakeWorker {
             authority,
             change_history_repository: identities.change_history_repository(),
             shared_state,
+            #[cfg(feature = "std")]
+            metrics: HandshakeMetrics::start(role),
         };
 
         WorkerBuilder::new(worker)
@@ -214,6 +246,8 @@ impl HandshakeWorker {
                     let res = callback_waiter.receive_timeout(timeout).await;
 
                     if let Some(err) = res.err() {
+                        #[cfg(feature = "std")]
+                        record_handshake_timeout();
                         error!(
                             "Timeout {:?} reached when creating secure channel for: {}. Encryptor: {}",
                             timeout, identifier, addresses.encryptor
@@ -266,6 +300,8 @@ impl HandshakeWorker {
         if let Some(final_state) = self.state_machine.get_handshake_results() {
             // start the encryptor worker and return the decryptor
             self.decryptor_handler = Some(self.finalize(context, final_state).await?);
+            #[cfg(feature = "std")]
+            self.metrics.succeeded();
             if let Some(callback_sender) = self.callback_sender.take() {
                 callback_sender.send(())?;
             }
@@ -339,6 +375,9 @@ impl HandshakeWorker {
         context: &Context,
         handshake_results: HandshakeResults,
     ) -> Result<DecryptorHandler> {
+        // the rekey metrics are shared by the encryptor and the decryptor
+        let rekey_metrics = RekeyMetricsRecorder::default();
+
         // create a decryptor to delegate the processing of all messages after the handshake
         let decryptor = DecryptorHandler::new(
             self.secure_channels.identities.clone(),
@@ -349,7 +388,9 @@ impl HandshakeWorker {
             self.secure_channels.identities.vault().secure_channel_vault,
             handshake_results.their_identifier.clone(),
             self.shared_state.clone(),
-        );
+            handshake_results.decryptor_rekey_policy,
+            rekey_metrics.clone(),
+        )?;
 
         // create a separate encryptor worker which will be started independently
         {
@@ -361,7 +402,9 @@ impl HandshakeWorker {
                     handshake_results.handshake_keys.encryption_key,
                     0,
                     self.secure_channels.identities.vault().secure_channel_vault,
-                ),
+                    handshake_results.encryptor_rekey_policy,
+                    rekey_metrics.clone(),
+                )?,
                 self.identifier.clone(),
                 self.change_history_repository.clone(),
                 self.credential_retriever.clone(),
@@ -385,13 +428,18 @@ impl HandshakeWorker {
                 Arc::new(AllowAll),
                 Arc::new(DenyAll),
             );
+            let rekey_mailbox = Mailbox::new(
+                self.addresses.encryptor_rekey.clone(),
+                Arc::new(AllowAll),
+                Arc::new(DenyAll),
+            );
 
             let their_identifier = handshake_results.their_identifier.clone();
 
             WorkerBuilder::new(encryptor)
                 .with_mailboxes(Mailboxes::new(
                     main_mailbox,
-                    vec![api_mailbox, internal_mailbox],
+                    vec![api_mailbox, internal_mailbox, rekey_mailbox],
                 ))
                 .terminal_with_attributes(
                     self.addresses.encryptor.clone(),
@@ -427,7 +475,8 @@ impl HandshakeWorker {
             self.identifier.clone(),
             handshake_results.their_identifier,
             their_decryptor_address,
-        );
+        )
+        .with_rekey(handshake_results.encryptor_rekey_policy, rekey_metrics);
 
         self.secure_channels
             .secure_channel_registry()
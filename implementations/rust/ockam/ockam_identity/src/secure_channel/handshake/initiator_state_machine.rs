@@ -17,7 +17,10 @@ use crate::secure_channel::handshake::handshake_state_machine::{
     Action, CommonStateMachine, Event, HandshakeKeys, HandshakeResults, IdentityAndCredentials,
     StateMachine, Status,
 };
-use crate::{CredentialRetriever, Identities, Role, SecureChannelPurposeKey, TrustPolicy};
+use crate::{
+    CredentialRetriever, Identities, KeyExchange, RekeyPolicy, Role, SecureChannelPurposeKey,
+    TrustPolicy,
+};
 
 /// Implementation of a state machine for the key exchange on the initiator side
 #[async_trait]
@@ -97,6 +100,7 @@ impl InitiatorStateMachine {
 }
 
 impl InitiatorStateMachine {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         vault: Arc<dyn VaultForSecureChannels>,
         identities: Arc<Identities>,
@@ -105,6 +109,8 @@ impl InitiatorStateMachine {
         credential_retriever: Option<Arc<dyn CredentialRetriever>>,
         trust_policy: Arc<dyn TrustPolicy>,
         authority: Option<Identifier>,
+        rekey_policy: RekeyPolicy,
+        key_exchange: KeyExchange,
     ) -> Result<InitiatorStateMachine> {
         let common = CommonStateMachine::new(
             identities,
@@ -113,11 +119,12 @@ impl InitiatorStateMachine {
             credential_retriever,
             trust_policy,
             authority,
+            rekey_policy,
         );
 
-        Ok(InitiatorStateMachine {
-            common,
-            handshake: Handshake::new(vault, purpose_key.key().clone()).await?,
-        })
+        let mut handshake = Handshake::new(vault, purpose_key.key().clone()).await?;
+        handshake.set_key_exchange(key_exchange);
+
+        Ok(InitiatorStateMachine { common, handshake })
     }
 }
@@ -7,7 +7,14 @@ compile_error!("Key Exchange is only supported on little-endian machines");
 
 #[allow(clippy::module_inception)]
 mod handshake;
+#[cfg(feature = "std")]
+mod handshake_metrics;
 pub(crate) mod handshake_state_machine;
 pub(crate) mod handshake_worker;
 mod initiator_state_machine;
 mod responder_state_machine;
+
+#[cfg(feature = "std")]
+pub use handshake_metrics::{
+    HANDSHAKES, HANDSHAKE_DURATION, HANDSHAKE_FAILURES, HANDSHAKE_TIMEOUTS,
+};
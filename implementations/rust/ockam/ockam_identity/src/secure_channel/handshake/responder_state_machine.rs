@@ -17,7 +17,10 @@ use crate::secure_channel::handshake::handshake_state_machine::{
     Action, CommonStateMachine, Event, HandshakeKeys, HandshakeResults, IdentityAndCredentials,
     StateMachine, Status,
 };
-use crate::{CredentialRetriever, Identities, Role, SecureChannelPurposeKey, TrustPolicy};
+use crate::{
+    CredentialRetriever, Identities, KeyExchange, RekeyPolicy, Role, SecureChannelPurposeKey,
+    TrustPolicy,
+};
 
 /// Implementation of a state machine for the key exchange on the responder side
 #[async_trait]
@@ -25,14 +28,22 @@ impl StateMachine for ResponderStateMachine {
     async fn on_event(&mut self, event: Event) -> Result<Action> {
         let state = self.handshake.state.clone();
         match (state.status, event) {
-            // Initialize the handshake and wait for message 1
+            // Wait for message 1. The handshake is initialized once the key exchange
+            // chosen by the initiator is known
             (Initial, Initialize) => {
-                self.initialize_handshake().await?;
                 self.handshake.state.status = WaitingForMessage1;
                 Ok(NoAction)
             }
             // Process message 1 and send message 2
             (WaitingForMessage1, ReceivedMessage(message)) => {
+                let key_exchange = Handshake::get_key_exchange_from_message1(&message);
+                if let Some(required_key_exchange) = self.required_key_exchange {
+                    if key_exchange != required_key_exchange {
+                        return Err(XXError::KeyExchangeNotAllowed)?;
+                    }
+                }
+                self.handshake.set_key_exchange(key_exchange);
+                self.initialize_handshake().await?;
                 self.decode_message1(&message).await?;
                 let identity_payload = self
                     .common
@@ -77,6 +88,7 @@ impl StateMachine for ResponderStateMachine {
 pub struct ResponderStateMachine {
     common: CommonStateMachine,
     handshake: Handshake,
+    required_key_exchange: Option<KeyExchange>,
 }
 
 impl ResponderStateMachine {
@@ -100,6 +112,7 @@ impl ResponderStateMachine {
 }
 
 impl ResponderStateMachine {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         vault: Arc<dyn VaultForSecureChannels>,
         identities: Arc<Identities>,
@@ -108,6 +121,8 @@ impl ResponderStateMachine {
         credential_retriever: Option<Arc<dyn CredentialRetriever>>,
         trust_policy: Arc<dyn TrustPolicy>,
         authority: Option<Identifier>,
+        rekey_policy: RekeyPolicy,
+        required_key_exchange: Option<KeyExchange>,
     ) -> Result<ResponderStateMachine> {
         let common = CommonStateMachine::new(
             identities,
@@ -116,11 +131,13 @@ impl ResponderStateMachine {
             credential_retriever,
             trust_policy,
             authority,
+            rekey_policy,
         );
 
         Ok(ResponderStateMachine {
             common,
             handshake: Handshake::new(vault, purpose_key.key().clone()).await?,
+            required_key_exchange,
         })
     }
 }
@@ -11,6 +11,7 @@ pub(crate) struct KeyTracker {
     number_of_rekeys: u64,
     max_rekeys_reached: bool,
     renewal_interval: u64,
+    max_intervals_ahead: u64,
 }
 
 impl KeyTracker {
@@ -21,8 +22,17 @@ impl KeyTracker {
             max_rekeys_reached: false,
             previous_key: None,
             renewal_interval,
+            max_intervals_ahead: 1,
         }
     }
+
+    /// Accept nonces up to `max_intervals_ahead` intervals after the current one.
+    /// This is necessary when the other side can start a new interval before having used all
+    /// the nonces of the current one, in which case a few lost messages can skip several keys
+    pub(crate) fn with_max_intervals_ahead(mut self, max_intervals_ahead: u64) -> Self {
+        self.max_intervals_ahead = max_intervals_ahead.max(1);
+        self
+    }
 }
 
 impl KeyTracker {
@@ -34,9 +44,11 @@ impl KeyTracker {
     /// This is either:
     ///   - the current key if the nonce falls into the current interval
     ///   - the previous key if the nonce falls before the current interval
-    ///   - nothing if the the nonce falls after the current interval -> this indicates that a new key must be created
+    ///   - nothing if the the nonce falls after the current interval -> this indicates that new keys must be created,
+    ///     see [`KeyTracker::intervals_ahead`]
     ///   - an error if
     ///      - if the the nonce falls before the previous interval
+    ///      - if the nonce falls more than `max_intervals_ahead` intervals after the current interval
     ///      - if it the previous nonce but is not set
     ///      - we reached the maximum number of rekeyings
     #[instrument(skip_all)]
@@ -64,9 +76,9 @@ impl KeyTracker {
             if nonce_age < self.renewal_interval {
                 Ok(Some(self.current_key.clone()))
             }
-            // if the nonce falls in the next interval
-            // otherwise indicate that we need to create a new key
-            else if nonce_age < self.renewal_interval * 2 {
+            // if the nonce falls in one of the next intervals
+            // indicate that we need to create new keys
+            else if nonce_age / self.renewal_interval <= self.max_intervals_ahead {
                 Ok(None)
             }
             // otherwise the nonce is too far ahead
@@ -88,6 +100,13 @@ impl KeyTracker {
         }
     }
 
+    /// Return the number of intervals between the current interval and the interval of the nonce.
+    /// This is the number of keys which must be derived from the current key to decrypt a message
+    pub(crate) fn intervals_ahead(&self, nonce: u64) -> u64 {
+        let current_interval_start = self.number_of_rekeys * self.renewal_interval;
+        nonce.saturating_sub(current_interval_start) / self.renewal_interval
+    }
+
     // Update the key if a key renewal happened
     #[instrument(skip_all)]
     pub(crate) fn update_key(
@@ -158,6 +177,7 @@ mod tests {
             max_rekeys_reached: false,
             previous_key: Some(previous_handle.clone()),
             renewal_interval: 10,
+            max_intervals_ahead: 1,
         };
 
         assert_eq!(
@@ -212,6 +232,7 @@ mod tests {
             max_rekeys_reached: true,
             previous_key: Some(previous_handle),
             renewal_interval: 10,
+            max_intervals_ahead: 1,
         };
 
         assert_eq!(
@@ -221,6 +242,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_key_several_intervals_ahead() {
+        let handle = b"handle".to_vec();
+        let handle = AeadSecretKeyHandle(Aes256GcmSecretKeyHandle(HandleToSecret::new(handle)));
+        let key_tracker = KeyTracker::new(handle.clone(), 10).with_max_intervals_ahead(3);
+
+        assert_eq!(key_tracker.get_key(9).unwrap(), Some(handle));
+        assert_eq!(key_tracker.get_key(10).unwrap(), None);
+        assert_eq!(key_tracker.intervals_ahead(10), 1);
+        assert_eq!(key_tracker.get_key(39).unwrap(), None);
+        assert_eq!(key_tracker.intervals_ahead(39), 3);
+        assert_eq!(
+            key_tracker.get_key(40).ok(),
+            None,
+            "this nonce is too far in the future"
+        );
+    }
+
     #[test]
     fn test_update_key() {
         let handle = b"handle".to_vec();
@@ -238,6 +277,7 @@ mod tests {
             max_rekeys_reached: false,
             previous_key: Some(previous_handle.clone()),
             renewal_interval: 10,
+            max_intervals_ahead: 1,
         };
 
         assert_eq!(key_tracker.update_key(handle.clone()).unwrap(), None);
@@ -271,6 +311,7 @@ mod tests {
             max_rekeys_reached: false,
             previous_key: Some(previous_handle),
             renewal_interval: 10,
+            max_intervals_ahead: 1,
         };
 
         // this brings us to the last interval
@@ -99,6 +99,8 @@ impl Worker for SecureChannelListenerWorker {
             None,
             None,
             Role::Responder,
+            self.options.required_key_exchange,
+            self.options.rekey_policy,
         )
         .await?;
 
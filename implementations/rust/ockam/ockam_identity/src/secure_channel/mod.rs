@@ -13,6 +13,8 @@ mod message;
 mod nonce_tracker;
 mod options;
 mod registry;
+mod rekey_metrics;
+mod rekey_policy;
 mod role;
 
 /// List of trust policies to setup ABAC controls
@@ -22,17 +24,25 @@ pub use access_control::*;
 pub(crate) use addresses::*;
 pub use api::*;
 pub(crate) use handshake::*;
+#[cfg(feature = "std")]
+pub use handshake::{HANDSHAKES, HANDSHAKE_DURATION, HANDSHAKE_FAILURES, HANDSHAKE_TIMEOUTS};
 pub(crate) use listener::*;
 pub use local_info::*;
 pub use message::*;
 pub use options::*;
 pub use registry::*;
+pub(crate) use rekey_metrics::RekeyMetricsRecorder;
+pub use rekey_metrics::SecureChannelRekeyMetrics;
+pub use rekey_policy::*;
 pub(crate) use role::*;
 pub use trust_policy::*;
 
 #[cfg(test)]
 mod tests {
+    use crate::secure_channel::encryptor::MAX_INTERVALS_AHEAD;
     use crate::secure_channel::{decryptor::Decryptor, encryptor::Encryptor};
+    use crate::secure_channel::{RekeyMetricsRecorder, RekeyPolicy};
+    use core::time::Duration;
     use ockam_core::compat::rand::RngCore;
     use ockam_core::Result;
     use ockam_vault::{SoftwareVaultForSecureChannels, VaultForSecureChannels};
@@ -128,7 +138,177 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_rekey_every_messages() {
+        let rekey_policy = RekeyPolicy::new().every_messages(5);
+        let (mut encryptor, mut decryptor) = create_encryptor_decryptor_with_policy(rekey_policy)
+            .await
+            .unwrap();
+
+        for n in 0..20 {
+            let msg = vec![n];
+            let mut ciphertext = Vec::new();
+            encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+            assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+        }
+        let metrics = encryptor.rekey_metrics();
+        assert_eq!(metrics.encryption_rekeys, 3);
+        assert_eq!(metrics.max_messages_per_key, 5);
+
+        let metrics = decryptor.rekey_metrics();
+        assert_eq!(metrics.decryption_rekeys, 3);
+        assert_eq!(metrics.max_messages_per_key, 5);
+    }
+
+    #[tokio::test]
+    async fn test_rekey_every_bytes() {
+        let rekey_policy = RekeyPolicy::new().every_bytes(100);
+        let (mut encryptor, mut decryptor) = create_encryptor_decryptor_with_policy(rekey_policy)
+            .await
+            .unwrap();
+
+        for _ in 0..10 {
+            let msg = vec![0; 40];
+            let mut ciphertext = Vec::new();
+            encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+            assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+        }
+        let metrics = encryptor.rekey_metrics();
+        assert_eq!(metrics.encryption_rekeys, 4);
+        assert_eq!(metrics.max_bytes_per_key, 80);
+        assert_eq!(decryptor.rekey_metrics().decryption_rekeys, 4);
+    }
+
+    #[tokio::test]
+    async fn test_rekey_every_duration() {
+        let rekey_policy = RekeyPolicy::new().every_duration(Duration::from_secs(1));
+        let (mut encryptor, mut decryptor) = create_encryptor_decryptor_with_policy(rekey_policy)
+            .await
+            .unwrap();
+
+        for n in 0..2 {
+            let msg = vec![n];
+            let mut ciphertext = Vec::new();
+            encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+            assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+            tokio::time::sleep(Duration::from_millis(1100)).await;
+        }
+        let msg = vec![2];
+        let mut ciphertext = Vec::new();
+        encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+        assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+
+        assert_eq!(encryptor.rekey_metrics().encryption_rekeys, 2);
+        assert_eq!(decryptor.rekey_metrics().decryption_rekeys, 2);
+    }
+
+    #[tokio::test]
+    async fn test_rekey_every_message_with_messages_lost() {
+        let rekey_policy = RekeyPolicy::new().every_messages(1);
+        let (mut encryptor, mut decryptor) = create_encryptor_decryptor_with_policy(rekey_policy)
+            .await
+            .unwrap();
+
+        for n in 0..100 {
+            let msg = vec![n];
+            let mut ciphertext = Vec::new();
+            encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+            // each message uses a new key and several consecutive messages are lost
+            if n % 10 == 0 {
+                assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+            }
+        }
+        assert_eq!(encryptor.rekey_metrics().encryption_rekeys, 99);
+        assert_eq!(decryptor.rekey_metrics().decryption_rekeys, 9);
+    }
+
+    #[tokio::test]
+    async fn test_reject_nonces_too_far_ahead_with_an_aggressive_rekey_policy() {
+        let rekey_policy = RekeyPolicy::new().every_messages(1);
+        let (mut encryptor, mut decryptor) = create_encryptor_decryptor_with_policy(rekey_policy)
+            .await
+            .unwrap();
+
+        for n in 0..=MAX_INTERVALS_AHEAD {
+            let mut ciphertext = Vec::new();
+            encryptor
+                .encrypt(&mut ciphertext, &[n as u8])
+                .await
+                .unwrap();
+        }
+        let msg = vec![1];
+        let mut ciphertext = Vec::new();
+        encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+        assert!(decryptor.decrypt(&ciphertext).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_renew_expired_key() {
+        let rekey_policy = RekeyPolicy::new().every_duration(Duration::from_secs(1));
+        let (mut encryptor, mut decryptor) = create_encryptor_decryptor_with_policy(rekey_policy)
+            .await
+            .unwrap();
+
+        // an unused key is not renewed
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert!(!encryptor.renew_expired_key().await.unwrap());
+
+        let msg = vec![0];
+        let mut ciphertext = Vec::new();
+        encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+        assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+
+        // the key is renewed while the channel is idle
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert!(encryptor.renew_expired_key().await.unwrap());
+        assert!(!encryptor.renew_expired_key().await.unwrap());
+        assert_eq!(encryptor.rekey_metrics().encryption_rekeys, 1);
+
+        // and the next message is decrypted with the new key
+        let msg = vec![1];
+        let mut ciphertext = Vec::new();
+        encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+        assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+        assert_eq!(encryptor.rekey_metrics().encryption_rekeys, 1);
+        assert_eq!(decryptor.rekey_metrics().decryption_rekeys, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reject_messages_exceeding_the_rekey_policy() {
+        // the encryptor does not honor the rekey policy of the decryptor
+        let (mut encryptor, mut decryptor) = create_encryptor_decryptor_with_policies(
+            RekeyPolicy::default(),
+            RekeyPolicy::new().every_messages(5),
+        )
+        .await
+        .unwrap();
+
+        for n in 0..5 {
+            let msg = vec![n];
+            let mut ciphertext = Vec::new();
+            encryptor.encrypt(&mut ciphertext, &msg).await.unwrap();
+            assert_eq!(msg, decryptor.decrypt(&ciphertext).await.unwrap());
+        }
+
+        let mut ciphertext = Vec::new();
+        encryptor.encrypt(&mut ciphertext, &[5]).await.unwrap();
+        assert!(decryptor.decrypt(&ciphertext).await.is_err());
+    }
+
     async fn create_encryptor_decryptor() -> Result<(Encryptor, Decryptor)> {
+        create_encryptor_decryptor_with_policy(RekeyPolicy::default()).await
+    }
+
+    async fn create_encryptor_decryptor_with_policy(
+        rekey_policy: RekeyPolicy,
+    ) -> Result<(Encryptor, Decryptor)> {
+        create_encryptor_decryptor_with_policies(rekey_policy, rekey_policy).await
+    }
+
+    async fn create_encryptor_decryptor_with_policies(
+        encryptor_rekey_policy: RekeyPolicy,
+        decryptor_rekey_policy: RekeyPolicy,
+    ) -> Result<(Encryptor, Decryptor)> {
         let vault1 = SoftwareVaultForSecureChannels::create().await?;
         let vault2 = SoftwareVaultForSecureChannels::create().await?;
 
@@ -143,8 +323,19 @@ mod tests {
         let key_on_v2 = vault2.convert_secret_buffer_to_aead_key(key_on_v2).await?;
 
         Ok((
-            Encryptor::new(key_on_v1, 0, vault1),
-            Decryptor::new(key_on_v2, vault2),
+            Encryptor::new(
+                key_on_v1,
+                0,
+                vault1,
+                encryptor_rekey_policy,
+                RekeyMetricsRecorder::default(),
+            )?,
+            Decryptor::new(
+                key_on_v2,
+                vault2,
+                decryptor_rekey_policy,
+                RekeyMetricsRecorder::default(),
+            )?,
         ))
     }
 }
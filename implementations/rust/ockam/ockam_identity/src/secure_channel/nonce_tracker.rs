@@ -13,6 +13,7 @@ type BitmapType = u64;
 pub(crate) struct NonceTracker {
     nonce_bitmap: BitmapType,
     current_nonce: u64,
+    max_shift: u64,
 }
 
 impl NonceTracker {
@@ -20,21 +21,36 @@ impl NonceTracker {
         Self {
             nonce_bitmap: 0,
             current_nonce: 0,
+            max_shift: KEY_RENEWAL_INTERVAL,
         }
     }
 
+    /// Accept nonces up to `max_shift` values after the last received nonce.
+    /// The window of accepted out of order nonces is still [`KEY_RENEWAL_INTERVAL`]
+    pub(crate) fn with_max_shift(mut self, max_shift: u64) -> Self {
+        self.max_shift = max_shift.max(KEY_RENEWAL_INTERVAL);
+        self
+    }
+
     /// Mark a nonce as received, reject all invalid nonce values
     #[instrument(skip_all)]
     pub(crate) fn mark(&self, nonce: u64) -> ockam_core::Result<NonceTracker> {
         let new_tracker = if nonce > self.current_nonce {
             // normal case, we increase the nonce and move the window
             let relative_shift: u64 = nonce - self.current_nonce;
-            if relative_shift > KEY_RENEWAL_INTERVAL {
+            if relative_shift > self.max_shift {
                 return Err(IdentityError::InvalidNonce)?;
             }
+            // all the previously received nonces are out of the window after a large shift
+            let nonce_bitmap = if relative_shift >= BitmapType::BITS as u64 {
+                1
+            } else {
+                self.nonce_bitmap << relative_shift | 1
+            };
             NonceTracker {
-                nonce_bitmap: self.nonce_bitmap.overflowing_shl(relative_shift as u32).0 | 1,
+                nonce_bitmap,
                 current_nonce: nonce,
+                max_shift: self.max_shift,
             }
         } else {
             // first message or an out of order message
@@ -52,6 +68,7 @@ impl NonceTracker {
             NonceTracker {
                 nonce_bitmap: self.nonce_bitmap | bit,
                 current_nonce: self.current_nonce,
+                max_shift: self.max_shift,
             }
         };
 
@@ -83,3 +100,16 @@ pub fn check_nonce_tracker() {
         tracker = tracker.mark(n).unwrap();
     }
 }
+
+#[test]
+pub fn check_nonce_tracker_with_large_shifts() {
+    let mut tracker = NonceTracker::new().with_max_shift(4 * KEY_RENEWAL_INTERVAL);
+    tracker = tracker.mark(1).unwrap();
+    tracker.mark(4 * KEY_RENEWAL_INTERVAL + 2).unwrap_err();
+    tracker = tracker.mark(4 * KEY_RENEWAL_INTERVAL + 1).unwrap();
+    tracker.mark(4 * KEY_RENEWAL_INTERVAL + 1).unwrap_err();
+    tracker.mark(1).unwrap_err();
+    tracker = tracker.mark(4 * KEY_RENEWAL_INTERVAL).unwrap();
+    tracker = tracker.mark(5 * KEY_RENEWAL_INTERVAL).unwrap();
+    tracker.mark(5 * KEY_RENEWAL_INTERVAL).unwrap_err();
+}
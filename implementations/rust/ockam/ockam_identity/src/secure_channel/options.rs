@@ -7,7 +7,7 @@ use crate::models::CredentialAndPurposeKey;
 use crate::secure_channel::Addresses;
 use crate::{
     CredentialRetrieverCreator, Identifier, IdentityError, MemoryCredentialRetrieverCreator,
-    TrustEveryonePolicy, TrustPolicy,
+    RekeyPolicy, TrustEveryonePolicy, TrustPolicy,
 };
 
 use core::fmt;
@@ -17,6 +17,18 @@ use core::time::Duration;
 /// This is the default timeout for creating a secure channel
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Key exchange performed during the secure channel handshake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyExchange {
+    /// Noise XX with X25519 Diffie-Hellman keys
+    #[default]
+    X25519,
+    /// Noise XX with X25519 Diffie-Hellman keys, combined with an ephemeral ML-KEM-768
+    /// key encapsulation. The channel keys stay secret as long as either X25519 or ML-KEM is not broken
+    /// which protects recorded traffic against a future quantum computer
+    HybridX25519MlKem768,
+}
+
 /// Trust options for a Secure Channel
 pub struct SecureChannelOptions {
     pub(crate) flow_control_id: FlowControlId,
@@ -26,6 +38,8 @@ pub struct SecureChannelOptions {
     // To obtain our credentials
     pub(crate) credential_retriever_creator: Option<Arc<dyn CredentialRetrieverCreator>>,
     pub(crate) timeout: Duration,
+    pub(crate) key_exchange: KeyExchange,
+    pub(crate) rekey_policy: RekeyPolicy,
 }
 
 impl fmt::Debug for SecureChannelOptions {
@@ -48,9 +62,25 @@ impl SecureChannelOptions {
             authority: None,
             credential_retriever_creator: None,
             timeout: DEFAULT_TIMEOUT,
+            key_exchange: KeyExchange::default(),
+            rekey_policy: RekeyPolicy::default(),
         }
     }
 
+    /// Set the policy deciding when the channel keys are renewed.
+    /// The strictest combination of this policy and the policy of the listener is used
+    pub fn with_rekey_policy(mut self, rekey_policy: RekeyPolicy) -> Self {
+        self.rekey_policy = rekey_policy;
+        self
+    }
+
+    /// Set the key exchange used to establish the channel.
+    /// The listener accepts both key exchanges unless it requires a specific one
+    pub fn with_key_exchange(mut self, key_exchange: KeyExchange) -> Self {
+        self.key_exchange = key_exchange;
+        self
+    }
+
     /// Sets a timeout different from the default one [`DEFAULT_TIMEOUT`]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
@@ -144,6 +174,9 @@ pub struct SecureChannelListenerOptions {
     pub(crate) authority: Option<Identifier>,
     // To obtain our credentials
     pub(crate) credential_retriever_creator: Option<Arc<dyn CredentialRetrieverCreator>>,
+    // Key exchange that initiators must use, any if not set
+    pub(crate) required_key_exchange: Option<KeyExchange>,
+    pub(crate) rekey_policy: RekeyPolicy,
 }
 
 impl fmt::Debug for SecureChannelListenerOptions {
@@ -164,9 +197,24 @@ impl SecureChannelListenerOptions {
             trust_policy: Arc::new(TrustEveryonePolicy),
             authority: None,
             credential_retriever_creator: None,
+            required_key_exchange: None,
+            rekey_policy: RekeyPolicy::default(),
         }
     }
 
+    /// Set the policy deciding when the keys of the spawned channels are renewed.
+    /// The strictest combination of this policy and the policy of the initiator is used
+    pub fn with_rekey_policy(mut self, rekey_policy: RekeyPolicy) -> Self {
+        self.rekey_policy = rekey_policy;
+        self
+    }
+
+    /// Only accept secure channels established with the given key exchange
+    pub fn with_required_key_exchange(mut self, key_exchange: KeyExchange) -> Self {
+        self.required_key_exchange = Some(key_exchange);
+        self
+    }
+
     /// Mark that this Secure Channel Listener is a Consumer for to the given [`FlowControlId`]
     /// Also, in this case spawned Secure Channels will be marked as Consumers with [`FlowControlId`]
     /// of the message that was used to create the Secure Channel
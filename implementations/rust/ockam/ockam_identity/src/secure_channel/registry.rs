@@ -4,7 +4,8 @@ use ockam_core::compat::vec::Vec;
 use ockam_core::{Address, Result};
 
 use crate::models::Identifier;
-use crate::IdentityError;
+use crate::secure_channel::RekeyMetricsRecorder;
+use crate::{IdentityError, RekeyPolicy, SecureChannelRekeyMetrics};
 
 /// Known information about particular SecureChannel
 #[derive(Clone, Debug)]
@@ -17,6 +18,8 @@ pub struct SecureChannelRegistryEntry {
     my_id: Identifier,
     their_id: Identifier,
     their_decryptor_address: Address,
+    rekey_policy: RekeyPolicy,
+    rekey_metrics: RekeyMetricsRecorder,
 }
 
 impl SecureChannelRegistryEntry {
@@ -41,9 +44,22 @@ impl SecureChannelRegistryEntry {
             my_id,
             their_id,
             their_decryptor_address,
+            rekey_policy: RekeyPolicy::default(),
+            rekey_metrics: RekeyMetricsRecorder::default(),
         }
     }
 
+    /// Set the rekey policy of the channel and the recorder of its rekey metrics
+    pub(crate) fn with_rekey(
+        mut self,
+        rekey_policy: RekeyPolicy,
+        rekey_metrics: RekeyMetricsRecorder,
+    ) -> Self {
+        self.rekey_policy = rekey_policy;
+        self.rekey_metrics = rekey_metrics;
+        self
+    }
+
     /// Encryptor messaging address
     pub fn encryptor_messaging_address(&self) -> &Address {
         &self.encryptor_messaging_address
@@ -83,6 +99,16 @@ impl SecureChannelRegistryEntry {
     pub fn their_decryptor_address(&self) -> Address {
         self.their_decryptor_address.clone()
     }
+
+    /// Rekey policy negotiated for this channel
+    pub fn rekey_policy(&self) -> RekeyPolicy {
+        self.rekey_policy
+    }
+
+    /// Current metrics about the renewal of the channel keys
+    pub fn rekey_metrics(&self) -> SecureChannelRekeyMetrics {
+        self.rekey_metrics.get()
+    }
 }
 
 /// Registry of all known Secure Channels
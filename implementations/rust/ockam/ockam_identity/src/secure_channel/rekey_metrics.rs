@@ -0,0 +1,102 @@
+use core::cmp::max;
+use ockam_core::compat::sync::{Arc, RwLock};
+
+use crate::models::TimestampInSeconds;
+
+/// Metrics about the renewal of the keys of a secure channel.
+///
+/// They can be used to check that the lifetime of the keys used on a channel is bounded
+/// by its [`RekeyPolicy`](crate::RekeyPolicy)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecureChannelRekeyMetrics {
+    /// Number of times the encryption key has been renewed
+    pub encryption_rekeys: u64,
+    /// Number of times the decryption key has been renewed
+    pub decryption_rekeys: u64,
+    /// Creation time of the current encryption key
+    pub encryption_key_created_at: Option<TimestampInSeconds>,
+    /// Creation time of the current decryption key
+    pub decryption_key_created_at: Option<TimestampInSeconds>,
+    /// Maximum number of messages encrypted or decrypted with a single key
+    pub max_messages_per_key: u64,
+    /// Maximum number of bytes encrypted or decrypted with a single key
+    pub max_bytes_per_key: u64,
+    /// Maximum number of seconds during which a key has been used
+    pub max_key_lifetime_seconds: u64,
+}
+
+/// Usage of a key since its creation
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct KeyUsage {
+    pub(crate) messages: u64,
+    pub(crate) bytes: u64,
+    pub(crate) created_at: u64,
+}
+
+impl KeyUsage {
+    /// Start tracking the usage of a key created now
+    pub(crate) fn new(now: u64) -> Self {
+        Self {
+            messages: 0,
+            bytes: 0,
+            created_at: now,
+        }
+    }
+
+    /// Record a message encrypted or decrypted with the key
+    pub(crate) fn add_message(&mut self, bytes: usize) {
+        self.messages += 1;
+        self.bytes = self.bytes.saturating_add(bytes as u64);
+    }
+
+    /// Number of seconds since the key has been created
+    pub(crate) fn lifetime(&self, now: u64) -> u64 {
+        now.saturating_sub(self.created_at)
+    }
+}
+
+/// Shared recorder of the rekey metrics of a secure channel, updated by its encryptor and decryptor
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RekeyMetricsRecorder {
+    metrics: Arc<RwLock<SecureChannelRekeyMetrics>>,
+}
+
+impl RekeyMetricsRecorder {
+    /// Return the current metrics
+    pub(crate) fn get(&self) -> SecureChannelRekeyMetrics {
+        self.metrics.read().unwrap().clone()
+    }
+
+    /// Record the creation of the first encryption key
+    pub(crate) fn start_encryption(&self, now: u64) {
+        self.metrics.write().unwrap().encryption_key_created_at = Some(TimestampInSeconds(now));
+    }
+
+    /// Record the creation of the first decryption key
+    pub(crate) fn start_decryption(&self, now: u64) {
+        self.metrics.write().unwrap().decryption_key_created_at = Some(TimestampInSeconds(now));
+    }
+
+    /// Record the renewal of the encryption key, given the usage of the previous key
+    pub(crate) fn record_encryption_rekey(&self, usage: &KeyUsage, now: u64) {
+        let mut metrics = self.metrics.write().unwrap();
+        metrics.encryption_rekeys += 1;
+        metrics.encryption_key_created_at = Some(TimestampInSeconds(now));
+        Self::record_usage(&mut metrics, usage, now);
+    }
+
+    /// Record the renewal of the decryption key, given the usage of the previous key
+    pub(crate) fn record_decryption_rekey(&self, usage: &KeyUsage, now: u64) {
+        let mut metrics = self.metrics.write().unwrap();
+        metrics.decryption_rekeys += 1;
+        metrics.decryption_key_created_at = Some(TimestampInSeconds(now));
+        Self::record_usage(&mut metrics, usage, now);
+    }
+
+    fn record_usage(metrics: &mut SecureChannelRekeyMetrics, usage: &KeyUsage, now: u64) {
+        metrics.max_messages_per_key = max(metrics.max_messages_per_key, usage.messages);
+        metrics.max_bytes_per_key = max(metrics.max_bytes_per_key, usage.bytes);
+        metrics.max_key_lifetime_seconds =
+            max(metrics.max_key_lifetime_seconds, usage.lifetime(now));
+    }
+}
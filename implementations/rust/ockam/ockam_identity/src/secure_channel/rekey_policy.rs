@@ -0,0 +1,140 @@
+use core::cmp::min;
+use core::time::Duration;
+use minicbor::{Decode, Encode};
+
+use crate::secure_channel::encryptor::{KEY_RENEWAL_INTERVAL, MAX_INTERVALS_AHEAD};
+
+/// Policy specifying when the keys of a secure channel must be renewed.
+///
+/// A key is renewed as soon as one of the limits is reached:
+///
+///  - a number of messages encrypted with the same key. This number can not exceed
+///    the maximum number of messages per key supported by the protocol
+///  - a number of bytes encrypted with the same key
+///  - a duration since the key has been created
+///
+/// During the handshake each side sends its own policy and both sides apply the strictest
+/// combination of the two policies. The limits on messages and bytes are also checked by the
+/// decryptor, so that messages sent by a party which does not honor the policy are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+pub struct RekeyPolicy {
+    #[n(0)] messages: u64,
+    #[n(1)] bytes: Option<u64>,
+    #[n(2)] seconds: Option<u64>,
+}
+
+impl Default for RekeyPolicy {
+    fn default() -> Self {
+        Self {
+            messages: KEY_RENEWAL_INTERVAL,
+            bytes: None,
+            seconds: None,
+        }
+    }
+}
+
+impl RekeyPolicy {
+    /// Create the default policy, renewing a key every [`RekeyPolicy::max_messages`] messages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of messages which can be encrypted with the same key
+    pub fn max_messages() -> u64 {
+        KEY_RENEWAL_INTERVAL
+    }
+
+    /// Renew the key every `messages` messages.
+    /// The value is bounded between 1 and [`RekeyPolicy::max_messages`]
+    pub fn every_messages(mut self, messages: u64) -> Self {
+        self.messages = messages.clamp(1, KEY_RENEWAL_INTERVAL);
+        self
+    }
+
+    /// Renew the key once `bytes` bytes have been encrypted with it
+    pub fn every_bytes(mut self, bytes: u64) -> Self {
+        self.bytes = Some(bytes.max(1));
+        self
+    }
+
+    /// Renew the key once it has been used for the given duration.
+    /// The duration is counted in seconds and is at least 1 second
+    pub fn every_duration(mut self, duration: Duration) -> Self {
+        self.seconds = Some(duration.as_secs().max(1));
+        self
+    }
+
+    /// Number of messages after which a key is renewed
+    pub fn messages(&self) -> u64 {
+        self.messages
+    }
+
+    /// Number of bytes after which a key is renewed, if any
+    pub fn bytes(&self) -> Option<u64> {
+        self.bytes
+    }
+
+    /// Duration after which a key is renewed, if any
+    pub fn duration(&self) -> Option<Duration> {
+        self.seconds.map(Duration::from_secs)
+    }
+
+    /// Number of intervals of nonces a decryptor must accept after its current interval.
+    ///
+    /// With the default policy a key is renewed once all the nonces of its interval are used,
+    /// so a received nonce is at most one interval ahead. Otherwise the encryptor can start
+    /// a new interval for each message and a few lost messages are enough to skip several keys
+    pub(crate) fn max_intervals_ahead(&self) -> u64 {
+        if *self == Self::default() {
+            1
+        } else {
+            MAX_INTERVALS_AHEAD
+        }
+    }
+
+    /// Return a policy respecting the limits of both this policy and the other one
+    pub(crate) fn combine(&self, other: &RekeyPolicy) -> RekeyPolicy {
+        Self {
+            messages: min(self.messages, other.messages).clamp(1, KEY_RENEWAL_INTERVAL),
+            bytes: min_option(self.bytes, other.bytes),
+            seconds: min_option(self.seconds, other.seconds),
+        }
+    }
+}
+
+fn min_option(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(min(a, b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_policies() {
+        let policy1 = RekeyPolicy::new().every_messages(10).every_bytes(1000);
+        let policy2 = RekeyPolicy::new()
+            .every_messages(20)
+            .every_duration(Duration::from_secs(60));
+
+        let combined = policy1.combine(&policy2);
+        assert_eq!(combined.messages(), 10);
+        assert_eq!(combined.bytes(), Some(1000));
+        assert_eq!(combined.duration(), Some(Duration::from_secs(60)));
+        assert_eq!(combined, policy2.combine(&policy1));
+    }
+
+    #[test]
+    fn test_messages_are_bounded() {
+        assert_eq!(
+            RekeyPolicy::new().every_messages(1000).messages(),
+            KEY_RENEWAL_INTERVAL
+        );
+        assert_eq!(RekeyPolicy::new().every_messages(0).messages(), 1);
+    }
+}
@@ -31,6 +31,9 @@ impl SecureChannelTrustInfo {
 
 /// TrustPolicy check is run when creating new SecureChannel, its creation only succeeds if this
 /// check succeeds
+///
+/// The check is run after the credentials presented by the other participant have been verified,
+/// so the attributes of those credentials are available to the policy
 #[async_trait]
 pub trait TrustPolicy: Send + Sync + 'static {
     /// Check SecureChannel
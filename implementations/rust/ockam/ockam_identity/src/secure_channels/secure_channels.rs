@@ -135,6 +135,8 @@ impl SecureChannels {
             Some(route),
             Some(options.timeout),
             Role::Initiator,
+            Some(options.key_exchange),
+            options.rekey_policy,
         )
         .await?;
 
@@ -8,8 +8,8 @@ use ockam_identity::secure_channels::secure_channels;
 use ockam_identity::utils::AttributesBuilder;
 use ockam_identity::{
     DecryptionResponse, EncryptionRequest, EncryptionResponse, IdentityAccessControlBuilder,
-    IdentitySecureChannelLocalInfo, SecureChannelListenerOptions, SecureChannelOptions,
-    SecureChannels, TrustEveryonePolicy, TrustIdentifierPolicy, Vault,
+    IdentitySecureChannelLocalInfo, KeyExchange, RekeyPolicy, SecureChannelListenerOptions,
+    SecureChannelOptions, SecureChannels, TrustEveryonePolicy, TrustIdentifierPolicy, Vault,
     IDENTITY_SECURE_CHANNEL_IDENTIFIER,
 };
 use ockam_node::{Context, MessageReceiveOptions, WorkerBuilder};
@@ -81,6 +81,213 @@ async fn test_channel(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[ockam_macros::test]
+async fn test_channel_hybrid_key_exchange(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels().await?;
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    let bob_options = SecureChannelListenerOptions::new()
+        .with_trust_policy(TrustIdentifierPolicy::new(alice.clone()))
+        .with_required_key_exchange(KeyExchange::HybridX25519MlKem768);
+    let bob_listener = secure_channels
+        .create_secure_channel_listener(ctx, &bob, "bob_listener", bob_options)
+        .await?;
+
+    // a classical key exchange is rejected by the listener
+    let result = secure_channels
+        .create_secure_channel(
+            ctx,
+            &alice,
+            route!["bob_listener"],
+            SecureChannelOptions::new().with_timeout(Duration::from_millis(500)),
+        )
+        .await;
+    assert!(result.is_err());
+
+    let alice_options = SecureChannelOptions::new()
+        .with_trust_policy(TrustIdentifierPolicy::new(bob.clone()))
+        .with_key_exchange(KeyExchange::HybridX25519MlKem768);
+    let alice_channel = secure_channels
+        .create_secure_channel(ctx, &alice, route!["bob_listener"], alice_options)
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+
+    ctx.flow_controls()
+        .add_consumer("child", bob_listener.flow_control_id());
+
+    child_ctx
+        .send(
+            route![alice_channel, child_ctx.address()],
+            "Hello, Bob!".to_string(),
+        )
+        .await?;
+
+    let msg = child_ctx.receive::<String>().await?;
+    let local_info = IdentitySecureChannelLocalInfo::find_info(msg.local_message())?;
+    assert_eq!(local_info.their_identity_id(), alice);
+    assert_eq!("Hello, Bob!", msg.into_body()?);
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn test_channel_rekey_policy(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels().await?;
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    // the listener policy is stricter than the initiator policy for messages
+    // and the initiator policy adds a limit on bytes
+    let bob_options =
+        SecureChannelListenerOptions::new().with_rekey_policy(RekeyPolicy::new().every_messages(2));
+    let bob_listener = secure_channels
+        .create_secure_channel_listener(ctx, &bob, "bob_listener", bob_options)
+        .await?;
+
+    let alice_options = SecureChannelOptions::new()
+        .with_rekey_policy(RekeyPolicy::new().every_messages(10).every_bytes(10_000));
+    let alice_channel = secure_channels
+        .create_secure_channel(ctx, &alice, route!["bob_listener"], alice_options)
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+
+    ctx.flow_controls()
+        .add_consumer("child", bob_listener.flow_control_id());
+
+    for n in 0..5 {
+        child_ctx
+            .send(
+                route![alice_channel.clone(), child_ctx.address()],
+                format!("Hello, Bob! {n}"),
+            )
+            .await?;
+        let msg = child_ctx.receive::<String>().await?;
+        assert_eq!(format!("Hello, Bob! {n}"), msg.into_body()?);
+    }
+
+    let alice_entry = secure_channels
+        .secure_channel_registry()
+        .get_channel_by_encryptor_address(alice_channel.encryptor_address())
+        .unwrap();
+    assert_eq!(
+        alice_entry.rekey_policy(),
+        RekeyPolicy::new().every_messages(2).every_bytes(10_000)
+    );
+    let alice_metrics = alice_entry.rekey_metrics();
+    assert_eq!(alice_metrics.encryption_rekeys, 2);
+    assert_eq!(alice_metrics.max_messages_per_key, 2);
+
+    let bob_entry = secure_channels
+        .secure_channel_registry()
+        .get_channel_by_decryptor_address(&alice_entry.their_decryptor_address())
+        .unwrap();
+    assert_eq!(bob_entry.rekey_policy(), alice_entry.rekey_policy());
+    let bob_metrics = bob_entry.rekey_metrics();
+    assert_eq!(bob_metrics.decryption_rekeys, 2);
+    assert_eq!(bob_metrics.max_messages_per_key, 2);
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn test_channel_renews_keys_when_idle(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels().await?;
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    let rekey_policy = RekeyPolicy::new().every_duration(Duration::from_secs(1));
+    let bob_listener = secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &bob,
+            "bob_listener",
+            SecureChannelListenerOptions::new().with_rekey_policy(rekey_policy),
+        )
+        .await?;
+    let alice_channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            &alice,
+            route!["bob_listener"],
+            SecureChannelOptions::new().with_rekey_policy(rekey_policy),
+        )
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+    ctx.flow_controls()
+        .add_consumer("child", bob_listener.flow_control_id());
+
+    let alice_entry = secure_channels
+        .secure_channel_registry()
+        .get_channel_by_encryptor_address(alice_channel.encryptor_address())
+        .unwrap();
+
+    child_ctx
+        .send(
+            route![alice_channel.clone(), child_ctx.address()],
+            "Hello, Bob!".to_string(),
+        )
+        .await?;
+    let msg = child_ctx.receive::<String>().await?;
+    assert_eq!("Hello, Bob!", msg.into_body()?);
+
+    // the used key is renewed by a timer while the channel is idle
+    let mut encryption_rekeys = 0;
+    for _ in 0..50 {
+        encryption_rekeys = alice_entry.rekey_metrics().encryption_rekeys;
+        if encryption_rekeys > 0 {
+            break;
+        }
+        ctx.sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(encryption_rekeys, 1);
+
+    // and the other side follows the renewal when the next message arrives
+    child_ctx
+        .send(
+            route![alice_channel.clone(), child_ctx.address()],
+            "Hello again, Bob!".to_string(),
+        )
+        .await?;
+    let msg = child_ctx.receive::<String>().await?;
+    assert_eq!("Hello again, Bob!", msg.into_body()?);
+
+    let bob_entry = secure_channels
+        .secure_channel_registry()
+        .get_channel_by_decryptor_address(&alice_entry.their_decryptor_address())
+        .unwrap();
+    assert_eq!(bob_entry.rekey_metrics().decryption_rekeys, 1);
+
+    Ok(())
+}
+
 #[ockam_macros::test]
 async fn test_channel_send_credentials(context: &mut Context) -> Result<()> {
     let secure_channels = secure_channels().await?;
@@ -109,7 +109,7 @@ impl VaultForVerifyingSignatures for CrazyVerifyingVault {
     ) -> Result<bool> {
         match &signature {
             Signature::EdDSACurve25519(value) => {
-                if value.0.as_ref().iter().all(|&x| x == 0) {
+                if value.0.as_slice().iter().all(|&x| x == 0) {
                     return Ok(true);
                 }
             }
@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use ockam_core::Result;
+use ockam_identity::models::{CredentialSchemaIdentifier, RevokedSubject};
+use ockam_identity::secure_channels::secure_channels;
+use ockam_identity::utils::{now, AttributesBuilder};
+use ockam_identity::{
+    CachedCredentialRetrieverCreator, CredentialRetrieverCreator, Identifier, Identities,
+};
+
+#[tokio::test]
+async fn test_verify_revocation_list() -> Result<()> {
+    let secure_channels = secure_channels().await?;
+    let identities = secure_channels.identities();
+    let identities_creation = identities.identities_creation();
+    let credentials = identities.credentials();
+
+    let authority = identities_creation.create_identity().await?;
+    let other_authority = identities_creation.create_identity().await?;
+    let revoked = identities_creation.create_identity().await?;
+
+    let revocation_list = credentials
+        .credentials_creation()
+        .issue_revocation_list(
+            &authority,
+            vec![RevokedSubject {
+                subject: revoked.clone(),
+                revoked_at: now()?,
+            }],
+        )
+        .await?;
+
+    let verification = credentials.credentials_verification();
+    let data = verification
+        .verify_revocation_list(&[authority.clone()], &revocation_list)
+        .await?;
+    assert_eq!(data.revoked_subjects.len(), 1);
+    assert_eq!(data.revoked_subjects[0].subject, revoked);
+
+    // a revocation list is only accepted from a known authority
+    assert!(verification
+        .verify_revocation_list(&[other_authority], &revocation_list)
+        .await
+        .is_err());
+
+    // a tampered revocation list is rejected
+    let mut tampered = revocation_list.clone();
+    let last = tampered.revocation_list.data.len() - 1;
+    tampered.revocation_list.data[last] ^= 1;
+    assert!(verification
+        .verify_revocation_list(&[authority], &tampered)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revoked_subject_credential_is_rejected() -> Result<()> {
+    let secure_channels = secure_channels().await?;
+    let identities = secure_channels.identities();
+    let identities_creation = identities.identities_creation();
+    let credentials = identities.credentials();
+
+    let authority = identities_creation.create_identity().await?;
+    let subject = identities_creation.create_identity().await?;
+    let credential = issue_credential(&identities, &authority, &subject).await?;
+
+    let verification = credentials.credentials_verification();
+    verification
+        .receive_presented_credential(&subject, &[authority.clone()], &credential)
+        .await?;
+
+    identities
+        .revocation_repository()
+        .add_revoked_subject(
+            &authority,
+            RevokedSubject {
+                subject: subject.clone(),
+                revoked_at: now()?,
+            },
+        )
+        .await?;
+
+    assert!(verification
+        .receive_presented_credential(&subject, &[authority], &credential)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cached_credential_of_revoked_subject_is_not_presented() -> Result<()> {
+    let secure_channels = secure_channels().await?;
+    let identities = secure_channels.identities();
+    let identities_creation = identities.identities_creation();
+
+    let authority = identities_creation.create_identity().await?;
+    let subject = identities_creation.create_identity().await?;
+    let credential = issue_credential(&identities, &authority, &subject).await?;
+
+    let cache = identities.cached_credentials_repository();
+    cache
+        .put(
+            &subject,
+            &authority,
+            "scope",
+            credential.get_expires_at()?,
+            credential.clone(),
+        )
+        .await?;
+
+    let retriever = CachedCredentialRetrieverCreator::new(
+        authority.clone(),
+        "scope".to_string(),
+        cache.clone(),
+        identities.revocation_repository(),
+    )
+    .create(&subject)
+    .await?;
+    assert_eq!(retriever.retrieve().await?, credential);
+
+    identities
+        .revocation_repository()
+        .add_revoked_subject(
+            &authority,
+            RevokedSubject {
+                subject: subject.clone(),
+                revoked_at: now()?,
+            },
+        )
+        .await?;
+
+    // the revoked credential is not presented anymore, and is removed from the cache
+    assert!(retriever.retrieve().await.is_err());
+    assert!(cache.get(&subject, &authority, "scope").await?.is_none());
+
+    Ok(())
+}
+
+/// HELPERS
+async fn issue_credential(
+    identities: &Identities,
+    authority: &Identifier,
+    subject: &Identifier,
+) -> Result<ockam_identity::models::CredentialAndPurposeKey> {
+    identities
+        .credentials()
+        .credentials_creation()
+        .issue_credential(
+            authority,
+            subject,
+            AttributesBuilder::with_schema(CredentialSchemaIdentifier(0))
+                .with_attribute("key", "value")
+                .build(),
+            Duration::from_secs(60 * 60),
+        )
+        .await
+}
@@ -1,6 +1,7 @@
 use super::{Buffer, Checked, Code, Protocol};
 use crate::Error;
 use alloc::borrow::Cow;
+use alloc::string::String;
 use core::fmt;
 use core::ops::Deref;
 use core::str::{self, FromStr};
@@ -153,6 +154,143 @@ impl Protocol<'_> for Tcp {
     }
 }
 
+macro_rules! gen_port_proto {
+    ($(#[$meta:meta])* $t:ident, $c:literal, $p:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $t(pub u16);
+
+        impl $t {
+            pub fn new(v: u16) -> Self {
+                $t(v)
+            }
+        }
+
+        impl Deref for $t {
+            type Target = u16;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl Protocol<'_> for $t {
+            const CODE: Code = Code::new($c);
+            const PREFIX: &'static str = $p;
+
+            fn read_str(input: Checked<&str>) -> Result<Self, Error> {
+                u16::from_str(&input).map($t).map_err(Error::message)
+            }
+
+            fn read_bytes(input: Checked<&[u8]>) -> Result<Self, Error> {
+                let mut b = [0; 2];
+                b.copy_from_slice(&input);
+                Ok($t(u16::from_be_bytes(b)))
+            }
+
+            fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+                write!(f, "/{}/{}", Self::PREFIX, self.0)?;
+                Ok(())
+            }
+
+            fn write_bytes(&self, buf: &mut dyn Buffer) {
+                let mut b = encode::u32_buffer();
+                let uvi = encode::u32(Self::CODE.into(), &mut b);
+                buf.extend_with(uvi);
+                buf.extend_with(&self.0.to_be_bytes())
+            }
+        }
+    };
+}
+
+gen_port_proto!(
+    /// A UDP port number.
+    Udp,
+    273,
+    "udp"
+);
+
+gen_port_proto!(
+    /// The port number of a WebSocket listener.
+    Ws,
+    477,
+    "ws"
+);
+
+/// The path of a Unix domain socket.
+///
+/// In the textual representation the `/` and `%` characters of the path are
+/// percent-encoded, so that the path can be followed by other protocols,
+/// e.g. `/unix/%2Ftmp%2Fockam.sock/service/api`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Unix<'a>(Cow<'a, str>);
+
+impl<'a> Unix<'a> {
+    pub fn new<S: Into<Cow<'a, str>>>(s: S) -> Self {
+        Self(s.into())
+    }
+}
+
+impl Deref for Unix<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> Protocol<'a> for Unix<'a> {
+    const CODE: Code = Code::new(400);
+    const PREFIX: &'static str = "unix";
+
+    fn read_str(input: Checked<&'a str>) -> Result<Self, Error> {
+        if !input.0.contains('%') {
+            return Ok(Self(Cow::Borrowed(input.0)));
+        }
+        let mut path = String::with_capacity(input.0.len());
+        let mut rest = input.0;
+        while let Some(i) = rest.find('%') {
+            path.push_str(&rest[..i]);
+            let c = match rest.get(i + 1..i + 3) {
+                Some("2F" | "2f") => '/',
+                Some("25") => '%',
+                _ => return Err(Error::message("invalid percent-encoding in unix path")),
+            };
+            path.push(c);
+            rest = &rest[i + 3..];
+        }
+        path.push_str(rest);
+        Ok(Self(Cow::Owned(path)))
+    }
+
+    fn read_bytes(input: Checked<&'a [u8]>) -> Result<Self, Error> {
+        let s = str::from_utf8(&input).map_err(Error::message)?;
+        Ok(Self(Cow::Borrowed(s)))
+    }
+
+    fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        write!(f, "/{}/", Self::PREFIX)?;
+        for c in self.0.chars() {
+            match c {
+                '/' => f.write_str("%2F")?,
+                '%' => f.write_str("%25")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_bytes(&self, buf: &mut dyn Buffer) {
+        let mut b = encode::u32_buffer();
+        let uvi = encode::u32(Self::CODE.into(), &mut b);
+        buf.extend_with(uvi);
+        let mut b = encode::usize_buffer();
+        let uvi = encode::usize(self.0.len(), &mut b);
+        buf.extend_with(uvi);
+        buf.extend_with(self.0.as_bytes())
+    }
+}
+
 macro_rules! gen_str_proto {
     ($t:ident, $c:literal, $p:literal) => {
         #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
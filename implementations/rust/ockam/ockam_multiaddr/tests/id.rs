@@ -1,5 +1,7 @@
 use core::fmt;
-use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp};
+use ockam_multiaddr::proto::{
+    DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp, Udp, Unix, Ws,
+};
 use ockam_multiaddr::{Code, Match, MultiAddr, Protocol};
 use quickcheck::{quickcheck, Arbitrary, Gen};
 use rand::distributions::{Alphanumeric, DistString};
@@ -100,6 +102,18 @@ quickcheck! {
                         addr.push_back(Tcp::new(0)).unwrap();
                         prot.push_back(Tcp::CODE);
                     }
+                    Udp::CODE => {
+                        addr.push_back(Udp::new(0)).unwrap();
+                        prot.push_back(Udp::CODE);
+                    }
+                    Ws::CODE => {
+                        addr.push_back(Ws::new(0)).unwrap();
+                        prot.push_back(Ws::CODE);
+                    }
+                    Unix::CODE => {
+                        addr.push_back(Unix::new("/tmp/ockam.sock")).unwrap();
+                        prot.push_back(Unix::CODE);
+                    }
                     DnsAddr::CODE => {
                         addr.push_back(DnsAddr::new("localhost")).unwrap();
                         prot.push_back(DnsAddr::CODE);
@@ -145,6 +159,9 @@ quickcheck! {
 
 const PROTOS: &[Code] = &[
     Tcp::CODE,
+    Udp::CODE,
+    Ws::CODE,
+    Unix::CODE,
     DnsAddr::CODE,
     Ip4::CODE,
     Ip6::CODE,
@@ -161,6 +178,9 @@ impl Arbitrary for Addr {
         for _ in 0..g.size() {
             match *g.choose(PROTOS).unwrap() {
                 Tcp::CODE => a.push_back(Tcp::new(u16::arbitrary(g))).unwrap(),
+                Udp::CODE => a.push_back(Udp::new(u16::arbitrary(g))).unwrap(),
+                Ws::CODE => a.push_back(Ws::new(u16::arbitrary(g))).unwrap(),
+                Unix::CODE => a.push_back(Unix::new(gen_path())).unwrap(),
                 DnsAddr::CODE => a.push_back(DnsAddr::new(gen_hostname())).unwrap(),
                 Ip4::CODE => a.push_back(Ip4::new(Ipv4Addr::arbitrary(g))).unwrap(),
                 Ip6::CODE => a.push_back(Ip6::new(Ipv6Addr::arbitrary(g))).unwrap(),
@@ -220,3 +240,18 @@ fn gen_string() -> String {
     s.retain(|c| c != '/');
     s
 }
+
+fn gen_path() -> String {
+    // a unix path with '/' and '%' characters which must be escaped in the textual representation
+    format!("/tmp/{}%/{}.sock", gen_string(), gen_string())
+}
+
+#[test]
+fn unix_path_is_percent_encoded() {
+    let ma = MultiAddr::from_str("/unix/%2Ftmp%2Fockam.sock/service/api").unwrap();
+    let mut it = ma.iter();
+    let proto = it.next().unwrap();
+    assert_eq!(&*proto.cast::<Unix>().unwrap(), "/tmp/ockam.sock");
+    assert_eq!(it.next().unwrap().code(), Service::CODE);
+    assert_eq!(ma.to_string(), "/unix/%2Ftmp%2Fockam.sock/service/api");
+}
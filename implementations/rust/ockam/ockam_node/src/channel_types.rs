@@ -1,3 +1,7 @@
+mod mailbox;
+
+pub use mailbox::*;
+
 /// Sender used to send payload messages
 pub type MessageSender<T> = crate::tokio::sync::mpsc::Sender<T>;
 /// Receiver used to receive payload messages
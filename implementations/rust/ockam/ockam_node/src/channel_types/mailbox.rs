@@ -0,0 +1,309 @@
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll, Waker};
+use futures::future::poll_fn;
+use ockam_core::compat::collections::VecDeque;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+
+/// Default number of messages a worker mailbox can hold
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 8;
+
+/// What happens to a message sent to a full worker mailbox
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// The sender waits until there is room in the mailbox
+    Block,
+    /// The oldest message of the mailbox is dropped to make room for the new one
+    DropOldest,
+    /// The new message is dropped
+    DropNewest,
+    /// The new message is dropped and the sender gets an error
+    Reject,
+}
+
+impl Default for OverflowPolicy {
+    fn default() -> Self {
+        Self::Block
+    }
+}
+
+/// Error returned when sending to a mailbox, containing the message
+/// which could not be delivered
+#[derive(Debug)]
+pub enum MailboxSendError<T> {
+    /// The receiving side of the mailbox was dropped
+    Closed(T),
+    /// The mailbox is full and its policy is [`OverflowPolicy::Reject`]
+    Full(T),
+}
+
+impl<T> fmt::Display for MailboxSendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed(_) => write!(f, "mailbox closed"),
+            Self::Full(_) => write!(f, "mailbox full"),
+        }
+    }
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    closed: bool,
+    receiver_waker: Option<Waker>,
+    /// Senders waiting for room in the mailbox
+    sender_wakers: Vec<Waker>,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    /// Number of messages in the mailbox, readable without locking the state
+    count: Arc<AtomicUsize>,
+    /// Number of messages dropped because the mailbox was full
+    dropped: AtomicUsize,
+}
+
+/// Create a worker mailbox holding up to `capacity` messages.
+///
+/// `count` is kept up to date with the number of messages in the mailbox
+pub fn mailbox_channel<T>(
+    capacity: usize,
+    policy: OverflowPolicy,
+    count: Arc<AtomicUsize>,
+) -> (MailboxSender<T>, MailboxReceiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            senders: 1,
+            closed: false,
+            receiver_waker: None,
+            sender_wakers: Vec::new(),
+        }),
+        // A mailbox which can't hold any message would block its senders forever
+        capacity: capacity.max(1),
+        policy,
+        count,
+        dropped: AtomicUsize::new(0),
+    });
+    (
+        MailboxSender {
+            shared: shared.clone(),
+        },
+        MailboxReceiver { shared },
+    )
+}
+
+/// Sending side of a worker mailbox
+pub struct MailboxSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> MailboxSender<T> {
+    /// Send a message, applying the [`OverflowPolicy`] of the mailbox if it is full
+    pub async fn send(&self, msg: T) -> Result<(), MailboxSendError<T>> {
+        let mut msg = Some(msg);
+        poll_fn(|cx| self.poll_send(cx, &mut msg)).await
+    }
+
+    fn poll_send(
+        &self,
+        cx: &mut Context<'_>,
+        msg: &mut Option<T>,
+    ) -> Poll<Result<(), MailboxSendError<T>>> {
+        let shared = &self.shared;
+        let mut state = shared.state.lock().unwrap();
+        let msg_value = match msg.take() {
+            Some(msg) => msg,
+            None => return Poll::Ready(Ok(())),
+        };
+
+        if state.closed {
+            return Poll::Ready(Err(MailboxSendError::Closed(msg_value)));
+        }
+
+        if state.queue.len() >= shared.capacity {
+            match shared.policy {
+                OverflowPolicy::Block => {
+                    *msg = Some(msg_value);
+                    state.sender_wakers.push(cx.waker().clone());
+                    return Poll::Pending;
+                }
+                OverflowPolicy::DropOldest => {
+                    state.queue.pop_front();
+                    shared.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                OverflowPolicy::DropNewest => {
+                    shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Poll::Ready(Ok(()));
+                }
+                OverflowPolicy::Reject => {
+                    shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Poll::Ready(Err(MailboxSendError::Full(msg_value)));
+                }
+            }
+        }
+
+        state.queue.push_back(msg_value);
+        shared.count.store(state.queue.len(), Ordering::Release);
+        if let Some(waker) = state.receiver_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Clone for MailboxSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for MailboxSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.senders -= 1;
+        if state.senders == 0 {
+            // Let the receiver know that no more messages will arrive
+            if let Some(waker) = state.receiver_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for MailboxSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MailboxSender")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .finish()
+    }
+}
+
+/// Receiving side of a worker mailbox
+pub struct MailboxReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> MailboxReceiver<T> {
+    /// Receive the next message, or `None` once all the senders are dropped
+    /// and the mailbox is empty
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Number of messages dropped or rejected because the mailbox was full
+    pub fn dropped_count(&self) -> usize {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let shared = &self.shared;
+        let mut state = shared.state.lock().unwrap();
+        match state.queue.pop_front() {
+            Some(msg) => {
+                shared.count.store(state.queue.len(), Ordering::Release);
+                // Blocked senders retry, the ones which were dropped in the meantime
+                // are simply ignored
+                for waker in state.sender_wakers.drain(..) {
+                    waker.wake();
+                }
+                Poll::Ready(Some(msg))
+            }
+            None if state.senders == 0 => Poll::Ready(None),
+            None => {
+                state.receiver_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for MailboxReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.closed = true;
+        state.queue.clear();
+        self.shared.count.store(0, Ordering::Release);
+        for waker in state.sender_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> fmt::Debug for MailboxReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MailboxReceiver")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(
+        capacity: usize,
+        policy: OverflowPolicy,
+    ) -> (MailboxSender<u8>, MailboxReceiver<u8>, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (sender, receiver) = mailbox_channel(capacity, policy, count.clone());
+        (sender, receiver, count)
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest() {
+        let (sender, mut receiver, count) = channel(2, OverflowPolicy::DropOldest);
+        for i in 0..3 {
+            sender.send(i).await.unwrap();
+        }
+        assert_eq!(count.load(Ordering::Acquire), 2);
+        assert_eq!(receiver.dropped_count(), 1);
+        assert_eq!(receiver.recv().await, Some(1));
+        assert_eq!(receiver.recv().await, Some(2));
+        assert_eq!(count.load(Ordering::Acquire), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest_and_reject() {
+        let (sender, mut receiver, _) = channel(1, OverflowPolicy::DropNewest);
+        sender.send(0).await.unwrap();
+        sender.send(1).await.unwrap();
+        assert_eq!(receiver.recv().await, Some(0));
+
+        let (sender, mut receiver, _) = channel(1, OverflowPolicy::Reject);
+        sender.send(0).await.unwrap();
+        assert!(matches!(
+            sender.send(1).await,
+            Err(MailboxSendError::Full(1))
+        ));
+        assert_eq!(receiver.recv().await, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_block() {
+        let (sender, mut receiver, _) = channel(1, OverflowPolicy::Block);
+        sender.send(0).await.unwrap();
+
+        let blocked = sender.clone();
+        let task = tokio::spawn(async move { blocked.send(1).await.is_ok() });
+        tokio::task::yield_now().await;
+        assert!(!task.is_finished());
+
+        assert_eq!(receiver.recv().await, Some(0));
+        assert!(task.await.unwrap());
+        assert_eq!(receiver.recv().await, Some(1));
+
+        drop(sender);
+        assert_eq!(receiver.recv().await, None);
+    }
+}
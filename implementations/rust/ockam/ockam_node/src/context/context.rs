@@ -1,7 +1,7 @@
-use crate::channel_types::{SmallReceiver, SmallSender};
+use crate::channel_types::{MailboxReceiver, SmallSender};
 use crate::tokio::runtime::Handle;
 use crate::{error::*, AsyncDropSender, NodeMessage};
-use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::{AtomicUsize, Ordering};
 use ockam_core::compat::collections::HashMap;
 use ockam_core::compat::sync::{Arc, RwLock};
 use ockam_core::compat::time::Duration;
@@ -27,7 +27,7 @@ pub struct Context {
     pub(super) mailboxes: Mailboxes,
     pub(super) sender: SmallSender<NodeMessage>,
     pub(super) rt: Handle,
-    pub(super) receiver: SmallReceiver<RelayMessage>,
+    pub(super) receiver: MailboxReceiver<RelayMessage>,
     pub(super) async_drop_sender: Option<AsyncDropSender>,
     pub(super) mailbox_count: Arc<AtomicUsize>,
     /// List of transports used to resolve external addresses to local workers in routes
@@ -63,8 +63,21 @@ impl Context {
         &self.rt
     }
 
+    /// Return the number of messages waiting in the mailbox of this worker
+    pub fn mailbox_count(&self) -> usize {
+        self.mailbox_count.load(Ordering::Acquire)
+    }
+
+    /// Return the number of messages which were dropped or rejected
+    /// because the mailbox of this worker was full, see [`OverflowPolicy`]
+    ///
+    /// [`OverflowPolicy`]: crate::channel_types::OverflowPolicy
+    pub fn mailbox_dropped_count(&self) -> usize {
+        self.receiver.dropped_count()
+    }
+
     /// Return mailbox_count clone
-    pub(crate) fn mailbox_count(&self) -> Arc<AtomicUsize> {
+    pub(crate) fn mailbox_counter(&self) -> Arc<AtomicUsize> {
         self.mailbox_count.clone()
     }
 
@@ -178,6 +191,16 @@ impl Context {
         Ok(())
     }
 
+    /// This function is called by Relay to restart the other members of the
+    /// worker clusters, when a worker supervised with the one-for-all strategy fails
+    pub(crate) async fn restart_cluster(&self) -> Result<()> {
+        self.sender
+            .send(NodeMessage::restart_cluster(self.address()))
+            .await
+            .map_err(NodeError::from_send_err)?;
+        Ok(())
+    }
+
     /// Wait for a particular address to become "ready"
     pub async fn wait_for<A: Into<Address>>(&self, addr: A) -> Result<()> {
         let (msg, mut reply) = NodeMessage::get_ready(addr.into());
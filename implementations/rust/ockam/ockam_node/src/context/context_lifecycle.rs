@@ -1,3 +1,4 @@
+use core::sync::atomic::AtomicUsize;
 use core::time::Duration;
 
 use ockam_core::compat::collections::HashMap;
@@ -14,7 +15,10 @@ use ockam_core::{
 use ockam_transport_core::Transport;
 
 use crate::async_drop::AsyncDrop;
-use crate::channel_types::{message_channel, small_channel, SmallReceiver, SmallSender};
+use crate::channel_types::{
+    mailbox_channel, small_channel, OverflowPolicy, SmallReceiver, SmallSender,
+    DEFAULT_MAILBOX_CAPACITY,
+};
 use crate::tokio::{self, runtime::Handle};
 use crate::{debugger, Context};
 use crate::{error::*, relay::CtrlSignal, router::SenderPair, NodeMessage};
@@ -67,9 +71,13 @@ impl Context {
         async_drop_sender: Option<AsyncDropSender>,
         transports: Arc<RwLock<HashMap<TransportType, Arc<dyn Transport>>>>,
         flow_controls: &FlowControls,
+        mailbox_capacity: usize,
+        overflow_policy: OverflowPolicy,
         #[cfg(feature = "std")] tracing_context: OpenTelemetryContext,
     ) -> (Self, SenderPair, SmallReceiver<CtrlSignal>) {
-        let (mailbox_tx, receiver) = message_channel();
+        let mailbox_count = Arc::new(AtomicUsize::new(0));
+        let (mailbox_tx, receiver) =
+            mailbox_channel(mailbox_capacity, overflow_policy, mailbox_count.clone());
         let (ctrl_tx, ctrl_rx) = small_channel();
         (
             Self {
@@ -79,7 +87,7 @@ impl Context {
                 mailboxes,
                 receiver,
                 async_drop_sender,
-                mailbox_count: Arc::new(0.into()),
+                mailbox_count,
                 transports,
                 flow_controls: flow_controls.clone(),
                 #[cfg(feature = "std")]
@@ -96,6 +104,19 @@ impl Context {
     pub(crate) fn copy_with_mailboxes(
         &self,
         mailboxes: Mailboxes,
+    ) -> (Context, SenderPair, SmallReceiver<CtrlSignal>) {
+        self.copy_with_mailboxes_and_capacity(
+            mailboxes,
+            DEFAULT_MAILBOX_CAPACITY,
+            OverflowPolicy::default(),
+        )
+    }
+
+    pub(crate) fn copy_with_mailboxes_and_capacity(
+        &self,
+        mailboxes: Mailboxes,
+        mailbox_capacity: usize,
+        overflow_policy: OverflowPolicy,
     ) -> (Context, SenderPair, SmallReceiver<CtrlSignal>) {
         Context::new(
             self.protocol_version(),
@@ -105,6 +126,8 @@ impl Context {
             None,
             self.transports.clone(),
             &self.flow_controls,
+            mailbox_capacity,
+            overflow_policy,
             #[cfg(feature = "std")]
             self.tracing_context(),
         )
@@ -123,6 +146,8 @@ impl Context {
             Some(drop_sender),
             self.transports.clone(),
             &self.flow_controls,
+            DEFAULT_MAILBOX_CAPACITY,
+            OverflowPolicy::default(),
             #[cfg(feature = "std")]
             OpenTelemetryContext::current(),
         )
@@ -237,13 +262,8 @@ impl Context {
         let (ctx, sender, _) = self.copy_with_mailboxes_detached(mailboxes, drop_sender);
 
         // Create a "detached relay" and register it with the router
-        let (msg, mut rx) = NodeMessage::start_worker(
-            addresses,
-            sender,
-            true,
-            Arc::clone(&self.mailbox_count),
-            vec![],
-        );
+        let (msg, mut rx) =
+            NodeMessage::start_worker(addresses, sender, true, ctx.mailbox_counter(), vec![]);
         self.sender
             .send(msg)
             .await
@@ -1,4 +1,3 @@
-use core::sync::atomic::Ordering;
 use core::time::Duration;
 
 use ockam_core::{Message, RelayMessage, Result, Routed};
@@ -62,10 +61,6 @@ impl Context {
         loop {
             let relay_msg = if let Some(msg) = self.receiver.recv().await.map(|msg| {
                 trace!("{}: received new message!", self.address());
-
-                // First we update the mailbox fill metrics
-                self.mailbox_count.fetch_sub(1, Ordering::Acquire);
-
                 msg
             }) {
                 msg
@@ -74,6 +69,9 @@ impl Context {
                 return Ok(None);
             };
 
+            #[cfg(feature = "std")]
+            crate::metrics::record_mailbox_depth(self.mailbox_count());
+
             debugger::log_incoming_message(self, &relay_msg);
 
             if !self.mailboxes.is_incoming_authorized(&relay_msg).await? {
@@ -82,6 +80,11 @@ impl Context {
                     relay_msg.return_route(),
                     relay_msg.destination()
                 );
+                #[cfg(feature = "std")]
+                crate::metrics::record_access_control_denial(
+                    relay_msg.destination(),
+                    crate::metrics::INCOMING,
+                );
                 continue;
             }
 
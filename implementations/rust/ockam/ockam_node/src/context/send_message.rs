@@ -248,11 +248,14 @@ impl Context {
             .send(req)
             .await
             .map_err(NodeError::from_send_err)?;
-        let (addr, sender) = reply_rx
+        let reply = reply_rx
             .recv()
             .await
-            .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??
-            .take_sender()?;
+            .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??;
+        #[cfg(feature = "std")]
+        let (addr, sender, messages_routed) = reply.take_sender_and_counter()?;
+        #[cfg(not(feature = "std"))]
+        let (addr, sender) = reply.take_sender()?;
 
         // Pack the payload into a TransportMessage
         let payload = msg.encode().map_err(|_| NodeError::Data.internal())?;
@@ -289,6 +292,11 @@ impl Context {
                 relay_msg.source(),
                 relay_msg.destination()
             );
+            #[cfg(feature = "std")]
+            crate::metrics::record_access_control_denial(
+                relay_msg.source(),
+                crate::metrics::OUTGOING,
+            );
             return Ok(());
         }
 
@@ -296,7 +304,10 @@ impl Context {
         sender
             .send(relay_msg)
             .await
-            .map_err(NodeError::from_send_err)?;
+            .map_err(NodeError::from_mailbox_send_err)?;
+
+        #[cfg(feature = "std")]
+        messages_routed.inc();
 
         Ok(())
     }
@@ -357,11 +368,14 @@ impl Context {
             .send(req)
             .await
             .map_err(NodeError::from_send_err)?;
-        let (addr, sender) = reply_rx
+        let reply = reply_rx
             .recv()
             .await
-            .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??
-            .take_sender()?;
+            .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??;
+        #[cfg(feature = "std")]
+        let (addr, sender, messages_routed) = reply.take_sender_and_counter()?;
+        #[cfg(not(feature = "std"))]
+        let (addr, sender) = reply.take_sender()?;
 
         // Pack the transport message into a RelayMessage wrapper
         let mut local_msg = local_msg;
@@ -377,6 +391,11 @@ impl Context {
                 relay_msg.source(),
                 relay_msg.destination(),
             );
+            #[cfg(feature = "std")]
+            crate::metrics::record_access_control_denial(
+                relay_msg.source(),
+                crate::metrics::OUTGOING,
+            );
             return Ok(());
         }
 
@@ -384,7 +403,10 @@ impl Context {
         sender
             .send(relay_msg)
             .await
-            .map_err(NodeError::from_send_err)?;
+            .map_err(NodeError::from_mailbox_send_err)?;
+
+        #[cfg(feature = "std")]
+        messages_routed.inc();
 
         Ok(())
     }
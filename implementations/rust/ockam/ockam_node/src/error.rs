@@ -1,3 +1,4 @@
+use crate::channel_types::MailboxSendError;
 use crate::tokio::{sync::mpsc::error::SendError, time::error::Elapsed};
 use core::fmt;
 use ockam_core::{
@@ -56,6 +57,23 @@ impl NodeError {
         .context("SendError", err)
     }
 
+    /// Create an ockam_core::Error based on a [`MailboxSendError`]
+    #[track_caller]
+    pub(crate) fn from_mailbox_send_err<T>(err: MailboxSendError<T>) -> Error {
+        match err {
+            MailboxSendError::Closed(_) => Error::new(
+                Origin::Node,
+                Kind::Internal,
+                NodeError::WorkerState(WorkerReason::Shutdown),
+            ),
+            MailboxSendError::Full(_) => Error::new(
+                Origin::Node,
+                Kind::ResourceExhausted,
+                NodeError::WorkerState(WorkerReason::MailboxFull),
+            ),
+        }
+    }
+
     /// Create an ockam_core::Error from a tokio::Elapsed
     #[track_caller]
     pub(crate) fn with_elapsed(self, err: Elapsed) -> Error {
@@ -148,6 +166,8 @@ pub enum WorkerReason {
     Faulty,
     /// The worker is otherwise corrupt and can not be recovered
     Corrupt,
+    /// The worker mailbox is full and rejects new messages
+    MailboxFull,
 }
 
 impl fmt::Display for WorkerReason {
@@ -159,6 +179,7 @@ impl fmt::Display for WorkerReason {
                 Self::Shutdown => "target worker is shutting down",
                 Self::Faulty => "target worker is faulty and waiting for supervisor",
                 Self::Corrupt => "target worker is corrupt and can not be recovered",
+                Self::MailboxFull => "target worker mailbox is full",
             }
         )
     }
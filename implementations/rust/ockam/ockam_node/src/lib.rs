@@ -37,8 +37,9 @@ pub mod compat;
 /// MPSC channel type aliases
 pub mod channel_types;
 
-#[cfg(feature = "metrics")]
-mod metrics;
+/// Counters, gauges and histograms describing the activity of a node
+#[cfg(feature = "std")]
+pub mod metrics;
 
 /// Api helpers
 pub mod api;
@@ -59,6 +60,12 @@ mod node;
 mod processor_builder;
 mod relay;
 mod router;
+#[cfg(feature = "std")]
+mod supervisor;
+
+/// Deterministic simulation of several nodes connected by a virtual network
+#[cfg(feature = "simulation")]
+pub mod simulation;
 
 /// Support for storing persistent values
 pub mod storage;
@@ -76,6 +83,8 @@ pub use messages::*;
 pub use processor_builder::ProcessorBuilder;
 #[cfg(feature = "std")]
 pub use storage::database;
+#[cfg(feature = "std")]
+pub use supervisor::{RestartPolicy, RestartStrategy};
 pub use worker_builder::WorkerBuilder;
 
 pub use node::{NodeBuilder, NullWorker};
@@ -1,4 +1,6 @@
-use crate::channel_types::{small_channel, MessageSender, SmallReceiver, SmallSender};
+use crate::channel_types::{small_channel, MailboxSender, SmallReceiver, SmallSender};
+#[cfg(feature = "std")]
+use crate::metrics::Counter;
 use crate::{
     error::{NodeError, NodeReason, RouterReason, WorkerReason},
     router::SenderPair,
@@ -64,6 +66,8 @@ pub enum NodeMessage {
     FindTerminalAddress(Vec<Address>, SmallSender<NodeReplyResult>),
     /// Get address metadata
     GetMetadata(Address, SmallSender<NodeReplyResult>),
+    /// Restart the other supervised members of the clusters of an address
+    RestartCluster(Address),
 }
 
 impl fmt::Display for NodeMessage {
@@ -84,6 +88,7 @@ impl fmt::Display for NodeMessage {
             NodeMessage::CheckReady(_, _) => write!(f, "CheckReady"),
             NodeMessage::FindTerminalAddress(_, _) => write!(f, "FindTerminalAddress"),
             NodeMessage::GetMetadata(_, _) => write!(f, "ReadMetadata"),
+            NodeMessage::RestartCluster(_) => write!(f, "RestartCluster"),
         }
     }
 }
@@ -177,6 +182,11 @@ impl NodeMessage {
         Self::SetReady(addr)
     }
 
+    /// Create a RestartCluster message
+    pub fn restart_cluster(addr: Address) -> Self {
+        Self::RestartCluster(addr)
+    }
+
     /// Create a GetReady message and reply receiver
     pub fn get_ready(addr: Address) -> (Self, SmallReceiver<NodeReplyResult>) {
         let (tx, rx) = small_channel();
@@ -211,7 +221,10 @@ pub enum RouterReply {
         /// The address a message is being sent to
         addr: Address,
         /// The relay sender
-        sender: MessageSender<RelayMessage>,
+        sender: MailboxSender<RelayMessage>,
+        /// Counter of the messages routed to the address
+        #[cfg(feature = "std")]
+        messages_routed: Counter,
     },
     /// Indicate the 'ready' state of an address
     State(bool),
@@ -321,14 +334,39 @@ impl RouterReply {
     }
 
     /// Return [RouterReply::Sender] for the given information
-    pub fn sender(addr: Address, sender: MessageSender<RelayMessage>) -> NodeReplyResult {
-        Ok(RouterReply::Sender { addr, sender })
+    pub fn sender(
+        addr: Address,
+        sender: MailboxSender<RelayMessage>,
+        #[cfg(feature = "std")] messages_routed: Counter,
+    ) -> NodeReplyResult {
+        Ok(RouterReply::Sender {
+            addr,
+            sender,
+            #[cfg(feature = "std")]
+            messages_routed,
+        })
     }
 
     /// Consume the wrapper and return [RouterReply::Sender]
-    pub fn take_sender(self) -> Result<(Address, MessageSender<RelayMessage>)> {
+    pub fn take_sender(self) -> Result<(Address, MailboxSender<RelayMessage>)> {
+        match self {
+            Self::Sender { addr, sender, .. } => Ok((addr, sender)),
+            _ => Err(NodeError::NodeState(NodeReason::Unknown).internal()),
+        }
+    }
+
+    /// Consume the wrapper and return [RouterReply::Sender] with the counter of the messages
+    /// routed to its address
+    #[cfg(feature = "std")]
+    pub fn take_sender_and_counter(
+        self,
+    ) -> Result<(Address, MailboxSender<RelayMessage>, Counter)> {
         match self {
-            Self::Sender { addr, sender } => Ok((addr, sender)),
+            Self::Sender {
+                addr,
+                sender,
+                messages_routed,
+            } => Ok((addr, sender, messages_routed)),
             _ => Err(NodeError::NodeState(NodeReason::Unknown).internal()),
         }
     }
@@ -0,0 +1,81 @@
+#[cfg(feature = "metrics")]
+mod collector;
+mod prometheus;
+mod registry;
+
+#[cfg(feature = "metrics")]
+pub(crate) use collector::Metrics;
+pub use prometheus::*;
+pub use registry::*;
+
+use ockam_core::Address;
+use once_cell::sync::Lazy;
+
+/// Number of messages routed to each address
+pub const MESSAGES_ROUTED: &str = "ockam_node_messages_routed_total";
+
+/// Number of messages rejected by an access control, per address and direction
+pub const ACCESS_CONTROL_DENIALS: &str = "ockam_node_access_control_denials_total";
+
+/// Direction of a message rejected by an incoming access control
+pub(crate) const INCOMING: &str = "incoming";
+
+/// Direction of a message rejected by an outgoing access control
+pub(crate) const OUTGOING: &str = "outgoing";
+
+/// Number of messages waiting in a worker mailbox when a new message is received
+pub const MAILBOX_DEPTH: &str = "ockam_node_mailbox_depth";
+
+/// Buckets of the [`MAILBOX_DEPTH`] histogram
+const MAILBOX_DEPTH_BUCKETS: &[f64] = &[0.0, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0];
+
+/// Return the counter of the messages delivered to the mailbox of `address`.
+///
+/// The router keeps this counter with the record of the address, so that routing a message
+/// does not go through the registry
+pub(crate) fn messages_routed(address: &Address) -> Counter {
+    MetricsRegistry::global().counter(
+        MESSAGES_ROUTED,
+        "Number of messages routed to an address",
+        &[("address", &address.to_string())],
+    )
+}
+
+/// Record a message rejected by the incoming or outgoing access control of `address`
+pub(crate) fn record_access_control_denial(address: &Address, direction: &str) {
+    MetricsRegistry::global()
+        .counter(
+            ACCESS_CONTROL_DENIALS,
+            "Number of messages rejected by an access control",
+            &[("address", &address.to_string()), ("direction", direction)],
+        )
+        .inc();
+}
+
+/// Histogram of the mailbox depths, registered once since it has no labels
+static MAILBOX_DEPTH_HISTOGRAM: Lazy<Histogram> = Lazy::new(|| {
+    MetricsRegistry::global().histogram(
+        MAILBOX_DEPTH,
+        "Number of messages waiting in a worker mailbox when a message is received",
+        MAILBOX_DEPTH_BUCKETS,
+        &[],
+    )
+});
+
+/// Record the number of messages left in a mailbox when a message is taken out of it
+pub(crate) fn record_mailbox_depth(depth: usize) {
+    MAILBOX_DEPTH_HISTOGRAM.observe(depth as f64);
+}
+
+/// Remove the series describing an address which is not used anymore
+pub(crate) fn remove_address_metrics(address: &Address) {
+    let registry = MetricsRegistry::global();
+    let address = address.to_string();
+    registry.remove(MESSAGES_ROUTED, &[("address", &address)]);
+    for direction in [INCOMING, OUTGOING] {
+        registry.remove(
+            ACCESS_CONTROL_DENIALS,
+            &[("address", &address), ("direction", direction)],
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use super::{Labels, MetricFamily, MetricValue};
+use core::fmt::Write;
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Encode metrics in the Prometheus text exposition format
+pub fn encode_prometheus(families: &[MetricFamily]) -> String {
+    let mut output = String::new();
+    for family in families {
+        // writing to a String can't fail
+        let _ = writeln!(
+            output,
+            "# HELP {} {}",
+            family.name,
+            escape_help(family.help)
+        );
+        let _ = writeln!(output, "# TYPE {} {}", family.name, family.kind);
+
+        for series in &family.series {
+            match &series.value {
+                MetricValue::Counter(value) => {
+                    write_sample(&mut output, family.name, "", &series.labels, None, value)
+                }
+                MetricValue::Gauge(value) => {
+                    write_sample(&mut output, family.name, "", &series.labels, None, value)
+                }
+                MetricValue::Histogram(histogram) => {
+                    for (bound, count) in &histogram.buckets {
+                        let le = format!("{}", bound);
+                        write_sample(
+                            &mut output,
+                            family.name,
+                            "_bucket",
+                            &series.labels,
+                            Some(&le),
+                            count,
+                        );
+                    }
+                    write_sample(
+                        &mut output,
+                        family.name,
+                        "_bucket",
+                        &series.labels,
+                        Some("+Inf"),
+                        &histogram.count,
+                    );
+                    write_sample(
+                        &mut output,
+                        family.name,
+                        "_sum",
+                        &series.labels,
+                        None,
+                        &histogram.sum,
+                    );
+                    write_sample(
+                        &mut output,
+                        family.name,
+                        "_count",
+                        &series.labels,
+                        None,
+                        &histogram.count,
+                    );
+                }
+            }
+        }
+    }
+    output
+}
+
+fn write_sample(
+    output: &mut String,
+    name: &str,
+    suffix: &str,
+    labels: &Labels,
+    le: Option<&str>,
+    value: &dyn core::fmt::Display,
+) {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+
+    if pairs.is_empty() {
+        let _ = writeln!(output, "{}{} {}", name, suffix, value);
+    } else {
+        let _ = writeln!(
+            output,
+            "{}{}{{{}}} {}",
+            name,
+            suffix,
+            pairs.join(","),
+            value
+        );
+    }
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    escape_help(value).replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metrics::MetricsRegistry;
+
+    #[test]
+    fn test_encode_prometheus() {
+        let registry = MetricsRegistry::default();
+        registry
+            .counter("requests_total", "Number of requests", &[("path", "a\"b")])
+            .inc_by(3);
+        let histogram = registry.histogram("latency", "Latency", &[0.5, 1.0], &[]);
+        histogram.observe(0.25);
+        histogram.observe(2.0);
+
+        assert_eq!(
+            registry.encode_prometheus(),
+            "# HELP latency Latency\n\
+             # TYPE latency histogram\n\
+             latency_bucket{le=\"0.5\"} 1\n\
+             latency_bucket{le=\"1\"} 1\n\
+             latency_bucket{le=\"+Inf\"} 2\n\
+             latency_sum 2.25\n\
+             latency_count 2\n\
+             # HELP requests_total Number of requests\n\
+             # TYPE requests_total counter\n\
+             requests_total{path=\"a\\\"b\"} 3\n"
+        );
+    }
+}
@@ -0,0 +1,427 @@
+use core::fmt;
+use core::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Registry shared by all the nodes of the process
+static GLOBAL_REGISTRY: Lazy<MetricsRegistry> = Lazy::new(MetricsRegistry::default);
+
+/// Buckets for histograms of durations, in seconds
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Labels identifying a series of a metric, as `(name, value)` pairs
+pub type Labels = Vec<(&'static str, String)>;
+
+/// Type of a metric
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricKind {
+    /// Monotonic counter
+    Counter,
+    /// Value which can go up and down
+    Gauge,
+    /// Distribution of observed values
+    Histogram,
+}
+
+impl fmt::Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricKind::Counter => write!(f, "counter"),
+            MetricKind::Gauge => write!(f, "gauge"),
+            MetricKind::Histogram => write!(f, "histogram"),
+        }
+    }
+}
+
+/// Monotonic counter, cheap to clone and to update
+#[derive(Clone, Debug, Default)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    /// Increment the counter by 1
+    pub fn inc(&self) {
+        self.inc_by(1)
+    }
+
+    /// Increment the counter by `value`
+    pub fn inc_by(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Current value of the counter
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Value which can go up and down, cheap to clone and to update
+#[derive(Clone, Debug, Default)]
+pub struct Gauge(Arc<AtomicI64>);
+
+impl Gauge {
+    /// Increment the gauge by 1
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decrement the gauge by 1
+    pub fn dec(&self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Set the value of the gauge
+    pub fn set(&self, value: i64) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    /// Current value of the gauge
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+struct HistogramState {
+    bounds: &'static [f64],
+    /// Number of observations per bucket, the last one is for the values above all the bounds
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    /// Bits of the `f64` sum of all the observations
+    sum: AtomicU64,
+}
+
+/// Distribution of observed values over fixed buckets, cheap to clone and to update
+#[derive(Clone, Debug)]
+pub struct Histogram(Arc<HistogramState>);
+
+impl Histogram {
+    /// Create a histogram with the given upper bounds, in increasing order
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self(Arc::new(HistogramState {
+            bounds,
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0f64.to_bits()),
+        }))
+    }
+
+    /// Record an observed value
+    pub fn observe(&self, value: f64) {
+        let state = &self.0;
+        let bucket = state
+            .bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(state.bounds.len());
+        state.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        state.count.fetch_add(1, Ordering::Relaxed);
+
+        let mut sum = state.sum.load(Ordering::Relaxed);
+        loop {
+            let new_sum = (f64::from_bits(sum) + value).to_bits();
+            match state.sum.compare_exchange_weak(
+                sum,
+                new_sum,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => sum = current,
+            }
+        }
+    }
+
+    /// Current state of the histogram
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        let state = &self.0;
+        let mut cumulative = 0;
+        let buckets = state
+            .bounds
+            .iter()
+            .zip(state.buckets.iter())
+            .map(|(bound, count)| {
+                cumulative += count.load(Ordering::Relaxed);
+                (*bound, cumulative)
+            })
+            .collect();
+
+        HistogramSnapshot {
+            buckets,
+            count: state.count.load(Ordering::Relaxed),
+            sum: f64::from_bits(state.sum.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// State of a [`Histogram`] at a given time
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramSnapshot {
+    /// Upper bound of each bucket with the number of observations lower or equal to it
+    pub buckets: Vec<(f64, u64)>,
+    /// Total number of observations
+    pub count: u64,
+    /// Sum of all the observations
+    pub sum: f64,
+}
+
+/// Value of a series at a given time
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetricValue {
+    /// Value of a [`Counter`]
+    Counter(u64),
+    /// Value of a [`Gauge`]
+    Gauge(i64),
+    /// State of a [`Histogram`]
+    Histogram(HistogramSnapshot),
+}
+
+/// Value of one series of a metric
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricSeries {
+    /// Labels of the series
+    pub labels: Labels,
+    /// Current value of the series
+    pub value: MetricValue,
+}
+
+/// All the series of a metric at a given time
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricFamily {
+    /// Name of the metric
+    pub name: &'static str,
+    /// Description of the metric
+    pub help: &'static str,
+    /// Type of the metric
+    pub kind: MetricKind,
+    /// Series of the metric, one per set of labels
+    pub series: Vec<MetricSeries>,
+}
+
+#[derive(Debug)]
+enum Metric {
+    Counter(Counter),
+    Gauge(Gauge),
+    Histogram(Histogram),
+}
+
+impl Metric {
+    fn value(&self) -> MetricValue {
+        match self {
+            Metric::Counter(counter) => MetricValue::Counter(counter.get()),
+            Metric::Gauge(gauge) => MetricValue::Gauge(gauge.get()),
+            Metric::Histogram(histogram) => MetricValue::Histogram(histogram.snapshot()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Family {
+    help: &'static str,
+    kind: MetricKind,
+    series: BTreeMap<Labels, Metric>,
+}
+
+impl Family {
+    fn snapshot(&self, name: &'static str) -> MetricFamily {
+        MetricFamily {
+            name,
+            help: self.help,
+            kind: self.kind,
+            series: self
+                .series
+                .iter()
+                .map(|(labels, metric)| MetricSeries {
+                    labels: labels.clone(),
+                    value: metric.value(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Set of named metrics, each metric having one series per set of labels.
+///
+/// Metrics are registered the first time they are accessed, and the returned handles can be kept
+/// to update them without going through the registry again:
+///
+/// ```rust
+/// use ockam_node::metrics::MetricsRegistry;
+///
+/// let registry = MetricsRegistry::default();
+/// let sent = registry.counter("messages_sent_total", "Number of sent messages", &[("peer", "alice")]);
+/// sent.inc();
+/// assert!(registry.encode_prometheus().contains("messages_sent_total{peer=\"alice\"} 1"));
+/// ```
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    families: Mutex<BTreeMap<&'static str, Family>>,
+}
+
+impl MetricsRegistry {
+    /// Registry shared by all the nodes of the current process.
+    /// This is where Ockam crates record their metrics
+    pub fn global() -> &'static MetricsRegistry {
+        &GLOBAL_REGISTRY
+    }
+
+    /// Return the counter with the given name and labels, registering it if necessary
+    pub fn counter(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&'static str, &str)],
+    ) -> Counter {
+        match self.get_or_register(name, help, MetricKind::Counter, labels, || {
+            Metric::Counter(Counter::default())
+        }) {
+            Some(Metric::Counter(counter)) => counter,
+            _ => Counter::default(),
+        }
+    }
+
+    /// Return the gauge with the given name and labels, registering it if necessary
+    pub fn gauge(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&'static str, &str)],
+    ) -> Gauge {
+        match self.get_or_register(name, help, MetricKind::Gauge, labels, || {
+            Metric::Gauge(Gauge::default())
+        }) {
+            Some(Metric::Gauge(gauge)) => gauge,
+            _ => Gauge::default(),
+        }
+    }
+
+    /// Return the histogram with the given name and labels, registering it with the given
+    /// buckets if necessary
+    pub fn histogram(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        buckets: &'static [f64],
+        labels: &[(&'static str, &str)],
+    ) -> Histogram {
+        match self.get_or_register(name, help, MetricKind::Histogram, labels, || {
+            Metric::Histogram(Histogram::new(buckets))
+        }) {
+            Some(Metric::Histogram(histogram)) => histogram,
+            _ => Histogram::new(buckets),
+        }
+    }
+
+    /// Remove the series with the given name and labels, for example when the connection
+    /// or the worker it describes is gone
+    pub fn remove(&self, name: &'static str, labels: &[(&'static str, &str)]) {
+        let mut families = self.families.lock().unwrap();
+        if let Some(family) = families.get_mut(name) {
+            family.series.remove(&to_labels(labels));
+        }
+    }
+
+    /// Current state of all the metrics, sorted by name
+    pub fn snapshot(&self) -> Vec<MetricFamily> {
+        let families = self.families.lock().unwrap();
+        families
+            .iter()
+            .map(|(name, family)| family.snapshot(name))
+            .collect()
+    }
+
+    /// Current state of the metric with the given name, if it is registered
+    pub fn family(&self, name: &str) -> Option<MetricFamily> {
+        let families = self.families.lock().unwrap();
+        families
+            .get_key_value(name)
+            .map(|(name, family)| family.snapshot(name))
+    }
+
+    /// Current state of all the metrics in the Prometheus text exposition format
+    pub fn encode_prometheus(&self) -> String {
+        super::encode_prometheus(&self.snapshot())
+    }
+
+    /// Return a handle on the series with the given labels.
+    /// Return `None`, and log a warning, if the metric was already registered with another type
+    fn get_or_register(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        kind: MetricKind,
+        labels: &[(&'static str, &str)],
+        create: impl FnOnce() -> Metric,
+    ) -> Option<Metric> {
+        let mut families = self.families.lock().unwrap();
+        let family = families.entry(name).or_insert_with(|| Family {
+            help,
+            kind,
+            series: BTreeMap::new(),
+        });
+        if family.kind != kind {
+            warn!(
+                "The metric {} is a {}, it can't be used as a {}",
+                name, family.kind, kind
+            );
+            return None;
+        }
+
+        let metric = family
+            .series
+            .entry(to_labels(labels))
+            .or_insert_with(create);
+        Some(match metric {
+            Metric::Counter(counter) => Metric::Counter(counter.clone()),
+            Metric::Gauge(gauge) => Metric::Gauge(gauge.clone()),
+            Metric::Histogram(histogram) => Metric::Histogram(histogram.clone()),
+        })
+    }
+}
+
+fn to_labels(labels: &[(&'static str, &str)]) -> Labels {
+    labels
+        .iter()
+        .map(|(name, value)| (*name, value.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_are_shared_per_labels() {
+        let registry = MetricsRegistry::default();
+        registry.counter("c", "help", &[("a", "1")]).inc();
+        registry.counter("c", "help", &[("a", "1")]).inc_by(2);
+        registry.counter("c", "help", &[("a", "2")]).inc();
+
+        let family = &registry.snapshot()[0];
+        assert_eq!(family.kind, MetricKind::Counter);
+        assert_eq!(family.series.len(), 2);
+        assert_eq!(family.series[0].value, MetricValue::Counter(3));
+
+        registry.remove("c", &[("a", "1")]);
+        assert_eq!(registry.snapshot()[0].series.len(), 1);
+
+        // a metric keeps the type it was registered with
+        registry.gauge("c", "help", &[]).set(10);
+        assert_eq!(registry.snapshot()[0].series.len(), 1);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let histogram = Histogram::new(&[1.0, 5.0]);
+        histogram.observe(0.5);
+        histogram.observe(3.0);
+        histogram.observe(10.0);
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.buckets, vec![(1.0, 1), (5.0, 2)]);
+        assert_eq!(snapshot.count, 3);
+        assert_eq!(snapshot.sum, 13.5);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::channel_types::{OverflowPolicy, DEFAULT_MAILBOX_CAPACITY};
 use crate::tokio::runtime::Runtime;
 use crate::{debugger, Context, Executor};
 use ockam_core::compat::sync::Arc;
@@ -50,7 +51,9 @@ impl NodeBuilder {
         }
     }
 
-    /// Disable exit on panic on this node
+    /// Disable exit on panic on this node.
+    /// The panics of supervised workers and processors never exit the process,
+    /// see [`RestartPolicy`](crate::RestartPolicy)
     pub fn no_exit_on_panic(self) -> Self {
         Self {
             logging: self.logging,
@@ -83,6 +86,11 @@ impl NodeBuilder {
         #[cfg(feature = "std")]
         if self.exit_on_panic {
             std::panic::set_hook(Box::new(|panic_info| {
+                // The panics of supervised workers and processors are recovered by restarting them
+                if crate::supervisor::is_supervised() {
+                    error!("A supervised worker panicked: {panic_info}");
+                    return;
+                }
                 let message1 = format!("A fatal error occurred: {panic_info}.");
                 let message2 = "Please report this issue, with a copy of your logs, to https://github.com/build-trust/ockam/issues.";
                 error!(message1);
@@ -132,6 +140,8 @@ impl NodeBuilder {
             None,
             Default::default(),
             &flow_controls,
+            DEFAULT_MAILBOX_CAPACITY,
+            OverflowPolicy::default(),
             #[cfg(feature = "std")]
             OpenTelemetryContext::current(),
         );
@@ -1,5 +1,7 @@
 use crate::debugger;
 use crate::error::{NodeError, NodeReason};
+#[cfg(feature = "std")]
+use crate::supervisor::{RestartPolicy, Supervisor};
 use crate::{relay::ProcessorRelay, Context, NodeMessage};
 use alloc::string::String;
 use ockam_core::compat::{sync::Arc, vec::Vec};
@@ -42,6 +44,8 @@ where
             processor: self.processor,
             address: address.into(),
             metadata: None,
+            #[cfg(feature = "std")]
+            supervisor: None,
         }
     }
 
@@ -51,6 +55,8 @@ where
             mailboxes,
             processor: self.processor,
             metadata_list: vec![],
+            #[cfg(feature = "std")]
+            supervisor: None,
         }
     }
 }
@@ -62,6 +68,8 @@ where
     mailboxes: Mailboxes,
     processor: P,
     metadata_list: Vec<AddressAndMetadata>,
+    #[cfg(feature = "std")]
+    supervisor: Option<Supervisor<P>>,
 }
 
 impl<P> ProcessorBuilderMultipleAddresses<P>
@@ -124,9 +132,27 @@ where
         self
     }
 
+    /// Restart the processor when it fails, see [`RestartPolicy`]
+    #[cfg(feature = "std")]
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self
+    where
+        P: Clone,
+    {
+        self.supervisor = Some(Supervisor::new(policy, self.processor.clone()));
+        self
+    }
+
     /// Consume this builder and start a new Ockam [`Processor`] from the given context
     pub async fn start(self, context: &Context) -> Result<()> {
-        start(context, self.mailboxes, self.processor, self.metadata_list).await
+        start(
+            context,
+            self.mailboxes,
+            self.processor,
+            self.metadata_list,
+            #[cfg(feature = "std")]
+            self.supervisor,
+        )
+        .await
     }
 }
 
@@ -139,6 +165,8 @@ where
     address: Address,
     processor: P,
     metadata: Option<AddressAndMetadata>,
+    #[cfg(feature = "std")]
+    supervisor: Option<Supervisor<P>>,
 }
 
 impl<P> ProcessorBuilderOneAddress<P>
@@ -190,6 +218,16 @@ where
         self
     }
 
+    /// Restart the processor when it fails, see [`RestartPolicy`]
+    #[cfg(feature = "std")]
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self
+    where
+        P: Clone,
+    {
+        self.supervisor = Some(Supervisor::new(policy, self.processor.clone()));
+        self
+    }
+
     /// Consume this builder and start a new Ockam [`Processor`] from the given context
     pub async fn start(self, context: &Context) -> Result<()> {
         start(
@@ -197,6 +235,8 @@ where
             Mailboxes::main(self.address, self.incoming_ac, self.outgoing_ac),
             self.processor,
             self.metadata.map(|m| vec![m]).unwrap_or_default(),
+            #[cfg(feature = "std")]
+            self.supervisor,
         )
         .await
     }
@@ -250,6 +290,7 @@ pub async fn start<P>(
     mailboxes: Mailboxes,
     processor: P,
     metadata: Vec<AddressAndMetadata>,
+    #[cfg(feature = "std")] supervisor: Option<Supervisor<P>>,
 ) -> Result<()>
 where
     P: Processor<Context = Context>,
@@ -282,7 +323,14 @@ where
         .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??;
 
     // Then initialise the processor message relay
-    ProcessorRelay::<P>::init(context.runtime(), processor, ctx, ctrl_rx);
+    ProcessorRelay::<P>::init(
+        context.runtime(),
+        processor,
+        ctx,
+        ctrl_rx,
+        #[cfg(feature = "std")]
+        supervisor,
+    );
 
     Ok(())
 }
@@ -1,6 +1,10 @@
 use crate::channel_types::SmallReceiver;
+#[cfg(feature = "std")]
+use crate::supervisor::{catch_failure, wait_before_restart, Failure, RestartStrategy, Supervisor};
 use crate::{relay::CtrlSignal, tokio::runtime::Handle, Context};
-use ockam_core::{Processor, Result};
+use ockam_core::Processor;
+#[cfg(not(feature = "std"))]
+use ockam_core::Result;
 
 pub struct ProcessorRelay<P>
 where
@@ -8,6 +12,8 @@ where
 {
     processor: P,
     ctx: Context,
+    #[cfg(feature = "std")]
+    supervisor: Option<Supervisor<P>>,
 }
 
 impl<P> ProcessorRelay<P>
@@ -15,7 +21,12 @@ where
     P: Processor<Context = Context>,
 {
     pub fn new(processor: P, ctx: Context) -> Self {
-        Self { processor, ctx }
+        Self {
+            processor,
+            ctx,
+            #[cfg(feature = "std")]
+            supervisor: None,
+        }
     }
 
     #[cfg_attr(not(feature = "std"), allow(unused_mut))]
@@ -24,25 +35,93 @@ where
         let mut ctx = self.ctx;
         let mut processor = self.processor;
         let ctx_addr = ctx.address();
+        #[cfg(feature = "std")]
+        let mut supervisor = self.supervisor;
 
-        match processor.initialize(&mut ctx).await {
-            Ok(()) => {}
+        #[cfg(feature = "std")]
+        let initialized =
+            match catch_failure(processor.initialize(&mut ctx), supervisor.is_some()).await {
+                Ok(()) => true,
+                Err(failure) => {
+                    error!(
+                        "Failure during '{}' processor initialisation: {}",
+                        ctx_addr, failure
+                    );
+                    restart(&mut processor, &mut ctx, &mut supervisor, &mut ctrl_rx).await
+                }
+            };
+        #[cfg(not(feature = "std"))]
+        let initialized = match processor.initialize(&mut ctx).await {
+            Ok(()) => true,
             Err(e) => {
                 error!(
                     "Failure during '{}' processor initialisation: {}",
                     ctx.address(),
                     e
                 );
-                shutdown_and_stop_ack(&mut processor, &mut ctx, &ctx_addr).await;
-                return;
+                false
             }
+        };
+
+        if !initialized {
+            shutdown_and_stop_ack(&mut processor, &mut ctx, &ctx_addr).await;
+            return;
         }
 
         if let Err(e) = ctx.set_ready().await {
             error!("Failed to mark processor '{}' as 'ready': {}", ctx_addr, e);
         }
 
+        #[cfg(feature = "std")]
+        loop {
+            // Run the processor until it stops, fails or a control signal is received
+            tokio::select! {
+                signal = ctrl_rx.recv() => match signal {
+                    // Another member of the cluster failed
+                    Some(CtrlSignal::Restart) => {
+                        if supervisor.is_none() {
+                            debug!(
+                                "Processor '{}' is not supervised, ignoring restart signal",
+                                ctx_addr
+                            );
+                        } else if !restart(
+                            &mut processor,
+                            &mut ctx,
+                            &mut supervisor,
+                            &mut ctrl_rx,
+                        )
+                        .await
+                        {
+                            break;
+                        }
+                    }
+                    _ => {
+                        debug!("Shutting down processor {} due to shutdown signal", ctx_addr);
+                        break;
+                    }
+                },
+                failure = run_until_failure(&mut processor, &mut ctx, &mut supervisor) => {
+                    let failure = match failure {
+                        Some(failure) => failure,
+                        None => break,
+                    };
+                    if !handle_failure(
+                        failure,
+                        &mut processor,
+                        &mut ctx,
+                        &mut supervisor,
+                        &mut ctrl_rx,
+                    )
+                    .await
+                    {
+                        break;
+                    }
+                },
+            };
+        }
+
         // This future encodes the main processor run loop logic
+        #[cfg(not(feature = "std"))]
         let run_loop = async {
             loop {
                 match processor.process(&mut ctx).await {
@@ -66,20 +145,6 @@ where
             Result::<()>::Ok(())
         };
 
-        #[cfg(feature = "std")]
-        {
-            // This future resolves when a stop control signal is received
-            let shutdown_signal = async { ctrl_rx.recv().await };
-
-            // Then select over the two futures
-            tokio::select! {
-                _ = shutdown_signal => {
-                    debug!("Shutting down processor {} due to shutdown signal", ctx_addr);
-                },
-                _ = run_loop => {}
-            };
-        }
-
         // TODO wait on run_loop until we have a no_std select! implementation
         #[cfg(not(feature = "std"))]
         match run_loop.await {
@@ -97,12 +162,144 @@ where
         processor: P,
         ctx: Context,
         ctrl_rx: SmallReceiver<CtrlSignal>,
+        #[cfg(feature = "std")] supervisor: Option<Supervisor<P>>,
     ) {
-        let relay = ProcessorRelay::<P>::new(processor, ctx);
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut relay = ProcessorRelay::<P>::new(processor, ctx);
+        #[cfg(feature = "std")]
+        {
+            relay.supervisor = supervisor;
+        }
         rt.spawn(relay.run(ctrl_rx));
     }
 }
 
+/// Run the processor until it stops, returning `None`, or until it fails
+/// in a way which requires a restart or a shutdown
+#[cfg(feature = "std")]
+async fn run_until_failure<P>(
+    processor: &mut P,
+    ctx: &mut Context,
+    supervisor: &mut Option<Supervisor<P>>,
+) -> Option<Failure>
+where
+    P: Processor<Context = Context>,
+{
+    let ctx_addr = ctx.address();
+    loop {
+        match catch_failure(processor.process(ctx), supervisor.is_some()).await {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(failure) => {
+                #[cfg(feature = "debugger")]
+                error!(
+                    "Error encountered during '{}' processing: {:?}",
+                    ctx_addr, failure
+                );
+                #[cfg(not(feature = "debugger"))]
+                error!(
+                    "Error encountered during '{}' processing: {}",
+                    ctx_addr, failure
+                );
+
+                let restarts = supervisor
+                    .as_ref()
+                    .map_or(false, |supervisor| supervisor.restarts_on(&failure));
+                // The state of a processor which panicked can't be trusted anymore
+                if restarts || matches!(failure, Failure::Panic(_)) {
+                    return Some(failure);
+                }
+            }
+        }
+    }
+}
+
+/// Handle a failure of the running processor, return false if it must be stopped
+#[cfg(feature = "std")]
+async fn handle_failure<P>(
+    failure: Failure,
+    processor: &mut P,
+    ctx: &mut Context,
+    supervisor: &mut Option<Supervisor<P>>,
+    ctrl_rx: &mut SmallReceiver<CtrlSignal>,
+) -> bool
+where
+    P: Processor<Context = Context>,
+{
+    let strategy = match supervisor.as_ref() {
+        Some(supervisor) if supervisor.restarts_on(&failure) => supervisor.strategy(),
+        _ => return false,
+    };
+
+    if strategy == RestartStrategy::OneForAll {
+        if let Err(e) = ctx.restart_cluster().await {
+            error!(
+                "Failed to restart the cluster of '{}': {}",
+                ctx.address(),
+                e
+            );
+        }
+    }
+
+    restart(processor, ctx, supervisor, ctrl_rx).await
+}
+
+/// Replace the failed processor with a new instance and initialize it,
+/// return false if the processor must be stopped instead
+#[cfg(feature = "std")]
+async fn restart<P>(
+    processor: &mut P,
+    ctx: &mut Context,
+    supervisor: &mut Option<Supervisor<P>>,
+    ctrl_rx: &mut SmallReceiver<CtrlSignal>,
+) -> bool
+where
+    P: Processor<Context = Context>,
+{
+    let ctx_addr = ctx.address();
+    let supervisor = match supervisor.as_mut() {
+        Some(supervisor) => supervisor,
+        None => return false,
+    };
+
+    loop {
+        let delay = match supervisor.next_restart() {
+            Some(delay) => delay,
+            None => {
+                error!(
+                    "Processor '{}' failed too many times, stopping it",
+                    ctx_addr
+                );
+                return false;
+            }
+        };
+
+        debug!("Restarting processor '{}' in {:?}", ctx_addr, delay);
+        if !wait_before_restart(delay, ctrl_rx).await {
+            return false;
+        }
+
+        if let Err(failure) = catch_failure(processor.shutdown(ctx), true).await {
+            error!(
+                "Failure during '{}' processor shutdown: {}",
+                ctx_addr, failure
+            );
+        }
+        *processor = supervisor.new_instance();
+
+        match catch_failure(processor.initialize(ctx), true).await {
+            Ok(()) => {
+                info!("Processor '{}' restarted", ctx_addr);
+                return true;
+            }
+            Err(failure) => error!(
+                "Failure during '{}' processor initialisation: {}",
+                ctx_addr, failure
+            ),
+        }
+    }
+}
+
 async fn shutdown_and_stop_ack<P>(
     processor: &mut P,
     ctx: &mut Context,
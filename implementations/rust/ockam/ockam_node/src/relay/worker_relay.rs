@@ -1,5 +1,7 @@
 use crate::channel_types::SmallReceiver;
 use crate::relay::CtrlSignal;
+#[cfg(feature = "std")]
+use crate::supervisor::{catch_failure, wait_before_restart, Failure, RestartStrategy, Supervisor};
 use crate::tokio::runtime::Handle;
 use crate::Context;
 use cfg_if::cfg_if;
@@ -15,11 +17,18 @@ use opentelemetry::trace::FutureExt;
 pub struct WorkerRelay<W> {
     worker: W,
     ctx: Context,
+    #[cfg(feature = "std")]
+    supervisor: Option<Supervisor<W>>,
 }
 
 impl<W: Worker> WorkerRelay<W> {
     pub fn new(worker: W, ctx: Context) -> Self {
-        Self { worker, ctx }
+        Self {
+            worker,
+            ctx,
+            #[cfg(feature = "std")]
+            supervisor: None,
+        }
     }
 }
 
@@ -98,17 +107,39 @@ where
     #[cfg_attr(not(feature = "std"), allow(unused_mut))]
     #[cfg_attr(not(feature = "std"), allow(unused_variables))]
     async fn run(mut self, mut ctrl_rx: SmallReceiver<CtrlSignal>) {
-        match self.worker.initialize(&mut self.ctx).await {
-            Ok(()) => {}
+        #[cfg(feature = "std")]
+        let initialized = match catch_failure(
+            self.worker.initialize(&mut self.ctx),
+            self.supervisor.is_some(),
+        )
+        .await
+        {
+            Ok(()) => true,
+            Err(failure) => {
+                error!(
+                    "Failure during '{}' worker initialisation: {}",
+                    self.ctx.address(),
+                    failure
+                );
+                self.restart(&mut ctrl_rx).await
+            }
+        };
+        #[cfg(not(feature = "std"))]
+        let initialized = match self.worker.initialize(&mut self.ctx).await {
+            Ok(()) => true,
             Err(e) => {
                 error!(
                     "Failure during '{}' worker initialisation: {}",
                     self.ctx.address(),
                     e
                 );
-                self.shutdown_and_stop_ack().await;
-                return;
+                false
             }
+        };
+
+        if !initialized {
+            self.shutdown_and_stop_ack().await;
+            return;
         }
 
         let address = self.ctx.address();
@@ -117,10 +148,12 @@ where
             error!("Failed to mark worker '{}' as 'ready': {}", address, e);
         }
 
+        #[cfg(feature = "std")]
+        let supervised = self.supervisor.is_some();
         #[cfg(feature = "std")]
         loop {
             crate::tokio::select! {
-                result = self.recv_message() => {
+                result = catch_failure(self.recv_message(), supervised) => {
                     match result {
                         // Successful message handling -- keep running
                         Ok(true) => {},
@@ -128,22 +161,37 @@ where
                         Ok(false) => {
                             break;
                         },
-                        // An error occurred -- log and continue
-                        Err(e) => {
+                        // An error occurred -- log, then restart the worker if it is
+                        // supervised, stop it if it panicked, or continue
+                        Err(failure) => {
                             #[cfg(feature = "debugger")]
-                            error!("Error encountered during '{}' message handling: {:?}", address, e);
+                            error!("Error encountered during '{}' message handling: {:?}", address, failure);
                             #[cfg(not(feature = "debugger"))]
-                            error!("Error encountered during '{}' message handling: {}", address, e);
+                            error!("Error encountered during '{}' message handling: {}", address, failure);
+
+                            if !self.handle_failure(failure, &mut ctrl_rx).await {
+                                break;
+                            }
                         }
                     }
                 },
                 result = ctrl_rx.recv() => {
-                    if result.is_some() {
-                        debug!("Relay received shutdown signal, terminating!");
-                        break;
+                    match result {
+                        // Another member of the cluster failed
+                        Some(CtrlSignal::Restart) => {
+                            if self.supervisor.is_none() {
+                                debug!("Worker '{}' is not supervised, ignoring restart signal", address);
+                            } else if !self.restart(&mut ctrl_rx).await {
+                                break;
+                            }
+                        }
+                        Some(_) => {
+                            debug!("Relay received shutdown signal, terminating!");
+                            break;
+                        }
+                        // We are stopping
+                        None => {}
                     }
-
-                    // We are stopping
                 }
             };
         }
@@ -167,6 +215,73 @@ where
         self.shutdown_and_stop_ack().await;
     }
 
+    /// Handle a failure of the running worker, return false if it must be stopped
+    #[cfg(feature = "std")]
+    async fn handle_failure(
+        &mut self,
+        failure: Failure,
+        ctrl_rx: &mut SmallReceiver<CtrlSignal>,
+    ) -> bool {
+        let strategy = match self.supervisor.as_ref() {
+            Some(supervisor) if supervisor.restarts_on(&failure) => supervisor.strategy(),
+            // The state of a worker which panicked can't be trusted anymore
+            _ => return !matches!(failure, Failure::Panic(_)),
+        };
+
+        if strategy == RestartStrategy::OneForAll {
+            if let Err(e) = self.ctx.restart_cluster().await {
+                error!(
+                    "Failed to restart the cluster of '{}': {}",
+                    self.ctx.address(),
+                    e
+                );
+            }
+        }
+
+        self.restart(ctrl_rx).await
+    }
+
+    /// Replace the failed worker with a new instance and initialize it,
+    /// return false if the worker must be stopped instead
+    #[cfg(feature = "std")]
+    async fn restart(&mut self, ctrl_rx: &mut SmallReceiver<CtrlSignal>) -> bool {
+        let address = self.ctx.address();
+        loop {
+            let supervisor = match self.supervisor.as_mut() {
+                Some(supervisor) => supervisor,
+                None => return false,
+            };
+            let delay = match supervisor.next_restart() {
+                Some(delay) => delay,
+                None => {
+                    error!("Worker '{}' failed too many times, stopping it", address);
+                    return false;
+                }
+            };
+
+            debug!("Restarting worker '{}' in {:?}", address, delay);
+            if !wait_before_restart(delay, ctrl_rx).await {
+                return false;
+            }
+
+            if let Err(failure) = catch_failure(self.worker.shutdown(&mut self.ctx), true).await {
+                error!("Failure during '{}' worker shutdown: {}", address, failure);
+            }
+            self.worker = supervisor.new_instance();
+
+            match catch_failure(self.worker.initialize(&mut self.ctx), true).await {
+                Ok(()) => {
+                    info!("Worker '{}' restarted", address);
+                    return true;
+                }
+                Err(failure) => error!(
+                    "Failure during '{}' worker initialisation: {}",
+                    address, failure
+                ),
+            }
+        }
+    }
+
     async fn shutdown_and_stop_ack(&mut self) {
         // Run the shutdown hook for this worker
         match self.worker.shutdown(&mut self.ctx).await {
@@ -188,8 +303,19 @@ where
     }
 
     /// Build and spawn a new worker relay, returning a send handle to it
-    pub(crate) fn init(rt: &Handle, worker: W, ctx: Context, ctrl_rx: SmallReceiver<CtrlSignal>) {
-        let relay = WorkerRelay::new(worker, ctx);
+    pub(crate) fn init(
+        rt: &Handle,
+        worker: W,
+        ctx: Context,
+        ctrl_rx: SmallReceiver<CtrlSignal>,
+        #[cfg(feature = "std")] supervisor: Option<Supervisor<W>>,
+    ) {
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut relay = WorkerRelay::new(worker, ctx);
+        #[cfg(feature = "std")]
+        {
+            relay.supervisor = supervisor;
+        }
         rt.spawn(relay.run(ctrl_rx));
     }
 }
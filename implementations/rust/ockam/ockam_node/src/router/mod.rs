@@ -13,7 +13,7 @@ use std::sync::atomic::AtomicUsize;
 use record::{AddressRecord, InternalMap, WorkerMeta};
 use state::{NodeState, RouterState};
 
-use crate::channel_types::{router_channel, MessageSender, RouterReceiver, SmallSender};
+use crate::channel_types::{router_channel, MailboxSender, RouterReceiver, SmallSender};
 use crate::{
     error::{NodeError, NodeReason},
     relay::CtrlSignal,
@@ -26,7 +26,7 @@ use ockam_core::{Address, RelayMessage, Result, TransportType};
 /// A pair of senders to a worker relay
 #[derive(Debug)]
 pub struct SenderPair {
-    pub msgs: MessageSender<RelayMessage>,
+    pub msgs: MailboxSender<RelayMessage>,
     pub ctrl: SmallSender<CtrlSignal>,
 }
 
@@ -273,6 +273,14 @@ impl Router {
                     .map_err(|_| NodeError::NodeState(NodeReason::Unknown).internal())?;
             }
 
+            RestartCluster(addr) if self.state.running() => {
+                debug!("Restarting the clusters of address {}", addr);
+                self.map.restart_cluster(&addr);
+            }
+
+            // Members are stopping anyway
+            RestartCluster(_) => {}
+
             SetReady(addr) => {
                 trace!("Marking address {} as ready!", addr);
                 match self.map.set_ready(addr) {
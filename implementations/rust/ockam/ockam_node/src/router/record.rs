@@ -1,4 +1,6 @@
-use crate::channel_types::{MessageSender, SmallSender};
+use crate::channel_types::{MailboxSender, SmallSender};
+#[cfg(feature = "std")]
+use crate::metrics::Counter;
 use crate::relay::CtrlSignal;
 use crate::{
     error::{NodeError, NodeReason},
@@ -58,10 +60,6 @@ impl InternalMap {
         self.address_records_map.clear()
     }
 
-    pub(super) fn get_address_record(&self, primary_address: &Address) -> Option<&AddressRecord> {
-        self.address_records_map.get(primary_address)
-    }
-
     pub(super) fn get_address_record_mut(
         &mut self,
         primary_address: &Address,
@@ -216,6 +214,23 @@ impl InternalMap {
         }
     }
 
+    /// Signal the other workers and processors of the clusters of an address to restart.
+    /// Only the supervised ones handle this signal
+    pub(super) fn restart_cluster(&mut self, primary: &Address) {
+        let members: BTreeSet<Address> = self
+            .clusters
+            .values()
+            .filter(|addrs| addrs.contains(primary))
+            .flat_map(|addrs| addrs.iter().cloned())
+            .collect();
+
+        for (addr, rec) in self.address_records_map.iter_mut() {
+            if addr != primary && members.contains(addr) && !rec.meta.detached {
+                rec.restart();
+            }
+        }
+    }
+
     /// Mark this address as "having started to stop"
     pub(super) fn init_stop(&mut self, addr: Address) {
         self.stopping.insert(addr);
@@ -255,6 +270,8 @@ impl InternalMap {
         self.stopping.remove(&primary);
         if let Some(record) = self.remove_address_record(&primary) {
             for addr in record.address_set {
+                #[cfg(feature = "std")]
+                crate::metrics::remove_address_metrics(&addr);
                 self.remove_alias(&addr);
                 self.address_metadata_map.remove(&addr);
             }
@@ -272,12 +289,15 @@ pub struct WorkerMeta {
 #[derive(Debug)]
 pub struct AddressRecord {
     address_set: Vec<Address>,
-    sender: Option<MessageSender<RelayMessage>>,
+    sender: Option<MailboxSender<RelayMessage>>,
     ctrl_tx: SmallSender<CtrlSignal>, // Unused for not-detached workers
     state: AddressState,
     ready: ReadyState,
     meta: WorkerMeta,
     msg_count: Arc<AtomicUsize>,
+    /// Counters of the messages routed to each address, registered on the first message
+    #[cfg(feature = "std")]
+    messages_routed: BTreeMap<Address, Counter>,
 }
 
 impl AddressRecord {
@@ -285,7 +305,7 @@ impl AddressRecord {
         &self.address_set
     }
 
-    pub fn sender(&self) -> MessageSender<RelayMessage> {
+    pub fn sender(&self) -> MailboxSender<RelayMessage> {
         self.sender.clone().expect("No such sender!")
     }
 
@@ -295,7 +315,7 @@ impl AddressRecord {
 
     pub fn new(
         address_set: Vec<Address>,
-        sender: MessageSender<RelayMessage>,
+        sender: MailboxSender<RelayMessage>,
         ctrl_tx: SmallSender<CtrlSignal>,
         msg_count: Arc<AtomicUsize>,
         meta: WorkerMeta,
@@ -308,12 +328,24 @@ impl AddressRecord {
             ready: ReadyState::Initialising(vec![]),
             msg_count,
             meta,
+            #[cfg(feature = "std")]
+            messages_routed: BTreeMap::new(),
         }
     }
 
+    /// Return the counter of the messages routed to one of the addresses of this worker
+    #[cfg(feature = "std")]
+    pub fn messages_routed(&mut self, address: &Address) -> Counter {
+        self.messages_routed
+            .entry(address.clone())
+            .or_insert_with(|| crate::metrics::messages_routed(address))
+            .clone()
+    }
+
+    /// Number of messages waiting in the mailbox of this worker
     #[inline]
-    pub fn increment_msg_count(&self) {
-        self.msg_count.fetch_add(1, Ordering::Relaxed);
+    pub fn mailbox_count(&self) -> usize {
+        self.msg_count.load(Ordering::Acquire)
     }
 
     /// Signal this worker to stop -- it will no longer be able to receive messages
@@ -330,6 +362,17 @@ impl AddressRecord {
         Ok(())
     }
 
+    /// Signal this worker to restart, if it is supervised
+    pub fn restart(&mut self) {
+        if self.state != AddressState::Running {
+            return;
+        }
+        // A full channel means that a signal is already pending for this worker
+        if self.ctrl_tx.try_send(CtrlSignal::Restart).is_err() {
+            debug!("Could not signal {:?} to restart", self.address_set);
+        }
+    }
+
     /// Check the integrity of this record
     #[inline]
     pub fn check(&self) -> bool {
@@ -382,7 +425,9 @@ pub enum ReadyState {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::channel_types::small_channel;
+    use crate::channel_types::{
+        mailbox_channel, small_channel, OverflowPolicy, DEFAULT_MAILBOX_CAPACITY,
+    };
     use crate::router::record::InternalMap;
 
     #[test]
@@ -416,7 +461,11 @@ mod test {
 
     /// HELPERS
     fn create_address_record(primary: &str) -> AddressRecord {
-        let (tx1, _) = small_channel();
+        let (tx1, _) = mailbox_channel(
+            DEFAULT_MAILBOX_CAPACITY,
+            OverflowPolicy::default(),
+            Arc::new(AtomicUsize::new(0)),
+        );
         let (tx2, _) = small_channel();
         AddressRecord::new(
             vec![primary.into()],
@@ -19,7 +19,8 @@ pub(super) async fn resolve(
     let base = format!("Resolving worker address '{}'...", addr);
 
     let address_record = if let Some(primary_address) = router.map.get_primary_address(&addr) {
-        router.map.get_address_record(primary_address)
+        let primary_address = primary_address.clone();
+        router.map.get_address_record_mut(&primary_address)
     } else {
         trace!("{} FAILED; no such worker", base);
         reply
@@ -32,9 +33,19 @@ pub(super) async fn resolve(
 
     match address_record {
         Some(record) if record.check() => {
-            trace!("{} OK", base);
-            record.increment_msg_count();
-            reply.send(RouterReply::sender(addr, record.sender()))
+            trace!(
+                "{} OK, {} messages in mailbox",
+                base,
+                record.mailbox_count()
+            );
+            #[cfg(feature = "std")]
+            let messages_routed = record.messages_routed(&addr);
+            reply.send(RouterReply::sender(
+                addr,
+                record.sender(),
+                #[cfg(feature = "std")]
+                messages_routed,
+            ))
         }
         Some(_) => {
             trace!("{} REJECTED; worker shutting down", base);
@@ -0,0 +1,34 @@
+use crate::tokio::time::{advance, Instant};
+use core::time::Duration;
+
+/// Virtual clock of a [`Simulation`](super::Simulation)
+///
+/// The time of a simulation only moves when all its tasks are waiting. It then jumps
+/// to the next timer to fire, so that waiting for a timeout takes no real time.
+///
+/// The clock must be read from a task running in the simulation, otherwise it returns the real time.
+#[derive(Clone, Copy, Debug)]
+pub struct SimulatedClock {
+    start: Instant,
+}
+
+impl SimulatedClock {
+    pub(crate) fn new(start: Instant) -> Self {
+        Self { start }
+    }
+
+    /// Current instant of the virtual clock
+    pub fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Virtual time elapsed since the start of the simulation
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Move the clock forward, firing all the timers expiring in the meantime
+    pub async fn advance(&self, duration: Duration) {
+        advance(duration).await
+    }
+}
@@ -0,0 +1,10 @@
+mod clock;
+mod network;
+mod simulator;
+mod transport;
+mod workers;
+
+pub use clock::*;
+pub use network::{LinkConditions, NetworkStatistics, SimulatedNetwork};
+pub use simulator::*;
+pub use transport::*;
@@ -0,0 +1,311 @@
+use crate::tokio::sync::mpsc::UnboundedSender;
+use crate::tokio::time::{sleep_until, Instant};
+use core::time::Duration;
+use ockam_core::compat::collections::{BTreeMap, BTreeSet};
+use ockam_core::compat::rand::prelude::SeedableRng;
+use ockam_core::compat::rand::rngs::StdRng;
+use ockam_core::compat::rand::Rng;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+use ockam_transport_core::TransportError;
+
+/// Conditions applied to the messages sent from a node to another one
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkConditions {
+    latency: Duration,
+    jitter: Duration,
+    loss: f64,
+}
+
+impl LinkConditions {
+    /// Conditions of a perfect link, delivering all the messages immediately and in order
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay every message by the given latency
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Delay every message by a random duration, up to `jitter`, on top of the latency.
+    /// Messages can be reordered when some jitter is set
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Lose messages with the given probability, between 0 and 1
+    pub fn with_loss(mut self, loss: f64) -> Self {
+        self.loss = loss.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Fixed delay of every message
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// Maximum random delay added to the latency
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Probability of losing a message
+    pub fn loss(&self) -> f64 {
+        self.loss
+    }
+}
+
+/// Number of messages handled by a [`SimulatedNetwork`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NetworkStatistics {
+    sent: u64,
+    delivered: u64,
+    dropped: u64,
+}
+
+impl NetworkStatistics {
+    /// Number of messages sent by the nodes
+    pub fn sent(&self) -> u64 {
+        self.sent
+    }
+
+    /// Number of messages delivered to their destination node
+    pub fn delivered(&self) -> u64 {
+        self.delivered
+    }
+
+    /// Number of messages which were lost, sent across a partition or sent to an unknown node
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+/// Encoded message in transit between two nodes
+pub(crate) struct Datagram {
+    pub(crate) source: String,
+    pub(crate) payload: Vec<u8>,
+}
+
+/// Virtual network connecting the nodes of a [`Simulation`](super::Simulation)
+///
+/// Each message sent on the network is subject to the [`LinkConditions`] of the link
+/// between its source and its destination. The nodes can also be partitioned, in which case
+/// the messages sent across the partition, or still in transit when it is created, are dropped.
+///
+/// All the random decisions are taken with a generator seeded when the network is
+/// created, so that a simulation behaves the same way every time it is run with the same seed.
+#[derive(Clone)]
+pub struct SimulatedNetwork {
+    state: Arc<Mutex<NetworkState>>,
+}
+
+struct NetworkState {
+    rng: StdRng,
+    /// Inbox of each node attached to the network
+    nodes: BTreeMap<String, UnboundedSender<Datagram>>,
+    default_conditions: LinkConditions,
+    /// Conditions of the links from a node to another one
+    conditions: BTreeMap<(String, String), LinkConditions>,
+    /// Pairs of nodes, in alphabetical order, which can't reach each other
+    cut_links: BTreeSet<(String, String)>,
+    /// Nodes which can't reach any other node
+    isolated: BTreeSet<String>,
+    /// Messages in transit, ordered by delivery time and then by sending order
+    in_transit: BTreeMap<(Instant, u64), (String, Datagram)>,
+    next_sequence_number: u64,
+    statistics: NetworkStatistics,
+}
+
+impl NetworkState {
+    fn can_reach(&self, source: &str, destination: &str) -> bool {
+        !self.isolated.contains(source)
+            && !self.isolated.contains(destination)
+            && !self.cut_links.contains(&link(source, destination))
+    }
+}
+
+/// Key of the link between 2 nodes, independently of the direction of the messages
+fn link(node: &str, other_node: &str) -> (String, String) {
+    if node <= other_node {
+        (node.to_string(), other_node.to_string())
+    } else {
+        (other_node.to_string(), node.to_string())
+    }
+}
+
+impl SimulatedNetwork {
+    /// Create a network taking its random decisions with a generator seeded with `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(NetworkState {
+                rng: StdRng::seed_from_u64(seed),
+                nodes: Default::default(),
+                default_conditions: LinkConditions::new(),
+                conditions: Default::default(),
+                cut_links: Default::default(),
+                isolated: Default::default(),
+                in_transit: Default::default(),
+                next_sequence_number: 0,
+                statistics: Default::default(),
+            })),
+        }
+    }
+
+    /// Set the conditions of all the links which have no specific conditions
+    pub fn set_default_conditions(&self, conditions: LinkConditions) {
+        self.state.lock().unwrap().default_conditions = conditions;
+    }
+
+    /// Set the conditions of the messages sent from `source` to `destination`
+    pub fn set_link_conditions(&self, source: &str, destination: &str, conditions: LinkConditions) {
+        self.state
+            .lock()
+            .unwrap()
+            .conditions
+            .insert((source.to_string(), destination.to_string()), conditions);
+    }
+
+    /// Prevent the nodes of a group from reaching the nodes of another group, in both directions
+    pub fn partition(&self, group: &[&str], other_group: &[&str]) {
+        let mut state = self.state.lock().unwrap();
+        for node in group {
+            for other_node in other_group {
+                state.cut_links.insert(link(node, other_node));
+            }
+        }
+    }
+
+    /// Prevent a node from reaching any other node, in both directions
+    pub fn isolate(&self, node: &str) {
+        self.state.lock().unwrap().isolated.insert(node.to_string());
+    }
+
+    /// Remove all the partitions, including the isolated nodes
+    pub fn heal(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.cut_links.clear();
+        state.isolated.clear();
+    }
+
+    /// Return true if the messages sent by `source` can currently reach `destination`
+    pub fn can_reach(&self, source: &str, destination: &str) -> bool {
+        self.state.lock().unwrap().can_reach(source, destination)
+    }
+
+    /// Names of the nodes attached to the network
+    pub fn nodes(&self) -> Vec<String> {
+        self.state.lock().unwrap().nodes.keys().cloned().collect()
+    }
+
+    /// Number of messages handled by the network so far
+    pub fn statistics(&self) -> NetworkStatistics {
+        self.state.lock().unwrap().statistics
+    }
+
+    /// Attach a node to the network, with the inbox receiving its messages
+    pub(crate) fn attach(&self, name: &str, inbox: UnboundedSender<Datagram>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.nodes.contains_key(name) {
+            warn!("a node named {name} is already attached to the simulated network");
+            return Err(TransportError::BindFailed)?;
+        }
+        state.nodes.insert(name.to_string(), inbox);
+        Ok(())
+    }
+
+    /// Detach a node from the network. The messages sent to that node are dropped
+    pub(crate) fn detach(&self, name: &str) {
+        self.state.lock().unwrap().nodes.remove(name);
+    }
+
+    /// Return true if a node with that name is attached to the network
+    pub(crate) fn is_attached(&self, name: &str) -> bool {
+        self.state.lock().unwrap().nodes.contains_key(name)
+    }
+
+    /// Send a message from a node to another one, according to the conditions of their link.
+    /// This function must be called from a task running in the simulation
+    pub(crate) fn send(&self, source: &str, destination: &str, payload: Vec<u8>) {
+        let mut state = self.state.lock().unwrap();
+        state.statistics.sent += 1;
+
+        if !state.can_reach(source, destination) {
+            trace!("dropping a message from {source} to {destination}: the nodes are partitioned");
+            state.statistics.dropped += 1;
+            return;
+        }
+
+        let conditions = state
+            .conditions
+            .get(&(source.to_string(), destination.to_string()))
+            .unwrap_or(&state.default_conditions)
+            .clone();
+
+        if conditions.loss > 0.0 && state.rng.gen_bool(conditions.loss) {
+            trace!("dropping a message from {source} to {destination}: the message is lost");
+            state.statistics.dropped += 1;
+            return;
+        }
+
+        let mut delay = conditions.latency;
+        if !conditions.jitter.is_zero() {
+            let jitter = conditions.jitter.as_nanos() as u64;
+            delay += Duration::from_nanos(state.rng.gen_range(0..=jitter));
+        }
+        let deadline = Instant::now() + delay;
+        let sequence_number = state.next_sequence_number;
+        state.next_sequence_number += 1;
+        state.in_transit.insert(
+            (deadline, sequence_number),
+            (
+                destination.to_string(),
+                Datagram {
+                    source: source.to_string(),
+                    payload,
+                },
+            ),
+        );
+        drop(state);
+
+        // Timers expiring at the same instant don't necessarily fire in the order where
+        // they were created, so each timer delivers all the messages which are due, in order
+        let network = self.clone();
+        crate::tokio::spawn(async move {
+            sleep_until(deadline).await;
+            network.deliver_due_messages();
+        });
+    }
+
+    /// Deliver the messages in transit whose delivery time has passed
+    fn deliver_due_messages(&self) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        while let Some(key) = state.in_transit.keys().next().cloned() {
+            if key.0 > now {
+                break;
+            }
+            let (destination, datagram) = match state.in_transit.remove(&key) {
+                Some(message) => message,
+                None => break,
+            };
+
+            // a partition also drops the messages which are still in transit
+            let delivered = state.can_reach(&datagram.source, &destination)
+                && match state.nodes.get(&destination) {
+                    Some(inbox) => inbox.send(datagram).is_ok(),
+                    None => false,
+                };
+            if delivered {
+                state.statistics.delivered += 1;
+            } else {
+                trace!("dropping a message sent to {destination}");
+                state.statistics.dropped += 1;
+            }
+        }
+    }
+}
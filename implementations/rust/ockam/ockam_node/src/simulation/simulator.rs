@@ -0,0 +1,178 @@
+use crate::simulation::{SimulatedClock, SimulatedNetwork, SimulatedTransport};
+use crate::tokio::runtime::{Builder, Runtime};
+use crate::tokio::time::Instant;
+use crate::{Context, NodeBuilder};
+use core::future::Future;
+use ockam_core::compat::rand::prelude::SeedableRng;
+use ockam_core::compat::rand::random;
+use ockam_core::compat::rand::rngs::StdRng;
+use ockam_core::compat::sync::Arc;
+use ockam_core::env::get_env;
+use ockam_core::Result;
+
+/// Seed of the simulations created with [`Simulation::from_env`].
+/// Set it to replay a simulation which was run with a random seed
+pub const OCKAM_SIMULATION_SEED: &str = "OCKAM_SIMULATION_SEED";
+
+/// Deterministic runtime for several nodes connected by a [`SimulatedNetwork`]
+///
+/// All the nodes of a simulation run on a single threaded runtime with a
+/// [`SimulatedClock`], and all the random decisions of the network are taken with
+/// a generator seeded with the seed of the simulation. Hence a simulation runs
+/// the same way every time it is started with the same seed, as long as its nodes
+/// don't depend on external resources, like a real network, other threads or a database.
+///
+/// The seed doesn't cover the values which are generated with the randomness of the
+/// operating system: the addresses created with [`Address::random_tagged`](ockam_core::Address::random_tagged), the
+/// flow control ids and the keys of the vaults differ from one run to the other.
+/// Tests must not depend on them, nor on their ordering.
+///
+/// Nodes using a database, like the ones storing identities for secure channels, wait
+/// for its answers on another thread. Since the clock jumps to the next timer when all
+/// the nodes are waiting, those simulations must call [`tokio::time::resume`](crate::tokio::time::resume) at the
+/// start of [`Simulation::run`] to follow the real time instead.
+///
+/// ```rust
+/// use ockam_core::Result;
+/// use ockam_node::simulation::{LinkConditions, Simulation};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<()> {
+/// let simulation = Simulation::new(42);
+/// simulation.network().set_default_conditions(
+///     LinkConditions::new()
+///         .with_latency(Duration::from_millis(50))
+///         .with_loss(0.1),
+/// );
+///
+/// simulation.run(async {
+///     let alice = simulation.create_node("alice").await?;
+///     let bob = simulation.create_node("bob").await?;
+///
+///     // send messages to bob via the returned address
+///     let _to_bob = alice.transport().connect("bob").await?;
+///
+///     alice.stop().await?;
+///     bob.stop().await
+/// })
+/// # }
+/// ```
+pub struct Simulation {
+    seed: u64,
+    runtime: Arc<Runtime>,
+    network: SimulatedNetwork,
+    clock: SimulatedClock,
+}
+
+impl Simulation {
+    /// Create a simulation with the given seed
+    pub fn new(seed: u64) -> Self {
+        let runtime = Arc::new(
+            Builder::new_current_thread()
+                .enable_time()
+                .start_paused(true)
+                .build()
+                .expect("cannot initialize the simulation runtime"),
+        );
+        let clock = SimulatedClock::new(runtime.block_on(async { Instant::now() }));
+
+        Self {
+            seed,
+            runtime,
+            network: SimulatedNetwork::new(seed),
+            clock,
+        }
+    }
+
+    /// Create a simulation with the seed set in the `OCKAM_SIMULATION_SEED` environment
+    /// variable, or with a random seed. The seed is logged so that the simulation can be replayed
+    pub fn from_env() -> Result<Self> {
+        let seed = get_env::<u64>(OCKAM_SIMULATION_SEED)?.unwrap_or_else(random);
+        info!("starting a simulation with the seed {seed}, set {OCKAM_SIMULATION_SEED}={seed} to replay it");
+        Ok(Self::new(seed))
+    }
+
+    /// Seed of the simulation
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Network connecting the nodes of the simulation
+    pub fn network(&self) -> &SimulatedNetwork {
+        &self.network
+    }
+
+    /// Virtual clock of the simulation
+    pub fn clock(&self) -> &SimulatedClock {
+        &self.clock
+    }
+
+    /// Runtime running the nodes of the simulation
+    pub fn runtime(&self) -> Arc<Runtime> {
+        self.runtime.clone()
+    }
+
+    /// Return a random number generator seeded with the seed of the simulation,
+    /// for the random decisions taken by a test
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed)
+    }
+
+    /// Run a future to completion on the simulation runtime.
+    ///
+    /// The nodes of the simulation only make progress while this function is running.
+    pub fn run<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Start a node and attach it to the network under the given name.
+    /// This function must be called from [`Simulation::run`]
+    pub async fn create_node(&self, name: &str) -> Result<SimulatedNode> {
+        let (ctx, mut executor) = NodeBuilder::new()
+            .no_exit_on_panic()
+            .with_runtime(self.runtime.clone())
+            .build();
+        let node_name = name.to_string();
+        self.runtime.spawn(async move {
+            if let Err(e) = executor.start_router().await {
+                error!("the router of the simulated node {node_name} failed: {e}");
+            }
+        });
+
+        let transport = SimulatedTransport::create(&ctx, &self.network, name).await?;
+        Ok(SimulatedNode { ctx, transport })
+    }
+}
+
+/// Node created by a [`Simulation`]
+pub struct SimulatedNode {
+    ctx: Context,
+    transport: SimulatedTransport,
+}
+
+impl SimulatedNode {
+    /// Name of the node on the network
+    pub fn name(&self) -> &str {
+        self.transport.name()
+    }
+
+    /// Root context of the node
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Mutable root context of the node, to receive messages
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.ctx
+    }
+
+    /// Transport attaching the node to the network
+    pub fn transport(&self) -> &SimulatedTransport {
+        &self.transport
+    }
+
+    /// Stop all the workers of the node, and detach it from the network
+    pub async fn stop(&self) -> Result<()> {
+        self.ctx.stop().await
+    }
+}
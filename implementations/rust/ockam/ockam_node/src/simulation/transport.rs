@@ -0,0 +1,147 @@
+use crate::simulation::workers::{NodeEndpoint, SimulatedReceiver};
+use crate::simulation::SimulatedNetwork;
+use crate::tokio::sync::mpsc::unbounded_channel;
+use crate::{Context, ProcessorBuilder};
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
+use ockam_core::{async_trait, Address, AsyncTryClone, DenyAll, Error, Result, TransportType};
+use ockam_transport_core::{Transport, TransportError};
+
+/// Transport type of the addresses of simulated nodes, for example: `(SIMULATION, "node name")`
+pub const SIMULATION: TransportType = TransportType::new(7);
+
+/// Transport attaching a node to a [`SimulatedNetwork`]
+///
+/// The messages are sent to another node via a sender worker, returned by
+/// [`SimulatedTransport::connect`]. The messages received from the network carry
+/// the address of the sender worker for their source node in their return route.
+///
+/// All the messages received by a node are produced under the same [`FlowControlId`],
+/// given by [`SimulatedTransport::flow_control_id`].
+#[derive(Clone)]
+pub struct SimulatedTransport {
+    ctx: Arc<Context>,
+    endpoint: NodeEndpoint,
+}
+
+impl SimulatedTransport {
+    /// Attach the node of `ctx` to the network, under the given name
+    pub async fn create(
+        ctx: &Context,
+        network: &SimulatedNetwork,
+        name: impl Into<String>,
+    ) -> Result<Self> {
+        let name = name.into();
+        let (sender, inbox) = unbounded_channel();
+        network.attach(&name, sender)?;
+
+        let endpoint = NodeEndpoint::new(
+            name.clone(),
+            network.clone(),
+            Address::random_tagged("SimulatedReceiver"),
+            FlowControls::generate_flow_control_id(),
+        );
+        ctx.flow_controls().add_producer(
+            endpoint.receiver_address.clone(),
+            &endpoint.flow_control_id,
+            None,
+            vec![],
+        );
+
+        let started = ProcessorBuilder::new(SimulatedReceiver::new(endpoint.clone(), inbox))
+            .with_address(endpoint.receiver_address.clone())
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(FlowControlOutgoingAccessControl::new(
+                ctx.flow_controls(),
+                endpoint.flow_control_id.clone(),
+                None,
+            ))
+            .start(ctx)
+            .await;
+        if let Err(e) = started {
+            network.detach(&name);
+            return Err(e);
+        }
+
+        let transport = Self {
+            ctx: Arc::new(ctx.async_try_clone().await?),
+            endpoint,
+        };
+        // make the transport available to resolve the (SIMULATION, "node name") addresses
+        ctx.register_transport(Arc::new(transport.clone()));
+        Ok(transport)
+    }
+
+    /// Name of the node on the network
+    pub fn name(&self) -> &str {
+        &self.endpoint.name
+    }
+
+    /// Network the node is attached to
+    pub fn network(&self) -> &SimulatedNetwork {
+        &self.endpoint.network
+    }
+
+    /// [`FlowControlId`] of the messages received from the network
+    pub fn flow_control_id(&self) -> &FlowControlId {
+        &self.endpoint.flow_control_id
+    }
+
+    /// Return the address of the worker sending messages to the node named `peer`
+    pub async fn connect(&self, peer: impl Into<String>) -> Result<Address> {
+        let peer = peer.into();
+        if !self.endpoint.network.is_attached(&peer) {
+            return Err(TransportError::PeerNotFound)?;
+        }
+        self.endpoint.sender(&self.ctx, &peer).await
+    }
+
+    /// Stop a sender worker returned by [`SimulatedTransport::connect`]
+    pub async fn disconnect(&self, address: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_worker(address).await
+    }
+
+    /// Addresses of the sender workers of this node
+    pub fn senders(&self) -> Vec<Address> {
+        self.endpoint.senders()
+    }
+
+    /// Detach the node from the network
+    pub async fn stop(&self) -> Result<()> {
+        for sender in self.senders() {
+            let _ = self.ctx.stop_worker(sender).await;
+        }
+        self.ctx
+            .stop_processor(self.endpoint.receiver_address.clone())
+            .await
+    }
+}
+
+#[async_trait]
+impl Transport for SimulatedTransport {
+    fn transport_type(&self) -> TransportType {
+        SIMULATION
+    }
+
+    async fn resolve_address(&self, address: Address) -> Result<Address> {
+        if address.transport_type() == SIMULATION {
+            self.connect(address.address().to_string()).await
+        } else {
+            Err(Error::new(
+                Origin::Transport,
+                Kind::NotFound,
+                format!(
+                    "this address can not be resolved by a simulated transport {}",
+                    address
+                ),
+            ))
+        }
+    }
+
+    async fn disconnect(&self, address: Address) -> Result<()> {
+        self.disconnect(address).await
+    }
+}
@@ -0,0 +1,185 @@
+use crate::simulation::network::Datagram;
+use crate::simulation::SimulatedNetwork;
+use crate::tokio::sync::mpsc::UnboundedReceiver;
+use crate::{Context, WorkerBuilder};
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+use ockam_core::flow_control::FlowControlId;
+use ockam_core::{
+    async_trait, Address, AllowAll, Any, DenyAll, Encodable, LocalMessage, Processor, Result,
+    Routed, TransportMessage, Worker,
+};
+use ockam_transport_core::{TransportError, MAXIMUM_MESSAGE_LENGTH};
+
+/// State shared by the transport and the workers of a simulated node
+#[derive(Clone)]
+pub(crate) struct NodeEndpoint {
+    pub(crate) name: String,
+    pub(crate) network: SimulatedNetwork,
+    pub(crate) receiver_address: Address,
+    pub(crate) flow_control_id: FlowControlId,
+    /// Address of the sender worker for each peer
+    senders: Arc<Mutex<BTreeMap<String, Address>>>,
+}
+
+impl NodeEndpoint {
+    pub(crate) fn new(
+        name: String,
+        network: SimulatedNetwork,
+        receiver_address: Address,
+        flow_control_id: FlowControlId,
+    ) -> Self {
+        Self {
+            name,
+            network,
+            receiver_address,
+            flow_control_id,
+            senders: Default::default(),
+        }
+    }
+
+    /// Return the address of the worker sending messages to `peer`, starting it if necessary
+    pub(crate) async fn sender(&self, ctx: &Context, peer: &str) -> Result<Address> {
+        let address = {
+            let mut senders = self.senders.lock().unwrap();
+            if let Some(address) = senders.get(peer) {
+                return Ok(address.clone());
+            }
+            // the address is registered before the worker is started so that it is
+            // started only once, even if a message is received from the peer in the meantime
+            let address = Address::random_tagged("SimulatedSender");
+            senders.insert(peer.to_string(), address.clone());
+            address
+        };
+
+        // Like for the other transports, the replies received from a peer can be sent
+        // to the workers which are consumers of the flow control of its sender
+        ctx.flow_controls().add_producer(
+            self.receiver_address.clone(),
+            &self.flow_control_id,
+            None,
+            vec![address.clone()],
+        );
+
+        let sender = SimulatedSender {
+            endpoint: self.clone(),
+            peer: peer.to_string(),
+        };
+        let started = WorkerBuilder::new(sender)
+            .with_address(address.clone())
+            .with_incoming_access_control(AllowAll)
+            .with_outgoing_access_control(DenyAll)
+            .terminal()
+            .start(ctx)
+            .await;
+
+        if let Err(e) = started {
+            self.remove_sender(peer);
+            return Err(e);
+        }
+        Ok(address)
+    }
+
+    /// Addresses of the sender workers
+    pub(crate) fn senders(&self) -> Vec<Address> {
+        self.senders.lock().unwrap().values().cloned().collect()
+    }
+
+    fn remove_sender(&self, peer: &str) {
+        self.senders.lock().unwrap().remove(peer);
+    }
+}
+
+/// Worker sending the messages routed to its address to a peer node
+///
+/// Its address is removed from the onward route of the messages
+/// before they are sent on the simulated network.
+struct SimulatedSender {
+    endpoint: NodeEndpoint,
+    peer: String,
+}
+
+#[async_trait]
+impl Worker for SimulatedSender {
+    type Message = Any;
+    type Context = Context;
+
+    async fn shutdown(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+        self.endpoint.remove_sender(&self.peer);
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let local_message = msg.into_local_message().pop_front_onward_route()?;
+        let payload = local_message
+            .into_transport_message()
+            .encode()
+            .map_err(|_| TransportError::SendBadMessage)?;
+        if payload.len() > MAXIMUM_MESSAGE_LENGTH {
+            return Err(TransportError::Capacity)?;
+        }
+
+        self.endpoint
+            .network
+            .send(&self.endpoint.name, &self.peer, payload);
+        Ok(())
+    }
+}
+
+/// Processor forwarding the messages received by a node from the simulated network
+///
+/// The address of the sender worker for the peer sending a message is added to its
+/// return route so that a reply can be routed back.
+pub(crate) struct SimulatedReceiver {
+    endpoint: NodeEndpoint,
+    inbox: UnboundedReceiver<Datagram>,
+}
+
+impl SimulatedReceiver {
+    pub(crate) fn new(endpoint: NodeEndpoint, inbox: UnboundedReceiver<Datagram>) -> Self {
+        Self { endpoint, inbox }
+    }
+}
+
+#[async_trait]
+impl Processor for SimulatedReceiver {
+    type Context = Context;
+
+    async fn shutdown(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+        self.endpoint.network.detach(&self.endpoint.name);
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
+        let Datagram { source, payload } = match self.inbox.recv().await {
+            Some(datagram) => datagram,
+            None => return Ok(false),
+        };
+
+        let transport_message = match TransportMessage::decode_message(payload) {
+            Ok(transport_message) => transport_message,
+            Err(e) => {
+                warn!("cannot decode a message received from {source}: {e}");
+                return Ok(true);
+            }
+        };
+
+        let sender = self.endpoint.sender(ctx, &source).await?;
+        let local_message = LocalMessage::from_transport_message(transport_message)
+            .push_front_return_route(&sender);
+        if !local_message.has_next_on_onward_route() {
+            trace!("dropping a message without onward route received from {source}");
+            return Ok(true);
+        }
+
+        if let Err(e) = ctx
+            .forward_from_address(local_message, self.endpoint.receiver_address.clone())
+            .await
+        {
+            debug!("cannot forward a message received from {source}: {e}");
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,12 @@
+-- This table stores the subjects revoked by an authority.
+-- On an authority node it is the source of the signed revocation list,
+-- on other nodes it caches the latest revocation list retrieved from the authority
+CREATE TABLE revoked_subject
+(
+    subject_identifier TEXT    NOT NULL, -- Identifier of the revoked subject
+    issuer_identifier  TEXT    NOT NULL, -- Identifier of the authority which revoked the subject
+    revoked_at         INTEGER NOT NULL, -- Revocation time
+    node_name          TEXT    NOT NULL  -- node name to isolate revocations that each node has
+);
+
+CREATE UNIQUE INDEX revoked_subject_issuer_subject_index ON revoked_subject (issuer_identifier, subject_identifier, node_name);
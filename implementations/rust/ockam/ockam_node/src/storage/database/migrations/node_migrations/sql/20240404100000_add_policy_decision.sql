@@ -0,0 +1,17 @@
+-- This table stores the decisions taken when evaluating policies on a node.
+-- It provides an audit trail of the access granted or denied to resources
+CREATE TABLE policy_decision
+(
+    timestamp     INTEGER NOT NULL, -- Time of the decision
+    identifier    TEXT,             -- Identifier of the subject, if one could be found
+    resource_name TEXT    NOT NULL, -- Name of the accessed resource
+    resource_type TEXT    NOT NULL, -- Type of the accessed resource
+    action        TEXT    NOT NULL, -- Action performed on the resource
+    expression    TEXT,             -- Evaluated policy expression, if a policy exists
+    attributes    BLOB    NOT NULL, -- CBOR map of the attributes consulted by the policy
+    is_authorized INTEGER NOT NULL, -- 1 if the access was granted
+    reason        TEXT    NOT NULL, -- Explanation of the decision
+    node_name     TEXT    NOT NULL  -- node name to isolate the decisions taken by each node
+);
+
+CREATE INDEX policy_decision_node_name_index ON policy_decision (node_name, timestamp);
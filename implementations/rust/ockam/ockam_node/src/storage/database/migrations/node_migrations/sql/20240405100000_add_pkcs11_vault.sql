@@ -0,0 +1,4 @@
+-- Vaults can store their signing keys in a PKCS#11 token.
+-- In that case only the key handles are stored in the database, and the columns below are not null
+ALTER TABLE vault ADD COLUMN pkcs11_module TEXT;      -- path of the PKCS#11 module used to access the token
+ALTER TABLE vault ADD COLUMN pkcs11_token_label TEXT; -- label of the token storing the signing keys
@@ -0,0 +1,13 @@
+-- Secrets can be encrypted at rest with a data encryption key.
+-- That key is stored here, encrypted with a key derived from a passphrase or supplied by the user.
+-- There is at most one row in this table. When it is present all the secrets in the
+-- signing_secret and x25519_secret tables are encrypted
+CREATE TABLE secrets_encryption
+(
+    kdf         TEXT NOT NULL, -- key derivation function used to obtain the key encryption key: 'argon2id' or 'raw'
+    salt        BLOB,          -- salt used by the key derivation function, if any
+    wrapped_key BLOB NOT NULL  -- data encryption key, encrypted with the key encryption key
+);
+
+-- Vaults can have their secrets encrypted at rest
+ALTER TABLE vault ADD COLUMN is_encrypted INTEGER DEFAULT 0;
@@ -0,0 +1,14 @@
+-- This table stores the messages of the store-and-forward message queues of a node.
+-- A message is deleted once its delivery has been acknowledged by the destination
+CREATE TABLE queued_message
+(
+    message_id      INTEGER PRIMARY KEY AUTOINCREMENT, -- Identifier of the message, never reused
+    queue_name      TEXT    NOT NULL,                  -- Name of the queue storing the message
+    payload         BLOB    NOT NULL,                  -- Payload to deliver to the destination
+    enqueued_at     INTEGER NOT NULL,                  -- Time when the message was stored
+    attempts        INTEGER NOT NULL,                  -- Number of delivery attempts so far
+    next_attempt_at INTEGER NOT NULL,                  -- Time of the next delivery attempt
+    node_name       TEXT    NOT NULL                   -- Node name to isolate the queues of each node
+);
+
+CREATE INDEX queued_message_queue_index ON queued_message (node_name, queue_name, next_attempt_at);
@@ -0,0 +1,7 @@
+-- This table stores the private key of the self-signed certificate served by the TLS inlets of a node,
+-- so that the certificate stays the same when the node is restarted
+CREATE TABLE node_tls_key
+(
+    node_name   TEXT PRIMARY KEY, -- Name of the node
+    private_key TEXT NOT NULL     -- PEM-encoded private key
+);
@@ -0,0 +1,363 @@
+use crate::channel_types::SmallReceiver;
+use crate::relay::CtrlSignal;
+use core::cell::Cell;
+use core::fmt;
+use core::future::{poll_fn, Future};
+use core::panic::AssertUnwindSafe;
+use core::pin::pin;
+use core::time::Duration;
+use futures::FutureExt;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::collections::VecDeque;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::{Error, Result};
+use std::any::Any;
+use std::time::Instant;
+
+/// Default maximum number of restarts, see [`RestartPolicy::with_max_restarts`]
+const DEFAULT_MAX_RESTARTS: usize = 3;
+
+/// Default period of the restart intensity, see [`RestartPolicy::with_max_restarts`]
+const DEFAULT_RESTARTS_PERIOD: Duration = Duration::from_secs(5);
+
+/// Default delays before a restart, see [`RestartPolicy::with_backoff`]
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// What is restarted when a supervised worker or processor fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Only the failed worker or processor is restarted
+    OneForOne,
+    /// The failed worker or processor is restarted, as well as the other supervised
+    /// workers and processors of its cluster, see
+    /// [`Context::set_cluster`](crate::Context::set_cluster)
+    OneForAll,
+}
+
+/// Automatic restart of a worker or processor, set with
+/// [`WorkerBuilder`](crate::WorkerBuilder) or [`ProcessorBuilder`](crate::ProcessorBuilder).
+///
+/// A supervised worker is restarted when it panics or when its initialization fails,
+/// and optionally when it returns an error. The failed instance is shut down and replaced
+/// with a clone of the instance given to the builder, which is then initialized again.
+/// The restarted worker keeps its addresses, its access controls and its pending messages.
+///
+/// If the worker fails more than `max_restarts` times within `period`,
+/// it is stopped for good.
+///
+/// The panics of a supervised worker are recovered even on nodes which exit on panic,
+/// see [`NodeBuilder::no_exit_on_panic`](crate::NodeBuilder::no_exit_on_panic).
+///
+/// ```rust
+/// use core::time::Duration;
+/// use ockam_node::{Context, RestartPolicy, WorkerBuilder};
+/// # use ockam_core::{Result, Worker, Routed, async_trait};
+/// # #[derive(Clone)]
+/// # struct MyWorker;
+/// # #[async_trait]
+/// # impl Worker for MyWorker {
+/// #     type Message = String;
+/// #     type Context = Context;
+/// #     async fn handle_message(&mut self, _: &mut Context, _: Routed<String>) -> Result<()> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// # async fn test(ctx: &Context) -> Result<()> {
+/// WorkerBuilder::new(MyWorker)
+///     .with_address("my_worker")
+///     .with_restart_policy(
+///         RestartPolicy::one_for_one()
+///             .with_max_restarts(5, Duration::from_secs(60))
+///             .with_backoff(Duration::from_millis(50), Duration::from_secs(5)),
+///     )
+///     .start(ctx)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RestartPolicy {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    period: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    restart_on_error: bool,
+}
+
+impl RestartPolicy {
+    fn new(strategy: RestartStrategy) -> Self {
+        Self {
+            strategy,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            period: DEFAULT_RESTARTS_PERIOD,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            restart_on_error: false,
+        }
+    }
+
+    /// Only restart the failed worker or processor
+    pub fn one_for_one() -> Self {
+        Self::new(RestartStrategy::OneForOne)
+    }
+
+    /// Restart the failed worker or processor and the other supervised
+    /// members of its cluster
+    pub fn one_for_all() -> Self {
+        Self::new(RestartStrategy::OneForAll)
+    }
+
+    /// Stop the worker for good if it fails more than `max_restarts` times within `period`.
+    /// The default is 3 restarts within 5 seconds
+    pub fn with_max_restarts(mut self, max_restarts: usize, period: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.period = period;
+        self
+    }
+
+    /// Wait `initial` before the first restart, and double that delay for each
+    /// subsequent restart within the restart period, up to `max`.
+    /// The default is 100 milliseconds, up to 10 seconds
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Also restart the worker when it returns an error from `handle_message` or `process`.
+    /// By default these errors are only logged
+    pub fn with_restart_on_error(mut self) -> Self {
+        self.restart_on_error = true;
+        self
+    }
+
+    /// The restart strategy
+    pub fn strategy(&self) -> RestartStrategy {
+        self.strategy
+    }
+}
+
+/// Failure of a worker or processor
+#[derive(Debug)]
+pub(crate) enum Failure {
+    /// An error was returned
+    Error(Error),
+    /// The worker panicked
+    Panic(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Error(e) => write!(f, "{}", e),
+            Failure::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// Run a future, turning a panic into a [`Failure`].
+///
+/// The future of a supervised worker or processor is marked as such while it runs,
+/// so that its panics don't exit the process, see [`is_supervised`]
+pub(crate) async fn catch_failure<T>(
+    future: impl Future<Output = Result<T>>,
+    supervised: bool,
+) -> Result<T, Failure> {
+    let result = if supervised {
+        AssertUnwindSafe(run_supervised(future))
+            .catch_unwind()
+            .await
+    } else {
+        AssertUnwindSafe(future).catch_unwind().await
+    };
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(Failure::Error(e)),
+        Err(panic) => Err(Failure::Panic(panic_message(panic))),
+    }
+}
+
+thread_local! {
+    /// True while the future of a supervised worker or processor is polled on this thread
+    static SUPERVISED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Return true if a panic raised now on this thread is recovered by a supervisor.
+///
+/// The panic hook of a node built with exit on panic uses it to only exit the process
+/// for the panics which can't be recovered
+pub(crate) fn is_supervised() -> bool {
+    SUPERVISED.with(|supervised| supervised.get())
+}
+
+/// Mark the current thread as running a supervised future until it is dropped,
+/// including when the future panics
+struct SupervisedGuard {
+    previous: bool,
+}
+
+impl SupervisedGuard {
+    fn new() -> Self {
+        Self {
+            previous: SUPERVISED.with(|supervised| supervised.replace(true)),
+        }
+    }
+}
+
+impl Drop for SupervisedGuard {
+    fn drop(&mut self) {
+        SUPERVISED.with(|supervised| supervised.set(self.previous));
+    }
+}
+
+async fn run_supervised<T>(future: impl Future<Output = T>) -> T {
+    let mut future = pin!(future);
+    poll_fn(|cx| {
+        let _guard = SupervisedGuard::new();
+        future.as_mut().poll(cx)
+    })
+    .await
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Wait before restarting a worker or processor.
+/// Return false if it was signaled to stop in the meantime
+pub(crate) async fn wait_before_restart(
+    delay: Duration,
+    ctrl_rx: &mut SmallReceiver<CtrlSignal>,
+) -> bool {
+    let sleep = tokio::time::sleep(delay);
+    tokio::pin!(sleep);
+    loop {
+        tokio::select! {
+            _ = &mut sleep => return true,
+            signal = ctrl_rx.recv() => match signal {
+                // We are already restarting
+                Some(CtrlSignal::Restart) => {}
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Restart state of a supervised worker or processor
+pub(crate) struct Supervisor<T> {
+    policy: RestartPolicy,
+    factory: Box<dyn Fn() -> T + Send>,
+    restarts: VecDeque<Instant>,
+}
+
+impl<T> Supervisor<T> {
+    /// Create a supervisor restarting clones of the given instance
+    pub(crate) fn new(policy: RestartPolicy, instance: T) -> Self
+    where
+        T: Clone + Send + 'static,
+    {
+        Self {
+            policy,
+            factory: Box::new(move || instance.clone()),
+            restarts: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn strategy(&self) -> RestartStrategy {
+        self.policy.strategy
+    }
+
+    /// Return true if this failure of the running worker must trigger a restart
+    pub(crate) fn restarts_on(&self, failure: &Failure) -> bool {
+        match failure {
+            Failure::Error(_) => self.policy.restart_on_error,
+            Failure::Panic(_) => true,
+        }
+    }
+
+    /// Record a restart and return the delay to wait before it,
+    /// or `None` if the maximum restart intensity is reached
+    pub(crate) fn next_restart(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        while let Some(restart) = self.restarts.front() {
+            if now.duration_since(*restart) > self.policy.period {
+                self.restarts.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.restarts.len() >= self.policy.max_restarts {
+            return None;
+        }
+        self.restarts.push_back(now);
+
+        let exponent = (self.restarts.len() - 1).min(16) as u32;
+        let backoff = self.policy.initial_backoff.saturating_mul(1 << exponent);
+        Some(backoff.min(self.policy.max_backoff))
+    }
+
+    /// Create a fresh instance of the supervised worker
+    pub(crate) fn new_instance(&self) -> T {
+        (self.factory)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_restart() {
+        let policy = RestartPolicy::one_for_one()
+            .with_max_restarts(3, Duration::from_secs(60))
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(25));
+        let mut supervisor = Supervisor::new(policy, ());
+
+        assert_eq!(supervisor.next_restart(), Some(Duration::from_millis(10)));
+        assert_eq!(supervisor.next_restart(), Some(Duration::from_millis(20)));
+        assert_eq!(supervisor.next_restart(), Some(Duration::from_millis(25)));
+        assert_eq!(supervisor.next_restart(), None);
+    }
+
+    #[test]
+    fn test_restarts_on() {
+        let supervisor = Supervisor::new(RestartPolicy::one_for_one(), ());
+        assert!(supervisor.restarts_on(&Failure::Panic("panic".to_string())));
+        assert!(!supervisor.restarts_on(&Failure::Error(crate::NodeError::Data.internal())));
+
+        let supervisor = Supervisor::new(RestartPolicy::one_for_all().with_restart_on_error(), ());
+        assert!(supervisor.restarts_on(&Failure::Error(crate::NodeError::Data.internal())));
+        assert_eq!(supervisor.strategy(), RestartStrategy::OneForAll);
+    }
+
+    #[tokio::test]
+    async fn test_catch_failure() {
+        let result = catch_failure(async { Ok(1) }, false).await;
+        assert!(matches!(result, Ok(1)));
+
+        let result: Result<(), Failure> = catch_failure(async { panic!("boom") }, false).await;
+        assert!(matches!(result, Err(Failure::Panic(message)) if message == "boom"));
+    }
+
+    #[tokio::test]
+    async fn test_supervised_futures_are_marked() {
+        let result = catch_failure(async { Ok(is_supervised()) }, true).await;
+        assert!(matches!(result, Ok(true)));
+        let result = catch_failure(async { Ok(is_supervised()) }, false).await;
+        assert!(matches!(result, Ok(false)));
+
+        // The mark is removed when a supervised future panics
+        let result: Result<(), Failure> = catch_failure(async { panic!("boom") }, true).await;
+        assert!(matches!(result, Err(Failure::Panic(_))));
+        assert!(!is_supervised());
+    }
+}
@@ -1,5 +1,8 @@
+use crate::channel_types::{OverflowPolicy, DEFAULT_MAILBOX_CAPACITY};
 use crate::debugger;
 use crate::error::{NodeError, NodeReason};
+#[cfg(feature = "std")]
+use crate::supervisor::{RestartPolicy, Supervisor};
 use crate::{relay::WorkerRelay, Context, NodeMessage};
 use alloc::string::String;
 use ockam_core::compat::{sync::Arc, vec::Vec};
@@ -42,6 +45,10 @@ where
             worker: self.worker,
             address: address.into(),
             metadata: None,
+            mailbox_capacity: DEFAULT_MAILBOX_CAPACITY,
+            overflow_policy: OverflowPolicy::default(),
+            #[cfg(feature = "std")]
+            supervisor: None,
         }
     }
 
@@ -51,6 +58,10 @@ where
             mailboxes,
             worker: self.worker,
             metadata_list: vec![],
+            mailbox_capacity: DEFAULT_MAILBOX_CAPACITY,
+            overflow_policy: OverflowPolicy::default(),
+            #[cfg(feature = "std")]
+            supervisor: None,
         }
     }
 }
@@ -62,6 +73,10 @@ where
     mailboxes: Mailboxes,
     worker: W,
     metadata_list: Vec<AddressAndMetadata>,
+    mailbox_capacity: usize,
+    overflow_policy: OverflowPolicy,
+    #[cfg(feature = "std")]
+    supervisor: Option<Supervisor<W>>,
 }
 
 impl<W> WorkerBuilderMultipleAddresses<W>
@@ -124,9 +139,43 @@ where
         self
     }
 
+    /// Set the number of messages the mailbox of the worker can hold,
+    /// the default is [`DEFAULT_MAILBOX_CAPACITY`]
+    pub fn with_mailbox_capacity(mut self, mailbox_capacity: usize) -> Self {
+        self.mailbox_capacity = mailbox_capacity;
+        self
+    }
+
+    /// Set what happens to the messages sent when the mailbox of the worker is full,
+    /// the default is [`OverflowPolicy::Block`]
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Restart the worker when it fails, see [`RestartPolicy`]
+    #[cfg(feature = "std")]
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self
+    where
+        W: Clone,
+    {
+        self.supervisor = Some(Supervisor::new(policy, self.worker.clone()));
+        self
+    }
+
     /// Consume this builder and start a new Ockam [`Worker`] from the given context
     pub async fn start(self, context: &Context) -> Result<()> {
-        start(context, self.mailboxes, self.worker, self.metadata_list).await
+        start(
+            context,
+            self.mailboxes,
+            self.worker,
+            self.metadata_list,
+            self.mailbox_capacity,
+            self.overflow_policy,
+            #[cfg(feature = "std")]
+            self.supervisor,
+        )
+        .await
     }
 }
 
@@ -139,6 +188,10 @@ where
     address: Address,
     worker: W,
     metadata: Option<AddressAndMetadata>,
+    mailbox_capacity: usize,
+    overflow_policy: OverflowPolicy,
+    #[cfg(feature = "std")]
+    supervisor: Option<Supervisor<W>>,
 }
 
 impl<W> WorkerBuilderOneAddress<W>
@@ -190,6 +243,30 @@ where
         self
     }
 
+    /// Set the number of messages the mailbox of the worker can hold,
+    /// the default is [`DEFAULT_MAILBOX_CAPACITY`]
+    pub fn with_mailbox_capacity(mut self, mailbox_capacity: usize) -> Self {
+        self.mailbox_capacity = mailbox_capacity;
+        self
+    }
+
+    /// Set what happens to the messages sent when the mailbox of the worker is full,
+    /// the default is [`OverflowPolicy::Block`]
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Restart the worker when it fails, see [`RestartPolicy`]
+    #[cfg(feature = "std")]
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self
+    where
+        W: Clone,
+    {
+        self.supervisor = Some(Supervisor::new(policy, self.worker.clone()));
+        self
+    }
+
     /// Consume this builder and start a new Ockam [`Worker`] from the given context
     pub async fn start(self, context: &Context) -> Result<()> {
         start(
@@ -197,6 +274,10 @@ where
             Mailboxes::main(self.address, self.incoming_ac, self.outgoing_ac),
             self.worker,
             self.metadata.map(|m| vec![m]).unwrap_or_default(),
+            self.mailbox_capacity,
+            self.overflow_policy,
+            #[cfg(feature = "std")]
+            self.supervisor,
         )
         .await
     }
@@ -249,6 +330,9 @@ async fn start<W>(
     mailboxes: Mailboxes,
     worker: W,
     metadata: Vec<AddressAndMetadata>,
+    mailbox_capacity: usize,
+    overflow_policy: OverflowPolicy,
+    #[cfg(feature = "std")] supervisor: Option<Supervisor<W>>,
 ) -> Result<()>
 where
     W: Worker<Context = Context>,
@@ -263,13 +347,14 @@ where
     let addresses = mailboxes.addresses();
 
     // Pass it to the context
-    let (ctx, sender, ctrl_rx) = context.copy_with_mailboxes(mailboxes);
+    let (ctx, sender, ctrl_rx) =
+        context.copy_with_mailboxes_and_capacity(mailboxes, mailbox_capacity, overflow_policy);
 
     debugger::log_inherit_context("WORKER", context, &ctx);
 
     // Send start request to router
     let (msg, mut rx) =
-        NodeMessage::start_worker(addresses, sender, false, context.mailbox_count(), metadata);
+        NodeMessage::start_worker(addresses, sender, false, ctx.mailbox_counter(), metadata);
     context
         .sender()
         .send(msg)
@@ -282,7 +367,14 @@ where
         .ok_or_else(|| NodeError::NodeState(NodeReason::Unknown).internal())??;
 
     // Then initialise the worker message relay
-    WorkerRelay::init(context.runtime(), worker, ctx, ctrl_rx);
+    WorkerRelay::init(
+        context.runtime(),
+        worker,
+        ctx,
+        ctrl_rx,
+        #[cfg(feature = "std")]
+        supervisor,
+    );
 
     Ok(())
 }
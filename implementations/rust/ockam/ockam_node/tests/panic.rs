@@ -0,0 +1,60 @@
+//! The tests of this file run in their own process: a node built with exit on panic
+//! installs a process-wide panic hook, which would exit on the panics raised here.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::time::Duration;
+use ockam_core::compat::{boxed::Box, string::String, sync::Arc};
+use ockam_core::{async_trait, Address, Result, Routed, Worker};
+use ockam_node::Context;
+
+struct PanickingWorker {
+    initialize_count: Arc<AtomicU32>,
+}
+
+#[async_trait]
+impl Worker for PanickingWorker {
+    type Context = Context;
+    type Message = String;
+
+    async fn initialize(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+        self.initialize_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        _ctx: &mut Self::Context,
+        _msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        panic!("the worker was asked to panic");
+    }
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn unsupervised_worker__panic_during_handling__worker_stopped(
+    ctx: &mut Context,
+) -> Result<()> {
+    let initialize_count = Arc::new(AtomicU32::new(0));
+    let address = Address::from_string("unsupervised");
+    ctx.start_worker(
+        address.clone(),
+        PanickingWorker {
+            initialize_count: initialize_count.clone(),
+        },
+    )
+    .await?;
+
+    ctx.send(address.clone(), "panic".to_string()).await?;
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while ctx.list_workers().await?.contains(&address) {
+            ctx.sleep(Duration::from_millis(10)).await;
+        }
+        Result::<()>::Ok(())
+    })
+    .await
+    .expect("the worker should be stopped before the timeout")?;
+    assert_eq!(1, initialize_count.load(Ordering::Relaxed));
+
+    Ok(())
+}
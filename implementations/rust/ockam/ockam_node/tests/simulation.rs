@@ -0,0 +1,157 @@
+use core::time::Duration;
+use ockam_core::{async_trait, route, Result, Routed, Worker};
+use ockam_node::simulation::{LinkConditions, SimulatedNode, Simulation};
+use ockam_node::{Context, MessageReceiveOptions, MessageSendReceiveOptions};
+
+struct Echoer;
+
+#[async_trait]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.into_body()?).await
+    }
+}
+
+/// Start an echoer on a node, accepting the messages received from the network
+async fn start_echoer(node: &SimulatedNode) -> Result<()> {
+    node.context()
+        .flow_controls()
+        .add_consumer("echoer", node.transport().flow_control_id());
+    node.context().start_worker("echoer", Echoer).await
+}
+
+#[test]
+fn messages_are_delayed_by_the_link_latency() -> Result<()> {
+    let simulation = Simulation::new(0);
+    simulation
+        .network()
+        .set_default_conditions(LinkConditions::new().with_latency(Duration::from_millis(100)));
+
+    simulation.run(async {
+        let alice = simulation.create_node("alice").await?;
+        let bob = simulation.create_node("bob").await?;
+        start_echoer(&bob).await?;
+
+        let to_bob = alice.transport().connect("bob").await?;
+        let start = simulation.clock().elapsed();
+        let reply: String = alice
+            .context()
+            .send_and_receive(route![to_bob, "echoer"], "hello".to_string())
+            .await?;
+        let elapsed = simulation.clock().elapsed() - start;
+
+        assert_eq!(reply, "hello");
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(1));
+
+        let statistics = simulation.network().statistics();
+        assert_eq!(statistics.sent(), 2);
+        assert_eq!(statistics.delivered(), 2);
+
+        alice.stop().await?;
+        bob.stop().await
+    })
+}
+
+#[test]
+fn partitioned_nodes_cannot_reach_each_other() -> Result<()> {
+    let simulation = Simulation::new(0);
+
+    simulation.run(async {
+        let alice = simulation.create_node("alice").await?;
+        let bob = simulation.create_node("bob").await?;
+        start_echoer(&bob).await?;
+        let to_bob = alice.transport().connect("bob").await?;
+
+        simulation.network().partition(&["alice"], &["bob"]);
+        assert!(!simulation.network().can_reach("bob", "alice"));
+        let result = alice
+            .context()
+            .send_and_receive_extended::<String>(
+                route![to_bob.clone(), "echoer"],
+                "hello".to_string(),
+                MessageSendReceiveOptions::new().with_timeout(Duration::from_secs(5)),
+            )
+            .await;
+        assert!(result.is_err());
+        assert_eq!(simulation.network().statistics().dropped(), 1);
+
+        simulation.network().heal();
+        let reply: String = alice
+            .context()
+            .send_and_receive(route![to_bob, "echoer"], "hello".to_string())
+            .await?;
+        assert_eq!(reply, "hello");
+
+        alice.stop().await?;
+        bob.stop().await
+    })
+}
+
+#[test]
+fn a_simulation_is_reproducible_with_the_same_seed() -> Result<()> {
+    let first_run = receive_numbers(7)?;
+    let second_run = receive_numbers(7)?;
+    assert_eq!(first_run, second_run);
+
+    // some messages are lost and the other ones are reordered
+    let mut sorted = first_run.clone();
+    sorted.sort_unstable();
+    assert!(first_run.len() < 20);
+    assert_ne!(first_run, sorted);
+    Ok(())
+}
+
+/// Send 20 numbers from a node to another one over a lossy link with some jitter,
+/// and return the numbers received, in order
+fn receive_numbers(seed: u64) -> Result<Vec<u32>> {
+    let simulation = Simulation::new(seed);
+    simulation.network().set_default_conditions(
+        LinkConditions::new()
+            .with_latency(Duration::from_millis(10))
+            .with_jitter(Duration::from_millis(50))
+            .with_loss(0.3),
+    );
+
+    simulation.run(async {
+        let alice = simulation.create_node("alice").await?;
+        let mut bob = simulation.create_node("bob").await?;
+        bob.context()
+            .flow_controls()
+            .add_consumer(bob.context().address(), bob.transport().flow_control_id());
+
+        let to_bob = alice.transport().connect("bob").await?;
+        for n in 0..20u32 {
+            alice
+                .context()
+                .send(
+                    route![to_bob.clone(), bob.context().address()],
+                    n.to_string(),
+                )
+                .await?;
+        }
+
+        let mut received = vec![];
+        while let Ok(msg) = bob
+            .context_mut()
+            .receive_extended::<String>(
+                MessageReceiveOptions::new().with_timeout(Duration::from_secs(1)),
+            )
+            .await
+        {
+            received.push(msg.into_body()?.parse::<u32>().unwrap());
+        }
+
+        let statistics = simulation.network().statistics();
+        assert_eq!(statistics.sent(), 20);
+        assert_eq!(statistics.delivered(), received.len() as u64);
+        assert_eq!(statistics.delivered() + statistics.dropped(), 20);
+
+        alice.stop().await?;
+        bob.stop().await?;
+        Ok(received)
+    })
+}
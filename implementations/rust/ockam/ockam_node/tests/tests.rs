@@ -8,8 +8,9 @@ use ockam_core::compat::{
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{async_trait, Address, AllowAll, Any, Decodable, DenyAll, Message, LOCAL};
 use ockam_core::{route, Processor, Result, Routed, Worker};
+use ockam_node::channel_types::OverflowPolicy;
 use ockam_node::compat::futures::FutureExt;
-use ockam_node::{Context, MessageReceiveOptions, NodeBuilder};
+use ockam_node::{Context, MessageReceiveOptions, NodeBuilder, RestartPolicy, WorkerBuilder};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicI8, AtomicU32};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -549,8 +550,8 @@ async fn worker_calls_stopworker_from_handlemessage(ctx: &mut Context) -> Result
             h.await.unwrap();
         }
     }
-    // Wait till tokio Runtime is shut down
-    std::thread::sleep(Duration::new(1, 0));
+    // Wait till all the workers have handled their message
+    ctx.sleep(Duration::new(1, 0)).await;
 
     // Assert all handle_message() entry and exit counts match
     assert_eq!(
@@ -691,3 +692,236 @@ async fn message_handle__error_during_handling__keep_worker_running(
 
     Ok(())
 }
+
+#[derive(Clone)]
+struct PanickingWorker {
+    initialize_count: Arc<AtomicU32>,
+}
+
+#[async_trait]
+impl Worker for PanickingWorker {
+    type Context = Context;
+    type Message = String;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        ctx.set_cluster("supervised").await?;
+        self.initialize_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let return_route = msg.return_route();
+        let body = msg.into_body()?;
+        if body == "panic" {
+            panic!("the worker was asked to panic");
+        }
+        ctx.send(return_route, body).await
+    }
+}
+
+fn restart_policy(policy: RestartPolicy) -> RestartPolicy {
+    policy.with_backoff(Duration::from_millis(10), Duration::from_millis(10))
+}
+
+/// Wait until a condition holds, failing after 5 seconds
+async fn wait_until(ctx: &Context, condition: impl Fn() -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !condition() {
+            ctx.sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the condition should hold before the timeout")
+}
+
+/// Wait until a worker is stopped, failing after 5 seconds
+async fn wait_for_stop(ctx: &Context, address: &Address) -> Result<()> {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while ctx.list_workers().await?.contains(address) {
+            ctx.sleep(Duration::from_millis(10)).await;
+        }
+        Result::<()>::Ok(())
+    })
+    .await
+    .expect("the worker should be stopped before the timeout")
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn supervised_worker__panic_during_handling__worker_restarted(
+    ctx: &mut Context,
+) -> Result<()> {
+    let initialize_count = Arc::new(AtomicU32::new(0));
+    WorkerBuilder::new(PanickingWorker {
+        initialize_count: initialize_count.clone(),
+    })
+    .with_address("supervised")
+    .with_restart_policy(restart_policy(RestartPolicy::one_for_one()))
+    .start(ctx)
+    .await?;
+    ctx.wait_for("supervised").await?;
+    assert_eq!(1, initialize_count.load(Ordering::Relaxed));
+
+    ctx.send("supervised", "panic".to_string()).await?;
+    ctx.send("supervised", "hello".to_string()).await?;
+    let reply = ctx.receive::<String>().await?.into_body()?;
+    assert_eq!(reply, "hello");
+    assert_eq!(2, initialize_count.load(Ordering::Relaxed));
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn supervised_worker__panic_on_node_exiting_on_panic__worker_restarted() {
+    let (ctx, mut executor) = NodeBuilder::new().build();
+    executor
+        .execute(async move {
+            let mut ctx = ctx;
+            let initialize_count = Arc::new(AtomicU32::new(0));
+            WorkerBuilder::new(PanickingWorker {
+                initialize_count: initialize_count.clone(),
+            })
+            .with_address("supervised_on_exit")
+            .with_restart_policy(restart_policy(RestartPolicy::one_for_one()))
+            .start(&ctx)
+            .await?;
+
+            // The process would exit here if the panic was not recovered
+            ctx.send("supervised_on_exit", "panic".to_string()).await?;
+            ctx.send("supervised_on_exit", "hello".to_string()).await?;
+            let reply = ctx.receive::<String>().await?.into_body()?;
+            assert_eq!(reply, "hello");
+            assert_eq!(2, initialize_count.load(Ordering::Relaxed));
+
+            ctx.stop().await
+        })
+        .unwrap()
+        .unwrap()
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn supervised_workers__one_for_all__cluster_restarted(ctx: &mut Context) -> Result<()> {
+    let failing_count = Arc::new(AtomicU32::new(0));
+    let other_count = Arc::new(AtomicU32::new(0));
+    for (address, initialize_count) in [("failing", &failing_count), ("other", &other_count)] {
+        WorkerBuilder::new(PanickingWorker {
+            initialize_count: initialize_count.clone(),
+        })
+        .with_address(address)
+        .with_restart_policy(restart_policy(RestartPolicy::one_for_all()))
+        .start(ctx)
+        .await?;
+        ctx.wait_for(address).await?;
+    }
+
+    ctx.send("failing", "panic".to_string()).await?;
+    wait_until(ctx, || {
+        failing_count.load(Ordering::Relaxed) == 2 && other_count.load(Ordering::Relaxed) == 2
+    })
+    .await;
+
+    ctx.send("other", "hello".to_string()).await?;
+    let reply = ctx.receive::<String>().await?.into_body()?;
+    assert_eq!(reply, "hello");
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn supervised_worker__too_many_restarts__worker_stopped(ctx: &mut Context) -> Result<()> {
+    let initialize_count = Arc::new(AtomicU32::new(0));
+    let address = Address::from_string("fragile");
+    WorkerBuilder::new(PanickingWorker {
+        initialize_count: initialize_count.clone(),
+    })
+    .with_address(address.clone())
+    .with_restart_policy(
+        restart_policy(RestartPolicy::one_for_one()).with_max_restarts(1, Duration::from_secs(60)),
+    )
+    .start(ctx)
+    .await?;
+
+    ctx.send(address.clone(), "panic".to_string()).await?;
+    ctx.send(address.clone(), "panic".to_string()).await?;
+    wait_for_stop(ctx, &address).await?;
+    assert_eq!(2, initialize_count.load(Ordering::Relaxed));
+
+    Ok(())
+}
+
+struct SlowWorker {
+    counter: Arc<AtomicI8>,
+}
+
+#[async_trait]
+impl Worker for SlowWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(
+        &mut self,
+        context: &mut Self::Context,
+        _msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let _ = self.counter.fetch_add(1, Ordering::Relaxed);
+        context.sleep(Duration::from_millis(300)).await;
+        Ok(())
+    }
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn full_mailbox__drop_newest__messages_dropped(ctx: &mut Context) -> Result<()> {
+    let counter = Arc::new(AtomicI8::new(0));
+    WorkerBuilder::new(SlowWorker {
+        counter: counter.clone(),
+    })
+    .with_address("slow")
+    .with_mailbox_capacity(1)
+    .with_overflow_policy(OverflowPolicy::DropNewest)
+    .start(ctx)
+    .await?;
+
+    // The worker is busy with the first message
+    ctx.send("slow", "test".to_string()).await?;
+    ctx.sleep(Duration::from_millis(100)).await;
+
+    // The second message waits in the mailbox, the others are dropped
+    for _ in 0..3 {
+        ctx.send("slow", "test".to_string()).await?;
+    }
+    ctx.sleep(Duration::from_millis(800)).await;
+    assert_eq!(2, counter.load(Ordering::Relaxed));
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn full_mailbox__reject__sender_gets_error(ctx: &mut Context) -> Result<()> {
+    let counter = Arc::new(AtomicI8::new(0));
+    WorkerBuilder::new(SlowWorker {
+        counter: counter.clone(),
+    })
+    .with_address("slow")
+    .with_mailbox_capacity(1)
+    .with_overflow_policy(OverflowPolicy::Reject)
+    .start(ctx)
+    .await?;
+
+    ctx.send("slow", "test".to_string()).await?;
+    ctx.sleep(Duration::from_millis(100)).await;
+
+    ctx.send("slow", "test".to_string()).await?;
+    let res = ctx.send("slow", "test".to_string()).await;
+    assert_eq!(res.unwrap_err().code().kind, Kind::ResourceExhausted);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! This crate provides a QUIC Transport for Ockam's Routing Protocol.
+//!
+//! Messages sent to different next hops on the other side of a connection are spread
+//! over several QUIC streams, so that a lost packet only delays the messages of one stream.
+//! QUIC connections also survive a change of the network address of one of the peers,
+//! see [`QuicConnection::rebind`].
+//!
+//! Nodes behind a NAT can use a [`UdpRendezvousService`](ockam_transport_udp::UdpRendezvousService)
+//! to find each other, see the [`rendezvous`] module.
+//!
+//! This crate requires the rust standard library `"std"`
+#![deny(unsafe_code)]
+#![warn(
+    missing_docs,
+    dead_code,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+mod options;
+mod registry;
+mod tls;
+mod transport;
+
+pub mod rendezvous;
+
+use ockam_core::TransportType;
+pub use options::{QuicConnectionOptions, QuicListenerOptions};
+pub use registry::*;
+pub use transport::common::*;
+pub use transport::*;
+
+mod workers;
+pub(crate) use workers::*;
+
+pub(crate) const CLUSTER_NAME: &str = "_internals.transport.quic";
+
+/// Transport type for QUIC addresses
+pub const QUIC: TransportType = TransportType::new(6);
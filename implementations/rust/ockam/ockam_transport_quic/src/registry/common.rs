@@ -0,0 +1,159 @@
+use core::fmt;
+use core::fmt::Formatter;
+use ockam_core::flow_control::FlowControlId;
+use ockam_core::Address;
+use std::net::SocketAddr;
+
+/// Quic connection mode
+#[derive(Copy, Debug, Clone)]
+pub enum QuicConnectionMode {
+    /// Connection was initiated from our node
+    Outgoing,
+    /// Connection was accepted from a QUIC listener
+    Incoming,
+}
+
+impl fmt::Display for QuicConnectionMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            QuicConnectionMode::Outgoing => write!(f, "outgoing"),
+            QuicConnectionMode::Incoming => write!(f, "incoming"),
+        }
+    }
+}
+
+/// Information about specific Quic sender (corresponds to one specific Quic connection)
+#[derive(Debug, Clone)]
+pub struct QuicSenderInfo {
+    address: Address,
+    receiver_address: Address,
+    socket_address: SocketAddr,
+    mode: QuicConnectionMode,
+    flow_control_id: FlowControlId,
+}
+
+impl QuicSenderInfo {
+    /// Constructor
+    pub fn new(
+        address: Address,
+        receiver_address: Address,
+        socket_address: SocketAddr,
+        mode: QuicConnectionMode,
+        flow_control_id: FlowControlId,
+    ) -> Self {
+        Self {
+            address,
+            receiver_address,
+            socket_address,
+            mode,
+            flow_control_id,
+        }
+    }
+
+    /// Address of the Sender worker
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+    /// Corresponding Quic Receiver Processor Address
+    pub fn receiver_address(&self) -> &Address {
+        &self.receiver_address
+    }
+    /// Corresponding socket address
+    pub fn socket_address(&self) -> SocketAddr {
+        self.socket_address
+    }
+    /// Corresponding [`FlowControlId`]
+    pub fn flow_control_id(&self) -> &FlowControlId {
+        &self.flow_control_id
+    }
+    /// [`QuicConnectionMode`] for this connection
+    pub fn mode(&self) -> &QuicConnectionMode {
+        &self.mode
+    }
+}
+
+/// Information about specific Quic sender (corresponds to one specific Quic connection)
+#[derive(Debug, Clone)]
+pub struct QuicReceiverInfo {
+    address: Address,
+    sender_address: Address,
+    socket_address: SocketAddr,
+    mode: QuicConnectionMode,
+    flow_control_id: FlowControlId,
+}
+
+impl QuicReceiverInfo {
+    /// Constructor
+    pub fn new(
+        address: Address,
+        sender_address: Address,
+        socket_address: SocketAddr,
+        mode: QuicConnectionMode,
+        flow_control_id: FlowControlId,
+    ) -> Self {
+        Self {
+            address,
+            sender_address,
+            socket_address,
+            mode,
+            flow_control_id,
+        }
+    }
+
+    /// Address of the Receiver processor
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+    /// Corresponding Sender Worker Address
+    pub fn sender_address(&self) -> &Address {
+        &self.sender_address
+    }
+    /// Corresponding socket address
+    pub fn socket_address(&self) -> SocketAddr {
+        self.socket_address
+    }
+    /// Corresponding [`FlowControlId`]
+    pub fn flow_control_id(&self) -> &FlowControlId {
+        &self.flow_control_id
+    }
+    /// [`QuicConnectionMode`] for this connection
+    pub fn mode(&self) -> &QuicConnectionMode {
+        &self.mode
+    }
+}
+
+/// Information about specific Quic listener
+#[derive(Debug, Clone)]
+pub struct QuicListenerInfo {
+    address: Address,
+    socket_address: SocketAddr,
+    flow_control_id: FlowControlId,
+}
+
+impl QuicListenerInfo {
+    /// Constructor
+    pub fn new(
+        address: Address,
+        socket_address: SocketAddr,
+        flow_control_id: FlowControlId,
+    ) -> Self {
+        Self {
+            address,
+            socket_address,
+            flow_control_id,
+        }
+    }
+
+    /// Address of the Processor
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+    /// Corresponding socket address
+    pub fn socket_address(&self) -> SocketAddr {
+        self.socket_address
+    }
+    /// Corresponding [`FlowControlId`]
+    pub fn flow_control_id(&self) -> &FlowControlId {
+        &self.flow_control_id
+    }
+}
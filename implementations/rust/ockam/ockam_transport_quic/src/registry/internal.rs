@@ -0,0 +1,30 @@
+use crate::{QuicListenerInfo, QuicReceiverInfo, QuicSenderInfo};
+use ockam_core::Address;
+
+#[derive(Default, Debug)]
+pub(super) struct InternalRegistry {
+    pub(super) listener_processors: Vec<QuicListenerInfo>,
+    pub(super) sender_workers: Vec<QuicSenderInfo>,
+    pub(super) receiver_processors: Vec<QuicReceiverInfo>,
+}
+
+impl InternalRegistry {
+    pub(super) fn add_listener_processor(&mut self, info: QuicListenerInfo) {
+        self.listener_processors.push(info)
+    }
+    pub(super) fn remove_listener_processor(&mut self, addr: &Address) {
+        self.listener_processors.retain(|x| x.address() != addr);
+    }
+    pub(super) fn add_sender_worker(&mut self, info: QuicSenderInfo) {
+        self.sender_workers.push(info)
+    }
+    pub(super) fn remove_sender_worker(&mut self, addr: &Address) {
+        self.sender_workers.retain(|x| x.address() != addr);
+    }
+    pub(super) fn add_receiver_processor(&mut self, info: QuicReceiverInfo) {
+        self.receiver_processors.push(info)
+    }
+    pub(super) fn remove_receiver_processor(&mut self, addr: &Address) {
+        self.receiver_processors.retain(|x| x.address() != addr);
+    }
+}
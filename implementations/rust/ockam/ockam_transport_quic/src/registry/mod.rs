@@ -0,0 +1,8 @@
+mod common;
+mod crate_api;
+mod internal;
+#[allow(clippy::module_inception)]
+mod registry;
+
+pub use common::*;
+pub use registry::*;
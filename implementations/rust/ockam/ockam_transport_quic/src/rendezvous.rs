@@ -0,0 +1,217 @@
+//! NAT traversal with a [`UdpRendezvousService`](ockam_transport_udp::UdpRendezvousService).
+//!
+//! A node behind a NAT binds a UDP socket and registers its public address,
+//! as seen by the rendezvous service, with [`QuicRendezvousClient::register`].
+//! The same socket is then passed to
+//! [`QuicTransport::listen_on_socket`](crate::QuicTransport::listen_on_socket),
+//! so that the NAT keeps forwarding the packets sent to the registered address.
+//!
+//! The connecting node does the same with its own socket, looks up the public address of
+//! the listening node with [`QuicRendezvousClient::lookup`] and connects with
+//! [`QuicTransport::connect_from_socket`](crate::QuicTransport::connect_from_socket).
+//! If the NAT of the listening node only accepts packets from addresses it has already
+//! sent packets to, the listening node must call [`open_hole`] with the public address of
+//! the connecting node first.
+//!
+//! ```rust
+//! use ockam_transport_quic::rendezvous::QuicRendezvousClient;
+//! use ockam_transport_quic::{QuicListenerOptions, QuicTransport};
+//! use std::net::UdpSocket;
+//! # use ockam_node::Context;
+//! # use ockam_core::Result;
+//! # async fn test(ctx: Context) -> Result<()> {
+//! let rendezvous = QuicRendezvousClient::new("1.2.3.4:4000".parse().unwrap(), "rendezvous");
+//! let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
+//! let _public_address = rendezvous.register(&socket, "my_node").await?;
+//!
+//! let quic = QuicTransport::create(&ctx).await?;
+//! quic.listen_on_socket(socket, QuicListenerOptions::new()).await?;
+//! # Ok(()) }
+//! ```
+use core::time::Duration;
+use ockam_core::compat::net::SocketAddr;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{route, Address, Decodable, Encodable, Error, Result, TransportMessage};
+use ockam_transport_core::{encode_transport_message, TransportError};
+use ockam_transport_udp::{RendezvousRequest, RendezvousResponse, UDP};
+use std::net::UdpSocket;
+use tokio::time::timeout;
+use tracing::{debug, warn};
+
+/// Number of times a request is sent before giving up, since UDP datagrams can be lost
+const REQUEST_TRIES: usize = 3;
+
+/// How long to wait for the reply to a request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Return address of the requests, the rendezvous service replies to it over UDP
+const REPLY_ADDRESS: &str = "quic_rendezvous_client";
+
+/// Maximum size of a UDP datagram
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+/// Client of a [`UdpRendezvousService`](ockam_transport_udp::UdpRendezvousService)
+/// running on a UDP transport
+#[derive(Clone, Debug)]
+pub struct QuicRendezvousClient {
+    rendezvous_address: SocketAddr,
+    service_address: Address,
+}
+
+impl QuicRendezvousClient {
+    /// Use the rendezvous service started at `service_address`, on a node listening
+    /// on UDP at `rendezvous_address`
+    pub fn new(rendezvous_address: SocketAddr, service_address: impl Into<Address>) -> Self {
+        Self {
+            rendezvous_address,
+            service_address: service_address.into(),
+        }
+    }
+
+    /// Register the public address of `socket` under the given name,
+    /// and return this public address
+    pub async fn register(&self, socket: &UdpSocket, name: &str) -> Result<SocketAddr> {
+        let update = RendezvousRequest::Update {
+            puncher_name: name.to_string(),
+        };
+        // The service does not reply to updates, the query checks that it was received
+        self.send(socket, update)?;
+        let public_address = self.lookup(socket, name).await?;
+        debug!("Registered the public address {public_address} as {name}");
+
+        Ok(public_address)
+    }
+
+    /// Return the public address registered under the given name
+    pub async fn lookup(&self, socket: &UdpSocket, name: &str) -> Result<SocketAddr> {
+        for _ in 0..REQUEST_TRIES {
+            let query = RendezvousRequest::Query {
+                puncher_name: name.to_string(),
+            };
+            self.send(socket, query)?;
+            match timeout(REQUEST_TIMEOUT, self.receive(socket)).await {
+                Ok(Ok(RendezvousResponse::Query(route))) => return public_address(&route?),
+                Ok(Ok(RendezvousResponse::Pong)) => continue,
+                Ok(Err(e)) => warn!("Invalid reply from the rendezvous service: {e}"),
+                Err(_) => debug!("No reply from the rendezvous service, retrying"),
+            }
+        }
+
+        Err(Error::new(
+            Origin::Transport,
+            Kind::Timeout,
+            format!(
+                "The rendezvous service at {} did not reply",
+                self.rendezvous_address
+            ),
+        ))
+    }
+
+    fn send(&self, socket: &UdpSocket, request: RendezvousRequest) -> Result<()> {
+        let message = TransportMessage::latest(
+            route![self.service_address.clone()],
+            route![REPLY_ADDRESS],
+            request.encode()?,
+        );
+        let datagram = encode_transport_message(message)?;
+        socket
+            .send_to(&datagram, self.rendezvous_address)
+            .map_err(TransportError::from)?;
+
+        Ok(())
+    }
+
+    async fn receive(&self, socket: &UdpSocket) -> Result<RendezvousResponse> {
+        // Declared first, so that the blocking mode is restored once the duplicate is dropped
+        let _non_blocking = NonBlockingGuard::new(socket)?;
+        let socket = async_socket(socket)?;
+        let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+        loop {
+            let (len, from) = socket
+                .recv_from(&mut buf)
+                .await
+                .map_err(TransportError::from)?;
+            // Ignore the packets from other peers, for example a QUIC handshake
+            if from != self.rendezvous_address || len < 2 {
+                continue;
+            }
+            let message = TransportMessage::decode_message(buf[2..len].to_vec())?;
+            return RendezvousResponse::decode(&message.payload);
+        }
+    }
+}
+
+/// Send an empty datagram to `peer` from `socket`, so that the NAT in front of
+/// the socket accepts the packets coming back from the peer
+pub fn open_hole(socket: &UdpSocket, peer: SocketAddr) -> Result<()> {
+    socket.send_to(&[], peer).map_err(TransportError::from)?;
+    Ok(())
+}
+
+/// Read the socket without blocking the runtime. The socket stays open once the
+/// returned handle is dropped, since it uses a duplicate of the file descriptor.
+/// The socket must be in non-blocking mode, see [`NonBlockingGuard`]
+fn async_socket(socket: &UdpSocket) -> Result<tokio::net::UdpSocket> {
+    let socket = socket.try_clone().map_err(TransportError::from)?;
+    Ok(tokio::net::UdpSocket::from_std(socket).map_err(TransportError::from)?)
+}
+
+/// Put a socket in non-blocking mode, and restore its previous mode when dropped.
+///
+/// The mode is shared by the duplicates of a file descriptor, so setting it on the
+/// duplicate read by the runtime would also change the socket of the caller
+struct NonBlockingGuard<'a> {
+    socket: &'a UdpSocket,
+    was_non_blocking: bool,
+}
+
+impl<'a> NonBlockingGuard<'a> {
+    fn new(socket: &'a UdpSocket) -> Result<Self> {
+        #[cfg(unix)]
+        let was_non_blocking = socket2::SockRef::from(socket)
+            .nonblocking()
+            .map_err(TransportError::from)?;
+        // The mode can't be read on Windows, where sockets are blocking by default
+        #[cfg(not(unix))]
+        let was_non_blocking = false;
+
+        socket.set_nonblocking(true).map_err(TransportError::from)?;
+        Ok(Self {
+            socket,
+            was_non_blocking,
+        })
+    }
+}
+
+impl Drop for NonBlockingGuard<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.socket.set_nonblocking(self.was_non_blocking) {
+            warn!("Cannot restore the blocking mode of the socket: {e}");
+        }
+    }
+}
+
+/// Extract the public address from a route returned by the rendezvous service
+fn public_address(route: &ockam_core::Route) -> Result<SocketAddr> {
+    route
+        .iter()
+        .find(|address| address.transport_type() == UDP)
+        .and_then(|address| address.address().parse().ok())
+        .ok_or_else(|| TransportError::InvalidAddress.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_address_is_the_udp_address_of_the_route() {
+        let route = route![(UDP, "1.2.3.4:5000"), REPLY_ADDRESS];
+        assert_eq!(
+            public_address(&route).unwrap(),
+            "1.2.3.4:5000".parse::<SocketAddr>().unwrap()
+        );
+
+        assert!(public_address(&route![REPLY_ADDRESS]).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+use quinn::crypto::rustls::{QuicClientConfig, QuicServerConfig};
+use quinn::{ClientConfig, ServerConfig, TransportConfig, VarInt};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+
+/// Application protocol negotiated by the QUIC handshake
+const ALPN_PROTOCOL: &[u8] = b"ockam";
+
+/// Name of the self-signed certificate of the listeners, and name expected by the clients
+pub(crate) const SERVER_NAME: &str = "ockam";
+
+/// Interval at which keep-alive packets are sent on idle connections,
+/// this also keeps the NAT bindings open
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Maximum number of streams that the other side of a connection can open at the same time
+const MAX_CONCURRENT_STREAMS: u32 = 256;
+
+/// Configuration of the listeners.
+///
+/// QUIC mandates TLS, but the peers are not authenticated at this level:
+/// Ockam Secure Channels are established on top of the connection for that purpose.
+/// This is why the listeners use a freshly generated self-signed certificate.
+pub(crate) fn server_config() -> Result<ServerConfig> {
+    let certified_key = rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()])
+        .map_err(|e| tls_error(format!("Cannot generate a self-signed certificate: {e}")))?;
+    let certificate = certified_key.cert.der().clone();
+    let private_key = PrivatePkcs8KeyDer::from(certified_key.key_pair.serialize_der());
+
+    let mut crypto =
+        rustls::ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .map_err(|e| tls_error(format!("Invalid TLS configuration: {e}")))?
+            .with_no_client_auth()
+            .with_single_cert(vec![certificate], private_key.into())
+            .map_err(|e| tls_error(format!("Invalid certificate or private key: {e}")))?;
+    crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+
+    let crypto = QuicServerConfig::try_from(crypto)
+        .map_err(|e| tls_error(format!("Invalid QUIC TLS configuration: {e}")))?;
+    let mut config = ServerConfig::with_crypto(Arc::new(crypto));
+    config.transport_config(transport_config());
+
+    Ok(config)
+}
+
+/// Configuration of the outgoing connections, see [`server_config`]
+pub(crate) fn client_config() -> Result<ClientConfig> {
+    let provider = Arc::new(ring::default_provider());
+    let mut crypto = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(|e| tls_error(format!("Invalid TLS configuration: {e}")))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AnyServerCertificate(provider)))
+        .with_no_client_auth();
+    crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+
+    let crypto = QuicClientConfig::try_from(crypto)
+        .map_err(|e| tls_error(format!("Invalid QUIC TLS configuration: {e}")))?;
+    let mut config = ClientConfig::new(Arc::new(crypto));
+    config.transport_config(transport_config());
+
+    Ok(config)
+}
+
+fn transport_config() -> Arc<TransportConfig> {
+    let mut config = TransportConfig::default();
+    config
+        .keep_alive_interval(Some(KEEP_ALIVE_INTERVAL))
+        .max_concurrent_uni_streams(VarInt::from_u32(MAX_CONCURRENT_STREAMS))
+        .max_concurrent_bidi_streams(VarInt::from_u32(0));
+    Arc::new(config)
+}
+
+/// Accept any server certificate, but still check the handshake signatures
+/// so that the server proves that it owns the certificate it presented
+#[derive(Debug)]
+struct AnyServerCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AnyServerCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn tls_error(message: impl Into<String>) -> Error {
+    Error::new(Origin::Transport, Kind::Invalid, message.into())
+}
@@ -0,0 +1,206 @@
+use crate::tls::{client_config, server_config};
+use crate::QuicConnectionMode;
+use core::fmt;
+use core::fmt::Formatter;
+use ockam_core::compat::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use ockam_core::compat::sync::Arc;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::flow_control::FlowControlId;
+use ockam_core::{Address, Error, Result};
+use ockam_node::Context;
+use ockam_transport_core::TransportError;
+use quinn::{Endpoint, EndpointConfig, TokioRuntime};
+use std::net::UdpSocket;
+
+/// Result of [`QuicTransport::connect`](crate::QuicTransport::connect) call.
+#[derive(Clone, Debug)]
+pub struct QuicConnection {
+    sender_address: Address,
+    receiver_address: Address,
+    socket_address: SocketAddr,
+    mode: QuicConnectionMode,
+    flow_control_id: FlowControlId,
+    endpoint: Endpoint,
+}
+
+impl fmt::Display for QuicConnection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Socket: {}, Worker: {}, Processor: {}, FlowId: {}",
+            self.socket_address, self.sender_address, self.receiver_address, self.flow_control_id
+        )
+    }
+}
+
+impl From<QuicConnection> for Address {
+    fn from(value: QuicConnection) -> Self {
+        value.sender_address
+    }
+}
+
+impl QuicConnection {
+    /// Constructor
+    pub(crate) fn new(
+        sender_address: Address,
+        receiver_address: Address,
+        socket_address: SocketAddr,
+        mode: QuicConnectionMode,
+        flow_control_id: FlowControlId,
+        endpoint: Endpoint,
+    ) -> Self {
+        Self {
+            sender_address,
+            receiver_address,
+            socket_address,
+            mode,
+            flow_control_id,
+            endpoint,
+        }
+    }
+    /// Stops the [`QuicConnection`], this method must be called to avoid
+    /// leakage of the connection.
+    /// Simply dropping this object won't close the connection
+    pub async fn stop(&self, context: &Context) -> Result<()> {
+        context.stop_worker(self.sender_address.clone()).await
+    }
+    /// Send the packets of this connection from another local socket, for example
+    /// when the device moved to another network.
+    ///
+    /// The other side of the connection keeps it open and starts replying to the new
+    /// address as soon as it receives packets from there.
+    pub fn rebind(&self, socket: UdpSocket) -> Result<()> {
+        socket.set_nonblocking(true).map_err(TransportError::from)?;
+        self.endpoint.rebind(socket).map_err(TransportError::from)?;
+        Ok(())
+    }
+    /// Local socket address that the packets of this connection are sent from
+    pub fn local_address(&self) -> Result<SocketAddr> {
+        Ok(self.endpoint.local_addr().map_err(TransportError::from)?)
+    }
+    /// Corresponding [`QuicSendWorker`](crate::workers::QuicSendWorker) [`Address`] that can be used
+    /// in a route to send messages to the other side of the QUIC connection
+    pub fn sender_address(&self) -> &Address {
+        &self.sender_address
+    }
+    /// Corresponding [`QuicRecvProcessor`](crate::workers::QuicRecvProcessor) [`Address`]
+    pub fn receiver_address(&self) -> &Address {
+        &self.receiver_address
+    }
+    /// Corresponding [`SocketAddr`]
+    pub fn socket_address(&self) -> &SocketAddr {
+        &self.socket_address
+    }
+    /// Generated fresh random [`FlowControlId`]
+    pub fn flow_control_id(&self) -> &FlowControlId {
+        &self.flow_control_id
+    }
+    /// Corresponding [`QuicConnectionMode`]
+    pub fn mode(&self) -> QuicConnectionMode {
+        self.mode
+    }
+}
+
+/// Result of [`QuicTransport::listen`](crate::QuicTransport::listen) call.
+#[derive(Clone, Debug)]
+pub struct QuicListener {
+    processor_address: Address,
+    socket_address: SocketAddr,
+    flow_control_id: FlowControlId,
+}
+
+impl fmt::Display for QuicListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Socket: {}, Processor: {}, FlowId: {}",
+            self.socket_address, self.processor_address, self.flow_control_id
+        )
+    }
+}
+
+impl QuicListener {
+    /// Constructor
+    pub fn new(
+        processor_address: Address,
+        socket_address: SocketAddr,
+        flow_control_id: FlowControlId,
+    ) -> Self {
+        Self {
+            processor_address,
+            socket_address,
+            flow_control_id,
+        }
+    }
+    /// Corresponding Worker [`Address`] that can be used to stop the Listener
+    pub fn processor_address(&self) -> &Address {
+        &self.processor_address
+    }
+    /// Corresponding [`SocketAddr`]
+    pub fn socket_address(&self) -> &SocketAddr {
+        &self.socket_address
+    }
+    /// Corresponding [`SocketAddr`] in String format
+    pub fn socket_string(&self) -> String {
+        self.socket_address.to_string()
+    }
+    /// Generated fresh random [`FlowControlId`]
+    pub fn flow_control_id(&self) -> &FlowControlId {
+        &self.flow_control_id
+    }
+}
+
+/// Resolve the given peer, which is either a socket address or a `hostname:port`
+pub(crate) async fn resolve_peer(peer: &str) -> Result<SocketAddr> {
+    if let Ok(socket_address) = peer.parse() {
+        return Ok(socket_address);
+    }
+
+    tokio::net::lookup_host(peer)
+        .await
+        .map_err(|_| TransportError::InvalidAddress)?
+        .next()
+        .ok_or_else(|| TransportError::InvalidAddress.into())
+}
+
+/// Bind a socket to a random port, to connect to the given peer
+pub(crate) fn bind_socket_for(peer: &SocketAddr) -> Result<UdpSocket> {
+    let ip = if peer.is_ipv4() {
+        Ipv4Addr::UNSPECIFIED.into()
+    } else {
+        Ipv6Addr::UNSPECIFIED.into()
+    };
+    Ok(UdpSocket::bind(SocketAddr::new(ip, 0)).map_err(TransportError::from)?)
+}
+
+/// Create an endpoint only used for outgoing connections
+pub(crate) fn client_endpoint(socket: UdpSocket) -> Result<Endpoint> {
+    let mut endpoint = create_endpoint(socket, false)?;
+    endpoint.set_default_client_config(client_config()?);
+    Ok(endpoint)
+}
+
+/// Create an endpoint accepting incoming connections
+pub(crate) fn server_endpoint(socket: UdpSocket) -> Result<Endpoint> {
+    create_endpoint(socket, true)
+}
+
+fn create_endpoint(socket: UdpSocket, server: bool) -> Result<Endpoint> {
+    let server_config = if server { Some(server_config()?) } else { None };
+    socket.set_nonblocking(true).map_err(TransportError::from)?;
+    Ok(Endpoint::new(
+        EndpointConfig::default(),
+        server_config,
+        socket,
+        Arc::new(TokioRuntime),
+    )
+    .map_err(TransportError::from)?)
+}
+
+pub(crate) fn connection_error(peer: &str, message: impl fmt::Display) -> Error {
+    Error::new(
+        Origin::Transport,
+        Kind::Io,
+        format!("Cannot connect to {peer}: {message}"),
+    )
+}
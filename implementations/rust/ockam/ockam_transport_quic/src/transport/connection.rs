@@ -0,0 +1,101 @@
+use crate::tls::SERVER_NAME;
+use crate::transport::common::{
+    bind_socket_for, client_endpoint, connection_error, resolve_peer, QuicConnection,
+};
+use crate::workers::{Addresses, QuicRecvProcessor, QuicSendWorker};
+use crate::{QuicConnectionMode, QuicConnectionOptions, QuicTransport};
+use ockam_core::{Address, Result};
+use std::net::UdpSocket;
+use tracing::debug;
+
+impl QuicTransport {
+    /// Establish an outgoing QUIC connection.
+    ///
+    /// ```rust
+    /// use ockam_transport_quic::{QuicConnectionOptions, QuicListenerOptions, QuicTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let quic = QuicTransport::create(&ctx).await?;
+    /// quic.listen("127.0.0.1:8000", QuicListenerOptions::new()).await?; // Listen on port 8000
+    /// let connection = quic.connect("127.0.0.1:5000", QuicConnectionOptions::new()).await?; // and connect to port 5000
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect(
+        &self,
+        peer: impl Into<String>,
+        options: QuicConnectionOptions,
+    ) -> Result<QuicConnection> {
+        let peer = peer.into();
+        let socket_address = resolve_peer(&peer).await?;
+        let socket = bind_socket_for(&socket_address)?;
+        self.connect_from_socket(socket, peer, options).await
+    }
+
+    /// Establish an outgoing QUIC connection, sending its packets from the given socket.
+    ///
+    /// This is used to traverse a NAT: the socket must be the one which was registered
+    /// to the rendezvous service, see the [`rendezvous`](crate::rendezvous) module.
+    pub async fn connect_from_socket(
+        &self,
+        socket: UdpSocket,
+        peer: impl Into<String>,
+        options: QuicConnectionOptions,
+    ) -> Result<QuicConnection> {
+        let peer = peer.into();
+        let socket_address = resolve_peer(&peer).await?;
+        let endpoint = client_endpoint(socket)?;
+
+        debug!("Connecting to {}", peer);
+        let connection = endpoint
+            .connect(socket_address, SERVER_NAME)
+            .map_err(|e| connection_error(&peer, e))?
+            .await
+            .map_err(|e| connection_error(&peer, e))?;
+
+        let mode = QuicConnectionMode::Outgoing;
+        let addresses = Addresses::generate(mode);
+
+        options.setup_flow_control(self.ctx.flow_controls(), &addresses);
+        let flow_control_id = options.flow_control_id.clone();
+        let access_control = options.create_access_control(self.ctx.flow_controls());
+
+        QuicSendWorker::start(
+            &self.ctx,
+            self.registry.clone(),
+            connection.clone(),
+            &addresses,
+            socket_address,
+            mode,
+            access_control.sender_incoming_access_control,
+            &flow_control_id,
+        )
+        .await?;
+
+        QuicRecvProcessor::start(
+            &self.ctx,
+            self.registry.clone(),
+            connection,
+            &addresses,
+            socket_address,
+            mode,
+            &flow_control_id,
+            access_control.receiver_outgoing_access_control,
+        )
+        .await?;
+
+        Ok(QuicConnection::new(
+            addresses.sender_address().clone(),
+            addresses.receiver_address().clone(),
+            socket_address,
+            mode,
+            flow_control_id,
+            endpoint,
+        ))
+    }
+
+    /// Interrupt an active QUIC connection given its Sender `Address`
+    pub async fn disconnect(&self, address: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_worker(address.into()).await
+    }
+}
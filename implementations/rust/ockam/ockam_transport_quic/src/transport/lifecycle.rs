@@ -0,0 +1,172 @@
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Address, AsyncTryClone, Error, Result, TransportType};
+use ockam_node::Context;
+use ockam_transport_core::Transport;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::{
+    QuicConnectionOptions, QuicListenerInfo, QuicRegistry, QuicSenderInfo, QuicTransport, QUIC,
+};
+
+impl QuicTransport {
+    /// Create a QUIC transport
+    ///
+    /// ```rust
+    /// use ockam_transport_quic::QuicTransport;
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let quic = QuicTransport::create(&ctx).await?;
+    /// # Ok(()) }
+    /// ```
+    #[instrument(name = "create quic transport", skip_all)]
+    pub async fn create(ctx: &Context) -> Result<Self> {
+        let quic = Self {
+            ctx: Arc::new(ctx.async_try_clone().await?),
+            registry: QuicRegistry::default(),
+        };
+        // make the QUIC transport available in the list of supported transports for
+        // later address resolution when socket addresses will need to be instantiated as QUIC
+        // worker addresses
+        ctx.register_transport(Arc::new(quic.clone()));
+        Ok(quic)
+    }
+}
+
+impl QuicTransport {
+    /// Getter
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+    /// Registry of all active connections
+    pub fn registry(&self) -> &QuicRegistry {
+        &self.registry
+    }
+
+    /// Search for a connection with the provided socket address
+    pub fn find_connection_by_socketaddr(
+        &self,
+        socket_address: SocketAddr,
+    ) -> Option<QuicSenderInfo> {
+        self.registry()
+            .get_all_sender_workers()
+            .into_iter()
+            .find(|x| x.socket_address() == socket_address)
+    }
+
+    /// Search for a connection with the provided address
+    pub fn find_connection(&self, address: String) -> Option<QuicSenderInfo> {
+        match address.parse::<SocketAddr>() {
+            Ok(socket_address) => self.find_connection_by_socketaddr(socket_address),
+            Err(_err) => {
+                let address: Address = address.into();
+
+                // Check if it's a Receiver Address
+                let address = if let Some(receiver) = self
+                    .registry()
+                    .get_all_receiver_processors()
+                    .into_iter()
+                    .find(|x| x.address() == &address)
+                {
+                    receiver.sender_address().clone()
+                } else {
+                    address
+                };
+
+                self.registry()
+                    .get_all_sender_workers()
+                    .into_iter()
+                    .find(|x| x.address() == &address)
+            }
+        }
+    }
+
+    /// Search for a listener with the provided socket address
+    pub fn find_listener_by_socketaddress(
+        &self,
+        socket_address: SocketAddr,
+    ) -> Option<QuicListenerInfo> {
+        self.registry()
+            .get_all_listeners()
+            .into_iter()
+            .find(|x| x.socket_address() == socket_address)
+    }
+
+    /// Search for a listener with the provided address
+    pub fn find_listener(&self, address: String) -> Option<QuicListenerInfo> {
+        match address.parse::<SocketAddr>() {
+            Ok(socket_address) => self.find_listener_by_socketaddress(socket_address),
+            Err(_err) => {
+                let address: Address = address.into();
+
+                self.registry()
+                    .get_all_listeners()
+                    .into_iter()
+                    .find(|x| x.address() == &address)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for QuicTransport {
+    fn transport_type(&self) -> TransportType {
+        QUIC
+    }
+
+    async fn resolve_address(&self, address: Address) -> Result<Address> {
+        if address.transport_type() == QUIC {
+            Ok(self
+                .connect(address.address().to_string(), QuicConnectionOptions::new())
+                .await?
+                .into())
+        } else {
+            Err(Error::new(
+                Origin::Transport,
+                Kind::NotFound,
+                format!(
+                    "this address can not be resolved by a QUIC transport {}",
+                    address
+                ),
+            ))
+        }
+    }
+
+    async fn disconnect(&self, address: Address) -> Result<()> {
+        self.disconnect(address).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QuicListenerOptions;
+
+    #[ockam_macros::test]
+    async fn test_resolve_address(ctx: &mut Context) -> Result<()> {
+        let quic = QuicTransport::create(ctx).await?;
+        let listener = quic
+            .listen("127.0.0.1:0", QuicListenerOptions::new())
+            .await?;
+
+        let resolved = quic
+            .resolve_address(Address::new(QUIC, listener.socket_string()))
+            .await?;
+
+        // the QUIC address is replaced with the QUIC sender worker address
+        assert!(ctx.list_workers().await?.contains(&resolved));
+
+        // an address of another transport can not be resolved
+        let result = quic
+            .resolve_address(Address::new(
+                TransportType::new(1),
+                listener.socket_string(),
+            ))
+            .await;
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
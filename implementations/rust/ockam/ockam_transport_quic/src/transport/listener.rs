@@ -0,0 +1,60 @@
+use crate::transport::common::{server_endpoint, QuicListener};
+use crate::workers::QuicListenProcessor;
+use crate::{QuicListenerOptions, QuicTransport};
+use ockam_core::compat::net::SocketAddr;
+use ockam_core::{Address, Result};
+use ockam_transport_core::TransportError;
+use std::net::UdpSocket;
+
+impl QuicTransport {
+    /// Start listening to incoming connections on an existing transport
+    ///
+    /// Returns the local address that this transport is bound to.
+    ///
+    /// This can be useful, for example, when binding to port 0 to figure out
+    /// which port was actually bound.
+    ///
+    /// ```rust
+    /// use ockam_transport_quic::{QuicListenerOptions, QuicTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let quic = QuicTransport::create(&ctx).await?;
+    /// quic.listen("127.0.0.1:8000", QuicListenerOptions::new()).await?;
+    /// # Ok(()) }
+    pub async fn listen(
+        &self,
+        bind_addr: impl AsRef<str>,
+        options: QuicListenerOptions,
+    ) -> Result<QuicListener> {
+        let bind_addr: SocketAddr = bind_addr
+            .as_ref()
+            .parse()
+            .map_err(|_| TransportError::InvalidAddress)?;
+        let socket = UdpSocket::bind(bind_addr).map_err(|_| TransportError::BindFailed)?;
+        self.listen_on_socket(socket, options).await
+    }
+
+    /// Start listening to incoming connections on the given socket.
+    ///
+    /// This is used to traverse a NAT: the socket must be the one which was registered
+    /// to the rendezvous service, see the [`rendezvous`](crate::rendezvous) module.
+    pub async fn listen_on_socket(
+        &self,
+        socket: UdpSocket,
+        options: QuicListenerOptions,
+    ) -> Result<QuicListener> {
+        let flow_control_id = options.flow_control_id.clone();
+        let endpoint = server_endpoint(socket)?;
+        // Could be different from the bind address, e.g., if binding to port 0
+        let (socket_addr, address) =
+            QuicListenProcessor::start(&self.ctx, self.registry.clone(), endpoint, options).await?;
+
+        Ok(QuicListener::new(address, socket_addr, flow_control_id))
+    }
+
+    /// Interrupt an active QUIC listener given its `Address`
+    pub async fn stop_listener(&self, address: &Address) -> Result<()> {
+        self.ctx.stop_processor(address.clone()).await
+    }
+}
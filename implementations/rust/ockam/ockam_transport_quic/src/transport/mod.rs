@@ -0,0 +1,52 @@
+pub(crate) mod common;
+mod connection;
+mod lifecycle;
+mod listener;
+
+pub use common::*;
+
+use crate::QuicRegistry;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, Result};
+use ockam_node::{Context, HasContext};
+
+/// High level management interface for QUIC transports
+///
+/// Be aware that only one `QuicTransport` can exist per node, as it
+/// registers itself as a router for the `QUIC` address type.
+///
+/// To listen for incoming connections use
+/// [`quic.listen()`](crate::QuicTransport::listen).
+///
+/// To register additional connections on an already initialised
+/// `QuicTransport`, use [`quic.connect()`](crate::QuicTransport::connect).
+/// This step is optional because a connection is established
+/// upon arrival of an initial message for a `QUIC` address.
+///
+/// ```rust
+/// use ockam_transport_quic::{QuicConnectionOptions, QuicListenerOptions, QuicTransport};
+/// # use ockam_node::Context;
+/// # use ockam_core::Result;
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let quic = QuicTransport::create(&ctx).await?;
+/// quic.listen("127.0.0.1:8000", QuicListenerOptions::new()).await?; // Listen on port 8000
+/// quic.connect("127.0.0.1:5000", QuicConnectionOptions::new()).await?; // And connect to port 5000
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct QuicTransport {
+    ctx: Arc<Context>,
+    registry: QuicRegistry,
+}
+
+/// This trait adds a `create_quic_transport` method to any struct returning a Context.
+/// This is the case for an ockam::Node, so you can write `node.create_quic_transport()`
+#[async_trait]
+pub trait QuicTransportExtension: HasContext {
+    /// Create a QUIC transport
+    async fn create_quic_transport(&self) -> Result<QuicTransport> {
+        QuicTransport::create(self.get_context()).await
+    }
+}
+
+impl<A: HasContext> QuicTransportExtension for A {}
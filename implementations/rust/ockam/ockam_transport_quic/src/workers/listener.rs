@@ -0,0 +1,156 @@
+use crate::workers::{Addresses, QuicRecvProcessor};
+use crate::{
+    QuicConnectionMode, QuicListenerInfo, QuicListenerOptions, QuicRegistry, QuicSendWorker,
+};
+use ockam_core::{async_trait, compat::net::SocketAddr};
+use ockam_core::{Address, AsyncTryClone, Processor, Result};
+use ockam_node::Context;
+use ockam_transport_core::TransportError;
+use quinn::{Connection, Endpoint};
+use tracing::{debug, instrument, warn};
+
+/// A QUIC Listen processor
+///
+/// QUIC listen processors are created by `QuicTransport`
+/// after a call is made to
+/// [`QuicTransport::listen`](crate::QuicTransport::listen).
+pub(crate) struct QuicListenProcessor {
+    registry: QuicRegistry,
+    endpoint: Endpoint,
+    socket_address: SocketAddr,
+    options: QuicListenerOptions,
+}
+
+impl QuicListenProcessor {
+    #[instrument(skip_all, name = "QuicListenProcessor::start")]
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: QuicRegistry,
+        endpoint: Endpoint,
+        options: QuicListenerOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let saddr = endpoint.local_addr().map_err(TransportError::from)?;
+        debug!("QUIC endpoint listening on {}", saddr);
+
+        let address = Address::random_tagged("QuicListenProcessor");
+        options.setup_flow_control_for_listener(ctx.flow_controls(), &address);
+
+        let processor = Self {
+            registry,
+            endpoint,
+            socket_address: saddr,
+            options,
+        };
+
+        ctx.start_processor(address.clone(), processor).await?;
+
+        Ok((saddr, address))
+    }
+}
+
+#[async_trait]
+impl Processor for QuicListenProcessor {
+    type Context = Context;
+
+    #[instrument(skip_all, name = "QuicListenProcessor::initialize")]
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry.add_listener_processor(QuicListenerInfo::new(
+            ctx.address(),
+            self.socket_address,
+            self.options.flow_control_id.clone(),
+        ));
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "QuicListenProcessor::shutdown")]
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_listener_processor(&ctx.address());
+        // Connections accepted by this endpoint are closed by their sender workers
+        self.endpoint.set_server_config(None);
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "QuicListenProcessor::process")]
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        debug!("Waiting for incoming QUIC connection...");
+
+        // Wait for an incoming connection
+        let incoming = match self.endpoint.accept().await {
+            Some(incoming) => incoming,
+            None => return Err(TransportError::BindFailed)?,
+        };
+
+        // Run the handshake in the background and keep accepting other connections
+        let handshake_ctx = ctx.async_try_clone().await?;
+        let registry = self.registry.clone();
+        let options = self.options.clone();
+        ctx.runtime().spawn(async move {
+            let connection = match incoming.await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    warn!("QUIC handshake failed: {e}");
+                    return;
+                }
+            };
+            let peer = connection.remote_address();
+            debug!("QUIC connection accepted from {}", peer);
+
+            if let Err(e) =
+                Self::start_connection(&handshake_ctx, registry, &options, connection).await
+            {
+                warn!("Cannot start the QUIC connection with {peer}: {e:?}");
+            }
+        });
+
+        Ok(true)
+    }
+}
+
+impl QuicListenProcessor {
+    /// Start the workers of an accepted connection
+    async fn start_connection(
+        ctx: &Context,
+        registry: QuicRegistry,
+        options: &QuicListenerOptions,
+        connection: Connection,
+    ) -> Result<()> {
+        let peer = connection.remote_address();
+        let mode = QuicConnectionMode::Incoming;
+        let addresses = Addresses::generate(mode);
+
+        let receiver_flow_control_id =
+            options.setup_flow_control_for_connection(ctx.flow_controls(), &addresses);
+        let access_control =
+            options.create_access_control(ctx.flow_controls(), receiver_flow_control_id.clone());
+
+        // Worker to receive messages from the Node and send them over the wire
+        QuicSendWorker::start(
+            ctx,
+            registry.clone(),
+            connection.clone(),
+            &addresses,
+            peer,
+            mode,
+            access_control.sender_incoming_access_control,
+            &receiver_flow_control_id,
+        )
+        .await?;
+
+        // Processor to receive messages over the wire and forward them to the node
+        QuicRecvProcessor::start(
+            ctx,
+            registry,
+            connection,
+            &addresses,
+            peer,
+            mode,
+            &receiver_flow_control_id,
+            access_control.receiver_outgoing_access_control,
+        )
+        .await
+    }
+}
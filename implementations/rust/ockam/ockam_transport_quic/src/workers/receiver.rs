@@ -0,0 +1,223 @@
+use crate::workers::Addresses;
+use crate::{QuicConnectionMode, QuicReceiverInfo, QuicRegistry, QuicSendWorkerMsg};
+use ockam_core::compat::net::SocketAddr;
+use ockam_core::compat::sync::Arc;
+use ockam_core::flow_control::FlowControlId;
+use ockam_core::{
+    async_trait, AllowOnwardAddress, DenyAll, Mailbox, Mailboxes, OutgoingAccessControl,
+};
+use ockam_core::{LocalMessage, Processor, Result, TransportMessage};
+use ockam_node::{Context, ProcessorBuilder};
+use quinn::{Connection, RecvStream, VarInt};
+use tokio::io::AsyncReadExt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, instrument, trace};
+
+/// Number of received messages which can wait to be forwarded by the processor
+const INCOMING_MESSAGES_CAPACITY: usize = 128;
+
+/// A QUIC receiving message processor
+///
+/// This half of the worker is created when spawning a new connection
+/// worker pair. It accepts the streams opened by the other side of the
+/// connection, reads the messages of each stream in a separate task
+/// and relays them into the node message system.
+pub(crate) struct QuicRecvProcessor {
+    registry: QuicRegistry,
+    connection: Connection,
+    socket_address: SocketAddr,
+    addresses: Addresses,
+    mode: QuicConnectionMode,
+    flow_control_id: FlowControlId,
+    incoming_messages: mpsc::Receiver<LocalMessage>,
+    incoming_messages_sender: Option<mpsc::Sender<LocalMessage>>,
+    accept_task: Option<JoinHandle<()>>,
+}
+
+impl QuicRecvProcessor {
+    /// Create a new `QuicRecvProcessor`
+    fn new(
+        registry: QuicRegistry,
+        connection: Connection,
+        socket_address: SocketAddr,
+        addresses: Addresses,
+        mode: QuicConnectionMode,
+        flow_control_id: FlowControlId,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(INCOMING_MESSAGES_CAPACITY);
+        Self {
+            registry,
+            connection,
+            socket_address,
+            addresses,
+            mode,
+            flow_control_id,
+            incoming_messages: receiver,
+            incoming_messages_sender: Some(sender),
+            accept_task: None,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, name = "QuicRecvProcessor::start")]
+    pub async fn start(
+        ctx: &Context,
+        registry: QuicRegistry,
+        connection: Connection,
+        addresses: &Addresses,
+        socket_address: SocketAddr,
+        mode: QuicConnectionMode,
+        flow_control_id: &FlowControlId,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let receiver = QuicRecvProcessor::new(
+            registry,
+            connection,
+            socket_address,
+            addresses.clone(),
+            mode,
+            flow_control_id.clone(),
+        );
+
+        let mailbox = Mailbox::new(
+            addresses.receiver_address().clone(),
+            Arc::new(DenyAll),
+            receiver_outgoing_access_control,
+        );
+        let internal = Mailbox::new(
+            addresses.receiver_internal_address().clone(),
+            Arc::new(DenyAll),
+            Arc::new(AllowOnwardAddress(
+                addresses.sender_internal_address().clone(),
+            )),
+        );
+        ProcessorBuilder::new(receiver)
+            .with_mailboxes(Mailboxes::new(mailbox, vec![internal]))
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Accept the streams opened by the other side until the connection is closed
+async fn accept_streams(connection: Connection, messages: mpsc::Sender<LocalMessage>) {
+    while let Ok(stream) = connection.accept_uni().await {
+        tokio::spawn(read_stream(stream, messages.clone()));
+    }
+}
+
+/// Read the messages of a stream until it is finished
+async fn read_stream(mut stream: RecvStream, messages: mpsc::Sender<LocalMessage>) {
+    loop {
+        // First read a message length header...
+        let len = match stream.read_u16().await {
+            Ok(len) => len,
+            Err(_) => return,
+        };
+
+        trace!("Received message header for {} bytes", len);
+
+        // Then read the message itself
+        let mut buf = vec![0; len as usize];
+        if stream.read_exact(&mut buf).await.is_err() {
+            error!("Failed to receive message of length: {}", len);
+            return;
+        }
+
+        let transport_message = match TransportMessage::decode_message(buf) {
+            Ok(transport_message) => transport_message,
+            Err(e) => {
+                error!("{e:?}");
+                let _ = stream.stop(VarInt::from_u32(0));
+                return;
+            }
+        };
+
+        let local_message = LocalMessage::from_transport_message(transport_message);
+        if messages.send(local_message).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[async_trait]
+impl Processor for QuicRecvProcessor {
+    type Context = Context;
+
+    #[instrument(skip_all, name = "QuicRecvProcessor::initialize")]
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        // The channel is closed once the connection and all its streams are closed
+        if let Some(messages) = self.incoming_messages_sender.take() {
+            self.accept_task = Some(tokio::spawn(accept_streams(
+                self.connection.clone(),
+                messages,
+            )));
+        }
+
+        self.registry.add_receiver_processor(QuicReceiverInfo::new(
+            ctx.address(),
+            self.addresses.sender_address().clone(),
+            self.socket_address,
+            self.mode,
+            self.flow_control_id.clone(),
+        ));
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "QuicRecvProcessor::shutdown")]
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_receiver_processor(&ctx.address());
+
+        if let Some(accept_task) = self.accept_task.take() {
+            accept_task.abort();
+        }
+
+        Ok(())
+    }
+
+    /// Get the next message received on any of the streams of the connection
+    /// and forward it to the next hop in the route.
+    #[instrument(skip_all, name = "QuicRecvProcessor::process", fields(worker = %ctx.address()))]
+    async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
+        let local_message = match self.incoming_messages.recv().await {
+            Some(local_message) => local_message,
+            None => {
+                info!(
+                    "Connection to peer '{}' was closed; dropping stream",
+                    self.socket_address
+                );
+
+                // Notify sender tx is closed
+                ctx.send_from_address(
+                    self.addresses.sender_internal_address().clone(),
+                    QuicSendWorkerMsg::ConnectionClosed,
+                    self.addresses.receiver_internal_address().clone(),
+                )
+                .await?;
+                return Ok(false);
+            }
+        };
+
+        if !local_message.has_next_on_onward_route() {
+            trace!("Got heartbeat message from: {}", self.socket_address);
+            return Ok(true);
+        }
+
+        // Insert the peer address into the return route so that
+        // reply routing can be properly resolved
+        let local_message = local_message.push_front_return_route(self.addresses.sender_address());
+
+        trace!("Message onward route: {}", local_message.onward_route_ref());
+        trace!("Message return route: {}", local_message.return_route_ref());
+
+        // Forward the message to the next hop in the route
+        ctx.forward_from_address(local_message, self.addresses.receiver_address().clone())
+            .await?;
+        Ok(true)
+    }
+}
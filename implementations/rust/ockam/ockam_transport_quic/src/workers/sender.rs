@@ -0,0 +1,277 @@
+use crate::workers::Addresses;
+use crate::{QuicConnectionMode, QuicRegistry, QuicSenderInfo};
+use core::hash::{Hash, Hasher};
+use core::time::Duration;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::flow_control::FlowControlId;
+use ockam_core::{
+    async_trait,
+    compat::{net::SocketAddr, sync::Arc},
+    AllowSourceAddress, DenyAll, IncomingAccessControl,
+};
+use ockam_core::{Address, Any, Decodable, Mailbox, Mailboxes, Message, Result, Routed, Worker};
+use ockam_node::{Context, WorkerBuilder};
+use ockam_transport_core::{encode_transport_message, TransportError};
+use quinn::{Connection, SendStream, VarInt};
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::hash_map::DefaultHasher;
+use tracing::{info, instrument, trace, warn};
+
+/// Maximum number of streams opened by a sender. Each next hop is always sent on the
+/// same stream, chosen by hashing its address, so that its messages are kept in order
+const MAX_OPEN_STREAMS: usize = 64;
+
+/// How long to wait for the other side to read the pending messages when the sender stops
+const FINISH_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Message, Clone)]
+pub(crate) enum QuicSendWorkerMsg {
+    ConnectionClosed,
+}
+
+/// A QUIC sending message worker
+///
+/// This half of the worker is created when spawning a new connection
+/// worker pair, and listens for messages from the node message system
+/// to dispatch to a remote peer.
+///
+/// The messages for a given next hop are written, in order, on a long-lived
+/// unidirectional stream, so that a lost packet for one next hop does not
+/// delay the messages sent to the next hops using other streams.
+pub(crate) struct QuicSendWorker {
+    registry: QuicRegistry,
+    connection: Connection,
+    socket_address: SocketAddr,
+    addresses: Addresses,
+    mode: QuicConnectionMode,
+    receiver_flow_control_id: FlowControlId,
+    /// Streams, opened on first use, indexed by the hash of the next hops
+    streams: BTreeMap<usize, SendStream>,
+    rx_should_be_stopped: bool,
+}
+
+impl QuicSendWorker {
+    /// Create a new `QuicSendWorker`
+    fn new(
+        registry: QuicRegistry,
+        connection: Connection,
+        socket_address: SocketAddr,
+        addresses: Addresses,
+        mode: QuicConnectionMode,
+        receiver_flow_control_id: FlowControlId,
+    ) -> Self {
+        Self {
+            registry,
+            connection,
+            socket_address,
+            addresses,
+            mode,
+            receiver_flow_control_id,
+            streams: BTreeMap::new(),
+            rx_should_be_stopped: true,
+        }
+    }
+}
+
+impl QuicSendWorker {
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, name = "QuicSendWorker::start")]
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: QuicRegistry,
+        connection: Connection,
+        addresses: &Addresses,
+        socket_address: SocketAddr,
+        mode: QuicConnectionMode,
+        sender_incoming_access_control: Arc<dyn IncomingAccessControl>,
+        receiver_flow_control_id: &FlowControlId,
+    ) -> Result<()> {
+        trace!("Creating new QUIC worker pair");
+        let sender_worker = Self::new(
+            registry,
+            connection,
+            socket_address,
+            addresses.clone(),
+            mode,
+            receiver_flow_control_id.clone(),
+        );
+
+        let main_mailbox = Mailbox::new(
+            addresses.sender_address().clone(),
+            sender_incoming_access_control,
+            Arc::new(DenyAll),
+        );
+
+        let internal_mailbox = Mailbox::new(
+            addresses.sender_internal_address().clone(),
+            Arc::new(AllowSourceAddress(
+                addresses.receiver_internal_address().clone(),
+            )),
+            Arc::new(DenyAll),
+        );
+
+        WorkerBuilder::new(sender_worker)
+            .with_mailboxes(Mailboxes::new(main_mailbox, vec![internal_mailbox]))
+            .terminal(addresses.sender_address().clone())
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "QuicSendWorker::stop")]
+    async fn stop(&self, ctx: &Context) -> Result<()> {
+        ctx.stop_worker(self.addresses.sender_address().clone())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Return the stream used for the messages sent to `next_hop`, opening it if necessary.
+    /// A stream is never finished before the sender stops, since the messages written on
+    /// a new stream could be read before the end of the previous one
+    async fn stream(&mut self, next_hop: &Address) -> Result<&mut SendStream> {
+        match self.streams.entry(stream_index(next_hop)) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let stream = self
+                    .connection
+                    .open_uni()
+                    .await
+                    .map_err(|_| TransportError::ConnectionDrop)?;
+                Ok(entry.insert(stream))
+            }
+        }
+    }
+
+    /// Finish all the streams and wait, for a short time, until the other side has read them
+    async fn finish_streams(&mut self) {
+        let streams = core::mem::take(&mut self.streams);
+        let read = async move {
+            for (_, mut stream) in streams {
+                if stream.finish().is_ok() {
+                    let _ = stream.stopped().await;
+                }
+            }
+        };
+        let _ = tokio::time::timeout(FINISH_TIMEOUT, read).await;
+    }
+}
+
+#[async_trait]
+impl Worker for QuicSendWorker {
+    type Context = Context;
+    type Message = Any;
+
+    #[instrument(skip_all, name = "QuicSendWorker::initialize")]
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry.add_sender_worker(QuicSenderInfo::new(
+            self.addresses.sender_address().clone(),
+            self.addresses.receiver_address().clone(),
+            self.socket_address,
+            self.mode,
+            self.receiver_flow_control_id.clone(),
+        ));
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "QuicSendWorker::shutdown")]
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry
+            .remove_sender_worker(self.addresses.sender_address());
+
+        if self.rx_should_be_stopped {
+            self.finish_streams().await;
+        }
+        self.connection.close(VarInt::from_u32(0), b"closed");
+
+        if self.rx_should_be_stopped {
+            let _ = ctx
+                .stop_processor(self.addresses.receiver_address().clone())
+                .await;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "QuicSendWorker::handle_message", fields(worker = %ctx.address()))]
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let recipient = msg.msg_addr();
+        if &recipient == self.addresses.sender_internal_address() {
+            let msg = QuicSendWorkerMsg::decode(msg.payload())?;
+
+            match msg {
+                QuicSendWorkerMsg::ConnectionClosed => {
+                    info!(
+                        "Stopping sender due to closed connection {}",
+                        self.socket_address
+                    );
+                    // No need to stop Receiver as it notified us about connection drop and will
+                    // stop itself
+                    self.rx_should_be_stopped = false;
+                    self.stop(ctx).await?;
+
+                    return Ok(());
+                }
+            }
+        }
+
+        let mut local_message = msg.into_local_message();
+        // Remove our own address from the route so the other end
+        // knows what to do with the incoming message
+        local_message = local_message.pop_front_onward_route()?;
+        // Keep-alive packets are sent by QUIC itself
+        let next_hop = match local_message.next_on_onward_route() {
+            Ok(next_hop) => next_hop,
+            Err(_) => {
+                trace!("Dropping a message without onward route");
+                return Ok(());
+            }
+        };
+        // Create a message buffer with prepended length
+        let transport_message = local_message.into_transport_message();
+        let msg = encode_transport_message(transport_message)?;
+
+        let written = match self.stream(&next_hop).await {
+            Ok(stream) => stream.write_all(msg.as_slice()).await.is_ok(),
+            Err(_) => false,
+        };
+
+        if !written {
+            warn!("Failed to send message to peer {}", self.socket_address);
+            self.stop(ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Return the index of the stream used for a next hop
+fn stream_index(next_hop: &Address) -> usize {
+    let mut hasher = DefaultHasher::new();
+    next_hop.hash(&mut hasher);
+    (hasher.finish() % MAX_OPEN_STREAMS as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_next_hop_always_uses_the_same_stream() {
+        for i in 0..1000 {
+            let next_hop = Address::from_string(format!("next_hop_{i}"));
+            let index = stream_index(&next_hop);
+            assert!(index < MAX_OPEN_STREAMS);
+            assert_eq!(stream_index(&next_hop.clone()), index);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use core::time::Duration;
+use ockam_core::{route, Result, Routed, Worker};
+use ockam_node::Context;
+use ockam_transport_quic::rendezvous::QuicRendezvousClient;
+use ockam_transport_quic::{QuicConnectionOptions, QuicListenerOptions, QuicTransport};
+use ockam_transport_udp::{UdpRendezvousService, UdpTransport};
+use std::io::ErrorKind;
+use std::net::UdpSocket;
+use std::time::Instant;
+
+pub struct Echoer;
+
+#[ockam_core::worker]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.into_body()?).await
+    }
+}
+
+/// Two nodes find each other with a rendezvous service, then connect with the
+/// sockets registered to the service
+#[ockam_macros::test]
+async fn connect_through_rendezvous(ctx: &mut Context) -> Result<()> {
+    // Rendezvous service, listening on a free local port
+    let rendezvous_address = UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let udp = UdpTransport::create(ctx).await?;
+    udp.listen(rendezvous_address.to_string()).await?;
+    UdpRendezvousService::start(ctx, "rendezvous").await?;
+    let rendezvous = QuicRendezvousClient::new(rendezvous_address, "rendezvous");
+
+    // The listening node registers its socket
+    let listener_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let public_address = rendezvous.register(&listener_socket, "listener").await?;
+    assert_eq!(public_address, listener_socket.local_addr().unwrap());
+
+    // The socket of the caller is still blocking
+    listener_socket
+        .set_read_timeout(Some(Duration::from_millis(200)))
+        .unwrap();
+    let start = Instant::now();
+    let error = listener_socket.recv_from(&mut [0; 16]).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::WouldBlock | ErrorKind::TimedOut
+    ));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    let options = QuicListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &options.spawner_flow_control_id());
+    ctx.start_worker("echoer", Echoer).await?;
+    let quic = QuicTransport::create(ctx).await?;
+    quic.listen_on_socket(listener_socket, options).await?;
+
+    // The connecting node looks up the listening node
+    let connector_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    rendezvous.register(&connector_socket, "connector").await?;
+    let peer = rendezvous.lookup(&connector_socket, "listener").await?;
+    assert_eq!(peer, public_address);
+
+    let connection = quic
+        .connect_from_socket(
+            connector_socket,
+            peer.to_string(),
+            QuicConnectionOptions::new(),
+        )
+        .await?;
+    let reply = ctx
+        .send_and_receive::<String>(
+            route![connection.sender_address().clone(), "echoer"],
+            "Hello".to_string(),
+        )
+        .await?;
+    assert_eq!(reply, "Hello");
+
+    // An unknown name can't be looked up
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    assert!(rendezvous.lookup(&socket, "unknown").await.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use ockam_core::{route, Result, Routed, Worker};
+use ockam_node::Context;
+use ockam_transport_quic::{QuicConnectionOptions, QuicListenerOptions, QuicTransport};
+
+pub struct Echoer;
+
+#[ockam_core::worker]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.into_body()?).await
+    }
+}
+
+#[ockam_macros::test]
+async fn send_receive(ctx: &mut Context) -> Result<()> {
+    let options = QuicListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &options.spawner_flow_control_id());
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let transport = QuicTransport::create(ctx).await?;
+    let listener = transport.listen("127.0.0.1:0", options).await?;
+
+    let connection = transport
+        .connect(listener.socket_string(), QuicConnectionOptions::new())
+        .await?;
+
+    // Several messages are sent on the same stream, in order
+    for i in 0..10 {
+        let msg = format!("Hello {i}");
+        let reply = ctx
+            .send_and_receive::<String>(
+                route![connection.sender_address().clone(), "echoer"],
+                msg.clone(),
+            )
+            .await?;
+        assert_eq!(reply, msg, "Should receive the same message");
+    }
+
+    // The connection survives a change of local address
+    connection.rebind(std::net::UdpSocket::bind("127.0.0.1:0").unwrap())?;
+    let reply = ctx
+        .send_and_receive::<String>(
+            route![connection.sender_address().clone(), "echoer"],
+            "Hello again".to_string(),
+        )
+        .await?;
+    assert_eq!(reply, "Hello again");
+
+    Ok(())
+}
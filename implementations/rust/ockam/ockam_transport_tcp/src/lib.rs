@@ -18,14 +18,21 @@ extern crate core;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod metrics;
 mod options;
 mod portal;
 mod registry;
 mod transport;
 
+pub use metrics::{
+    TCP_BYTES_RECEIVED, TCP_BYTES_SENT, TCP_PORTAL_SESSIONS, TCP_PORTAL_SESSIONS_STARTED,
+};
 use ockam_core::TransportType;
 pub use options::{TcpConnectionOptions, TcpListenerOptions};
-pub use portal::{PortalInternalMessage, PortalMessage, MAX_PAYLOAD_SIZE};
+pub use portal::{
+    PortalInternalMessage, PortalMessage, TcpInletTls, TcpPortalConnectionInfo, TcpPortalLimits,
+    TcpPortalStats, DEFAULT_PORTAL_RECEIVE_WINDOW, MAX_PAYLOAD_SIZE,
+};
 pub use registry::*;
 pub use transport::common::*;
 pub use transport::*;
@@ -0,0 +1,70 @@
+use ockam_core::compat::net::SocketAddr;
+use ockam_core::Address;
+use ockam_node::metrics::{Counter, Gauge, MetricsRegistry};
+
+/// Number of bytes written to each TCP connection
+pub const TCP_BYTES_SENT: &str = "ockam_tcp_bytes_sent_total";
+
+/// Number of bytes read from each TCP connection
+pub const TCP_BYTES_RECEIVED: &str = "ockam_tcp_bytes_received_total";
+
+/// Number of portal sessions started, per portal type
+pub const TCP_PORTAL_SESSIONS_STARTED: &str = "ockam_tcp_portal_sessions_started_total";
+
+/// Number of active portal sessions, per portal type
+pub const TCP_PORTAL_SESSIONS: &str = "ockam_tcp_portal_sessions";
+
+/// Counter of the bytes written to the connection handled by the given sender worker
+pub(crate) fn bytes_sent(sender_address: &Address, peer: &SocketAddr) -> Counter {
+    MetricsRegistry::global().counter(
+        TCP_BYTES_SENT,
+        "Number of bytes written to a TCP connection",
+        &connection_labels(&sender_address.to_string(), &peer.to_string()),
+    )
+}
+
+/// Counter of the bytes read from the connection handled by the given sender worker
+pub(crate) fn bytes_received(sender_address: &Address, peer: &SocketAddr) -> Counter {
+    MetricsRegistry::global().counter(
+        TCP_BYTES_RECEIVED,
+        "Number of bytes read from a TCP connection",
+        &connection_labels(&sender_address.to_string(), &peer.to_string()),
+    )
+}
+
+/// Remove the series of a closed connection
+pub(crate) fn remove_connection_metrics(
+    name: &'static str,
+    sender_address: &Address,
+    peer: &SocketAddr,
+) {
+    MetricsRegistry::global().remove(
+        name,
+        &connection_labels(&sender_address.to_string(), &peer.to_string()),
+    );
+}
+
+fn connection_labels<'a>(connection: &'a str, peer: &'a str) -> [(&'static str, &'a str); 2] {
+    [("connection", connection), ("peer", peer)]
+}
+
+/// Record the start of a portal session and return the gauge of active sessions,
+/// to be decremented when the session ends
+pub(crate) fn start_portal_session(portal_type: &'static str) -> Gauge {
+    let registry = MetricsRegistry::global();
+    let labels = [("type", portal_type)];
+    registry
+        .counter(
+            TCP_PORTAL_SESSIONS_STARTED,
+            "Number of TCP portal sessions started",
+            &labels,
+        )
+        .inc();
+    let sessions = registry.gauge(
+        TCP_PORTAL_SESSIONS,
+        "Number of active TCP portal sessions",
+        &labels,
+    );
+    sessions.inc();
+    sessions
+}
@@ -1,4 +1,5 @@
 use crate::workers::Addresses;
+use crate::{TcpProxy, TcpProxyConfig};
 use ockam_core::compat::sync::Arc;
 use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
 use ockam_core::{Address, AllowAll, IncomingAccessControl, OutgoingAccessControl};
@@ -13,6 +14,7 @@ pub(crate) struct TcpConnectionAccessControl {
 pub struct TcpConnectionOptions {
     pub(super) consumer: Vec<FlowControlId>,
     pub(crate) flow_control_id: FlowControlId,
+    pub(crate) proxy: TcpProxyConfig,
 }
 
 impl TcpConnectionOptions {
@@ -22,9 +24,26 @@ impl TcpConnectionOptions {
         Self {
             consumer: vec![],
             flow_control_id: FlowControls::generate_flow_control_id(),
+            proxy: TcpProxyConfig::default(),
         }
     }
 
+    /// Connect through the given proxy
+    pub fn with_proxy(self, proxy: TcpProxy) -> Self {
+        self.with_proxy_config(TcpProxyConfig::Proxy(proxy))
+    }
+
+    /// Connect directly, ignoring the proxy environment variables
+    pub fn without_proxy(self) -> Self {
+        self.with_proxy_config(TcpProxyConfig::NoProxy)
+    }
+
+    /// Set the proxy configuration. By default, the proxy environment variables are used
+    pub fn with_proxy_config(mut self, proxy: TcpProxyConfig) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
     /// Mark that this Connection is a Consumer for to the given [`FlowControlId`]
     pub fn as_consumer(mut self, id: &FlowControlId) -> Self {
         self.consumer.push(id.clone());
@@ -1,23 +1,29 @@
 use crate::portal::addresses::{Addresses, PortalType};
+use crate::portal::TcpPortalLimiter;
 use crate::{portal::TcpPortalWorker, HostnamePort, TcpInletOptions, TcpRegistry};
 use ockam_core::compat::net::SocketAddr;
+use ockam_core::compat::sync::Arc;
 use ockam_core::{async_trait, compat::boxed::Box};
 use ockam_core::{Address, Processor, Result, Route};
-use ockam_node::Context;
+use ockam_node::{Context, ProcessorBuilder, RestartPolicy};
 use ockam_transport_core::TransportError;
 use tokio::net::TcpListener;
-use tracing::{debug, error, instrument};
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, error, instrument, warn};
 
 /// A TCP Portal Inlet listen processor
 ///
 /// TCP Portal Inlet listen processors are created by `TcpTransport`
 /// after a call is made to
 /// [`TcpTransport::create_inlet`](crate::TcpTransport::create_inlet).
+#[derive(Clone)]
 pub(crate) struct TcpInletListenProcessor {
     registry: TcpRegistry,
-    inner: TcpListener,
+    inner: Arc<TcpListener>,
     outlet_listener_route: Route,
-    options: TcpInletOptions,
+    options: Arc<TcpInletOptions>,
+    tls_acceptor: Option<TlsAcceptor>,
+    limiter: TcpPortalLimiter,
 }
 
 impl TcpInletListenProcessor {
@@ -26,12 +32,16 @@ impl TcpInletListenProcessor {
         inner: TcpListener,
         outlet_listener_route: Route,
         options: TcpInletOptions,
+        tls_acceptor: Option<TlsAcceptor>,
     ) -> Self {
+        let limiter = TcpPortalLimiter::new(options.limits.clone(), options.stats.clone());
         Self {
             registry,
-            inner,
+            inner: Arc::new(inner),
             outlet_listener_route,
-            options,
+            options: Arc::new(options),
+            tls_acceptor,
+            limiter,
         }
     }
 
@@ -45,6 +55,7 @@ impl TcpInletListenProcessor {
         options: TcpInletOptions,
     ) -> Result<(SocketAddr, Address)> {
         let processor_address = Address::random_tagged("TcpInletListenProcessor");
+        let tls_acceptor = options.tls.as_ref().map(|tls| tls.acceptor()).transpose()?;
 
         debug!("Binding TcpPortalListenerWorker to {}", addr);
         let inner = match TcpListener::bind(addr).await {
@@ -55,9 +66,19 @@ impl TcpInletListenProcessor {
             }
         };
         let socket_addr = inner.local_addr().map_err(TransportError::from)?;
-        let processor = Self::new(registry, inner, outlet_listener_route, options);
+        let processor = Self::new(
+            registry,
+            inner,
+            outlet_listener_route,
+            options,
+            tls_acceptor,
+        );
 
-        ctx.start_processor(processor_address.clone(), processor)
+        // A restarted processor keeps listening on the same socket
+        ProcessorBuilder::new(processor)
+            .with_address(processor_address.clone())
+            .with_restart_policy(RestartPolicy::one_for_one())
+            .start(ctx)
             .await?;
 
         Ok((socket_addr, processor_address))
@@ -85,6 +106,17 @@ impl Processor for TcpInletListenProcessor {
 
     #[instrument(skip_all, name = "TcpInletListenProcessor::process")]
     async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let (stream, socket_addr) = self.inner.accept().await.map_err(TransportError::from)?;
+
+        let connection = match self.limiter.open_connection(socket_addr.to_string()) {
+            Some(connection) => connection,
+            None => {
+                // Dropping the stream closes the connection
+                warn!(%socket_addr, "Too many connections, rejecting a new inlet connection");
+                return Ok(true);
+            }
+        };
+
         let addresses = Addresses::generate(PortalType::Inlet);
         let outlet_listener_route = self.outlet_listener_route.clone();
 
@@ -94,11 +126,13 @@ impl Processor for TcpInletListenProcessor {
             outlet_listener_route.next()?,
         );
 
-        let (stream, socket_addr) = self.inner.accept().await.map_err(TransportError::from)?;
         TcpPortalWorker::start_new_inlet(
             ctx,
             self.registry.clone(),
             stream,
+            self.tls_acceptor.clone(),
+            connection,
+            self.options.receive_window,
             HostnamePort::from_socket_addr(socket_addr)?,
             outlet_listener_route,
             addresses,
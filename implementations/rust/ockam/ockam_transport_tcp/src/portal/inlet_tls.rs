@@ -0,0 +1,213 @@
+use core::fmt;
+use core::fmt::{Debug, Formatter};
+use ockam_core::compat::sync::Arc;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+use rcgen::{CertificateParams, KeyPair};
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+/// TLS configuration of a TCP Inlet.
+///
+/// When it is set on the [`TcpInletOptions`](crate::TcpInletOptions), the inlet terminates
+/// the TLS connections of its clients and forwards the decrypted stream to the outlet.
+pub struct TcpInletTls {
+    certificate_chain: Vec<CertificateDer<'static>>,
+    private_key: PrivateKeyDer<'static>,
+    client_certificate_authorities: Option<RootCertStore>,
+}
+
+impl TcpInletTls {
+    /// Serve the given PEM-encoded certificate chain, signed by the PEM-encoded private key
+    pub fn from_pem(certificate_chain: &[u8], private_key: &[u8]) -> Result<Self> {
+        let certificate_chain = parse_certificates(certificate_chain)?;
+        if certificate_chain.is_empty() {
+            return Err(tls_error("No certificate found in the certificate chain"));
+        }
+
+        let private_key = rustls_pemfile::private_key(&mut &*private_key)
+            .map_err(|e| tls_error(format!("Cannot parse the private key: {e}")))?
+            .ok_or_else(|| tls_error("No private key found"))?;
+
+        Ok(Self {
+            certificate_chain,
+            private_key,
+            client_certificate_authorities: None,
+        })
+    }
+
+    /// Serve a self-signed certificate valid for the given names, with a freshly generated key.
+    /// Use [`TcpInletTls::self_signed_with_private_key`] to serve the same certificate
+    /// across restarts
+    pub fn self_signed(subject_alt_names: Vec<String>) -> Result<Self> {
+        let private_key = Self::generate_private_key()?;
+        Self::self_signed_with_private_key(subject_alt_names, private_key.as_bytes())
+    }
+
+    /// Serve a self-signed certificate valid for the given names, signed by the
+    /// PEM-encoded private key. The certificate is derived from the key, so that
+    /// clients can trust it once for all
+    pub fn self_signed_with_private_key(
+        subject_alt_names: Vec<String>,
+        private_key: &[u8],
+    ) -> Result<Self> {
+        let private_key = core::str::from_utf8(private_key)
+            .map_err(|e| tls_error(format!("Cannot parse the private key: {e}")))?;
+        let key_pair = KeyPair::from_pem(private_key)
+            .map_err(|e| tls_error(format!("Cannot parse the private key: {e}")))?;
+        let certificate = CertificateParams::new(subject_alt_names)
+            .and_then(|params| params.self_signed(&key_pair))
+            .map_err(|e| tls_error(format!("Cannot generate a self-signed certificate: {e}")))?;
+
+        Ok(Self {
+            certificate_chain: vec![certificate.der().clone()],
+            private_key: PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der())),
+            client_certificate_authorities: None,
+        })
+    }
+
+    /// Generate a new PEM-encoded private key for a self-signed certificate
+    pub fn generate_private_key() -> Result<String> {
+        let key_pair = KeyPair::generate()
+            .map_err(|e| tls_error(format!("Cannot generate a private key: {e}")))?;
+        Ok(key_pair.serialize_pem())
+    }
+
+    /// Require the clients to present a certificate signed by one of the
+    /// given PEM-encoded certificate authorities
+    pub fn with_client_authentication(mut self, certificate_authorities: &[u8]) -> Result<Self> {
+        let mut root_cert_store = RootCertStore::empty();
+        for certificate in parse_certificates(certificate_authorities)? {
+            root_cert_store
+                .add(certificate)
+                .map_err(|e| tls_error(format!("Invalid client certificate authority: {e}")))?;
+        }
+        if root_cert_store.is_empty() {
+            return Err(tls_error("No client certificate authority found"));
+        }
+
+        self.client_certificate_authorities = Some(root_cert_store);
+        Ok(self)
+    }
+
+    /// Return the DER-encoded certificate chain served by the inlet
+    pub fn certificate_chain(&self) -> &[CertificateDer<'static>] {
+        &self.certificate_chain
+    }
+
+    /// Return true if the clients must present a certificate
+    pub fn requires_client_authentication(&self) -> bool {
+        self.client_certificate_authorities.is_some()
+    }
+
+    /// Create the acceptor used by the inlet for each new connection
+    pub(crate) fn acceptor(&self) -> Result<TlsAcceptor> {
+        let builder = ServerConfig::builder();
+        let builder = match &self.client_certificate_authorities {
+            Some(roots) => {
+                let verifier = WebPkiClientVerifier::builder(Arc::new(roots.clone()))
+                    .build()
+                    .map_err(|e| tls_error(format!("Cannot verify client certificates: {e}")))?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(self.certificate_chain.clone(), self.private_key.clone_key())
+            .map_err(|e| tls_error(format!("Invalid certificate or private key: {e}")))?;
+
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+impl Clone for TcpInletTls {
+    fn clone(&self) -> Self {
+        Self {
+            certificate_chain: self.certificate_chain.clone(),
+            private_key: self.private_key.clone_key(),
+            client_certificate_authorities: self.client_certificate_authorities.clone(),
+        }
+    }
+}
+
+impl Debug for TcpInletTls {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpInletTls")
+            .field("certificates", &self.certificate_chain.len())
+            .field(
+                "client_authentication",
+                &self.requires_client_authentication(),
+            )
+            .finish()
+    }
+}
+
+fn parse_certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>> {
+    rustls_pemfile::certs(&mut &*pem)
+        .collect::<core::result::Result<Vec<_>, _>>()
+        .map_err(|e| tls_error(format!("Cannot parse the certificates: {e}")))
+}
+
+fn tls_error(message: impl Into<String>) -> Error {
+    Error::new(Origin::Transport, Kind::Invalid, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_signed_certificate_can_be_served() -> Result<()> {
+        let tls = TcpInletTls::self_signed(vec!["localhost".to_string()])?;
+        assert_eq!(tls.certificate_chain().len(), 1);
+        assert!(!tls.requires_client_authentication());
+        tls.acceptor()?;
+        Ok(())
+    }
+
+    #[test]
+    fn self_signed_certificate_is_derived_from_its_private_key() -> Result<()> {
+        let private_key = TcpInletTls::generate_private_key()?;
+        let names = vec!["localhost".to_string()];
+        let tls1 =
+            TcpInletTls::self_signed_with_private_key(names.clone(), private_key.as_bytes())?;
+        let tls2 = TcpInletTls::self_signed_with_private_key(names, private_key.as_bytes())?;
+
+        // Both certificates contain the public key of the private key
+        let public_key = KeyPair::from_pem(&private_key)
+            .unwrap()
+            .public_key_raw()
+            .to_vec();
+        for tls in [tls1, tls2] {
+            let certificate = tls.certificate_chain()[0].as_ref();
+            assert!(certificate
+                .windows(public_key.len())
+                .any(|bytes| bytes == public_key.as_slice()));
+        }
+        assert!(TcpInletTls::self_signed_with_private_key(vec![], b"not a key").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn pem_certificate_can_be_served_with_client_authentication() -> Result<()> {
+        let certified_key = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+            .map_err(|e| tls_error(e.to_string()))?;
+        let certificate = certified_key.cert.pem();
+        let private_key = certified_key.key_pair.serialize_pem();
+
+        let tls = TcpInletTls::from_pem(certificate.as_bytes(), private_key.as_bytes())?
+            .with_client_authentication(certificate.as_bytes())?;
+        assert!(tls.requires_client_authentication());
+        tls.acceptor()?;
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pem_is_rejected() {
+        assert!(TcpInletTls::from_pem(b"not a certificate", b"not a key").is_err());
+        let tls = TcpInletTls::self_signed(vec!["localhost".to_string()]).unwrap();
+        assert!(tls.with_client_authentication(b"").is_err());
+    }
+}
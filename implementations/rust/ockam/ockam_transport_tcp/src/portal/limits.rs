@@ -0,0 +1,558 @@
+use core::time::Duration;
+use minicbor::{Decode, Encode};
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+use ockam_core::LocalInfo;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Weak;
+use std::time::{Instant, SystemTime};
+use tokio::sync::Notify;
+
+/// Limits applied to the connections of a TCP Inlet or a TCP Outlet
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct TcpPortalLimits {
+    #[n(1)] max_connections: Option<usize>,
+    #[n(2)] connection_bytes_per_second: Option<u64>,
+    #[n(3)] total_bytes_per_second: Option<u64>,
+    #[n(4)] idle_timeout: Option<Duration>,
+}
+
+impl TcpPortalLimits {
+    /// No limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject the new connections when `max_connections` connections are already open
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    /// Limit the number of bytes per second transferred by each connection, in both directions
+    pub fn with_connection_rate_limit(mut self, bytes_per_second: u64) -> Self {
+        self.connection_bytes_per_second = Some(bytes_per_second);
+        self
+    }
+
+    /// Limit the number of bytes per second transferred by all the connections, in both directions
+    pub fn with_total_rate_limit(mut self, bytes_per_second: u64) -> Self {
+        self.total_bytes_per_second = Some(bytes_per_second);
+        self
+    }
+
+    /// Close the connections which did not transfer any byte for `idle_timeout`
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Maximum number of concurrent connections
+    pub fn max_connections(&self) -> Option<usize> {
+        self.max_connections
+    }
+
+    /// Maximum number of bytes per second for each connection
+    pub fn connection_rate_limit(&self) -> Option<u64> {
+        self.connection_bytes_per_second
+    }
+
+    /// Maximum number of bytes per second for all the connections
+    pub fn total_rate_limit(&self) -> Option<u64> {
+        self.total_bytes_per_second
+    }
+
+    /// Delay after which an inactive connection is closed
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+}
+
+/// Counters of the connections of a TCP Inlet or a TCP Outlet.
+///
+/// The counters are shared by all the clones of a `TcpPortalStats`.
+#[derive(Clone, Debug, Default)]
+pub struct TcpPortalStats {
+    inner: Arc<TcpPortalStatsInner>,
+}
+
+#[derive(Debug, Default)]
+struct TcpPortalStatsInner {
+    active_connections: AtomicUsize,
+    total_connections: AtomicU64,
+    rejected_connections: AtomicU64,
+    bytes_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    connections: Mutex<BTreeMap<u64, Weak<PortalConnectionInner>>>,
+}
+
+impl TcpPortalStats {
+    /// Number of currently open connections
+    pub fn active_connections(&self) -> usize {
+        self.inner.active_connections.load(Ordering::Relaxed)
+    }
+
+    /// Number of accepted connections since the creation of the portal
+    pub fn total_connections(&self) -> u64 {
+        self.inner.total_connections.load(Ordering::Relaxed)
+    }
+
+    /// Number of connections rejected because the maximum number of connections was reached
+    pub fn rejected_connections(&self) -> u64 {
+        self.inner.rejected_connections.load(Ordering::Relaxed)
+    }
+
+    /// Number of bytes read from the local TCP connections
+    pub fn bytes_received(&self) -> u64 {
+        self.inner.bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Number of bytes written to the local TCP connections
+    pub fn bytes_sent(&self) -> u64 {
+        self.inner.bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Return the currently open connections
+    pub fn connections(&self) -> Vec<TcpPortalConnectionInfo> {
+        // The connections are collected before creating their info, so that the last
+        // reference to a connection is never dropped while the lock is held
+        let connections: Vec<Arc<PortalConnectionInner>> = match self.inner.connections.lock() {
+            Ok(connections) => connections.values().filter_map(Weak::upgrade).collect(),
+            Err(_) => return vec![],
+        };
+        connections
+            .iter()
+            .map(|connection| connection.info())
+            .collect()
+    }
+
+    /// Return the open connection with the given id
+    pub fn connection(&self, id: u64) -> Option<TcpPortalConnectionInfo> {
+        self.find_connection(id).map(|connection| connection.info())
+    }
+
+    /// Close the open connection with the given id.
+    /// Return false if there is no such connection
+    pub fn close_connection(&self, id: u64) -> bool {
+        match self.find_connection(id) {
+            Some(connection) => {
+                connection.close.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn find_connection(&self, id: u64) -> Option<Arc<PortalConnectionInner>> {
+        let connections = self.inner.connections.lock().ok()?;
+        connections.get(&id).and_then(Weak::upgrade)
+    }
+
+    /// Count a new connection if there are less than `max_connections` open connections,
+    /// and return its id
+    fn try_open_connection(&self, max_connections: Option<usize>) -> Option<u64> {
+        let opened = self
+            .inner
+            .active_connections
+            .fetch_update(
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                |active| match max_connections {
+                    Some(max_connections) if active >= max_connections => None,
+                    _ => Some(active + 1),
+                },
+            )
+            .is_ok();
+
+        if opened {
+            Some(self.inner.total_connections.fetch_add(1, Ordering::Relaxed) + 1)
+        } else {
+            self.inner
+                .rejected_connections
+                .fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+
+    fn register_connection(&self, id: u64, connection: &Arc<PortalConnectionInner>) {
+        if let Ok(mut connections) = self.inner.connections.lock() {
+            connections.insert(id, Arc::downgrade(connection));
+        }
+    }
+
+    fn unregister_connection(&self, id: u64) {
+        if let Ok(mut connections) = self.inner.connections.lock() {
+            connections.remove(&id);
+        }
+        self.inner.active_connections.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Information about an open connection of a TCP Inlet or a TCP Outlet
+#[derive(Clone, Debug)]
+pub struct TcpPortalConnectionInfo {
+    id: u64,
+    peer_address: String,
+    remote_local_info: Vec<LocalInfo>,
+    started_at: SystemTime,
+    last_activity: SystemTime,
+    bytes_received: u64,
+    bytes_sent: u64,
+}
+
+impl TcpPortalConnectionInfo {
+    /// Identifier of the connection, unique for a given portal
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Address of the local TCP peer: the client of an inlet, or the destination of an outlet
+    pub fn peer_address(&self) -> &str {
+        &self.peer_address
+    }
+
+    /// [`LocalInfo`] of the message received from the other side of the portal
+    /// when the connection was established. It can be used to identify the remote node
+    pub fn remote_local_info(&self) -> &[LocalInfo] {
+        &self.remote_local_info
+    }
+
+    /// Time when the connection was opened
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    /// Time when the connection last transferred some data
+    pub fn last_activity(&self) -> SystemTime {
+        self.last_activity
+    }
+
+    /// Number of bytes read from the local TCP connection
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+
+    /// Number of bytes written to the local TCP connection
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+}
+
+/// Token bucket limiting a number of bytes per second.
+///
+/// The bucket can go into debt: a caller consuming more bytes than available
+/// waits until the debt is paid back.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    bytes_per_second: u64,
+    state: Mutex<TokenBucketState>,
+}
+
+#[derive(Debug)]
+struct TokenBucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second,
+            state: Mutex::new(TokenBucketState {
+                tokens: bytes_per_second as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Consume `bytes` tokens and return how long the caller must wait before going on
+    fn consume(&self, bytes: usize) -> Duration {
+        let rate = self.bytes_per_second.max(1) as f64;
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        // Allow bursts of at most one second of traffic
+        state.tokens = (state.tokens + elapsed * rate).min(rate);
+        state.last_refill = now;
+        state.tokens -= bytes as f64;
+
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / rate)
+        }
+    }
+
+    /// Wait until `bytes` can be transferred
+    pub(crate) async fn throttle(&self, bytes: usize) {
+        let delay = self.consume(bytes);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Limits and counters shared by all the connections of a TCP Inlet or a TCP Outlet
+#[derive(Clone, Debug)]
+pub(crate) struct TcpPortalLimiter {
+    limits: TcpPortalLimits,
+    stats: TcpPortalStats,
+    total_rate_limit: Option<Arc<TokenBucket>>,
+}
+
+impl TcpPortalLimiter {
+    pub(crate) fn new(limits: TcpPortalLimits, stats: TcpPortalStats) -> Self {
+        let total_rate_limit = limits
+            .total_bytes_per_second
+            .map(|bytes_per_second| Arc::new(TokenBucket::new(bytes_per_second)));
+        Self {
+            limits,
+            stats,
+            total_rate_limit,
+        }
+    }
+
+    /// Open a new connection with the given local TCP peer,
+    /// unless the maximum number of connections is reached
+    pub(crate) fn open_connection(&self, peer_address: String) -> Option<PortalConnection> {
+        let id = self
+            .stats
+            .try_open_connection(self.limits.max_connections)?;
+
+        let inner = Arc::new(PortalConnectionInner {
+            id,
+            peer_address,
+            stats: self.stats.clone(),
+            rate_limit: self
+                .limits
+                .connection_bytes_per_second
+                .map(TokenBucket::new),
+            total_rate_limit: self.total_rate_limit.clone(),
+            idle_timeout: self.limits.idle_timeout,
+            started_at: SystemTime::now(),
+            last_activity: Mutex::new(Instant::now()),
+            bytes_received: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            remote_local_info: Mutex::new(vec![]),
+            close: Notify::new(),
+        });
+        self.stats.register_connection(id, &inner);
+
+        Some(PortalConnection { inner })
+    }
+}
+
+/// Limits and counters of a single portal connection, shared by its
+/// portal worker and its receiver.
+///
+/// The connection is counted as closed once all its clones are dropped.
+#[derive(Clone, Debug)]
+pub(crate) struct PortalConnection {
+    inner: Arc<PortalConnectionInner>,
+}
+
+#[derive(Debug)]
+struct PortalConnectionInner {
+    id: u64,
+    peer_address: String,
+    stats: TcpPortalStats,
+    rate_limit: Option<TokenBucket>,
+    total_rate_limit: Option<Arc<TokenBucket>>,
+    idle_timeout: Option<Duration>,
+    started_at: SystemTime,
+    last_activity: Mutex<Instant>,
+    bytes_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    remote_local_info: Mutex<Vec<LocalInfo>>,
+    /// Notified when the connection must be closed
+    close: Notify,
+}
+
+impl PortalConnectionInner {
+    fn info(&self) -> TcpPortalConnectionInfo {
+        let idle_time = self
+            .last_activity
+            .lock()
+            .map(|last_activity| last_activity.elapsed())
+            .unwrap_or_default();
+        TcpPortalConnectionInfo {
+            id: self.id,
+            peer_address: self.peer_address.clone(),
+            remote_local_info: self
+                .remote_local_info
+                .lock()
+                .map(|local_info| local_info.clone())
+                .unwrap_or_default(),
+            started_at: self.started_at,
+            last_activity: SystemTime::now()
+                .checked_sub(idle_time)
+                .unwrap_or(self.started_at),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Drop for PortalConnectionInner {
+    fn drop(&mut self) {
+        self.stats.unregister_connection(self.id);
+    }
+}
+
+impl PortalConnection {
+    /// Account for bytes read from the local TCP connection, waiting if a rate limit is exceeded
+    pub(crate) async fn received(&self, bytes: usize) {
+        self.inner
+            .bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.inner
+            .stats
+            .inner
+            .bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.transferred(bytes).await
+    }
+
+    /// Account for bytes written to the local TCP connection, waiting if a rate limit is exceeded
+    pub(crate) async fn sent(&self, bytes: usize) {
+        self.inner
+            .bytes_sent
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.inner
+            .stats
+            .inner
+            .bytes_sent
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.transferred(bytes).await
+    }
+
+    async fn transferred(&self, bytes: usize) {
+        if let Ok(mut last_activity) = self.inner.last_activity.lock() {
+            *last_activity = Instant::now();
+        }
+        if let Some(rate_limit) = &self.inner.rate_limit {
+            rate_limit.throttle(bytes).await;
+        }
+        if let Some(total_rate_limit) = &self.inner.total_rate_limit {
+            total_rate_limit.throttle(bytes).await;
+        }
+    }
+
+    /// Keep the [`LocalInfo`] of the message received from the other side of the portal
+    pub(crate) fn set_remote_local_info(&self, local_info: Vec<LocalInfo>) {
+        if let Ok(mut remote_local_info) = self.inner.remote_local_info.lock() {
+            *remote_local_info = local_info;
+        }
+    }
+
+    /// Wait until the connection is closed with [`TcpPortalStats::close_connection`]
+    pub(crate) async fn closed(&self) {
+        self.inner.close.notified().await
+    }
+
+    /// Return the time left before the connection is considered idle,
+    /// or `None` if the connection has no idle timeout
+    pub(crate) fn idle_time_left(&self) -> Option<Duration> {
+        let idle_timeout = self.inner.idle_timeout?;
+        let elapsed = self
+            .inner
+            .last_activity
+            .lock()
+            .map(|last_activity| last_activity.elapsed())
+            .unwrap_or_default();
+        Some(idle_timeout.saturating_sub(elapsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connections_over_the_limit_are_rejected() {
+        let stats = TcpPortalStats::default();
+        let limiter = TcpPortalLimiter::new(
+            TcpPortalLimits::new().with_max_connections(2),
+            stats.clone(),
+        );
+
+        let connection1 = limiter.open_connection("127.0.0.1:5000".to_string());
+        let connection2 = limiter.open_connection("127.0.0.1:5000".to_string());
+        assert!(connection1.is_some());
+        assert!(connection2.is_some());
+        assert!(limiter
+            .open_connection("127.0.0.1:5000".to_string())
+            .is_none());
+        assert_eq!(stats.active_connections(), 2);
+        assert_eq!(stats.rejected_connections(), 1);
+
+        drop(connection1);
+        assert_eq!(stats.active_connections(), 1);
+        assert!(limiter
+            .open_connection("127.0.0.1:5000".to_string())
+            .is_some());
+        assert_eq!(stats.total_connections(), 3);
+    }
+
+    #[test]
+    fn token_bucket_delays_traffic_over_the_rate() {
+        let bucket = TokenBucket::new(1000);
+        assert_eq!(bucket.consume(1000), Duration::ZERO);
+
+        let delay = bucket.consume(500);
+        assert!(delay > Duration::from_millis(400), "{delay:?}");
+        assert!(delay <= Duration::from_millis(500), "{delay:?}");
+    }
+
+    #[tokio::test]
+    async fn traffic_is_counted_and_resets_the_idle_timeout() {
+        let stats = TcpPortalStats::default();
+        let limiter = TcpPortalLimiter::new(
+            TcpPortalLimits::new().with_idle_timeout(Duration::from_secs(60)),
+            stats.clone(),
+        );
+        let connection = limiter
+            .open_connection("127.0.0.1:5000".to_string())
+            .unwrap();
+
+        connection.received(10).await;
+        connection.sent(20).await;
+        assert_eq!(stats.bytes_received(), 10);
+        assert_eq!(stats.bytes_sent(), 20);
+        assert!(connection.idle_time_left().unwrap() > Duration::from_secs(59));
+    }
+
+    #[tokio::test]
+    async fn open_connections_can_be_listed_and_closed() {
+        let stats = TcpPortalStats::default();
+        let limiter = TcpPortalLimiter::new(TcpPortalLimits::new(), stats.clone());
+        let connection1 = limiter
+            .open_connection("127.0.0.1:5001".to_string())
+            .unwrap();
+        let connection2 = limiter
+            .open_connection("127.0.0.1:5002".to_string())
+            .unwrap();
+        connection2.received(10).await;
+
+        let connections = stats.connections();
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].peer_address(), "127.0.0.1:5001");
+        assert_eq!(connections[1].id(), 2);
+        assert_eq!(connections[1].bytes_received(), 10);
+
+        assert!(stats.close_connection(1));
+        connection1.closed().await;
+        assert!(!stats.close_connection(3));
+
+        drop(connection1);
+        assert!(stats.connection(1).is_none());
+        assert_eq!(stats.connections().len(), 1);
+    }
+}
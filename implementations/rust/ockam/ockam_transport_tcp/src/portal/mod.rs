@@ -1,13 +1,19 @@
 mod addresses;
 mod inlet_listener;
+mod inlet_tls;
+mod limits;
 pub mod options;
 mod outlet_listener;
 mod portal_message;
 mod portal_receiver;
 mod portal_worker;
+mod window;
 
 pub(crate) use inlet_listener::*;
+pub use inlet_tls::*;
+pub use limits::*;
 pub(crate) use outlet_listener::*;
 pub use portal_message::*;
 pub(crate) use portal_receiver::*;
 pub(crate) use portal_worker::*;
+pub use window::*;
@@ -1,4 +1,9 @@
 use crate::portal::addresses::Addresses;
+use crate::portal::MIN_PORTAL_RECEIVE_WINDOW;
+use crate::{
+    TcpInletTls, TcpPortalLimits, TcpPortalStats, TcpProxy, TcpProxyConfig,
+    DEFAULT_PORTAL_RECEIVE_WINDOW,
+};
 use ockam_core::compat::sync::Arc;
 use ockam_core::flow_control::{FlowControlId, FlowControls};
 use ockam_core::{Address, AllowAll, IncomingAccessControl, OutgoingAccessControl};
@@ -8,6 +13,10 @@ use ockam_core::{Address, AllowAll, IncomingAccessControl, OutgoingAccessControl
 pub struct TcpInletOptions {
     pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
     pub(super) outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    pub(super) tls: Option<TcpInletTls>,
+    pub(super) limits: TcpPortalLimits,
+    pub(super) stats: TcpPortalStats,
+    pub(super) receive_window: Option<u32>,
 }
 
 impl TcpInletOptions {
@@ -16,9 +25,51 @@ impl TcpInletOptions {
         Self {
             incoming_access_control: Arc::new(AllowAll),
             outgoing_access_control: Arc::new(AllowAll),
+            tls: None,
+            limits: TcpPortalLimits::default(),
+            stats: TcpPortalStats::default(),
+            receive_window: Some(DEFAULT_PORTAL_RECEIVE_WINDOW),
         }
     }
 
+    /// Terminate the TLS connections of the inlet clients with the given configuration
+    pub fn with_tls(mut self, tls: TcpInletTls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Limit the number of connections and their throughput
+    pub fn with_limits(mut self, limits: TcpPortalLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Count the connections into the given stats
+    pub fn with_stats(mut self, stats: TcpPortalStats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Return the counters of the connections
+    pub fn stats(&self) -> TcpPortalStats {
+        self.stats.clone()
+    }
+
+    /// Set the number of bytes that the other side of the portal can send to a connection
+    /// before the data is written to the TCP connection. The window can't be smaller than
+    /// [`MAX_PAYLOAD_SIZE`](crate::MAX_PAYLOAD_SIZE)
+    pub fn with_receive_window(mut self, bytes: u32) -> Self {
+        self.receive_window = Some(bytes.max(MIN_PORTAL_RECEIVE_WINDOW));
+        self
+    }
+
+    /// Disable the flow control of the connections. This is only needed when the other
+    /// side of the portal can't decode the flow control messages
+    pub fn without_receive_window(mut self) -> Self {
+        self.receive_window = None;
+        self
+    }
+
     /// Set Incoming Access Control
     pub fn with_incoming_access_control_impl(
         mut self,
@@ -84,6 +135,10 @@ pub struct TcpOutletOptions {
     pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
     pub(super) outgoing_access_control: Arc<dyn OutgoingAccessControl>,
     pub(super) tls: bool,
+    pub(super) proxy: TcpProxyConfig,
+    pub(super) limits: TcpPortalLimits,
+    pub(super) stats: TcpPortalStats,
+    pub(super) receive_window: Option<u32>,
 }
 
 impl TcpOutletOptions {
@@ -94,6 +149,10 @@ impl TcpOutletOptions {
             incoming_access_control: Arc::new(AllowAll),
             outgoing_access_control: Arc::new(AllowAll),
             tls: false,
+            proxy: TcpProxyConfig::default(),
+            limits: TcpPortalLimits::default(),
+            stats: TcpPortalStats::default(),
+            receive_window: Some(DEFAULT_PORTAL_RECEIVE_WINDOW),
         }
     }
 
@@ -121,6 +180,54 @@ impl TcpOutletOptions {
         self
     }
 
+    /// Connect to the outlet destination through the given proxy
+    pub fn with_proxy(self, proxy: TcpProxy) -> Self {
+        self.with_proxy_config(TcpProxyConfig::Proxy(proxy))
+    }
+
+    /// Connect directly to the outlet destination, ignoring the proxy environment variables
+    pub fn without_proxy(self) -> Self {
+        self.with_proxy_config(TcpProxyConfig::NoProxy)
+    }
+
+    /// Set the proxy configuration. By default, the proxy environment variables are used
+    pub fn with_proxy_config(mut self, proxy: TcpProxyConfig) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Limit the number of connections and their throughput
+    pub fn with_limits(mut self, limits: TcpPortalLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Count the connections into the given stats
+    pub fn with_stats(mut self, stats: TcpPortalStats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Return the counters of the connections
+    pub fn stats(&self) -> TcpPortalStats {
+        self.stats.clone()
+    }
+
+    /// Set the number of bytes that the other side of the portal can send to a connection
+    /// before the data is written to the TCP connection. The window can't be smaller than
+    /// [`MAX_PAYLOAD_SIZE`](crate::MAX_PAYLOAD_SIZE)
+    pub fn with_receive_window(mut self, bytes: u32) -> Self {
+        self.receive_window = Some(bytes.max(MIN_PORTAL_RECEIVE_WINDOW));
+        self
+    }
+
+    /// Disable the flow control of the connections. This is only needed when the other
+    /// side of the portal can't decode the flow control messages
+    pub fn without_receive_window(mut self) -> Self {
+        self.receive_window = None;
+        self
+    }
+
     /// Set Outgoing Access Control
     pub fn with_outgoing_access_control_impl(
         mut self,
@@ -1,28 +1,34 @@
 use crate::portal::addresses::{Addresses, PortalType};
+use crate::portal::TcpPortalLimiter;
 use crate::{portal::TcpPortalWorker, HostnamePort, PortalMessage, TcpOutletOptions, TcpRegistry};
-use ockam_core::{async_trait, Address, DenyAll, NeutralMessage, Result, Routed, Worker};
-use ockam_node::{Context, WorkerBuilder};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, Address, NeutralMessage, Result, Routed, Worker};
+use ockam_node::{Context, RestartPolicy, WorkerBuilder};
 use ockam_transport_core::TransportError;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 /// A TCP Portal Outlet listen worker
 ///
 /// TCP Portal Outlet listen workers are created by `TcpTransport`
 /// after a call is made to
 /// [`TcpTransport::create_outlet`](crate::TcpTransport::create_outlet).
+#[derive(Clone)]
 pub(crate) struct TcpOutletListenWorker {
     registry: TcpRegistry,
     hostname_port: HostnamePort,
-    options: TcpOutletOptions,
+    options: Arc<TcpOutletOptions>,
+    limiter: TcpPortalLimiter,
 }
 
 impl TcpOutletListenWorker {
     /// Create a new `TcpOutletListenWorker`
     fn new(registry: TcpRegistry, hostname_port: HostnamePort, options: TcpOutletOptions) -> Self {
+        let limiter = TcpPortalLimiter::new(options.limits.clone(), options.stats.clone());
         Self {
             registry,
             hostname_port,
-            options,
+            options: Arc::new(options),
+            limiter,
         }
     }
 
@@ -35,6 +41,8 @@ impl TcpOutletListenWorker {
         options: TcpOutletOptions,
     ) -> Result<()> {
         let access_control = options.incoming_access_control.clone();
+        // The listener only sends messages to reject the inlets over the connection limit
+        let outgoing_access_control = options.outgoing_access_control.clone();
 
         options.setup_flow_control_for_outlet_listener(ctx.flow_controls(), &address);
 
@@ -42,7 +50,8 @@ impl TcpOutletListenWorker {
         WorkerBuilder::new(worker)
             .with_address(address)
             .with_incoming_access_control_arc(access_control)
-            .with_outgoing_access_control(DenyAll)
+            .with_outgoing_access_control_arc(outgoing_access_control)
+            .with_restart_policy(RestartPolicy::one_for_one())
             .start(ctx)
             .await?;
 
@@ -77,12 +86,32 @@ impl Worker for TcpOutletListenWorker {
     ) -> Result<()> {
         let return_route = msg.return_route();
         let src_addr = msg.src_addr();
+        let local_info = msg.local_message().local_info();
         let body = msg.into_body()?.into_vec();
         let msg = PortalMessage::decode(&body)?;
 
-        if !matches!(msg, PortalMessage::Ping) {
-            return Err(TransportError::Protocol)?;
-        }
+        let remote_window = match msg {
+            PortalMessage::Ping(remote_window) => remote_window,
+            _ => return Err(TransportError::Protocol)?,
+        };
+
+        let connection = match self.limiter.open_connection(self.hostname_port.to_string()) {
+            Some(connection) => connection,
+            None => {
+                warn!(
+                    "Too many connections for the outlet to {}, rejecting a new inlet connection",
+                    self.hostname_port
+                );
+                ctx.send(
+                    return_route,
+                    PortalMessage::Disconnect.to_neutral_message()?,
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+
+        connection.set_remote_local_info(local_info);
 
         let addresses = Addresses::generate(PortalType::Outlet);
 
@@ -94,6 +123,10 @@ impl Worker for TcpOutletListenWorker {
             self.registry.clone(),
             self.hostname_port.clone(),
             self.options.tls,
+            self.options.proxy.clone(),
+            connection,
+            self.options.receive_window,
+            remote_window,
             return_route.clone(),
             addresses.clone(),
             self.options.incoming_access_control.clone(),
@@ -6,15 +6,20 @@ use serde::{Deserialize, Serialize};
 /// A command message type for a Portal
 #[derive(Debug, PartialEq, Eq)]
 pub enum PortalMessage<'de> {
-    /// First message that Inlet sends to the Outlet
-    Ping,
-    /// First message that Outlet sends to the Inlet
-    Pong,
+    /// First message that Inlet sends to the Outlet,
+    /// with the receive window of the Inlet if it supports flow control
+    Ping(Option<u32>),
+    /// First message that Outlet sends to the Inlet,
+    /// with the receive window of the Outlet if both sides support flow control
+    Pong(Option<u32>),
     /// Message to indicate that connection from Outlet to the target,
     /// or from the target to the Inlet was dropped
     Disconnect,
     /// Message with binary payload and packet counter
     Payload(&'de [u8], Option<u16>),
+    /// Number of bytes that the other side can send in addition to its current window,
+    /// once the data it previously sent has been written to the TCP connection
+    WindowUpdate(u32),
 }
 
 impl<'de> PortalMessage<'de> {
@@ -30,8 +35,9 @@ impl<'de> PortalMessage<'de> {
         let enum_variant = slice.get(0)?;
         let mut index = 1;
         match enum_variant {
-            0 => Some(PortalMessage::Ping),
-            1 => Some(PortalMessage::Pong),
+            // The window is an optional suffix, ignored by the older implementations
+            0 => Some(PortalMessage::Ping(Self::read_u32(slice, index))),
+            1 => Some(PortalMessage::Pong(Self::read_u32(slice, index))),
             2 => Some(PortalMessage::Disconnect),
             3 => {
                 if let Some(payload) = read_slice(slice, &mut index) {
@@ -53,10 +59,16 @@ impl<'de> PortalMessage<'de> {
                     None
                 }
             }
+            4 => Some(PortalMessage::WindowUpdate(Self::read_u32(slice, index)?)),
             _ => None,
         }
     }
 
+    fn read_u32(slice: &[u8], index: usize) -> Option<u32> {
+        let bytes = slice.get(index..index + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
     /// Shortcut to encode a PortalMessage into a NeutralMessage
     pub fn to_neutral_message(self) -> ockam_core::Result<NeutralMessage> {
         Ok(NeutralMessage::from(self.encode()?))
@@ -73,9 +85,10 @@ impl Encodable for PortalMessage<'_> {
 impl PortalMessage<'_> {
     fn internal_encode(self) -> std::io::Result<Encoded> {
         match self {
-            PortalMessage::Ping => Ok(vec![0]),
-            PortalMessage::Pong => Ok(vec![1]),
+            PortalMessage::Ping(window) => Ok(Self::encode_with_window(0, window)),
+            PortalMessage::Pong(window) => Ok(Self::encode_with_window(1, window)),
             PortalMessage::Disconnect => Ok(vec![2]),
+            PortalMessage::WindowUpdate(window) => Ok(Self::encode_with_window(4, Some(window))),
             PortalMessage::Payload(payload, counter) => {
                 // to avoid an extra allocation, it's worth doing some math
                 let capacity = 1 + payload.len() + if counter.is_some() { 3 } else { 1 } + {
@@ -95,6 +108,15 @@ impl PortalMessage<'_> {
             }
         }
     }
+
+    fn encode_with_window(variant: u8, window: Option<u32>) -> Encoded {
+        let mut vec = Vec::with_capacity(5);
+        vec.push(variant);
+        if let Some(window) = window {
+            vec.extend_from_slice(&window.to_le_bytes());
+        }
+        vec
+    }
 }
 
 /// An internal message type for a Portal
@@ -128,11 +150,11 @@ mod test {
 
         let encoded = PortalMessageV1::encode(PortalMessageV1::Ping).unwrap();
         let decoded = PortalMessage::decode(&encoded).unwrap();
-        assert!(matches!(decoded, PortalMessage::Ping));
+        assert!(matches!(decoded, PortalMessage::Ping(None)));
 
         let encoded = PortalMessageV1::encode(PortalMessageV1::Pong).unwrap();
         let decoded = PortalMessage::decode(&encoded).unwrap();
-        assert!(matches!(decoded, PortalMessage::Pong));
+        assert!(matches!(decoded, PortalMessage::Pong(None)));
 
         let encoded = PortalMessageV1::encode(PortalMessageV1::Disconnect).unwrap();
         let decoded = PortalMessage::decode(&encoded).unwrap();
@@ -151,11 +173,19 @@ mod test {
     fn newer_message_can_be_decoded() {
         let payload = "hello".as_bytes().to_vec();
 
-        let encoded = PortalMessage::encode(PortalMessage::Ping).unwrap();
+        let encoded = PortalMessage::encode(PortalMessage::Ping(None)).unwrap();
         let decoded = PortalMessageV1::decode(&encoded).unwrap();
         assert!(matches!(decoded, PortalMessageV1::Ping));
 
-        let encoded = PortalMessage::encode(PortalMessage::Pong).unwrap();
+        let encoded = PortalMessage::encode(PortalMessage::Ping(Some(1024))).unwrap();
+        let decoded = PortalMessageV1::decode(&encoded).unwrap();
+        assert!(matches!(decoded, PortalMessageV1::Ping));
+
+        let encoded = PortalMessage::encode(PortalMessage::Pong(None)).unwrap();
+        let decoded = PortalMessageV1::decode(&encoded).unwrap();
+        assert!(matches!(decoded, PortalMessageV1::Pong));
+
+        let encoded = PortalMessage::encode(PortalMessage::Pong(Some(1024))).unwrap();
         let decoded = PortalMessageV1::decode(&encoded).unwrap();
         assert!(matches!(decoded, PortalMessageV1::Pong));
 
@@ -172,18 +202,33 @@ mod test {
         }
     }
 
+    #[test]
+    fn flow_control_messages_can_be_encoded() {
+        let encoded = PortalMessage::encode(PortalMessage::Ping(Some(1024))).unwrap();
+        let decoded = PortalMessage::decode(&encoded).unwrap();
+        assert_eq!(decoded, PortalMessage::Ping(Some(1024)));
+
+        let encoded = PortalMessage::encode(PortalMessage::Pong(Some(2048))).unwrap();
+        let decoded = PortalMessage::decode(&encoded).unwrap();
+        assert_eq!(decoded, PortalMessage::Pong(Some(2048)));
+
+        let encoded = PortalMessage::encode(PortalMessage::WindowUpdate(4096)).unwrap();
+        let decoded = PortalMessage::decode(&encoded).unwrap();
+        assert_eq!(decoded, PortalMessage::WindowUpdate(4096));
+    }
+
     #[ignore]
     #[test]
     fn newer_message_can_be_encoded() {
         let payload = "hello".as_bytes().to_vec();
 
-        let encoded = PortalMessage::encode(PortalMessage::Ping).unwrap();
+        let encoded = PortalMessage::encode(PortalMessage::Ping(None)).unwrap();
         let decoded = PortalMessage::decode(&encoded).unwrap();
-        assert!(matches!(decoded, PortalMessage::Ping));
+        assert!(matches!(decoded, PortalMessage::Ping(None)));
 
-        let encoded = PortalMessage::encode(PortalMessage::Pong).unwrap();
+        let encoded = PortalMessage::encode(PortalMessage::Pong(None)).unwrap();
         let decoded = PortalMessage::decode(&encoded).unwrap();
-        assert!(matches!(decoded, PortalMessage::Pong));
+        assert!(matches!(decoded, PortalMessage::Pong(None)));
 
         let encoded = PortalMessage::encode(PortalMessage::Disconnect).unwrap();
         let decoded = PortalMessage::decode(&encoded).unwrap();
@@ -1,6 +1,9 @@
 use crate::portal::addresses::Addresses;
 use crate::portal::portal_message::MAX_PAYLOAD_SIZE;
+use crate::portal::{PortalConnection, SendWindow};
 use crate::{PortalInternalMessage, PortalMessage, TcpRegistry};
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
 use ockam_core::{
     async_trait, Encodable, LocalMessage, OpenTelemetryContext, Route, OCKAM_TRACER_NAME,
@@ -11,7 +14,7 @@ use opentelemetry::global;
 use opentelemetry::trace::Tracer;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
-use tracing::{error, instrument, warn};
+use tracing::{debug, error, instrument, warn};
 
 /// A TCP Portal receiving message processor
 ///
@@ -25,6 +28,8 @@ pub(crate) struct TcpPortalRecvProcessor<R> {
     addresses: Addresses,
     onward_route: Route,
     payload_packet_counter: u16,
+    connection: PortalConnection,
+    send_window: Option<Arc<SendWindow>>,
 }
 
 impl<R: AsyncRead + Unpin + Send + Sync + 'static> TcpPortalRecvProcessor<R> {
@@ -34,6 +39,8 @@ impl<R: AsyncRead + Unpin + Send + Sync + 'static> TcpPortalRecvProcessor<R> {
         read_half: R,
         addresses: Addresses,
         onward_route: Route,
+        connection: PortalConnection,
+        send_window: Option<Arc<SendWindow>>,
     ) -> Self {
         Self {
             registry,
@@ -42,10 +49,58 @@ impl<R: AsyncRead + Unpin + Send + Sync + 'static> TcpPortalRecvProcessor<R> {
             addresses,
             onward_route,
             payload_packet_counter: 0,
+            connection,
+            send_window,
         }
     }
 }
 
+/// Result of waiting for data on the TCP connection
+enum ReadOutcome {
+    Read(std::io::Result<usize>),
+    /// No data was transferred for the idle timeout of the connection
+    Idle,
+    /// The connection was closed with [`TcpPortalStats::close_connection`](crate::TcpPortalStats::close_connection)
+    Closed,
+}
+
+impl<R: AsyncRead + Unpin + Send + Sync + 'static> TcpPortalRecvProcessor<R> {
+    /// Notify the sender and the other end of the portal that the connection was closed
+    async fn notify_disconnection(&self, ctx: &mut Context) -> Result<()> {
+        let tracer = global::tracer(OCKAM_TRACER_NAME);
+        let tracing_context = tracer
+            .in_span("TcpPortalRecvProcessor::notify_disconnection", |cx| {
+                OpenTelemetryContext::inject(&cx)
+            });
+
+        // Notify Sender that connection was closed
+        ctx.set_tracing_context(tracing_context.clone());
+        if let Err(err) = ctx
+            .send_from_address(
+                route![self.addresses.sender_internal.clone()],
+                PortalInternalMessage::Disconnect,
+                self.addresses.receiver_internal.clone(),
+            )
+            .await
+        {
+            warn!(
+                "Error notifying Tcp Portal Sender about dropped connection {}",
+                err
+            );
+        }
+
+        ctx.forward_from_address(
+            LocalMessage::new()
+                .with_tracing_context(tracing_context)
+                .with_onward_route(self.onward_route.clone())
+                .with_return_route(route![self.addresses.sender_remote.clone()])
+                .with_payload(PortalMessage::Disconnect.encode()?),
+            self.addresses.receiver_remote.clone(),
+        )
+        .await
+    }
+}
+
 #[async_trait]
 impl<R: AsyncRead + Unpin + Send + Sync + 'static> Processor for TcpPortalRecvProcessor<R> {
     type Context = Context;
@@ -69,7 +124,41 @@ impl<R: AsyncRead + Unpin + Send + Sync + 'static> Processor for TcpPortalRecvPr
     async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
         self.buf.clear();
 
-        let _len = match self.read_half.read_buf(&mut self.buf).await {
+        let idle_time_left = self.connection.idle_time_left();
+        let read = self.read_half.read_buf(&mut self.buf);
+        let read = async move {
+            match idle_time_left {
+                Some(idle_time_left) => match tokio::time::timeout(idle_time_left, read).await {
+                    Ok(read) => ReadOutcome::Read(read),
+                    Err(_) => ReadOutcome::Idle,
+                },
+                None => ReadOutcome::Read(read.await),
+            }
+        };
+        let read = tokio::select! {
+            _ = self.connection.closed() => ReadOutcome::Closed,
+            read = read => read,
+        };
+
+        let read = match read {
+            ReadOutcome::Read(read) => read,
+            ReadOutcome::Idle => {
+                // Data may have been sent to the TCP connection in the meantime
+                if self.connection.idle_time_left() != Some(Duration::ZERO) {
+                    return Ok(true);
+                }
+                debug!("Tcp Portal connection is idle, closing it");
+                self.notify_disconnection(ctx).await?;
+                return Ok(false);
+            }
+            ReadOutcome::Closed => {
+                debug!("Tcp Portal connection was closed on request");
+                self.notify_disconnection(ctx).await?;
+                return Ok(false);
+            }
+        };
+
+        let len = match read {
             Ok(len) => len,
             Err(err) => {
                 error!("Tcp Portal connection read failed with error: {}", err);
@@ -77,43 +166,33 @@ impl<R: AsyncRead + Unpin + Send + Sync + 'static> Processor for TcpPortalRecvPr
             }
         };
 
+        if self.buf.is_empty() {
+            self.notify_disconnection(ctx).await?;
+            return Ok(false);
+        }
+
+        self.connection.received(len).await;
+
         let tracer = global::tracer(OCKAM_TRACER_NAME);
         let tracing_context = tracer.in_span("TcpPortalRecvProcessor::forward_message", |cx| {
             OpenTelemetryContext::inject(&cx)
         });
 
-        if self.buf.is_empty() {
-            // Notify Sender that connection was closed
-            ctx.set_tracing_context(tracing_context.clone());
-            if let Err(err) = ctx
-                .send_from_address(
-                    route![self.addresses.sender_internal.clone()],
-                    PortalInternalMessage::Disconnect,
-                    self.addresses.receiver_internal.clone(),
-                )
-                .await
-            {
-                warn!(
-                    "Error notifying Tcp Portal Sender about dropped connection {}",
-                    err
-                );
-            }
-
-            ctx.forward_from_address(
-                LocalMessage::new()
-                    .with_tracing_context(tracing_context.clone())
-                    .with_onward_route(self.onward_route.clone())
-                    .with_return_route(route![self.addresses.sender_remote.clone()])
-                    .with_payload(PortalMessage::Disconnect.encode()?),
-                self.addresses.receiver_remote.clone(),
-            )
-            .await?;
-
-            return Ok(false);
-        }
-
         // Loop just in case buf was extended (should not happen though)
         for chunk in self.buf.chunks(MAX_PAYLOAD_SIZE) {
+            if let Some(send_window) = &self.send_window {
+                // Wait until the other side wrote enough data to its TCP connection
+                let closed = tokio::select! {
+                    _ = self.connection.closed() => true,
+                    _ = send_window.consume(chunk.len()) => false,
+                };
+                if closed {
+                    debug!("Tcp Portal connection was closed on request");
+                    self.notify_disconnection(ctx).await?;
+                    return Ok(false);
+                }
+            }
+
             let msg = LocalMessage::new()
                 .with_tracing_context(tracing_context.clone())
                 .with_onward_route(self.onward_route.clone())
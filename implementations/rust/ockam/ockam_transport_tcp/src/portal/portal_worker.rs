@@ -3,22 +3,30 @@ use crate::portal::portal_worker::ReadHalfMaybeTls::{ReadHalfNoTls, ReadHalfWith
 use crate::portal::portal_worker::WriteHalfMaybeTls::{WriteHalfNoTls, WriteHalfWithTls};
 use crate::transport::{connect, connect_tls};
 use crate::{
-    portal::TcpPortalRecvProcessor, HostnamePort, PortalInternalMessage, PortalMessage, TcpRegistry,
+    portal::{PortalConnection, ReceiveWindow, SendWindow, TcpPortalRecvProcessor},
+    HostnamePort, PortalInternalMessage, PortalMessage, TcpProxyConfig, TcpRegistry,
 };
+use core::time::Duration;
 use ockam_core::compat::{boxed::Box, sync::Arc};
+use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{
-    async_trait, AllowOnwardAddress, AllowSourceAddress, Decodable, DenyAll, IncomingAccessControl,
-    Mailbox, Mailboxes, OutgoingAccessControl,
+    async_trait, AllowOnwardAddress, AllowSourceAddress, Decodable, DenyAll, Error,
+    IncomingAccessControl, Mailbox, Mailboxes, OutgoingAccessControl,
 };
 use ockam_core::{Any, Result, Route, Routed, Worker};
+use ockam_node::metrics::Gauge;
 use ockam_node::{Context, ProcessorBuilder, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use tokio::io::{AsyncRead, AsyncWriteExt, ReadHalf, WriteHalf};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
-use tokio_rustls::TlsStream;
+use tokio::time::timeout;
+use tokio_rustls::{TlsAcceptor, TlsStream};
 use tracing::{debug, info, instrument, trace, warn};
 
+/// Maximum duration of the TLS handshake with a client of an inlet
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Enumerate all `TcpPortalWorker` states
 ///
 /// Possible state transitions are:
@@ -27,8 +35,14 @@ use tracing::{debug, info, instrument, trace, warn};
 /// `Inlet`: `SendPing` -> `ReceivePong` -> `Initialized`
 #[derive(Clone)]
 enum State {
-    SendPing { ping_route: Route },
-    SendPong { pong_route: Route },
+    SendPing {
+        ping_route: Route,
+    },
+    SendPong {
+        pong_route: Route,
+        /// Receive window advertised by the inlet
+        remote_window: Option<u32>,
+    },
     ReceivePong,
     Initialized,
 }
@@ -52,6 +66,20 @@ pub(crate) struct TcpPortalWorker {
     last_received_packet_counter: u16,
     outgoing_access_control: Arc<dyn OutgoingAccessControl>,
     is_tls: bool,
+    proxy: TcpProxyConfig,
+    /// Connection of an inlet client, and the acceptor terminating its TLS session
+    /// once the worker is initialized
+    tls_handshake: Option<(TcpStream, TlsAcceptor)>,
+    /// Limits and counters of the connection, shared with the receiver
+    connection: PortalConnection,
+    /// Receive window advertised to the other side, if the flow control is enabled
+    receive_window_size: Option<u32>,
+    /// Flow control of the data written to the TCP connection, once negotiated
+    receive_window: Option<ReceiveWindow>,
+    /// Flow control of the data sent by the receiver, once negotiated
+    send_window: Option<Arc<SendWindow>>,
+    /// Active sessions of this portal type, set once the worker is initialized
+    active_sessions: Option<Gauge>,
 }
 
 enum ReadHalfMaybeTls {
@@ -72,6 +100,9 @@ impl TcpPortalWorker {
         ctx: &Context,
         registry: TcpRegistry,
         stream: TcpStream,
+        tls_acceptor: Option<TlsAcceptor>,
+        connection: PortalConnection,
+        receive_window: Option<u32>,
         hostname_port: HostnamePort,
         ping_route: Route,
         addresses: Addresses,
@@ -82,9 +113,13 @@ impl TcpPortalWorker {
             ctx,
             registry,
             hostname_port,
-            false,
+            tls_acceptor.is_some(),
+            TcpProxyConfig::NoProxy,
             State::SendPing { ping_route },
             Some(stream),
+            tls_acceptor,
+            connection,
+            receive_window,
             addresses,
             incoming_access_control,
             outgoing_access_control,
@@ -100,6 +135,10 @@ impl TcpPortalWorker {
         registry: TcpRegistry,
         hostname_port: HostnamePort,
         tls: bool,
+        proxy: TcpProxyConfig,
+        connection: PortalConnection,
+        receive_window: Option<u32>,
+        remote_window: Option<u32>,
         pong_route: Route,
         addresses: Addresses,
         incoming_access_control: Arc<dyn IncomingAccessControl>,
@@ -110,8 +149,15 @@ impl TcpPortalWorker {
             registry,
             hostname_port,
             tls,
-            State::SendPong { pong_route },
+            proxy,
+            State::SendPong {
+                pong_route,
+                remote_window,
+            },
             None,
+            None,
+            connection,
+            receive_window,
             addresses,
             incoming_access_control,
             outgoing_access_control,
@@ -127,8 +173,12 @@ impl TcpPortalWorker {
         registry: TcpRegistry,
         hostname_port: HostnamePort,
         is_tls: bool,
+        proxy: TcpProxyConfig,
         state: State,
         stream: Option<TcpStream>,
+        tls_acceptor: Option<TlsAcceptor>,
+        connection: PortalConnection,
+        receive_window_size: Option<u32>,
         addresses: Addresses,
         incoming_access_control: Arc<dyn IncomingAccessControl>,
         outgoing_access_control: Arc<dyn OutgoingAccessControl>,
@@ -144,14 +194,16 @@ impl TcpPortalWorker {
             addresses.sender_remote
         );
 
-        let (rx, tx) = match stream {
-            // A TcpStream is provided in case of an inlet
-            Some(s) => {
+        let (rx, tx, tls_handshake) = match (stream, tls_acceptor) {
+            // A TcpStream is provided in case of an inlet. The TLS handshake is done
+            // when the worker is initialized, to not block the inlet listener
+            (Some(s), Some(tls_acceptor)) => (None, None, Some((s, tls_acceptor))),
+            (Some(s), None) => {
                 debug!("Connected to {} (with no TLS)", &hostname_port);
                 let (rx, tx) = s.into_split();
-                (Some(ReadHalfNoTls(rx)), Some(WriteHalfNoTls(tx)))
+                (Some(ReadHalfNoTls(rx)), Some(WriteHalfNoTls(tx)), None)
             }
-            None => (None, None),
+            (None, _) => (None, None, None),
         };
         debug!("The {} supports TLS: {}", portal_type.str(), is_tls);
 
@@ -167,7 +219,14 @@ impl TcpPortalWorker {
             portal_type,
             last_received_packet_counter: u16::MAX,
             is_tls,
+            proxy,
+            tls_handshake,
+            connection,
+            receive_window_size,
+            receive_window: None,
+            send_window: None,
             outgoing_access_control: outgoing_access_control.clone(),
+            active_sessions: None,
         };
 
         let internal_mailbox = Mailbox::new(
@@ -229,6 +288,8 @@ impl TcpPortalWorker {
             rx,
             self.addresses.clone(),
             onward_route,
+            self.connection.clone(),
+            self.send_window.clone(),
         );
 
         let remote = Mailbox::new(
@@ -341,12 +402,56 @@ impl TcpPortalWorker {
         Ok(())
     }
 
+    /// Enable the flow control if both sides of the portal support it,
+    /// and return the receive window to advertise to the other side
+    fn negotiate_windows(&mut self, remote_window: Option<u32>) -> Option<u32> {
+        match (self.receive_window_size, remote_window) {
+            (Some(receive_window), Some(remote_window)) => {
+                self.receive_window = Some(ReceiveWindow::new(receive_window));
+                self.send_window = Some(Arc::new(SendWindow::new(remote_window)));
+                Some(receive_window)
+            }
+            _ => None,
+        }
+    }
+
+    /// Terminate the TLS session of an inlet client
+    #[instrument(skip_all)]
+    async fn accept_tls(&mut self) -> Result<()> {
+        if let Some((stream, tls_acceptor)) = self.tls_handshake.take() {
+            debug!("Accept a TLS connection from {}", &self.hostname_port);
+            let tls_stream = timeout(TLS_HANDSHAKE_TIMEOUT, tls_acceptor.accept(stream))
+                .await
+                .map_err(|_| {
+                    Error::new(
+                        Origin::Transport,
+                        Kind::Timeout,
+                        format!("TLS handshake with {} timed out", &self.hostname_port),
+                    )
+                })?
+                .map_err(|e| {
+                    Error::new(
+                        Origin::Transport,
+                        Kind::Io,
+                        format!("TLS handshake with {} failed: {e:?}", &self.hostname_port),
+                    )
+                })?;
+            let (rx, tx) = tokio::io::split(TlsStream::from(tls_stream));
+            self.read_half = Some(ReadHalfWithTls(rx));
+            self.write_half = Some(WriteHalfWithTls(tx));
+        }
+
+        Ok(())
+    }
+
     #[instrument(skip_all)]
-    async fn handle_send_ping(&self, ctx: &Context, ping_route: Route) -> Result<State> {
+    async fn handle_send_ping(&mut self, ctx: &Context, ping_route: Route) -> Result<State> {
+        self.accept_tls().await?;
+
         // Force creation of Outlet on the other side
         ctx.send_from_address(
             ping_route,
-            PortalMessage::Ping.to_neutral_message()?,
+            PortalMessage::Ping(self.receive_window_size).to_neutral_message()?,
             self.addresses.sender_remote.clone(),
         )
         .await?;
@@ -357,19 +462,24 @@ impl TcpPortalWorker {
     }
 
     #[instrument(skip_all)]
-    async fn handle_send_pong(&mut self, ctx: &Context, pong_route: Route) -> Result<State> {
+    async fn handle_send_pong(
+        &mut self,
+        ctx: &Context,
+        pong_route: Route,
+        remote_window: Option<u32>,
+    ) -> Result<State> {
         if self.write_half.is_some() {
             // Should not happen
             return Err(TransportError::PortalInvalidState)?;
         }
         if self.is_tls {
             debug!("Connect to {} via TLS", &self.hostname_port);
-            let (rx, tx) = connect_tls(&self.hostname_port).await?;
+            let (rx, tx) = connect_tls(&self.hostname_port, &self.proxy).await?;
             self.write_half = Some(WriteHalfWithTls(tx));
             self.read_half = Some(ReadHalfWithTls(rx));
         } else {
             debug!("Connect to {}", self.hostname_port);
-            let (rx, tx) = connect(self.hostname_port.to_socket_addr()?).await?;
+            let (_, rx, tx) = connect(&self.hostname_port, &self.proxy).await?;
             self.write_half = Some(WriteHalfNoTls(tx));
             self.read_half = Some(ReadHalfNoTls(rx));
         }
@@ -377,9 +487,10 @@ impl TcpPortalWorker {
         // Respond to Inlet before starting the processor but
         // after the connection has been established
         // to avoid a payload being sent before the pong
+        let receive_window = self.negotiate_windows(remote_window);
         ctx.send_from_address(
             pong_route.clone(),
-            PortalMessage::Pong.to_neutral_message()?,
+            PortalMessage::Pong(receive_window).to_neutral_message()?,
             self.addresses.sender_remote.clone(),
         )
         .await?;
@@ -411,8 +522,13 @@ impl Worker for TcpPortalWorker {
             State::SendPing { ping_route } => {
                 self.state = self.handle_send_ping(ctx, ping_route.clone()).await?;
             }
-            State::SendPong { pong_route } => {
-                self.state = self.handle_send_pong(ctx, pong_route.clone()).await?;
+            State::SendPong {
+                pong_route,
+                remote_window,
+            } => {
+                self.state = self
+                    .handle_send_pong(ctx, pong_route.clone(), remote_window)
+                    .await?;
             }
             State::ReceivePong | State::Initialized { .. } => {
                 return Err(TransportError::PortalInvalidState)?;
@@ -421,6 +537,7 @@ impl Worker for TcpPortalWorker {
 
         self.registry
             .add_portal_worker(&self.addresses.sender_remote);
+        self.active_sessions = Some(crate::metrics::start_portal_session(self.portal_type.str()));
 
         Ok(())
     }
@@ -429,6 +546,9 @@ impl Worker for TcpPortalWorker {
     async fn shutdown(&mut self, _ctx: &mut Self::Context) -> Result<()> {
         self.registry
             .remove_portal_worker(&self.addresses.sender_remote);
+        if let Some(active_sessions) = self.active_sessions.take() {
+            active_sessions.dec();
+        }
 
         Ok(())
     }
@@ -451,6 +571,7 @@ impl Worker for TcpPortalWorker {
         }
         let return_route = msg.return_route();
         let remote_packet = recipient != self.addresses.sender_internal;
+        let local_info = msg.local_message().local_info();
         let payload = msg.into_payload();
 
         match state {
@@ -458,10 +579,23 @@ impl Worker for TcpPortalWorker {
                 if !remote_packet {
                     return Err(TransportError::PortalInvalidState)?;
                 };
-                if PortalMessage::decode(&payload)? != PortalMessage::Pong {
-                    return Err(TransportError::Protocol)?;
-                };
-                self.handle_receive_pong(ctx, return_route).await
+                match PortalMessage::decode(&payload)? {
+                    PortalMessage::Pong(remote_window) => {
+                        self.connection.set_remote_local_info(local_info);
+                        self.handle_receive_pong(ctx, return_route, remote_window)
+                            .await
+                    }
+                    // The outlet rejected the connection, for example when it has too many connections
+                    PortalMessage::Disconnect => {
+                        self.start_disconnection(ctx, DisconnectionReason::Remote)
+                            .await
+                    }
+                    PortalMessage::Ping(_)
+                    | PortalMessage::Payload(..)
+                    | PortalMessage::WindowUpdate(_) => {
+                        return Err(TransportError::Protocol)?;
+                    }
+                }
             }
             State::Initialized => {
                 trace!(
@@ -482,7 +616,13 @@ impl Worker for TcpPortalWorker {
                             self.start_disconnection(ctx, DisconnectionReason::Remote)
                                 .await
                         }
-                        PortalMessage::Ping | PortalMessage::Pong => {
+                        PortalMessage::WindowUpdate(bytes) => {
+                            if let Some(send_window) = &self.send_window {
+                                send_window.grant(bytes);
+                            }
+                            Ok(())
+                        }
+                        PortalMessage::Ping(_) | PortalMessage::Pong(_) => {
                             return Err(TransportError::Protocol)?;
                         }
                     }
@@ -503,7 +643,13 @@ impl Worker for TcpPortalWorker {
 
 impl TcpPortalWorker {
     #[instrument(skip_all)]
-    async fn handle_receive_pong(&mut self, ctx: &Context, return_route: Route) -> Result<()> {
+    async fn handle_receive_pong(
+        &mut self,
+        ctx: &Context,
+        return_route: Route,
+        remote_window: Option<u32>,
+    ) -> Result<()> {
+        self.negotiate_windows(remote_window);
         self.start_receiver(ctx, return_route.clone()).await?;
         debug!("Inlet at: {} received pong", self.addresses.sender_internal);
         self.remote_route = Some(return_route);
@@ -548,11 +694,34 @@ impl TcpPortalWorker {
             );
             self.start_disconnection(ctx, DisconnectionReason::FailedTx)
                 .await?;
+        } else {
+            self.connection.sent(payload.len()).await;
+            if let Some(bytes) = self
+                .receive_window
+                .as_mut()
+                .and_then(|receive_window| receive_window.consume(payload.len()))
+            {
+                self.send_window_update(ctx, bytes).await?;
+            }
         }
 
         Ok(())
     }
 
+    /// Let the other side send `bytes` more bytes, since they were written to the TCP connection
+    #[instrument(skip_all)]
+    async fn send_window_update(&self, ctx: &Context, bytes: u32) -> Result<()> {
+        if let Some(remote_route) = &self.remote_route {
+            ctx.send_from_address(
+                remote_route.clone(),
+                PortalMessage::WindowUpdate(bytes).to_neutral_message()?,
+                self.addresses.sender_remote.clone(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     async fn check_packet_counter(
         &mut self,
@@ -0,0 +1,123 @@
+use crate::MAX_PAYLOAD_SIZE;
+use tokio::sync::Semaphore;
+
+/// Default number of bytes that the other side of a portal can send to a connection
+/// before the data is written to the TCP connection and acknowledged
+pub const DEFAULT_PORTAL_RECEIVE_WINDOW: u32 = 4 * MAX_PAYLOAD_SIZE as u32;
+
+/// Smallest receive window, so that a payload always fits in the window
+pub(crate) const MIN_PORTAL_RECEIVE_WINDOW: u32 = MAX_PAYLOAD_SIZE as u32;
+
+/// Credits, in bytes, to send payloads to the other side of a portal.
+///
+/// The credits are consumed by the receiver of the local TCP connection and
+/// granted back by the other side with [`PortalMessage::WindowUpdate`](crate::PortalMessage::WindowUpdate)
+#[derive(Debug)]
+pub(crate) struct SendWindow {
+    credits: Semaphore,
+    /// Receive window advertised by the other side, which the credits never exceed
+    size: usize,
+}
+
+impl SendWindow {
+    /// Create a window with the receive window advertised by the other side
+    pub(crate) fn new(window: u32) -> Self {
+        let size = window.max(MIN_PORTAL_RECEIVE_WINDOW) as usize;
+        Self {
+            credits: Semaphore::new(size),
+            size,
+        }
+    }
+
+    /// Wait until `bytes` can be sent to the other side
+    pub(crate) async fn consume(&self, bytes: usize) {
+        // The semaphore is never closed
+        if let Ok(permits) = self.credits.acquire_many(bytes as u32).await {
+            permits.forget();
+        }
+    }
+
+    /// Add the credits granted by the other side.
+    ///
+    /// The credits are clamped to the advertised window, so that a misbehaving peer
+    /// can't grant more than it can receive, nor overflow the semaphore
+    pub(crate) fn grant(&self, bytes: u32) {
+        let missing = self.size.saturating_sub(self.credits.available_permits());
+        self.credits.add_permits((bytes as usize).min(missing))
+    }
+
+    /// Number of bytes which can currently be sent
+    #[cfg(test)]
+    fn available(&self) -> usize {
+        self.credits.available_permits()
+    }
+}
+
+/// Number of bytes written to the local TCP connection which were not acknowledged
+/// to the other side of the portal yet
+#[derive(Debug)]
+pub(crate) struct ReceiveWindow {
+    size: u32,
+    unacknowledged: u32,
+}
+
+impl ReceiveWindow {
+    pub(crate) fn new(size: u32) -> Self {
+        Self {
+            size,
+            unacknowledged: 0,
+        }
+    }
+
+    /// Account for `bytes` written to the local TCP connection and return the number
+    /// of bytes to acknowledge, once half of the window is consumed
+    pub(crate) fn consume(&mut self, bytes: usize) -> Option<u32> {
+        self.unacknowledged = self.unacknowledged.saturating_add(bytes as u32);
+        if self.unacknowledged >= self.size / 2 {
+            Some(core::mem::take(&mut self.unacknowledged))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[tokio::test]
+    async fn send_window_waits_for_credits() {
+        let window = SendWindow::new(MIN_PORTAL_RECEIVE_WINDOW);
+        window.consume(MAX_PAYLOAD_SIZE).await;
+        assert_eq!(window.available(), 0);
+
+        let blocked = tokio::time::timeout(Duration::from_millis(100), window.consume(10)).await;
+        assert!(blocked.is_err());
+
+        window.grant(10);
+        window.consume(10).await;
+        assert_eq!(window.available(), 0);
+    }
+
+    #[tokio::test]
+    async fn send_window_credits_are_clamped_to_the_window() {
+        let window = SendWindow::new(MIN_PORTAL_RECEIVE_WINDOW);
+        window.grant(u32::MAX);
+        assert_eq!(window.available(), MIN_PORTAL_RECEIVE_WINDOW as usize);
+
+        window.consume(100).await;
+        window.grant(u32::MAX);
+        window.grant(u32::MAX);
+        assert_eq!(window.available(), MIN_PORTAL_RECEIVE_WINDOW as usize);
+    }
+
+    #[test]
+    fn receive_window_acknowledges_half_of_the_window() {
+        let mut window = ReceiveWindow::new(1000);
+        assert_eq!(window.consume(300), None);
+        assert_eq!(window.consume(300), Some(600));
+        assert_eq!(window.consume(100), None);
+        assert_eq!(window.consume(400), Some(500));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{HostnamePort, TcpConnectionMode};
+use crate::{HostnamePort, TcpConnectionMode, TcpProxyConfig};
 use cfg_if::cfg_if;
 use core::fmt;
 use core::fmt::Formatter;
@@ -166,10 +166,32 @@ pub(super) fn parse_socket_addr(s: &str) -> Result<SocketAddr> {
     Ok(s.parse().map_err(|_| TransportError::InvalidAddress)?)
 }
 
-/// Connect to a socket address via a regular TcpStream
+/// Connect to a hostname and port via a regular TcpStream, possibly through a proxy.
+/// Return the socket address of the peer of the stream: either the destination or the proxy
 #[instrument(skip_all)]
-pub(crate) async fn connect(socket_address: SocketAddr) -> Result<(OwnedReadHalf, OwnedWriteHalf)> {
-    Ok(create_tcp_stream(socket_address).await?.into_split())
+pub(crate) async fn connect(
+    hostname_port: &HostnamePort,
+    proxy: &TcpProxyConfig,
+) -> Result<(SocketAddr, OwnedReadHalf, OwnedWriteHalf)> {
+    let (socket_address, connection) = create_tcp_stream_to(hostname_port, proxy).await?;
+    let (read_half, write_half) = connection.into_split();
+    Ok((socket_address, read_half, write_half))
+}
+
+/// Create a TCP stream to a given hostname and port, going through a proxy if the
+/// configuration requires it.
+/// Return the socket address of the peer of the stream: either the destination or the proxy
+pub(crate) async fn create_tcp_stream_to(
+    hostname_port: &HostnamePort,
+    proxy: &TcpProxyConfig,
+) -> Result<(SocketAddr, TcpStream)> {
+    match proxy.proxy_for(&hostname_port.hostname())? {
+        Some(proxy) => proxy.connect(hostname_port).await,
+        None => {
+            let socket_address = hostname_port.to_socket_addr()?;
+            Ok((socket_address, create_tcp_stream(socket_address).await?))
+        }
+    }
 }
 
 /// Create a TCP stream to a given socket address
@@ -209,15 +231,15 @@ pub(crate) async fn create_tcp_stream(socket_address: SocketAddr) -> Result<TcpS
 #[instrument(skip_all)]
 pub(crate) async fn connect_tls(
     hostname_port: &HostnamePort,
+    proxy: &TcpProxyConfig,
 ) -> Result<(
     ReadHalf<TlsStream<TcpStream>>,
     WriteHalf<TlsStream<TcpStream>>,
 )> {
-    let socket_address = hostname_port.to_socket_addr()?;
-    debug!(hostname_port = %hostname_port, addr = %socket_address, "Trying to connect using TLS");
+    debug!(hostname_port = %hostname_port, "Trying to connect using TLS");
 
-    // create a tcp stream
-    let connection = create_tcp_stream(socket_address).await?;
+    // create a tcp stream, the TLS session is then established end-to-end through the proxy, if any
+    let (_, connection) = create_tcp_stream_to(hostname_port, proxy).await?;
 
     // create a TLS connector
     let tls_connector = create_tls_connector().await?;
@@ -1,8 +1,10 @@
-use crate::transport::common::{resolve_peer, TcpConnection};
+use crate::transport::common::TcpConnection;
 use crate::transport::connect;
 use crate::workers::{Addresses, TcpRecvProcessor, TcpSendWorker};
-use crate::{TcpConnectionMode, TcpConnectionOptions, TcpTransport};
+use crate::{HostnamePort, TcpConnectionMode, TcpConnectionOptions, TcpTransport};
+use core::str::FromStr;
 use ockam_core::{Address, Result};
+use ockam_transport_core::TransportError;
 use tracing::debug;
 
 impl TcpTransport {
@@ -18,15 +20,19 @@ impl TcpTransport {
     /// let connection = tcp.connect("127.0.0.1:5000", TcpConnectionOptions::new()).await?; // and connect to port 5000
     /// # Ok(()) }
     /// ```
+    ///
+    /// Unless specified otherwise in the options, the connection goes through the proxy
+    /// configured with the `HTTPS_PROXY` environment variable, see [`TcpProxyConfig`](crate::TcpProxyConfig).
     pub async fn connect(
         &self,
         peer: impl Into<String>,
         options: TcpConnectionOptions,
     ) -> Result<TcpConnection> {
         let peer = peer.into();
-        let socket = resolve_peer(peer.clone())?;
+        let hostname_port =
+            HostnamePort::from_str(&peer).map_err(|_| TransportError::InvalidAddress)?;
         debug!("Connecting to {}", peer.clone());
-        let (read_half, write_half) = connect(socket).await?;
+        let (socket, read_half, write_half) = connect(&hostname_port, &options.proxy).await?;
 
         let mode = TcpConnectionMode::Outgoing;
         let addresses = Addresses::generate(mode);
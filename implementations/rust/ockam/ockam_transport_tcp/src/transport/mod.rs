@@ -4,9 +4,11 @@ mod hostname_port;
 mod lifecycle;
 mod listener;
 mod portals;
+mod proxy;
 
 pub use common::*;
 pub use hostname_port::*;
+pub use proxy::*;
 
 pub use crate::portal::options::*;
 
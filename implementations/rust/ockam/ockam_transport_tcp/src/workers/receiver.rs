@@ -1,3 +1,4 @@
+use crate::metrics::{bytes_received, remove_connection_metrics, TCP_BYTES_RECEIVED};
 use crate::workers::Addresses;
 use crate::{TcpConnectionMode, TcpReceiverInfo, TcpRegistry, TcpSendWorkerMsg};
 use ockam_core::compat::net::SocketAddr;
@@ -7,6 +8,7 @@ use ockam_core::{
     async_trait, AllowOnwardAddress, DenyAll, Mailbox, Mailboxes, OutgoingAccessControl,
 };
 use ockam_core::{LocalMessage, Processor, Result, TransportMessage};
+use ockam_node::metrics::Counter;
 use ockam_node::{Context, ProcessorBuilder};
 use ockam_transport_core::TransportError;
 use tokio::{io::AsyncReadExt, net::tcp::OwnedReadHalf};
@@ -27,6 +29,7 @@ pub(crate) struct TcpRecvProcessor {
     addresses: Addresses,
     mode: TcpConnectionMode,
     flow_control_id: FlowControlId,
+    bytes_received: Counter,
 }
 
 impl TcpRecvProcessor {
@@ -39,6 +42,7 @@ impl TcpRecvProcessor {
         mode: TcpConnectionMode,
         flow_control_id: FlowControlId,
     ) -> Self {
+        let bytes_received = bytes_received(addresses.sender_address(), &socket_address);
         Self {
             registry,
             read_half,
@@ -46,6 +50,7 @@ impl TcpRecvProcessor {
             addresses,
             mode,
             flow_control_id,
+            bytes_received,
         }
     }
 
@@ -113,6 +118,11 @@ impl Processor for TcpRecvProcessor {
     #[instrument(skip_all, name = "TcpRecvProcessor::shutdown")]
     async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
         self.registry.remove_receiver_processor(&ctx.address());
+        remove_connection_metrics(
+            TCP_BYTES_RECEIVED,
+            self.addresses.sender_address(),
+            &self.socket_address,
+        );
 
         Ok(())
     }
@@ -164,6 +174,8 @@ impl Processor for TcpRecvProcessor {
                 return Ok(true);
             }
         }
+        // Include the length header
+        self.bytes_received.inc_by(len as u64 + 2);
 
         // Deserialize the message now
         let transport_message = TransportMessage::decode_message(buf).map_err(|e| {
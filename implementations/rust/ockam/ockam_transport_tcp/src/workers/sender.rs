@@ -1,3 +1,4 @@
+use crate::metrics::{bytes_sent, remove_connection_metrics, TCP_BYTES_SENT};
 use crate::workers::Addresses;
 use crate::{TcpConnectionMode, TcpRegistry, TcpSenderInfo};
 use ockam_core::flow_control::FlowControlId;
@@ -7,6 +8,7 @@ use ockam_core::{
     AllowSourceAddress, DenyAll, IncomingAccessControl,
 };
 use ockam_core::{Any, Decodable, Mailbox, Mailboxes, Message, Result, Routed, Worker};
+use ockam_node::metrics::Counter;
 use ockam_node::{Context, WorkerBuilder};
 
 use ockam_transport_core::encode_transport_message;
@@ -36,6 +38,7 @@ pub(crate) struct TcpSendWorker {
     mode: TcpConnectionMode,
     receiver_flow_control_id: FlowControlId,
     rx_should_be_stopped: bool,
+    bytes_sent: Counter,
 }
 
 impl TcpSendWorker {
@@ -48,6 +51,7 @@ impl TcpSendWorker {
         mode: TcpConnectionMode,
         receiver_flow_control_id: FlowControlId,
     ) -> Self {
+        let bytes_sent = bytes_sent(addresses.sender_address(), &socket_address);
         Self {
             registry,
             write_half,
@@ -56,6 +60,7 @@ impl TcpSendWorker {
             receiver_flow_control_id,
             mode,
             rx_should_be_stopped: true,
+            bytes_sent,
         }
     }
 }
@@ -141,6 +146,11 @@ impl Worker for TcpSendWorker {
     async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
         self.registry
             .remove_sender_worker(self.addresses.sender_address());
+        remove_connection_metrics(
+            TCP_BYTES_SENT,
+            self.addresses.sender_address(),
+            &self.socket_address,
+        );
 
         if self.rx_should_be_stopped {
             let _ = ctx
@@ -192,6 +202,7 @@ impl Worker for TcpSendWorker {
 
                 return Ok(());
             }
+            self.bytes_sent.inc_by(msg.len() as u64);
         }
 
         Ok(())
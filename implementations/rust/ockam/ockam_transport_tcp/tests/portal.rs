@@ -7,8 +7,13 @@ use ockam_core::compat::rand::random;
 use ockam_core::{route, Result};
 use ockam_node::Context;
 use ockam_transport_tcp::{
-    TcpConnectionOptions, TcpInletOptions, TcpListenerOptions, TcpOutletOptions, TcpTransport,
+    TcpConnectionOptions, TcpInletOptions, TcpInletTls, TcpListenerOptions, TcpOutletOptions,
+    TcpPortalLimits, TcpPortalStats, TcpTransport,
 };
+use rcgen::{BasicConstraints, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+use tokio_rustls::rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer, ServerName};
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
 
 const LENGTH: usize = 32;
 
@@ -112,6 +117,316 @@ async fn portal__reverse_flow__should_succeed(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__tls_inlet__should_succeed(ctx: &mut Context) -> Result<()> {
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let tcp = TcpTransport::create(ctx).await?;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet("outlet", bind_address, TcpOutletOptions::new())
+        .await?;
+
+    let tls = TcpInletTls::self_signed(vec!["localhost".to_string()])?;
+    let mut root_cert_store = RootCertStore::empty();
+    root_cert_store
+        .add(tls.certificate_chain()[0].clone())
+        .unwrap();
+    let (inlet_addr, _) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            TcpInletOptions::new().with_tls(tls),
+        )
+        .await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        read_assert_binary(&mut stream, payload1).await;
+        write_binary(&mut stream, payload2).await;
+        stream
+    });
+
+    let config = ClientConfig::builder()
+        .with_root_certificates(root_cert_store)
+        .with_no_client_auth();
+    let stream = TcpStream::connect(inlet_addr).await.unwrap();
+    let mut stream = TlsConnector::from(std::sync::Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+        .unwrap();
+
+    stream.write_all(&payload1).await.unwrap();
+    let mut payload = [0u8; LENGTH];
+    stream.read_exact(&mut payload).await.unwrap();
+    assert_eq!(payload, payload2);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__tls_inlet_with_client_authentication__should_reject_unknown_clients(
+    ctx: &mut Context,
+) -> Result<()> {
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let tcp = TcpTransport::create(ctx).await?;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet("outlet", bind_address, TcpOutletOptions::new())
+        .await?;
+
+    // A certificate authority and a client certificate signed by it
+    let ca_key = KeyPair::generate().unwrap();
+    let mut ca_params = CertificateParams::new(vec![]).unwrap();
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca_certificate = ca_params.self_signed(&ca_key).unwrap();
+    let client_key = KeyPair::generate().unwrap();
+    let mut client_params = CertificateParams::new(vec!["client".to_string()]).unwrap();
+    client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+    let client_certificate = client_params
+        .signed_by(&client_key, &ca_certificate, &ca_key)
+        .unwrap();
+
+    let tls = TcpInletTls::self_signed(vec!["localhost".to_string()])?
+        .with_client_authentication(ca_certificate.pem().as_bytes())?;
+    let mut root_cert_store = RootCertStore::empty();
+    root_cert_store
+        .add(tls.certificate_chain()[0].clone())
+        .unwrap();
+    let (inlet_addr, _) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            TcpInletOptions::new().with_tls(tls),
+        )
+        .await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        read_assert_binary(&mut stream, payload1).await;
+        write_binary(&mut stream, payload2).await;
+        stream
+    });
+
+    // A client without a certificate is rejected
+    let config = ClientConfig::builder()
+        .with_root_certificates(root_cert_store.clone())
+        .with_no_client_auth();
+    let stream = TcpStream::connect(inlet_addr).await.unwrap();
+    if let Ok(mut stream) = TlsConnector::from(std::sync::Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+    {
+        // With TLS 1.3 the client only learns that it was rejected after the handshake
+        let _ = stream.write_all(&payload1).await;
+        let mut payload = [0u8; LENGTH];
+        assert!(stream.read_exact(&mut payload).await.is_err());
+    }
+
+    // A client presenting a certificate signed by the authority is accepted
+    let config = ClientConfig::builder()
+        .with_root_certificates(root_cert_store)
+        .with_client_auth_cert(
+            vec![client_certificate.der().clone()],
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(client_key.serialize_der())),
+        )
+        .unwrap();
+    let stream = TcpStream::connect(inlet_addr).await.unwrap();
+    let mut stream = TlsConnector::from(std::sync::Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+        .unwrap();
+
+    stream.write_all(&payload1).await.unwrap();
+    let mut payload = [0u8; LENGTH];
+    stream.read_exact(&mut payload).await.unwrap();
+    assert_eq!(payload, payload2);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__max_connections__should_reject_new_connections(ctx: &mut Context) -> Result<()> {
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let tcp = TcpTransport::create(ctx).await?;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet("outlet", bind_address, TcpOutletOptions::new())
+        .await?;
+
+    let stats = TcpPortalStats::default();
+    let (inlet_addr, _) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            TcpInletOptions::new()
+                .with_limits(TcpPortalLimits::new().with_max_connections(1))
+                .with_stats(stats.clone()),
+        )
+        .await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        read_assert_binary(&mut stream, payload1).await;
+        write_binary(&mut stream, payload2).await;
+        stream
+    });
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    write_binary(&mut stream, payload1).await;
+    read_assert_binary(&mut stream, payload2).await;
+
+    // The second connection is closed right away by the inlet
+    let mut rejected = TcpStream::connect(inlet_addr).await.unwrap();
+    let mut buf = [0u8; LENGTH];
+    let length = rejected.read(&mut buf).await.unwrap_or_default();
+    assert_eq!(length, 0);
+
+    assert_eq!(stats.active_connections(), 1);
+    assert_eq!(stats.total_connections(), 1);
+    assert_eq!(stats.rejected_connections(), 1);
+    assert_eq!(stats.bytes_received(), LENGTH as u64);
+    assert_eq!(stats.bytes_sent(), LENGTH as u64);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 30000)]
+async fn portal__slow_consumer__should_stop_reading_from_the_client(
+    ctx: &mut Context,
+) -> Result<()> {
+    const TOTAL: usize = 64 * 1024 * 1024;
+    const CHUNK: usize = 1024 * 1024;
+
+    let tcp = TcpTransport::create(ctx).await?;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet("outlet", bind_address, TcpOutletOptions::new())
+        .await?;
+
+    let stats = TcpPortalStats::default();
+    let (inlet_addr, _) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            TcpInletOptions::new().with_stats(stats.clone()),
+        )
+        .await?;
+
+    let server = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        // The server doesn't read until the client stopped sending data
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let mut buf = vec![0u8; CHUNK];
+        let mut total = 0;
+        while total < TOTAL {
+            total += stream.read(&mut buf).await.unwrap();
+        }
+        total
+    });
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    let client = tokio::spawn(async move {
+        let chunk = vec![0u8; CHUNK];
+        for _ in 0..TOTAL / CHUNK {
+            stream.write_all(&chunk).await.unwrap();
+        }
+        stream
+    });
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // Only the TCP buffers and the windows are filled, the data is not buffered in the mailboxes
+    assert!(
+        stats.bytes_received() < (TOTAL / 2) as u64,
+        "{} bytes were read from the client",
+        stats.bytes_received()
+    );
+
+    let total = server.await.unwrap();
+    assert_eq!(total, TOTAL);
+    let _stream = client.await.unwrap();
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 15000)]
+async fn portal__close_connection__should_disconnect_the_client(ctx: &mut Context) -> Result<()> {
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let tcp = TcpTransport::create(ctx).await?;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet("outlet", bind_address, TcpOutletOptions::new())
+        .await?;
+
+    let stats = TcpPortalStats::default();
+    let (inlet_addr, _) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            TcpInletOptions::new().with_stats(stats.clone()),
+        )
+        .await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        read_assert_binary(&mut stream, payload1).await;
+        write_binary(&mut stream, payload2).await;
+        stream
+    });
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    write_binary(&mut stream, payload1).await;
+    read_assert_binary(&mut stream, payload2).await;
+
+    let connections = stats.connections();
+    assert_eq!(connections.len(), 1);
+    let connection = &connections[0];
+    assert_eq!(
+        connection.peer_address(),
+        stream.local_addr().unwrap().to_string()
+    );
+    assert_eq!(connection.bytes_received(), LENGTH as u64);
+    assert_eq!(connection.bytes_sent(), LENGTH as u64);
+
+    assert!(stats.close_connection(connection.id()));
+
+    // The inlet closes the client connection
+    let mut buf = [0u8; LENGTH];
+    let length = stream.read(&mut buf).await.unwrap_or_default();
+    assert_eq!(length, 0);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    Ok(())
+}
+
 #[allow(non_snake_case)]
 #[ockam_macros::test(timeout = 15000)]
 async fn portal__tcp_connection__should_succeed(ctx: &mut Context) -> Result<()> {
@@ -10,11 +10,15 @@
 use ockam_core::TransportType;
 
 pub use hole_puncher::{PunchError, UdpHolePuncher};
-pub use rendezvous_service::UdpRendezvousService;
+pub use portal::{UdpInletOptions, UdpOutletOptions, DEFAULT_UDP_PORTAL_IDLE_TIMEOUT};
+pub use reliability::UdpReliabilityOptions;
+pub use rendezvous_service::{RendezvousRequest, RendezvousResponse, UdpRendezvousService};
 pub use transport::UdpTransport;
 pub use transport::UdpTransportExtension;
 
 mod hole_puncher;
+mod portal;
+mod reliability;
 mod rendezvous_service;
 mod router;
 mod transport;
@@ -0,0 +1,38 @@
+use ockam_core::Address;
+
+/// Enumerate all portal types
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub(crate) enum PortalType {
+    Inlet,
+    Outlet,
+}
+
+impl PortalType {
+    pub fn str(&self) -> &'static str {
+        match self {
+            PortalType::Inlet => "inlet",
+            PortalType::Outlet => "outlet",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Addresses {
+    /// Used to receive the datagrams read from the local socket
+    pub(crate) internal: Address,
+    /// Used to exchange messages with the other side of the portal
+    pub(crate) remote: Address,
+    /// Used by the processor reading the socket of an outlet session
+    pub(crate) receiver: Address,
+}
+
+impl Addresses {
+    pub(crate) fn generate(portal_type: PortalType) -> Self {
+        let type_name = portal_type.str();
+        Self {
+            internal: Address::random_tagged(&format!("UdpPortalWorker.{type_name}.internal")),
+            remote: Address::random_tagged(&format!("UdpPortalWorker.{type_name}.remote")),
+            receiver: Address::random_tagged(&format!("UdpPortalRecvProcessor.{type_name}")),
+        }
+    }
+}
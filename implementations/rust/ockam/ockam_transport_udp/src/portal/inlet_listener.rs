@@ -0,0 +1,156 @@
+use crate::portal::{
+    Addresses, PortalType, UdpInletOptions, UdpInletSessions, UdpPortalMessage, UdpPortalWorker,
+    MAX_DATAGRAM_SIZE,
+};
+use ockam_core::{async_trait, route, Address, AllowAll, DenyAll, Processor, Result, Route};
+use ockam_node::{Context, ProcessorBuilder, RestartPolicy};
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::UdpSocket;
+use tracing::{debug, error, warn};
+
+/// A UDP Portal Inlet listen processor
+///
+/// UDP Portal Inlet listen processors are created by `UdpTransport`
+/// after a call is made to
+/// [`UdpTransport::create_inlet`](crate::UdpTransport::create_inlet).
+///
+/// The processor reads the datagrams sent to the inlet socket and dispatches them to the
+/// session of their client, starting a new session for a new client.
+#[derive(Clone)]
+pub(crate) struct UdpInletListenProcessor {
+    socket: Arc<UdpSocket>,
+    outlet_listener_route: Route,
+    options: UdpInletOptions,
+    sessions: UdpInletSessions,
+    buf: Vec<u8>,
+}
+
+impl UdpInletListenProcessor {
+    /// Start a new `UdpInletListenProcessor`
+    pub(crate) async fn start(
+        ctx: &Context,
+        outlet_listener_route: Route,
+        addr: SocketAddr,
+        options: UdpInletOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let processor_address = Address::random_tagged("UdpInletListenProcessor");
+
+        debug!("Binding UdpInletListenProcessor to {}", addr);
+        let socket = match UdpSocket::bind(addr).await {
+            Ok(socket) => socket,
+            Err(err) => {
+                error!(%addr, %err, "could not bind to address");
+                return Err(TransportError::from(err))?;
+            }
+        };
+        let socket_addr = socket.local_addr().map_err(TransportError::from)?;
+        let processor = Self {
+            socket: Arc::new(socket),
+            outlet_listener_route,
+            options,
+            sessions: Default::default(),
+            buf: vec![0; MAX_DATAGRAM_SIZE],
+        };
+
+        // The processor sends the datagrams to the sessions that it creates
+        ProcessorBuilder::new(processor)
+            .with_address(processor_address.clone())
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(AllowAll)
+            .with_restart_policy(RestartPolicy::one_for_one())
+            .start(ctx)
+            .await?;
+
+        Ok((socket_addr, processor_address))
+    }
+
+    /// Return the address of the session of a client, starting a new one if necessary.
+    /// Return `None` if the maximum number of sessions is reached
+    async fn session(&self, ctx: &Context, peer: SocketAddr) -> Result<Option<Address>> {
+        {
+            let sessions = self.sessions.lock().unwrap();
+            if let Some(address) = sessions.get(&peer) {
+                return Ok(Some(address.clone()));
+            }
+            if sessions.len() >= self.options.max_sessions {
+                return Ok(None);
+            }
+        }
+
+        let addresses = Addresses::generate(PortalType::Inlet);
+        let outlet_listener_route = self.outlet_listener_route.clone();
+        self.options.setup_flow_control(
+            ctx.flow_controls(),
+            &addresses,
+            outlet_listener_route.next()?,
+        );
+
+        debug!("New client {peer} for the UDP inlet {}", ctx.address());
+        UdpPortalWorker::start_new_inlet(
+            ctx,
+            self.socket.clone(),
+            peer,
+            self.sessions.clone(),
+            ctx.address(),
+            outlet_listener_route,
+            addresses.clone(),
+            self.options.idle_timeout,
+            self.options.incoming_access_control.clone(),
+            self.options.outgoing_access_control.clone(),
+        )
+        .await?;
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(peer, addresses.internal.clone());
+
+        Ok(Some(addresses.internal))
+    }
+}
+
+#[async_trait]
+impl Processor for UdpInletListenProcessor {
+    type Context = Context;
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        let sessions: Vec<Address> = self.sessions.lock().unwrap().values().cloned().collect();
+        for session in sessions {
+            let _ = ctx.stop_worker(session).await;
+        }
+
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let (len, peer) = match self.socket.recv_from(&mut self.buf).await {
+            Ok(received) => received,
+            Err(e) => {
+                debug!("Cannot receive a datagram on the UDP inlet: {e}");
+                return Ok(true);
+            }
+        };
+        let datagram = self.buf[..len].to_vec();
+
+        let session = match self.session(ctx, peer).await {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                warn!("Dropping a datagram from {peer}, the UDP inlet has too many sessions");
+                return Ok(true);
+            }
+            Err(e) => {
+                warn!("Cannot start a session for {peer} on the UDP inlet: {e}");
+                return Ok(true);
+            }
+        };
+        if let Err(e) = ctx
+            .send(route![session], UdpPortalMessage::Datagram(datagram))
+            .await
+        {
+            warn!("Cannot forward a datagram from {peer} to its session: {e}");
+        }
+
+        Ok(true)
+    }
+}
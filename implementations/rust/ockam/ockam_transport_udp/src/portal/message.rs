@@ -0,0 +1,15 @@
+use ockam_core::Message;
+use serde::{Deserialize, Serialize};
+
+/// Message exchanged by the two sides of a UDP portal session
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Message)]
+pub(crate) enum UdpPortalMessage {
+    /// First message that an inlet session sends to the outlet
+    Ping,
+    /// First message that an outlet session sends to the inlet session
+    Pong,
+    /// A single datagram
+    Datagram(Vec<u8>),
+    /// Message to indicate that the session was closed on the other side
+    Disconnect,
+}
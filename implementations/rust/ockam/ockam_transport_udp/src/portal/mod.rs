@@ -0,0 +1,29 @@
+//! UDP portals.
+//!
+//! A UDP inlet binds a UDP socket and carries the datagrams it receives to a UDP outlet,
+//! through any route, for example a secure channel. The outlet sends them to its destination
+//! and carries the answers back to the inlet, which sends them to the original client.
+//!
+//! Each client address seen by an inlet gets its own session: a pair of
+//! [`UdpPortalWorker`]s, one on each side of the portal. The outlet side of a session
+//! uses its own socket so that the answers of the destination can be routed back to the
+//! right client. Datagram boundaries are preserved: each datagram is carried in its own
+//! [`UdpPortalMessage`]. Since UDP has no notion of connection, a session is closed when
+//! no datagram was exchanged for an idle timeout.
+
+pub use options::*;
+
+pub(crate) use addresses::*;
+pub(crate) use inlet_listener::*;
+pub(crate) use message::*;
+pub(crate) use outlet_listener::*;
+pub(crate) use portal_receiver::*;
+pub(crate) use portal_worker::*;
+
+mod addresses;
+mod inlet_listener;
+mod message;
+mod options;
+mod outlet_listener;
+mod portal_receiver;
+mod portal_worker;
@@ -0,0 +1,124 @@
+use crate::portal::{
+    Addresses, PortalType, UdpOutletOptions, UdpOutletSessions, UdpPortalMessage, UdpPortalWorker,
+};
+use ockam_core::{Address, Any, Decodable, DenyAll, Result, Routed, Worker};
+use ockam_node::{Context, RestartPolicy, WorkerBuilder};
+use ockam_transport_core::TransportError;
+use std::sync::atomic::Ordering;
+use tokio::net::lookup_host;
+use tracing::{debug, warn};
+
+/// A UDP Portal Outlet listen worker
+///
+/// UDP Portal Outlet listen workers are created by `UdpTransport`
+/// after a call is made to
+/// [`UdpTransport::create_outlet`](crate::UdpTransport::create_outlet).
+///
+/// The worker starts a new session for each ping received from an inlet session.
+#[derive(Clone)]
+pub(crate) struct UdpOutletListenWorker {
+    destination: String,
+    options: UdpOutletOptions,
+    sessions: UdpOutletSessions,
+}
+
+impl UdpOutletListenWorker {
+    pub(crate) async fn start(
+        ctx: &Context,
+        address: Address,
+        destination: String,
+        options: UdpOutletOptions,
+    ) -> Result<()> {
+        let access_control = options.incoming_access_control.clone();
+
+        options.setup_flow_control_for_outlet_listener(ctx.flow_controls(), &address);
+
+        let worker = Self {
+            destination,
+            options,
+            sessions: Default::default(),
+        };
+        WorkerBuilder::new(worker)
+            .with_address(address)
+            .with_incoming_access_control_arc(access_control)
+            .with_outgoing_access_control(DenyAll)
+            .with_restart_policy(RestartPolicy::one_for_one())
+            .start(ctx)
+            .await
+    }
+}
+
+#[ockam_core::worker]
+impl Worker for UdpOutletListenWorker {
+    type Message = Any;
+    type Context = Context;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        if !matches!(
+            UdpPortalMessage::decode(msg.payload())?,
+            UdpPortalMessage::Ping
+        ) {
+            return Err(TransportError::Protocol)?;
+        }
+
+        // Count the new session before it starts, since it is discounted when it stops
+        if self
+            .sessions
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                (n < self.options.max_sessions).then_some(n + 1)
+            })
+            .is_err()
+        {
+            warn!(
+                "Ignoring a ping on the UDP outlet {}, it has too many sessions",
+                ctx.address()
+            );
+            return Ok(());
+        }
+
+        if let Err(e) = self.start_session(ctx, &msg).await {
+            self.sessions.fetch_sub(1, Ordering::Relaxed);
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+impl UdpOutletListenWorker {
+    async fn start_session(&self, ctx: &Context, msg: &Routed<Any>) -> Result<()> {
+        // The destination is resolved for each session, to follow changes of its address
+        let destination = lookup_host(self.destination.as_str())
+            .await
+            .map_err(TransportError::from)?
+            .next()
+            .ok_or(TransportError::InvalidAddress)?;
+
+        let addresses = Addresses::generate(PortalType::Outlet);
+        self.options.setup_flow_control_for_outlet(
+            ctx.flow_controls(),
+            &addresses,
+            &msg.src_addr(),
+        );
+
+        UdpPortalWorker::start_new_outlet(
+            ctx,
+            destination,
+            self.sessions.clone(),
+            msg.return_route(),
+            addresses.clone(),
+            self.options.idle_timeout,
+            self.options.incoming_access_control.clone(),
+            self.options.outgoing_access_control.clone(),
+        )
+        .await?;
+
+        debug!("Created UDP Outlet session at {}", addresses.remote);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::portal::{Addresses, UdpPortalMessage};
+use ockam_core::{async_trait, route, AllowOnwardAddress, DenyAll, Processor, Result};
+use ockam_node::{Context, ProcessorBuilder};
+use std::sync::Arc;
+use tokio::net::UdpSocket;
+use tracing::debug;
+
+/// Maximum size of a UDP datagram
+pub(crate) const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+/// A processor reading the datagrams sent by the destination of an outlet session
+///
+/// Each datagram is forwarded to the [`UdpPortalWorker`](crate::portal::UdpPortalWorker)
+/// of the session, which sends it to the inlet side.
+pub(crate) struct UdpPortalRecvProcessor {
+    socket: Arc<UdpSocket>,
+    addresses: Addresses,
+    buf: Vec<u8>,
+}
+
+impl UdpPortalRecvProcessor {
+    /// Start a new `UdpPortalRecvProcessor` at the `receiver` address
+    pub(crate) async fn start(
+        ctx: &Context,
+        socket: Arc<UdpSocket>,
+        addresses: &Addresses,
+    ) -> Result<()> {
+        let processor = Self {
+            socket,
+            addresses: addresses.clone(),
+            buf: vec![0; MAX_DATAGRAM_SIZE],
+        };
+        ProcessorBuilder::new(processor)
+            .with_address(addresses.receiver.clone())
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(AllowOnwardAddress(addresses.internal.clone()))
+            .start(ctx)
+            .await
+    }
+}
+
+#[async_trait]
+impl Processor for UdpPortalRecvProcessor {
+    type Context = Context;
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let len = match self.socket.recv(&mut self.buf).await {
+            Ok(len) => len,
+            // Errors can be received for previously sent datagrams, for example
+            // when the destination is not listening. They don't close the session
+            Err(e) => {
+                debug!("Cannot receive a datagram from the outlet destination: {e}");
+                return Ok(true);
+            }
+        };
+
+        ctx.send(
+            route![self.addresses.internal.clone()],
+            UdpPortalMessage::Datagram(self.buf[..len].to_vec()),
+        )
+        .await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,407 @@
+use crate::portal::{Addresses, PortalType, UdpPortalMessage, UdpPortalRecvProcessor};
+use core::time::Duration;
+use ockam_core::{
+    Address, AllowSourceAddresses, Any, Decodable, DenyAll, IncomingAccessControl, Mailbox,
+    Mailboxes, OutgoingAccessControl, Result, Route, Routed, Worker,
+};
+use ockam_node::{Context, DelayedEvent, WorkerBuilder};
+use ockam_transport_core::TransportError;
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::net::UdpSocket;
+use tracing::{debug, trace, warn};
+
+/// Maximum number of datagrams kept by an inlet session until the outlet answers
+const MAX_PENDING_DATAGRAMS: usize = 64;
+
+/// Sessions of an inlet, indexed by client address.
+/// The value is the address of the 'internal' mailbox of the session worker
+pub(crate) type UdpInletSessions = Arc<Mutex<HashMap<SocketAddr, Address>>>;
+
+/// Number of open sessions of an outlet
+pub(crate) type UdpOutletSessions = Arc<AtomicUsize>;
+
+/// [`Worker`] for one side of a UDP portal session
+///
+/// See the documentation of the [`portal`](crate::portal) module.
+///
+/// # 'Internal' Mailbox
+///
+/// Receives the datagrams read from the local socket, either by the inlet listener
+/// or by the [`UdpPortalRecvProcessor`] of an outlet session. Also receives the timer
+/// messages used to close the session when it is idle.
+///
+/// # 'Remote' Mailbox
+///
+/// Sends [`UdpPortalMessage`]s to, and receives them from, the other side of the portal.
+pub(crate) struct UdpPortalWorker {
+    portal_type: PortalType,
+    addresses: Addresses,
+    socket: Arc<UdpSocket>,
+    /// Client of an inlet session. The socket of an outlet session is connected
+    /// to the outlet destination
+    peer: Option<SocketAddr>,
+    /// Route to the outlet listener, to which an inlet session sends its ping
+    ping_route: Option<Route>,
+    /// Route to the other side of the session, known once the outlet answered
+    remote_route: Option<Route>,
+    /// Datagrams received by an inlet session before the outlet answered
+    pending: VecDeque<Vec<u8>>,
+    timer: DelayedEvent<()>,
+    idle_timeout: Duration,
+    last_activity: Instant,
+    /// Sessions of the inlet, to remove this session when it is closed
+    sessions: Option<UdpInletSessions>,
+    /// Number of sessions of the outlet, decremented when this session is closed
+    outlet_sessions: Option<UdpOutletSessions>,
+}
+
+impl UdpPortalWorker {
+    /// Start the inlet side of a session for a new client of an inlet.
+    /// The session sends a ping to the outlet listener at the end of `outlet_route`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn start_new_inlet(
+        ctx: &Context,
+        socket: Arc<UdpSocket>,
+        peer: SocketAddr,
+        sessions: UdpInletSessions,
+        listener_address: Address,
+        outlet_route: Route,
+        addresses: Addresses,
+        idle_timeout: Duration,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+        outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let mut worker = Self::new(
+            ctx,
+            PortalType::Inlet,
+            addresses,
+            socket,
+            Some(peer),
+            idle_timeout,
+            Some(sessions),
+        )
+        .await?;
+        worker.ping_route = Some(outlet_route);
+
+        worker
+            .start(
+                ctx,
+                listener_address,
+                incoming_access_control,
+                outgoing_access_control,
+            )
+            .await
+    }
+
+    /// Start the outlet side of a session, answering the inlet session
+    /// whose ping was received with `pong_route` as a return route.
+    /// `outlet_sessions` must already count this session
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn start_new_outlet(
+        ctx: &Context,
+        destination: SocketAddr,
+        outlet_sessions: UdpOutletSessions,
+        pong_route: Route,
+        addresses: Addresses,
+        idle_timeout: Duration,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+        outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let bind_address: SocketAddr = if destination.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(bind_address)
+            .await
+            .map_err(TransportError::from)?;
+        socket
+            .connect(destination)
+            .await
+            .map_err(TransportError::from)?;
+        let socket = Arc::new(socket);
+
+        let mut worker = Self::new(
+            ctx,
+            PortalType::Outlet,
+            addresses.clone(),
+            socket.clone(),
+            None,
+            idle_timeout,
+            None,
+        )
+        .await?;
+        worker.remote_route = Some(pong_route);
+        worker.outlet_sessions = Some(outlet_sessions);
+
+        // The worker is started last, so that it only discounts the session
+        // when it stops if the session was fully started
+        UdpPortalRecvProcessor::start(ctx, socket, &addresses).await?;
+        if let Err(e) = worker
+            .start(
+                ctx,
+                addresses.receiver.clone(),
+                incoming_access_control,
+                outgoing_access_control,
+            )
+            .await
+        {
+            let _ = ctx.stop_processor(addresses.receiver).await;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    async fn new(
+        ctx: &Context,
+        portal_type: PortalType,
+        addresses: Addresses,
+        socket: Arc<UdpSocket>,
+        peer: Option<SocketAddr>,
+        idle_timeout: Duration,
+        sessions: Option<UdpInletSessions>,
+    ) -> Result<Self> {
+        let timer = DelayedEvent::create(ctx, addresses.internal.clone(), ()).await?;
+        Ok(Self {
+            portal_type,
+            addresses,
+            socket,
+            peer,
+            ping_route: None,
+            remote_route: None,
+            pending: VecDeque::new(),
+            timer,
+            idle_timeout,
+            last_activity: Instant::now(),
+            sessions,
+            outlet_sessions: None,
+        })
+    }
+
+    /// Start the worker. `reader_address` is the address of the processor reading
+    /// the datagrams from the local socket
+    async fn start(
+        self,
+        ctx: &Context,
+        reader_address: Address,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+        outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let internal_mailbox = Mailbox::new(
+            self.addresses.internal.clone(),
+            Arc::new(AllowSourceAddresses(vec![
+                reader_address,
+                self.timer.address(),
+            ])),
+            Arc::new(DenyAll),
+        );
+
+        let remote_mailbox = Mailbox::new(
+            self.addresses.remote.clone(),
+            incoming_access_control,
+            outgoing_access_control,
+        );
+
+        WorkerBuilder::new(self)
+            .with_mailboxes(Mailboxes::new(internal_mailbox, vec![remote_mailbox]))
+            .start(ctx)
+            .await
+    }
+
+    /// Send a datagram to the client of an inlet session, or to the destination of an outlet
+    async fn send_datagram(&self, datagram: &[u8]) -> Result<()> {
+        let result = match self.peer {
+            Some(peer) => self.socket.send_to(datagram, peer).await,
+            None => self.socket.send(datagram).await,
+        };
+        // A datagram which can not be sent is lost, as it would be on the network
+        if let Err(e) = result {
+            debug!(
+                "Cannot send a datagram from the UDP {} {}: {e}",
+                self.portal_type.str(),
+                self.addresses.internal
+            );
+        }
+        Ok(())
+    }
+
+    /// Send a message to the other side of the session
+    async fn send_remote(&self, ctx: &Context, msg: UdpPortalMessage) -> Result<()> {
+        if let Some(remote_route) = &self.remote_route {
+            ctx.send_from_address(remote_route.clone(), msg, self.addresses.remote.clone())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Handle messages from the other side of the session
+    async fn handle_remote(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let return_route = msg.return_route();
+        match UdpPortalMessage::decode(msg.payload())? {
+            UdpPortalMessage::Pong if self.portal_type == PortalType::Inlet => {
+                if self.remote_route.is_some() {
+                    warn!("Received a duplicate pong on {}", self.addresses.remote);
+                    return Ok(());
+                }
+                debug!("UDP inlet session {} is connected", self.addresses.remote);
+                self.remote_route = Some(return_route);
+                while let Some(datagram) = self.pending.pop_front() {
+                    self.send_remote(ctx, UdpPortalMessage::Datagram(datagram))
+                        .await?;
+                }
+                Ok(())
+            }
+            UdpPortalMessage::Datagram(datagram) => {
+                trace!(
+                    "UDP {} {} received a datagram of {} bytes from the other side",
+                    self.portal_type.str(),
+                    self.addresses.remote,
+                    datagram.len()
+                );
+                self.last_activity = Instant::now();
+                self.send_datagram(&datagram).await
+            }
+            UdpPortalMessage::Disconnect => {
+                debug!(
+                    "UDP {} session {} was closed by the other side",
+                    self.portal_type.str(),
+                    self.addresses.remote
+                );
+                self.remote_route = None;
+                ctx.stop_worker(self.addresses.internal.clone()).await
+            }
+            msg => {
+                warn!(
+                    "Unexpected message on the UDP {} {}: {msg:?}",
+                    self.portal_type.str(),
+                    self.addresses.remote
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Handle the datagrams read from the local socket
+    async fn handle_internal(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let datagram = match UdpPortalMessage::decode(msg.payload())? {
+            UdpPortalMessage::Datagram(datagram) => datagram,
+            msg => {
+                warn!(
+                    "Unexpected local message on {}: {msg:?}",
+                    self.addresses.internal
+                );
+                return Ok(());
+            }
+        };
+        self.last_activity = Instant::now();
+
+        if self.remote_route.is_some() {
+            self.send_remote(ctx, UdpPortalMessage::Datagram(datagram))
+                .await
+        } else if self.pending.len() < MAX_PENDING_DATAGRAMS {
+            self.pending.push_back(datagram);
+            Ok(())
+        } else {
+            trace!(
+                "Dropping a datagram, the outlet did not answer the session {} yet",
+                self.addresses.remote
+            );
+            Ok(())
+        }
+    }
+
+    /// Handle the timer messages: close the session if it is idle
+    async fn handle_timer(&mut self, ctx: &mut Context) -> Result<()> {
+        let idle = self.last_activity.elapsed();
+        if idle >= self.idle_timeout {
+            debug!(
+                "Closing the idle UDP {} session {}",
+                self.portal_type.str(),
+                self.addresses.remote
+            );
+            self.send_remote(ctx, UdpPortalMessage::Disconnect).await?;
+            self.remote_route = None;
+            ctx.stop_worker(self.addresses.internal.clone()).await
+        } else {
+            self.timer.schedule(self.idle_timeout - idle).await
+        }
+    }
+}
+
+#[ockam_core::worker]
+impl Worker for UdpPortalWorker {
+    type Message = Any;
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        match self.portal_type {
+            PortalType::Inlet => {
+                if let Some(ping_route) = self.ping_route.take() {
+                    ctx.send_from_address(
+                        ping_route,
+                        UdpPortalMessage::Ping,
+                        self.addresses.remote.clone(),
+                    )
+                    .await?;
+                }
+            }
+            PortalType::Outlet => self.send_remote(ctx, UdpPortalMessage::Pong).await?,
+        }
+        self.timer.schedule(self.idle_timeout).await
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.timer.cancel();
+
+        match self.portal_type {
+            PortalType::Inlet => {
+                if let (Some(sessions), Some(peer)) = (&self.sessions, self.peer) {
+                    if let Ok(mut sessions) = sessions.lock() {
+                        // The client may already have a new session
+                        if sessions.get(&peer) == Some(&self.addresses.internal) {
+                            sessions.remove(&peer);
+                        }
+                    }
+                }
+            }
+            PortalType::Outlet => {
+                if let Some(outlet_sessions) = self.outlet_sessions.take() {
+                    outlet_sessions.fetch_sub(1, Ordering::Relaxed);
+                }
+                let _ = ctx.stop_processor(self.addresses.receiver.clone()).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        match msg.msg_addr() {
+            // 'internal' mailbox
+            addr if addr == self.addresses.internal => {
+                if msg.sender()? == self.timer.address() {
+                    self.handle_timer(ctx).await
+                } else {
+                    self.handle_internal(ctx, msg).await
+                }
+            }
+
+            // 'remote' mailbox
+            addr if addr == self.addresses.remote => self.handle_remote(ctx, msg).await,
+
+            addr => {
+                warn!("Message received on an unknown address {addr}");
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use ockam_core::Message;
+use serde::{Deserialize, Serialize};
+
+/// Frame exchanged by the two sides of a reliable connection
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Message)]
+pub(crate) enum ReliableFrame {
+    /// A fragment of a message. A message is made of consecutive fragments,
+    /// the last one being marked with `last = true`
+    Data { seq: u64, last: bool, data: Vec<u8> },
+    /// Acknowledgement of all the fragments with a sequence number lower than `next`
+    /// and of the fragments received out of order listed in `selective`
+    Ack { next: u64, selective: Vec<u64> },
+}
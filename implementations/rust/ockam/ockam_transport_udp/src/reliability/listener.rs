@@ -0,0 +1,113 @@
+use crate::reliability::{UdpReliabilityOptions, UdpReliableWorker};
+use ockam_core::{
+    async_trait, Address, AllowAll, Any, Mailbox, Mailboxes, Result, Route, Routed, Worker,
+};
+use ockam_node::{Context, WorkerBuilder};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, trace};
+
+/// [`Worker`] accepting reliable connections
+///
+/// The frames sent by the initiator of a reliable connection are first received
+/// by this listener. A [`UdpReliableWorker`] is started to answer each new initiator,
+/// identified by the return route of its frames, and the frames are forwarded to it
+/// until the initiator starts sending them to the responder directly.
+///
+/// Responders notify the listener on its internal address when they stop, so that
+/// they are removed from the listener.
+pub(crate) struct UdpReliableListener {
+    /// Address used by the responders to notify the listener when they stop
+    internal_addr: Address,
+    options: UdpReliabilityOptions,
+    /// Address of the responder for each initiator
+    responders: HashMap<Route, Address>,
+}
+
+impl UdpReliableListener {
+    /// Start a listener at the given address
+    pub(crate) async fn create(
+        ctx: &Context,
+        address: Address,
+        options: UdpReliabilityOptions,
+    ) -> Result<()> {
+        let internal_addr = Address::random_tagged("UdpReliableListener.internal");
+        let listener = Self {
+            internal_addr: internal_addr.clone(),
+            options,
+            responders: HashMap::new(),
+        };
+
+        let main_mailbox = Mailbox::new(
+            address,
+            Arc::new(AllowAll), // FIXME: @ac
+            Arc::new(AllowAll), // FIXME: @ac
+        );
+        let internal_mailbox = Mailbox::new(
+            internal_addr,
+            Arc::new(AllowAll), // FIXME: @ac
+            Arc::new(AllowAll), // FIXME: @ac
+        );
+        WorkerBuilder::new(listener)
+            .with_mailboxes(Mailboxes::new(main_mailbox, vec![internal_mailbox]))
+            .start(ctx)
+            .await?;
+        Ok(())
+    }
+
+    /// Remove a responder which has been stopped
+    fn remove_responder(&mut self, responder: &Address) {
+        self.responders.retain(|_, r| r != responder);
+        trace!(
+            "Removed the responder {responder}, {} responders left",
+            self.responders.len()
+        );
+    }
+}
+
+#[async_trait]
+impl Worker for UdpReliableListener {
+    type Message = Any;
+    type Context = Context;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        if msg.msg_addr() == self.internal_addr {
+            let responder = msg.sender()?;
+            self.remove_responder(&responder);
+            return Ok(());
+        }
+
+        let return_route = msg.return_route();
+        let responder = match self.responders.get(&return_route) {
+            Some(responder) => responder.clone(),
+            None => {
+                debug!("Accepting a reliable connection from {return_route}");
+                let responder = UdpReliableWorker::create_responder(
+                    ctx,
+                    return_route.clone(),
+                    self.internal_addr.clone(),
+                    self.options.clone(),
+                )
+                .await?;
+                self.responders
+                    .insert(return_route.clone(), responder.clone());
+                responder
+            }
+        };
+
+        trace!("Forwarding a frame to the responder {responder}");
+        let local_message = msg
+            .into_local_message()
+            .replace_front_onward_route(&responder)?;
+        if let Err(e) = ctx.forward(local_message).await {
+            // The responder has been stopped
+            self.responders.remove(&return_route);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
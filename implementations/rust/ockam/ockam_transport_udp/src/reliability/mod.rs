@@ -0,0 +1,28 @@
+//! Optional reliability layer for the UDP transport.
+//!
+//! A reliable connection is a pair of workers, one on each node, exchanging
+//! [`ReliableFrame`]s over any route, for example a route going through a UDP
+//! address or through a [`UdpHolePuncher`](crate::UdpHolePuncher).
+//! Messages sent through a reliable connection are:
+//!
+//!  - split into fragments so that they fit in a single datagram
+//!  - numbered, acknowledged and retransmitted when they are lost
+//!  - delivered in order, once all their fragments have been received
+//!
+//! The number of unacknowledged fragments is limited by a congestion window which
+//! grows when fragments are acknowledged and shrinks when they have to be retransmitted.
+
+pub use options::*;
+
+pub(crate) use frame::*;
+pub(crate) use listener::*;
+pub(crate) use receiver::*;
+pub(crate) use sender::*;
+pub(crate) use worker::*;
+
+mod frame;
+mod listener;
+mod options;
+mod receiver;
+mod sender;
+mod worker;
@@ -0,0 +1,73 @@
+use core::time::Duration;
+
+/// Default maximum size of the data carried by a single fragment.
+/// It leaves enough room for the routes and the encoding overhead in a datagram
+/// which does not exceed a common path MTU
+const DEFAULT_MAX_FRAGMENT_SIZE: usize = 1024;
+
+/// Default delay before an unacknowledged fragment is sent again
+const DEFAULT_RETRANSMISSION_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Default number of times a fragment is sent again before the connection is dropped
+const DEFAULT_MAX_RETRANSMISSIONS: u32 = 10;
+
+/// Default number of unacknowledged fragments when a connection starts
+const DEFAULT_INITIAL_WINDOW: u32 = 4;
+
+/// Default maximum number of unacknowledged fragments
+const DEFAULT_MAX_WINDOW: u32 = 256;
+
+/// Options for a reliable UDP connection
+#[derive(Debug, Clone)]
+pub struct UdpReliabilityOptions {
+    pub(crate) max_fragment_size: usize,
+    pub(crate) retransmission_timeout: Duration,
+    pub(crate) max_retransmissions: u32,
+    pub(crate) initial_window: u32,
+    pub(crate) max_window: u32,
+}
+
+impl Default for UdpReliabilityOptions {
+    fn default() -> Self {
+        Self {
+            max_fragment_size: DEFAULT_MAX_FRAGMENT_SIZE,
+            retransmission_timeout: DEFAULT_RETRANSMISSION_TIMEOUT,
+            max_retransmissions: DEFAULT_MAX_RETRANSMISSIONS,
+            initial_window: DEFAULT_INITIAL_WINDOW,
+            max_window: DEFAULT_MAX_WINDOW,
+        }
+    }
+}
+
+impl UdpReliabilityOptions {
+    /// Default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum size of the data carried by a single fragment
+    pub fn with_max_fragment_size(mut self, max_fragment_size: usize) -> Self {
+        self.max_fragment_size = max_fragment_size.max(1);
+        self
+    }
+
+    /// Set the delay before an unacknowledged fragment is sent again.
+    /// The delay doubles for each retransmission of the same fragment
+    pub fn with_retransmission_timeout(mut self, retransmission_timeout: Duration) -> Self {
+        self.retransmission_timeout = retransmission_timeout;
+        self
+    }
+
+    /// Set the number of times a fragment is sent again before the connection is dropped
+    pub fn with_max_retransmissions(mut self, max_retransmissions: u32) -> Self {
+        self.max_retransmissions = max_retransmissions;
+        self
+    }
+
+    /// Set the initial and the maximum number of unacknowledged fragments
+    pub fn with_window(mut self, initial_window: u32, max_window: u32) -> Self {
+        self.max_window = max_window.max(1);
+        self.initial_window = initial_window.clamp(1, self.max_window);
+        self
+    }
+}
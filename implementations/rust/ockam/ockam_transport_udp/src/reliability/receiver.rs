@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use ockam_core::Result;
+use ockam_transport_core::{TransportError, MAXIMUM_MESSAGE_LENGTH};
+use tracing::warn;
+
+use crate::reliability::ReliableFrame;
+
+/// Receiving side of a reliable connection.
+///
+/// Fragments are buffered until all the fragments preceding them have been received,
+/// then they are reassembled into messages which are delivered in order.
+/// Messages longer than [`MAXIMUM_MESSAGE_LENGTH`] are dropped.
+pub(crate) struct ReliableReceiver {
+    /// Sequence number of the next fragment to deliver
+    next_seq: u64,
+    /// Maximum number of fragments received out of order which can be buffered
+    max_buffered: usize,
+    /// Fragments received out of order
+    buffered: BTreeMap<u64, (bool, Vec<u8>)>,
+    /// Data of the message currently being reassembled
+    message: Vec<u8>,
+    /// True when the message currently being reassembled is too long and its
+    /// remaining fragments must be dropped
+    dropping: bool,
+}
+
+impl ReliableReceiver {
+    pub(crate) fn new(max_buffered: usize) -> Self {
+        Self {
+            next_seq: 0,
+            max_buffered,
+            buffered: BTreeMap::new(),
+            message: vec![],
+            dropping: false,
+        }
+    }
+
+    /// Receive a fragment and return the messages which are now complete.
+    ///
+    /// Fragments which have already been received are ignored
+    pub(crate) fn on_data(&mut self, seq: u64, last: bool, data: Vec<u8>) -> Result<Vec<Vec<u8>>> {
+        if seq < self.next_seq || self.buffered.contains_key(&seq) {
+            return Ok(vec![]);
+        }
+        if seq - self.next_seq >= self.max_buffered as u64 {
+            return Err(TransportError::Capacity)?;
+        }
+        self.buffered.insert(seq, (last, data));
+
+        let mut messages = vec![];
+        while let Some((last, data)) = self.buffered.remove(&self.next_seq) {
+            self.next_seq += 1;
+            if !self.dropping {
+                if self.message.len() + data.len() > MAXIMUM_MESSAGE_LENGTH {
+                    warn!("Dropping a message longer than {MAXIMUM_MESSAGE_LENGTH} bytes");
+                    self.message = vec![];
+                    self.dropping = true;
+                } else {
+                    self.message.extend_from_slice(&data);
+                }
+            }
+            if last {
+                if self.dropping {
+                    self.dropping = false;
+                } else {
+                    messages.push(core::mem::take(&mut self.message));
+                }
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Return an acknowledgement for all the fragments received so far
+    pub(crate) fn ack(&self) -> ReliableFrame {
+        ReliableFrame::Ack {
+            next: self.next_seq,
+            selective: self.buffered.keys().copied().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reassembly_in_order() -> Result<()> {
+        let mut receiver = ReliableReceiver::new(16);
+
+        // fragments are received out of order and duplicated
+        assert!(receiver.on_data(1, true, vec![2])?.is_empty());
+        assert!(receiver.on_data(2, true, vec![3])?.is_empty());
+        assert_eq!(
+            receiver.ack(),
+            ReliableFrame::Ack {
+                next: 0,
+                selective: vec![1, 2]
+            }
+        );
+        assert_eq!(
+            receiver.on_data(0, false, vec![1])?,
+            vec![vec![1, 2], vec![3]]
+        );
+        assert!(receiver.on_data(1, true, vec![2])?.is_empty());
+        assert_eq!(
+            receiver.ack(),
+            ReliableFrame::Ack {
+                next: 3,
+                selective: vec![]
+            }
+        );
+
+        // fragments too far ahead are rejected
+        assert!(receiver.on_data(100, true, vec![]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_messages_too_long() -> Result<()> {
+        let mut receiver = ReliableReceiver::new(16);
+        let fragment = vec![0; MAXIMUM_MESSAGE_LENGTH / 2 + 1];
+
+        // the second fragment exceeds the maximum length, the whole message is dropped
+        assert!(receiver.on_data(0, false, fragment.clone())?.is_empty());
+        assert!(receiver.on_data(1, false, fragment.clone())?.is_empty());
+        assert!(receiver.on_data(2, true, vec![1])?.is_empty());
+
+        // the next message is delivered
+        assert_eq!(receiver.on_data(3, true, fragment.clone())?, vec![fragment]);
+        Ok(())
+    }
+}
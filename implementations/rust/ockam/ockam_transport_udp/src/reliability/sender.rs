@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Instant;
+
+use ockam_core::Result;
+use ockam_transport_core::TransportError;
+
+use crate::reliability::{ReliableFrame, UdpReliabilityOptions};
+
+/// Maximum exponent used to back off the retransmission timeout of a fragment
+const MAX_BACKOFF_EXPONENT: u32 = 6;
+
+/// A fragment which has been sent but not acknowledged yet
+struct InFlight {
+    last: bool,
+    data: Vec<u8>,
+    sent_at: Instant,
+    retransmissions: u32,
+}
+
+/// Sending side of a reliable connection.
+///
+/// Messages are split into numbered fragments which are sent as long as the
+/// congestion window allows it, and kept until they are acknowledged.
+pub(crate) struct ReliableSender {
+    options: UdpReliabilityOptions,
+    next_seq: u64,
+    /// Fragments waiting for some room in the congestion window
+    pending: VecDeque<(u64, bool, Vec<u8>)>,
+    /// Fragments sent but not acknowledged yet
+    in_flight: BTreeMap<u64, InFlight>,
+    /// Congestion window, in number of fragments
+    window: f64,
+    /// Slow start threshold, in number of fragments
+    threshold: f64,
+}
+
+impl ReliableSender {
+    pub(crate) fn new(options: UdpReliabilityOptions) -> Self {
+        let window = options.initial_window as f64;
+        let threshold = options.max_window as f64;
+        Self {
+            options,
+            next_seq: 0,
+            pending: VecDeque::new(),
+            in_flight: BTreeMap::new(),
+            window,
+            threshold,
+        }
+    }
+
+    /// Split a message into fragments to be sent
+    pub(crate) fn push_message(&mut self, message: &[u8]) {
+        let mut chunks = message.chunks(self.options.max_fragment_size).peekable();
+        if chunks.peek().is_none() {
+            self.push_fragment(true, vec![]);
+        }
+        while let Some(chunk) = chunks.next() {
+            let last = chunks.peek().is_none();
+            self.push_fragment(last, chunk.to_vec());
+        }
+    }
+
+    fn push_fragment(&mut self, last: bool, data: Vec<u8>) {
+        self.pending.push_back((self.next_seq, last, data));
+        self.next_seq += 1;
+    }
+
+    /// Return the fragments which can be sent now given the congestion window
+    pub(crate) fn poll_send(&mut self, now: Instant) -> Vec<ReliableFrame> {
+        let mut frames = vec![];
+        while (self.in_flight.len() as f64) < self.window.floor() {
+            let (seq, last, data) = match self.pending.pop_front() {
+                Some(fragment) => fragment,
+                None => break,
+            };
+            frames.push(ReliableFrame::Data {
+                seq,
+                last,
+                data: data.clone(),
+            });
+            self.in_flight.insert(
+                seq,
+                InFlight {
+                    last,
+                    data,
+                    sent_at: now,
+                    retransmissions: 0,
+                },
+            );
+        }
+        frames
+    }
+
+    /// Remove the acknowledged fragments and grow the congestion window
+    pub(crate) fn on_ack(&mut self, next: u64, selective: &[u64]) {
+        let mut acknowledged: Vec<u64> = self.in_flight.range(..next).map(|(s, _)| *s).collect();
+        acknowledged.extend(selective.iter().filter(|s| self.in_flight.contains_key(s)));
+
+        for seq in acknowledged {
+            if self.in_flight.remove(&seq).is_none() {
+                continue;
+            }
+            if self.window < self.threshold {
+                // slow start
+                self.window += 1.0;
+            } else {
+                // congestion avoidance
+                self.window += 1.0 / self.window;
+            }
+        }
+        self.window = self.window.min(self.options.max_window as f64);
+    }
+
+    /// Return the fragments which must be sent again because they have not been
+    /// acknowledged in time. The congestion window is reduced if that's the case.
+    ///
+    /// An error is returned if a fragment has been sent too many times
+    pub(crate) fn poll_retransmit(&mut self, now: Instant) -> Result<Vec<ReliableFrame>> {
+        let mut frames = vec![];
+        for (seq, fragment) in self.in_flight.iter_mut() {
+            let backoff = 2u32.pow(fragment.retransmissions.min(MAX_BACKOFF_EXPONENT));
+            if now.duration_since(fragment.sent_at) < self.options.retransmission_timeout * backoff
+            {
+                continue;
+            }
+            if fragment.retransmissions >= self.options.max_retransmissions {
+                return Err(TransportError::ConnectionDrop)?;
+            }
+            fragment.retransmissions += 1;
+            fragment.sent_at = now;
+            frames.push(ReliableFrame::Data {
+                seq: *seq,
+                last: fragment.last,
+                data: fragment.data.clone(),
+            });
+        }
+
+        if !frames.is_empty() {
+            self.threshold = (self.window / 2.0).max(2.0);
+            self.window = 1.0;
+        }
+        Ok(frames)
+    }
+
+    /// Return true if some fragments are not acknowledged yet
+    pub(crate) fn has_in_flight(&self) -> bool {
+        !self.in_flight.is_empty()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn window(&self) -> f64 {
+        self.window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[test]
+    fn test_fragmentation_and_window() {
+        let options = UdpReliabilityOptions::new()
+            .with_max_fragment_size(10)
+            .with_window(2, 8);
+        let mut sender = ReliableSender::new(options);
+        sender.push_message(&[1; 35]);
+
+        let now = Instant::now();
+        let frames = sender.poll_send(now);
+        assert_eq!(
+            frames,
+            vec![
+                ReliableFrame::Data {
+                    seq: 0,
+                    last: false,
+                    data: vec![1; 10]
+                },
+                ReliableFrame::Data {
+                    seq: 1,
+                    last: false,
+                    data: vec![1; 10]
+                }
+            ]
+        );
+        // the window is full
+        assert!(sender.poll_send(now).is_empty());
+
+        // acknowledging the fragments grows the window
+        sender.on_ack(2, &[]);
+        assert_eq!(sender.window(), 4.0);
+        let frames = sender.poll_send(now);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(
+            frames[1],
+            ReliableFrame::Data {
+                seq: 3,
+                last: true,
+                data: vec![1; 5]
+            }
+        );
+    }
+
+    #[test]
+    fn test_retransmission() {
+        let options = UdpReliabilityOptions::new()
+            .with_retransmission_timeout(Duration::from_millis(100))
+            .with_max_retransmissions(1)
+            .with_window(4, 8);
+        let mut sender = ReliableSender::new(options);
+        sender.push_message(&[1]);
+        sender.push_message(&[2]);
+
+        let now = Instant::now();
+        assert_eq!(sender.poll_send(now).len(), 2);
+        assert!(sender.poll_retransmit(now).unwrap().is_empty());
+
+        // the second fragment is acknowledged selectively, the first one is sent again
+        sender.on_ack(0, &[1]);
+        let frames = sender
+            .poll_retransmit(now + Duration::from_millis(100))
+            .unwrap();
+        assert_eq!(
+            frames,
+            vec![ReliableFrame::Data {
+                seq: 0,
+                last: true,
+                data: vec![1]
+            }]
+        );
+        assert_eq!(sender.window(), 1.0);
+
+        // the connection is dropped when the fragment is still not acknowledged
+        assert!(sender
+            .poll_retransmit(now + Duration::from_millis(400))
+            .is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::reliability::{ReliableFrame, ReliableReceiver, ReliableSender, UdpReliabilityOptions};
+use ockam_core::{
+    Address, AllowAll, Any, Decodable, Encodable, LocalMessage, Mailbox, Mailboxes, Result, Route,
+    Routed, TransportMessage, Worker,
+};
+use ockam_node::{Context, DelayedEvent, WorkerBuilder};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, trace, warn};
+
+/// Number of fragments which can be received out of order, relative to the maximum window
+const RECEIVE_BUFFER_FACTOR: usize = 4;
+
+/// [`Worker`] for one side of a reliable connection
+///
+/// See the documentation of the [`reliability`](crate::reliability) module.
+///
+/// # 'Main' Mailbox
+///
+/// Sends [`ReliableFrame`]s to, and receives them from, the other side of the connection.
+/// Also receives the timer messages used to retransmit the fragments which are not
+/// acknowledged in time.
+///
+/// # 'Local' Mailbox
+///
+/// Sends and receives to and from entities in the local node.
+///
+/// Messages received by the 'local' mailbox are fragmented and sent to the other side
+/// of the connection from the 'main' mailbox.
+///
+/// Messages reassembled by the 'main' mailbox are forwarded to local entities from
+/// the 'local' mailbox.
+pub(crate) struct UdpReliableWorker {
+    /// Address of main mailbox
+    main_addr: Address,
+    /// Address of local mailbox
+    local_addr: Address,
+    /// Route to the other side of the connection
+    remote_route: Route,
+    /// True when the route to the other side of the connection is known.
+    /// The initiator of a connection first sends its frames to a listener and
+    /// then directly to the worker answering them
+    remote_route_confirmed: bool,
+    /// For generating retransmission timer messages
+    timer: DelayedEvent<()>,
+    /// Is a retransmission timer message scheduled?
+    timer_scheduled: bool,
+    sender: ReliableSender,
+    receiver: ReliableReceiver,
+    options: UdpReliabilityOptions,
+    /// Address of the listener which created this worker, notified when the worker stops
+    listener: Option<Address>,
+}
+
+impl UdpReliableWorker {
+    /// Create the initiator of a reliable connection, sending its first frames to
+    /// a [`UdpReliableListener`](crate::reliability::UdpReliableListener) at the end of `route`.
+    ///
+    /// Return the address of the local mailbox
+    pub(crate) async fn create_initiator(
+        ctx: &Context,
+        route: Route,
+        options: UdpReliabilityOptions,
+    ) -> Result<Address> {
+        let (_, local_addr) = Self::create(ctx, route, false, None, options).await?;
+        Ok(local_addr)
+    }
+
+    /// Create the responder of a reliable connection, answering an initiator
+    /// whose frames are received with the given return route.
+    /// The listener is notified when the responder stops.
+    ///
+    /// Return the address of the main mailbox
+    pub(crate) async fn create_responder(
+        ctx: &Context,
+        return_route: Route,
+        listener: Address,
+        options: UdpReliabilityOptions,
+    ) -> Result<Address> {
+        let (main_addr, _) = Self::create(ctx, return_route, true, Some(listener), options).await?;
+        Ok(main_addr)
+    }
+
+    async fn create(
+        ctx: &Context,
+        remote_route: Route,
+        remote_route_confirmed: bool,
+        listener: Option<Address>,
+        options: UdpReliabilityOptions,
+    ) -> Result<(Address, Address)> {
+        // Create worker' addresses, timer & mailboxes
+        let main_addr = Address::random_tagged("UdpReliableWorker.main");
+        let local_addr = Address::random_tagged("UdpReliableWorker.local");
+
+        let timer = DelayedEvent::create(ctx, main_addr.clone(), ()).await?;
+
+        let main_mailbox = Mailbox::new(
+            main_addr.clone(),
+            Arc::new(AllowAll), // FIXME: @ac
+            Arc::new(AllowAll), // FIXME: @ac
+        );
+
+        let local_mailbox = Mailbox::new(
+            local_addr.clone(),
+            Arc::new(AllowAll), // FIXME: @ac
+            Arc::new(AllowAll), // FIXME: @ac
+        );
+
+        // Create and start worker
+        let receiver = ReliableReceiver::new(options.max_window as usize * RECEIVE_BUFFER_FACTOR);
+        let worker = Self {
+            main_addr: main_addr.clone(),
+            local_addr: local_addr.clone(),
+            remote_route,
+            remote_route_confirmed,
+            timer,
+            timer_scheduled: false,
+            sender: ReliableSender::new(options.clone()),
+            receiver,
+            options,
+            listener,
+        };
+        WorkerBuilder::new(worker)
+            .with_mailboxes(Mailboxes::new(main_mailbox, vec![local_mailbox]))
+            .start(ctx)
+            .await?;
+
+        Ok((main_addr, local_addr))
+    }
+
+    /// Send a frame to the other side of the connection
+    async fn send_frame(&self, ctx: &Context, frame: ReliableFrame) -> Result<()> {
+        ctx.send(self.remote_route.clone(), frame).await
+    }
+
+    /// Send the fragments allowed by the congestion window and make sure that
+    /// they will be retransmitted if they are not acknowledged
+    async fn send_fragments(&mut self, ctx: &Context) -> Result<()> {
+        for frame in self.sender.poll_send(Instant::now()) {
+            self.send_frame(ctx, frame).await?;
+        }
+        if self.sender.has_in_flight() && !self.timer_scheduled {
+            self.timer
+                .schedule(self.options.retransmission_timeout)
+                .await?;
+            self.timer_scheduled = true;
+        }
+        Ok(())
+    }
+
+    /// Handle frames from the other side of the connection
+    async fn handle_remote(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Any>,
+        return_route: Route,
+    ) -> Result<()> {
+        if !self.remote_route_confirmed {
+            trace!("Received the first frame from the responder, updating the remote route");
+            self.remote_route = return_route;
+            self.remote_route_confirmed = true;
+        }
+
+        match ReliableFrame::decode(msg.payload())? {
+            ReliableFrame::Data { seq, last, data } => {
+                match self.receiver.on_data(seq, last, data) {
+                    Ok(messages) => {
+                        for message in messages {
+                            self.forward_local(ctx, message).await?;
+                        }
+                    }
+                    // The fragment is dropped and will be sent again
+                    Err(e) => debug!("Dropping fragment {seq}: {e}"),
+                }
+                self.send_frame(ctx, self.receiver.ack()).await
+            }
+            ReliableFrame::Ack { next, selective } => {
+                self.sender.on_ack(next, &selective);
+                self.send_fragments(ctx).await
+            }
+        }
+    }
+
+    /// Forward a reassembled message to local entities
+    async fn forward_local(&self, ctx: &Context, message: Vec<u8>) -> Result<()> {
+        let local_message =
+            LocalMessage::from_transport_message(TransportMessage::decode_message(message)?)
+                .push_front_return_route(&self.local_addr);
+
+        debug!("Reliable => App: {:?}", local_message);
+        ctx.forward(local_message).await
+    }
+
+    /// Handle retransmission timer messages
+    async fn handle_timer(&mut self, ctx: &mut Context) -> Result<()> {
+        self.timer_scheduled = false;
+
+        match self.sender.poll_retransmit(Instant::now()) {
+            Ok(frames) => {
+                for frame in frames {
+                    trace!("Retransmitting {:?}", frame);
+                    self.send_frame(ctx, frame).await?;
+                }
+                self.send_fragments(ctx).await
+            }
+            Err(e) => {
+                warn!(
+                    "Stopping the reliable connection {}, the other side is not responding: {e}",
+                    self.local_addr
+                );
+                ctx.stop_worker(self.main_addr.clone()).await
+            }
+        }
+    }
+
+    /// Handle messages from local entities
+    async fn handle_local(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let local_message = msg.into_local_message().pop_front_onward_route()?;
+        debug!("App => Reliable: {:?}", local_message);
+
+        let message = local_message.into_transport_message().encode()?;
+        self.sender.push_message(&message);
+        self.send_fragments(ctx).await
+    }
+}
+
+#[ockam_core::worker]
+impl Worker for UdpReliableWorker {
+    type Message = Any;
+    type Context = Context;
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.timer.cancel();
+        if let Some(listener) = self.listener.take() {
+            // The listener may already be stopped
+            let _ = ctx.send(listener, ()).await;
+        }
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        match msg.msg_addr() {
+            // 'main' mailbox
+            addr if addr == self.main_addr => {
+                if msg.sender()? == self.timer.address() {
+                    self.handle_timer(ctx).await
+                } else {
+                    let return_route = msg.return_route();
+                    self.handle_remote(ctx, msg, return_route).await
+                }
+            }
+
+            // 'local' mailbox
+            addr if addr == self.local_addr => self.handle_local(ctx, msg).await,
+
+            addr => {
+                warn!("Message received on an unknown address {addr}");
+                Ok(())
+            }
+        }
+    }
+}
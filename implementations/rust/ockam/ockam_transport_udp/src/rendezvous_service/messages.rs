@@ -25,6 +25,8 @@ pub enum RendezvousRequest {
 /// Response type for UDP Hole Punching Rendezvous service
 #[derive(Serialize, Deserialize, Debug, Message)]
 pub enum RendezvousResponse {
+    /// Public route to the queried puncher
     Query(Result<Route>),
+    /// Reply to a [`RendezvousRequest::Ping`]
     Pong,
 }
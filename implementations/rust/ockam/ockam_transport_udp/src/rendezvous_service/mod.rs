@@ -1,4 +1,4 @@
-pub(crate) use messages::{RendezvousRequest, RendezvousResponse};
+pub use messages::{RendezvousRequest, RendezvousResponse};
 pub use rendezvous::UdpRendezvousService;
 
 mod messages;
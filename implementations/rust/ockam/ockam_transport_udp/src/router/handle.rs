@@ -1,6 +1,7 @@
 use crate::router::messages::{UdpRouterRequest, UdpRouterResponse};
-use ockam_core::{Address, AllowAll, Result};
+use ockam_core::{async_trait, Address, AllowAll, AsyncTryClone, Result};
 use ockam_node::Context;
+use ockam_transport_core::TransportError;
 use std::net::SocketAddr;
 
 /// A handle to connect to a UdpRouter
@@ -11,6 +12,13 @@ pub(crate) struct UdpRouterHandle {
     api_addr: Address,
 }
 
+#[async_trait]
+impl AsyncTryClone for UdpRouterHandle {
+    async fn async_try_clone(&self) -> Result<Self> {
+        Self::try_new(&self.ctx, &self.api_addr).await
+    }
+}
+
 impl UdpRouterHandle {
     pub async fn try_new(ctx: &Context, api_addr: &Address) -> Result<Self> {
         // FIXME: @ac. The handle will only ever need to send & receive messages
@@ -29,14 +37,51 @@ impl UdpRouterHandle {
         })
     }
 
+    /// Context used by this handle
+    pub(crate) fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
     /// Request router start listening on a local UDP port
     /// so the local node can act as a server to other nodes
     pub async fn listen(&self, local_addr: SocketAddr) -> Result<()> {
         let msg = UdpRouterRequest::Listen { local_addr };
-        let UdpRouterResponse::Listen(res) = self
+        if let UdpRouterResponse::Listen(res) = self
             .ctx
             .send_and_receive(self.api_addr.clone(), msg)
-            .await?;
-        res
+            .await?
+        {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType)?
+        }
+    }
+
+    /// Return the address of a worker forwarding messages to the given UDP address
+    pub async fn resolve(&self, peer: Address) -> Result<Address> {
+        let msg = UdpRouterRequest::Resolve { peer };
+        if let UdpRouterResponse::Resolve(res) = self
+            .ctx
+            .send_and_receive(self.api_addr.clone(), msg)
+            .await?
+        {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType)?
+        }
+    }
+
+    /// Stop a worker created with [`UdpRouterHandle::resolve`]
+    pub async fn disconnect(&self, worker_addr: Address) -> Result<()> {
+        let msg = UdpRouterRequest::Disconnect { worker_addr };
+        if let UdpRouterResponse::Disconnect(res) = self
+            .ctx
+            .send_and_receive(self.api_addr.clone(), msg)
+            .await?
+        {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType)?
+        }
     }
 }
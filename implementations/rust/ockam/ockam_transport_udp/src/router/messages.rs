@@ -1,4 +1,4 @@
-use ockam_core::{Message, Result};
+use ockam_core::{Address, Message, Result};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 
@@ -7,9 +7,16 @@ pub enum UdpRouterRequest {
     /// Listen on a local UDP port so the local node can
     /// act as a server to other nodes
     Listen { local_addr: SocketAddr },
+    /// Return the address of a worker forwarding messages to a UDP peer,
+    /// creating that worker if necessary
+    Resolve { peer: Address },
+    /// Stop the worker forwarding messages to a UDP peer
+    Disconnect { worker_addr: Address },
 }
 
 #[derive(Serialize, Deserialize, Debug, Message)]
 pub enum UdpRouterResponse {
     Listen(Result<()>),
+    Resolve(Result<Address>),
+    Disconnect(Result<()>),
 }
@@ -1,18 +1,21 @@
 use crate::router::messages::{UdpRouterRequest, UdpRouterResponse};
 use crate::router::UdpRouterHandle;
-use crate::workers::{TransportMessageCodec, UdpListenProcessor, UdpSendWorker};
+use crate::workers::{TransportMessageCodec, UdpListenProcessor, UdpPeerWorker, UdpSendWorker};
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
 use futures_util::StreamExt;
 use ockam_core::{
     async_trait, Address, AllowAll, Any, Decodable, DenyAll, LocalMessage, Mailbox, Mailboxes,
     Result, Routed, Worker,
 };
-use ockam_node::{Context, WorkerBuilder};
+use ockam_node::{Context, DelayedEvent, WorkerBuilder};
 use ockam_transport_core::TransportError;
+use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
-use tokio::net::UdpSocket;
+use tokio::net::{lookup_host, UdpSocket};
 use tokio_util::udp::UdpFramed;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, trace, warn};
 
 /// The router for the UDP transport
 ///
@@ -31,18 +34,51 @@ use tracing::{debug, error, trace};
 /// sender. 'server' messages bypass the router as listeners inject the
 /// sender's address into the return route of received messages.
 ///
+/// When a route containing UDP addresses is resolved, the router creates
+/// a [`UdpPeerWorker`](UdpPeerWorker) per peer, forwarding messages to the 'client' sender.
+/// A peer worker is stopped once every resolution of its peer has been disconnected, or
+/// when it has not forwarded any message for [`PEER_IDLE_TIMEOUT`]. A route must then be
+/// resolved again.
+///
 /// This transport only supports IPv4.
 pub(crate) struct UdpRouter {
     ctx: Context,
     main_addr: Address,
     api_addr: Address,
+    reap_addr: Address,
     /// Sender for 'client' messages
     client_sender: Address,
+    /// Workers forwarding messages to UDP peers, by UDP address
+    peers: HashMap<Address, UdpPeer>,
+    /// Timer used to periodically stop idle peer workers
+    reap_timer: DelayedEvent<()>,
+    peer_idle_timeout: Duration,
+}
+
+/// Duration after which a peer worker which did not forward any message is stopped
+pub(crate) const PEER_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// A worker forwarding messages to a UDP peer
+struct UdpPeer {
+    worker_addr: Address,
+    /// Number of resolutions of the peer which have not been disconnected yet
+    references: usize,
+    /// Time of the last activity of the worker, in seconds since the epoch
+    last_activity: Arc<AtomicU64>,
 }
 
 impl UdpRouter {
     /// Create and register a new UDP router with the node context
     pub(crate) async fn register(ctx: &Context) -> Result<UdpRouterHandle> {
+        Self::register_with_peer_idle_timeout(ctx, PEER_IDLE_TIMEOUT).await
+    }
+
+    /// Create and register a new UDP router, stopping the peer workers which
+    /// did not forward any message for `peer_idle_timeout`
+    pub(crate) async fn register_with_peer_idle_timeout(
+        ctx: &Context,
+        peer_idle_timeout: Duration,
+    ) -> Result<UdpRouterHandle> {
         // This context is only used to start workers, doesn't need to send nor receive messages
         let child_ctx = ctx
             .new_detached(
@@ -54,6 +90,7 @@ impl UdpRouter {
 
         let main_addr = Address::random_tagged("UdpRouter.main_addr");
         let api_addr = Address::random_tagged("UdpRouter.api_addr");
+        let reap_addr = Address::random_tagged("UdpRouter.reap_addr");
         debug!("Initialising new UdpRouter with address {}", &main_addr);
 
         let handle = UdpRouterHandle::try_new(&child_ctx, &api_addr).await?;
@@ -65,11 +102,17 @@ impl UdpRouter {
         )
         .await?;
 
+        let reap_timer = DelayedEvent::create(&child_ctx, reap_addr.clone(), ()).await?;
+
         let router = Self {
             ctx: child_ctx,
             main_addr: main_addr.clone(),
             api_addr: api_addr.clone(),
+            reap_addr: reap_addr.clone(),
             client_sender,
+            peers: HashMap::new(),
+            reap_timer,
+            peer_idle_timeout,
         };
 
         let main_mailbox = Mailbox::new(
@@ -82,8 +125,12 @@ impl UdpRouter {
             Arc::new(AllowAll), // FIXME: @ac
             Arc::new(AllowAll), // FIXME: @ac
         );
+        let reap_mailbox = Mailbox::new(reap_addr, Arc::new(AllowAll), Arc::new(DenyAll));
         WorkerBuilder::new(router)
-            .with_mailboxes(Mailboxes::new(main_mailbox, vec![api_mailbox]))
+            .with_mailboxes(Mailboxes::new(
+                main_mailbox,
+                vec![api_mailbox, reap_mailbox],
+            ))
             .start(ctx)
             .await?;
 
@@ -100,6 +147,97 @@ impl UdpRouter {
             .await
     }
 
+    /// Return the address of the worker forwarding messages to a UDP peer,
+    /// creating that worker if necessary
+    async fn resolve_peer(&mut self, peer: Address) -> Result<Address> {
+        let now = ockam_core::compat::time::now()?;
+        if let Some(udp_peer) = self.peers.get_mut(&peer) {
+            udp_peer.references += 1;
+            udp_peer.last_activity.store(now, Ordering::Relaxed);
+            return Ok(udp_peer.worker_addr.clone());
+        }
+
+        // This transport only supports IPv4
+        let is_ipv4 = lookup_host(peer.address())
+            .await
+            .map_err(|_| TransportError::InvalidAddress)?
+            .any(|a| a.is_ipv4());
+        if !is_ipv4 {
+            warn!("No IPv4 address resolved for peer {}", peer);
+            return Err(TransportError::InvalidAddress)?;
+        }
+
+        let worker_addr = Address::random_tagged("UdpPeerWorker");
+        let last_activity = Arc::new(AtomicU64::new(now));
+        let worker = UdpPeerWorker::new(
+            self.client_sender.clone(),
+            peer.clone(),
+            last_activity.clone(),
+        );
+        // FIXME: @ac
+        self.ctx.start_worker(worker_addr.clone(), worker).await?;
+        debug!("Created peer worker {} for {}", worker_addr, peer);
+
+        self.peers.insert(
+            peer,
+            UdpPeer {
+                worker_addr: worker_addr.clone(),
+                references: 1,
+                last_activity,
+            },
+        );
+        Ok(worker_addr)
+    }
+
+    /// Release a resolution of a UDP peer.
+    /// The worker forwarding messages to that peer is stopped once all its resolutions are released
+    async fn disconnect_peer(&mut self, worker_addr: Address) -> Result<()> {
+        let peer = match self
+            .peers
+            .iter_mut()
+            .find(|(_, udp_peer)| udp_peer.worker_addr == worker_addr)
+        {
+            Some((peer, udp_peer)) => {
+                udp_peer.references -= 1;
+                if udp_peer.references > 0 {
+                    return Ok(());
+                }
+                peer.clone()
+            }
+            None => return Err(TransportError::PeerNotFound)?,
+        };
+        self.peers.remove(&peer);
+        self.ctx.stop_worker(worker_addr).await
+    }
+
+    /// Stop the peer workers which did not forward any message since the last check
+    async fn reap_idle_peers(&mut self) -> Result<()> {
+        let now = ockam_core::compat::time::now()?;
+        let idle_timeout = self.peer_idle_timeout.as_secs();
+        let idle_peers: Vec<Address> = self
+            .peers
+            .iter()
+            .filter(|(_, udp_peer)| {
+                now.saturating_sub(udp_peer.last_activity.load(Ordering::Relaxed)) >= idle_timeout
+            })
+            .map(|(peer, _)| peer.clone())
+            .collect();
+
+        for peer in idle_peers {
+            if let Some(udp_peer) = self.peers.remove(&peer) {
+                debug!(
+                    "Stopping idle peer worker {} for {}",
+                    udp_peer.worker_addr, peer
+                );
+                if let Err(e) = self.ctx.stop_worker(udp_peer.worker_addr).await {
+                    warn!("Failed to stop idle peer worker for {}: {}", peer, e);
+                }
+            }
+        }
+
+        self.reap_timer.schedule(self.peer_idle_timeout).await
+    }
+
     /// Create a sender, listener pair for the given socket address.
     ///
     /// Returns the address of the created sender.
@@ -140,6 +278,12 @@ impl Worker for UdpRouter {
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
         ctx.set_cluster(crate::CLUSTER_NAME).await?;
+        self.reap_timer.schedule(self.peer_idle_timeout).await?;
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> Result<()> {
+        self.reap_timer.cancel();
         Ok(())
     }
 
@@ -167,7 +311,23 @@ impl Worker for UdpRouter {
                     ctx.send_from_address(return_route, UdpRouterResponse::Listen(res), msg_addr)
                         .await?;
                 }
+                UdpRouterRequest::Resolve { peer } => {
+                    let res = self.resolve_peer(peer).await;
+                    ctx.send_from_address(return_route, UdpRouterResponse::Resolve(res), msg_addr)
+                        .await?;
+                }
+                UdpRouterRequest::Disconnect { worker_addr } => {
+                    let res = self.disconnect_peer(worker_addr).await;
+                    ctx.send_from_address(
+                        return_route,
+                        UdpRouterResponse::Disconnect(res),
+                        msg_addr,
+                    )
+                    .await?;
+                }
             };
+        } else if msg_addr == self.reap_addr {
+            self.reap_idle_peers().await?;
         } else {
             return Err(TransportError::Protocol)?;
         }
@@ -175,3 +335,60 @@ impl Worker for UdpRouter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::UdpRouter;
+    use core::time::Duration;
+    use ockam_core::{Address, Result};
+    use ockam_node::Context;
+
+    #[ockam_macros::test]
+    async fn peer_worker_stopped_after_last_disconnection(ctx: &mut Context) -> Result<()> {
+        let handle = UdpRouter::register(ctx).await?;
+        let peer = Address::from_string("127.0.0.1:4000");
+
+        let worker_addr = handle.resolve(peer.clone()).await?;
+        assert_eq!(handle.resolve(peer).await?, worker_addr);
+
+        // the worker is still used by the second resolution
+        handle.disconnect(worker_addr.clone()).await?;
+        assert!(ctx.list_workers().await?.contains(&worker_addr));
+
+        handle.disconnect(worker_addr.clone()).await?;
+        assert!(is_stopped(ctx, &worker_addr).await?);
+        assert!(handle.disconnect(worker_addr).await.is_err());
+
+        ctx.stop().await
+    }
+
+    #[ockam_macros::test]
+    async fn idle_peer_worker_is_stopped(ctx: &mut Context) -> Result<()> {
+        let handle =
+            UdpRouter::register_with_peer_idle_timeout(ctx, Duration::from_secs(1)).await?;
+
+        let worker_addr = handle
+            .resolve(Address::from_string("127.0.0.1:4000"))
+            .await?;
+        assert!(ctx.list_workers().await?.contains(&worker_addr));
+
+        assert!(
+            is_stopped(ctx, &worker_addr).await?,
+            "the idle peer worker must be stopped"
+        );
+        assert!(handle.disconnect(worker_addr).await.is_err());
+
+        ctx.stop().await
+    }
+
+    /// Wait until a worker is stopped, for at most 5 seconds
+    async fn is_stopped(ctx: &Context, worker_addr: &Address) -> Result<bool> {
+        for _ in 0..50 {
+            if !ctx.list_workers().await?.contains(worker_addr) {
+                return Ok(true);
+            }
+            ctx.sleep(Duration::from_millis(100)).await;
+        }
+        Ok(false)
+    }
+}
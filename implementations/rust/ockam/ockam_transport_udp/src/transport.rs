@@ -1,13 +1,26 @@
+use crate::portal::{
+    UdpInletListenProcessor, UdpInletOptions, UdpOutletListenWorker, UdpOutletOptions,
+};
+use crate::reliability::{UdpReliabilityOptions, UdpReliableListener, UdpReliableWorker};
 use crate::router::{UdpRouter, UdpRouterHandle};
-use ockam_core::{async_trait, Result};
+use crate::UDP;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Address, AsyncTryClone, Error, Result, Route, TransportType};
 use ockam_node::{Context, HasContext};
-use ockam_transport_core::TransportError;
+use ockam_transport_core::{Transport, TransportError};
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 /// High level management interface for UDP transport
 ///
 /// A node will have, at most, one UDP transport running.
 ///
 /// This transport only supports IPv4.
+///
+/// The transport is registered with the node so that routes containing
+/// `UDP` addresses can be resolved with `Context::resolve_transport_route`.
+#[derive(AsyncTryClone)]
+#[async_try_clone(crate = "ockam_core")]
 pub struct UdpTransport {
     router_handle: UdpRouterHandle,
 }
@@ -16,7 +29,10 @@ impl UdpTransport {
     /// Create a new UDP transport for the current node
     pub async fn create(ctx: &Context) -> Result<UdpTransport> {
         let router_handle = UdpRouter::register(ctx).await?;
-        Ok(Self { router_handle })
+        let udp = Self { router_handle };
+        // make the UDP transport available for the resolution of UDP addresses in routes
+        ctx.register_transport(Arc::new(udp.async_try_clone().await?));
+        Ok(udp)
     }
 
     /// Start listening to incoming datagrams on a specified local address
@@ -27,6 +43,107 @@ impl UdpTransport {
             .map_err(|_| TransportError::InvalidAddress)?;
         self.router_handle.listen(bind_addr).await
     }
+
+    /// Start accepting reliable connections at the given local address.
+    ///
+    /// Messages received through a reliable connection are forwarded to their onward route
+    /// and can be answered using their return route, as with any other transport.
+    pub async fn create_reliable_listener(
+        &self,
+        address: impl Into<Address>,
+        options: UdpReliabilityOptions,
+    ) -> Result<()> {
+        UdpReliableListener::create(self.router_handle.ctx(), address.into(), options).await
+    }
+
+    /// Create a reliable connection to a listener started with
+    /// [`UdpTransport::create_reliable_listener`] at the end of `route`.
+    ///
+    /// The route can go through a UDP address or a [`UdpHolePuncher`](crate::UdpHolePuncher).
+    /// Return the local address of the connection: messages sent to a route starting with
+    /// this address are delivered in order to the rest of the route, on the other side.
+    pub async fn create_reliable_connection(
+        &self,
+        route: impl Into<Route>,
+        options: UdpReliabilityOptions,
+    ) -> Result<Address> {
+        UdpReliableWorker::create_initiator(self.router_handle.ctx(), route.into(), options).await
+    }
+
+    /// Create a UDP inlet bound to `bind_addr`, carrying the datagrams that it receives to
+    /// the UDP outlet at the end of `outlet_route`.
+    ///
+    /// Return the socket address of the inlet and the address of its listener, which can
+    /// be used to stop it with [`UdpTransport::stop_inlet`]
+    pub async fn create_inlet(
+        &self,
+        bind_addr: impl AsRef<str>,
+        outlet_route: impl Into<Route>,
+        options: UdpInletOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let bind_addr = bind_addr
+            .as_ref()
+            .parse()
+            .map_err(|_| TransportError::InvalidAddress)?;
+        UdpInletListenProcessor::start(
+            self.router_handle.ctx(),
+            outlet_route.into(),
+            bind_addr,
+            options,
+        )
+        .await
+    }
+
+    /// Stop a UDP inlet and all its sessions
+    pub async fn stop_inlet(&self, addr: impl Into<Address>) -> Result<()> {
+        self.router_handle.ctx().stop_processor(addr).await
+    }
+
+    /// Create a UDP outlet at `address`, sending the datagrams received from UDP inlets
+    /// to `destination`, a `hostname:port` string
+    pub async fn create_outlet(
+        &self,
+        address: impl Into<Address>,
+        destination: impl Into<String>,
+        options: UdpOutletOptions,
+    ) -> Result<()> {
+        UdpOutletListenWorker::start(
+            self.router_handle.ctx(),
+            address.into(),
+            destination.into(),
+            options,
+        )
+        .await
+    }
+
+    /// Stop a UDP outlet. The sessions which are already established are closed
+    /// when they become idle
+    pub async fn stop_outlet(&self, addr: impl Into<Address>) -> Result<()> {
+        self.router_handle.ctx().stop_worker(addr).await
+    }
+}
+
+#[async_trait]
+impl Transport for UdpTransport {
+    fn transport_type(&self) -> TransportType {
+        UDP
+    }
+
+    async fn resolve_address(&self, address: Address) -> Result<Address> {
+        if address.transport_type() == UDP {
+            self.router_handle.resolve(address).await
+        } else {
+            Err(Error::new(
+                Origin::Transport,
+                Kind::NotFound,
+                format!("this address can not be resolved by a UDP transport {address}"),
+            ))
+        }
+    }
+
+    async fn disconnect(&self, address: Address) -> Result<()> {
+        self.router_handle.disconnect(address).await
+    }
 }
 
 /// This trait adds a `create_udp_transport` method to any struct returning a Context.
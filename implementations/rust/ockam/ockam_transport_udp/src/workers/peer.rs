@@ -0,0 +1,58 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+use ockam_core::{async_trait, Address, Any, Result, Routed, Worker};
+use ockam_node::Context;
+use std::sync::Arc;
+use tracing::trace;
+
+/// A worker representing a remote UDP peer
+///
+/// This worker is created when a route containing a UDP address is resolved
+/// with [`Context::resolve_transport_route`]. It forwards the messages it receives to the
+/// 'client' sender ([`UdpSendWorker`](crate::workers::UdpSendWorker)), inserting the UDP
+/// address of the peer in the onward route.
+///
+/// The time of the last forwarded message is shared with the router, which stops idle workers.
+pub(crate) struct UdpPeerWorker {
+    /// Address of the 'client' sender
+    sender_addr: Address,
+    /// UDP address of the peer
+    peer: Address,
+    /// Time of the last activity, in seconds since the epoch
+    last_activity: Arc<AtomicU64>,
+}
+
+impl UdpPeerWorker {
+    /// Create a new `UdpPeerWorker`
+    pub(crate) fn new(sender_addr: Address, peer: Address, last_activity: Arc<AtomicU64>) -> Self {
+        Self {
+            sender_addr,
+            peer,
+            last_activity,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for UdpPeerWorker {
+    type Message = Any;
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        self.last_activity
+            .store(ockam_core::compat::time::now()?, Ordering::Relaxed);
+        let msg = msg
+            .into_local_message()
+            .replace_front_onward_route(&self.peer)?
+            .push_front_onward_route(&self.sender_addr);
+        trace!("Forwarding message to UDP peer {}", self.peer);
+        ctx.forward(msg).await
+    }
+}
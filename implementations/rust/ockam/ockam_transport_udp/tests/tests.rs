@@ -1,7 +1,9 @@
 use ockam_core::compat::rand::{self, Rng};
-use ockam_core::{route, Address, AllowAll, Result, Routed, Worker};
+use ockam_core::{route, Address, AllowAll, Any, Result, Routed, Worker};
 use ockam_node::{Context, MessageReceiveOptions, MessageSendReceiveOptions};
-use ockam_transport_udp::{UdpTransport, UDP};
+use ockam_transport_udp::{
+    UdpInletOptions, UdpOutletOptions, UdpReliabilityOptions, UdpTransport, UDP,
+};
 use std::net::SocketAddr;
 use std::time::Duration;
 use tracing::{debug, error, trace};
@@ -106,6 +108,45 @@ async fn recover_from_sender_error(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+/// A route containing a UDP address can be resolved by the transport
+/// registered with the node, without using the UDP router.
+#[ockam_macros::test]
+async fn send_with_resolved_route(ctx: &mut Context) -> Result<()> {
+    // Find an available port
+    let bind_addr = *utils::available_local_ports(1).await?.first().unwrap();
+
+    // Transport
+    let transport = UdpTransport::create(ctx).await?;
+
+    // Listener
+    ctx.start_worker("echoer", Echoer::new()).await?;
+    transport.listen(bind_addr.to_string()).await?;
+
+    // The UDP address is replaced with the address of a local worker
+    let r = ctx
+        .resolve_transport_route(route![(UDP, bind_addr.to_string()), "echoer"])
+        .await?;
+    let peer_worker = r.next()?.clone();
+    assert!(peer_worker.is_local());
+
+    // Resolving the same peer again returns the same worker
+    let r_again = ctx
+        .resolve_transport_route(route![(UDP, bind_addr.to_string()), "echoer"])
+        .await?;
+    assert_eq!(r_again.next()?, &peer_worker);
+
+    let res: Routed<String> = ctx
+        .send_and_receive_extended(
+            r,
+            String::from("Hola"),
+            MessageSendReceiveOptions::new().with_timeout(TIMEOUT),
+        )
+        .await?;
+    assert_eq!(res.into_body()?, "Hola");
+
+    Ok(())
+}
+
 /// The transport should send messages to peers, with different
 /// destination addresses, from the same UDP port.
 ///
@@ -187,6 +228,278 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+/// Messages larger than a datagram can be sent through a reliable connection
+/// and are delivered in order.
+#[ockam_macros::test]
+async fn send_receive_reliable(ctx: &mut Context) -> Result<()> {
+    // Find an available port
+    let bind_addr = utils::available_local_ports(1)
+        .await?
+        .first()
+        .unwrap()
+        .to_string();
+
+    // Transport
+    let transport = UdpTransport::create(ctx).await?;
+
+    // Listener
+    ctx.start_worker("reliable_echoer", ReliableEchoer).await?;
+    transport.listen(bind_addr.clone()).await?;
+    transport
+        .create_reliable_listener("reliable_listener", UdpReliabilityOptions::new())
+        .await?;
+
+    // Connection
+    let options = UdpReliabilityOptions::new()
+        .with_max_fragment_size(512)
+        .with_window(2, 16);
+    let connection = transport
+        .create_reliable_connection(route![(UDP, bind_addr), "reliable_listener"], options)
+        .await?;
+
+    // Sender
+    for _ in 0..3 {
+        let msg: String = rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(16 * 1024)
+            .map(char::from)
+            .collect();
+        let reply = ctx
+            .send_and_receive_extended::<String>(
+                route![connection.clone(), "reliable_echoer"],
+                msg.clone(),
+                MessageSendReceiveOptions::new().with_timeout(TIMEOUT),
+            )
+            .await?
+            .into_body()?;
+
+        assert_eq!(reply, msg, "Should receive the same message");
+    }
+    Ok(())
+}
+
+/// Messages sent through a reliable connection are delivered in order
+/// even when frames are lost in both directions.
+#[ockam_macros::test]
+async fn send_receive_reliable_over_lossy_link(ctx: &mut Context) -> Result<()> {
+    // Find an available port
+    let bind_addr = utils::available_local_ports(1)
+        .await?
+        .first()
+        .unwrap()
+        .to_string();
+
+    // Transport
+    let transport = UdpTransport::create(ctx).await?;
+
+    // Listener
+    ctx.start_worker("reliable_echoer", ReliableEchoer).await?;
+    transport.listen(bind_addr.clone()).await?;
+    transport
+        .create_reliable_listener("reliable_listener", UdpReliabilityOptions::new())
+        .await?;
+
+    // One frame out of four is lost in each direction
+    ctx.start_worker("lossy_link", LossyLink::new(4)).await?;
+
+    // Connection
+    let options = UdpReliabilityOptions::new()
+        .with_max_fragment_size(512)
+        .with_retransmission_timeout(Duration::from_millis(50))
+        .with_window(2, 16);
+    let connection = transport
+        .create_reliable_connection(
+            route!["lossy_link", (UDP, bind_addr), "reliable_listener"],
+            options,
+        )
+        .await?;
+
+    // Sender
+    for n in 0..5 {
+        let msg: String = rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(8 * 1024)
+            .map(char::from)
+            .collect();
+        let msg = format!("{n}{msg}");
+        let reply = ctx
+            .send_and_receive_extended::<String>(
+                route![connection.clone(), "reliable_echoer"],
+                msg.clone(),
+                MessageSendReceiveOptions::new().with_timeout(TIMEOUT),
+            )
+            .await?
+            .into_body()?;
+
+        assert_eq!(reply, msg, "Should receive the same message");
+    }
+    Ok(())
+}
+
+/// Datagrams sent to a UDP inlet are delivered to the outlet destination and the
+/// answers are sent back to the right client.
+#[ockam_macros::test]
+async fn udp_portal(ctx: &mut Context) -> Result<()> {
+    let transport = UdpTransport::create(ctx).await?;
+
+    // UDP echo server
+    let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let server_addr = server.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut buf = vec![0; 1024];
+        while let Ok((len, peer)) = server.recv_from(&mut buf).await {
+            let _ = server.send_to(&buf[..len], peer).await;
+        }
+    });
+
+    transport
+        .create_outlet(
+            "udp_outlet",
+            server_addr.to_string(),
+            UdpOutletOptions::new(),
+        )
+        .await?;
+    let (inlet_addr, _) = transport
+        .create_inlet("127.0.0.1:0", route!["udp_outlet"], UdpInletOptions::new())
+        .await?;
+
+    // Two clients, each with its own session
+    let client1 = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let client2 = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    for (client, msg) in [
+        (&client1, b"first".to_vec()),
+        (&client2, b"second".to_vec()),
+    ] {
+        for _ in 0..3 {
+            client.send_to(&msg, inlet_addr).await.unwrap();
+            let mut buf = vec![0; 1024];
+            let (len, from) = tokio::time::timeout(TIMEOUT, client.recv_from(&mut buf))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(from, inlet_addr);
+            assert_eq!(&buf[..len], msg.as_slice());
+        }
+    }
+    Ok(())
+}
+
+/// A UDP inlet and a UDP outlet don't open more sessions than their maximum.
+#[ockam_macros::test]
+async fn udp_portal_max_sessions(ctx: &mut Context) -> Result<()> {
+    let transport = UdpTransport::create(ctx).await?;
+
+    // UDP echo server
+    let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let server_addr = server.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut buf = vec![0; 1024];
+        while let Ok((len, peer)) = server.recv_from(&mut buf).await {
+            let _ = server.send_to(&buf[..len], peer).await;
+        }
+    });
+
+    async fn echo(client: &tokio::net::UdpSocket, inlet_addr: SocketAddr) -> bool {
+        client.send_to(b"hello", inlet_addr).await.unwrap();
+        let mut buf = vec![0; 1024];
+        tokio::time::timeout(Duration::from_secs(1), client.recv_from(&mut buf))
+            .await
+            .is_ok()
+    }
+
+    // An inlet accepting a single client
+    transport
+        .create_outlet(
+            "udp_outlet",
+            server_addr.to_string(),
+            UdpOutletOptions::new(),
+        )
+        .await?;
+    let (inlet_addr, _) = transport
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["udp_outlet"],
+            UdpInletOptions::new().with_max_sessions(1),
+        )
+        .await?;
+    let client1 = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let client2 = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    assert!(echo(&client1, inlet_addr).await);
+    assert!(!echo(&client2, inlet_addr).await);
+    assert!(echo(&client1, inlet_addr).await);
+
+    // An outlet accepting a single session, shared by two inlets
+    transport
+        .create_outlet(
+            "single_session_outlet",
+            server_addr.to_string(),
+            UdpOutletOptions::new().with_max_sessions(1),
+        )
+        .await?;
+    let (inlet_addr1, _) = transport
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["single_session_outlet"],
+            UdpInletOptions::new(),
+        )
+        .await?;
+    let (inlet_addr2, _) = transport
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["single_session_outlet"],
+            UdpInletOptions::new(),
+        )
+        .await?;
+    assert!(echo(&client1, inlet_addr1).await);
+    assert!(!echo(&client1, inlet_addr2).await);
+
+    Ok(())
+}
+
+pub struct ReliableEchoer;
+
+#[ockam_core::worker]
+impl Worker for ReliableEchoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.into_body()?).await
+    }
+}
+
+/// Forward messages and drop one message out of `period`,
+/// adding itself to the return route so that the answers are also dropped
+pub struct LossyLink {
+    period: usize,
+    count: usize,
+}
+
+impl LossyLink {
+    fn new(period: usize) -> Self {
+        Self { period, count: 0 }
+    }
+}
+
+#[ockam_core::worker]
+impl Worker for LossyLink {
+    type Message = Any;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        self.count += 1;
+        if self.count % self.period == 0 {
+            trace!("Dropping a message");
+            return Ok(());
+        }
+        let msg = msg
+            .into_local_message()
+            .pop_front_onward_route()?
+            .push_front_return_route(&ctx.address());
+        ctx.forward(msg).await
+    }
+}
+
 pub struct Echoer {
     prev_src_addr: Option<String>,
 }
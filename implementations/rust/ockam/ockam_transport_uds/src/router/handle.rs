@@ -110,6 +110,24 @@ impl UdsRouterHandle {
         }
     }
 
+    /// Return the address of the connection worker for the given UDS address.
+    /// A new connection is established if there is no connection to that peer yet
+    pub async fn resolve(&self, peer: &Address) -> Result<Address> {
+        let response = self
+            .ctx
+            .send_and_receive(
+                self.api_addr.clone(),
+                UdsRouterRequest::Resolve { peer: peer.clone() },
+            )
+            .await?;
+
+        if let UdsRouterResponse::Resolve(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType)?
+        }
+    }
+
     /// Register a new connection worker with this router
     pub async fn register(&self, pair: &WorkerPair) -> Result<()> {
         let uds_address: Address = address_from_socket_addr(pair.peer())?;
@@ -14,6 +14,8 @@ pub enum UdsRouterRequest {
     Connect { peer: String },
     /// Disconnect from a UDS Peer
     Disconnect { peer: String },
+    /// Return the connection worker for a UDS address, connecting to the peer if necessary
+    Resolve { peer: Address },
     /// Unregister (usually, after disconnection)
     Unregister {
         /// The clients own worker bus address
@@ -29,6 +31,8 @@ pub enum UdsRouterResponse {
     Connect(Result<Address>),
     /// Response containing a result when attempting to disconnect from a peer
     Disconnect(Result<()>),
+    /// Response containing the [`Address`] of the connection worker for a peer
+    Resolve(Result<Address>),
     /// Response containing a result when attempt to unregister
     Unregister(Result<()>),
 }
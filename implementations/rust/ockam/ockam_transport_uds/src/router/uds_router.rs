@@ -284,6 +284,12 @@ impl Worker for UdsRouter {
                     ctx.send(return_route, UdsRouterResponse::Disconnect(res))
                         .await?;
                 }
+                UdsRouterRequest::Resolve { peer } => {
+                    let res = self.resolve_route(&peer).await;
+
+                    ctx.send(return_route, UdsRouterResponse::Resolve(res))
+                        .await?;
+                }
                 UdsRouterRequest::Unregister { self_addr } => {
                     let res = self.handle_unregister(self_addr).await;
 
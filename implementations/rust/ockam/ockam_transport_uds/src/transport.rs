@@ -1,11 +1,15 @@
 use std::os::unix::net::SocketAddr;
 
-use ockam_core::{async_trait, Address, AsyncTryClone, Result};
+use ockam_core::compat::sync::Arc;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Address, AsyncTryClone, Error, Result, TransportType};
 use ockam_node::{Context, HasContext};
+use ockam_transport_core::Transport;
 
 use crate::{
     parse_socket_addr,
     router::{UdsRouter, UdsRouterHandle},
+    UDS,
 };
 
 /// High level management interface for UDS transports
@@ -57,9 +61,12 @@ impl UdsTransport {
     pub async fn create(ctx: &Context) -> Result<Self> {
         let router = UdsRouter::register(ctx).await?;
 
-        Ok(Self {
+        let uds = Self {
             router_handle: router,
-        })
+        };
+        // make the UDS transport available for the resolution of UDS addresses in routes
+        ctx.register_transport(Arc::new(uds.async_try_clone().await?));
+        Ok(uds)
     }
 
     /// Connects the [`UdsTransport`] to the given socket peer.
@@ -111,6 +118,30 @@ impl UdsTransport {
     }
 }
 
+#[async_trait]
+impl Transport for UdsTransport {
+    fn transport_type(&self) -> TransportType {
+        UDS
+    }
+
+    async fn resolve_address(&self, address: Address) -> Result<Address> {
+        if address.transport_type() == UDS {
+            self.router_handle.resolve(&address).await
+        } else {
+            Err(Error::new(
+                Origin::Transport,
+                Kind::NotFound,
+                format!("this address can not be resolved by a UDS transport {address}"),
+            ))
+        }
+    }
+
+    async fn disconnect(&self, address: Address) -> Result<()> {
+        self.router_handle.unregister(address.clone()).await?;
+        self.router_handle.ctx().stop_worker(address).await
+    }
+}
+
 /// This trait adds a `create_uds_transport` method to any struct returning a Context.
 /// This is the case for an ockam::Node, so you can write `node.create_uds_transport()`
 #[async_trait]
@@ -122,3 +153,31 @@ pub trait UdsTransportExtension: HasContext {
 }
 
 impl<A: HasContext> UdsTransportExtension for A {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam_core::route;
+
+    #[ockam_macros::test]
+    async fn test_resolve_address(ctx: &mut Context) -> Result<()> {
+        let path = std::env::temp_dir().join(format!("{}.sock", Address::random_local().address()));
+        let path = path.to_str().unwrap().to_string();
+
+        let uds = UdsTransport::create(ctx).await?;
+        uds.listen(&path).await?;
+
+        let resolved = ctx
+            .resolve_transport_route(route![(UDS, path.clone())])
+            .await?;
+        let worker = resolved.next()?.clone();
+        assert!(worker.is_local());
+
+        // the same connection is used when resolving the address a second time
+        let resolved_again = ctx.resolve_transport_route(route![(UDS, path)]).await?;
+        assert_eq!(resolved_again.next()?, &worker);
+
+        Transport::disconnect(&uds, worker).await?;
+        Ok(())
+    }
+}
@@ -89,12 +89,16 @@ use std::net::SocketAddr;
 
 use ockam_core::{Result, TransportType};
 use ockam_transport_core::TransportError;
+pub use options::*;
+pub use tls::*;
 pub use transport::*;
 
 use crate::router::{WebSocketRouter, WebSocketRouterHandle};
 
 mod error;
+mod options;
 mod router;
+mod tls;
 mod transport;
 mod workers;
 
@@ -0,0 +1,83 @@
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+
+use crate::error::WebSocketError;
+use crate::WebSocketClientTls;
+
+/// Options for an outgoing WebSocket connection
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketConnectOptions {
+    tls: Option<WebSocketClientTls>,
+    headers: Vec<(String, String)>,
+}
+
+impl WebSocketConnectOptions {
+    /// Plain `ws://` connection without additional headers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect with `wss://`, using the given TLS configuration
+    pub fn with_tls(mut self, tls: WebSocketClientTls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Add an HTTP header to the upgrade request, for example an `Authorization` header
+    /// required by a proxy
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// TLS configuration, if the connection uses `wss://`
+    pub(crate) fn tls(&self) -> Option<&WebSocketClientTls> {
+        self.tls.as_ref()
+    }
+
+    /// Create the upgrade request sent to `host_port`
+    pub(crate) fn client_request(&self, host_port: &str) -> Result<Request> {
+        let scheme = if self.tls.is_some() { "wss" } else { "ws" };
+        let mut request = format!("{scheme}://{host_port}")
+            .into_client_request()
+            .map_err(WebSocketError::from)?;
+
+        for (name, value) in &self.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| invalid_header(&format!("Invalid header name {name}: {e}")))?;
+            let header_value = HeaderValue::from_str(value)
+                .map_err(|e| invalid_header(&format!("Invalid value for header {name}: {e}")))?;
+            request.headers_mut().append(header_name, header_value);
+        }
+        Ok(request)
+    }
+}
+
+fn invalid_header(message: &str) -> Error {
+    Error::new(Origin::Transport, Kind::Invalid, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_request() -> Result<()> {
+        let options = WebSocketConnectOptions::new()
+            .with_tls(WebSocketClientTls::new())
+            .with_header("Authorization", "Bearer token");
+        let request = options.client_request("example.com:443")?;
+        assert_eq!(request.uri().to_string(), "wss://example.com:443/");
+        assert_eq!(request.headers()["Authorization"], "Bearer token");
+
+        let request = WebSocketConnectOptions::new().client_request("127.0.0.1:8000")?;
+        assert_eq!(request.uri().to_string(), "ws://127.0.0.1:8000/");
+
+        let options = WebSocketConnectOptions::new().with_header("Invalid Name", "value");
+        assert!(options.client_request("127.0.0.1:8000").is_err());
+        Ok(())
+    }
+}
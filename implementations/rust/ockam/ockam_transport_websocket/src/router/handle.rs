@@ -1,4 +1,3 @@
-use core::str::FromStr;
 use std::net::{SocketAddr, ToSocketAddrs};
 
 use ockam_core::{async_trait, Address, AsyncTryClone, DenyAll, Result};
@@ -7,7 +6,7 @@ use ockam_transport_core::TransportError;
 
 use crate::router::{WebSocketRouterRequest, WebSocketRouterResponse};
 use crate::workers::{WebSocketListenProcessor, WorkerPair};
-use crate::{parse_socket_addr, WebSocketAddress};
+use crate::{parse_socket_addr, WebSocketConnectOptions, WebSocketServerTls};
 
 /// A handle to connect to a WebSocketRouter.
 ///
@@ -39,14 +38,7 @@ impl WebSocketRouterHandle {
 
     /// Register a new connection worker with this router.
     pub(crate) async fn register(&self, pair: &WorkerPair) -> Result<()> {
-        let mut accepts = vec![pair.peer()];
-
-        accepts.extend(
-            pair.hostnames()
-                .iter()
-                .filter_map(|x| WebSocketAddress::from_str(x).ok())
-                .map(|addr| addr.into()),
-        );
+        let accepts = pair.accepts();
         let self_addr = pair.tx_addr();
         let response = self
             .ctx
@@ -56,15 +48,62 @@ impl WebSocketRouterHandle {
             )
             .await?;
 
-        let WebSocketRouterResponse::Register(res) = response;
+        if let WebSocketRouterResponse::Register(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType)?
+        }
+    }
+
+    /// Return the address of the connection worker for the given WebSocket address.
+    /// A new connection is established if there is no connection to that peer yet.
+    pub(crate) async fn resolve(&self, peer: &Address) -> Result<Address> {
+        let response = self
+            .ctx
+            .send_and_receive(
+                self.api_addr.clone(),
+                WebSocketRouterRequest::Resolve { peer: peer.clone() },
+            )
+            .await?;
+
+        if let WebSocketRouterResponse::Resolve(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType)?
+        }
+    }
+
+    /// Unregister a connection worker and stop it.
+    pub(crate) async fn disconnect(&self, self_addr: Address) -> Result<()> {
+        let response = self
+            .ctx
+            .send_and_receive(
+                self.api_addr.clone(),
+                WebSocketRouterRequest::Unregister {
+                    self_addr: self_addr.clone(),
+                },
+            )
+            .await?;
+
+        if let WebSocketRouterResponse::Unregister(res) = response {
+            res?
+        } else {
+            return Err(TransportError::InvalidRouterResponseType)?;
+        }
 
-        res
+        self.ctx.stop_worker(self_addr).await
     }
 
     /// Bind an incoming connection listener for this router.
-    pub(crate) async fn bind(&self, addr: impl Into<SocketAddr>) -> Result<SocketAddr> {
+    /// The connections are accepted with `wss://` if a TLS configuration is provided.
+    pub(crate) async fn bind(
+        &self,
+        addr: impl Into<SocketAddr>,
+        tls: Option<WebSocketServerTls>,
+    ) -> Result<SocketAddr> {
         let socket_addr = addr.into();
-        WebSocketListenProcessor::start(&self.ctx, self.async_try_clone().await?, socket_addr).await
+        WebSocketListenProcessor::start(&self.ctx, self.async_try_clone().await?, socket_addr, tls)
+            .await
     }
 
     /// Return the peer's `SocketAddr` and `hostnames` given a plain `String` address.
@@ -96,13 +135,17 @@ impl WebSocketRouterHandle {
     }
 
     /// Establish an outgoing WS connection on an existing transport.
-    pub(crate) async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
+    pub(crate) async fn connect<S: AsRef<str>>(
+        &self,
+        peer: S,
+        options: WebSocketConnectOptions,
+    ) -> Result<()> {
         // Get peer address and connect to it.
         let (peer_addr, hostnames) = Self::resolve_peer(peer.as_ref())?;
 
         // Create a new `WorkerPair` for the given peer, initializing a new pair
         // of sender worker and receiver processor.
-        let pair = WorkerPair::from_client(&self.ctx, peer_addr, hostnames).await?;
+        let pair = WorkerPair::from_client(&self.ctx, peer_addr, hostnames, options).await?;
 
         // Handle node's register request.
         self.register(&pair).await
@@ -1,4 +1,3 @@
-use core::str::FromStr;
 use std::collections::BTreeMap;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -12,7 +11,7 @@ use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
 
 use crate::workers::WorkerPair;
-use crate::{WebSocketAddress, WS};
+use crate::{WebSocketConnectOptions, WS};
 use serde::{Deserialize, Serialize};
 
 mod handle;
@@ -26,11 +25,21 @@ pub enum WebSocketRouterRequest {
         /// The clients own worker bus address.
         self_addr: Address,
     },
+    /// Return the connection worker for a WebSocket address,
+    /// connecting to the peer if necessary.
+    Resolve { peer: Address },
+    /// Unregister a client, usually before disconnecting it.
+    Unregister {
+        /// The clients own worker bus address.
+        self_addr: Address,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Message)]
 pub enum WebSocketRouterResponse {
     Register(Result<()>),
+    Resolve(Result<Address>),
+    Unregister(Result<()>),
 }
 
 /// A WebSocket address router and connection listener.
@@ -141,6 +150,28 @@ impl Worker for WebSocketRouter {
                     )
                     .await?;
                 }
+                WebSocketRouterRequest::Resolve { peer } => {
+                    trace!("handle_message resolve: {:?}", peer);
+                    let res = self.resolve(&peer).await;
+
+                    ctx.send_from_address(
+                        return_route,
+                        WebSocketRouterResponse::Resolve(res),
+                        self.api_addr.clone(),
+                    )
+                    .await?;
+                }
+                WebSocketRouterRequest::Unregister { self_addr } => {
+                    trace!("handle_message unregister: {:?}", self_addr);
+                    self.map.retain(|_, v| v != &self_addr);
+
+                    ctx.send_from_address(
+                        return_route,
+                        WebSocketRouterResponse::Unregister(Ok(())),
+                        self.api_addr.clone(),
+                    )
+                    .await?;
+                }
             };
         } else {
             return Err(TransportError::InvalidAddress)?;
@@ -155,36 +186,41 @@ impl WebSocketRouter {
         trace!("WS route request: {:?}", msg.onward_route_ref().next());
 
         // Get the next hop
-        let onward = msg.onward_route_ref().next()?;
+        let onward = msg.onward_route_ref().next()?.clone();
 
-        let next;
         // Look up the connection worker responsible
-        if let Some(n) = self.map.get(onward) {
-            // Connection already exists
-            next = n.clone();
-        } else {
-            // No existing connection
-            let peer_str = match String::from_utf8(onward.deref().clone()) {
-                Ok(s) => s,
-                Err(_e) => return Err(TransportError::UnknownRoute)?,
-            };
-
-            // TODO: Check if this is the hostname and we have existing/pending connection to this IP
-            if self.allow_auto_connection {
-                next = self.connect(peer_str).await?;
-            } else {
-                return Err(TransportError::UnknownRoute)?;
-            }
-        }
+        let next = self.resolve(&onward).await?;
 
         let msg = msg.replace_front_onward_route(&next)?;
 
-        // Send the transport message to the connection worker
-        ctx.send(next.clone(), msg).await?;
+        // Forward the message to the connection worker
+        ctx.forward(msg).await?;
 
         Ok(())
     }
 
+    /// Return the connection worker for a peer address, creating a new connection
+    /// if there is none yet and auto connection is allowed.
+    async fn resolve(&mut self, onward: &Address) -> Result<Address> {
+        if let Some(n) = self.map.get(onward) {
+            // Connection already exists
+            return Ok(n.clone());
+        }
+
+        // No existing connection
+        let peer_str = match String::from_utf8(onward.deref().clone()) {
+            Ok(s) => s,
+            Err(_e) => return Err(TransportError::UnknownRoute)?,
+        };
+
+        // TODO: Check if this is the hostname and we have existing/pending connection to this IP
+        if self.allow_auto_connection {
+            self.connect(peer_str).await
+        } else {
+            Err(TransportError::UnknownRoute)?
+        }
+    }
+
     async fn handle_register(&mut self, accepts: Vec<Address>, self_addr: Address) -> Result<()> {
         // The `accepts` vector should always contain at least one address.
         if let Some(f) = accepts.first().cloned() {
@@ -217,18 +253,18 @@ impl WebSocketRouter {
 
         // Create a new `WorkerPair` for the given peer, initializing a new pair
         // of sender worker and receiver processor.
-        let pair = WorkerPair::from_client(&self.ctx, peer_addr, hostnames).await?;
+        let pair = WorkerPair::from_client(
+            &self.ctx,
+            peer_addr,
+            hostnames,
+            WebSocketConnectOptions::new(),
+        )
+        .await?;
 
         // Handle node's register request.
-        let mut accepts = vec![pair.peer()];
-        accepts.extend(
-            pair.hostnames()
-                .iter()
-                .filter_map(|x| WebSocketAddress::from_str(x).ok())
-                .map(|addr| addr.into()),
-        );
         let self_addr = pair.tx_addr();
-        self.handle_register(accepts, self_addr.clone()).await?;
+        self.handle_register(pair.accepts(), self_addr.clone())
+            .await?;
 
         Ok(self_addr)
     }
@@ -0,0 +1,218 @@
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName};
+use tokio_rustls::rustls::{ClientConfig, RootCertStore, ServerConfig};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+/// TLS configuration used to connect to a `wss://` server.
+///
+/// By default the certificate of the server is verified with the certificates
+/// of the operating system and the name sent with SNI is the host name of the peer.
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketClientTls {
+    ca_certificates: Option<Vec<CertificateDer<'static>>>,
+    server_name: Option<String>,
+}
+
+impl WebSocketClientTls {
+    /// Verify the server certificate with the certificates of the operating system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify the server certificate with the PEM-encoded CA certificates
+    /// instead of the certificates of the operating system
+    pub fn with_ca_certificates_pem(mut self, pem: &[u8]) -> Result<Self> {
+        let certificates = parse_certificates(pem)?;
+        if certificates.is_empty() {
+            return Err(tls_error(Kind::Invalid, "no CA certificate was found"));
+        }
+        self.ca_certificates = Some(certificates);
+        Ok(self)
+    }
+
+    /// Verify the server certificate with the CA certificates of a PEM bundle file
+    pub fn with_ca_bundle_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        let pem = read_file(path.as_ref())?;
+        self.with_ca_certificates_pem(&pem)
+    }
+
+    /// Send this name with SNI, and expect it in the server certificate,
+    /// instead of the host name of the peer
+    pub fn with_server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.server_name = Some(server_name.into());
+        self
+    }
+
+    /// Establish a TLS session over a TCP stream connected to `host_port`
+    pub(crate) async fn connect(
+        &self,
+        host_port: &str,
+        stream: TcpStream,
+    ) -> Result<TlsStream<TcpStream>> {
+        let server_name = self
+            .server_name
+            .clone()
+            .unwrap_or_else(|| hostname(host_port).to_string());
+        let server_name = ServerName::try_from(server_name.clone()).map_err(|e| {
+            tls_error(
+                Kind::Invalid,
+                &format!("Cannot create a ServerName from {server_name}: {e:?}"),
+            )
+        })?;
+
+        TlsConnector::from(self.client_config()?)
+            .connect(server_name, stream)
+            .await
+            .map_err(|e| {
+                tls_error(
+                    Kind::Io,
+                    &format!("Cannot connect using TLS to {host_port}: {e:?}"),
+                )
+            })
+    }
+
+    fn client_config(&self) -> Result<Arc<ClientConfig>> {
+        let certificates = match &self.ca_certificates {
+            Some(certificates) => certificates,
+            None => return native_client_config(),
+        };
+
+        let mut root_cert_store = RootCertStore::empty();
+        for certificate in certificates {
+            root_cert_store
+                .add(certificate.clone())
+                .map_err(|e| tls_error(Kind::Invalid, &format!("Invalid CA certificate: {e:?}")))?;
+        }
+        let config = ClientConfig::builder()
+            .with_root_certificates(root_cert_store)
+            .with_no_client_auth();
+        Ok(Arc::new(config))
+    }
+}
+
+/// TLS configuration used to accept `wss://` connections
+#[derive(Debug, Clone)]
+pub struct WebSocketServerTls {
+    config: Arc<ServerConfig>,
+}
+
+impl WebSocketServerTls {
+    /// Use a PEM-encoded certificate chain and its PEM-encoded private key
+    pub fn from_pem(certificate_chain: &[u8], private_key: &[u8]) -> Result<Self> {
+        let certificates = parse_certificates(certificate_chain)?;
+        if certificates.is_empty() {
+            return Err(tls_error(Kind::Invalid, "no certificate was found"));
+        }
+        let private_key = rustls_pemfile::private_key(&mut BufReader::new(private_key))
+            .map_err(|e| tls_error(Kind::Invalid, &format!("Invalid private key: {e:?}")))?
+            .ok_or_else(|| tls_error(Kind::Invalid, "no private key was found"))?;
+
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certificates, private_key)
+            .map_err(|e| {
+                tls_error(
+                    Kind::Invalid,
+                    &format!("Invalid certificate or private key: {e:?}"),
+                )
+            })?;
+        Ok(Self {
+            config: Arc::new(config),
+        })
+    }
+
+    /// Use a PEM file containing a certificate chain and a PEM file containing its private key
+    pub fn from_pem_files(
+        certificate_chain_path: impl AsRef<Path>,
+        private_key_path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        Self::from_pem(
+            &read_file(certificate_chain_path.as_ref())?,
+            &read_file(private_key_path.as_ref())?,
+        )
+    }
+
+    pub(crate) fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.config.clone())
+    }
+}
+
+/// Return a client configuration verifying certificates with the certificates of the
+/// operating system. The certificates are only loaded once, by the first successful call
+fn native_client_config() -> Result<Arc<ClientConfig>> {
+    static NATIVE_CLIENT_CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    if let Some(config) = NATIVE_CLIENT_CONFIG.get() {
+        return Ok(config.clone());
+    }
+
+    let certificates = rustls_native_certs::load_native_certs().map_err(|e| {
+        tls_error(
+            Kind::Io,
+            &format!("Cannot load the native certificates: {e:?}"),
+        )
+    })?;
+    let mut root_cert_store = RootCertStore::empty();
+    root_cert_store.add_parsable_certificates(certificates);
+    let config = ClientConfig::builder()
+        .with_root_certificates(root_cert_store)
+        .with_no_client_auth();
+    Ok(NATIVE_CLIENT_CONFIG
+        .get_or_init(|| Arc::new(config))
+        .clone())
+}
+
+/// Return the host part of a `host:port` string
+fn hostname(host_port: &str) -> &str {
+    let host = match host_port.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => host_port,
+    };
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+fn parse_certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>> {
+    rustls_pemfile::certs(&mut BufReader::new(pem))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| tls_error(Kind::Invalid, &format!("Invalid certificate: {e:?}")))
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| {
+        tls_error(
+            Kind::Io,
+            &format!("Cannot read the file {}: {e:?}", path.display()),
+        )
+    })
+}
+
+fn tls_error(kind: Kind, message: &str) -> Error {
+    Error::new(Origin::Transport, kind, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hostname() {
+        assert_eq!(hostname("example.com:443"), "example.com");
+        assert_eq!(hostname("127.0.0.1:8000"), "127.0.0.1");
+        assert_eq!(hostname("[::1]:8000"), "::1");
+        assert_eq!(hostname("example.com"), "example.com");
+    }
+
+    #[test]
+    fn test_invalid_pem() {
+        assert!(WebSocketClientTls::new()
+            .with_ca_certificates_pem(b"not a certificate")
+            .is_err());
+        assert!(WebSocketServerTls::from_pem(b"", b"").is_err());
+    }
+}
@@ -1,11 +1,17 @@
 use core::fmt;
 use std::net::SocketAddr;
 use std::str::FromStr;
+use std::sync::Arc;
 
-use ockam_core::{async_trait, Address, Result};
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Address, AsyncTryClone, Error, Result, TransportType};
 use ockam_node::{Context, HasContext};
+use ockam_transport_core::Transport;
 
-use crate::{parse_socket_addr, WebSocketRouter, WebSocketRouterHandle, WS};
+use crate::{
+    parse_socket_addr, WebSocketClientTls, WebSocketConnectOptions, WebSocketRouter,
+    WebSocketRouterHandle, WebSocketServerTls, WS,
+};
 
 /// High level management interface for WebSocket transports.
 ///
@@ -45,6 +51,11 @@ use crate::{parse_socket_addr, WebSocketRouter, WebSocketRouterHandle, WS};
 /// ws.listen("127.0.0.1:9000").await?; // Listen on port 9000
 /// # Ok(()) }
 /// ```
+///
+/// The transport is also registered with the node so that routes containing
+/// `WS` addresses can be resolved with `Context::resolve_transport_route`.
+#[derive(AsyncTryClone)]
+#[async_try_clone(crate = "ockam_core")]
 pub struct WebSocketTransport {
     router_handle: WebSocketRouterHandle,
 }
@@ -62,11 +73,18 @@ impl WebSocketTransport {
     /// ```
     pub async fn create(ctx: &Context) -> Result<WebSocketTransport> {
         let router_handle = WebSocketRouter::register(ctx).await?;
-        Ok(Self { router_handle })
+        let ws = Self { router_handle };
+        // make the WebSocket transport available for the resolution of WS addresses in routes
+        ctx.register_transport(Arc::new(ws.async_try_clone().await?));
+        Ok(ws)
     }
 
     /// Establish an outgoing WebSocket connection on an existing transport.
     ///
+    /// The peer can be prefixed with `ws://` or `wss://`. With `wss://` the connection
+    /// uses TLS and the server certificate is verified with the certificates of the
+    /// operating system. The port defaults to 80 for `ws://` and 443 for `wss://`.
+    ///
     /// ```rust
     /// use ockam_transport_websocket::WebSocketTransport;
     /// # use ockam_node::Context;
@@ -75,10 +93,42 @@ impl WebSocketTransport {
     /// let ws = WebSocketTransport::create(&ctx).await?;
     /// ws.listen("127.0.0.1:8000").await?; // Listen on port 8000
     /// ws.connect("127.0.0.1:5000").await?; // and connect to port 5000
+    /// ws.connect("wss://example.com").await?; // and connect to example.com with TLS
     /// # Ok(()) }
     /// ```
     pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
-        self.router_handle.connect(peer).await
+        self.connect_with_options(peer, WebSocketConnectOptions::new())
+            .await
+    }
+
+    /// Establish an outgoing WebSocket connection with some specific options:
+    /// a TLS configuration and additional headers for the upgrade request.
+    ///
+    /// Once connected, the peer can be reached with a route starting with
+    /// `(WS, "<host>:<port>")`.
+    ///
+    /// ```rust
+    /// use ockam_transport_websocket::{WebSocketClientTls, WebSocketConnectOptions, WebSocketTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let ws = WebSocketTransport::create(&ctx).await?;
+    /// let options = WebSocketConnectOptions::new()
+    ///     .with_tls(WebSocketClientTls::new().with_ca_bundle_file("ca.pem")?)
+    ///     .with_header("Authorization", "Bearer <token>");
+    /// ws.connect_with_options("proxy.example.com:443", options).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect_with_options<S: AsRef<str>>(
+        &self,
+        peer: S,
+        mut options: WebSocketConnectOptions,
+    ) -> Result<()> {
+        let (tls, peer) = parse_peer(peer.as_ref());
+        if tls && options.tls().is_none() {
+            options = options.with_tls(WebSocketClientTls::new());
+        }
+        self.router_handle.connect(peer, options).await
     }
 
     /// Start listening to incoming connections on an existing transport.
@@ -98,7 +148,53 @@ impl WebSocketTransport {
     /// # Ok(()) }
     pub async fn listen<S: AsRef<str>>(&self, bind_addr: S) -> Result<SocketAddr> {
         let bind_addr = parse_socket_addr(bind_addr)?;
-        self.router_handle.bind(bind_addr).await
+        self.router_handle.bind(bind_addr, None).await
+    }
+
+    /// Start listening to incoming `wss://` connections on an existing transport.
+    ///
+    /// Returns the local address that this transport is bound to.
+    ///
+    /// ```rust
+    /// use ockam_transport_websocket::{WebSocketServerTls, WebSocketTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let ws = WebSocketTransport::create(&ctx).await?;
+    /// let tls = WebSocketServerTls::from_pem_files("cert.pem", "key.pem")?;
+    /// ws.listen_tls("127.0.0.1:8443", tls).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn listen_tls<S: AsRef<str>>(
+        &self,
+        bind_addr: S,
+        tls: WebSocketServerTls,
+    ) -> Result<SocketAddr> {
+        let bind_addr = parse_socket_addr(bind_addr)?;
+        self.router_handle.bind(bind_addr, Some(tls)).await
+    }
+}
+
+#[async_trait]
+impl Transport for WebSocketTransport {
+    fn transport_type(&self) -> TransportType {
+        WS
+    }
+
+    async fn resolve_address(&self, address: Address) -> Result<Address> {
+        if address.transport_type() == WS {
+            self.router_handle.resolve(&address).await
+        } else {
+            Err(Error::new(
+                Origin::Transport,
+                Kind::NotFound,
+                format!("this address can not be resolved by a WebSocket transport {address}"),
+            ))
+        }
+    }
+
+    async fn disconnect(&self, address: Address) -> Result<()> {
+        self.router_handle.disconnect(address).await
     }
 }
 
@@ -114,6 +210,35 @@ pub trait WebSocketTransportExtension: HasContext {
 
 impl<A: HasContext> WebSocketTransportExtension for A {}
 
+/// Split a peer into a flag indicating if TLS must be used, and a `host:port` string.
+/// The `ws://` or `wss://` scheme is removed and the default port of the scheme is added if
+/// the peer has no port.
+fn parse_peer(peer: &str) -> (bool, String) {
+    let (tls, host_port) = if let Some(host_port) = peer.strip_prefix("wss://") {
+        (true, host_port)
+    } else if let Some(host_port) = peer.strip_prefix("ws://") {
+        (false, host_port)
+    } else {
+        return (false, peer.to_string());
+    };
+    let host_port = host_port.trim_end_matches('/');
+
+    let has_port = match host_port.rsplit_once(':') {
+        Some((host, port)) => {
+            !port.is_empty()
+                && port.chars().all(|c| c.is_ascii_digit())
+                && (!host.starts_with('[') || host.ends_with(']'))
+        }
+        None => false,
+    };
+    if has_port {
+        (tls, host_port.to_string())
+    } else {
+        let port = if tls { 443 } else { 80 };
+        (tls, format!("{host_port}:{port}"))
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct WebSocketAddress {
     protocol: String,
@@ -148,7 +273,7 @@ impl From<&WebSocketAddress> for String {
 }
 
 impl FromStr for WebSocketAddress {
-    type Err = ockam_core::Error;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
         let socket_addr = parse_socket_addr(s)?;
@@ -161,3 +286,29 @@ impl fmt::Display for WebSocketAddress {
         write!(f, "{}://{}", &self.protocol, &self.socket_addr)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_peer() {
+        assert_eq!(
+            parse_peer("127.0.0.1:8000"),
+            (false, "127.0.0.1:8000".to_string())
+        );
+        assert_eq!(
+            parse_peer("ws://localhost:8000"),
+            (false, "localhost:8000".to_string())
+        );
+        assert_eq!(
+            parse_peer("wss://example.com"),
+            (true, "example.com:443".to_string())
+        );
+        assert_eq!(
+            parse_peer("wss://example.com:8443/"),
+            (true, "example.com:8443".to_string())
+        );
+        assert_eq!(parse_peer("ws://[::1]"), (false, "[::1]:80".to_string()));
+    }
+}
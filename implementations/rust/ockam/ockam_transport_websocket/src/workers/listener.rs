@@ -1,29 +1,45 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsAcceptor;
 
-use ockam_core::{async_trait, Address, AllowAll, Processor, Result};
+use ockam_core::{async_trait, Address, AllowAll, AsyncTryClone, Processor, Result};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 
-use crate::{error::WebSocketError, workers::WorkerPair, WebSocketRouterHandle};
+use crate::workers::TcpServerStream;
+use crate::{
+    error::WebSocketError, workers::WorkerPair, WebSocketRouterHandle, WebSocketServerTls,
+};
 
 /// A worker that runs in the background as a `Processor` waiting for incoming
 /// clients' connections.
 ///
 /// When a new connection is established, a new `WorkerPair` is spawned and
 /// registered by the router.
+///
+/// When the listener has a TLS configuration, a TLS session is established
+/// with each client before the WebSocket handshake.
+///
+/// The handshakes run in their own task, and must complete within [`HANDSHAKE_TIMEOUT`],
+/// so that a slow client does not prevent other clients from connecting.
 pub(crate) struct WebSocketListenProcessor {
     inner: TcpListener,
     router_handle: WebSocketRouterHandle,
+    tls_acceptor: Option<TlsAcceptor>,
 }
 
+/// Maximum duration of the TLS and WebSocket handshakes with a client
+pub(crate) const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 impl WebSocketListenProcessor {
     /// Create and start a new instance bound to the given `addr`.
     pub(crate) async fn start(
         ctx: &Context,
         router_handle: WebSocketRouterHandle,
         addr: SocketAddr,
+        tls: Option<WebSocketServerTls>,
     ) -> Result<SocketAddr> {
         debug!("Binding WebSocketListener to {}", addr);
         let inner = TcpListener::bind(addr)
@@ -33,6 +49,7 @@ impl WebSocketListenProcessor {
         let processor = Self {
             inner,
             router_handle,
+            tls_acceptor: tls.map(|tls| tls.acceptor()),
         };
         let waddr = Address::random_tagged("WebSocketListenProcessor");
         ctx.start_processor_with_access_control(
@@ -57,18 +74,63 @@ impl Processor for WebSocketListenProcessor {
 
         // Wait for an incoming connection
         let (tcp_stream, peer) = self.inner.accept().await.map_err(TransportError::from)?;
-        let ws_stream = tokio_tungstenite::accept_async(tcp_stream)
+        debug!("TCP connection accepted");
+
+        // Run the handshakes in the background and keep accepting other connections
+        let handshake_ctx = ctx.async_try_clone().await?;
+        let router_handle = self.router_handle.async_try_clone().await?;
+        let tls_acceptor = self.tls_acceptor.clone();
+        ctx.runtime().spawn(async move {
+            let result = tokio::time::timeout(
+                HANDSHAKE_TIMEOUT,
+                Self::accept_connection(
+                    &handshake_ctx,
+                    &router_handle,
+                    tls_acceptor,
+                    tcp_stream,
+                    peer,
+                ),
+            )
+            .await;
+            match result {
+                Ok(Ok(())) => debug!("TCP connection registered"),
+                Ok(Err(e)) => warn!("Handshake with {} failed: {:?}", peer, e),
+                Err(_) => warn!("Handshake with {} timed out", peer),
+            }
+        });
+
+        Ok(true)
+    }
+}
+
+impl WebSocketListenProcessor {
+    /// Establish the TLS session, if any, and the WebSocket connection with a client,
+    /// then start a connection worker and register it with the router
+    async fn accept_connection(
+        ctx: &Context,
+        router_handle: &WebSocketRouterHandle,
+        tls_acceptor: Option<TlsAcceptor>,
+        tcp_stream: TcpStream,
+        peer: SocketAddr,
+    ) -> Result<()> {
+        let stream = match tls_acceptor {
+            Some(tls_acceptor) => {
+                let tls_stream = tls_acceptor
+                    .accept(tcp_stream)
+                    .await
+                    .map_err(TransportError::from)?;
+                TcpServerStream::Tls(Box::new(tls_stream))
+            }
+            None => TcpServerStream::Plain(tcp_stream),
+        };
+        let ws_stream = tokio_tungstenite::accept_async(stream)
             .await
             .map_err(WebSocketError::from)?;
-        debug!("TCP connection accepted");
 
         // Spawn a connection worker for it
         let pair = WorkerPair::from_server(ctx, ws_stream, peer, vec![]).await?;
 
-        // Register the connection with the local TcpRouter
-        self.router_handle.register(&pair).await?;
-        debug!("TCP connection registered");
-
-        Ok(true)
+        // Register the connection with the local router
+        router_handle.register(&pair).await
     }
 }
@@ -4,12 +4,14 @@ use std::time::Duration;
 
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
 use tokio_tungstenite::tungstenite::protocol::Message as WebSocketMessage;
+use tokio_tungstenite::MaybeTlsStream;
 
 use crate::error::WebSocketError;
 use ockam_core::{
-    async_trait, route, Address, AllowAll, Any, Decodable, Encodable, LocalMessage, Mailbox,
-    Mailboxes, Result, Routed, TransportMessage, Worker,
+    async_trait, route, Address, AllowAll, Any, Encodable, Mailbox, Mailboxes, Result, Routed,
+    TransportMessage, Worker,
 };
 use ockam_node::{Context, DelayedEvent, WorkerBuilder};
 use ockam_transport_core::TransportError;
@@ -17,7 +19,7 @@ use ockam_transport_core::TransportError;
 use crate::workers::{
     AsyncStream, TcpClientStream, TcpServerStream, WebSocketRecvProcessor, WebSocketStream,
 };
-use crate::WebSocketAddress;
+use crate::{WebSocketAddress, WebSocketConnectOptions, WS};
 
 /// Transmit and receive peers of a WebSocket connection.
 #[derive(Debug)]
@@ -28,9 +30,6 @@ pub(crate) struct WorkerPair {
 }
 
 impl WorkerPair {
-    pub(crate) fn hostnames(&self) -> &[String] {
-        &self.hostnames
-    }
     pub(crate) fn peer(&self) -> Address {
         self.peer.clone()
     }
@@ -38,6 +37,18 @@ impl WorkerPair {
         self.tx_addr.clone()
     }
 
+    /// Addresses for which this pair must be registered by the router:
+    /// the peer socket address and its host names
+    pub(crate) fn accepts(&self) -> Vec<Address> {
+        let mut accepts = vec![self.peer()];
+        accepts.extend(
+            self.hostnames
+                .iter()
+                .map(|hostname| Address::new(WS, hostname.as_str())),
+        );
+        accepts
+    }
+
     /// Spawn instances of `WebSocketSendWorker` and `WebSocketRecvProcessor` and
     /// returns a `WorkerPair` instance that will be registered by the `WebSocketRouter`.
     ///
@@ -46,12 +57,21 @@ impl WorkerPair {
         ctx: &Context,
         peer: SocketAddr,
         hostnames: Vec<String>,
+        options: WebSocketConnectOptions,
     ) -> Result<WorkerPair> {
         trace!("Creating new WS worker pair");
 
+        // Use the host name of the peer, if there is one, for the upgrade request and TLS
+        let host_port = hostnames
+            .first()
+            .cloned()
+            .unwrap_or_else(|| peer.to_string());
+
         let internal_addr = Address::random_tagged("WebSocketSender.internal.from_client");
         let sender = WebSocketSendWorker::<TcpClientStream>::new(
             peer,
+            host_port,
+            options,
             internal_addr.clone(),
             DelayedEvent::create(ctx, internal_addr.clone(), vec![]).await?,
         );
@@ -140,6 +160,9 @@ where
     ws_stream: Option<SplitStream<WebSocketStream<S>>>,
     ws_sink: Option<SplitSink<WebSocketStream<S>, WebSocketMessage>>,
     peer: SocketAddr,
+    /// Host and port used in the upgrade request and to establish a TLS session
+    host_port: String,
+    options: WebSocketConnectOptions,
     internal_addr: Address,
     heartbeat: DelayedEvent<Vec<u8>>,
     heartbeat_interval: Option<Duration>,
@@ -205,11 +228,9 @@ where
             }
             debug!("Sent heartbeat to peer {}", self.peer);
         } else {
-            let mut msg = LocalMessage::decode(msg.payload())?;
-
             // Remove our own address from the route so the other end
             // knows what to do with the incoming message
-            msg = msg.pop_front_onward_route()?;
+            let msg = msg.into_local_message().pop_front_onward_route()?;
 
             let msg = WebSocketMessage::from(msg.into_transport_message().encode()?);
             if ws_sink.send(msg).await.is_err() {
@@ -238,6 +259,8 @@ impl WebSocketSendWorker<TcpServerStream> {
             ws_sink: Some(ws_sink),
             ws_stream: Some(ws_stream),
             peer,
+            host_port: peer.to_string(),
+            options: WebSocketConnectOptions::new(),
             internal_addr,
             heartbeat,
             heartbeat_interval: None,
@@ -246,11 +269,19 @@ impl WebSocketSendWorker<TcpServerStream> {
 }
 
 impl WebSocketSendWorker<TcpClientStream> {
-    fn new(peer: SocketAddr, internal_addr: Address, heartbeat: DelayedEvent<Vec<u8>>) -> Self {
+    fn new(
+        peer: SocketAddr,
+        host_port: String,
+        options: WebSocketConnectOptions,
+        internal_addr: Address,
+        heartbeat: DelayedEvent<Vec<u8>>,
+    ) -> Self {
         Self {
             ws_stream: None,
             ws_sink: None,
             peer,
+            host_port,
+            options,
             internal_addr,
             heartbeat,
             heartbeat_interval: None,
@@ -259,8 +290,17 @@ impl WebSocketSendWorker<TcpClientStream> {
 
     async fn initialize_stream(&mut self) -> Result<()> {
         if self.ws_stream.is_none() {
-            let peer = WebSocketAddress::from(self.peer).to_string();
-            let (stream, _) = tokio_tungstenite::connect_async(peer)
+            let request = self.options.client_request(&self.host_port)?;
+            let tcp_stream = TcpStream::connect(self.peer)
+                .await
+                .map_err(TransportError::from)?;
+            let stream = match self.options.tls() {
+                Some(tls) => {
+                    MaybeTlsStream::Rustls(tls.connect(&self.host_port, tcp_stream).await?)
+                }
+                None => MaybeTlsStream::Plain(tcp_stream),
+            };
+            let (stream, _) = tokio_tungstenite::client_async(request, stream)
                 .await
                 .map_err(WebSocketError::from)?;
             let (ws_sink, ws_stream) = stream.split();
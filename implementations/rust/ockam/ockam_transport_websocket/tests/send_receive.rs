@@ -1,7 +1,9 @@
 use ockam_core::compat::rand::{self, Rng};
 use ockam_core::{route, Result, Routed, Worker};
 use ockam_node::Context;
-use ockam_transport_websocket::{WebSocketTransport, WS};
+use ockam_transport_websocket::{
+    WebSocketClientTls, WebSocketConnectOptions, WebSocketServerTls, WebSocketTransport, WS,
+};
 
 #[ignore]
 #[ockam_macros::test]
@@ -25,6 +27,50 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[ockam_macros::test]
+async fn send_receive_with_resolved_route(ctx: &mut Context) -> Result<()> {
+    let transport = WebSocketTransport::create(ctx).await?;
+    let listener_address = transport.listen("127.0.0.1:0").await?;
+    ctx.start_worker("echoer", Echoer).await?;
+
+    // The WS address is replaced with the address of a connection worker
+    let r = ctx
+        .resolve_transport_route(route![(WS, listener_address.to_string()), "echoer"])
+        .await?;
+    assert!(r.next()?.is_local());
+
+    let reply = ctx
+        .send_and_receive::<String>(r, "Hello".to_string())
+        .await?;
+    assert_eq!(reply, "Hello");
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn send_receive_tls(ctx: &mut Context) -> Result<()> {
+    let certificate = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let certificate_pem = certificate.serialize_pem().unwrap();
+    let private_key_pem = certificate.serialize_private_key_pem();
+
+    let transport = WebSocketTransport::create(ctx).await?;
+    let tls = WebSocketServerTls::from_pem(certificate_pem.as_bytes(), private_key_pem.as_bytes())?;
+    let listener_address = transport.listen_tls("127.0.0.1:0", tls).await?;
+    ctx.start_worker("echoer", Echoer).await?;
+
+    // The self-signed certificate is trusted by the client and issued for "localhost"
+    let peer = format!("localhost:{}", listener_address.port());
+    let options = WebSocketConnectOptions::new()
+        .with_tls(WebSocketClientTls::new().with_ca_certificates_pem(certificate_pem.as_bytes())?)
+        .with_header("Authorization", "Bearer token");
+    transport.connect_with_options(&peer, options).await?;
+
+    let reply = ctx
+        .send_and_receive::<String>(route![(WS, peer), "echoer"], "Hello".to_string())
+        .await?;
+    assert_eq!(reply, "Hello");
+    Ok(())
+}
+
 pub struct Echoer;
 
 #[ockam_core::worker]
@@ -33,6 +33,12 @@ pub enum VaultError {
     InvalidSha256Len,
     /// Invalid Signature Size
     InvalidSignatureSize,
+    /// Invalid KEM ciphertext length
+    InvalidKemCiphertextLength,
+    /// ML-KEM encapsulation failed
+    MlKemEncapsulate,
+    /// ML-KEM decapsulation failed
+    MlKemDecapsulate,
 }
 
 impl ockam_core::compat::error::Error for VaultError {}
@@ -54,6 +60,9 @@ impl core::fmt::Display for VaultError {
             Self::KeyNotFound => write!(f, "key not found"),
             Self::InvalidSha256Len => write!(f, "invalid sha256 len"),
             Self::InvalidSignatureSize => write!(f, "invalid signature len"),
+            Self::InvalidKemCiphertextLength => write!(f, "invalid KEM ciphertext length"),
+            Self::MlKemEncapsulate => write!(f, "ML-KEM encapsulation failed"),
+            Self::MlKemDecapsulate => write!(f, "ML-KEM decapsulation failed"),
         }
     }
 }
@@ -1,3 +1,5 @@
+use ml_kem::kem::{Decapsulate, Encapsulate};
+use ml_kem::{Ciphertext, Encoded, EncodedSizeUser, KemCore, MlKem768};
 use sha2::{Digest, Sha256};
 use tracing::instrument;
 
@@ -15,18 +17,29 @@ use crate::storage::SecretsSqlxDatabase;
 
 use crate::{
     AeadSecret, AeadSecretKeyHandle, BufferSecret, HKDFNumberOfOutputs, HandleToSecret, HashOutput,
-    HkdfOutput, SecretBufferHandle, SoftwareVaultForVerifyingSignatures, VaultError,
-    VaultForSecureChannels, X25519PublicKey, X25519SecretKey, X25519SecretKeyHandle,
-    AEAD_SECRET_LENGTH,
+    HkdfOutput, MlKem768Ciphertext, MlKem768PublicKey, MlKem768SecretKeyHandle, SecretBufferHandle,
+    SoftwareVaultForVerifyingSignatures, VaultError, VaultForSecureChannels, X25519PublicKey,
+    X25519SecretKey, X25519SecretKeyHandle, AEAD_SECRET_LENGTH, ML_KEM_768_CIPHERTEXT_LENGTH,
+    ML_KEM_768_PUBLIC_KEY_LENGTH,
 };
 
 use super::make_aes;
 
+type MlKem768DecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+type MlKem768EncapsulationKey = <MlKem768 as KemCore>::EncapsulationKey;
+
+/// Ephemeral ML-KEM-768 key pair
+struct MlKem768KeyPair {
+    decapsulation_key: MlKem768DecapsulationKey,
+    public_key: MlKem768PublicKey,
+}
+
 /// [`SecureChannelVault`] implementation using software
 pub struct SoftwareVaultForSecureChannels {
     ephemeral_buffer_secrets: Arc<RwLock<BTreeMap<SecretBufferHandle, BufferSecret>>>,
     ephemeral_aead_secrets: Arc<RwLock<BTreeMap<AeadSecretKeyHandle, AeadSecret>>>,
     ephemeral_x25519_secrets: Arc<RwLock<BTreeMap<X25519SecretKeyHandle, X25519SecretKey>>>,
+    ephemeral_ml_kem_768_secrets: Arc<RwLock<BTreeMap<MlKem768SecretKeyHandle, MlKem768KeyPair>>>,
     static_x25519_secrets: Arc<dyn SecretsRepository>,
 }
 
@@ -37,6 +50,7 @@ impl SoftwareVaultForSecureChannels {
             ephemeral_buffer_secrets: Default::default(),
             ephemeral_aead_secrets: Default::default(),
             ephemeral_x25519_secrets: Default::default(),
+            ephemeral_ml_kem_768_secrets: Default::default(),
             static_x25519_secrets: repository,
         }
     }
@@ -102,6 +116,11 @@ impl SoftwareVaultForSecureChannels {
         self.ephemeral_x25519_secrets.read().unwrap().len()
     }
 
+    /// Return the total number of ephemeral ML-KEM-768 secrets present in the Vault
+    pub fn number_of_ephemeral_ml_kem_768_secrets(&self) -> usize {
+        self.ephemeral_ml_kem_768_secrets.read().unwrap().len()
+    }
+
     /// Return the total number of ephemeral buffer secrets present in the Vault
     pub fn number_of_ephemeral_buffer_secrets(&self) -> usize {
         self.ephemeral_buffer_secrets.read().unwrap().len()
@@ -410,4 +429,93 @@ impl VaultForSecureChannels for SoftwareVaultForSecureChannels {
             .remove(&secret_key_handle)
             .is_some())
     }
+
+    async fn generate_ephemeral_ml_kem_768_secret_key(&self) -> Result<MlKem768SecretKeyHandle> {
+        let (decapsulation_key, encapsulation_key) = MlKem768::generate(&mut thread_rng());
+        let public_key = MlKem768PublicKey(encapsulation_key.as_bytes().to_vec());
+        let handle = MlKem768SecretKeyHandle(Self::generate_random_handle());
+
+        self.ephemeral_ml_kem_768_secrets.write().unwrap().insert(
+            handle.clone(),
+            MlKem768KeyPair {
+                decapsulation_key,
+                public_key,
+            },
+        );
+
+        Ok(handle)
+    }
+
+    async fn delete_ephemeral_ml_kem_768_secret_key(
+        &self,
+        secret_key_handle: MlKem768SecretKeyHandle,
+    ) -> Result<bool> {
+        Ok(self
+            .ephemeral_ml_kem_768_secrets
+            .write()
+            .unwrap()
+            .remove(&secret_key_handle)
+            .is_some())
+    }
+
+    async fn get_ml_kem_768_public_key(
+        &self,
+        secret_key_handle: &MlKem768SecretKeyHandle,
+    ) -> Result<MlKem768PublicKey> {
+        match self
+            .ephemeral_ml_kem_768_secrets
+            .read()
+            .unwrap()
+            .get(secret_key_handle)
+        {
+            Some(key_pair) => Ok(key_pair.public_key.clone()),
+            None => Err(VaultError::KeyNotFound)?,
+        }
+    }
+
+    async fn ml_kem_768_encapsulate(
+        &self,
+        peer_public_key: &MlKem768PublicKey,
+    ) -> Result<(MlKem768Ciphertext, SecretBufferHandle)> {
+        if peer_public_key.0.len() != ML_KEM_768_PUBLIC_KEY_LENGTH {
+            return Err(VaultError::InvalidPublicLength)?;
+        }
+        let encoded = Encoded::<MlKem768EncapsulationKey>::try_from(peer_public_key.0.as_slice())
+            .map_err(|_| VaultError::InvalidPublicLength)?;
+        let encapsulation_key = MlKem768EncapsulationKey::from_bytes(&encoded);
+
+        let (ciphertext, shared_secret) = encapsulation_key
+            .encapsulate(&mut thread_rng())
+            .map_err(|_| VaultError::MlKemEncapsulate)?;
+
+        let handle = self.import_buffer_secret_impl(BufferSecret::new(shared_secret.to_vec()));
+        Ok((MlKem768Ciphertext(ciphertext.to_vec()), handle))
+    }
+
+    async fn ml_kem_768_decapsulate(
+        &self,
+        secret_key_handle: &MlKem768SecretKeyHandle,
+        ciphertext: &MlKem768Ciphertext,
+    ) -> Result<SecretBufferHandle> {
+        if ciphertext.0.len() != ML_KEM_768_CIPHERTEXT_LENGTH {
+            return Err(VaultError::InvalidKemCiphertextLength)?;
+        }
+        let ciphertext = Ciphertext::<MlKem768>::try_from(ciphertext.0.as_slice())
+            .map_err(|_| VaultError::InvalidKemCiphertextLength)?;
+
+        let shared_secret = match self
+            .ephemeral_ml_kem_768_secrets
+            .read()
+            .unwrap()
+            .get(secret_key_handle)
+        {
+            Some(key_pair) => key_pair
+                .decapsulation_key
+                .decapsulate(&ciphertext)
+                .map_err(|_| VaultError::MlKemDecapsulate)?,
+            None => return Err(VaultError::KeyNotFound)?,
+        };
+
+        Ok(self.import_buffer_secret_impl(BufferSecret::new(shared_secret.to_vec())))
+    }
 }
@@ -156,7 +156,7 @@ impl SoftwareVaultForSigning {
     fn import_p256_key(
         key: &[u8; ECDSA_SHA256_CURVEP256_SECRET_KEY_LENGTH],
     ) -> Result<p256::ecdsa::SigningKey> {
-        p256::ecdsa::SigningKey::from_bytes(key.as_ref().into()).map_err(Self::from_bytes)
+        p256::ecdsa::SigningKey::from_bytes(key.as_slice().into()).map_err(Self::from_bytes)
     }
 
     fn import_ed25519_key(
@@ -0,0 +1,237 @@
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use argon2::Argon2;
+use zeroize::Zeroizing;
+
+use ockam_core::compat::rand::{thread_rng, RngCore};
+use ockam_core::compat::string::String;
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::Result;
+
+/// Length of the nonce prepended to each encrypted secret
+const NONCE_LENGTH: usize = 12;
+
+/// Length of the salt used to derive a key from a passphrase
+const SALT_LENGTH: usize = 16;
+
+/// Additional data used when wrapping the data encryption key
+const WRAPPED_KEY_AAD: &[u8] = b"ockam-secrets-encryption-key";
+
+/// Name of the key derivation function used for passphrases
+const ARGON2ID: &str = "argon2id";
+
+/// Name used when the key encrypting the data encryption key is supplied directly
+const RAW_KEY: &str = "raw";
+
+/// Source of the key protecting the secrets of an encrypted secrets repository.
+///
+/// The secrets are encrypted with a random data encryption key which is itself
+/// encrypted (wrapped) with a key either:
+///
+///  - derived from a passphrase with Argon2id
+///  - or supplied directly, for example from an OS keyring or a key file
+///
+#[derive(Clone)]
+pub enum SecretsKeySource {
+    /// Derive the key encryption key from a passphrase
+    Passphrase(String),
+    /// Use a 256 bits key as the key encryption key
+    Key([u8; 32]),
+}
+
+impl SecretsKeySource {
+    /// Return the name of the key derivation function used with this source
+    pub(super) fn kdf(&self) -> &'static str {
+        match self {
+            SecretsKeySource::Passphrase(_) => ARGON2ID,
+            SecretsKeySource::Key(_) => RAW_KEY,
+        }
+    }
+
+    /// Return the key used to wrap the data encryption key
+    fn key_encryption_key(&self, salt: Option<&[u8]>) -> Result<Zeroizing<[u8; 32]>> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        match self {
+            SecretsKeySource::Passphrase(passphrase) => {
+                let salt = salt.ok_or_else(|| {
+                    encryption_error("a salt is required to derive a key from a passphrase")
+                })?;
+                Argon2::default()
+                    .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+                    .map_err(|e| encryption_error(&format!("cannot derive a key: {e}")))?;
+            }
+            SecretsKeySource::Key(k) => key.copy_from_slice(k),
+        };
+        Ok(key)
+    }
+}
+
+impl core::fmt::Debug for SecretsKeySource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SecretsKeySource::Passphrase(_) => f.write_str("Passphrase(<redacted>)"),
+            SecretsKeySource::Key(_) => f.write_str("Key(<redacted>)"),
+        }
+    }
+}
+
+/// Key material stored in the database to be able to unlock an encrypted secrets repository:
+/// the wrapped data encryption key and, for a passphrase, the salt used by the key derivation function
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct WrappedKey {
+    pub(super) kdf: String,
+    pub(super) salt: Option<Vec<u8>>,
+    pub(super) wrapped_key: Vec<u8>,
+}
+
+/// Data encryption key used to seal secrets with AES-256-GCM
+pub(super) struct SecretsEncryption {
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl SecretsEncryption {
+    /// Create a new random data encryption key and wrap it with a key coming from the key source
+    pub(super) fn generate(key_source: &SecretsKeySource) -> Result<(Self, WrappedKey)> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        thread_rng().fill_bytes(key.as_mut());
+
+        let salt = match key_source {
+            SecretsKeySource::Passphrase(_) => {
+                let mut salt = vec![0u8; SALT_LENGTH];
+                thread_rng().fill_bytes(&mut salt);
+                Some(salt)
+            }
+            SecretsKeySource::Key(_) => None,
+        };
+
+        let key_encryption_key = key_source.key_encryption_key(salt.as_deref())?;
+        let wrapped_key = seal(&key_encryption_key, key.as_ref(), WRAPPED_KEY_AAD)?;
+        Ok((
+            Self { key },
+            WrappedKey {
+                kdf: key_source.kdf().into(),
+                salt,
+                wrapped_key,
+            },
+        ))
+    }
+
+    /// Unwrap a data encryption key.
+    /// This fails if the key source is not the one which was used to wrap the key
+    pub(super) fn unwrap(key_source: &SecretsKeySource, wrapped_key: &WrappedKey) -> Result<Self> {
+        if key_source.kdf() != wrapped_key.kdf {
+            return Err(encryption_error(&format!(
+                "the secrets are protected with a {} key, not a {} key",
+                wrapped_key.kdf,
+                key_source.kdf()
+            )));
+        }
+        let key_encryption_key = key_source.key_encryption_key(wrapped_key.salt.as_deref())?;
+        let key = open(
+            &key_encryption_key,
+            &wrapped_key.wrapped_key,
+            WRAPPED_KEY_AAD,
+        )
+        .map_err(|_| {
+            encryption_error("cannot unlock the secrets, the passphrase or key is incorrect")
+        })?;
+        let key: [u8; 32] = key
+            .as_slice()
+            .try_into()
+            .map_err(|_| encryption_error("the data encryption key must be 32 bytes long"))?;
+        Ok(Self {
+            key: Zeroizing::new(key),
+        })
+    }
+
+    /// Encrypt a secret. The handle of the secret is authenticated as additional data
+    /// so that an encrypted secret cannot be swapped with another one
+    pub(super) fn encrypt(&self, handle: &[u8], secret: &[u8]) -> Result<Vec<u8>> {
+        seal(&self.key, secret, handle)
+    }
+
+    /// Decrypt a secret
+    pub(super) fn decrypt(&self, handle: &[u8], encrypted: &[u8]) -> Result<Vec<u8>> {
+        open(&self.key, encrypted, handle)
+    }
+}
+
+/// Encrypt some data with AES-256-GCM and return nonce || ciphertext || tag
+fn seal(key: &[u8; 32], data: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    thread_rng().fill_bytes(&mut nonce);
+
+    let ciphertext = Aes256Gcm::new(key.into())
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: data, aad })
+        .map_err(|_| encryption_error("cannot encrypt a secret"))?;
+
+    let mut result = Vec::with_capacity(NONCE_LENGTH + ciphertext.len());
+    result.extend_from_slice(&nonce);
+    result.extend_from_slice(&ciphertext);
+    Ok(result)
+}
+
+/// Decrypt some data encrypted with the `seal` function
+fn open(key: &[u8; 32], encrypted: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if encrypted.len() < NONCE_LENGTH {
+        return Err(encryption_error("an encrypted secret is too short"));
+    }
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+    Aes256Gcm::new(key.into())
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| encryption_error("cannot decrypt a secret"))
+}
+
+fn encryption_error(message: &str) -> ockam_core::Error {
+    ockam_core::Error::new(Origin::Vault, Kind::Invalid, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_unwrap_with_passphrase() -> Result<()> {
+        let key_source = SecretsKeySource::Passphrase("correct horse battery staple".into());
+        let (encryption, wrapped_key) = SecretsEncryption::generate(&key_source)?;
+        assert_eq!(wrapped_key.kdf, ARGON2ID);
+        assert_eq!(
+            wrapped_key.salt.as_ref().map(|s| s.len()),
+            Some(SALT_LENGTH)
+        );
+
+        let encrypted = encryption.encrypt(b"handle", &[1; 32])?;
+        let unwrapped = SecretsEncryption::unwrap(&key_source, &wrapped_key)?;
+        assert_eq!(unwrapped.decrypt(b"handle", &encrypted)?, vec![1; 32]);
+
+        // a wrong passphrase cannot unlock the key
+        let wrong = SecretsKeySource::Passphrase("wrong".into());
+        assert!(SecretsEncryption::unwrap(&wrong, &wrapped_key).is_err());
+
+        // a secret cannot be decrypted with another handle
+        assert!(unwrapped.decrypt(b"other handle", &encrypted).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrap_unwrap_with_key() -> Result<()> {
+        let key_source = SecretsKeySource::Key([7; 32]);
+        let (encryption, wrapped_key) = SecretsEncryption::generate(&key_source)?;
+        assert_eq!(wrapped_key.kdf, RAW_KEY);
+        assert_eq!(wrapped_key.salt, None);
+
+        let encrypted = encryption.encrypt(b"handle", &[2; 32])?;
+        let unwrapped = SecretsEncryption::unwrap(&key_source, &wrapped_key)?;
+        assert_eq!(unwrapped.decrypt(b"handle", &encrypted)?, vec![2; 32]);
+
+        assert!(SecretsEncryption::unwrap(&SecretsKeySource::Key([8; 32]), &wrapped_key).is_err());
+        Ok(())
+    }
+}
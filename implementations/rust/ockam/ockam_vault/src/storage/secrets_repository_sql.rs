@@ -2,11 +2,13 @@ use sqlx::*;
 use tracing::debug;
 
 use ockam_core::async_trait;
+use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::Result;
 use ockam_node::database::{FromSqlxError, SqlxDatabase, SqlxType, ToSqlxType, ToVoid};
 
+use crate::storage::secrets_encryption::{SecretsEncryption, SecretsKeySource, WrappedKey};
 use crate::storage::secrets_repository::SecretsRepository;
 
 use crate::{
@@ -15,22 +17,109 @@ use crate::{
 };
 
 /// Implementation of a secrets repository using a SQL database
+///
+/// Secrets are stored in clear by default. When the repository is created with a key source
+/// the secrets are encrypted at rest with AES-256-GCM, using a data encryption key
+/// which is itself stored encrypted in the database.
 #[derive(Clone)]
 pub struct SecretsSqlxDatabase {
     database: SqlxDatabase,
+    encryption: Option<Arc<SecretsEncryption>>,
 }
 
 impl SecretsSqlxDatabase {
     /// Create a new database for secrets
     pub fn new(database: SqlxDatabase) -> Self {
         debug!("create a repository for secrets");
-        Self { database }
+        Self {
+            database,
+            encryption: None,
+        }
+    }
+
+    /// Create a new database for secrets where secrets are encrypted at rest.
+    ///
+    ///  - if the secrets are already encrypted, the key source is used to unlock them
+    ///    and an error is returned if the passphrase or key is incorrect
+    ///  - otherwise a new data encryption key is created and all the existing secrets are encrypted
+    ///
+    pub async fn create_encrypted(
+        database: SqlxDatabase,
+        key_source: SecretsKeySource,
+    ) -> Result<Self> {
+        debug!("create a repository for encrypted secrets");
+        let mut transaction = database.begin().await.into_core()?;
+        let query1 = query_as("SELECT kdf, salt, wrapped_key FROM secrets_encryption");
+        let row: Option<WrappedKeyRow> =
+            query1.fetch_optional(&mut *transaction).await.into_core()?;
+
+        let encryption = match row {
+            Some(row) => SecretsEncryption::unwrap(&key_source, &row.wrapped_key())?,
+            None => {
+                let (encryption, wrapped_key) = SecretsEncryption::generate(&key_source)?;
+                let query2 = query("INSERT INTO secrets_encryption VALUES (?, ?, ?)")
+                    .bind(wrapped_key.kdf.to_sql())
+                    .bind(wrapped_key.salt.map(|s| s.to_sql()))
+                    .bind(wrapped_key.wrapped_key.to_sql());
+                query2.execute(&mut *transaction).await.void()?;
+
+                // encrypt the secrets which were stored before encryption was enabled
+                let query3 = query_as("SELECT handle, secret_type, secret FROM signing_secret");
+                let rows: Vec<SigningSecretRow> =
+                    query3.fetch_all(&mut *transaction).await.into_core()?;
+                for row in rows {
+                    let query = query("UPDATE signing_secret SET secret = ? WHERE handle = ?")
+                        .bind(encryption.encrypt(&row.handle, &row.secret)?.to_sql())
+                        .bind(row.handle.to_sql());
+                    query.execute(&mut *transaction).await.void()?;
+                }
+
+                let query4 = query_as("SELECT handle, secret FROM x25519_secret");
+                let rows: Vec<X25519SecretRow> =
+                    query4.fetch_all(&mut *transaction).await.into_core()?;
+                for row in rows {
+                    let query = query("UPDATE x25519_secret SET secret = ? WHERE handle = ?")
+                        .bind(encryption.encrypt(&row.handle, &row.secret)?.to_sql())
+                        .bind(row.handle.to_sql());
+                    query.execute(&mut *transaction).await.void()?;
+                }
+                encryption
+            }
+        };
+        transaction.commit().await.void()?;
+
+        Ok(Self {
+            database,
+            encryption: Some(Arc::new(encryption)),
+        })
     }
 
     /// Create a new in-memory database for secrets
     pub async fn create() -> Result<Self> {
         Ok(Self::new(SqlxDatabase::in_memory("secrets").await?))
     }
+
+    /// Return true if the secrets stored in this database are encrypted at rest
+    pub async fn is_encrypted(database: &SqlxDatabase) -> Result<bool> {
+        let query = query_as("SELECT kdf, salt, wrapped_key FROM secrets_encryption");
+        let row: Option<WrappedKeyRow> = query.fetch_optional(&*database.pool).await.into_core()?;
+        Ok(row.is_some())
+    }
+
+    /// Remove the encryption key of a database.
+    /// This must only be called once all the secrets have been deleted since they cannot be decrypted anymore
+    pub async fn delete_encryption_key(database: &SqlxDatabase) -> Result<()> {
+        let query = query("DELETE FROM secrets_encryption");
+        query.execute(&*database.pool).await.void()
+    }
+
+    /// Return the value to store for a secret, encrypted if encryption is enabled
+    fn secret_to_sql(&self, handle: &[u8], secret: &[u8]) -> Result<SqlxType> {
+        match &self.encryption {
+            Some(encryption) => Ok(encryption.encrypt(handle, secret)?.to_sql()),
+            None => Ok(secret.to_vec().to_sql()),
+        }
+    }
 }
 
 const ED_DSA_CURVE_25519: &str = "EdDSACurve25519";
@@ -47,11 +136,15 @@ impl SecretsRepository for SecretsSqlxDatabase {
             SigningSecretKeyHandle::EdDSACurve25519(_) => ED_DSA_CURVE_25519.into(),
             SigningSecretKeyHandle::ECDSASHA256CurveP256(_) => EC_DSA_SHA256_CURVE_P256.into(),
         };
+        let secret_key: &[u8] = match &secret {
+            SigningSecret::EdDSACurve25519(k) => k.key(),
+            SigningSecret::ECDSASHA256CurveP256(k) => k.key(),
+        };
 
         let query = query("INSERT OR REPLACE INTO signing_secret VALUES (?, ?, ?)")
             .bind(handle.to_sql())
             .bind(secret_type.to_sql())
-            .bind(secret.to_sql());
+            .bind(self.secret_to_sql(handle.handle().value(), secret_key)?);
         query.execute(&*self.database.pool).await.void()
     }
 
@@ -65,7 +158,9 @@ impl SecretsRepository for SecretsSqlxDatabase {
                 .bind(handle.to_sql());
         let row: Option<SigningSecretRow> =
             query1.fetch_optional(&mut *transaction).await.into_core()?;
-        let secret = row.map(|r| r.signing_secret()).transpose()?;
+        let secret = row
+            .map(|r| r.signing_secret(&self.encryption))
+            .transpose()?;
 
         let result = if let Some(secret) = secret {
             let query = query("DELETE FROM signing_secret WHERE handle = ?").bind(handle.to_sql());
@@ -89,7 +184,9 @@ impl SecretsRepository for SecretsSqlxDatabase {
             .fetch_optional(&*self.database.pool)
             .await
             .into_core()?;
-        Ok(row.map(|r| r.signing_secret()).transpose()?)
+        Ok(row
+            .map(|r| r.signing_secret(&self.encryption))
+            .transpose()?)
     }
 
     async fn get_signing_secret_handles(&self) -> Result<Vec<SigningSecretKeyHandle>> {
@@ -109,7 +206,7 @@ impl SecretsRepository for SecretsSqlxDatabase {
     ) -> Result<()> {
         let query = query("INSERT OR REPLACE INTO x25519_secret VALUES (?, ?)")
             .bind(handle.to_sql())
-            .bind(secret.to_sql());
+            .bind(self.secret_to_sql(handle.0.value(), secret.key())?);
         query.execute(&*self.database.pool).await.void()
     }
 
@@ -122,7 +219,7 @@ impl SecretsRepository for SecretsSqlxDatabase {
             .bind(handle.to_sql());
         let row: Option<X25519SecretRow> =
             query1.fetch_optional(&mut *transaction).await.into_core()?;
-        let secret = row.map(|r| r.x25519_secret()).transpose()?;
+        let secret = row.map(|r| r.x25519_secret(&self.encryption)).transpose()?;
 
         let result = if let Some(secret) = secret {
             let query = query("DELETE FROM x25519_secret WHERE handle = ?").bind(handle.to_sql());
@@ -145,7 +242,7 @@ impl SecretsRepository for SecretsSqlxDatabase {
             .fetch_optional(&*self.database.pool)
             .await
             .into_core()?;
-        Ok(row.map(|r| r.x25519_secret()).transpose()?)
+        Ok(row.map(|r| r.x25519_secret(&self.encryption)).transpose()?)
     }
 
     async fn get_x25519_secret_handles(&self) -> Result<Vec<X25519SecretKeyHandle>> {
@@ -168,15 +265,6 @@ impl SecretsRepository for SecretsSqlxDatabase {
     }
 }
 
-impl ToSqlxType for SigningSecret {
-    fn to_sql(&self) -> SqlxType {
-        match self {
-            SigningSecret::EdDSACurve25519(k) => k.key().to_sql(),
-            SigningSecret::ECDSASHA256CurveP256(k) => k.key().to_sql(),
-        }
-    }
-}
-
 impl ToSqlxType for SigningSecretKeyHandle {
     fn to_sql(&self) -> SqlxType {
         self.handle().to_sql()
@@ -195,12 +283,6 @@ impl ToSqlxType for HandleToSecret {
     }
 }
 
-impl ToSqlxType for X25519SecretKey {
-    fn to_sql(&self) -> SqlxType {
-        self.key().to_sql()
-    }
-}
-
 #[derive(FromRow)]
 struct SigningSecretRow {
     handle: Vec<u8>,
@@ -209,8 +291,9 @@ struct SigningSecretRow {
 }
 
 impl SigningSecretRow {
-    fn signing_secret(&self) -> Result<SigningSecret> {
-        let secret: [u8; 32] = self.secret.clone().try_into().map_err(|_| {
+    fn signing_secret(&self, encryption: &Option<Arc<SecretsEncryption>>) -> Result<SigningSecret> {
+        let secret = decrypt_secret(encryption, &self.handle, &self.secret)?;
+        let secret: [u8; 32] = secret.try_into().map_err(|_| {
             ockam_core::Error::new(
                 Origin::Api,
                 Kind::Serialization,
@@ -256,8 +339,12 @@ struct X25519SecretRow {
 }
 
 impl X25519SecretRow {
-    fn x25519_secret(&self) -> Result<X25519SecretKey> {
-        let secret: [u8; 32] = self.secret.clone().try_into().map_err(|_| {
+    fn x25519_secret(
+        &self,
+        encryption: &Option<Arc<SecretsEncryption>>,
+    ) -> Result<X25519SecretKey> {
+        let secret = decrypt_secret(encryption, &self.handle, &self.secret)?;
+        let secret: [u8; 32] = secret.try_into().map_err(|_| {
             ockam_core::Error::new(
                 Origin::Api,
                 Kind::Serialization,
@@ -274,6 +361,35 @@ impl X25519SecretRow {
     }
 }
 
+#[derive(FromRow)]
+struct WrappedKeyRow {
+    kdf: String,
+    salt: Option<Vec<u8>>,
+    wrapped_key: Vec<u8>,
+}
+
+impl WrappedKeyRow {
+    fn wrapped_key(&self) -> WrappedKey {
+        WrappedKey {
+            kdf: self.kdf.clone(),
+            salt: self.salt.clone(),
+            wrapped_key: self.wrapped_key.clone(),
+        }
+    }
+}
+
+/// Return the secret stored in a row, decrypting it if encryption is enabled
+fn decrypt_secret(
+    encryption: &Option<Arc<SecretsEncryption>>,
+    handle: &[u8],
+    secret: &[u8],
+) -> Result<Vec<u8>> {
+    match encryption {
+        Some(encryption) => encryption.decrypt(handle, secret),
+        None => Ok(secret.to_vec()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -344,6 +460,51 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_encrypted_secrets_repository() -> Result<()> {
+        let database = SqlxDatabase::in_memory("secrets").await?;
+
+        // store a secret in clear before enabling encryption
+        let handle1 = SigningSecretKeyHandle::EdDSACurve25519(HandleToSecret::new(vec![1, 2, 3]));
+        let secret1 = SigningSecret::EdDSACurve25519(EdDSACurve25519SecretKey::new([1; 32]));
+        let repository = SecretsSqlxDatabase::new(database.clone());
+        repository
+            .store_signing_secret(&handle1, secret1.clone())
+            .await?;
+        assert!(!SecretsSqlxDatabase::is_encrypted(&database).await?);
+
+        // enabling encryption encrypts the existing secrets
+        let passphrase = SecretsKeySource::Passphrase("passphrase".into());
+        let repository =
+            SecretsSqlxDatabase::create_encrypted(database.clone(), passphrase.clone()).await?;
+        assert!(SecretsSqlxDatabase::is_encrypted(&database).await?);
+        assert!(repository.get_signing_secret(&handle1).await? == Some(secret1.clone()));
+
+        let handle2 = X25519SecretKeyHandle(HandleToSecret::new(vec![4, 5, 6]));
+        let secret2 = X25519SecretKey::new([2; 32]);
+        repository
+            .store_x25519_secret(&handle2, secret2.clone())
+            .await?;
+
+        // the secrets are not readable without the passphrase
+        let clear_repository = SecretsSqlxDatabase::new(database.clone());
+        assert!(clear_repository.get_signing_secret(&handle1).await.is_err());
+        assert!(clear_repository.get_x25519_secret(&handle2).await.is_err());
+
+        // the repository cannot be unlocked with a wrong passphrase
+        let wrong_passphrase = SecretsKeySource::Passphrase("wrong".into());
+        let result =
+            SecretsSqlxDatabase::create_encrypted(database.clone(), wrong_passphrase).await;
+        assert!(result.is_err());
+
+        // the secrets can be read again after unlocking the repository
+        let repository = SecretsSqlxDatabase::create_encrypted(database, passphrase).await?;
+        assert!(repository.get_signing_secret(&handle1).await? == Some(secret1));
+        assert!(repository.get_x25519_secret(&handle2).await? == Some(secret2));
+
+        Ok(())
+    }
+
     /// HELPERS
     async fn create_repository() -> Result<Arc<dyn SecretsRepository>> {
         Ok(Arc::new(SecretsSqlxDatabase::create().await?))
@@ -1,6 +1,6 @@
 use crate::{
-    AeadSecretKeyHandle, HashOutput, HkdfOutput, SecretBufferHandle, X25519PublicKey,
-    X25519SecretKeyHandle,
+    AeadSecretKeyHandle, HashOutput, HkdfOutput, MlKem768Ciphertext, MlKem768PublicKey,
+    MlKem768SecretKeyHandle, SecretBufferHandle, X25519PublicKey, X25519SecretKeyHandle,
 };
 
 use ockam_core::compat::vec::Vec;
@@ -103,4 +103,34 @@ pub trait VaultForSecureChannels: Send + Sync + 'static {
 
     /// Delete AEAD Key.
     async fn delete_aead_secret_key(&self, secret_key_handle: AeadSecretKeyHandle) -> Result<bool>;
+
+    /// Generate a fresh ephemeral (not persisted) ML-KEM-768 Key.
+    /// [1]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.203.pdf
+    async fn generate_ephemeral_ml_kem_768_secret_key(&self) -> Result<MlKem768SecretKeyHandle>;
+
+    /// Delete ephemeral ML-KEM-768 Key.
+    async fn delete_ephemeral_ml_kem_768_secret_key(
+        &self,
+        secret_key_handle: MlKem768SecretKeyHandle,
+    ) -> Result<bool>;
+
+    /// Get [`MlKem768PublicKey`] of the corresponding ML-KEM-768 Secret Key given its Handle.
+    async fn get_ml_kem_768_public_key(
+        &self,
+        secret_key_handle: &MlKem768SecretKeyHandle,
+    ) -> Result<MlKem768PublicKey>;
+
+    /// Encapsulate a fresh shared secret for the other party public key.
+    /// Return the ciphertext to send to the other party and a handle to the shared secret.
+    async fn ml_kem_768_encapsulate(
+        &self,
+        peer_public_key: &MlKem768PublicKey,
+    ) -> Result<(MlKem768Ciphertext, SecretBufferHandle)>;
+
+    /// Decapsulate the shared secret contained in a ciphertext sent by the other party.
+    async fn ml_kem_768_decapsulate(
+        &self,
+        secret_key_handle: &MlKem768SecretKeyHandle,
+        ciphertext: &MlKem768Ciphertext,
+    ) -> Result<SecretBufferHandle>;
 }
@@ -0,0 +1,45 @@
+use ockam_core::errcode::{Kind, Origin};
+use thiserror::Error;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("the PKCS#11 module {path} cannot be loaded: {error}")]
+    Module { path: String, error: String },
+    #[error("no PKCS#11 token with label {label} was found")]
+    TokenNotFound { label: String },
+    #[error("PKCS#11 error opening a session: {0}")]
+    Session(String),
+    #[error("PKCS#11 error creating new key: {0}")]
+    Create(String),
+    #[error("PKCS#11 error signing message with key {keyid}: {error}")]
+    Sign { keyid: String, error: String },
+    #[error("PKCS#11 error exporting public key {keyid}: {error}")]
+    Export { keyid: String, error: String },
+    #[error("PKCS#11 error deleting key {keyid}: {error}")]
+    Delete { keyid: String, error: String },
+    #[error("PKCS#11 error listing keys: {0}")]
+    FindKeys(String),
+    #[error("the environment variable {0} is not set")]
+    MissingEnvironmentVariable(String),
+    #[error("key type is not supported")]
+    UnsupportedKeyType,
+    #[error("public key point is incorrect")]
+    InvalidPublicKeyPoint,
+    #[error("signature is incorrect")]
+    InvalidSignature,
+    #[error("key was not found")]
+    KeyNotFound,
+}
+
+impl From<Error> for ockam_core::Error {
+    #[track_caller]
+    fn from(e: Error) -> Self {
+        let kind = match e {
+            Error::TokenNotFound { .. } | Error::KeyNotFound => Kind::NotFound,
+            Error::MissingEnvironmentVariable(_) => Kind::Invalid,
+            _ => Kind::Io,
+        };
+        ockam_core::Error::new(Origin::Vault, kind, e)
+    }
+}
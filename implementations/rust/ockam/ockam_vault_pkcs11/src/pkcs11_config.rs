@@ -0,0 +1,88 @@
+use crate::error::Error;
+use ockam_core::Result;
+use std::path::{Path, PathBuf};
+
+/// Environment variable containing the path of the PKCS#11 module
+pub const OCKAM_PKCS11_MODULE: &str = "OCKAM_PKCS11_MODULE";
+
+/// Environment variable containing the label of the PKCS#11 token
+pub const OCKAM_PKCS11_TOKEN_LABEL: &str = "OCKAM_PKCS11_TOKEN_LABEL";
+
+/// Environment variable containing the user PIN of the PKCS#11 token
+pub const OCKAM_PKCS11_PIN: &str = "OCKAM_PKCS11_PIN";
+
+/// PKCS#11 configuration: which module to load, which token to use and how to log in.
+///
+/// The PIN is never persisted by Ockam, it is either given explicitly or read
+/// from the `OCKAM_PKCS11_PIN` environment variable.
+#[derive(Clone)]
+pub struct Pkcs11Config {
+    module_path: PathBuf,
+    token_label: String,
+    pin: Option<String>,
+}
+
+impl Pkcs11Config {
+    /// Create a new configuration for a token accessed with the given PKCS#11 module
+    pub fn new(module_path: impl Into<PathBuf>, token_label: impl Into<String>) -> Self {
+        Self {
+            module_path: module_path.into(),
+            token_label: token_label.into(),
+            pin: None,
+        }
+    }
+
+    /// Create a configuration from the `OCKAM_PKCS11_MODULE`, `OCKAM_PKCS11_TOKEN_LABEL`
+    /// and `OCKAM_PKCS11_PIN` environment variables
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(
+            get_env(OCKAM_PKCS11_MODULE)?,
+            get_env(OCKAM_PKCS11_TOKEN_LABEL)?,
+        )
+        .with_pin_from_env())
+    }
+
+    /// Log in the token with the given user PIN
+    pub fn with_pin(self, pin: impl Into<String>) -> Self {
+        Self {
+            pin: Some(pin.into()),
+            ..self
+        }
+    }
+
+    /// Log in the token with the PIN set in the `OCKAM_PKCS11_PIN` environment variable, if any
+    pub fn with_pin_from_env(self) -> Self {
+        Self {
+            pin: get_env(OCKAM_PKCS11_PIN).ok(),
+            ..self
+        }
+    }
+
+    /// Path of the PKCS#11 module
+    pub fn module_path(&self) -> &Path {
+        self.module_path.as_path()
+    }
+
+    /// Label of the PKCS#11 token
+    pub fn token_label(&self) -> &str {
+        self.token_label.as_str()
+    }
+
+    pub(crate) fn pin(&self) -> Option<&str> {
+        self.pin.as_deref()
+    }
+}
+
+impl core::fmt::Debug for Pkcs11Config {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Pkcs11Config")
+            .field("module_path", &self.module_path)
+            .field("token_label", &self.token_label)
+            .field("pin", &self.pin.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+fn get_env(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| Error::MissingEnvironmentVariable(name.to_string()).into())
+}
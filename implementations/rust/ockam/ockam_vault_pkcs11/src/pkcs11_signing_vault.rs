@@ -0,0 +1,404 @@
+use crate::error::Error;
+use crate::pkcs11_config::Pkcs11Config;
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::error::{Error as CryptokiError, RvError};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use ockam_core::compat::rand::{thread_rng, RngCore};
+use ockam_core::{async_trait, Result};
+use ockam_vault::{
+    ECDSASHA256CurveP256PublicKey, ECDSASHA256CurveP256Signature, EdDSACurve25519PublicKey,
+    EdDSACurve25519Signature, HandleToSecret, Signature, SigningKeyType, SigningSecretKeyHandle,
+    VaultForSigning, VerifyingPublicKey,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tracing as log;
+
+/// DER encoding of the OID of the NIST P-256 curve (1.2.840.10045.3.1.7)
+const P256_EC_PARAMS: &[u8] = &[0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+/// DER encoding of the OID of the Ed25519 curve (1.3.101.112)
+const ED25519_EC_PARAMS: &[u8] = &[0x06, 0x03, 0x2b, 0x65, 0x70];
+
+/// Label set on the keys generated by this vault
+const KEY_LABEL: &[u8] = b"ockam";
+
+/// PKCS#11 modules loaded by this process, by module path.
+/// A module can only be initialized once per process, so it is shared by all the vaults using it
+static MODULES: OnceLock<Mutex<HashMap<PathBuf, Pkcs11>>> = OnceLock::new();
+
+/// Security module implementation using a PKCS#11 token.
+///
+/// Private keys are generated on the token, are marked as sensitive and non-extractable,
+/// and are referenced by their `CKA_ID` attribute which is used as the key handle.
+pub struct Pkcs11SigningVault {
+    // The PKCS#11 functions are blocking and a session must not be used concurrently
+    session: Mutex<Session>,
+}
+
+impl Pkcs11SigningVault {
+    /// Create a PKCS#11 security module configured with environment variables
+    pub async fn create() -> Result<Self> {
+        Self::create_with_config(Pkcs11Config::from_env()?).await
+    }
+
+    /// Load the PKCS#11 module, open a session on the configured token and log in
+    pub async fn create_with_config(config: Pkcs11Config) -> Result<Self> {
+        let pkcs11 = Self::load_module(config.module_path())?;
+
+        let slot = pkcs11
+            .get_slots_with_token()
+            .map_err(|e| Error::Session(e.to_string()))?
+            .into_iter()
+            .find(|slot| {
+                pkcs11
+                    .get_token_info(*slot)
+                    .map(|info| info.label().trim_end() == config.token_label())
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| Error::TokenNotFound {
+                label: config.token_label().to_string(),
+            })?;
+
+        let session = pkcs11
+            .open_rw_session(slot)
+            .map_err(|e| Error::Session(e.to_string()))?;
+        if let Some(pin) = config.pin() {
+            match session.login(UserType::User, Some(&AuthPin::new(pin.to_string()))) {
+                // the login state is shared by all the sessions of the process
+                Ok(()) | Err(CryptokiError::Pkcs11(RvError::UserAlreadyLoggedIn)) => (),
+                Err(e) => return Err(Error::Session(e.to_string()))?,
+            }
+        }
+        log::debug!(token = %config.token_label(), "opened a PKCS#11 session");
+
+        Ok(Self {
+            session: Mutex::new(session),
+        })
+    }
+
+    /// Return the list of all the signing keys available on the token
+    pub fn keys(&self) -> Result<Vec<SigningSecretKeyHandle>> {
+        let session = self.session.lock().unwrap();
+        let objects = session
+            .find_objects(&[Attribute::Class(ObjectClass::PRIVATE_KEY)])
+            .map_err(|e| Error::FindKeys(e.to_string()))?;
+
+        let mut keys = vec![];
+        for object in objects {
+            match Self::key_handle(&session, object) {
+                Ok(key) => keys.push(key),
+                // The token can contain keys which were not created by Ockam,
+                // with a key type that we don't support. They are skipped
+                Err(err) => log::debug!("skipping a PKCS#11 key: {err}"),
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Return number of keys
+    pub async fn number_of_keys(&self) -> Result<usize> {
+        Ok(self.keys()?.len())
+    }
+}
+
+/// Private functions
+impl Pkcs11SigningVault {
+    /// Return the PKCS#11 module at the given path, loading and initializing it
+    /// if it is not already loaded
+    fn load_module(module_path: &Path) -> Result<Pkcs11> {
+        let mut modules = MODULES.get_or_init(Default::default).lock().unwrap();
+        if let Some(pkcs11) = modules.get(module_path) {
+            return Ok(pkcs11.clone());
+        }
+
+        let pkcs11 = Pkcs11::new(module_path).map_err(|e| Error::Module {
+            path: module_path.display().to_string(),
+            error: e.to_string(),
+        })?;
+        match pkcs11.initialize(CInitializeArgs::OsThreads) {
+            Ok(()) => (),
+            // the module can also be initialized by another library of this process
+            Err(CryptokiError::Pkcs11(RvError::CryptokiAlreadyInitialized)) => {
+                log::debug!(path = %module_path.display(), "the PKCS#11 module is already initialized")
+            }
+            Err(e) => {
+                return Err(Error::Module {
+                    path: module_path.display().to_string(),
+                    error: e.to_string(),
+                })?
+            }
+        }
+        modules.insert(module_path.to_path_buf(), pkcs11.clone());
+        Ok(pkcs11)
+    }
+
+    /// Return the key handle of a private or public key object
+    fn key_handle(session: &Session, object: ObjectHandle) -> Result<SigningSecretKeyHandle> {
+        let attributes = session
+            .get_attributes(object, &[AttributeType::KeyType, AttributeType::Id])
+            .map_err(|e| Error::FindKeys(e.to_string()))?;
+
+        let mut key_type = None;
+        let mut id = None;
+        for attribute in attributes {
+            match attribute {
+                Attribute::KeyType(t) => key_type = Some(t),
+                Attribute::Id(i) => id = Some(i),
+                _ => (),
+            }
+        }
+        let id = HandleToSecret::new(id.ok_or(Error::KeyNotFound)?);
+        match key_type {
+            Some(KeyType::EC) => Ok(SigningSecretKeyHandle::ECDSASHA256CurveP256(id)),
+            Some(KeyType::EC_EDWARDS) => Ok(SigningSecretKeyHandle::EdDSACurve25519(id)),
+            _ => Err(Error::UnsupportedKeyType)?,
+        }
+    }
+
+    /// Find the object of the given class having the id of the key handle
+    fn find_object(
+        session: &Session,
+        class: ObjectClass,
+        handle: &SigningSecretKeyHandle,
+    ) -> Result<Option<ObjectHandle>> {
+        let objects = session
+            .find_objects(&[
+                Attribute::Class(class),
+                Attribute::Id(handle.handle().value().clone()),
+            ])
+            .map_err(|e| Error::FindKeys(e.to_string()))?;
+        Ok(objects.into_iter().next())
+    }
+
+    /// Read the public key of a key pair.
+    /// The CKA_EC_POINT attribute is a DER OCTET STRING, but some modules return the raw point
+    fn public_key(
+        session: &Session,
+        handle: &SigningSecretKeyHandle,
+    ) -> Result<VerifyingPublicKey> {
+        let keyid = key_id(handle);
+        let object = Self::find_object(session, ObjectClass::PUBLIC_KEY, handle)?
+            .ok_or(Error::KeyNotFound)?;
+        let point = session
+            .get_attributes(object, &[AttributeType::EcPoint])
+            .map_err(|e| Error::Export {
+                keyid: keyid.clone(),
+                error: e.to_string(),
+            })?
+            .into_iter()
+            .find_map(|a| match a {
+                Attribute::EcPoint(point) => Some(point),
+                _ => None,
+            })
+            .ok_or(Error::InvalidPublicKeyPoint)?;
+
+        match handle {
+            SigningSecretKeyHandle::EdDSACurve25519(_) => Ok(VerifyingPublicKey::EdDSACurve25519(
+                EdDSACurve25519PublicKey(unwrap_octet_string(&point)?),
+            )),
+            SigningSecretKeyHandle::ECDSASHA256CurveP256(_) => {
+                Ok(VerifyingPublicKey::ECDSASHA256CurveP256(
+                    ECDSASHA256CurveP256PublicKey(unwrap_octet_string(&point)?),
+                ))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl VaultForSigning for Pkcs11SigningVault {
+    async fn sign(
+        &self,
+        signing_secret_key_handle: &SigningSecretKeyHandle,
+        data: &[u8],
+    ) -> Result<Signature> {
+        let keyid = key_id(signing_secret_key_handle);
+        log::trace!(%keyid, "sign message");
+        let session = self.session.lock().unwrap();
+        let key = Self::find_object(
+            &session,
+            ObjectClass::PRIVATE_KEY,
+            signing_secret_key_handle,
+        )?
+        .ok_or(Error::KeyNotFound)?;
+
+        let sign = |mechanism: &Mechanism, data: &[u8]| {
+            session.sign(mechanism, key, data).map_err(|e| Error::Sign {
+                keyid: keyid.clone(),
+                error: e.to_string(),
+            })
+        };
+
+        // Both mechanisms return the raw signature: R || S
+        let signature = match signing_secret_key_handle {
+            SigningSecretKeyHandle::EdDSACurve25519(_) => {
+                let signature = sign(&Mechanism::Eddsa, data)?;
+                Signature::EdDSACurve25519(EdDSACurve25519Signature(
+                    signature.try_into().map_err(|_| Error::InvalidSignature)?,
+                ))
+            }
+            SigningSecretKeyHandle::ECDSASHA256CurveP256(_) => {
+                let signature = sign(&Mechanism::Ecdsa, Sha256::digest(data).as_slice())?;
+                Signature::ECDSASHA256CurveP256(ECDSASHA256CurveP256Signature(
+                    signature.try_into().map_err(|_| Error::InvalidSignature)?,
+                ))
+            }
+        };
+        log::debug!(%keyid, "signed message");
+        Ok(signature)
+    }
+
+    async fn generate_signing_secret_key(
+        &self,
+        signing_key_type: SigningKeyType,
+    ) -> Result<SigningSecretKeyHandle> {
+        let mut id = vec![0u8; 16];
+        thread_rng().fill_bytes(&mut id);
+
+        let (mechanism, ec_params, handle) = match signing_key_type {
+            SigningKeyType::EdDSACurve25519 => (
+                Mechanism::EccEdwardsKeyPairGen,
+                ED25519_EC_PARAMS,
+                SigningSecretKeyHandle::EdDSACurve25519(HandleToSecret::new(id.clone())),
+            ),
+            SigningKeyType::ECDSASHA256CurveP256 => (
+                Mechanism::EccKeyPairGen,
+                P256_EC_PARAMS,
+                SigningSecretKeyHandle::ECDSASHA256CurveP256(HandleToSecret::new(id.clone())),
+            ),
+        };
+
+        let public_key_template = [
+            Attribute::Token(true),
+            Attribute::Verify(true),
+            Attribute::EcParams(ec_params.to_vec()),
+            Attribute::Id(id.clone()),
+            Attribute::Label(KEY_LABEL.to_vec()),
+        ];
+        let private_key_template = [
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+            Attribute::Id(id),
+            Attribute::Label(KEY_LABEL.to_vec()),
+        ];
+
+        self.session
+            .lock()
+            .unwrap()
+            .generate_key_pair(&mechanism, &public_key_template, &private_key_template)
+            .map_err(|e| Error::Create(e.to_string()))?;
+        log::debug!(keyid = %key_id(&handle), "created new key");
+
+        Ok(handle)
+    }
+
+    async fn get_verifying_public_key(
+        &self,
+        signing_secret_key_handle: &SigningSecretKeyHandle,
+    ) -> Result<VerifyingPublicKey> {
+        let session = self.session.lock().unwrap();
+        Self::public_key(&session, signing_secret_key_handle)
+    }
+
+    async fn get_secret_key_handle(
+        &self,
+        verifying_public_key: &VerifyingPublicKey,
+    ) -> Result<SigningSecretKeyHandle> {
+        for key in self.keys()? {
+            let session = self.session.lock().unwrap();
+            if Self::public_key(&session, &key).ok().as_ref() == Some(verifying_public_key) {
+                return Ok(key);
+            }
+        }
+        Err(Error::KeyNotFound)?
+    }
+
+    async fn delete_signing_secret_key(
+        &self,
+        signing_secret_key_handle: SigningSecretKeyHandle,
+    ) -> Result<bool> {
+        let keyid = key_id(&signing_secret_key_handle);
+        let session = self.session.lock().unwrap();
+        let Some(private_key) = Self::find_object(
+            &session,
+            ObjectClass::PRIVATE_KEY,
+            &signing_secret_key_handle,
+        )?
+        else {
+            log::debug!(%keyid, "key does not exist");
+            return Ok(false);
+        };
+        let public_key = Self::find_object(
+            &session,
+            ObjectClass::PUBLIC_KEY,
+            &signing_secret_key_handle,
+        )?;
+
+        for object in [Some(private_key), public_key].into_iter().flatten() {
+            session.destroy_object(object).map_err(|e| Error::Delete {
+                keyid: keyid.clone(),
+                error: e.to_string(),
+            })?;
+        }
+        log::debug!(%keyid, "deleted key");
+        Ok(true)
+    }
+}
+
+/// Return a printable version of a key handle
+fn key_id(handle: &SigningSecretKeyHandle) -> String {
+    handle
+        .handle()
+        .value()
+        .iter()
+        .fold(String::new(), |mut id, b| {
+            let _ = write!(id, "{b:02x}");
+            id
+        })
+}
+
+/// Return the content of a DER OCTET STRING of the expected length,
+/// or the value itself if it is not wrapped
+fn unwrap_octet_string<const N: usize>(value: &[u8]) -> Result<[u8; N]> {
+    let content = match value {
+        [0x04, length, content @ ..] if *length as usize == N && content.len() == N => content,
+        _ => value,
+    };
+    Ok(content
+        .try_into()
+        .map_err(|_| Error::InvalidPublicKeyPoint)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwrap_octet_string() {
+        let point = [1u8; 32];
+
+        let mut wrapped = vec![0x04, 32];
+        wrapped.extend_from_slice(&point);
+        assert_eq!(unwrap_octet_string::<32>(&wrapped).unwrap(), point);
+
+        // some modules return the raw point
+        assert_eq!(unwrap_octet_string::<32>(&point).unwrap(), point);
+
+        // an uncompressed P-256 point starts with 0x04 but is not wrapped
+        let mut p256_point = [65u8; 65];
+        p256_point[0] = 0x04;
+        assert_eq!(unwrap_octet_string::<65>(&p256_point).unwrap(), p256_point);
+
+        assert!(unwrap_octet_string::<32>(&[0u8; 31]).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use ockam_core::Result;
+use ockam_vault::{
+    SigningKeyType, SoftwareVaultForVerifyingSignatures, VaultForSigning,
+    VaultForVerifyingSignatures,
+};
+use ockam_vault_pkcs11::{Pkcs11Config, Pkcs11SigningVault};
+
+/// These tests need to be executed with a PKCS#11 token, for example with SoftHSM:
+///
+/// softhsm2-util --init-token --free --label ockam --pin 1234 --so-pin 1234
+///
+/// and the following environment variables
+/// OCKAM_PKCS11_MODULE (for example /usr/lib/softhsm/libsofthsm2.so)
+/// OCKAM_PKCS11_TOKEN_LABEL
+/// OCKAM_PKCS11_PIN
+
+/// This test is skipped when no PKCS#11 token is configured
+#[tokio::test]
+async fn test_open_vault_twice() -> Result<()> {
+    let config = match Pkcs11Config::from_env() {
+        Ok(config) if config.module_path().exists() => config,
+        _ => return Ok(()),
+    };
+
+    // the module is only initialized once, and the second session reuses the login
+    let signing_vault1 = Pkcs11SigningVault::create_with_config(config.clone()).await?;
+    let signing_vault2 = Pkcs11SigningVault::create_with_config(config).await?;
+    assert_eq!(
+        signing_vault1.number_of_keys().await?,
+        signing_vault2.number_of_keys().await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_open_vault_with_missing_module() {
+    let config = Pkcs11Config::new("/does/not/exist/libpkcs11.so", "ockam");
+
+    // a module which can't be loaded is not cached
+    for _ in 0..2 {
+        assert!(Pkcs11SigningVault::create_with_config(config.clone())
+            .await
+            .is_err());
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_sign_verify() -> Result<()> {
+    let signing_vault = Pkcs11SigningVault::create().await?;
+    let verifier = SoftwareVaultForVerifyingSignatures::new();
+
+    for key_type in [
+        SigningKeyType::EdDSACurve25519,
+        SigningKeyType::ECDSASHA256CurveP256,
+    ] {
+        let handle = signing_vault.generate_signing_secret_key(key_type).await?;
+        let message = b"hello world";
+        let signature = signing_vault.sign(&handle, message.as_slice()).await?;
+        let public_key = signing_vault.get_verifying_public_key(&handle).await?;
+
+        assert!(
+            verifier
+                .verify_signature(&public_key, message, &signature)
+                .await?
+        );
+
+        signing_vault.delete_signing_secret_key(handle).await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_keys_management() -> Result<()> {
+    let signing_vault = Pkcs11SigningVault::create().await?;
+
+    let number_of_keys1 = signing_vault.number_of_keys().await?;
+
+    let handle = signing_vault
+        .generate_signing_secret_key(SigningKeyType::ECDSASHA256CurveP256)
+        .await?;
+
+    let number_of_keys2 = signing_vault.number_of_keys().await?;
+    assert_eq!(number_of_keys1 + 1, number_of_keys2);
+
+    let public_key = signing_vault.get_verifying_public_key(&handle).await?;
+
+    let handle2 = signing_vault.get_secret_key_handle(&public_key).await?;
+    assert_eq!(handle, handle2);
+
+    assert!(
+        signing_vault
+            .delete_signing_secret_key(handle.clone())
+            .await?
+    );
+    let number_of_keys3 = signing_vault.number_of_keys().await?;
+    assert_eq!(number_of_keys2, number_of_keys3 + 1);
+
+    // a key cannot be deleted twice
+    assert!(!signing_vault.delete_signing_secret_key(handle).await?);
+
+    Ok(())
+}